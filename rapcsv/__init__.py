@@ -6,11 +6,12 @@ operations execute **outside the Python GIL**, ensuring event loops never stall
 
 Features
 --------
-- True async CSV reading and writing
+- True async CSV reading and writing, under asyncio or trio
 - Streaming support for large files (incremental reading, no full file load)
 - Context manager support (``async with``)
 - aiocsv compatibility (AsyncReader/AsyncWriter aliases)
-- CSV-specific exception types (CSVError, CSVFieldCountError)
+- An exception hierarchy under RapcsvError separating parse failures (CsvParseError)
+  from file failures (CsvIoError), with the row, column and byte offset of the error
 - RFC 4180 compliant CSV parsing and writing
 
 Example
@@ -34,6 +35,7 @@ Example
 For more information, see: https://github.com/eddiethedean/rapcsv
 """
 
+from abc import ABC, abstractmethod
 from typing import Any, Dict, List, Optional, Protocol, runtime_checkable
 
 
@@ -100,6 +102,78 @@ class WithAsyncWrite(Protocol):
         ...
 
 
+class SourceAdapter(ABC):
+    """Base class for byte sources that Reader can read from.
+
+    Subclass it to plug a backend rapcsv doesn't support natively, such as SFTP or a
+    custom object store, into ``Reader``. ``read()`` returns raw bytes, which are
+    decoded as UTF-8 (and gunzipped if they start with a gzip header) like any byte
+    stream. The reader calls ``close()`` once when its ``async with`` block exits.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import Reader, SourceAdapter
+
+        class SftpSource(SourceAdapter):
+            def __init__(self, remote_file):
+                self.remote_file = remote_file
+
+            async def read(self, n: int) -> bytes:
+                return await self.remote_file.read(n)
+
+            async def close(self) -> None:
+                await self.remote_file.close()
+
+        async with Reader(SftpSource(remote_file)) as reader:
+            rows = await reader.read_all()
+    """
+
+    @abstractmethod
+    async def read(self, n: int) -> bytes:
+        """Read up to ``n`` bytes, returning ``b""`` at the end of the data."""
+
+    async def close(self) -> None:  # noqa: B027
+        """Release the source. Does nothing unless overridden."""
+
+
+class SinkAdapter(ABC):
+    """Base class for byte sinks that Writer can write to.
+
+    Subclass it to plug a backend rapcsv doesn't support natively into ``Writer``.
+    ``write()`` receives the encoded CSV as UTF-8 bytes, in the writer's buffered
+    chunks, and ``close()`` is called once when the writer is closed, after the last
+    chunk was written.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import SinkAdapter, Writer
+
+        class ObjectStoreSink(SinkAdapter):
+            def __init__(self, upload):
+                self.upload = upload
+
+            async def write(self, data: bytes) -> None:
+                await self.upload.send_part(data)
+
+            async def close(self) -> None:
+                await self.upload.complete()
+
+        async with Writer(ObjectStoreSink(upload)) as writer:
+            await writer.write_row(["id", "name"])
+    """
+
+    @abstractmethod
+    async def write(self, data: bytes) -> None:
+        """Write ``data`` in full."""
+
+    async def close(self) -> None:  # noqa: B027
+        """Finish the output. Does nothing unless overridden."""
+
+
 # Internal helper function to call async file methods from any thread
 # This schedules the call on the event loop using run_coroutine_threadsafe
 def _call_file_method_threadsafe(file_handle, method_name, event_loop, *args):
@@ -139,6 +213,52 @@ def _call_file_method_threadsafe(file_handle, method_name, event_loop, *args):
     return future.result()
 
 
+def _next_batch_threadsafe(iterator, event_loop, size):
+    """Pull up to ``size`` items from an async iterator on the event loop.
+
+    Used by ``Writer.write_from()`` from a worker thread, so each batch costs one trip
+    to the event loop rather than one per row.
+
+    Returns:
+        ``(items, exhausted)``, where ``exhausted`` is True once the iterator stopped.
+
+    Note:
+        This is an internal helper function and should not be called directly
+        by user code.
+    """
+    import asyncio
+
+    async def _pull():
+        items = []
+        while len(items) < size:
+            try:
+                items.append(await iterator.__anext__())
+            except StopAsyncIteration:
+                return items, True
+        return items, False
+
+    return asyncio.run_coroutine_threadsafe(_pull(), event_loop).result()
+
+
+def _put_all_threadsafe(queue, event_loop, rows, batched):
+    """Put rows into an asyncio queue on the event loop, waiting while it is full.
+
+    Used by ``Reader.into_queue()`` from a worker thread. With ``batched`` the list of
+    rows is put as a single item.
+
+    Note:
+        This is an internal helper function and should not be called directly
+        by user code.
+    """
+    import asyncio
+
+    async def _put():
+        for item in [rows] if batched else rows:
+            await queue.put(item)
+
+    asyncio.run_coroutine_threadsafe(_put(), event_loop).result()
+
+
 # Internal helper function to wrap Futures into coroutines for run_coroutine_threadsafe
 # This is used by the Rust extension to convert rapfiles Futures to coroutines
 def _await_wrapper(fut):
@@ -188,8 +308,49 @@ try:
         AsyncDictWriter,
         CSVError,
         CSVFieldCountError,
+        ChecksumError,
+        CsvIoError,
+        CsvParseError,
+        Dialect,
+        EncodingError,
+        FileChangedError,
+        FixedWidthReader,
+        JsonlReader,
+        JsonlWriter,
+        QuotaExceeded,
+        RapcsvError,
         Reader,
+        RotatingWriter,
+        Row,
+        SchemaError,
         Writer,
+        clear_index_cache,
+        concat,
+        convert,
+        dedupe,
+        diff,
+        get_allowed_root,
+        index_cache_info,
+        io_backend,
+        join,
+        lint,
+        list_archive,
+        merge_sorted,
+        partition,
+        profile,
+        query,
+        query_batches,
+        repair,
+        restructure,
+        row_hash,
+        set_allowed_root,
+        set_log_level,
+        sort,
+        split,
+        to_sqlite,
+        transform,
+        transpose,
+        validate,
     )  # type: ignore[import-not-found]
 except ImportError:
     try:
@@ -198,8 +359,49 @@ except ImportError:
             AsyncDictWriter,
             CSVError,
             CSVFieldCountError,
+            ChecksumError,
+            CsvIoError,
+            CsvParseError,
+            Dialect,
+            EncodingError,
+            FileChangedError,
+            FixedWidthReader,
+            JsonlReader,
+            JsonlWriter,
+            QuotaExceeded,
+            RapcsvError,
             Reader,
+            RotatingWriter,
+            Row,
+            SchemaError,
             Writer,
+            clear_index_cache,
+            concat,
+            convert,
+            dedupe,
+            diff,
+            get_allowed_root,
+            index_cache_info,
+            io_backend,
+            join,
+            lint,
+            list_archive,
+            merge_sorted,
+            partition,
+            profile,
+            query,
+            query_batches,
+            repair,
+            restructure,
+            row_hash,
+            set_allowed_root,
+            set_log_level,
+            sort,
+            split,
+            to_sqlite,
+            transform,
+            transpose,
+            validate,
         )
     except ImportError as err:
         raise ImportError(
@@ -232,6 +434,56 @@ Example:
         writer = AsyncWriter("output.csv")
 """
 
+class TsvReader(Reader):
+    """:class:`Reader` for tab-separated files.
+
+    Fields are split on tabs and quote characters are read as ordinary
+    characters, as TSV files from bioinformatics and ML dataset tools expect.
+    Any other option, including ``delimiter`` or ``quoting``, can still be
+    passed; with ``dialect`` only its own options apply.
+
+    Example:
+        .. code-block:: python
+
+            from rapcsv import TsvReader
+
+            async with TsvReader("genes.tsv", row_type="row") as reader:
+                async for row in reader:
+                    print(row["gene_id"])
+    """
+
+    def __new__(cls, *args: Any, **kwargs: Any) -> "TsvReader":
+        if "dialect" not in kwargs:
+            kwargs.setdefault("delimiter", "\t")
+            kwargs.setdefault("quoting", 0)
+        return super().__new__(cls, *args, **kwargs)
+
+
+class TsvWriter(Writer):
+    """:class:`Writer` for tab-separated files.
+
+    Rows are written with tabs between fields, ``\\n`` line endings and no
+    quoting, so quote characters are written as they are. A field holding a
+    tab or line break raises ``ValueError`` unless ``escapechar`` is set.
+    Other options can still be passed, as for :class:`TsvReader`.
+
+    Example:
+        .. code-block:: python
+
+            from rapcsv import TsvWriter
+
+            async with TsvWriter("out.tsv", headers=["id", "label"]) as writer:
+                await writer.write_row([1, 'say "hi"'])
+    """
+
+    def __new__(cls, *args: Any, **kwargs: Any) -> "TsvWriter":
+        if "dialect" not in kwargs:
+            kwargs.setdefault("delimiter", "\t")
+            kwargs.setdefault("quoting", 0)
+            kwargs.setdefault("lineterminator", "\n")
+        return super().__new__(cls, *args, **kwargs)
+
+
 __version__: str = "0.2.0"
 
 # Dialect presets for common CSV formats
@@ -405,19 +657,69 @@ def _auto_convert(value: str) -> Any:
     return value
 
 
+# Blocking API (rapcsv.sync.Reader/Writer); imported last since it wraps the classes above
+from rapcsv import sync  # noqa: E402
+from rapcsv._benchmark import benchmark  # noqa: E402
+
 __all__: List[str] = [
     "Reader",
     "Writer",
+    "TsvReader",  # Reader for tab-separated files
+    "TsvWriter",  # Writer for tab-separated files
     "AsyncDictReader",
     "AsyncDictWriter",
+    "JsonlReader",
+    "JsonlWriter",
+    "RotatingWriter",  # Writer that rolls over to new files at size, row or time limits
+    "FixedWidthReader",
+    "Row",  # Rows from Reader(row_type="row")
+    "Dialect",  # Reusable formatting options and presets
     "AsyncReader",  # aiocsv compatibility
     "AsyncWriter",  # aiocsv compatibility
+    "RapcsvError",  # Base class of rapcsv errors
+    "CsvParseError",  # Input is not valid CSV (CSVError is the same class)
+    "CsvIoError",  # Reading or writing a file failed (also an OSError)
+    "SchemaError",  # A field cannot be converted to its column's type
+    "EncodingError",  # Input is not valid in its encoding
+    "QuotaExceeded",  # Raised by Writer(max_rows=..., max_bytes=...)
     "CSVError",
     "CSVFieldCountError",
+    "FileChangedError",
+    "ChecksumError",  # Raised by Reader(verify_checksum=...)
     "WithAsyncRead",  # Protocol for type checking
     "WithAsyncWrite",  # Protocol for type checking
+    "SourceAdapter",  # Base class for Python byte sources
+    "SinkAdapter",  # Base class for Python byte sinks
     "EXCEL_DIALECT",  # Dialect preset
     "UNIX_DIALECT",  # Dialect preset
     "RFC4180_DIALECT",  # Dialect preset
     "convert_types",  # Type conversion utility
+    "to_sqlite",  # CSV to SQLite loader
+    "query",  # SQL queries over CSV files
+    "query_batches",  # SQL query results in batches as they are produced
+    "concat",  # Concatenate CSV files
+    "split",  # Split a CSV file into parts
+    "restructure",  # Rename, drop and reorder columns
+    "partition",  # Hive-style partition directories by column value
+    "convert",  # Change dialect and encoding in one pass
+    "sort",  # External-memory sort
+    "merge_sorted",  # k-way merge of already-sorted files
+    "dedupe",  # Streaming row deduplication
+    "join",  # Hash join on key columns
+    "diff",  # Key-based file comparison
+    "profile",  # Column statistics
+    "validate",  # Schema validation
+    "lint",  # Structural checks with row/line locations
+    "repair",  # Best-effort fixes for broken files
+    "transform",  # Per-row Python callback pipeline
+    "transpose",  # Swap rows and columns of small files
+    "io_backend",  # File IO backend in use (io_uring or tokio)
+    "list_archive",  # Member names of ZIP and tar archives
+    "index_cache_info",  # Statistics of the record index shared by Reader.seek()
+    "clear_index_cache",  # Drop the shared record indexes
+    "row_hash",  # Stable hash of a row, as Reader(row_hash=True) appends
+    "benchmark",  # Read/write throughput for a configuration
+    "set_log_level",  # Level of core spans forwarded to the "rapcsv" logger
+    "set_allowed_root",  # Confine every path to a directory
+    "get_allowed_root",  # Directory set by set_allowed_root()
 ]
@@ -0,0 +1,125 @@
+"""Throughput measurement on the caller's own data (``rapcsv.benchmark()``).
+
+Runs a read or a write with the given options through the public ``Reader`` and
+``Writer`` classes, so the figures include everything an application pays for: I/O,
+parsing or encoding, and building Python rows. Comparing runs with different engines,
+buffer sizes and batch sizes on a representative file shows which configuration suits
+it best.
+
+This is an internal module; use :func:`rapcsv.benchmark`.
+"""
+
+import os
+import tempfile
+import time
+from typing import Any, Dict, List, Optional
+
+from rapcsv import Reader, Writer
+
+_MODES = ("read", "write")
+
+
+async def _time_read(path: str, batch_size: Optional[int], options: Dict[str, Any]):
+    """Read the whole file, returning (rows, bytes, seconds)."""
+    start = time.perf_counter()
+    async with Reader(path, **options) as reader:
+        if batch_size is None:
+            rows = len(await reader.read_all())
+        else:
+            rows = 0
+            while batch := await reader.read_rows(batch_size):
+                rows += len(batch)
+        seconds = time.perf_counter() - start
+        return rows, reader.stats()["bytes"], seconds
+
+
+async def _time_write(rows: List[Any], batch_size: Optional[int], options: Dict[str, Any]):
+    """Write ``rows`` to a temporary file, returning (rows, bytes, seconds)."""
+    fd, target = tempfile.mkstemp(suffix=".csv", prefix="rapcsv-benchmark-")
+    os.close(fd)
+    try:
+        start = time.perf_counter()
+        async with Writer(target, **options) as writer:
+            if batch_size is None:
+                await writer.writerows(rows)
+            else:
+                for i in range(0, len(rows), batch_size):
+                    await writer.writerows(rows[i : i + batch_size])
+        seconds = time.perf_counter() - start
+        return len(rows), writer.stats()["bytes"], seconds
+    finally:
+        os.unlink(target)
+
+
+async def benchmark(
+    path: str,
+    mode: str = "read",
+    *,
+    batch_size: Optional[int] = None,
+    repeat: int = 1,
+    **options: Any,
+) -> Dict[str, Any]:
+    """Measure read or write throughput on a CSV file with the given options.
+
+    In ``"read"`` mode the file is read to the end with ``Reader(path, **options)``.
+    In ``"write"`` mode its rows are first read with default options (not timed) and
+    then written to a temporary file with ``Writer(tmp, **options)``, which is deleted
+    afterwards. The figures are taken from the fastest of ``repeat`` runs.
+
+    Args:
+        path: CSV file to benchmark with.
+        mode: ``"read"`` or ``"write"`` (default: ``"read"``).
+        batch_size: Rows per ``read_rows()`` or ``writerows()`` call (default: None,
+            a single ``read_all()`` or ``writerows()`` call).
+        repeat: Number of runs (default: 1).
+        **options: ``Reader`` or ``Writer`` constructor options, such as ``engine``,
+            ``read_size`` or ``write_size``.
+
+    Returns:
+        Dict with ``mode``, ``rows``, ``bytes``, ``seconds`` (fastest run),
+        ``rows_per_sec``, ``mb_per_sec`` (10**6 bytes), ``runs`` (seconds of every
+        run) and ``options``.
+
+    Raises:
+        ValueError: If ``mode``, ``batch_size`` or ``repeat`` is invalid, or
+            ``engine`` is combined with ``batch_size`` in ``"read"`` mode.
+
+    Example:
+        .. code-block:: python
+
+            for engine in ("default", "fast"):
+                result = await rapcsv.benchmark("data.csv", engine=engine, repeat=3)
+                print(engine, round(result["mb_per_sec"]), "MB/s")
+    """
+    if mode not in _MODES:
+        raise ValueError(f"mode must be 'read' or 'write', got {mode!r}")
+    if batch_size is not None and batch_size < 1:
+        raise ValueError("batch_size must be at least 1")
+    if repeat < 1:
+        raise ValueError("repeat must be at least 1")
+    if mode == "read" and batch_size is not None and options.get("engine", "default") != "default":
+        raise ValueError("engine only applies to read_all(), so it needs batch_size=None")
+
+    if mode == "write":
+        async with Reader(path) as reader:
+            data = await reader.read_all()
+
+    runs = []
+    for _ in range(repeat):
+        if mode == "read":
+            rows, size, seconds = await _time_read(path, batch_size, options)
+        else:
+            rows, size, seconds = await _time_write(data, batch_size, options)
+        runs.append(seconds)
+
+    best = min(runs)
+    return {
+        "mode": mode,
+        "rows": rows,
+        "bytes": size,
+        "seconds": best,
+        "rows_per_sec": rows / best if best > 0 else float("inf"),
+        "mb_per_sec": size / best / 1e6 if best > 0 else float("inf"),
+        "runs": runs,
+        "options": dict(options, batch_size=batch_size),
+    }
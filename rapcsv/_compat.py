@@ -0,0 +1,64 @@
+"""Awaitables for async libraries other than asyncio.
+
+The Rust extension returns asyncio futures when an asyncio loop is running. Otherwise
+it calls :func:`foreign_awaitable` with a ``start`` function: ``start(done)`` spawns the
+operation and returns a ``cancel`` function, and ``done(value, error)`` is called from
+a Rust worker thread when the operation finishes.
+
+This is an internal module and should not be used directly.
+"""
+
+import sys
+from typing import Any, Callable, List, Optional, Tuple
+
+Start = Callable[[Callable[[Any, Optional[BaseException]], None]], Callable[[], None]]
+
+
+def _running_trio_token() -> Any:
+    """Return the running trio run's token, or None outside trio."""
+    trio = sys.modules.get("trio")
+    if trio is None:
+        return None
+    try:
+        return trio.lowlevel.current_trio_token()
+    except RuntimeError:
+        return None
+
+
+async def _await_trio(start: Start, token: Any) -> Any:
+    import trio
+
+    event = trio.Event()
+    outcome: List[Tuple[Any, Optional[BaseException]]] = []
+
+    def done(value: Any, error: Optional[BaseException]) -> None:
+        outcome.append((value, error))
+        try:
+            token.run_sync_soon(event.set)
+        except trio.RunFinishedError:
+            pass  # Nobody is waiting any more
+
+    cancel = start(done)
+    try:
+        await event.wait()
+    except BaseException:
+        cancel()
+        raise
+    value, error = outcome[0]
+    if error is not None:
+        raise error
+    return value
+
+
+def foreign_awaitable(start: Start) -> Any:
+    """Wrap an operation in an awaitable for the running async library.
+
+    Raises:
+        RuntimeError: If neither asyncio nor trio is running.
+    """
+    token = _running_trio_token()
+    if token is not None:
+        return _await_trio(start, token)
+    raise RuntimeError(
+        "rapcsv needs a running asyncio or trio event loop; use rapcsv.sync for blocking calls"
+    )
@@ -8,7 +8,27 @@ Note:
     The actual implementation is in the compiled Rust extension module.
 """
 
-from typing import Any, Coroutine, Dict, List, Optional
+import csv
+import os
+from typing import (
+    Any,
+    AsyncIterable,
+    Callable,
+    Coroutine,
+    Dict,
+    Iterator,
+    List,
+    Optional,
+    Sequence,
+    Tuple,
+    Union,
+)
+
+# File path arguments: str, bytes or os.PathLike (e.g. pathlib.Path)
+StrPath = Union[str, bytes, "os.PathLike[str]", "os.PathLike[bytes]"]
+
+# dialect= arguments: a Dialect, a preset name, or a csv.Dialect class or instance
+DialectLike = Union["Dialect", str, csv.Dialect, "type[csv.Dialect]"]
 
 class Reader:
     """Async CSV reader for streaming CSV files.
@@ -17,18 +37,215 @@ class Reader:
     Files are streamed incrementally without loading the entire file into memory.
 
     Args:
-        path: Path to CSV file or async file-like object (WithAsyncRead).
+        path: Path to CSV file (``str``, ``bytes`` or ``os.PathLike`` such as
+            ``pathlib.Path``), async file-like object (WithAsyncRead) or
+            :class:`SourceAdapter`, or a list of paths or glob pattern (e.g. ``"data/part-*.csv"``) read
+            one after another as a single stream. A file-like object may return
+            ``str`` or UTF-8 ``bytes``; bytes starting with a gzip header, such
+            as the body of a ``.csv.gz`` download, are decompressed as they are
+            read. An ``sftp://user@host[:port]/path`` URL is downloaded as it
+            is read, in builds with the ``sftp`` feature.
         delimiter: Field delimiter character (default: ',').
         quotechar: Quote character (default: '"').
         escapechar: Escape character (default: None).
         quoting: Quoting style: 0=QUOTE_NONE, 1=QUOTE_MINIMAL, 2=QUOTE_ALL,
             3=QUOTE_NONNUMERIC, 4=QUOTE_NOTNULL, 6=QUOTE_STRINGS (default: 1).
+            With QUOTE_NONE, quote characters are read as ordinary characters.
         lineterminator: Line terminator string (default: '\\r\\n').
         skipinitialspace: Skip whitespace after delimiter (default: False).
         strict: Strict mode for field count validation (default: False).
         double_quote: Handle doubled quotes (default: True).
         read_size: Buffer size for reading chunks in bytes (default: 8192).
         field_size_limit: Maximum field size in bytes (default: None).
+        follow: At EOF, wait for data appended by another process instead
+            of ending the stream, like ``tail -f`` (default: False). Rows are
+            only returned once their line terminator has been written. Stop
+            following by cancelling the awaiting task.
+        poll_interval: Seconds between checks for new data in follow mode
+            (default: 0.5).
+        has_header: Files start with a header row. When reading several
+            files, only the first file's header is returned (default: True).
+        include_filename: Append the path of the originating file to every
+            row; the header row gets ``"_file"`` (default: False).
+        concurrency: Number of files of a multi-file stream parsed at once
+            on worker threads. Rows are still returned in file order; each
+            file in flight is held in memory (default: 1).
+        engine: Parser used by ``read_all()``: ``"default"`` or ``"fast"``,
+            a SIMD-accelerated parser for well-formed files. Input the fast
+            parser rejects is re-parsed with the default engine, so results
+            are identical. ``"fast"`` raises ValueError for readers that
+            ``read_all()`` can't load whole (file handles, several files,
+            archive members, ``row_type="row"`` and the like); other reads,
+            and a ``read_all()`` after rows were read, use the default
+            engine (default: ``"default"``).
+        mmap: Parse the file in ``read_all()`` directly over a memory map
+            instead of reading a copy into memory. Only valid for file
+            paths, and not with ``follow``. The file must not change while
+            it is being read: a file that grows or is rewritten raises
+            ``IOError`` instead of returning rows, but one truncated while
+            mapped makes the OS kill the process with ``SIGBUS``
+            (default: False).
+        buffer_size: Size in bytes of the read buffer for file paths, i.e.
+            how much is requested from the OS at a time. Raise it (e.g. to
+            several MiB) on high-latency network filesystems; lower it in
+            memory-constrained environments (default: 65536).
+        raw: Return fields as ``bytes`` instead of ``str``, for consumers
+            that parse field values themselves (default: False).
+        lock: ``"shared"`` to hold a shared advisory lock (flock/LockFileEx)
+            on each file from its first read until its end or ``close()``,
+            waiting while a ``Writer(lock=True)`` holds the file. Only for
+            file paths; not with ``follow`` or ``concurrency`` (default: None).
+        progress: Called as ``progress(bytes_read, bytes_total, rows_emitted)``
+            every ``progress_interval`` rows and once at EOF, e.g. to drive a
+            tqdm bar. ``bytes_total`` is None for file handles. ``read_all()``
+            on an unread file reports only after parsing (default: None).
+        progress_interval: Rows between progress calls (default: 10000).
+        timeout: Seconds each read may take before raising
+            ``asyncio.TimeoutError``; the reader stays usable afterwards
+            (default: None, no limit).
+        retries: Times to retry opening or reading after a transient IO
+            error (EAGAIN, timeouts, dropped connections, busy files, stale
+            NFS handles, or the matching exceptions from a file handle)
+            (default: 0).
+        retry_backoff: Seconds before the first retry, doubling after each
+            one (default: 0.1).
+        on_retry: Called as ``on_retry(attempt, error, delay)`` before each
+            retry (default: None).
+        eof: What ``read_row()`` returns at EOF: ``"empty"`` for ``[]``,
+            ``"none"`` for ``None`` or ``"raise"`` to raise ``EOFError``
+            (default: ``"empty"``). ``async for`` always stops cleanly at EOF.
+        row_type: ``"list"``, ``"tuple"`` for immutable, hashable rows, or
+            ``"row"`` for :class:`Row` objects with access by column name. With
+            ``"row"`` the header is consumed rather than returned (default:
+            ``"list"``).
+        checkpoint_path: File the position is saved to as the reader
+            progresses, and resumed from on construction if it exists, so
+            rerunning a job reads only rows appended since the last run. Only for
+            file paths read one at a time (default: None).
+        checkpoint_interval: Rows returned between checkpoint saves
+            (default: 10000).
+        schema: Map of column name or 0-based position to ``"str"``,
+            ``"int"``, ``"float"``, ``"bool"``, ``"date"`` or ``"datetime"``.
+            Fields are converted in Rust and empty fields become None; a field
+            that doesn't convert raises CSVError. Naming a column consumes the
+            header row (default: None).
+        date_formats: chrono formats tried in order for ``"date"`` columns
+            (default: ``["%Y-%m-%d"]``).
+        datetime_formats: chrono formats tried in order for ``"datetime"``
+            columns. A format with an offset (``%z``, ``%:z``) gives an aware
+            datetime with that fixed offset (default: ISO 8601 / RFC 3339).
+        converters: Map of column name or 0-based position to a callable
+            applied to each of the column's values, after any ``schema``
+            conversion. Called while a batch is converted, so ``read_rows()``
+            and ``read_all()`` take the GIL once per batch (default: None).
+        utf8: ``"strict"`` raises IOError on invalid UTF-8 in a file;
+            ``"lossy"`` replaces each invalid sequence with U+FFFD and counts
+            it in ``utf8_replacements`` (default: ``"strict"``).
+        normalize_newlines: Replace ``\\r\\n`` and lone ``\\r`` line breaks inside
+            fields with ``\\n`` (default: False).
+        dialect: A :class:`Dialect`, a preset name (``"excel"``,
+            ``"excel-tab"``, ``"unix"``) or a ``csv.Dialect``; formatting
+            options passed alongside it override its values (default: None).
+        delim_whitespace: Separate fields by runs of spaces and tabs instead
+            of ``delimiter``, ignoring leading and trailing whitespace and
+            blank lines (default: False).
+        zip_member: Member of the ZIP, tar, ``.tar.gz`` or ``.tgz`` archive at
+            ``path`` to read, streamed out without extracting it;
+            ``"bundle.zip::member.csv"`` does the same. A ``.zip`` path alone
+            reads the archive's only file; tar members must be named
+            (default: None).
+        verify_checksum: Check each file against its expected digest before
+            parsing it and raise :class:`ChecksumError` on a mismatch. True
+            reads the digest from ``<path>.sha256`` or ``<path>.sha512``, as
+            written by ``Writer(checksum=...)`` or ``sha256sum``; a SHA-256 or
+            SHA-512 hex string is the digest of a single file (default: None).
+        row_hash: Append a stable hash of each row's fields, 16 hex digits
+            as computed by :func:`row_hash`, after any ``include_filename``
+            field; the header row gets ``"_hash"`` (default: False).
+        encryption_key: 32-byte AES-256 key of a file written by
+            ``Writer(encryption_key=...)``. The file is decrypted in memory as
+            it is read, and a wrong key or damaged file raises ``IOError``;
+            only for a single file path (default: None).
+        max_memory: Bytes of rows ``read_all()`` may hold. Beyond that it
+            raises ``MemoryError`` and the rows read so far are returned by
+            the next read. Also bounds the record index built by ``seek()``
+            (default: None, no limit).
+        prefetch: Rows to keep parsed ahead on a background task while the
+            current ones are processed. ``line_num`` and ``byte_offset`` then
+            count the rows parsed ahead, and ``state()`` is unavailable; not
+            combined with ``follow`` or ``checkpoint_path`` (default: None).
+        columns: Header names or 0-based positions of the columns to return,
+            in the order given. The other fields are skipped by the parser
+            without being decoded. Names require a header row; ``schema``,
+            ``converters`` and positional access see the selected columns
+            (default: None, every column).
+        intern_columns: Header names or positions of columns whose repeated
+            values share one Python string object, cutting the memory of
+            categorical columns such as country codes or statuses. True
+            interns every column, giving up on a column once it shows more
+            than 1024 distinct values. Not combined with ``raw``
+            (default: None).
+        skip_footer: Records to leave out at the end of every file, such as
+            the summary or totals lines of bank and ERP exports. Rows are
+            returned once that many more rows of the same file have been
+            parsed; not combined with ``follow`` or ``checkpoint_path``, and
+            ``state()`` is unavailable (default: 0).
+        limit: Stop after this many records, the header included, without
+            reading the rest of the file, e.g. to preview the top of a huge
+            file (default: None, no limit).
+        allowed_root: Directory the file, or each of several, must resolve
+            inside once ``..`` and symlinks are followed; checked as well as
+            ``set_allowed_root()``. A path outside raises ``PermissionError``
+            (default: None).
+        duplicate_headers: What to do with a name the header row holds more
+            than once: ``"keep_first"`` leaves the names and a name refers to
+            its first column, ``"suffix"`` renames the repeats ``id_1``,
+            ``id_2``, ... and ``"error"`` raises ``CSVError``. Applies to the
+            header row returned and to names in ``columns``, ``schema`` and
+            ``Row`` objects (default: ``"keep_first"``).
+        normalize_headers: True to trim, lowercase and snake_case the names of
+            the header row (``"Order ID"`` and ``"orderId"`` become
+            ``order_id``), or a callable given each name and returning the one
+            to use. Applied before ``duplicate_headers`` (default: None).
+        true_values: Strings read as True in ``"bool"`` columns of
+            ``schema``: a list for every such column, or a dict of column name
+            or position to a list (default: None, ``true``, ``1`` and ``yes``).
+        false_values: Strings read as False, given like ``true_values``
+            (default: None, ``false``, ``0`` and ``no``).
+        bool_case_sensitive: Match ``true_values`` and ``false_values``
+            exactly rather than in any case (default: False).
+        thousands: Character separating digit groups in ``"int"`` and
+            ``"float"`` columns of ``schema``, removed before parsing
+            (default: None).
+        decimal: Decimal point of ``"float"`` columns; with
+            ``thousands="."`` and ``decimal=","``, ``1.234,56`` reads as
+            ``1234.56`` (default: ``"."``).
+        number_symbols: Symbols such as ``"$"``, ``"€"`` or ``"%"`` removed
+            from before and after the numbers of ``"int"`` and ``"float"``
+            columns (default: None).
+        percent_scale: Read ``"float"`` fields ending in ``%`` as fractions,
+            ``12.5%`` as ``0.125`` (default: False).
+        strict_rfc4180: Raise CSVError at the first deviation from RFC 4180:
+            a line ending other than CRLF, a quote in an unquoted field, text
+            after a closing quote, a blank line, an unclosed quote or a record
+            with another field count than the first. Requires the RFC's
+            dialect (default: False).
+        empty_as_none: Read empty fields as None and keep ``""`` for quoted
+            empty strings, so ``a,,""`` is ``["a", None, ""]`` (default: False).
+        field_spans: Return each row as a ``(row, spans)`` tuple, ``spans``
+            holding the ``(start, end)`` byte offsets of every field in the
+            file, quotes included (default: False).
+        check_headers: Raise :class:`SchemaError` when the header of a file
+            differs from the first file's (``True``) or from a list of column
+            names, naming the file and the missing, unexpected or reordered
+            columns (default: None, no check).
+        binary: What to do with NUL bytes and other control characters
+            (besides tab and line breaks) in fields: ``"error"`` raises
+            CSVError naming the row and column, ``"strip"`` removes them and
+            ``"replace"`` replaces them with U+FFFD (default: None, kept).
+        ssh_key: Private key file to log in to the server of an ``sftp://``
+            URL with (default: None, the SSH agent's keys and then
+            ``~/.ssh/id_*``). The host must be in ``~/.ssh/known_hosts``.
 
     Examples
     --------
@@ -49,7 +266,7 @@ class Reader:
 
     def __init__(
         self,
-        path: str,
+        path: Union[StrPath, List[StrPath], Any],
         delimiter: Optional[str] = None,
         quotechar: Optional[str] = None,
         escapechar: Optional[str] = None,
@@ -60,16 +277,115 @@ class Reader:
         double_quote: Optional[bool] = None,
         read_size: Optional[int] = None,
         field_size_limit: Optional[int] = None,
+        follow: bool = False,
+        poll_interval: Optional[float] = None,
+        has_header: bool = True,
+        include_filename: bool = False,
+        concurrency: Optional[int] = None,
+        engine: str = "default",
+        mmap: bool = False,
+        buffer_size: Optional[int] = None,
+        raw: bool = False,
+        lock: Optional[str] = None,
+        progress: Optional[Callable[[int, Optional[int], int], Any]] = None,
+        progress_interval: Optional[int] = None,
+        timeout: Optional[float] = None,
+        retries: int = 0,
+        retry_backoff: Optional[float] = None,
+        on_retry: Optional[Callable[[int, BaseException, float], Any]] = None,
+        eof: str = "empty",
+        row_type: str = "list",
+        checkpoint_path: Optional[StrPath] = None,
+        checkpoint_interval: Optional[int] = None,
+        schema: Optional[Dict[Union[str, int], str]] = None,
+        date_formats: Optional[List[str]] = None,
+        datetime_formats: Optional[List[str]] = None,
+        converters: Optional[Dict[Union[str, int], Callable[[Any], Any]]] = None,
+        utf8: str = "strict",
+        normalize_newlines: bool = False,
+        dialect: Optional[DialectLike] = None,
+        delim_whitespace: bool = False,
+        zip_member: Optional[str] = None,
+        verify_checksum: Union[bool, str, None] = None,
+        row_hash: bool = False,
+        encryption_key: Optional[bytes] = None,
+        max_memory: Optional[int] = None,
+        prefetch: Optional[int] = None,
+        columns: Optional[Sequence[Union[str, int]]] = None,
+        intern_columns: Union[bool, Sequence[Union[str, int]], None] = None,
+        skip_footer: int = 0,
+        limit: Optional[int] = None,
+        allowed_root: Optional[Union[str, bytes, os.PathLike]] = None,
+        duplicate_headers: str = "keep_first",
+        normalize_headers: Union[bool, Callable[[str], str], None] = None,
+        true_values: Union[Sequence[str], Dict[Union[str, int], Sequence[str]], None] = None,
+        false_values: Union[Sequence[str], Dict[Union[str, int], Sequence[str]], None] = None,
+        bool_case_sensitive: bool = False,
+        thousands: Optional[str] = None,
+        decimal: str = ".",
+        number_symbols: Optional[List[str]] = None,
+        percent_scale: bool = False,
+        strict_rfc4180: bool = False,
+        empty_as_none: bool = False,
+        field_spans: bool = False,
+        check_headers: Union[bool, List[str], None] = None,
+        binary: Optional[str] = None,
+        ssh_key: Optional[StrPath] = None,
     ) -> None: ...
-    def read_row(self) -> Coroutine[Any, Any, List[str]]:
+    @classmethod
+    def from_fd(cls, fd: int, **kwargs: Any) -> Reader:
+        """Read from an open file descriptor (Unix only).
+
+        Works with pipes, temporary files and descriptors passed in by another
+        process. The descriptor is duplicated, so the caller still owns and
+        closes ``fd``; reading starts at its current offset.
+
+        Args:
+            fd: Open file descriptor.
+            **kwargs: Constructor options, except ``lock``, ``mmap``,
+                ``verify_checksum``, ``encryption_key``, ``allowed_root`` and
+                ``ssh_key``.
+        """
+        ...
+    @classmethod
+    def from_socket(
+        cls,
+        host: str,
+        port: int,
+        connect_timeout: Optional[float] = None,
+        **kwargs: Any,
+    ) -> Reader:
+        """Connect to a TCP server and read the CSV it sends.
+
+        The connection is made by the first read (or ``open()``), so this
+        never blocks. Records are parsed as they arrive; the reader reaches
+        EOF when the server closes the connection.
+
+        Args:
+            host: Host name or IP address to connect to.
+            port: TCP port.
+            connect_timeout: Seconds to wait for the connection (default:
+                None, the operating system's limit).
+            **kwargs: Constructor options, as for :meth:`from_fd`.
+
+        Raises:
+            ConnectionError: From the first read, if the connection cannot be
+                made.
+        """
+        ...
+
+    def read_row(self) -> Coroutine[Any, Any, Optional[List[str]]]:
         """Read the next row from the CSV file.
 
         Returns:
-            List of string values for the row, or empty list if EOF.
+            List of string values for the row. At EOF, an empty list, or ``None``
+            with ``eof="none"``. Rows always have at least one field (a line
+            holding only ``""`` is ``[""]``), so an empty list only means EOF.
 
         Raises:
             IOError: If the file cannot be read.
             CSVError: If the CSV file is malformed or cannot be parsed.
+            EOFError: At EOF with ``eof="raise"``.
 
         Note:
             The Reader maintains position state across calls, reading sequentially.
@@ -85,6 +401,76 @@ class Reader:
 
         Returns:
             List of rows, where each row is a list of string values.
+
+        Note:
+            If the call is cancelled, rows it had already read are returned by
+            the next read rather than lost.
+        """
+        ...
+
+    def read_columns(
+        self, batch_size: Optional[int] = None
+    ) -> Coroutine[Any, Any, Dict[Union[str, int], List[Any]]]:
+        """Read rows as columns: a dict of one list of values per column.
+
+        Columns are keyed by the header's names, or by position with
+        ``has_header=False``; a header not read yet is read first. Values are
+        typed under ``schema=``, and short rows have None in the columns they
+        lack.
+
+        Args:
+            batch_size: Read up to this many rows (default: None, every
+                remaining row).
+
+        Returns:
+            Dict of columns, empty at EOF.
+
+        Raises:
+            CSVFieldCountError: If a row has more fields than the header.
+        """
+        ...
+
+    def read_raw_lines(self, n: int) -> Coroutine[Any, Any, List[bytes]]:
+        """Read up to ``n`` records as bytes, without parsing their fields.
+
+        Records are split at line terminators outside quotes and keep their
+        terminator. Blank lines are skipped; the header is returned as a record.
+
+        Args:
+            n: Number of records to read.
+
+        Returns:
+            List of records, empty at EOF.
+
+        Raises:
+            ValueError: With several files, ``follow``, ``escapechar``,
+                ``delim_whitespace``, ``columns``, ``include_filename``,
+                ``row_hash``, ``skip_footer``, ``limit`` or ``prefetch``.
+        """
+        ...
+
+    def read_all(self, threads: Optional[int] = None) -> Coroutine[Any, Any, List[List[str]]]:
+        """Read all remaining rows.
+
+        A file path that has not been read from yet is parsed on a worker
+        thread with the GIL released; the GIL is only taken to build the
+        result, so other coroutines and Python threads keep running.
+
+        Args:
+            threads: Number of threads used to parse a file that has not been
+                read from yet. The file is split into byte ranges at record
+                boundaries (quoted newlines are respected) and the ranges are
+                parsed concurrently, then recombined in order. A dialect with
+                an ``escapechar`` is parsed as one range (default: 1).
+
+        Returns:
+            List of rows, where each row is a list of string values.
+
+        Raises:
+            ValueError: If ``threads`` is 0, the reader is in follow mode, or
+                ``threads`` is above 1 for rows that are streamed rather than
+                loaded whole (file handles, several files, archive members,
+                partially read files and the like).
         """
         ...
 
@@ -96,6 +482,23 @@ class Reader:
         """
         ...
 
+    def into_queue(self, queue: Any, batch_size: Optional[int] = None) -> Coroutine[Any, Any, int]:
+        """Put every remaining row into an ``asyncio.Queue`` until EOF.
+
+        Rows are put with ``await queue.put()``, so a bounded queue holds the reader
+        back. Nothing marks the end; put a sentinel per consumer afterwards. Requires
+        asyncio.
+
+        Args:
+            queue: ``asyncio.Queue`` or any object with an awaitable ``put()``.
+            batch_size: Put lists of up to this many rows instead of single rows
+                (default: None).
+
+        Returns:
+            The number of rows put.
+        """
+        ...
+
     @property
     def line_num(self) -> int:
         """Current line number (1-based).
@@ -104,6 +507,129 @@ class Reader:
         """
         ...
 
+    def state(self) -> Dict[str, Any]:
+        """Save the reader's position as a JSON-serializable dict.
+
+        Only for file paths read one at a time (not file handles or
+        ``concurrency`` above 1).
+
+        Returns:
+            Dict with ``version``, ``paths``, ``file_index``, ``byte_offset``,
+            ``byte_end`` (the end of a :meth:`split` part, else None),
+            ``row_index``, ``line_num``, ``has_header``, ``dialect`` and
+            ``header`` (the consumed header for ``row_type="row"``, else None).
+        """
+        ...
+
+    @classmethod
+    def from_state(cls, state: Dict[str, Any], **kwargs: Any) -> Reader:
+        """Continue reading where :meth:`state` left off.
+
+        Args:
+            state: Dict returned by :meth:`state`, possibly in another process.
+            **kwargs: Constructor options not stored in the state, such as
+                ``row_type`` or callbacks.
+
+        Raises:
+            ValueError: If the state is incomplete, from another version, or
+                points past the end of the file.
+            FileChangedError: If the file's header differs from the saved one.
+        """
+        ...
+
+    def split(self, n: int, **kwargs: Any) -> Coroutine[Any, Any, List[Reader]]:
+        """Split an unread file into readers over disjoint ranges of it.
+
+        Each range is about ``1/n`` of the file and starts at a record
+        boundary, so workers can read one big file in parallel. Reading the
+        parts one after another returns the rows this reader would; the first
+        part returns (or consumes) the header.
+
+        Args:
+            n: Number of parts. A file with fewer records gives fewer.
+            **kwargs: Constructor options for every part, as for
+                :meth:`from_state`.
+
+        Returns:
+            List of up to ``n`` readers.
+
+        Raises:
+            ValueError: If ``n`` is 0, the reader has been read from, or it
+                is not over a single file path, or uses ``escapechar`` or
+                ``utf8="lossy"``.
+
+        Examples:
+            .. code-block:: python
+
+                parts = await Reader("big.csv").split(4)
+                await asyncio.gather(*(consume(part) for part in parts))
+        """
+        ...
+
+    def seek(self, row_index: int) -> Coroutine[Any, Any, None]:
+        """Move to a record, counted from 0 with the header included.
+
+        Record starts come from an index of the file built on first use and
+        shared by every reader in the process; it is rebuilt when the file's
+        size or modification time changes. The cache keeps up to 64 MiB of
+        indexes, evicting the least recently used. See
+        :func:`index_cache_info`.
+
+        Args:
+            row_index: Record to read next, as counted by ``row_index`` in
+                :meth:`state`. The number of records moves to the end.
+
+        Raises:
+            ValueError: If ``row_index`` is past the end, or the reader is not
+                over a single file path, or uses ``escapechar``,
+                ``utf8="lossy"`` or ``skip_footer``.
+            MemoryError: If the file's index would take more than the
+                reader's ``max_memory`` bytes.
+
+        Examples:
+            .. code-block:: python
+
+                reader = Reader("big.csv")
+                await reader.seek(1_000_000)
+                row = await reader.read_row()
+        """
+        ...
+
+    def checkpoint(self) -> None:
+        """Save the position to ``checkpoint_path`` now.
+
+        Call it once the rows read so far have been processed.
+
+        Raises:
+            ValueError: If the reader has no ``checkpoint_path``.
+        """
+        ...
+
+    @property
+    def byte_offset(self) -> Optional[int]:
+        """Byte offset in the current file where the next row starts.
+
+        Resets to 0 at each file of a multi-file stream. None when files are
+        parsed in parallel (``concurrency`` above 1) or with ``utf8="lossy"``.
+        """
+        ...
+
+    @property
+    def utf8_replacements(self) -> int:
+        """Invalid UTF-8 sequences replaced with U+FFFD so far (``utf8="lossy"``)."""
+        ...
+
+    def stats(self) -> Dict[str, Union[int, float]]:
+        """Throughput counters since the reader was created.
+
+        Returns:
+            Dict with ``rows`` (rows returned), ``bytes`` (bytes consumed),
+            ``errors`` (failed reads), ``parse_errors`` (reads that raised
+            CSVError or CSVFieldCountError), ``elapsed`` (seconds since the
+            reader was created) and ``active`` (seconds spent inside reads).
+        """
+        ...
+
     def __aiter__(self) -> Reader:
         """Async iterator protocol - returns self."""
         ...
@@ -112,8 +638,38 @@ class Reader:
         """Async iterator next - returns next row or raises StopAsyncIteration."""
         ...
 
+    def open(self) -> Coroutine[Any, Any, None]:
+        """Open the reader ahead of the first read.
+
+        Every file is opened and the first record is read and checked, without
+        being consumed, so a bad path, archive member, key or header raises
+        here rather than at the first read. Does nothing once reading has
+        started.
+
+        Raises:
+            CsvIoError: If a file cannot be opened or read.
+            EncodingError: If the start of the file is not valid UTF-8.
+            ValueError: If ``columns`` names a column the header lacks.
+            SchemaError: If the header fails ``check_headers``.
+        """
+        ...
+
+    def dialect(self) -> Coroutine[Any, Any, Dict[str, Any]]:
+        """Report the format the reader reads with and the data turned out to use.
+
+        Opens the reader first (see ``open()``).
+
+        Returns:
+            The dialect keyword arguments, as in ``state()``, with
+            ``lineterminator`` set to the first record's line ending when it has
+            one, plus ``bom`` (whether the data starts with a UTF-8 byte order
+            mark, None for an empty file or if nothing has been read) and
+            ``encoding`` (``"utf-8-sig"`` with a BOM, else ``"utf-8"``).
+        """
+        ...
+
     def __aenter__(self) -> Coroutine[Any, Any, Reader]:
-        """Async context manager entry."""
+        """Async context manager entry; opens the reader with :meth:`open`."""
         ...
 
     def __aexit__(
@@ -129,18 +685,111 @@ class Writer:
     """Async CSV writer for streaming CSV files.
 
     Provides true async CSV writing with GIL-independent operations.
-    The Writer reuses file handles across multiple write operations for efficiency.
+    Encoded rows for a file path are buffered and written in batches of
+    ``write_size`` bytes (64 KiB by default); call ``flush()`` or ``close()``
+    (or use ``async with``) to write out the rest. A buffering or queued writer dropped
+    unclosed writes out what it holds to a file path and warns with
+    ``ResourceWarning``.
+
+    Calls are applied in the order they are made, even when several tasks
+    write concurrently: each call's place is taken when the method is called,
+    not when it is awaited, so rows never interleave out of submission order.
 
     Args:
-        path: Path to CSV file or async file-like object (WithAsyncWrite).
+        path: Path to CSV file or async file-like object (WithAsyncWrite) or
+            :class:`SinkAdapter`. An ``sftp://user@host[:port]/path`` URL is
+            appended to on the server, in builds with the ``sftp`` feature.
         delimiter: Field delimiter character (default: ',').
         quotechar: Quote character (default: '"').
         escapechar: Escape character (default: None).
         quoting: Quoting style: 0=QUOTE_NONE, 1=QUOTE_MINIMAL, 2=QUOTE_ALL,
             3=QUOTE_NONNUMERIC, 4=QUOTE_NOTNULL, 6=QUOTE_STRINGS (default: 1).
+            With QUOTE_NONE fields are never quoted, and ``escapechar`` is
+            written before each delimiter, quote, escape character and line
+            break in a field. Without one, quotes are written as they are and
+            a field with any other of these raises ValueError.
         lineterminator: Line terminator string (default: '\\r\\n').
         double_quote: Handle doubled quotes (default: True).
-        write_size: Buffer size for writing chunks in bytes (default: 8192).
+        write_size: Bytes of encoded rows buffered before they are written
+            to the file; 0 writes each call's rows through (default: None,
+            64 KiB for a file path and 0 for a file handle, descriptor or
+            socket).
+        queue_size: Hand rows to a background writer task through a bounded
+            queue of this many writes. ``write_row()`` then only waits when
+            the queue is full, decoupling producers from disk latency while
+            keeping memory bounded. Write errors are raised by the next
+            ``write_row()``, ``flush()`` or ``close()`` (default: None, rows
+            are written by the calling task).
+        lock: Hold an exclusive advisory lock (flock/LockFileEx) on the file
+            from the first write until ``close()``, so processes appending
+            to the same file with ``lock=True`` never interleave records.
+            Only for file paths (default: False).
+        progress: Called as ``progress(bytes_written, None, rows_written)``
+            every ``progress_interval`` rows and once on ``close()``
+            (default: None).
+        progress_interval: Rows between progress calls (default: 10000).
+        timeout: Seconds each write, flush or close may take before raising
+            ``asyncio.TimeoutError`` (default: None, no limit).
+        retries: Times to retry opening the file after a transient IO error.
+            Writes are not retried, since a failed write may have been partly
+            applied (default: 0).
+        retry_backoff: Seconds before the first retry, doubling after each
+            one (default: 0.1).
+        on_retry: Called as ``on_retry(attempt, error, delay)`` before each
+            retry (default: None).
+        headers: Header row written before the first row unless the file
+            already has content, so a writer appending to an existing file
+            doesn't repeat it. New and empty files always get it, even if no
+            rows are written (default: None).
+        float_precision: Digits after the decimal point for float fields
+            (default: None, the shortest form that reads back to the same
+            float, as ``repr()``).
+        true_value: Field written for ``True`` (default: ``"True"``).
+        false_value: Field written for ``False`` (default: ``"False"``).
+        none_value: Field written for ``None`` (default: ``""``).
+        float_format: printf-style conversion for float fields, such as
+            ``"%.4f"``, ``"%.3e"`` or ``"%g"``, rendered in Rust. Cannot be
+            combined with ``float_precision`` (default: None).
+        date_format: strftime-style pattern for ``date`` fields, e.g.
+            ``"%d/%m/%Y"`` (default: None, ``str()``).
+        datetime_format: strftime-style pattern for ``datetime`` fields, e.g.
+            ``"%Y-%m-%dT%H:%M:%S%z"`` (default: None, ``str()``).
+        dialect: A :class:`Dialect`, a preset name (``"excel"``,
+            ``"excel-tab"``, ``"unix"``) or a ``csv.Dialect``; formatting
+            options passed alongside it override its values (default: None).
+        zip_member: Write the CSV as this member of the ZIP archive at
+            ``path``, deflated as it is written and added to the archive if
+            it exists; ``"bundle.zip::member.csv"`` does the same. A ``.zip``
+            path alone writes a member named after it (default: None).
+        checksum: ``"sha256"`` or ``"sha512"``: on ``close()``, hash the file
+            and write the digest to ``<path>.sha256`` (or ``.sha512``) in the
+            format ``sha256sum -c`` checks; only for file paths (default: None).
+        encryption_key: 32-byte AES-256 key. Rows are encrypted with
+            AES-256-GCM before they reach the file, so no plaintext is written
+            to disk; the file must be new or empty (default: None).
+        max_bytes_per_sec: Write no faster than this many bytes per second
+            on average, pausing between writes, e.g. to keep a bulk export
+            from saturating a shared NFS mount; also applies to
+            ``from_fd()`` and ``to_socket()`` (default: None, no limit).
+        max_rows: Most data rows this writer may write; the header row is
+            not counted (default: None, no limit).
+        max_bytes: Most bytes this writer may write, the header included.
+            A call that would cross ``max_rows`` or ``max_bytes`` writes none
+            of its rows and raises ``QuotaExceeded`` (default: None, no limit).
+        allowed_root: Directory the path must resolve inside once ``..`` and
+            symlinks are followed; checked as well as ``set_allowed_root()``.
+            A path outside raises ``PermissionError`` (default: None).
+        file_mode: Permission bits, e.g. ``0o640``, for the file if the writer
+            creates it, set exactly rather than through the umask; an existing
+            file keeps its own. Unix only (default: None).
+        file_group: Group name or gid for the file if the writer creates it.
+            Unix only (default: None).
+        sanitize_formulas: Prefix ``str`` and ``bytes`` fields that start with
+            ``=``, ``+``, ``-``, ``@``, a tab or a carriage return with ``'``,
+            so spreadsheets show them as text instead of running them as
+            formulas; numbers are written as they are (default: False).
+        ssh_key: Private key file to log in to the server of an ``sftp://``
+            URL with, as for :class:`Reader` (default: None).
 
     Examples
     --------
@@ -149,19 +798,20 @@ class Writer:
         from rapcsv import Writer
 
         # Write to file path
-        writer = Writer("output.csv")
-        await writer.write_row(["name", "age"])
+        async with Writer("output.csv") as writer:
+            await writer.write_row(["name", "age"])
 
         # Write to async file handle
         import aiofiles
         async with aiofiles.open("output.csv", mode="w") as f:
             writer = Writer(f)
             await writer.write_row(["name", "age"])
+            await writer.close()  # Write buffered rows before the handle closes
     """
 
     def __init__(
         self,
-        path: str,
+        path: StrPath,
         delimiter: Optional[str] = None,
         quotechar: Optional[str] = None,
         escapechar: Optional[str] = None,
@@ -169,27 +819,164 @@ class Writer:
         lineterminator: Optional[str] = None,
         double_quote: Optional[bool] = None,
         write_size: Optional[int] = None,
+        queue_size: Optional[int] = None,
+        lock: bool = False,
+        progress: Optional[Callable[[int, Optional[int], int], Any]] = None,
+        progress_interval: Optional[int] = None,
+        timeout: Optional[float] = None,
+        retries: int = 0,
+        retry_backoff: Optional[float] = None,
+        on_retry: Optional[Callable[[int, BaseException, float], Any]] = None,
+        headers: Optional[List[str]] = None,
+        float_precision: Optional[int] = None,
+        true_value: Optional[str] = None,
+        false_value: Optional[str] = None,
+        none_value: Optional[str] = None,
+        float_format: Optional[str] = None,
+        date_format: Optional[str] = None,
+        datetime_format: Optional[str] = None,
+        dialect: Optional[DialectLike] = None,
+        zip_member: Optional[str] = None,
+        checksum: Optional[str] = None,
+        encryption_key: Optional[bytes] = None,
+        max_bytes_per_sec: Optional[float] = None,
+        max_rows: Optional[int] = None,
+        max_bytes: Optional[int] = None,
+        allowed_root: Optional[Union[str, bytes, os.PathLike]] = None,
+        file_mode: Optional[int] = None,
+        file_group: Optional[Union[str, int]] = None,
+        sanitize_formulas: bool = False,
+        ssh_key: Optional[StrPath] = None,
     ) -> None: ...
-    def write_row(self, row: List[str]) -> Coroutine[Any, Any, None]:
+    @classmethod
+    def from_fd(cls, fd: int, **kwargs: Any) -> Writer:
+        """Write to an open file descriptor (Unix only).
+
+        The descriptor is duplicated, so the caller still owns and closes
+        ``fd``; ``close()`` closes only the duplicate.
+
+        Args:
+            fd: Open file descriptor.
+            **kwargs: Constructor options, except ``lock``, ``checksum``,
+                ``encryption_key``, ``allowed_root``, ``file_mode``,
+                ``file_group`` and ``ssh_key``.
+        """
+        ...
+    @classmethod
+    def to_socket(
+        cls,
+        host: str,
+        port: int,
+        connect_timeout: Optional[float] = None,
+        **kwargs: Any,
+    ) -> Writer:
+        """Connect to a TCP server and send it the CSV written.
+
+        The connection is made by the first write, so this never blocks.
+        ``close()`` closes the connection once buffered rows are sent.
+
+        Args:
+            host: Host name or IP address to connect to.
+            port: TCP port.
+            connect_timeout: Seconds to wait for the connection (default:
+                None, the operating system's limit).
+            **kwargs: Constructor options, as for :meth:`from_fd`.
+
+        Raises:
+            ConnectionError: From the first write (or ``close()``), if the
+                connection cannot be made.
+        """
+        ...
+
+    def write_row(self, row: List[Any]) -> Coroutine[Any, Any, None]:
         """Write a row to the CSV file.
 
         Args:
-            row: List of string values to write as a CSV row.
+            row: Values to write as a CSV row. Strings are written as they are;
+                ints, floats, bools and None are serialized according to
+                ``float_precision``, ``true_value``, ``false_value`` and
+                ``none_value``, and other objects with ``str()``.
 
         Raises:
             IOError: If the file cannot be written.
 
         Note:
-            The Writer reuses the file handle across multiple calls for efficiency.
-            Proper RFC 4180 compliant CSV escaping and quoting is applied automatically.
+            Unless ``write_size`` is 0, the row is buffered and reaches the
+            file once the buffer holds that many bytes, or on ``flush()``/``close()``. Proper RFC 4180
+            compliant CSV escaping and quoting is applied automatically. A
+            cancelled call writes the row in full or not at all.
         """
         ...
 
-    def writerows(self, rows: List[List[str]]) -> Coroutine[Any, Any, None]:
+    def writerows(self, rows: List[List[Any]]) -> Coroutine[Any, Any, None]:
         """Write multiple rows to the CSV file efficiently.
 
         Args:
-            rows: List of rows, where each row is a list of string values.
+            rows: List of rows, each converted as for ``write_row()``.
+        """
+        ...
+
+    def write_columns(
+        self, columns: Union[Dict[str, Sequence[Any]], Sequence[Sequence[Any]]]
+    ) -> Coroutine[Any, Any, None]:
+        """Write columns as rows, without transposing them in Python first.
+
+        Args:
+            columns: A dict of columns, written in its order without the keys
+                (use ``headers=`` for a header row), or a sequence of columns.
+                Values are converted as for ``write_row()``.
+
+        Raises:
+            ValueError: If the columns have different lengths.
+        """
+        ...
+
+    def write_raw(self, data: bytes) -> Coroutine[Any, Any, None]:
+        """Write CSV bytes as they are, without parsing or re-encoding them.
+
+        The bytes go through the writer's buffering, compression and output, after
+        the header if it is still pending, and count toward ``max_rows`` by the
+        records they hold.
+
+        Args:
+            data: Whole records in the writer's dialect, such as those from
+                ``Reader.read_raw_lines()``.
+        """
+        ...
+
+    def write_from(
+        self, rows: AsyncIterable[List[Any]], batch_size: int = 1000
+    ) -> Coroutine[Any, Any, int]:
+        """Write every row of an async iterable in one call.
+
+        Rows are pulled ``batch_size`` at a time on the event loop and written batch
+        by batch, and the writer is flushed at the end. Other calls on the writer
+        wait until it finishes. Requires asyncio.
+
+        Args:
+            rows: Async iterable of rows, each converted as for ``write_row()``.
+            batch_size: Rows pulled and written at a time (default: 1000).
+
+        Returns:
+            The number of rows written.
+
+        Raises:
+            TypeError: If ``rows`` is not an async iterable.
+        """
+        ...
+
+    def flush(self) -> Coroutine[Any, Any, None]:
+        """Write out buffered rows and flush the file, keeping it open."""
+        ...
+
+    def stats(self) -> Dict[str, Union[int, float]]:
+        """Throughput counters since the writer was created.
+
+        Returns:
+            Dict with ``rows`` and ``bytes`` written, ``errors`` (failed
+            operations), ``parse_errors`` (always 0), ``elapsed`` (seconds
+            since the writer was created) and ``active`` (seconds spent inside
+            writes, flushes and closes).
         """
         ...
 
@@ -232,6 +1019,19 @@ class AsyncDictReader:
         strict: Strict mode for field count validation (default: False).
         double_quote: Handle doubled quotes (default: True).
         read_size: Buffer size for reading chunks in bytes (default: 8192).
+        eof: What ``read_row()`` returns at EOF: ``"empty"`` for ``{}``,
+            ``"none"`` for ``None`` or ``"raise"`` to raise ``EOFError``
+            (default: ``"empty"``).
+        dialect: A :class:`Dialect`, a preset name (``"excel"``,
+            ``"excel-tab"``, ``"unix"``) or a ``csv.Dialect``; formatting
+            options passed alongside it override its values (default: None).
+        duplicate_headers: What to do with a name the header row holds more
+            than once: ``"keep_first"`` keeps the first column's value,
+            ``"suffix"`` renames the repeats ``id_1``, ``id_2``, ... and
+            ``"error"`` raises ``CSVError`` (default: None, a later column's
+            value replaces an earlier one's, as in ``csv.DictReader``).
+        normalize_headers: True to trim, lowercase and snake_case the names of
+            the header row, or a callable given each name (default: None).
 
     Examples
     --------
@@ -250,7 +1050,7 @@ class AsyncDictReader:
 
     def __init__(
         self,
-        path: str,
+        path: StrPath,
         fieldnames: Optional[List[str]] = None,
         restkey: Optional[str] = None,
         restval: Optional[str] = None,
@@ -263,12 +1063,34 @@ class AsyncDictReader:
         strict: Optional[bool] = None,
         double_quote: Optional[bool] = None,
         read_size: Optional[int] = None,
+        eof: str = "empty",
+        dialect: Optional[DialectLike] = None,
+        duplicate_headers: Optional[str] = None,
+        normalize_headers: Union[bool, Callable[[str], str], None] = None,
     ) -> None: ...
-    def read_row(self) -> Coroutine[Any, Any, Dict[str, str]]:
+    def read_row(self) -> Coroutine[Any, Any, Optional[Dict[str, str]]]:
         """Read the next row as a dictionary.
 
         Returns:
-            Dictionary mapping field names to values, or empty dict if EOF.
+            Dictionary mapping field names to values. At EOF, an empty dict, or
+            ``None`` with ``eof="none"``.
+
+        Raises:
+            EOFError: At EOF with ``eof="raise"``.
+        """
+        ...
+
+    def read_rows(self, n: int) -> Coroutine[Any, Any, List[Dict[str, str]]]:
+        """Read up to ``n`` rows as dictionaries.
+
+        The dicts share one set of key strings, made once per reader rather
+        than per row, which makes batches of wide rows much cheaper to build.
+
+        Args:
+            n: Number of rows to read.
+
+        Returns:
+            List of dictionaries; an empty list at EOF.
         """
         ...
 
@@ -329,12 +1151,16 @@ class AsyncDictReader:
 class AsyncDictWriter:
     """Async dictionary-based CSV writer.
 
-    Writes rows from dictionaries mapping field names to values.
-    Requires explicit fieldnames to define CSV structure.
+    Writes rows from dictionaries mapping field names to values. The
+    fieldnames define the CSV structure; when omitted they are taken from the
+    first row written, whose header is then written automatically.
 
     Args:
         path: Path to CSV file or async file-like object (WithAsyncWrite).
-        fieldnames: List of column names defining CSV structure (required).
+        fieldnames: List of column names defining CSV structure. If None, the
+            keys of the first row written become the fieldnames, in order, and
+            the header is written before that row; later rows with other keys
+            raise ValueError unless extrasaction='ignore' (default: None).
         restval: Default value for missing keys in dictionary (default: '').
         extrasaction: Action for extra keys: 'raise' (default) or 'ignore'.
         delimiter: Field delimiter character (default: ',').
@@ -344,6 +1170,11 @@ class AsyncDictWriter:
         lineterminator: Line terminator string (default: '\\r\\n').
         double_quote: Handle doubled quotes (default: True).
         write_size: Buffer size for writing chunks in bytes (default: 8192).
+        dialect: A :class:`Dialect`, a preset name (``"excel"``,
+            ``"excel-tab"``, ``"unix"``) or a ``csv.Dialect``; formatting
+            options passed alongside it override its values (default: None).
+        sanitize_formulas: Prefix ``str`` values that a spreadsheet would run
+            as formulas with ``'``, as for :class:`Writer` (default: False).
 
     Examples
     --------
@@ -358,8 +1189,8 @@ class AsyncDictWriter:
 
     def __init__(
         self,
-        path: str,
-        fieldnames: List[str],
+        path: StrPath,
+        fieldnames: Optional[List[str]] = None,
         restval: Optional[str] = None,
         extrasaction: Optional[str] = None,
         delimiter: Optional[str] = None,
@@ -369,9 +1200,16 @@ class AsyncDictWriter:
         lineterminator: Optional[str] = None,
         double_quote: Optional[bool] = None,
         write_size: Optional[int] = None,
+        dialect: Optional[DialectLike] = None,
+        sanitize_formulas: bool = False,
     ) -> None: ...
     def writeheader(self) -> Coroutine[Any, Any, None]:
-        """Write header row with fieldnames."""
+        """Write header row with fieldnames.
+
+        Raises:
+            ValueError: If the writer was created with ``fieldnames=None``,
+                which writes the header with the first row.
+        """
         ...
 
     def writerow(self, row: Dict[str, str]) -> Coroutine[Any, Any, None]:
@@ -411,43 +1249,1585 @@ class AsyncDictWriter:
         """Async context manager exit - closes the file handle and flushes writes."""
         ...
 
-class CSVError(Exception):
-    """Raised when a CSV parsing error occurs.
+class Row:
+    """A row returned by ``Reader(row_type="row")``.
 
-    This exception is raised when the CSV file is malformed or cannot be parsed.
+    Behaves like a tuple of fields (indexing, slicing, ``len()``, iteration,
+    equality with rows and tuples, hashing) and also looks fields up by column
+    name. The header is shared by every row of a reader, so no dict is built
+    per row.
 
     Examples
     --------
     .. code-block:: python
 
-        from rapcsv import Reader, CSVError
+        async for row in Reader("orders.csv", row_type="row"):
+            total += float(row.amount)  # Or row["amount"], or row[2]
+    """
 
-        try:
-            reader = Reader("malformed.csv")
-            row = await reader.read_row()
-        except CSVError as e:
-            print(f"CSV parsing error: {e}")
+    def __getitem__(self, key: Union[int, slice, str]) -> Any:
+        """Field by position, slice of fields, or field by column name.
+
+        Raises:
+            KeyError: If ``key`` is a name that is not a column.
+        """
+        ...
+
+    def __getattr__(self, name: str) -> Any:
+        """Field by column name.
+
+        Raises:
+            AttributeError: If ``name`` is not a column.
+        """
+        ...
+
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[Any]: ...
+    def __hash__(self) -> int: ...
+    def keys(self) -> List[str]:
+        """Column names from the header."""
+        ...
+
+    def get(self, name: str, default: Any = None) -> Any:
+        """Field by column name, or ``default`` if there is no such column."""
+        ...
+
+    def asdict(self) -> Dict[str, Any]:
+        """The row as a new ``{column: field}`` dict."""
+        ...
+
+class Dialect:
+    """Reusable CSV formatting options, accepted as ``dialect=`` by the CSV classes.
+
+    Presets mirror Python's ``csv`` module: ``Dialect.excel``,
+    ``Dialect.excel_tab`` and ``Dialect.unix`` (``csv.unix_dialect``, which
+    quotes every field). Quoting uses rapcsv's numbering (1=QUOTE_MINIMAL,
+    2=QUOTE_ALL, ...); ``csv.Dialect`` objects passed as ``dialect=`` are
+    translated from the ``csv.QUOTE_*`` constants.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import Dialect, Reader, Writer
+
+        pipes = Dialect(delimiter="|", lineterminator="\\n")
+        writer = Writer("out.csv", dialect=pipes)
+        reader = Reader("in.tsv", dialect="excel-tab")
     """
 
-    ...
+    excel: Dialect
+    excel_tab: Dialect
+    unix: Dialect
+    delimiter: str
+    quotechar: str
+    escapechar: Optional[str]
+    quoting: int
+    lineterminator: str
+    skipinitialspace: bool
+    strict: bool
+    double_quote: bool
+    doublequote: bool
 
-class CSVFieldCountError(Exception):
-    """Raised when there's a mismatch in the number of fields between rows.
+    def __init__(
+        self,
+        delimiter: str = ",",
+        quotechar: str = '"',
+        escapechar: Optional[str] = None,
+        quoting: int = 1,
+        lineterminator: str = "\r\n",
+        skipinitialspace: bool = False,
+        strict: bool = False,
+        double_quote: bool = True,
+    ) -> None: ...
+    @staticmethod
+    def preset(name: str) -> Dialect:
+        """The preset called ``"excel"``, ``"excel-tab"`` or ``"unix"``.
 
-    This exception is raised when strict mode is enabled and rows have
-    inconsistent field counts.
+        Raises:
+            ValueError: If ``name`` is not a preset.
+        """
+        ...
+
+    def replace(self, **changes: Any) -> Dialect:
+        """A copy with some options changed."""
+        ...
+
+    def to_dict(self) -> Dict[str, Any]:
+        """The options as keyword arguments, e.g. ``Writer(path, **d.to_dict())``."""
+        ...
+
+class RotatingWriter:
+    """Async CSV writer that rolls over to new files.
+
+    Part n of ``path`` is written to ``<stem>-<nnnn>.<ext>`` beside it
+    (``out.csv`` becomes ``out-0001.csv``, ``out-0002.csv``, ...), with the
+    header at the top of each. Numbering continues after the highest part
+    already beside ``path``, so existing parts are never overwritten. A part
+    is opened by the first row written to it, and the thresholds are checked
+    as rows are written.
+
+    Args:
+        path: Base path the parts are named after.
+        headers: Header row written at the top of every part.
+        max_bytes: Start a new part before a row would take the current one
+            past this size, the header included. A larger row gets a part
+            of its own.
+        max_rows: Data rows per part.
+        max_seconds: Start a new part for rows written this long after the
+            current one was opened.
+        dialect: Dialect to write in, overridden by the options below.
+        delimiter, quotechar, escapechar, quoting, lineterminator,
+        double_quote: As for :class:`Writer`.
+
+    Raises:
+        ValueError: If none of ``max_bytes``, ``max_rows`` and
+            ``max_seconds`` is given, or one is not positive.
 
     Examples
     --------
     .. code-block:: python
 
-        from rapcsv import Reader, CSVFieldCountError
+        from rapcsv import RotatingWriter
 
-        try:
-            reader = Reader("data.csv", strict=True)
-            row = await reader.read_row()
-        except CSVFieldCountError as e:
-            print(f"Field count mismatch: {e}")
+        async with RotatingWriter("events.csv", headers=["ts", "event"], max_rows=100_000) as writer:
+            async for event in events():
+                await writer.write_row(event)
+        print(writer.paths)
     """
 
+    def __init__(
+        self,
+        path: StrPath,
+        headers: Optional[List[str]] = None,
+        max_bytes: Optional[int] = None,
+        max_rows: Optional[int] = None,
+        max_seconds: Optional[float] = None,
+        dialect: Optional[DialectLike] = None,
+        delimiter: Optional[str] = None,
+        quotechar: Optional[str] = None,
+        escapechar: Optional[str] = None,
+        quoting: Optional[int] = None,
+        lineterminator: Optional[str] = None,
+        double_quote: Optional[bool] = None,
+    ) -> None: ...
+    @property
+    def path(self) -> Optional[str]:
+        """Path of the part being written, or None before the first row."""
+        ...
+
+    @property
+    def paths(self) -> List[str]:
+        """Paths of every part opened so far, in order."""
+        ...
+
+    def write_row(self, row: List[Any]) -> Coroutine[Any, Any, None]:
+        """Write a row, first moving on to a new part if the current one is full."""
+        ...
+
+    def writerows(self, rows: List[List[Any]]) -> Coroutine[Any, Any, None]:
+        """Write multiple rows, moving on to new parts as they fill."""
+        ...
+
+    def rotate(self) -> Coroutine[Any, Any, None]:
+        """Close the current part, so the next row starts a new one."""
+        ...
+
+    def close(self) -> Coroutine[Any, Any, None]:
+        """Close the current part."""
+        ...
+
+    def __aenter__(self) -> Coroutine[Any, Any, RotatingWriter]:
+        """Async context manager entry."""
+        ...
+
+    def __aexit__(
+        self,
+        exc_type: Optional[Any],
+        exc_val: Optional[Any],
+        exc_tb: Optional[Any],
+    ) -> Coroutine[Any, Any, None]:
+        """Async context manager exit - closes the current part."""
+        ...
+
+class JsonlReader:
+    """Async JSON Lines reader.
+
+    Reads one JSON document per line and returns the decoded Python object.
+    Blank lines are skipped. Accepts the same sources as :class:`Reader`.
+
+    Args:
+        path: Path to JSONL file or async file-like object (WithAsyncRead).
+        read_size: Buffer size for reading chunks in bytes (default: 8192).
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import JsonlReader
+
+        async with JsonlReader("events.jsonl") as reader:
+            async for record in reader:
+                print(record["id"])
+    """
+
+    def __init__(self, path: StrPath, read_size: Optional[int] = None) -> None: ...
+    @property
+    def line_num(self) -> int:
+        """Current line number (1-based)."""
+        ...
+
+    def read_row(self) -> Coroutine[Any, Any, Any]:
+        """Read the next record.
+
+        Returns:
+            The decoded JSON value, or None at EOF.
+
+        Raises:
+            ValueError: If a line is not valid JSON.
+            EncodingError: If the file is not valid UTF-8.
+        """
+        ...
+
+    def read_rows(self, n: int) -> Coroutine[Any, Any, List[Any]]:
+        """Read up to ``n`` records.
+
+        Args:
+            n: Maximum number of records to read.
+
+        Returns:
+            List of decoded records. Shorter than ``n`` (or empty) at EOF.
+        """
+        ...
+
+    def __aiter__(self) -> JsonlReader:
+        """Async iterator protocol - returns self."""
+        ...
+
+    def __anext__(self) -> Coroutine[Any, Any, Any]:
+        """Return the next record, raising StopAsyncIteration at EOF."""
+        ...
+
+    def __aenter__(self) -> Coroutine[Any, Any, JsonlReader]:
+        """Async context manager entry."""
+        ...
+
+    def __aexit__(
+        self,
+        exc_type: Optional[Any],
+        exc_val: Optional[Any],
+        exc_tb: Optional[Any],
+    ) -> Coroutine[Any, Any, None]:
+        """Async context manager exit - closes the file handle."""
+        ...
+
+class JsonlWriter:
+    """Async JSON Lines writer.
+
+    Writes each record as compact JSON followed by a newline. Accepts the
+    same sinks as :class:`Writer`.
+
+    Args:
+        path: Path to JSONL file or async file-like object (WithAsyncWrite).
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import JsonlWriter
+
+        async with JsonlWriter("events.jsonl") as writer:
+            await writer.write_row({"id": 1, "tags": ["a", "b"]})
+    """
+
+    def __init__(self, path: StrPath) -> None: ...
+    def write_row(self, record: Any) -> Coroutine[Any, Any, None]:
+        """Write a single JSON-serializable record.
+
+        Args:
+            record: dict, list, str, int, float, bool or None (nested freely).
+
+        Raises:
+            TypeError: If the record contains a non-serializable object.
+            ValueError: If the record contains NaN or infinity.
+        """
+        ...
+
+    def writerows(self, records: List[Any]) -> Coroutine[Any, Any, None]:
+        """Write multiple JSON-serializable records.
+
+        Args:
+            records: Iterable of records to write.
+        """
+        ...
+
+    def close(self) -> Coroutine[Any, Any, None]:
+        """Explicitly close the file handle and flush any pending writes."""
+        ...
+
+    def __aenter__(self) -> Coroutine[Any, Any, JsonlWriter]:
+        """Async context manager entry."""
+        ...
+
+    def __aexit__(
+        self,
+        exc_type: Optional[Any],
+        exc_val: Optional[Any],
+        exc_tb: Optional[Any],
+    ) -> Coroutine[Any, Any, None]:
+        """Async context manager exit - closes the file handle and flushes writes."""
+        ...
+
+class FixedWidthReader:
+    """Async fixed-width file reader.
+
+    Cuts each line into fields at fixed character positions, such as the
+    columns of a mainframe extract. Blank lines are skipped and a line too
+    short for a column gives an empty field.
+
+    Args:
+        path: Path to the file or an async file-like object (WithAsyncRead).
+        widths: Width in characters of each column, left to right.
+        colspecs: ``(start, end)`` character range of each column, 0-based
+            and half-open, with ``end`` None for the rest of the line. Pass
+            either ``widths`` or ``colspecs``.
+        strip: Strip surrounding whitespace from fields (default: True).
+        read_size: Buffer size for reading chunks in bytes (default: 8192).
+
+    Raises:
+        ValueError: If neither or both of ``widths`` and ``colspecs`` are
+            given, or a width or range is empty.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import FixedWidthReader
+
+        async with FixedWidthReader("extract.dat", widths=[8, 20, 10]) as reader:
+            async for account, name, balance in reader:
+                print(account, float(balance))
+    """
+
+    def __init__(
+        self,
+        path: StrPath,
+        widths: Optional[List[int]] = None,
+        colspecs: Optional[List[Tuple[int, Optional[int]]]] = None,
+        strip: bool = True,
+        read_size: Optional[int] = None,
+    ) -> None: ...
+    @property
+    def line_num(self) -> int:
+        """Line number of the last row read (1-based), counting blank lines."""
+        ...
+
+    def read_row(self) -> Coroutine[Any, Any, Optional[List[str]]]:
+        """Read the next row. Returns None at EOF."""
+        ...
+
+    def read_rows(self, n: int) -> Coroutine[Any, Any, List[List[str]]]:
+        """Read up to ``n`` rows."""
+        ...
+
+    def __aiter__(self) -> FixedWidthReader: ...
+    def __anext__(self) -> Coroutine[Any, Any, List[str]]: ...
+    def __aenter__(self) -> Coroutine[Any, Any, FixedWidthReader]: ...
+    def __aexit__(
+        self,
+        exc_type: Optional[Any],
+        exc_val: Optional[Any],
+        exc_tb: Optional[Any],
+    ) -> Coroutine[Any, Any, None]: ...
+
+class RapcsvError(Exception):
+    """Base class of the errors rapcsv raises.
+
+    The hierarchy separates input that is not valid CSV from failures to read
+    or write a file::
+
+        RapcsvError
+        ├── CsvParseError (also exported as CSVError)
+        │   ├── CSVFieldCountError
+        │   ├── SchemaError
+        │   └── EncodingError (also an OSError)
+        ├── CsvIoError (also an OSError)
+        │   ├── FileChangedError
+        │   └── ChecksumError
+        └── QuotaExceeded
+
+    Attributes are None when the error could not be placed in the input. Parse
+    errors also give the line, byte offset, field and snippet in their message.
+
+    Attributes:
+        row: 0-based index of the record, the header included.
+        line: 1-based line the record starts on.
+        column: 0-based index of the field.
+        byte_offset: Byte offset of the record in its file.
+        field: The offending field as read.
+        snippet: The start of the offending line, at most 80 characters.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import Reader, CsvParseError, CsvIoError
+
+        try:
+            rows = await Reader("data.csv").read_all()
+        except CsvParseError as e:
+            print(f"Bad CSV at row {e.row} (byte {e.byte_offset}): {e}")
+        except CsvIoError as e:
+            print(f"Could not read the file: {e}")
+    """
+
+    row: Optional[int]
+    line: Optional[int]
+    column: Optional[int]
+    byte_offset: Optional[int]
+    field: Optional[str]
+    snippet: Optional[str]
+
+class CsvParseError(RapcsvError):
+    """Raised when a CSV parsing error occurs.
+
+    This exception is raised when the CSV file is malformed or cannot be parsed.
+    ``CSVError`` is another name for this class.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import Reader, CsvParseError
+
+        try:
+            reader = Reader("malformed.csv")
+            row = await reader.read_row()
+        except CsvParseError as e:
+            print(f"CSV parsing error at row {e.row}: {e}")
+    """
+
+    ...
+
+CSVError = CsvParseError
+
+class CsvIoError(RapcsvError, OSError):
+    """Raised when reading or writing a file fails.
+
+    A subclass of ``OSError``, so ``except IOError`` handlers keep working.
+    """
+
+    ...
+
+class SchemaError(CsvParseError):
+    """Raised when a field cannot be converted to the type its column is declared as.
+
+    ``column`` and ``field`` name the field that failed to convert.
+    """
+
+    ...
+
+class QuotaExceeded(RapcsvError):
+    """Raised when a write would take a Writer past its ``max_rows`` or ``max_bytes``.
+
+    None of the rows of the call that raised it are written; rows written
+    before it stay in the file.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import Writer, QuotaExceeded
+
+        async with Writer("export.csv", max_rows=1_000_000) as writer:
+            try:
+                async for row in query():
+                    await writer.write_row(row)
+            except QuotaExceeded:
+                ...  # Tell the user the export was cut short
+    """
+
+    ...
+
+class EncodingError(CsvParseError, OSError):
+    """Raised when the input is not valid in its encoding.
+
+    A subclass of ``OSError``, as invalid UTF-8 was reported as an ``IOError``
+    before the hierarchy existed.
+    """
+
+    ...
+
+class CSVFieldCountError(CsvParseError):
+    """Raised when there's a mismatch in the number of fields between rows.
+
+    This exception is raised when strict mode is enabled and rows have
+    inconsistent field counts.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import Reader, CSVFieldCountError
+
+        try:
+            reader = Reader("data.csv", strict=True)
+            row = await reader.read_row()
+        except CSVFieldCountError as e:
+            print(f"Field count mismatch: {e}")
+    """
+
+    ...
+
+class FileChangedError(CsvIoError):
+    """Raised when a file is truncated or replaced while a Reader is reading it.
+
+    Before each read from the operating system, a path-backed reader checks
+    that the file is not shorter than what it has already read and (on Unix)
+    that the path still names the same file. Appended data is not an error.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import Reader, FileChangedError
+
+        try:
+            rows = await Reader("app.log.csv", follow=True).read_rows(100)
+        except FileChangedError:
+            ...  # The log was rotated; reopen it
+    """
+
+    ...
+
+class ChecksumError(CsvIoError):
+    """Raised when a file does not match its checksum.
+
+    Raised by ``Reader(verify_checksum=...)`` before any row of the file is
+    returned, and when the expected checksum file is missing or unreadable.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import Reader, ChecksumError
+
+        try:
+            rows = await Reader("export.csv", verify_checksum=True).read_all()
+        except ChecksumError:
+            ...  # Download the export again
+    """
+
+    ...
+
+def to_sqlite(
+    path: str,
+    db: str,
+    table: str,
+    schema: Optional[Dict[str, str]] = None,
+    batch_size: Optional[int] = None,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+    progress: Optional[Callable[[int, Optional[int], int], Any]] = None,
+    progress_interval: Optional[int] = None,
+) -> Coroutine[Any, Any, int]:
+    """Load a CSV file into an SQLite table.
+
+    The CSV header provides the column names. The table is created if it
+    does not exist, and rows are inserted from Rust in transactions of
+    ``batch_size`` rows without holding the GIL.
+
+    Args:
+        path: Path to the CSV file.
+        db: Path to the SQLite database (created if missing).
+        table: Name of the table to create and fill.
+        schema: Optional mapping of column name to SQL type name, such as
+            ``INTEGER`` or ``VARCHAR(20)``. Columns not listed are ``TEXT``.
+            Empty fields in columns without ``TEXT`` affinity are stored as
+            NULL.
+        batch_size: Rows per transaction (default: 10000).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+        progress: Called as ``progress(bytes_read, bytes_total, rows_inserted)``
+            every ``progress_interval`` rows and once when done (default: None).
+        progress_interval: Rows between progress calls (default: 10000).
+
+    Returns:
+        Number of rows inserted.
+
+    Raises:
+        ValueError: If a schema column is not in the CSV header, a schema
+            type is not a type name, or an existing table lacks a column of
+            the header.
+        CSVError: If the CSV cannot be parsed.
+        IOError: If the file or database cannot be opened.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import to_sqlite
+
+        rows = await to_sqlite("sales.csv", "sales.db", "sales", schema={"amount": "REAL"})
+    """
+    ...
+
+def query(
+    sql: str,
+    params: Optional[List[Any]] = None,
+    as_dict: bool = False,
+    delimiter: Optional[str] = None,
+    as_arrow: bool = False,
+) -> Coroutine[Any, Any, Any]:
+    """Run an SQL query over one or more CSV files.
+
+    Files are referenced by quoting their path after ``FROM`` or ``JOIN``.
+    Each file becomes an SQLite virtual table that reads it as the query
+    runs, without copying it anywhere. Columns have ``NUMERIC`` affinity, so
+    numeric fields aggregate and compare as numbers. The query runs in Rust
+    without holding the GIL.
+
+    Args:
+        sql: Query in the SQLite dialect.
+        params: Optional values bound to ``?`` placeholders.
+        as_dict: Return each row as a dict keyed by column name
+            (default: False, rows are lists).
+        delimiter: Field delimiter (default: ',', or tab for ``.tsv`` files).
+        as_arrow: Return a ``pyarrow.Table`` instead of a list (default:
+            False). Requires pyarrow; cannot be combined with ``as_dict``.
+
+    Returns:
+        List of result rows, or a ``pyarrow.Table`` with ``as_arrow``. Use
+        :func:`query_batches` for a result too large to hold at once.
+
+    Raises:
+        ValueError: If the query does not reference a CSV file, or both
+            ``as_dict`` and ``as_arrow`` are set.
+        ImportError: If ``as_arrow`` is set and pyarrow is not installed.
+        CSVError: If a CSV file cannot be parsed.
+        IOError: If a file cannot be opened or the SQL is invalid.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import query
+
+        rows = await query("SELECT a, sum(b) FROM 'big.csv' GROUP BY a")
+    """
+    ...
+
+class QueryBatches:
+    """Async iterator over the result of :func:`query_batches`."""
+
+    def __aiter__(self) -> QueryBatches: ...
+    def __anext__(self) -> Coroutine[Any, Any, Any]: ...
+
+def query_batches(
+    sql: str,
+    params: Optional[List[Any]] = None,
+    as_dict: bool = False,
+    delimiter: Optional[str] = None,
+    as_arrow: bool = False,
+    batch_size: Optional[int] = None,
+) -> QueryBatches:
+    """Run an SQL query over one or more CSV files, yielding rows as produced.
+
+    Takes the same arguments as :func:`query`. The query runs in the
+    background at most one batch ahead of the consumer, so a large result is
+    never held in memory at once; leaving the loop early stops it.
+
+    Args:
+        batch_size: Rows per batch (default: 10000).
+
+    Returns:
+        Async iterator of lists of result rows, or of ``pyarrow.RecordBatch``
+        objects with ``as_arrow``.
+
+    Raises:
+        ValueError: If the query does not reference a CSV file, or both
+            ``as_dict`` and ``as_arrow`` are set.
+        ImportError: If ``as_arrow`` is set and pyarrow is not installed.
+        CSVError: If a CSV file cannot be parsed (raised by the iteration).
+        IOError: If a file cannot be opened or the SQL is invalid (raised by
+            the iteration).
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import query_batches
+
+        async for rows in query_batches("SELECT * FROM 'big.csv' WHERE b > 0"):
+            handle(rows)
+    """
+    ...
+
+def concat(
+    paths: List[str],
+    dst: str,
+    align_headers: bool = True,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+    progress: Optional[Callable[[int, Optional[int], int], Any]] = None,
+    progress_interval: Optional[int] = None,
+) -> Coroutine[Any, Any, int]:
+    """Concatenate multiple CSV files into one.
+
+    The first file's header becomes the output header and is written once.
+    With ``align_headers``, later files may list the same columns in a
+    different order and are reordered by name. Rows are streamed, so inputs
+    of any size can be combined.
+
+    Args:
+        paths: Input CSV files, in order.
+        dst: Output CSV file (created or truncated).
+        align_headers: Reorder columns by header name (default: True). When
+            False, every header must match the first exactly.
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+        progress: Called as ``progress(bytes_read, bytes_total, rows_written)``
+            every ``progress_interval`` rows and once when done (default: None).
+        progress_interval: Rows between progress calls (default: 10000).
+
+    Returns:
+        Number of data rows written.
+
+    Raises:
+        ValueError: If a file's columns are incompatible with the first file,
+            or ``dst`` is one of the input files.
+        CSVError: If an input cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import concat
+
+        rows = await concat(["jan.csv", "feb.csv", "mar.csv"], "q1.csv")
+    """
+    ...
+
+def split(
+    src: str,
+    dst_pattern: str,
+    rows_per_file: Optional[int] = None,
+    max_bytes: Optional[int] = None,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+    progress: Optional[Callable[[int, Optional[int], int], Any]] = None,
+    progress_interval: Optional[int] = None,
+) -> Coroutine[Any, Any, List[str]]:
+    """Split a large CSV file into numbered part files.
+
+    The header row is copied into every part. A new part starts when the
+    current one reaches ``rows_per_file`` data rows or when the next row
+    would push it past ``max_bytes``. At least one limit is required.
+
+    Args:
+        src: Input CSV file.
+        dst_pattern: Output path with a ``{}`` or ``{:0Nd}`` placeholder for
+            the 1-based part number, e.g. ``"out/part_{:03d}.csv"``.
+        rows_per_file: Maximum data rows per part.
+        max_bytes: Maximum size of each part in bytes, header included.
+            A part always holds at least one row.
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+        progress: Called as ``progress(bytes_read, bytes_total, rows_written)``
+            every ``progress_interval`` rows and once when done (default: None).
+        progress_interval: Rows between progress calls (default: 10000).
+
+    Returns:
+        Paths of the part files written, in order.
+
+    Raises:
+        ValueError: If no limit is given, the pattern has no placeholder, or
+            a part path is the same file as ``src``.
+        CSVError: If the input cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import split
+
+        parts = await split("big.csv", "big_{:03d}.csv", rows_per_file=1_000_000)
+    """
+    ...
+
+def restructure(
+    src: str,
+    dst: str,
+    rename: Optional[Dict[str, str]] = None,
+    drop: Optional[List[str]] = None,
+    order: Optional[List[str]] = None,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+    progress: Optional[Callable[[int, Optional[int], int], Any]] = None,
+    progress_interval: Optional[int] = None,
+) -> Coroutine[Any, Any, int]:
+    """Rename, drop and reorder columns in one streaming pass.
+
+    ``drop`` and ``order`` refer to the source column names, before
+    ``rename`` is applied.
+
+    Args:
+        src: Path to the input CSV file (first row is the header).
+        dst: Path to the output CSV file (created or truncated).
+        rename: Dict mapping source column names to new names.
+        drop: Source columns to remove.
+        order: Source columns in output order. Columns not listed are
+            removed.
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+        progress: Called as ``progress(bytes_read, bytes_total, rows_written)``
+            every ``progress_interval`` rows and once when done (default: None).
+        progress_interval: Rows between progress calls (default: 10000).
+
+    Returns:
+        Number of data rows written.
+
+    Raises:
+        ValueError: If a column name is not in the header, a column is
+            both ordered and dropped, or ``dst`` is the same file as ``src``.
+        CSVError: If the input cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import restructure
+
+        await restructure(
+            "export.csv", "load.csv",
+            rename={"cust_id": "customer_id"},
+            drop=["internal_notes"],
+        )
+    """
+    ...
+
+def partition(
+    src: str,
+    dst_dir: str,
+    by: Union[str, List[str]],
+    keep_columns: bool = False,
+    filename: str = "data.csv",
+    max_open_files: int = 64,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+) -> Coroutine[Any, Any, Dict[str, int]]:
+    """Write the rows of a CSV file into Hive-style partition directories.
+
+    Each row goes to ``<dst_dir>/<col>=<value>/.../<filename>`` by the values
+    of the ``by`` columns, with the header at the top of every file. Values
+    are escaped as Hive does (``/`` becomes ``%2F``), and an empty value is
+    written to ``__HIVE_DEFAULT_PARTITION__``.
+
+    Args:
+        src: Path to the input CSV file (first row is the header).
+        dst_dir: Output directory. Partition files written are truncated;
+            others already there are left alone.
+        by: Column name or list of names to partition by, outermost first.
+        keep_columns: Also write the ``by`` columns into the files
+            (default: False, as their values are in the path).
+        filename: Name of the file in each partition directory.
+        max_open_files: Most partition files kept open at once; the least
+            recently written is closed and later appended to.
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+
+    Returns:
+        Dict mapping each partition directory, relative to ``dst_dir``, to
+        the number of data rows written to it.
+
+    Raises:
+        ValueError: If a ``by`` column is not in the header, or a partition
+            file is the same file as ``src``.
+        CSVError: If the input cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import partition
+
+        await partition("events.csv", "lake/events", by=["year", "month"])
+        # lake/events/year=2024/month=01/data.csv, ...
+    """
+    ...
+
+def convert(
+    src: str,
+    dst: str,
+    from_dialect: Optional[DialectLike] = None,
+    to_dialect: Optional[DialectLike] = None,
+    from_encoding: str = "utf-8",
+    to_encoding: str = "utf-8",
+) -> Coroutine[Any, Any, int]:
+    """Convert a CSV file to another dialect and encoding in one streaming pass.
+
+    Records are parsed with ``from_dialect`` and written with ``to_dialect``,
+    so delimiters, quoting and line endings are redone rather than replaced
+    character by character.
+
+    Args:
+        src: Path to the input CSV file.
+        dst: Path to the output CSV file (created or truncated).
+        from_dialect: Dialect of ``src``: a ``Dialect``, preset name or
+            ``csv.Dialect`` (default: "excel").
+        to_dialect: Dialect to write ``dst`` in (default: "excel").
+        from_encoding: Encoding of ``src``: "utf-8", "utf-8-sig",
+            "latin-1" or "cp1252" (default: "utf-8").
+        to_encoding: Encoding to write ``dst`` in (default: "utf-8").
+
+    Returns:
+        Number of records written, the header included.
+
+    Raises:
+        ValueError: If an encoding is unsupported, a character cannot be
+            encoded in ``to_encoding``, or ``dst`` is the same file as ``src``.
+        IOError: If a file cannot be read or written, or ``src`` isn't valid
+            UTF-8 when read as UTF-8.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import Dialect, convert
+
+        await convert(
+            "export.csv",
+            "clean.csv",
+            from_dialect=Dialect(delimiter=";"),
+            to_dialect=Dialect(lineterminator="\\n"),
+            from_encoding="latin-1",
+        )
+    """
+    ...
+
+def sort(
+    src: str,
+    dst: str,
+    by: Union[str, List[str]],
+    typed: bool = True,
+    chunk_rows: Optional[int] = None,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+    max_memory: Optional[int] = None,
+    temp_dir: Optional[str] = None,
+) -> Coroutine[Any, Any, int]:
+    """Sort a CSV file by one or more columns.
+
+    Uses an external merge sort: at most ``chunk_rows`` rows are held in
+    memory, sorted runs are spilled to temporary files, and the runs are
+    merged into ``dst``, at most 64 at a time (in several passes when there
+    are more). Files far larger than RAM can be sorted this way.
+    The sort is stable and the header row is kept first.
+
+    Args:
+        src: Input CSV file.
+        dst: Output CSV file (created or truncated).
+        by: Column name or list of names. Prefix a name with ``-`` to sort
+            that column in descending order, e.g. ``["date", "-amount"]``.
+        typed: Compare numeric fields as numbers rather than strings
+            (default: True). Empty fields sort first.
+        chunk_rows: Rows sorted in memory before spilling to disk
+            (default: 500000).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+        max_memory: Also spill once the rows in memory take this many bytes
+            (default: None).
+        temp_dir: Existing directory to spill runs under (default: the
+            system's temporary directory). The runs are removed when the
+            sort ends, including when it fails or is cancelled.
+
+    Returns:
+        Number of data rows written.
+
+    Raises:
+        ValueError: If a sort column is not in the header, or ``temp_dir``
+            is not a directory.
+        CSVError: If the input cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import sort
+
+        await sort("sales.csv", "sales_sorted.csv", by=["date", "-amount"])
+    """
+    ...
+
+def merge_sorted(
+    paths: List[str],
+    dst: str,
+    by: Union[str, List[str]],
+    typed: bool = True,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+) -> Coroutine[Any, Any, int]:
+    """Merge CSV files that are each sorted by the same columns.
+
+    A streaming k-way merge: one row per input is held in memory, so files
+    that are already sorted, such as daily extracts of a time series, are
+    combined without a full sort. Rows that compare equal keep the order of
+    ``paths``.
+
+    Args:
+        paths: Input CSV files, each sorted by ``by``, with the same header.
+        dst: Output CSV file (created or truncated).
+        by: Column name or list of names, as for ``sort()``.
+        typed: Compare numeric fields as numbers (default: True).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+
+    Returns:
+        Number of data rows written.
+
+    Raises:
+        ValueError: If the headers differ, a column is not in the header,
+            an input is found out of order, or ``dst`` is one of the inputs.
+        CSVError: If an input cannot be parsed.
+    """
+    ...
+
+def dedupe(
+    src: str,
+    dst: str,
+    key: Optional[Union[str, List[str]]] = None,
+    max_keys: Optional[int] = None,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+    keep: str = "first",
+    temp_dir: Optional[str] = None,
+) -> Coroutine[Any, Any, int]:
+    """Remove duplicate rows from a CSV file.
+
+    The first (or with ``keep="last"``, the last) occurrence of each key is
+    kept, and kept rows stay in input order. Keys are tracked in memory; if
+    more than ``max_keys`` distinct keys are seen, the file is processed
+    again by hash-partitioning rows into temporary files so memory stays
+    bounded. ``keep="last"`` reads the input (or each partition) twice.
+
+    Args:
+        src: Input CSV file.
+        dst: Output CSV file (created or truncated).
+        key: Column name or list of names identifying a row
+            (default: the whole row).
+        max_keys: Distinct keys held in memory before spilling to disk
+            (default: 10000000).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+        keep: Which occurrence of each key to keep, "first" or "last"
+            (default: "first").
+        temp_dir: Existing directory to spill partitions under (default:
+            the system's temporary directory). The partitions are removed
+            when the call ends, including when it fails or is cancelled.
+
+    Returns:
+        Number of duplicate rows removed.
+
+    Raises:
+        ValueError: If a key column is not in the header, ``keep`` is not
+            "first" or "last", ``temp_dir`` is not a directory, or ``dst``
+            is the same file as ``src``.
+        CSVError: If the input cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import dedupe
+
+        removed = await dedupe("orders.csv", "orders_unique.csv", key=["id"])
+        # Collapse re-exports, keeping each order's latest version
+        await dedupe("orders.csv", "orders_latest.csv", key=["id"], keep="last")
+    """
+    ...
+
+def join(
+    left: str,
+    right: str,
+    on: Union[str, List[str]],
+    dst: str,
+    how: str = "inner",
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+    max_memory: Optional[int] = None,
+    temp_dir: Optional[str] = None,
+) -> Coroutine[Any, Any, int]:
+    """Join two CSV files on key columns.
+
+    Performs a hash join in Rust: ``right`` is loaded into a hash table on
+    the join columns and ``left`` is streamed, so the left file can be any
+    size. A right file over ``max_memory`` is joined a partition at a time
+    through temporary files instead, with the same output. Output columns
+    are every left column followed by the non-key right columns; right
+    columns whose names clash with a left column get a ``_right`` suffix.
+
+    Args:
+        left: Left CSV file (streamed).
+        right: Right CSV file (held in memory, or a partition at a time).
+        on: Column name or list of names present in both files.
+        dst: Output CSV file (created or truncated).
+        how: ``"inner"`` (default), ``"left"``, ``"right"`` or ``"outer"``.
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+        max_memory: Largest size in bytes of the right file's hash table;
+            past it both files are partitioned on disk (default: None).
+        temp_dir: Directory for the partition files, removed when the join
+            ends (default: the system's temporary directory).
+
+    Returns:
+        Number of data rows written.
+
+    Raises:
+        ValueError: If ``how`` is invalid, a key column is missing, or ``dst``
+            is the same file as an input.
+        CSVError: If an input cannot be parsed.
+        MemoryError: If the right rows of one partition would exceed
+            ``max_memory``, as when one key has too many of them.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import join
+
+        await join("orders.csv", "customers.csv", on="customer_id", how="left", dst="out.csv")
+    """
+    ...
+
+def diff(
+    a: str,
+    b: str,
+    key: Union[str, List[str]],
+    changed_columns_only: bool = False,
+    dst: Optional[str] = None,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+    temp_dir: Optional[str] = None,
+) -> Coroutine[Any, Any, Dict[str, Any]]:
+    """Compare two CSV files by key columns.
+
+    Rows are matched on ``key``. Rows only in ``b`` are added, rows only in
+    ``a`` are removed, and matched rows with any differing field are changed.
+    Both files must have the same set of columns (in any order). Large inputs
+    are hash-partitioned into temporary files so memory stays bounded; in that
+    case differences are reported partition by partition rather than in file
+    order.
+
+    Args:
+        a: The "before" CSV file.
+        b: The "after" CSV file.
+        key: Column name or list of names identifying a row.
+        changed_columns_only: Limit changed rows to the columns that differ,
+            plus the key columns (default: False).
+        dst: Optional report CSV. When given, differences are streamed there
+            with a leading ``_change`` column (``added``, ``removed``, or a
+            ``before``/``after`` pair for changed rows) instead of returned.
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+        temp_dir: Existing directory to partition large inputs under
+            (default: the system's temporary directory). The partitions are
+            removed when the call ends, including when it fails or is
+            cancelled.
+
+    Returns:
+        Without ``dst``: a dict with ``added`` and ``removed`` lists of row
+        dicts, and a ``changed`` list of ``{"key", "before", "after"}``
+        dicts. With ``dst``: a dict of counts for each kind.
+
+    Raises:
+        ValueError: If the files have different columns, a key is missing,
+            ``temp_dir`` is not a directory, or ``dst`` is the same file as
+            an input.
+        CSVError: If an input cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import diff
+
+        result = await diff("yesterday.csv", "today.csv", key=["id"])
+        for change in result["changed"]:
+            print(change["key"], change["before"], change["after"])
+    """
+    ...
+
+def profile(
+    path: str,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+) -> Coroutine[Any, Any, Dict[str, Dict[str, Any]]]:
+    """Compute per-column statistics in one pass.
+
+    Empty fields count as nulls. A column is numeric when every non-null
+    value parses as a number. Distinct counts are exact up to 10,000 values
+    and a HyperLogLog estimate (about 1% error) beyond that.
+
+    Args:
+        path: Path to the CSV file (first row is the header).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+
+    Returns:
+        Dict mapping each column name to a dict with ``type`` (``"integer"``,
+        ``"float"``, ``"string"`` or ``"empty"``), ``count``, ``nulls``,
+        ``distinct``, ``min``, ``max``, ``mean`` (None for non-numeric
+        columns) and ``max_length``.
+
+    Raises:
+        CSVError: If the file cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import profile
+
+        stats = await profile("sales.csv")
+        print(stats["amount"]["mean"], stats["region"]["distinct"])
+    """
+    ...
+
+def validate(
+    path: str,
+    schema: Dict[str, Union[str, Dict[str, Any]]],
+    max_errors: int = 100,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+) -> Coroutine[Any, Any, Dict[str, Any]]:
+    """Validate a CSV file against a per-column schema.
+
+    Each schema value is either a type name or a rule dict with the keys
+    ``type`` (``"string"``, ``"integer"``, ``"float"`` or ``"boolean"``;
+    default ``"string"``), ``required`` (column must be present; default
+    True), ``nullable`` (empty values allowed; default True), ``pattern``
+    (regular expression the whole value must match), ``min`` and ``max``
+    (numeric bounds, or length bounds for string columns). Columns not in
+    the schema are ignored.
+
+    Args:
+        path: Path to the CSV file (first row is the header).
+        schema: Dict mapping column names to rules.
+        max_errors: Stop after this many violations (default: 100).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+
+    Returns:
+        Dict with ``valid``, ``rows_checked``, ``truncated`` (True if
+        scanning stopped at ``max_errors``) and ``errors``, a list of dicts
+        with ``row`` (1-based data row, None for a missing column),
+        ``column``, ``value`` and ``message``.
+
+    Raises:
+        ValueError: If the schema is invalid.
+        CSVError: If the file cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import validate
+
+        report = await validate("users.csv", {
+            "id": {"type": "integer", "min": 1, "nullable": False},
+            "email": {"pattern": r"[^@]+@[^@]+"},
+        })
+        if not report["valid"]:
+            print(report["errors"][0])
+    """
+    ...
+
+def lint(
+    path: str,
+    max_findings: int = 100,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+) -> Coroutine[Any, Any, Dict[str, Any]]:
+    """Check a CSV file for structural problems before loading it.
+
+    Reports ragged rows (a field count other than the header's), a quote
+    that is never closed, mixed line endings, NUL bytes, duplicate header
+    names, and values whose type (number, boolean or text) differs from
+    most of their column. Empty fields are not typed.
+
+    Args:
+        path: Path to the CSV file (first row is the header).
+        max_findings: Report at most this many findings (default: 100).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+
+    Returns:
+        Dict with ``clean``, ``rows_checked``, ``truncated`` (True if
+        findings beyond ``max_findings`` were dropped) and ``findings``, a
+        list of dicts in line order with ``kind`` (``"ragged_row"``,
+        ``"unbalanced_quote"``, ``"mixed_line_endings"``, ``"nul_byte"``,
+        ``"duplicate_header"`` or ``"inconsistent_type"``), ``row``
+        (1-based data row, None for file-wide problems and the header),
+        ``line`` (1-based), ``column`` (name, or None) and ``message``.
+
+    Raises:
+        IOError: If the file cannot be read.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import lint
+
+        report = await lint("export.csv")
+        for finding in report["findings"]:
+            print(finding["line"], finding["kind"], finding["message"])
+    """
+    ...
+
+def repair(
+    src: str,
+    dst: str,
+    fixes: Optional[Union[str, Sequence[str]]] = None,
+    max_changes: int = 100,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+) -> Coroutine[Any, Any, Dict[str, Any]]:
+    """Rewrite a broken CSV file, applying best-effort fixes.
+
+    ``"quotes"`` closes a quote left open at the end of the file at the end
+    of the line it opens on. ``"ragged"`` pads short rows with empty fields
+    and truncates long ones to the header's width. ``"encoding"`` replaces
+    invalid UTF-8 with U+FFFD. Problems a fix wasn't asked for are written
+    as parsed.
+
+    Args:
+        src: Path to the input CSV file (first row is the header).
+        dst: Path to the output CSV file (created or truncated).
+        fixes: Fix name or list of fix names (default: all three).
+        max_changes: List at most this many changes (default: 100).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+
+    Returns:
+        Dict with ``rows_written``, ``quotes_closed``, ``rows_padded``,
+        ``rows_truncated``, ``fields_reencoded``, ``truncated`` (True if
+        changes beyond ``max_changes`` weren't listed) and ``changes``, a
+        list of dicts with ``fix``, ``row`` (1-based data row, or None),
+        ``line`` (1-based line of ``src``) and ``message``.
+
+    Raises:
+        ValueError: If ``fixes`` names an unknown fix, or ``dst`` is the
+            same file as ``src``.
+        IOError: If a file cannot be read or written.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import repair
+
+        report = await repair("broken.csv", "fixed.csv", fixes=["ragged"])
+        print(report["rows_padded"], "rows padded")
+    """
+    ...
+
+def transform(
+    src: str,
+    dst: str,
+    fn: Callable[[Any], Any],
+    batch_size: Optional[int] = None,
+    header: bool = True,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+    progress: Optional[Callable[[int, Optional[int], int], Any]] = None,
+    progress_interval: Optional[int] = None,
+) -> Coroutine[Any, Any, int]:
+    """Stream a CSV file through a Python function.
+
+    Reading, parsing and writing run in Rust on a worker thread; the GIL is
+    only taken to call ``fn``. Values returned by ``fn`` that aren't strings
+    are converted with ``str()``, and None becomes an empty field.
+
+    Args:
+        src: Path to the input CSV file.
+        dst: Path to the output CSV file (created or truncated).
+        fn: Called with each data row (a list of strings) and returns the
+            new row, or None to drop it. With ``batch_size``, called with a
+            list of rows and returns a list of rows.
+        batch_size: Rows per call to ``fn`` (default: one row per call).
+        header: Copy the first row unchanged instead of passing it to
+            ``fn`` (default: True).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+        progress: Called as ``progress(bytes_read, bytes_total, rows_written)``
+            every ``progress_interval`` rows and once when done (default: None).
+        progress_interval: Rows between progress calls (default: 10000).
+
+    Returns:
+        Number of data rows written.
+
+    Raises:
+        TypeError: If ``fn`` is not callable.
+        ValueError: If ``dst`` is the same file as ``src``.
+        CSVError: If the input cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import transform
+
+        await transform("in.csv", "out.csv", lambda row: [v.upper() for v in row])
+
+        # Batched: fewer Python calls for large files
+        await transform(
+            "in.csv", "out.csv",
+            lambda rows: [r for r in rows if r[2] != "0"],
+            batch_size=1000,
+        )
+    """
+    ...
+
+def transpose(
+    src: str,
+    dst: str,
+    max_cells: Optional[int] = None,
+    delimiter: Optional[str] = None,
+    quotechar: Optional[str] = None,
+) -> Coroutine[Any, Any, int]:
+    """Swap the rows and columns of a CSV file.
+
+    Meant for the matrix-style files some instruments and spreadsheets emit,
+    with one record per variable. The whole file is buffered, so the number
+    of fields is capped by ``max_cells``. Rows shorter than the longest are
+    padded with empty fields.
+
+    Args:
+        src: Input CSV file (a header, if any, becomes the first column).
+        dst: Output CSV file (created or truncated).
+        max_cells: Most fields to buffer (default: 10000000).
+        delimiter: Field delimiter (default: ',').
+        quotechar: Quote character (default: '"').
+
+    Returns:
+        Number of rows written, one per input column.
+
+    Raises:
+        MemoryError: If ``src`` holds more than ``max_cells`` fields.
+        CSVError: If the input cannot be parsed.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv import transpose
+
+        await transpose("plate_reader.csv", "samples.csv", max_cells=100_000)
+    """
+    ...
+
+def io_backend() -> str:
+    """Report the IO backend used for local file reads and writes.
+
+    Returns ``"io_uring"`` when rapcsv was built with the ``io-uring``
+    feature (as the Linux wheels are) and the kernel supports it;
+    otherwise ``"tokio"``.
+    The fallback is automatic, so callers never need to check this.
+
+    Returns:
+        ``"io_uring"`` or ``"tokio"``.
+    """
+    ...
+
+def list_archive(path: str) -> Coroutine[Any, Any, List[str]]:
+    """List the files in a ZIP, tar, ``.tar.gz`` or ``.tgz`` archive.
+
+    Args:
+        path: Path to the archive; the kind is taken from the extension.
+
+    Returns:
+        Member names in archive order, directories left out. Any name can be
+        read with ``Reader(f"{path}::{name}")``.
+
+    Raises:
+        ValueError: If ``path`` does not name a supported archive.
+        IOError: If the archive cannot be read.
+    """
+    ...
+
+def index_cache_info() -> Dict[str, int]:
+    """Statistics of the record index shared by :meth:`Reader.seek`.
+
+    Returns:
+        Dict with ``entries`` (files indexed), ``records`` (record starts
+        held), ``bytes`` (memory they take, at most 64 MiB), ``hits`` and
+        ``misses`` (lookups that reused or built an index).
+    """
+    ...
+
+def clear_index_cache() -> None:
+    """Drop every cached record index and reset the counters."""
+    ...
+
+def row_hash(row: List[str]) -> str:
+    """Hash a row of strings the way ``Reader(row_hash=True)`` does.
+
+    The hash is the XXH3-64 digest of the length-prefixed fields, so it is
+    the same across runs, platforms and releases and can be stored to find
+    changed or repeated rows in a later load.
+
+    Args:
+        row: Fields of the row.
+
+    Returns:
+        The hash as 16 lowercase hex digits.
+    """
+    ...
+
+def set_allowed_root(root: Optional[Union[str, bytes, os.PathLike]]) -> None:
+    """Confine every path rapcsv opens to a directory, or lift the limit with None.
+
+    Every function and class that takes a path checks it when it is given:
+    relative paths, ``..`` components and symlinks are resolved the way the
+    OS will open them, and a path that resolves outside ``root`` raises
+    ``PermissionError``. Paths that don't exist yet are resolved through
+    their longest existing parent; dangling symlinks are refused.
+
+    Args:
+        root: An existing directory, or None.
+
+    Raises:
+        ValueError: If ``root`` does not exist or is not a directory.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        import rapcsv
+
+        rapcsv.set_allowed_root("/srv/uploads")
+        Reader("/srv/uploads/../secrets.csv")  # PermissionError
+    """
+    ...
+
+def get_allowed_root() -> Optional[str]:
+    """The resolved directory set by ``set_allowed_root()``, or None."""
+    ...
+
+def set_log_level(level: Optional[Union[int, str]]) -> None:
+    """Set the most verbose level of core spans and events sent to the ``rapcsv`` logger.
+
+    At ``"DEBUG"`` the ``open``, ``refill``, ``parse`` and ``flush`` spans are logged
+    with their fields and durations when they end. The default, ``"WARNING"``,
+    forwards retries only; ``None`` forwards nothing. The logger's own level and
+    handlers still decide what is shown.
+
+    Args:
+        level: A ``logging`` level number, a level name (``"TRACE"``, ``"DEBUG"``,
+            ``"INFO"``, ``"WARNING"``, ``"ERROR"``) or None.
+
+    Raises:
+        ValueError: If the level name is unknown.
+    """
     ...
@@ -0,0 +1,298 @@
+"""Blocking CSV reading and writing for code without an event loop.
+
+``rapcsv.sync.Reader`` and ``rapcsv.sync.Writer`` take the same options as
+:class:`rapcsv.Reader` and :class:`rapcsv.Writer` and wrap them, so parsing, quoting
+and buffering behave identically. Each call runs on a background event loop thread
+shared by all blocking readers and writers, and returns once it completes.
+
+Only file paths (a single path, a list or a glob for ``Reader``), descriptors
+(``from_fd()``) and TCP connections (``from_socket()``, ``to_socket()``) are
+accepted; async file handles belong to an event loop and should be used with the
+async classes.
+
+Example
+-------
+.. code-block:: python
+
+    from rapcsv.sync import Reader, Writer
+
+    with Writer("output.csv") as writer:
+        writer.write_row(["name", "age"])
+        writer.write_row(["Alice", "30"])
+
+    with Reader("output.csv") as reader:
+        for row in reader:
+            print(row)
+"""
+
+import asyncio
+import os
+import threading
+from typing import (
+    Any,
+    Awaitable,
+    Callable,
+    Dict,
+    Iterator,
+    List,
+    Optional,
+    Sequence,
+    TypeVar,
+    Union,
+)
+
+from rapcsv import Reader as _AsyncReader
+from rapcsv import Writer as _AsyncWriter
+
+T = TypeVar("T")
+
+_loop: Optional[asyncio.AbstractEventLoop] = None
+_loop_lock = threading.Lock()
+
+
+def _event_loop() -> asyncio.AbstractEventLoop:
+    """Return the background event loop, starting its thread on first use."""
+    global _loop
+    with _loop_lock:
+        if _loop is None:
+            loop = asyncio.new_event_loop()
+            thread = threading.Thread(target=loop.run_forever, name="rapcsv-sync", daemon=True)
+            thread.start()
+            _loop = loop
+        return _loop
+
+
+def _run(call: Callable[[], Awaitable[T]]) -> T:
+    """Run ``call()`` on the background loop and block until it finishes.
+
+    The call is cancelled if waiting is interrupted (e.g. by ``KeyboardInterrupt``),
+    with the same effect as cancelling the awaiting task in async code.
+    """
+
+    async def _await() -> T:
+        return await call()
+
+    future = asyncio.run_coroutine_threadsafe(_await(), _event_loop())
+    try:
+        return future.result()
+    except BaseException:
+        future.cancel()
+        raise
+
+
+class Reader:
+    """Blocking CSV reader.
+
+    Accepts the same arguments as :class:`rapcsv.Reader`. Iterating yields rows until
+    EOF.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv.sync import Reader
+
+        reader = Reader("data.csv")
+        header = reader.read_row()
+        rows = reader.read_all()
+    """
+
+    def __init__(self, path: Any, **kwargs: Any) -> None:
+        if not isinstance(path, (str, bytes, os.PathLike, list, tuple)):
+            raise TypeError("rapcsv.sync.Reader requires a file path, list of paths or glob")
+        self._reader = _AsyncReader(path, **kwargs)
+
+    @classmethod
+    def from_fd(cls, fd: int, **kwargs: Any) -> "Reader":
+        """Read from an open file descriptor, as :meth:`rapcsv.Reader.from_fd`."""
+        reader = cls.__new__(cls)
+        reader._reader = _AsyncReader.from_fd(fd, **kwargs)
+        return reader
+
+    @classmethod
+    def from_socket(cls, host: str, port: int, **kwargs: Any) -> "Reader":
+        """Read from a TCP connection, as :meth:`rapcsv.Reader.from_socket`."""
+        reader = cls.__new__(cls)
+        reader._reader = _AsyncReader.from_socket(host, port, **kwargs)
+        return reader
+
+    @classmethod
+    def _wrap(cls, async_reader: _AsyncReader) -> "Reader":
+        """Wrap an async reader, such as a part from :meth:`split`."""
+        reader = cls.__new__(cls)
+        reader._reader = async_reader
+        return reader
+
+    def open(self) -> None:
+        """Open the reader ahead of the first read, as :meth:`rapcsv.Reader.open`."""
+        _run(self._reader.open)
+
+    def dialect(self) -> Dict[str, Any]:
+        """Report the format read with and found, as :meth:`rapcsv.Reader.dialect`."""
+        return _run(self._reader.dialect)
+
+    def read_row(self) -> Optional[List[Any]]:
+        """Read the next row; at EOF, an empty list, ``None`` or ``EOFError`` per ``eof``."""
+        return _run(self._reader.read_row)
+
+    def read_rows(self, n: int) -> List[List[Any]]:
+        """Read up to ``n`` rows."""
+        return _run(lambda: self._reader.read_rows(n))
+
+    def read_columns(self, batch_size: Optional[int] = None) -> Dict[Union[str, int], List[Any]]:
+        """Read rows as columns, as :meth:`rapcsv.Reader.read_columns`."""
+        return _run(lambda: self._reader.read_columns(batch_size))
+
+    def read_raw_lines(self, n: int) -> List[bytes]:
+        """Read up to ``n`` records as bytes, without parsing their fields."""
+        return _run(lambda: self._reader.read_raw_lines(n))
+
+    def read_all(self, threads: Optional[int] = None) -> List[List[Any]]:
+        """Read all remaining rows."""
+        return _run(lambda: self._reader.read_all(threads))
+
+    def skip_rows(self, n: int) -> None:
+        """Skip ``n`` rows without returning them."""
+        _run(lambda: self._reader.skip_rows(n))
+
+    def state(self) -> Dict[str, Any]:
+        """Save the reader's position, as :meth:`rapcsv.Reader.state`."""
+        return self._reader.state()
+
+    @classmethod
+    def from_state(cls, state: Dict[str, Any], **kwargs: Any) -> "Reader":
+        """Continue reading where :meth:`state` left off."""
+        reader = cls.__new__(cls)
+        reader._reader = _AsyncReader.from_state(state, **kwargs)
+        return reader
+
+    def split(self, n: int, **kwargs: Any) -> List["Reader"]:
+        """Split an unread file into readers over disjoint ranges of it.
+
+        Each part is a blocking reader, as :meth:`rapcsv.Reader.split` returns async
+        ones; parts can be read from separate threads.
+        """
+        parts = _run(lambda: self._reader.split(n, **kwargs))
+        return [Reader._wrap(part) for part in parts]
+
+    def seek(self, row_index: int) -> None:
+        """Move to a record, counted from 0 with the header included."""
+        _run(lambda: self._reader.seek(row_index))
+
+    def checkpoint(self) -> None:
+        """Save the position to ``checkpoint_path`` now."""
+        self._reader.checkpoint()
+
+    @property
+    def line_num(self) -> int:
+        """Line number of the last row read (1-based)."""
+        return self._reader.line_num
+
+    @property
+    def byte_offset(self) -> Optional[int]:
+        """Byte offset in the current file where the next row starts."""
+        return self._reader.byte_offset
+
+    @property
+    def utf8_replacements(self) -> int:
+        """Invalid UTF-8 sequences replaced so far, as for the async reader."""
+        return self._reader.utf8_replacements
+
+    def stats(self) -> Dict[str, Any]:
+        """Throughput counters, as for :meth:`rapcsv.Reader.stats`."""
+        return self._reader.stats()
+
+    def close(self) -> None:
+        """Close the file and release any lock held on it."""
+        _run(lambda: self._reader.__aexit__(None, None, None))
+
+    def __iter__(self) -> Iterator[List[Any]]:
+        return self
+
+    def __next__(self) -> List[Any]:
+        try:
+            return _run(self._reader.__anext__)
+        except StopAsyncIteration:
+            raise StopIteration from None
+
+    def __enter__(self) -> "Reader":
+        return self
+
+    def __exit__(self, exc_type: Any, exc_val: Any, exc_tb: Any) -> None:
+        self.close()
+
+
+class Writer:
+    """Blocking CSV writer.
+
+    Accepts the same arguments as :class:`rapcsv.Writer`. Rows are buffered the same
+    way, so call :meth:`close` (or use ``with``) to make sure they reach the file.
+
+    Examples
+    --------
+    .. code-block:: python
+
+        from rapcsv.sync import Writer
+
+        writer = Writer("output.csv")
+        writer.writerows([["name", "age"], ["Alice", "30"]])
+        writer.close()
+    """
+
+    def __init__(self, path: Union[str, bytes, "os.PathLike[str]"], **kwargs: Any) -> None:
+        if not isinstance(path, (str, bytes, os.PathLike)):
+            raise TypeError("rapcsv.sync.Writer requires a file path")
+        self._writer = _AsyncWriter(path, **kwargs)
+
+    @classmethod
+    def from_fd(cls, fd: int, **kwargs: Any) -> "Writer":
+        """Write to an open file descriptor, as :meth:`rapcsv.Writer.from_fd`."""
+        writer = cls.__new__(cls)
+        writer._writer = _AsyncWriter.from_fd(fd, **kwargs)
+        return writer
+
+    @classmethod
+    def to_socket(cls, host: str, port: int, **kwargs: Any) -> "Writer":
+        """Write to a TCP connection, as :meth:`rapcsv.Writer.to_socket`."""
+        writer = cls.__new__(cls)
+        writer._writer = _AsyncWriter.to_socket(host, port, **kwargs)
+        return writer
+
+    def write_row(self, row: List[Any]) -> None:
+        """Write one row."""
+        _run(lambda: self._writer.write_row(row))
+
+    def writerows(self, rows: List[List[Any]]) -> None:
+        """Write several rows."""
+        _run(lambda: self._writer.writerows(rows))
+
+    def write_columns(
+        self, columns: Union[Dict[str, Sequence[Any]], Sequence[Sequence[Any]]]
+    ) -> None:
+        """Write columns as rows, as :meth:`rapcsv.Writer.write_columns`."""
+        _run(lambda: self._writer.write_columns(columns))
+
+    def write_raw(self, data: bytes) -> None:
+        """Write CSV bytes as they are, as :meth:`rapcsv.Writer.write_raw`."""
+        _run(lambda: self._writer.write_raw(data))
+
+    def flush(self) -> None:
+        """Write out buffered rows and flush the file."""
+        _run(self._writer.flush)
+
+    def stats(self) -> Dict[str, Any]:
+        """Throughput counters, as for :meth:`rapcsv.Writer.stats`."""
+        return self._writer.stats()
+
+    def close(self) -> None:
+        """Write out buffered rows and close the file."""
+        _run(self._writer.close)
+
+    def __enter__(self) -> "Writer":
+        return self
+
+    def __exit__(self, exc_type: Any, exc_val: Any, exc_tb: Any) -> None:
+        self.close()
+
+
+__all__: List[str] = ["Reader", "Writer"]
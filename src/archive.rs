@@ -0,0 +1,301 @@
+//! CSV members of ZIP and tar archives (`"archive.zip::member.csv"` or `zip_member=`).
+//!
+//! A member being read is decompressed on its own thread into a pipe (see
+//! [`crate::pipe`]), so nothing is extracted to disk and a corrupt archive fails the
+//! read instead of looking like a short file. Writing works the other way round: the
+//! `Writer` writes into a pipe and a thread compresses what comes out of it into the
+//! archive, finishing the archive once the writer closes. Only ZIP archives are
+//! written; tar archives, plain or gzip-compressed, are read by scanning to the member.
+
+use crate::errors::CsvIoError;
+use crate::ops::spawn_blocking_py;
+use crate::paths::{self, FilePath};
+use crate::pipe::{spawn_reader, spawn_writer, PipeSink, PipeSource};
+use crate::validate_path;
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use flate2::read::MultiGzDecoder;
+use pyo3::prelude::*;
+use std::io::{BufReader, Read};
+use tokio::fs::File;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Separator between an archive path and a member name.
+const MEMBER_SEPARATOR: &str = "::";
+
+/// Archive formats, told apart by file extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    fn of(path: &str) -> Option<Self> {
+        let path = path.to_ascii_lowercase();
+        if path.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else if path.ends_with(".tar") {
+            Some(ArchiveKind::Tar)
+        } else if path.ends_with(".tar.gz") || path.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// The archive and member a `Reader` path names: `"archive.zip::member.csv"`, a path
+/// plus `zip_member`, or an archive path on its own for an archive holding one file
+/// (member name left empty). Returns `None` for ordinary files.
+pub(crate) fn member_of(
+    path: &FilePath,
+    zip_member: Option<&str>,
+) -> PyResult<Option<(FilePath, String)>> {
+    let split = path
+        .split_once(MEMBER_SEPARATOR)
+        .filter(|(archive, _)| ArchiveKind::of(archive).is_some());
+    match (split, zip_member) {
+        (Some(_), Some(_)) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "name the archive member in the path or with zip_member, not both",
+        )),
+        (Some((archive, member)), None) => Ok(Some((archive, member.to_string()))),
+        (None, Some(member)) => Ok(Some((path.clone(), member.to_string()))),
+        (None, None) if ArchiveKind::of(path).is_some() => Ok(Some((path.clone(), String::new()))),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Index of the only file in `zip`, for archives opened without a member name.
+fn only_file<R: Read + std::io::Seek>(zip: &ZipArchive<R>, archive: &str) -> PyResult<usize> {
+    let files: Vec<&str> = zip
+        .file_names()
+        .filter(|name| !name.ends_with('/'))
+        .collect();
+    match files.as_slice() {
+        [name] => Ok(zip.index_for_name(name).unwrap_or_default()),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "archive {archive} holds {} files; name one as '{archive}::member' or with zip_member",
+            files.len()
+        ))),
+    }
+}
+
+fn open_archive(archive: &FilePath) -> PyResult<std::fs::File> {
+    std::fs::File::open(paths::native(archive))
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file {archive}: {e}")))
+}
+
+/// Start decompressing `member` of the archive at `archive` (the only file of a ZIP
+/// archive when `member` is empty). Returns the file to read the member's bytes from.
+pub(crate) fn open_member(archive: &FilePath, member: &str) -> PyResult<(File, PipeSource)> {
+    match ArchiveKind::of(archive) {
+        Some(ArchiveKind::Zip) | None => open_zip_member(archive, member),
+        Some(kind) => open_tar_member(archive, member, kind),
+    }
+}
+
+fn open_zip_member(archive: &FilePath, member: &str) -> PyResult<(File, PipeSource)> {
+    let mut zip = ZipArchive::new(open_archive(archive)?)
+        .map_err(|e| CsvIoError::new_err(format!("Failed to read archive {archive}: {e}")))?;
+    let index = match member {
+        "" => only_file(&zip, archive)?,
+        member => zip.index_for_name(member).ok_or_else(|| {
+            CsvIoError::new_err(format!("No member '{member}' in archive {archive}"))
+        })?,
+    };
+    let name = zip.name_for_index(index).unwrap_or(member);
+    let location = format!("{archive}{MEMBER_SEPARATOR}{name}");
+    spawn_reader(move |writer| {
+        zip.by_index(index)
+            .map_err(std::io::Error::from)
+            .and_then(|mut entry| std::io::copy(&mut entry, writer))
+            .map(|_| ())
+            .map_err(|e| format!("Failed to read file {location}: {e}"))
+    })
+}
+
+/// A tar archive read from the start, through gzip for `.tar.gz`.
+fn tar_archive(file: std::fs::File, kind: ArchiveKind) -> tar::Archive<Box<dyn Read + Send>> {
+    let file = BufReader::new(file);
+    let reader: Box<dyn Read + Send> = match kind {
+        ArchiveKind::TarGz => Box::new(MultiGzDecoder::new(file)),
+        _ => Box::new(file),
+    };
+    tar::Archive::new(reader)
+}
+
+/// Name of a tar entry as listed by `list_archive()`, without a leading "./".
+fn tar_name<R: Read>(entry: &tar::Entry<'_, R>) -> std::io::Result<String> {
+    let path = entry.path()?;
+    Ok(path.to_string_lossy().trim_start_matches("./").to_string())
+}
+
+/// Tar archives have no index, so the member is found by scanning on the thread; a
+/// missing member is reported by the first read. Names match with or without "./".
+fn open_tar_member(
+    archive: &FilePath,
+    member: &str,
+    kind: ArchiveKind,
+) -> PyResult<(File, PipeSource)> {
+    if member.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "name the member of tar archive {archive} as '{archive}::member' or with zip_member"
+        )));
+    }
+    let mut tar = tar_archive(open_archive(archive)?, kind);
+    let (archive, member) = (archive.to_string(), member.to_string());
+    let wanted = member.trim_start_matches("./").to_string();
+    spawn_reader(move |writer| {
+        let location = format!("{archive}{MEMBER_SEPARATOR}{member}");
+        let error = |e: std::io::Error| format!("Failed to read file {location}: {e}");
+        for entry in tar.entries().map_err(error)? {
+            let mut entry = entry.map_err(error)?;
+            if entry.header().entry_type().is_file() && tar_name(&entry).map_err(error)? == wanted {
+                std::io::copy(&mut entry, writer).map_err(error)?;
+                return Ok(());
+            }
+        }
+        Err(format!("No member '{member}' in archive {archive}"))
+    })
+}
+
+/// Names of the files in the archive at `path`, in archive order.
+fn member_names(path: &FilePath, kind: ArchiveKind) -> PyResult<Vec<String>> {
+    let file = open_archive(path)?;
+    let error = |e: &dyn std::fmt::Display| {
+        CsvIoError::new_err(format!("Failed to read archive {path}: {e}"))
+    };
+    if kind == ArchiveKind::Zip {
+        let zip = ZipArchive::new(file).map_err(|e| error(&e))?;
+        return Ok((0..zip.len())
+            .filter_map(|i| zip.name_for_index(i))
+            .filter(|name| !name.ends_with('/'))
+            .map(String::from)
+            .collect());
+    }
+    let mut tar = tar_archive(file, kind);
+    let mut names = Vec::new();
+    for entry in tar.entries().map_err(|e| error(&e))? {
+        let entry = entry.map_err(|e| error(&e))?;
+        if entry.header().entry_type().is_file() {
+            names.push(tar_name(&entry).map_err(|e| error(&e))?);
+        }
+    }
+    Ok(names)
+}
+
+/// List the files in a ZIP, tar or gzip-compressed tar archive.
+///
+/// Returns the member names, in archive order and without directories, as accepted by
+/// `Reader("archive.zip::member")` and `zip_member=`. The archive type is taken from
+/// the extension: `.zip`, `.tar`, `.tar.gz` or `.tgz`.
+///
+/// # Example
+///
+/// ```python
+/// for name in await rapcsv.list_archive("bundle.tar.gz"):
+///     if name.endswith(".csv"):
+///         rows = await Reader(f"bundle.tar.gz::{name}").read_all()
+/// ```
+#[pyfunction]
+pub(crate) fn list_archive(py: Python<'_>, path: String) -> PyResult<Py<PyAny>> {
+    let path = FilePath::from(path);
+    validate_path(&path)?;
+    let kind = ArchiveKind::of(&path).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{path} is not a .zip, .tar, .tar.gz or .tgz archive"
+        ))
+    })?;
+    spawn_blocking_py(py, move || member_names(&path, kind))
+}
+
+/// The archive and member a `Writer` path names, as for reading. A `.zip` path on its
+/// own gets a member named after it: `export.zip` holds `export.csv`.
+pub(crate) fn writer_member_of(
+    path: &FilePath,
+    zip_member: Option<&str>,
+) -> PyResult<Option<(FilePath, String)>> {
+    let Some((archive, member)) = member_of(path, zip_member)? else {
+        return Ok(None);
+    };
+    if ArchiveKind::of(&archive) != Some(ArchiveKind::Zip) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "only ZIP archives can be written, not {archive}"
+        )));
+    }
+    if !member.is_empty() {
+        return Ok(Some((archive, member)));
+    }
+    let native = paths::native(&archive);
+    let stem = native
+        .file_stem()
+        .map_or("data".into(), |stem| stem.to_string_lossy());
+    let member = format!("{stem}.csv");
+    Ok(Some((archive, member)))
+}
+
+/// Deflate at the default level, with ZIP64 so members over 4 GiB can be streamed
+/// without knowing their size up front, stamped with the current time.
+fn member_options() -> SimpleFileOptions {
+    let now = DateTime::<Utc>::from(std::time::SystemTime::now());
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true)
+        .unix_permissions(0o644);
+    match zip::DateTime::from_date_and_time(
+        now.year() as u16,
+        now.month() as u8,
+        now.day() as u8,
+        now.hour() as u8,
+        now.minute() as u8,
+        now.second() as u8,
+    ) {
+        Ok(time) => options.last_modified_time(time),
+        Err(_) => options,
+    }
+}
+
+/// Start `member` in the ZIP file at `archive`, adding it to the archive if the file
+/// already holds one. Returns the file to write the member's bytes to.
+pub(crate) fn create_member(archive: &FilePath, member: &str) -> PyResult<(File, PipeSink)> {
+    let io_error = |e: &dyn std::fmt::Display| {
+        CsvIoError::new_err(format!("Failed to open file {archive}: {e}"))
+    };
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(paths::native(archive))
+        .map_err(|e| io_error(&e))?;
+    let existing = file.metadata().map_err(|e| io_error(&e))?.len() > 0;
+    // Check before opening for writing, which rewrites the directory when dropped
+    if existing
+        && ZipArchive::new(&file)
+            .map_err(|e| io_error(&e))?
+            .index_for_name(member)
+            .is_some()
+    {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "archive {archive} already has a member '{member}'"
+        )));
+    }
+    let mut zip = if existing {
+        ZipWriter::new_append(file).map_err(|e| io_error(&e))?
+    } else {
+        ZipWriter::new(file)
+    };
+    zip.start_file(member, member_options()).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Cannot add '{member}' to archive {archive}: {e}"
+        ))
+    })?;
+    let location = format!("{archive}{MEMBER_SEPARATOR}{member}");
+    spawn_writer(move |reader| {
+        std::io::copy(reader, &mut zip)
+            .and_then(|_| zip.finish().map(|_| ()).map_err(std::io::Error::from))
+            .map_err(|e| format!("Failed to write file {location}: {e}"))
+    })
+}
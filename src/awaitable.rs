@@ -0,0 +1,65 @@
+//! Awaitables for whichever async library the caller runs.
+//!
+//! Under asyncio (including anyio's asyncio backend) futures are handed to
+//! pyo3-async-runtimes as before. Without a running asyncio loop the future is spawned
+//! on the tokio runtime and `rapcsv._compat.foreign_awaitable` wraps it for the
+//! running library, currently trio (including anyio's trio backend). The result is
+//! delivered through a callback from the tokio thread, and cancelling the awaiting
+//! task aborts the tokio task.
+
+use pyo3::conversion::IntoPyObjectExt;
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyTuple};
+use std::future::Future;
+use std::sync::{Arc, Mutex as StdMutex};
+
+/// Convert a Rust future into an awaitable for the running async library.
+pub(crate) fn future_into_py<F, T>(py: Python<'_>, fut: F) -> PyResult<Bound<'_, PyAny>>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: for<'py> IntoPyObject<'py> + Send + 'static,
+{
+    let asyncio = py.import("asyncio")?;
+    if asyncio.call_method0("get_running_loop").is_ok() {
+        return pyo3_async_runtimes::tokio::future_into_py(py, fut);
+    }
+
+    // Started by the Python side once it has somewhere to deliver the result
+    let pending = Arc::new(StdMutex::new(Some(fut)));
+    let start = PyCFunction::new_closure(
+        py,
+        None,
+        None,
+        move |args: &Bound<'_, PyTuple>, _kwargs| -> PyResult<Py<PyAny>> {
+            let py = args.py();
+            let done = args.get_item(0)?.unbind();
+            let fut = pending
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take()
+                .ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("awaitable already started")
+                })?;
+            let task = pyo3_async_runtimes::tokio::get_runtime().spawn(async move {
+                let result = fut.await;
+                Python::attach(|py| {
+                    let (value, error) = match result.and_then(|v| v.into_py_any(py)) {
+                        Ok(value) => (value, py.None()),
+                        Err(e) => (py.None(), e.into_value(py).into_any()),
+                    };
+                    if let Err(e) = done.call1(py, (value, error)) {
+                        e.write_unraisable(py, None);
+                    }
+                });
+            });
+            let abort = task.abort_handle();
+            let cancel = PyCFunction::new_closure(py, None, None, move |_args, _kwargs| {
+                abort.abort();
+            })?;
+            Ok(cancel.into_any().unbind())
+        },
+    )?;
+    py.import("rapcsv._compat")?
+        .getattr("foreign_awaitable")?
+        .call1((start,))
+}
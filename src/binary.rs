@@ -0,0 +1,79 @@
+//! Control characters in fields (`binary=` on `Reader`).
+//!
+//! NUL bytes and other C0 control characters (besides tab and line breaks) and DEL
+//! turn up in fields of files cut from binary dumps or written by broken exporters,
+//! and most downstream systems reject them or truncate at a NUL. With a policy they
+//! are caught or cleaned as each row is finished, after parsing, so the dialect's
+//! delimiter and terminator may still be control characters themselves.
+
+use crate::errors::{CsvParseError, ErrorContext};
+use pyo3::prelude::*;
+
+/// What to do with a control character in a field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BinaryPolicy {
+    /// Fail the read with `CsvParseError`.
+    Error,
+    /// Remove the character.
+    Strip,
+    /// Replace the character with U+FFFD.
+    Replace,
+}
+
+/// Whether `b` is a control character that has no place in a text field. Bytes of
+/// multi-byte characters are never below 0x80, so fields can be checked bytewise.
+fn is_junk(b: u8) -> bool {
+    matches!(b, 0x00..=0x08 | 0x0b | 0x0c | 0x0e..=0x1f | 0x7f)
+}
+
+impl BinaryPolicy {
+    pub(crate) fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "error" => Ok(BinaryPolicy::Error),
+            "strip" => Ok(BinaryPolicy::Strip),
+            "replace" => Ok(BinaryPolicy::Replace),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "binary must be 'error', 'strip' or 'replace', got '{other}'"
+            ))),
+        }
+    }
+
+    /// Apply the policy to the fields of `row`, record `position` of the stream, read
+    /// from the file at `path`.
+    pub(crate) fn apply(self, row: &mut [String], position: usize, path: &str) -> PyResult<()> {
+        for (column, field) in row.iter_mut().enumerate() {
+            let Some(at) = field.bytes().position(is_junk) else {
+                continue;
+            };
+            match self {
+                BinaryPolicy::Error => {
+                    let context = ErrorContext {
+                        row: Some(position),
+                        column: Some(column),
+                        field: Some(field.clone()),
+                        ..ErrorContext::default()
+                    };
+                    return Err(context.attach(CsvParseError::new_err(format!(
+                        "Control character U+{:04X} in field {column} of row {position} \
+                         (0-indexed) in file '{path}'",
+                        field.as_bytes()[at]
+                    ))));
+                }
+                BinaryPolicy::Strip => field.retain(|c| !(c.is_ascii() && is_junk(c as u8))),
+                BinaryPolicy::Replace => {
+                    *field = field
+                        .chars()
+                        .map(|c| {
+                            if c.is_ascii() && is_junk(c as u8) {
+                                char::REPLACEMENT_CHARACTER
+                            } else {
+                                c
+                            }
+                        })
+                        .collect();
+                }
+            }
+        }
+        Ok(())
+    }
+}
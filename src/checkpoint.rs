@@ -0,0 +1,236 @@
+//! Saved reader positions: `Reader.state()`/`from_state()` and `checkpoint_path=`.
+//!
+//! A position is a small JSON object naming the files, the byte offset of the next
+//! record and the counters, plus the dialect so the reader can be rebuilt. A
+//! checkpoint file holds the same object. It is rewritten at the start of a read once
+//! `checkpoint_interval` rows have been returned since the last save, so it only ever
+//! covers rows the caller has moved past: after a crash, rows are read again rather
+//! than skipped (at-least-once processing).
+//!
+//! Since a read that finds EOF saves too, a job that reads a growing file with the
+//! same `checkpoint_path` on every run gets only the rows appended since the last
+//! run. The saved header is compared with the file's when resuming, so a file
+//! replaced by one with other columns raises `FileChangedError` instead.
+
+use crate::errors::CsvIoError;
+use crate::paths::{self, FilePath};
+use crate::stream::{RecordStream, StreamState};
+use pyo3::prelude::*;
+use serde_json::{json, Map, Value};
+use std::io::ErrorKind;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Format of saved positions.
+pub(crate) const STATE_VERSION: u64 = 1;
+
+/// Rows returned between checkpoint saves unless `checkpoint_interval` says otherwise.
+pub(crate) const DEFAULT_CHECKPOINT_INTERVAL: usize = 10_000;
+
+/// A position read back from `Reader.from_state()` or a checkpoint file.
+pub(crate) struct SavedState {
+    pub(crate) stream: StreamState,
+    pub(crate) dialect: Map<String, Value>, // Constructor keyword arguments
+    pub(crate) header: Option<Vec<String>>, // Header consumed by `row_type="row"`
+}
+
+/// Build the JSON form of a position.
+pub(crate) fn state_to_json(
+    state: StreamState,
+    dialect: &Value,
+    header: Option<Vec<String>>,
+) -> Value {
+    json!({
+        "version": STATE_VERSION,
+        "paths": state.files.iter().map(|path| &**path).collect::<Vec<&str>>(),
+        "file_index": state.file_index,
+        "byte_offset": state.offset,
+        "row_index": state.position,
+        "line_num": state.line_num,
+        "has_header": state.has_header,
+        "file_header": state.header,
+        "byte_end": state.end,
+        "dialect": dialect,
+        "header": header,
+    })
+}
+
+fn invalid(message: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(message)
+}
+
+/// Parse the JSON form of a position.
+pub(crate) fn state_from_json(value: &Value) -> PyResult<SavedState> {
+    let item = |key: &str| {
+        value
+            .get(key)
+            .ok_or_else(|| invalid(format!("reader state is missing '{key}'")))
+    };
+    let bad = |key: &str| invalid(format!("reader state has an invalid '{key}'"));
+    let number = |key: &str| item(key)?.as_u64().ok_or_else(|| bad(key));
+
+    let version = number("version")?;
+    if version != STATE_VERSION {
+        return Err(invalid(format!(
+            "unsupported reader state version {version}"
+        )));
+    }
+    let strings = |key: &str, value: &Value| -> PyResult<Vec<String>> {
+        value
+            .as_array()
+            .and_then(|items| {
+                items
+                    .iter()
+                    .map(|item| item.as_str().map(String::from))
+                    .collect()
+            })
+            .ok_or_else(|| bad(key))
+    };
+    let header = match item("header")? {
+        Value::Null => None,
+        names => Some(strings("header", names)?),
+    };
+    Ok(SavedState {
+        stream: StreamState {
+            files: strings("paths", item("paths")?)?
+                .into_iter()
+                .map(FilePath::from)
+                .collect(),
+            file_index: number("file_index")? as usize,
+            offset: number("byte_offset")?,
+            position: number("row_index")? as usize,
+            line_num: number("line_num")? as usize,
+            has_header: item("has_header")?
+                .as_bool()
+                .ok_or_else(|| bad("has_header"))?,
+            // Absent from states saved by earlier versions, which aren't checked
+            header: match value.get("file_header") {
+                None | Some(Value::Null) => None,
+                Some(names) => Some(strings("file_header", names)?),
+            },
+            end: match value.get("byte_end") {
+                None | Some(Value::Null) => None,
+                Some(end) => Some(end.as_u64().ok_or_else(|| bad("byte_end"))?),
+            },
+        },
+        dialect: item("dialect")?
+            .as_object()
+            .cloned()
+            .ok_or_else(|| bad("dialect"))?,
+        header,
+    })
+}
+
+/// Position persisted to a sidecar file while a reader runs (`checkpoint_path=`).
+pub(crate) struct Checkpoint {
+    path: FilePath,
+    interval: usize,
+    dialect: Value,
+    rows: AtomicUsize, // Rows returned since the last save
+}
+
+impl Checkpoint {
+    pub(crate) fn new(path: FilePath, interval: usize, dialect: Value) -> Self {
+        Checkpoint {
+            path,
+            interval,
+            dialect,
+            rows: AtomicUsize::new(0),
+        }
+    }
+
+    /// Read the saved position, or `None` if no checkpoint has been written yet.
+    pub(crate) fn load(&self) -> PyResult<Option<SavedState>> {
+        let text = match std::fs::read_to_string(paths::native(&self.path)) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(CsvIoError::new_err(format!(
+                    "Failed to read checkpoint {}: {e}",
+                    self.path
+                )))
+            }
+        };
+        let value: Value = serde_json::from_str(&text)
+            .map_err(|e| invalid(format!("checkpoint {} is not valid JSON: {e}", self.path)))?;
+        state_from_json(&value).map(Some)
+    }
+
+    /// Count `rows` just read for the caller. A read that found nothing but EOF saves,
+    /// since every row returned before it has been moved past.
+    pub(crate) async fn after_read(
+        &self,
+        stream: &RecordStream,
+        rows: usize,
+        header: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        self.rows.fetch_add(rows, Ordering::Relaxed);
+        if rows == 0 {
+            self.save(stream, header).await?;
+        }
+        Ok(())
+    }
+
+    /// Save before a read once `interval` rows have been returned since the last save.
+    pub(crate) async fn before_read(
+        &self,
+        stream: &RecordStream,
+        header: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        if self.rows.load(Ordering::Relaxed) >= self.interval {
+            self.save(stream, header).await?;
+        }
+        Ok(())
+    }
+
+    /// The checkpoint contents, or `None` while rows of a cancelled read are pending.
+    fn contents(
+        &self,
+        stream: &RecordStream,
+        header: Option<Vec<String>>,
+    ) -> PyResult<Option<String>> {
+        if stream.has_unread() {
+            return Ok(None);
+        }
+        let state = state_to_json(stream.snapshot()?, &self.dialect, header);
+        self.rows.store(0, Ordering::Relaxed);
+        Ok(Some(state.to_string()))
+    }
+
+    fn write_error(&self, e: std::io::Error) -> PyErr {
+        CsvIoError::new_err(format!("Failed to write checkpoint {}: {e}", self.path))
+    }
+
+    /// Write the current position, replacing the file atomically.
+    pub(crate) async fn save(
+        &self,
+        stream: &RecordStream,
+        header: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let Some(contents) = self.contents(stream, header)? else {
+            return Ok(());
+        };
+        let temp = paths::native(&self.path.with_suffix(".tmp"));
+        tokio::fs::write(&temp, contents)
+            .await
+            .map_err(|e| self.write_error(e))?;
+        tokio::fs::rename(&temp, paths::native(&self.path))
+            .await
+            .map_err(|e| self.write_error(e))
+    }
+
+    /// `save` for `Reader.checkpoint()`, called outside a read.
+    pub(crate) fn save_blocking(
+        &self,
+        stream: &RecordStream,
+        header: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let Some(contents) = self.contents(stream, header)? else {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "checkpoint() is unavailable until rows kept from a cancelled read are read",
+            ));
+        };
+        let temp = paths::native(&self.path.with_suffix(".tmp"));
+        std::fs::write(&temp, contents).map_err(|e| self.write_error(e))?;
+        std::fs::rename(&temp, paths::native(&self.path)).map_err(|e| self.write_error(e))
+    }
+}
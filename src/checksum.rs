@@ -0,0 +1,241 @@
+//! File checksums (`checksum=` on `Writer`, `verify_checksum=` on `Reader`).
+//!
+//! A writer with `checksum="sha256"` hashes the finished file on `close()` and writes
+//! the digest next to it as `<path>.sha256`, in the format `sha256sum` reads, so
+//! `sha256sum -c data.csv.sha256` checks it as well. A reader with `verify_checksum`
+//! hashes each file before parsing it and raises `ChecksumError` on a mismatch, so no
+//! row of a corrupt file is ever returned.
+
+use crate::errors::{ChecksumError, CsvIoError};
+use crate::paths::{self, FilePath};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyString};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::HashSet;
+use std::io::Read;
+use std::sync::{Arc, Mutex as StdMutex};
+
+/// Hash algorithm of a checksum, named like its sidecar file's extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Algorithm {
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    const ALL: [Algorithm; 2] = [Algorithm::Sha256, Algorithm::Sha512];
+
+    pub(crate) fn parse(name: &str) -> PyResult<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sha256" => Ok(Algorithm::Sha256),
+            "sha512" => Ok(Algorithm::Sha512),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "checksum must be 'sha256' or 'sha512', got '{name}'"
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// The algorithm whose hex digests have the length of `digest`.
+    fn of_digest(digest: &str) -> Option<Self> {
+        if !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        match digest.len() {
+            64 => Some(Algorithm::Sha256),
+            128 => Some(Algorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    /// Lowercase hex digest of the file at `path`, read in chunks.
+    fn digest_file(self, path: &FilePath) -> std::io::Result<String> {
+        fn hash<D: Digest>(mut file: std::fs::File) -> std::io::Result<String> {
+            let mut hasher = D::new();
+            let mut chunk = vec![0u8; 64 * 1024];
+            loop {
+                match file.read(&mut chunk)? {
+                    0 => break,
+                    n => hasher.update(&chunk[..n]),
+                }
+            }
+            Ok(hasher
+                .finalize()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect())
+        }
+        let file = std::fs::File::open(paths::native(path))?;
+        match self {
+            Algorithm::Sha256 => hash::<Sha256>(file),
+            Algorithm::Sha512 => hash::<Sha512>(file),
+        }
+    }
+}
+
+/// Path of the checksum file written next to `path`.
+fn sidecar(path: &FilePath, algorithm: Algorithm) -> FilePath {
+    path.with_suffix(&format!(".{}", algorithm.name()))
+}
+
+/// Hash the file at `path` and write the digest to its sidecar file as
+/// `<digest>  <file name>`. A file that was never created gets no checksum.
+pub(crate) async fn write_sidecar(path: &FilePath, algorithm: Algorithm) -> PyResult<()> {
+    let path = path.clone();
+    tokio::task::spawn_blocking(move || {
+        let digest = match algorithm.digest_file(&path) {
+            Ok(digest) => digest,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(CsvIoError::new_err(format!(
+                    "Failed to read file {path}: {e}"
+                )))
+            }
+        };
+        let name = paths::native(&path).file_name().map_or_else(
+            || path.to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let target = sidecar(&path, algorithm);
+        std::fs::write(paths::native(&target), format!("{digest}  {name}\n"))
+            .map_err(|e| CsvIoError::new_err(format!("Failed to write file {target}: {e}")))
+    })
+    .await
+    .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))?
+}
+
+/// The digest in a checksum file: the first word of its first line, as `sha256sum`
+/// writes it, or the last word for the BSD form `SHA256 (name) = <digest>`.
+fn sidecar_digest(content: &str) -> Option<&str> {
+    let line = content.lines().next()?;
+    let word = if line.contains(") = ") {
+        line.split_whitespace().last()?
+    } else {
+        line.split_whitespace().next()?
+    };
+    Some(word.trim_start_matches('\\'))
+}
+
+/// Checks files against their expected digests before they are parsed.
+#[derive(Debug)]
+pub(crate) struct Verifier {
+    expected: Option<(Algorithm, String)>, // Given digest; otherwise read from the sidecar
+    verified: StdMutex<HashSet<FilePath>>, // Paths already checked
+}
+
+impl Verifier {
+    /// The verifier for `verify_checksum=`: True reads each file's sidecar, a string is
+    /// the expected hex digest. Returns None for False.
+    pub(crate) fn from_arg(value: &Bound<'_, PyAny>) -> PyResult<Option<Arc<Self>>> {
+        let expected = if let Ok(flag) = value.cast::<PyBool>() {
+            if !flag.is_true() {
+                return Ok(None);
+            }
+            None
+        } else if let Ok(digest) = value.cast::<PyString>() {
+            let digest = digest.to_str()?.trim().to_ascii_lowercase();
+            match Algorithm::of_digest(&digest) {
+                Some(algorithm) => Some((algorithm, digest)),
+                None => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "verify_checksum must be a SHA-256 or SHA-512 hex digest",
+                    ))
+                }
+            }
+        } else {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "verify_checksum must be a bool or a hex digest string",
+            ));
+        };
+        Ok(Some(Arc::new(Verifier {
+            expected,
+            verified: StdMutex::new(HashSet::new()),
+        })))
+    }
+
+    /// Whether a digest was given, which only makes sense for a single file.
+    pub(crate) fn is_given(&self) -> bool {
+        self.expected.is_some()
+    }
+
+    /// The expected digest of `path` from its sidecar file.
+    fn adjacent(path: &FilePath) -> PyResult<(Algorithm, String)> {
+        for algorithm in Algorithm::ALL {
+            let target = sidecar(path, algorithm);
+            let content = match std::fs::read_to_string(paths::native(&target)) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(CsvIoError::new_err(format!(
+                        "Failed to read file {target}: {e}"
+                    )))
+                }
+            };
+            return match sidecar_digest(&content).map(str::to_ascii_lowercase) {
+                Some(digest) if Algorithm::of_digest(&digest) == Some(algorithm) => {
+                    Ok((algorithm, digest))
+                }
+                _ => Err(ChecksumError::new_err(format!(
+                    "{target} does not hold a {} digest",
+                    algorithm.name()
+                ))),
+            };
+        }
+        Err(ChecksumError::new_err(format!(
+            "No checksum file for {path} (looked for {} and {})",
+            sidecar(path, Algorithm::Sha256),
+            sidecar(path, Algorithm::Sha512)
+        )))
+    }
+
+    fn is_verified(&self, path: &FilePath) -> bool {
+        self.verified
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(path)
+    }
+
+    /// Hash `path` and compare it with the expected digest; blocks, so async callers
+    /// use `verify`. Each path is only hashed once.
+    pub(crate) fn check(&self, path: &FilePath) -> PyResult<()> {
+        if self.is_verified(path) {
+            return Ok(());
+        }
+        let (algorithm, expected) = match &self.expected {
+            Some(expected) => expected.clone(),
+            None => Self::adjacent(path)?,
+        };
+        let actual = algorithm
+            .digest_file(path)
+            .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+        if actual != expected {
+            return Err(ChecksumError::new_err(format!(
+                "{} checksum mismatch for {path}: expected {expected}, got {actual}",
+                algorithm.name()
+            )));
+        }
+        self.verified
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(path.clone());
+        Ok(())
+    }
+
+    /// `check` on a blocking thread.
+    pub(crate) async fn verify(self: &Arc<Self>, path: &FilePath) -> PyResult<()> {
+        if self.is_verified(path) {
+            return Ok(());
+        }
+        let verifier = Arc::clone(self);
+        let path = path.clone();
+        tokio::task::spawn_blocking(move || verifier.check(&path))
+            .await
+            .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))?
+    }
+}
@@ -0,0 +1,259 @@
+//! Dialect and encoding conversion (`rapcsv.convert()`).
+//!
+//! One streaming pass on a blocking worker thread: records are parsed with the source
+//! dialect and written with the target one, so delimiters, quoting and line endings
+//! are redone rather than substituted. The supported encodings are all ASCII
+//! supersets, so the parser works on the raw bytes and only field contents are
+//! transcoded, and not at all when both sides use the same character set.
+
+use crate::errors::{CsvIoError, EncodingError, ErrorContext};
+use crate::ops::{check_not_input, csv_err, spawn_blocking_py};
+use crate::{validate_path, DialectConfig};
+use csv::{ByteRecord, ReaderBuilder, WriterBuilder};
+use pyo3::prelude::*;
+use std::borrow::Cow;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Characters of Windows-1252 bytes 0x80 to 0x9F, where it differs from Latin-1.
+/// `None` marks the five bytes it leaves undefined.
+const CP1252_HIGH: [Option<char>; 32] = [
+    Some('\u{20AC}'),
+    None,
+    Some('\u{201A}'),
+    Some('\u{0192}'),
+    Some('\u{201E}'),
+    Some('\u{2026}'),
+    Some('\u{2020}'),
+    Some('\u{2021}'),
+    Some('\u{02C6}'),
+    Some('\u{2030}'),
+    Some('\u{0160}'),
+    Some('\u{2039}'),
+    Some('\u{0152}'),
+    None,
+    Some('\u{017D}'),
+    None,
+    None,
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{201C}'),
+    Some('\u{201D}'),
+    Some('\u{2022}'),
+    Some('\u{2013}'),
+    Some('\u{2014}'),
+    Some('\u{02DC}'),
+    Some('\u{2122}'),
+    Some('\u{0161}'),
+    Some('\u{203A}'),
+    Some('\u{0153}'),
+    None,
+    Some('\u{017E}'),
+    Some('\u{0178}'),
+];
+
+/// A text encoding `convert()` reads or writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    /// UTF-8 with a byte order mark: stripped when reading, written when writing.
+    Utf8Sig,
+    Latin1,
+    Cp1252,
+}
+
+impl Encoding {
+    /// Parse an encoding name, accepting the spellings of Python's codec aliases.
+    fn parse(name: &str) -> PyResult<Self> {
+        let normalized = name.trim().to_ascii_lowercase().replace('_', "-");
+        match normalized.as_str() {
+            "utf-8" | "utf8" => Ok(Encoding::Utf8),
+            "utf-8-sig" | "utf8-sig" => Ok(Encoding::Utf8Sig),
+            "latin-1" | "latin1" | "iso-8859-1" | "iso8859-1" | "l1" => Ok(Encoding::Latin1),
+            "cp1252" | "windows-1252" => Ok(Encoding::Cp1252),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unsupported encoding '{name}'; expected 'utf-8', 'utf-8-sig', 'latin-1' \
+                 or 'cp1252'"
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Utf8Sig => "utf-8-sig",
+            Encoding::Latin1 => "latin-1",
+            Encoding::Cp1252 => "cp1252",
+        }
+    }
+
+    fn is_utf8(self) -> bool {
+        matches!(self, Encoding::Utf8 | Encoding::Utf8Sig)
+    }
+
+    /// Decode one field. `record` is the 1-based record number, for errors.
+    fn decode(self, field: &[u8], record: usize) -> PyResult<Cow<'_, str>> {
+        match self {
+            Encoding::Utf8 | Encoding::Utf8Sig => {
+                std::str::from_utf8(field).map(Cow::Borrowed).map_err(|_| {
+                    let context = ErrorContext {
+                        row: Some(record - 1),
+                        field: Some(String::from_utf8_lossy(field).into_owned()),
+                        ..ErrorContext::default()
+                    };
+                    context.attach(EncodingError::new_err(format!(
+                        "Invalid UTF-8 in record {record}"
+                    )))
+                })
+            }
+            Encoding::Latin1 => Ok(field.iter().map(|&b| b as char).collect()),
+            Encoding::Cp1252 => field
+                .iter()
+                .map(|&b| match b {
+                    0x80..=0x9F => CP1252_HIGH[(b - 0x80) as usize].ok_or_else(|| {
+                        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                            "Byte 0x{b:02X} in record {record} is undefined in cp1252"
+                        ))
+                    }),
+                    _ => Ok(b as char),
+                })
+                .collect(),
+        }
+    }
+
+    /// Encode one field. `record` is the 1-based record number, for errors.
+    fn encode(self, text: &str, record: usize) -> PyResult<Vec<u8>> {
+        if self.is_utf8() {
+            return Ok(text.as_bytes().to_vec());
+        }
+        text.chars()
+            .map(|c| {
+                let byte = match (self, c as u32) {
+                    (_, code @ 0..=0x7F) | (Encoding::Latin1, code @ 0x80..=0xFF) => {
+                        Some(code as u8)
+                    }
+                    (Encoding::Cp1252, code @ 0xA0..=0xFF) => Some(code as u8),
+                    (Encoding::Cp1252, _) => CP1252_HIGH
+                        .iter()
+                        .position(|&high| high == Some(c))
+                        .map(|i| 0x80 + i as u8),
+                    _ => None,
+                };
+                byte.ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Character {c:?} in record {record} cannot be encoded in {}",
+                        self.name()
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Rewrite `src` to `dst` in another dialect and encoding.
+///
+/// Returns the number of records written, the header included.
+fn convert_file(
+    src: &str,
+    dst: &str,
+    from: &DialectConfig,
+    to: &DialectConfig,
+    from_encoding: Encoding,
+    to_encoding: Encoding,
+) -> PyResult<usize> {
+    check_not_input(dst, &[src])?;
+    let io_err = |action: &str, path: &str, e: std::io::Error| {
+        CsvIoError::new_err(format!("Failed to {action} file '{path}': {e}"))
+    };
+    let mut input = std::fs::File::open(src).map_err(|e| io_err("open", src, e))?;
+    if from_encoding == Encoding::Utf8Sig {
+        let mut start = [0u8; 3];
+        let n = input.read(&mut start).map_err(|e| io_err("read", src, e))?;
+        if start[..n] != *BOM {
+            input
+                .seek(SeekFrom::Start(0))
+                .map_err(|e| io_err("read", src, e))?;
+        }
+    }
+    let mut output = std::fs::File::create(dst).map_err(|e| io_err("create", dst, e))?;
+    if to_encoding == Encoding::Utf8Sig {
+        output.write_all(BOM).map_err(|e| io_err("write", dst, e))?;
+    }
+
+    let mut builder = ReaderBuilder::new();
+    from.apply_to_reader(&mut builder, None);
+    let mut reader = builder.has_headers(false).flexible(true).from_reader(input);
+    let mut builder = WriterBuilder::new();
+    to.apply_to_writer(&mut builder);
+    let mut writer = builder
+        .quote_style(to.quoting)
+        .flexible(true)
+        .from_writer(output);
+
+    // Same character set: fields are copied as they are
+    let transcode =
+        from_encoding != to_encoding && !(from_encoding.is_utf8() && to_encoding.is_utf8());
+    let mut record = ByteRecord::new();
+    let mut written = 0usize;
+    loop {
+        match reader.read_byte_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        if transcode {
+            let fields = record
+                .iter()
+                .map(|field| {
+                    to_encoding.encode(&from_encoding.decode(field, written + 1)?, written + 1)
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            writer.write_record(&fields)
+        } else {
+            writer.write_byte_record(&record)
+        }
+        .map_err(|e| csv_err(dst, e))?;
+        written += 1;
+    }
+
+    writer.flush().map_err(|e| io_err("flush", dst, e))?;
+    Ok(written)
+}
+
+/// Convert a CSV file to another dialect and encoding in one streaming pass.
+///
+/// # Arguments
+/// * `src` - Input CSV file
+/// * `dst` - Output CSV file (created or truncated)
+/// * `from_dialect` - Dialect of `src`: a `Dialect`, preset name or `csv.Dialect` (default: "excel")
+/// * `to_dialect` - Dialect to write `dst` in (default: "excel")
+/// * `from_encoding` - Encoding of `src`: "utf-8", "utf-8-sig", "latin-1" or "cp1252" (default: "utf-8")
+/// * `to_encoding` - Encoding to write `dst` in (default: "utf-8")
+///
+/// Returns the number of records written, the header included.
+#[pyfunction]
+#[pyo3(signature = (src, dst, from_dialect = None, to_dialect = None, from_encoding = "utf-8", to_encoding = "utf-8"))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn convert(
+    py: Python<'_>,
+    src: String,
+    dst: String,
+    from_dialect: Option<&Bound<'_, PyAny>>,
+    to_dialect: Option<&Bound<'_, PyAny>>,
+    from_encoding: &str,
+    to_encoding: &str,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&src)?;
+    validate_path(&dst)?;
+    let dialect = |dialect| {
+        DialectConfig::from_python(dialect, None, None, None, None, None, None, None, None)
+    };
+    let from = dialect(from_dialect)?;
+    let to = dialect(to_dialect)?;
+    let from_encoding = Encoding::parse(from_encoding)?;
+    let to_encoding = Encoding::parse(to_encoding)?;
+    spawn_blocking_py(py, move || {
+        convert_file(&src, &dst, &from, &to, from_encoding, to_encoding)
+    })
+}
@@ -0,0 +1,168 @@
+//! Encrypted CSV files (`encryption_key=` on `Reader` and `Writer`).
+//!
+//! Files are encrypted with AES-256-GCM in the STREAM construction: an 8-byte magic
+//! number and a random 7-byte nonce prefix, then the plaintext in 64 KiB segments,
+//! each sealed with its own tag and a nonce made of the prefix, the segment counter and
+//! a flag marking the final segment. Every segment is authenticated before any of its
+//! rows are parsed, and reordered, dropped or truncated segments fail decryption.
+//!
+//! Encryption and decryption run on a thread next to a pipe (see [`crate::pipe`]), so
+//! the plaintext only ever exists in memory.
+
+use crate::errors::CsvIoError;
+use crate::paths::{self, FilePath};
+use crate::pipe::{spawn_reader, spawn_writer, PipeSink, PipeSource};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::stream::{DecryptorBE32, EncryptorBE32};
+use aes_gcm::aead::{KeyInit, OsRng};
+use aes_gcm::Aes256Gcm;
+use pyo3::prelude::*;
+use std::io::{Read, Write};
+use tokio::fs::File;
+
+/// Start of every encrypted file, followed by the format version.
+const MAGIC: &[u8; 8] = b"RAPCSV\x00\x01";
+
+/// Length of the random nonce prefix stored after the magic number.
+const PREFIX_LEN: usize = 7;
+
+/// Plaintext bytes per segment.
+const SEGMENT: usize = 64 * 1024;
+
+/// Authentication tag added to every segment.
+const TAG_LEN: usize = 16;
+
+/// A validated AES-256 key.
+#[derive(Clone)]
+pub(crate) struct Key(Aes256Gcm);
+
+impl Key {
+    /// The key passed as `encryption_key=`: 32 bytes.
+    pub(crate) fn from_bytes(key: &[u8]) -> PyResult<Self> {
+        Aes256Gcm::new_from_slice(key).map(Key).map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "encryption_key must be 32 bytes (AES-256), got {}",
+                key.len()
+            ))
+        })
+    }
+}
+
+/// Read up to `buf.len()` bytes, stopping early only at EOF.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Decrypt `input` into `output` segment by segment.
+fn decrypt(key: Key, input: &mut impl Read, output: &mut impl Write) -> Result<(), String> {
+    let io = |e: std::io::Error| e.to_string();
+    let mut header = [0u8; MAGIC.len() + PREFIX_LEN];
+    if read_full(input, &mut header).map_err(io)? < header.len() || &header[..MAGIC.len()] != MAGIC
+    {
+        return Err("not a file encrypted by rapcsv".to_string());
+    }
+    let prefix = &header[MAGIC.len()..];
+    let mut decryptor = DecryptorBE32::from_aead(key.0, prefix.into());
+    let failed = |_| "decryption failed: wrong key or damaged file".to_string();
+
+    // A segment is the last one when nothing follows it
+    let mut current = vec![0u8; SEGMENT + TAG_LEN];
+    let mut next = vec![0u8; SEGMENT + TAG_LEN];
+    let mut current_len = read_full(input, &mut current).map_err(io)?;
+    loop {
+        let next_len = if current_len < current.len() {
+            0
+        } else {
+            read_full(input, &mut next).map_err(io)?
+        };
+        if next_len == 0 {
+            let plain = decryptor
+                .decrypt_last(&current[..current_len])
+                .map_err(failed)?;
+            return output.write_all(&plain).map_err(io);
+        }
+        let plain = decryptor
+            .decrypt_next(&current[..current_len])
+            .map_err(failed)?;
+        output.write_all(&plain).map_err(io)?;
+        std::mem::swap(&mut current, &mut next);
+        current_len = next_len;
+    }
+}
+
+/// Encrypt `input` into `output` until `input` reaches EOF.
+fn encrypt(key: Key, input: &mut impl Read, output: &mut impl Write) -> Result<(), String> {
+    let io = |e: std::io::Error| e.to_string();
+    let mut prefix = [0u8; PREFIX_LEN];
+    OsRng.fill_bytes(&mut prefix);
+    output.write_all(MAGIC).map_err(io)?;
+    output.write_all(&prefix).map_err(io)?;
+    let mut encryptor = EncryptorBE32::from_aead(key.0, (&prefix).into());
+    let failed = |_| "encryption failed".to_string();
+
+    // Hold one segment back: the last one must be sealed as such
+    let mut current = vec![0u8; SEGMENT];
+    let mut next = vec![0u8; SEGMENT];
+    let mut current_len = read_full(input, &mut current).map_err(io)?;
+    loop {
+        let next_len = if current_len < current.len() {
+            0
+        } else {
+            read_full(input, &mut next).map_err(io)?
+        };
+        if next_len == 0 {
+            let sealed = encryptor
+                .encrypt_last(&current[..current_len])
+                .map_err(failed)?;
+            output.write_all(&sealed).map_err(io)?;
+            return output.flush().map_err(io);
+        }
+        let sealed = encryptor
+            .encrypt_next(&current[..current_len])
+            .map_err(failed)?;
+        output.write_all(&sealed).map_err(io)?;
+        std::mem::swap(&mut current, &mut next);
+        current_len = next_len;
+    }
+}
+
+/// Start decrypting the file at `path`. Returns the file to read the plaintext from.
+pub(crate) fn open_decrypted(path: &FilePath, key: Key) -> PyResult<(File, PipeSource)> {
+    let mut file = std::fs::File::open(paths::native(path))
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+    let path = path.to_string();
+    spawn_reader(move |writer| {
+        decrypt(key, &mut file, writer).map_err(|e| format!("Failed to read file {path}: {e}"))
+    })
+}
+
+/// Create the encrypted file at `path`. Returns the file to write the plaintext to.
+/// Encrypted files cannot be appended to, so an existing file must be empty.
+pub(crate) fn create_encrypted(path: &FilePath, key: Key) -> PyResult<(File, PipeSink)> {
+    let file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(paths::native(path))
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+    let existing = file.metadata().map(|m| m.len() > 0).unwrap_or(false);
+    if existing {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "cannot append to encrypted file {path}; write to a new file"
+        )));
+    }
+    let path = path.to_string();
+    spawn_writer(move |reader| {
+        let mut output = std::io::BufWriter::new(file);
+        encrypt(key, reader, &mut output).map_err(|e| format!("Failed to write file {path}: {e}"))
+    })
+}
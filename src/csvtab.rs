@@ -0,0 +1,365 @@
+//! A read-only SQLite virtual table over a CSV file, for `query()`.
+//!
+//! SQLite pulls rows from the file as the query needs them, so a file is never copied
+//! into a database: a scan parses it from the first row again. Fields come back the way
+//! a column with `NUMERIC` affinity would store them, so numbers compare and aggregate
+//! as numbers. A join looks rows up by the joined column: the first lookup reads the
+//! file once to note where each value's rows start, and later ones seek straight to
+//! them, so the inner side of a join isn't scanned once per outer row.
+
+use crate::errors::{CsvIoError, CsvParseError};
+use crate::ops::csv_err;
+use crate::sqlite::quote_ident;
+use crate::DialectConfig;
+use csv::{Position, ReaderBuilder, StringRecord};
+use pyo3::prelude::*;
+use rusqlite::types::Value;
+use rusqlite::vtab::{
+    Context, CreateVTab, Filters, IndexConstraintOp, IndexInfo, Module, VTab, VTabConfig,
+    VTabConnection, VTabCursor, VTabKind,
+};
+use rusqlite::{ffi, Connection};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_int, CStr, CString};
+use std::fs::File;
+use std::sync::{Arc, Mutex, PoisonError};
+
+const MODULE_NAME: &CStr = c"rapcsv_csv";
+
+/// Planner cost of reading every row, against a few for a lookup.
+const SCAN_COST: f64 = 1_000_000.0;
+const LOOKUP_COST: f64 = 10.0;
+
+/// The files behind a connection's tables, by table name, and the first error reading
+/// one of them. SQLite only passes a message through, so the error is kept here to be
+/// raised as the `CSVError` or `IOError` it was.
+pub(crate) struct Sources {
+    files: HashMap<String, (String, DialectConfig)>, // table -> (path, dialect)
+    error: Mutex<Option<PyErr>>,
+}
+
+impl Sources {
+    /// Keep `e` to be raised in place of the SQLite error it causes.
+    fn fail(&self, e: PyErr) -> rusqlite::Error {
+        self.error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert(e);
+        rusqlite::Error::ModuleError("reading a CSV file failed".to_string())
+    }
+
+    /// The error raised for `e`: the CSV error behind it, if any.
+    pub(crate) fn error(&self, e: rusqlite::Error) -> PyErr {
+        let kept = self
+            .error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        kept.unwrap_or_else(|| CsvIoError::new_err(format!("SQLite error in query: {e}")))
+    }
+}
+
+/// Create a table on `conn` for each `(table, path, dialect)` in `files`. Returns the
+/// sources to turn the connection's errors into Python ones with.
+pub(crate) fn create(
+    conn: &Connection,
+    files: Vec<(String, String, DialectConfig)>,
+) -> PyResult<Arc<Sources>> {
+    const MODULE: Module<CsvTable> = Module::read_only_module();
+    let tables: Vec<String> = files.iter().map(|(table, _, _)| table.clone()).collect();
+    let sources = Arc::new(Sources {
+        files: files
+            .into_iter()
+            .map(|(table, path, dialect)| (table, (path, dialect)))
+            .collect(),
+        error: Mutex::new(None),
+    });
+    conn.create_module(MODULE_NAME, &MODULE, Some(Arc::clone(&sources)))
+        .map_err(|e| sources.error(e))?;
+    for table in tables {
+        let sql = format!(
+            "CREATE VIRTUAL TABLE {} USING {}",
+            quote_ident(&table),
+            MODULE_NAME.to_string_lossy()
+        );
+        conn.execute_batch(&sql).map_err(|e| sources.error(e))?;
+    }
+    Ok(sources)
+}
+
+/// The value a `NUMERIC` column stores for `field`: NULL when it's empty, an integer
+/// or a real when it reads as a number, the text otherwise.
+fn numeric(field: &str) -> Value {
+    let number = field.trim();
+    if field.is_empty() {
+        return Value::Null;
+    }
+    if let Ok(i) = number.parse::<i64>() {
+        return Value::Integer(i);
+    }
+    // f64 also reads "inf" and "nan", which SQLite keeps as text
+    let literal = number.bytes().any(|b| b.is_ascii_digit())
+        && number
+            .bytes()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.' | b'e' | b'E'));
+    match number.parse::<f64>() {
+        Ok(f) if literal => real(f),
+        _ => Value::Text(field.to_string()),
+    }
+}
+
+/// A real as SQLite stores it in a `NUMERIC` column: an integer if it is one.
+fn real(f: f64) -> Value {
+    if f.fract() == 0.0 && f.abs() < 9.2e18 {
+        Value::Integer(f as i64)
+    } else {
+        Value::Real(f)
+    }
+}
+
+/// A value as a lookup key; values that compare equal in SQLite have the same key, and
+/// NULL, which equals nothing, has none.
+#[derive(PartialEq, Eq, Hash)]
+enum Key {
+    Integer(i64),
+    Real(u64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl Key {
+    fn new(value: Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Integer(i) => Some(Key::Integer(i)),
+            Value::Real(f) => match real(f) {
+                Value::Integer(i) => Some(Key::Integer(i)),
+                _ => Some(Key::Real(f.to_bits())),
+            },
+            Value::Text(s) => Some(Key::Text(s)),
+            Value::Blob(b) => Some(Key::Blob(b)),
+        }
+    }
+}
+
+#[repr(C)]
+struct CsvTable {
+    base: ffi::sqlite3_vtab, // Must come first
+    sources: Arc<Sources>,
+    path: String,
+    dialect: DialectConfig,
+    first_row: Position,
+    /// Where the rows holding each value start, for each column looked up by
+    lookups: RefCell<HashMap<usize, HashMap<Key, Vec<Position>>>>,
+}
+
+impl CsvTable {
+    fn reader(&self) -> PyResult<csv::Reader<File>> {
+        let mut builder = ReaderBuilder::new();
+        self.dialect.apply_to_reader(&mut builder, None);
+        builder
+            .has_headers(true)
+            .from_path(&self.path)
+            .map_err(|e| CsvIoError::new_err(format!("Failed to open file '{}': {e}", self.path)))
+    }
+
+    /// Where the rows whose `column` holds `value` start, noting them for the whole
+    /// column on the first lookup by it.
+    fn lookup(&self, column: usize, value: Value) -> rusqlite::Result<Vec<Position>> {
+        let Some(key) = Key::new(value) else {
+            return Ok(Vec::new());
+        };
+        let mut lookups = self.lookups.borrow_mut();
+        if !lookups.contains_key(&column) {
+            let mut reader = self.reader().map_err(|e| self.sources.fail(e))?;
+            let mut rows: HashMap<Key, Vec<Position>> = HashMap::new();
+            let mut record = StringRecord::new();
+            loop {
+                let position = reader.position().clone();
+                let more = reader
+                    .read_record(&mut record)
+                    .map_err(|e| self.sources.fail(csv_err(&self.path, e)))?;
+                if !more {
+                    break;
+                }
+                if let Some(key) = Key::new(numeric(record.get(column).unwrap_or(""))) {
+                    rows.entry(key).or_default().push(position);
+                }
+            }
+            lookups.insert(column, rows);
+        }
+        Ok(lookups[&column].get(&key).cloned().unwrap_or_default())
+    }
+}
+
+unsafe impl<'vtab> VTab<'vtab> for CsvTable {
+    type Aux = Arc<Sources>;
+    type Cursor = CsvCursor<'vtab>;
+
+    fn connect(
+        db: &mut VTabConnection,
+        aux: Option<&Arc<Sources>>,
+        _module_name: &[u8],
+        _database_name: &[u8],
+        table_name: &[u8],
+        _args: &[&[u8]],
+    ) -> rusqlite::Result<(Cow<'static, CStr>, Self)> {
+        let sources = Arc::clone(aux.expect("module is created with its sources"));
+        let table = String::from_utf8_lossy(table_name);
+        let Some((path, dialect)) = sources.files.get(table.as_ref()).cloned() else {
+            return Err(rusqlite::Error::ModuleError(format!(
+                "no CSV file for table '{table}'"
+            )));
+        };
+        let mut vtab = CsvTable {
+            base: ffi::sqlite3_vtab::default(),
+            sources,
+            path,
+            dialect,
+            first_row: Position::new(),
+            lookups: RefCell::new(HashMap::new()),
+        };
+        let mut reader = vtab.reader().map_err(|e| vtab.sources.fail(e))?;
+        let headers = reader
+            .headers()
+            .map_err(|e| vtab.sources.fail(csv_err(&vtab.path, e)))?
+            .clone();
+        if headers.is_empty() {
+            return Err(vtab.sources.fail(CsvParseError::new_err(format!(
+                "File '{}' has no header row",
+                vtab.path
+            ))));
+        }
+        vtab.first_row = reader.position().clone();
+        let columns = headers
+            .iter()
+            .map(|h| format!("{} NUMERIC", quote_ident(h)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let schema = CString::new(format!("CREATE TABLE x({columns})"))?;
+        db.config(VTabConfig::DirectOnly)?;
+        Ok((Cow::Owned(schema), vtab))
+    }
+
+    /// Look rows up by a column equal to a value from another table, as in a join;
+    /// otherwise read them all. A constant is cheaper to filter on while scanning than
+    /// to note every row's value for.
+    fn best_index(&self, info: &mut IndexInfo) -> rusqlite::Result<bool> {
+        let lookup = info.constraints().enumerate().find_map(|(i, constraint)| {
+            let usable = constraint.is_usable()
+                && constraint.column() >= 0
+                && constraint.operator() == IndexConstraintOp::SQLITE_INDEX_CONSTRAINT_EQ
+                && info
+                    .collation(i)
+                    .is_ok_and(|c| c.eq_ignore_ascii_case("BINARY"))
+                && info.rhs_value(i).is_ok_and(|value| value.is_none());
+            usable.then_some((i, constraint.column()))
+        });
+        match lookup {
+            Some((i, column)) => {
+                // SQLite still checks the rows it gets back
+                let mut usage = info.constraint_usage(i);
+                usage.set_argv_index(1);
+                usage.set_omit(false);
+                info.set_idx_num(column + 1);
+                info.set_estimated_cost(LOOKUP_COST);
+                info.set_estimated_rows(LOOKUP_COST as i64);
+            }
+            None => {
+                info.set_idx_num(0);
+                info.set_estimated_cost(SCAN_COST);
+                info.set_estimated_rows(SCAN_COST as i64);
+            }
+        }
+        Ok(true)
+    }
+
+    fn open(&'vtab mut self) -> rusqlite::Result<CsvCursor<'vtab>> {
+        let reader = self.reader().map_err(|e| self.sources.fail(e))?;
+        Ok(CsvCursor {
+            base: ffi::sqlite3_vtab_cursor::default(),
+            table: self,
+            reader,
+            record: StringRecord::new(),
+            matches: None,
+            row: 0,
+            eof: false,
+        })
+    }
+}
+
+impl CreateVTab<'_> for CsvTable {
+    const KIND: VTabKind = VTabKind::Default;
+}
+
+#[repr(C)]
+struct CsvCursor<'vtab> {
+    base: ffi::sqlite3_vtab_cursor, // Must come first
+    table: &'vtab CsvTable,
+    reader: csv::Reader<File>,
+    record: StringRecord,
+    /// Where the rows a lookup has still to return start; `None` for a scan
+    matches: Option<std::vec::IntoIter<Position>>,
+    row: i64,
+    eof: bool,
+}
+
+unsafe impl VTabCursor for CsvCursor<'_> {
+    fn filter(
+        &mut self,
+        idx_num: c_int,
+        _idx_str: Option<&str>,
+        args: &Filters<'_>,
+    ) -> rusqlite::Result<()> {
+        self.matches = if idx_num == 0 {
+            let table = self.table;
+            self.reader
+                .seek(table.first_row.clone())
+                .map_err(|e| table.sources.fail(csv_err(&table.path, e)))?;
+            None
+        } else {
+            let value = match args.get::<Value>(0)? {
+                // The column's NUMERIC affinity applies to text it is compared with
+                Value::Text(s) => numeric(&s),
+                value => value,
+            };
+            let column = (idx_num - 1) as usize;
+            Some(self.table.lookup(column, value)?.into_iter())
+        };
+        self.next()
+    }
+
+    fn next(&mut self) -> rusqlite::Result<()> {
+        let table = self.table;
+        let fail = |e| table.sources.fail(csv_err(&table.path, e));
+        if let Some(matches) = &mut self.matches {
+            let Some(position) = matches.next() else {
+                self.eof = true;
+                return Ok(());
+            };
+            self.reader.seek(position).map_err(fail)?;
+        }
+        self.row = self.reader.position().record() as i64;
+        self.eof = !self.reader.read_record(&mut self.record).map_err(fail)?;
+        Ok(())
+    }
+
+    fn eof(&self) -> bool {
+        self.eof
+    }
+
+    fn column(&self, ctx: &mut Context, col: c_int) -> rusqlite::Result<()> {
+        // Short rows are padded with NULL
+        let field = usize::try_from(col)
+            .ok()
+            .and_then(|col| self.record.get(col))
+            .unwrap_or("");
+        ctx.set_result(&numeric(field))
+    }
+
+    fn rowid(&self) -> rusqlite::Result<i64> {
+        Ok(self.row)
+    }
+}
@@ -0,0 +1,126 @@
+//! UTF-8 decoding of the bytes read from CSV files (`utf8=` on `Reader`).
+//!
+//! Reads hand over arbitrary byte chunks, so a multi-byte character can be split
+//! between two of them; the incomplete tail is kept and decoded with the next chunk.
+//! In lossy mode invalid sequences become U+FFFD instead of failing the read, and the
+//! replacements are counted for `Reader.utf8_replacements`.
+
+use crate::errors::EncodingError;
+use pyo3::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex as StdMutex;
+
+/// What to do with bytes that are not valid UTF-8.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Utf8Mode {
+    /// Fail the read.
+    Strict,
+    /// Replace each invalid sequence with U+FFFD.
+    Lossy,
+}
+
+impl Utf8Mode {
+    pub(crate) fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "strict" => Ok(Utf8Mode::Strict),
+            "lossy" => Ok(Utf8Mode::Lossy),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "utf8 must be 'strict' or 'lossy', got '{other}'"
+            ))),
+        }
+    }
+}
+
+fn invalid_utf8() -> PyErr {
+    EncodingError::new_err("Invalid UTF-8 in CSV file")
+}
+
+/// Decoder for one stream: the mode, a split character carried between chunks and
+/// the number of replacements made so far.
+#[derive(Debug)]
+pub(crate) struct Utf8Decoder {
+    mode: Utf8Mode,
+    carry: StdMutex<Vec<u8>>,
+    replacements: AtomicU64,
+}
+
+impl Utf8Decoder {
+    pub(crate) fn new(mode: Utf8Mode) -> Self {
+        Utf8Decoder {
+            mode,
+            carry: StdMutex::new(Vec::new()),
+            replacements: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn mode(&self) -> Utf8Mode {
+        self.mode
+    }
+
+    /// Invalid sequences replaced with U+FFFD so far.
+    pub(crate) fn replacements(&self) -> u64 {
+        self.replacements.load(Ordering::Relaxed)
+    }
+
+    /// Forget a partial character, e.g. when moving to another file.
+    pub(crate) fn reset(&self) {
+        self.carry.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Decode the next chunk of a stream. At `eof` an incomplete character left over
+    /// is invalid rather than carried.
+    pub(crate) fn decode(&self, chunk: &[u8], eof: bool) -> PyResult<String> {
+        let mut carry = self.carry.lock().unwrap_or_else(|e| e.into_inner());
+        let mut data = std::mem::take(&mut *carry);
+        data.extend_from_slice(chunk);
+        if !eof {
+            let tail = incomplete_tail(&data);
+            *carry = data.split_off(data.len() - tail);
+        }
+        self.decode_all(data)
+    }
+
+    /// Decode a complete input, such as a whole file.
+    pub(crate) fn decode_all(&self, data: Vec<u8>) -> PyResult<String> {
+        match String::from_utf8(data) {
+            Ok(text) => Ok(text),
+            Err(_) if self.mode == Utf8Mode::Strict => Err(invalid_utf8()),
+            Err(e) => Ok(self.replace(e.as_bytes())),
+        }
+    }
+
+    /// `String::from_utf8_lossy`, counting the replacements.
+    fn replace(&self, data: &[u8]) -> String {
+        let mut text = String::with_capacity(data.len());
+        let mut replaced = 0;
+        for chunk in data.utf8_chunks() {
+            text.push_str(chunk.valid());
+            if !chunk.invalid().is_empty() {
+                text.push(char::REPLACEMENT_CHARACTER);
+                replaced += 1;
+            }
+        }
+        self.replacements.fetch_add(replaced, Ordering::Relaxed);
+        text
+    }
+}
+
+/// Length of a UTF-8 sequence cut off at the end of `data`, which may still be
+/// completed by the next chunk (0 if `data` ends on a character boundary).
+fn incomplete_tail(data: &[u8]) -> usize {
+    // A sequence is at most 4 bytes, so only the last 3 can start an unfinished one
+    for back in 1..=data.len().min(3) {
+        let byte = data[data.len() - back];
+        if byte & 0xC0 == 0x80 {
+            continue; // Continuation byte: keep looking for the lead byte
+        }
+        let needed = match byte {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return 0,
+        };
+        return if needed > back { back } else { 0 };
+    }
+    0
+}
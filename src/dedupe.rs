@@ -0,0 +1,407 @@
+//! Streaming row deduplication.
+//!
+//! Keys seen so far are kept in a hash set. When the set would grow past the
+//! configured limit, the file is deduplicated again with a spill strategy: rows are
+//! hash-partitioned into temporary files, each partition is deduplicated on its own,
+//! and the survivors are merged back into their original order. The partition files
+//! are removed when the operation ends, including when it fails or the awaiting task
+//! is cancelled.
+//!
+//! Keeping the last occurrence of each key takes two passes over the input (or over
+//! each partition): the first records the row number where every key last occurs, the
+//! second writes only those rows.
+
+use crate::errors::CsvIoError;
+use crate::ops::{
+    check_not_input, check_temp_dir, create_writer, csv_err, encode_key, extract_columns,
+    open_reader, read_header, resolve_columns, simple_dialect, spawn_cancellable_py, spill_dir,
+    Cancellation,
+};
+use crate::{validate_path, DialectConfig};
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use pyo3::prelude::*;
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Default number of distinct keys held in memory before switching to the spill strategy.
+const DEFAULT_MAX_KEYS: usize = 10_000_000;
+
+/// Number of partitions used by the spill strategy.
+const SPILL_PARTITIONS: usize = 64;
+
+/// Build an IOError with `context` prepended to the underlying error.
+fn io_err(context: &str, e: impl std::fmt::Display) -> PyErr {
+    CsvIoError::new_err(format!("{context}: {e}"))
+}
+
+/// Which occurrence of a duplicated key survives.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Keep {
+    First,
+    Last,
+}
+
+impl Keep {
+    fn parse(keep: &str) -> PyResult<Self> {
+        match keep {
+            "first" => Ok(Keep::First),
+            "last" => Ok(Keep::Last),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "keep must be 'first' or 'last', got '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Outcome of the in-memory pass.
+enum InMemory {
+    /// Finished; holds the number of duplicates removed.
+    Done(usize),
+    /// Too many distinct keys; the caller must use the spill strategy.
+    TooManyKeys,
+}
+
+/// Deduplicate with a single in-memory hash set, giving up once it exceeds `max_keys`.
+fn dedupe_in_memory(
+    src: &str,
+    dst: &str,
+    key: Option<&[String]>,
+    keep: Keep,
+    max_keys: usize,
+    dialect: &DialectConfig,
+    cancellation: &Cancellation,
+) -> PyResult<InMemory> {
+    if keep == Keep::Last {
+        return dedupe_last_in_memory(src, dst, key, max_keys, dialect, cancellation);
+    }
+    let mut reader = open_reader(src, dialect)?;
+    let mut writer = create_writer(dst, dialect)?;
+    let Some(header) = read_header(&mut reader, src)? else {
+        return Ok(InMemory::Done(0));
+    };
+    let columns = key
+        .map(|key| resolve_columns(key, &header, src))
+        .transpose()?;
+    writer.write_record(&header).map_err(|e| csv_err(dst, e))?;
+
+    let mut seen: HashSet<Vec<u8>> = HashSet::new();
+    let mut removed = 0usize;
+    let mut record = StringRecord::new();
+    let mut encoded = Vec::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        cancellation.check()?;
+        encode_key(&record, columns.as_deref(), &mut encoded);
+        if seen.contains(&encoded) {
+            removed += 1;
+            continue;
+        }
+        if seen.len() >= max_keys {
+            return Ok(InMemory::TooManyKeys);
+        }
+        seen.insert(encoded.clone());
+        writer.write_record(&record).map_err(|e| csv_err(dst, e))?;
+    }
+    writer
+        .flush()
+        .map_err(|e| io_err(&format!("Failed to flush file '{dst}'"), e))?;
+    Ok(InMemory::Done(removed))
+}
+
+/// Keep the last occurrence of each key, with the row numbers where keys last occur in
+/// a hash map of at most `max_keys` entries.
+fn dedupe_last_in_memory(
+    src: &str,
+    dst: &str,
+    key: Option<&[String]>,
+    max_keys: usize,
+    dialect: &DialectConfig,
+    cancellation: &Cancellation,
+) -> PyResult<InMemory> {
+    // Pass 1: where each key last occurs
+    let mut reader = open_reader(src, dialect)?;
+    let Some(header) = read_header(&mut reader, src)? else {
+        create_writer(dst, dialect)?;
+        return Ok(InMemory::Done(0));
+    };
+    let columns = key
+        .map(|key| resolve_columns(key, &header, src))
+        .transpose()?;
+    let mut last: HashMap<Vec<u8>, u64> = HashMap::new();
+    let mut record = StringRecord::new();
+    let mut encoded = Vec::new();
+    let mut seq = 0u64;
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        cancellation.check()?;
+        encode_key(&record, columns.as_deref(), &mut encoded);
+        if let Some(last_seq) = last.get_mut(&encoded) {
+            *last_seq = seq;
+        } else if last.len() >= max_keys {
+            return Ok(InMemory::TooManyKeys);
+        } else {
+            last.insert(encoded.clone(), seq);
+        }
+        seq += 1;
+    }
+
+    // Pass 2: write those rows
+    let mut reader = open_reader(src, dialect)?;
+    read_header(&mut reader, src)?;
+    let mut writer = create_writer(dst, dialect)?;
+    writer.write_record(&header).map_err(|e| csv_err(dst, e))?;
+    let mut removed = 0usize;
+    seq = 0;
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        cancellation.check()?;
+        encode_key(&record, columns.as_deref(), &mut encoded);
+        if last.get(&encoded) == Some(&seq) {
+            writer.write_record(&record).map_err(|e| csv_err(dst, e))?;
+        } else {
+            removed += 1;
+        }
+        seq += 1;
+    }
+    writer
+        .flush()
+        .map_err(|e| io_err(&format!("Failed to flush file '{dst}'"), e))?;
+    Ok(InMemory::Done(removed))
+}
+
+/// Open a spilled partition for reading.
+fn open_partition(path: &str) -> PyResult<csv::Reader<std::fs::File>> {
+    ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| csv_err(path, e))
+}
+
+/// Read the next spilled row into `row`, returning its sequence number.
+fn read_sequenced(
+    reader: &mut csv::Reader<std::fs::File>,
+    row: &mut StringRecord,
+    path: &str,
+) -> PyResult<Option<u64>> {
+    if !reader.read_record(row).map_err(|e| csv_err(path, e))? {
+        return Ok(None);
+    }
+    Ok(Some(
+        row.get(0)
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(u64::MAX),
+    ))
+}
+
+/// Deduplicate by hash-partitioning rows into temporary files.
+///
+/// Each spilled row is prefixed with its sequence number so the survivors of every
+/// partition (which stay in input order) can be merged back into the original order.
+fn dedupe_spilled(
+    src: &str,
+    dst: &str,
+    key: Option<&[String]>,
+    keep: Keep,
+    dialect: &DialectConfig,
+    temp_dir: Option<&str>,
+    cancellation: &Cancellation,
+) -> PyResult<usize> {
+    let spill = spill_dir(temp_dir, "dedupe")?;
+    let part_path = |kind: &str, i: usize| -> String {
+        Path::new(spill.path())
+            .join(format!("{kind}_{i}.csv"))
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    // Pass 1: partition rows by key hash
+    let mut reader = open_reader(src, dialect)?;
+    let Some(header) = read_header(&mut reader, src)? else {
+        create_writer(dst, dialect)?;
+        return Ok(0);
+    };
+    let columns = key
+        .map(|key| resolve_columns(key, &header, src))
+        .transpose()?;
+    let mut partitions = (0..SPILL_PARTITIONS)
+        .map(|i| {
+            let path = part_path("part", i);
+            WriterBuilder::new()
+                .flexible(true)
+                .from_path(&path)
+                .map_err(|e| csv_err(&path, e))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let mut record = StringRecord::new();
+    let mut encoded = Vec::new();
+    let mut seq = 0u64;
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        cancellation.check()?;
+        encode_key(&record, columns.as_deref(), &mut encoded);
+        let mut hasher = DefaultHasher::new();
+        encoded.hash(&mut hasher);
+        let partition = (hasher.finish() % SPILL_PARTITIONS as u64) as usize;
+        let seq_field = seq.to_string();
+        partitions[partition]
+            .write_record(std::iter::once(seq_field.as_str()).chain(record.iter()))
+            .map_err(|e| csv_err(&part_path("part", partition), e))?;
+        seq += 1;
+    }
+    for (i, mut partition) in partitions.into_iter().enumerate() {
+        partition
+            .flush()
+            .map_err(|e| io_err(&format!("Failed to write '{}'", part_path("part", i)), e))?;
+    }
+
+    // Pass 2: deduplicate each partition independently
+    let mut removed = 0usize;
+    for i in 0..SPILL_PARTITIONS {
+        let in_path = part_path("part", i);
+        let out_path = part_path("kept", i);
+        let mut row = StringRecord::new();
+        // With keep="last", where each key of the partition last occurs
+        let mut last: HashMap<Vec<u8>, u64> = HashMap::new();
+        if keep == Keep::Last {
+            let mut part_reader = open_partition(&in_path)?;
+            while let Some(seq) = read_sequenced(&mut part_reader, &mut row, &in_path)? {
+                let fields: StringRecord = row.iter().skip(1).collect();
+                encode_key(&fields, columns.as_deref(), &mut encoded);
+                last.insert(encoded.clone(), seq);
+            }
+        }
+        let mut part_reader = open_partition(&in_path)?;
+        let mut part_writer = WriterBuilder::new()
+            .flexible(true)
+            .from_path(&out_path)
+            .map_err(|e| csv_err(&out_path, e))?;
+        let mut seen: HashSet<Vec<u8>> = HashSet::new();
+        while let Some(seq) = read_sequenced(&mut part_reader, &mut row, &in_path)? {
+            cancellation.check()?;
+            let fields: StringRecord = row.iter().skip(1).collect();
+            encode_key(&fields, columns.as_deref(), &mut encoded);
+            let kept = match keep {
+                Keep::First => seen.insert(encoded.clone()),
+                Keep::Last => last.get(&encoded) == Some(&seq),
+            };
+            if kept {
+                part_writer
+                    .write_record(&row)
+                    .map_err(|e| csv_err(&out_path, e))?;
+            } else {
+                removed += 1;
+            }
+        }
+        part_writer
+            .flush()
+            .map_err(|e| io_err(&format!("Failed to write '{out_path}'"), e))?;
+        std::fs::remove_file(&in_path).ok();
+    }
+
+    // Pass 3: merge survivors back into input order
+    let mut writer = create_writer(dst, dialect)?;
+    writer.write_record(&header).map_err(|e| csv_err(dst, e))?;
+    let mut readers = (0..SPILL_PARTITIONS)
+        .map(|i| open_partition(&part_path("kept", i)))
+        .collect::<PyResult<Vec<_>>>()?;
+    let mut heads: Vec<StringRecord> = vec![StringRecord::new(); SPILL_PARTITIONS];
+    let mut heap: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
+    for i in 0..SPILL_PARTITIONS {
+        if let Some(seq) = read_sequenced(&mut readers[i], &mut heads[i], &part_path("kept", i))? {
+            heap.push(Reverse((seq, i)));
+        }
+    }
+    while let Some(Reverse((_, i))) = heap.pop() {
+        cancellation.check()?;
+        writer
+            .write_record(heads[i].iter().skip(1))
+            .map_err(|e| csv_err(dst, e))?;
+        if let Some(seq) = read_sequenced(&mut readers[i], &mut heads[i], &part_path("kept", i))? {
+            heap.push(Reverse((seq, i)));
+        }
+    }
+    writer
+        .flush()
+        .map_err(|e| io_err(&format!("Failed to flush file '{dst}'"), e))?;
+    Ok(removed)
+}
+
+/// Remove duplicate rows from a CSV file.
+///
+/// # Arguments
+/// * `src` - Input CSV file (first row is the header)
+/// * `dst` - Output CSV file (created or truncated)
+/// * `key` - Column name or list of names identifying a row (default: the whole row)
+/// * `max_keys` - Distinct keys held in memory before spilling to disk (default: 10000000)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+/// * `keep` - Which occurrence of each key to keep: "first" or "last" (default: "first")
+/// * `temp_dir` - Directory to create spill files under (default: None, the system's
+///   temporary directory)
+///
+/// Kept rows stay in input order. Returns the number of duplicate rows removed.
+#[pyfunction]
+#[pyo3(signature = (src, dst, key = None, max_keys = None, delimiter = None, quotechar = None, keep = "first", temp_dir = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn dedupe(
+    py: Python<'_>,
+    src: String,
+    dst: String,
+    key: Option<&Bound<'_, PyAny>>,
+    max_keys: Option<usize>,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+    keep: &str,
+    temp_dir: Option<String>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&src)?;
+    validate_path(&dst)?;
+    check_temp_dir(temp_dir.as_deref())?;
+    let key = key.map(|key| extract_columns(key, "key")).transpose()?;
+    let keep = Keep::parse(keep)?;
+    let max_keys = max_keys.unwrap_or(DEFAULT_MAX_KEYS);
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    spawn_cancellable_py(py, move |cancellation| {
+        check_not_input(&dst, &[&src])?;
+        match dedupe_in_memory(
+            &src,
+            &dst,
+            key.as_deref(),
+            keep,
+            max_keys,
+            &dialect,
+            cancellation,
+        )? {
+            InMemory::Done(removed) => Ok(removed),
+            InMemory::TooManyKeys => dedupe_spilled(
+                &src,
+                &dst,
+                key.as_deref(),
+                keep,
+                &dialect,
+                temp_dir.as_deref(),
+                cancellation,
+            ),
+        }
+    })
+}
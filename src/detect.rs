@@ -0,0 +1,80 @@
+//! What a stream's data turned out to use (`Reader.dialect()`).
+//!
+//! The parser accepts `\r\n`, `\n` and `\r` alike and skips a byte order mark, so
+//! neither shows in the rows. The data is looked at as it is first read, up to the end
+//! of its first record, so a pipeline can log or assert the format it is really
+//! processing.
+
+use crate::DialectConfig;
+use csv::Terminator;
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Traits of a stream found at the start of its data.
+#[derive(Clone, Debug)]
+pub(crate) struct Detected {
+    pub(crate) bom: bool,
+    pub(crate) terminator: Option<String>, // The first record's line ending, if it has one
+}
+
+/// Looks at a stream's data chunk by chunk until its first record ends, so a record
+/// longer than a chunk, or a `\r\n` split between two, is seen whole.
+#[derive(Debug, Default)]
+pub(crate) struct Detector {
+    bom: Option<bool>, // Set by the first data seen
+    in_quotes: bool,
+    cr: bool, // The data seen so far ends with a `\r` outside quotes
+}
+
+impl Detector {
+    /// Look at the next `chunk` of the data; `at_file_start` is false for a stream
+    /// resumed in the middle of a file, which can't have a byte order mark, and `eof`
+    /// says no data follows. Returns what was found once the first record has ended,
+    /// or None while more data is needed, or at the end of data that was all empty.
+    pub(crate) fn feed(
+        &mut self,
+        chunk: &[u8],
+        dialect: &DialectConfig,
+        at_file_start: bool,
+        eof: bool,
+    ) -> Option<Detected> {
+        if self.bom.is_none() && !chunk.is_empty() {
+            self.bom = Some(at_file_start && chunk.starts_with(BOM));
+        }
+        let bom = self.bom?;
+        let found = |terminator: Option<&str>| {
+            Some(Detected {
+                bom,
+                terminator: terminator.map(str::to_string),
+            })
+        };
+        if let Terminator::Any(b) = dialect.lineterminator {
+            if b != b'\r' && b != b'\n' {
+                return found(Some(&(b as char).to_string()));
+            }
+        }
+        let quote = dialect.read_quote();
+        for &b in chunk {
+            if self.cr {
+                return found(Some(if b == b'\n' { "\r\n" } else { "\r" }));
+            }
+            if Some(b) == quote {
+                self.in_quotes = !self.in_quotes;
+            } else if !self.in_quotes && b == b'\n' {
+                return found(Some("\n"));
+            } else if !self.in_quotes && b == b'\r' {
+                self.cr = true;
+            }
+        }
+        if eof {
+            found(self.cr.then_some("\r"))
+        } else {
+            None
+        }
+    }
+}
+
+/// Look at `data`, the whole of a stream from the start of its file.
+pub(crate) fn detect(data: &[u8], dialect: &DialectConfig) -> Option<Detected> {
+    Detector::default().feed(data, dialect, true, true)
+}
@@ -0,0 +1,248 @@
+//! Reusable dialects (`Dialect` and `dialect=` on the CSV readers and writers).
+//!
+//! A `Dialect` bundles the formatting options every CSV class accepts, so one
+//! configuration can be built once and passed around. `dialect=` takes a `Dialect`, a
+//! preset name ("excel", "excel-tab" or "unix", as in Python's `csv` module) or any
+//! object with the attributes of `csv.Dialect`. Options passed alongside it win.
+
+use crate::DialectConfig;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+
+/// A named set of CSV formatting options.
+///
+/// Quoting uses rapcsv's numbering: 0=QUOTE_NONE, 1=QUOTE_MINIMAL, 2=QUOTE_ALL,
+/// 3=QUOTE_NONNUMERIC, 4=QUOTE_NOTNULL, 6=QUOTE_STRINGS.
+#[pyclass(frozen, eq, module = "rapcsv")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Dialect {
+    #[pyo3(get)]
+    pub(crate) delimiter: String,
+    #[pyo3(get)]
+    pub(crate) quotechar: String,
+    #[pyo3(get)]
+    pub(crate) escapechar: Option<String>,
+    #[pyo3(get)]
+    pub(crate) quoting: u32,
+    #[pyo3(get)]
+    pub(crate) lineterminator: String,
+    #[pyo3(get)]
+    pub(crate) skipinitialspace: bool,
+    #[pyo3(get)]
+    pub(crate) strict: bool,
+    #[pyo3(get)]
+    pub(crate) double_quote: bool,
+}
+
+impl Default for Dialect {
+    /// The "excel" preset, which matches the defaults of every CSV class.
+    fn default() -> Self {
+        Dialect {
+            delimiter: ",".to_string(),
+            quotechar: "\"".to_string(),
+            escapechar: None,
+            quoting: 1,
+            lineterminator: "\r\n".to_string(),
+            skipinitialspace: false,
+            strict: false,
+            double_quote: true,
+        }
+    }
+}
+
+/// Translate a `csv.QUOTE_*` constant to rapcsv's numbering.
+fn csv_quoting(quoting: u32) -> PyResult<u32> {
+    match quoting {
+        0 => Ok(1), // QUOTE_MINIMAL
+        1 => Ok(2), // QUOTE_ALL
+        2 => Ok(3), // QUOTE_NONNUMERIC
+        3 => Ok(0), // QUOTE_NONE
+        4 => Ok(6), // QUOTE_STRINGS
+        5 => Ok(4), // QUOTE_NOTNULL
+        other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "unknown csv quoting constant {other}"
+        ))),
+    }
+}
+
+impl Dialect {
+    /// The dialect given as `dialect=`.
+    pub(crate) fn resolve(dialect: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(dialect) = dialect.cast::<Dialect>() {
+            return Ok(dialect.get().clone());
+        }
+        if let Ok(name) = dialect.cast::<PyString>() {
+            return Self::preset(name.to_str()?);
+        }
+        Self::from_csv(dialect)
+    }
+
+    /// Read the attributes of a `csv.Dialect` subclass or instance.
+    fn from_csv(dialect: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if !dialect.hasattr("delimiter")? || !dialect.hasattr("lineterminator")? {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "dialect must be a Dialect, a preset name or a csv.Dialect",
+            ));
+        }
+        let defaults = Dialect::default();
+        let attr = |name: &str| dialect.getattr(name).ok().filter(|value| !value.is_none());
+        let quoting = match attr("quoting") {
+            Some(quoting) => csv_quoting(quoting.extract()?)?,
+            None => defaults.quoting,
+        };
+        let flag = |name: &str, default: bool| -> PyResult<bool> {
+            attr(name).map_or(Ok(default), |value| value.is_truthy())
+        };
+        Ok(Dialect {
+            delimiter: dialect.getattr("delimiter")?.extract()?,
+            quotechar: match attr("quotechar") {
+                Some(quotechar) => quotechar.extract()?,
+                None => defaults.quotechar,
+            },
+            escapechar: attr("escapechar").map(|e| e.extract()).transpose()?,
+            quoting,
+            lineterminator: dialect.getattr("lineterminator")?.extract()?,
+            skipinitialspace: flag("skipinitialspace", false)?,
+            strict: flag("strict", false)?,
+            double_quote: flag("doublequote", true)?,
+        })
+    }
+
+    /// Check the options by building the parser configuration from them.
+    fn validate(&self) -> PyResult<()> {
+        DialectConfig::from_python(
+            None,
+            Some(&self.delimiter),
+            Some(&self.quotechar),
+            self.escapechar.as_deref(),
+            Some(self.quoting),
+            Some(&self.lineterminator),
+            Some(self.skipinitialspace),
+            Some(self.strict),
+            Some(self.double_quote),
+        )
+        .map(|_| ())
+    }
+
+    /// The options as keyword arguments of the CSV classes.
+    fn options<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let options = PyDict::new(py);
+        options.set_item("delimiter", &self.delimiter)?;
+        options.set_item("quotechar", &self.quotechar)?;
+        options.set_item("escapechar", &self.escapechar)?;
+        options.set_item("quoting", self.quoting)?;
+        options.set_item("lineterminator", &self.lineterminator)?;
+        options.set_item("skipinitialspace", self.skipinitialspace)?;
+        options.set_item("strict", self.strict)?;
+        options.set_item("double_quote", self.double_quote)?;
+        Ok(options)
+    }
+}
+
+#[pymethods]
+impl Dialect {
+    /// Create a dialect. Options default to the "excel" preset.
+    #[new]
+    #[pyo3(signature = (
+        delimiter = ",",
+        quotechar = "\"",
+        escapechar = None,
+        quoting = 1,
+        lineterminator = "\r\n",
+        skipinitialspace = false,
+        strict = false,
+        double_quote = true
+    ))]
+    #[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+    fn new(
+        delimiter: &str,
+        quotechar: &str,
+        escapechar: Option<&str>,
+        quoting: u32,
+        lineterminator: &str,
+        skipinitialspace: bool,
+        strict: bool,
+        double_quote: bool,
+    ) -> PyResult<Self> {
+        let dialect = Dialect {
+            delimiter: delimiter.to_string(),
+            quotechar: quotechar.to_string(),
+            escapechar: escapechar.map(String::from),
+            quoting,
+            lineterminator: lineterminator.to_string(),
+            skipinitialspace,
+            strict,
+            double_quote,
+        };
+        dialect.validate()?;
+        Ok(dialect)
+    }
+
+    /// The preset called `name`: "excel", "excel-tab" (or "excel_tab") or "unix".
+    #[staticmethod]
+    fn preset(name: &str) -> PyResult<Self> {
+        match name {
+            "excel" => Ok(Dialect::excel()),
+            "excel-tab" | "excel_tab" => Ok(Dialect::excel_tab()),
+            "unix" | "unix_dialect" => Ok(Dialect::unix()),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown dialect '{other}' (expected 'excel', 'excel-tab' or 'unix')"
+            ))),
+        }
+    }
+
+    /// Comma-separated with CRLF line endings, like `csv.excel`.
+    #[classattr]
+    fn excel() -> Self {
+        Dialect::default()
+    }
+
+    /// Tab-separated with CRLF line endings, like `csv.excel_tab`.
+    #[classattr]
+    fn excel_tab() -> Self {
+        Dialect {
+            delimiter: "\t".to_string(),
+            ..Dialect::default()
+        }
+    }
+
+    /// Every field quoted, LF line endings, like `csv.unix_dialect`.
+    #[classattr]
+    fn unix() -> Self {
+        Dialect {
+            quoting: 2, // QUOTE_ALL
+            lineterminator: "\n".to_string(),
+            ..Dialect::default()
+        }
+    }
+
+    /// Alias of `double_quote` under the name `csv.Dialect` uses.
+    #[getter]
+    fn doublequote(&self) -> bool {
+        self.double_quote
+    }
+
+    /// A copy with some options changed.
+    #[pyo3(signature = (**changes))]
+    fn replace(&self, py: Python<'_>, changes: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let options = self.options(py)?;
+        if let Some(changes) = changes {
+            options.update(changes.as_mapping())?;
+        }
+        let dialect = py.get_type::<Dialect>().call((), Some(&options))?;
+        Ok(dialect.cast_into::<Dialect>()?.get().clone())
+    }
+
+    /// The options as a dict of keyword arguments, e.g. `Writer(path, **d.to_dict())`.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.options(py)
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let mut parts = Vec::new();
+        for (key, value) in self.options(py)?.iter() {
+            parts.push(format!("{key}={}", value.repr()?));
+        }
+        Ok(format!("Dialect({})", parts.join(", ")))
+    }
+}
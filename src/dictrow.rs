@@ -0,0 +1,104 @@
+//! The dicts `AsyncDictReader` returns.
+//!
+//! Every dict of a reader has the same keys, so the key strings are made once, as
+//! interned Python strings, and shared by all of them instead of being allocated per
+//! row. For wide files this is most of the cost of a row. The keys are rebuilt only
+//! when the fieldnames change (`add_field()`, `rename_field()`, ...).
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+use std::sync::{Arc, Mutex as StdMutex};
+
+/// Key objects and the rules for filling one dict per row.
+#[derive(Debug)]
+pub(crate) struct DictKeys {
+    fieldnames: Vec<String>,
+    keys: Vec<Py<PyString>>,
+    skip: Vec<bool>, // Repeated names whose value the first column keeps (`keep_first`)
+    restkey: Option<Py<PyString>>,
+    restval: String,
+}
+
+impl DictKeys {
+    fn new(
+        py: Python<'_>,
+        fieldnames: &[String],
+        restkey: Option<&str>,
+        restval: &str,
+        keep_first: bool,
+    ) -> Self {
+        let skip = fieldnames
+            .iter()
+            .enumerate()
+            .map(|(i, name)| keep_first && fieldnames[..i].contains(name))
+            .collect();
+        DictKeys {
+            fieldnames: fieldnames.to_vec(),
+            keys: fieldnames
+                .iter()
+                .map(|name| PyString::intern(py, name).unbind())
+                .collect(),
+            skip,
+            restkey: restkey.map(|key| PyString::intern(py, key).unbind()),
+            restval: restval.to_string(),
+        }
+    }
+
+    /// Map the fieldnames to the fields of `row`. Missing fields get `restval`, and
+    /// fields past the fieldnames go in a list under `restkey` if it is set.
+    pub(crate) fn dict<'py>(
+        &self,
+        py: Python<'py>,
+        mut row: Vec<String>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        let extra = if row.len() > self.keys.len() {
+            row.split_off(self.keys.len())
+        } else {
+            Vec::new()
+        };
+        let mut fields = row.into_iter();
+        for (key, skip) in self.keys.iter().zip(&self.skip) {
+            let field = fields.next();
+            if *skip {
+                continue;
+            }
+            match field {
+                Some(field) => dict.set_item(key.bind(py), field)?,
+                None => dict.set_item(key.bind(py), &self.restval)?,
+            }
+        }
+        if let (Some(restkey), false) = (&self.restkey, extra.is_empty()) {
+            dict.set_item(restkey.bind(py), extra)?;
+        }
+        Ok(dict)
+    }
+}
+
+/// The keys of a reader's dicts, kept between reads.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct KeyCache {
+    keys: Arc<StdMutex<Option<Arc<DictKeys>>>>,
+}
+
+impl KeyCache {
+    /// Keys for `fieldnames`, made again only if they changed since the last call.
+    pub(crate) fn get(
+        &self,
+        py: Python<'_>,
+        fieldnames: &[String],
+        restkey: Option<&str>,
+        restval: &str,
+        keep_first: bool,
+    ) -> Arc<DictKeys> {
+        let mut cached = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        match &*cached {
+            Some(keys) if keys.fieldnames == fieldnames => Arc::clone(keys),
+            _ => {
+                let keys = Arc::new(DictKeys::new(py, fieldnames, restkey, restval, keep_first));
+                *cached = Some(Arc::clone(&keys));
+                keys
+            }
+        }
+    }
+}
@@ -0,0 +1,458 @@
+//! Key-based comparison of two CSV files.
+//!
+//! Rows are matched by key columns. Inputs larger than an in-memory threshold are first
+//! hash-partitioned into temporary files so that only one partition of the second file
+//! needs to be held in memory at a time. The partitions are removed when the
+//! comparison ends, including when it fails or the awaiting task is cancelled.
+
+use crate::errors::CsvIoError;
+use crate::ops::{
+    check_not_input, check_temp_dir, create_writer, csv_err, encode_key, extract_columns,
+    open_reader, read_header, resolve_columns, simple_dialect, spawn_cancellable_py, spill_dir,
+    Cancellation,
+};
+use crate::{validate_path, DialectConfig};
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+/// Combined input size above which both files are partitioned on disk.
+const PARTITION_BYTES: u64 = 256 * 1024 * 1024;
+
+/// A single difference between the two files. Rows are in the column order of `a`.
+enum DiffEntry {
+    Added(StringRecord),
+    Removed(StringRecord),
+    Changed {
+        before: StringRecord,
+        after: StringRecord,
+        columns: Vec<usize>,
+    },
+}
+
+/// Where differences go: collected for return to Python, or written to a report CSV.
+enum DiffSink {
+    Collect(Vec<DiffEntry>),
+    Report {
+        writer: Box<csv::Writer<std::fs::File>>,
+        path: String,
+        changed_columns_only: bool,
+        keys: Vec<usize>,
+    },
+}
+
+/// Counts of each kind of difference.
+#[derive(Default)]
+struct DiffCounts {
+    added: usize,
+    removed: usize,
+    changed: usize,
+}
+
+impl DiffSink {
+    fn emit(&mut self, entry: DiffEntry, counts: &mut DiffCounts) -> PyResult<()> {
+        match &entry {
+            DiffEntry::Added(_) => counts.added += 1,
+            DiffEntry::Removed(_) => counts.removed += 1,
+            DiffEntry::Changed { .. } => counts.changed += 1,
+        }
+        match self {
+            DiffSink::Collect(entries) => entries.push(entry),
+            DiffSink::Report {
+                writer,
+                path,
+                changed_columns_only,
+                keys,
+            } => {
+                let mut write = |kind: &str, record: &StringRecord, columns: Option<&[usize]>| {
+                    let fields = record.iter().enumerate().map(|(i, field)| match columns {
+                        Some(columns) if !columns.contains(&i) && !keys.contains(&i) => "",
+                        _ => field,
+                    });
+                    writer
+                        .write_record(std::iter::once(kind).chain(fields))
+                        .map_err(|e| csv_err(path, e))
+                };
+                match &entry {
+                    DiffEntry::Added(record) => write("added", record, None)?,
+                    DiffEntry::Removed(record) => write("removed", record, None)?,
+                    DiffEntry::Changed {
+                        before,
+                        after,
+                        columns,
+                    } => {
+                        let only = changed_columns_only.then_some(columns.as_slice());
+                        write("before", before, only)?;
+                        write("after", after, only)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One side of a partition: a CSV file plus the dialect it was written with.
+struct PartitionFile {
+    path: String,
+    dialect: Option<DialectConfig>,
+    has_header: bool,
+}
+
+impl PartitionFile {
+    fn open(&self) -> PyResult<csv::Reader<std::fs::File>> {
+        let mut builder = ReaderBuilder::new();
+        if let Some(dialect) = &self.dialect {
+            dialect.apply_to_reader(&mut builder, None);
+        }
+        let mut reader = builder
+            .has_headers(false)
+            .flexible(true)
+            .from_path(&self.path)
+            .map_err(|e| csv_err(&self.path, e))?;
+        if self.has_header {
+            read_header(&mut reader, &self.path)?;
+        }
+        Ok(reader)
+    }
+}
+
+/// Compare one partition of `a` against the same partition of `b`.
+///
+/// `b_order` maps each column of `a` to its position in `b`.
+fn diff_partition(
+    a: &PartitionFile,
+    b: &PartitionFile,
+    a_keys: &[usize],
+    b_order: &[usize],
+    sink: &mut DiffSink,
+    counts: &mut DiffCounts,
+) -> PyResult<()> {
+    let width = b_order.len();
+    let mut encoded = Vec::new();
+
+    // Load b, reordered to a's column order, keyed by the join columns
+    let mut b_rows: HashMap<Vec<u8>, VecDeque<(usize, StringRecord)>> = HashMap::new();
+    let mut b_reader = b.open()?;
+    let mut record = StringRecord::new();
+    let mut seq = 0usize;
+    loop {
+        match b_reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(&b.path, e)),
+        }
+        let reordered: StringRecord = b_order
+            .iter()
+            .map(|&i| record.get(i).unwrap_or(""))
+            .collect();
+        encode_key(&reordered, Some(a_keys), &mut encoded);
+        b_rows
+            .entry(encoded.clone())
+            .or_default()
+            .push_back((seq, reordered));
+        seq += 1;
+    }
+
+    // Stream a, matching each row with the next unmatched b row for the same key
+    let mut a_reader = a.open()?;
+    loop {
+        match a_reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(&a.path, e)),
+        }
+        let row: StringRecord = (0..width).map(|i| record.get(i).unwrap_or("")).collect();
+        encode_key(&row, Some(a_keys), &mut encoded);
+        match b_rows.get_mut(&encoded).and_then(VecDeque::pop_front) {
+            None => sink.emit(DiffEntry::Removed(row), counts)?,
+            Some((_, other)) => {
+                let columns: Vec<usize> =
+                    (0..width).filter(|&i| row.get(i) != other.get(i)).collect();
+                if !columns.is_empty() {
+                    sink.emit(
+                        DiffEntry::Changed {
+                            before: row,
+                            after: other,
+                            columns,
+                        },
+                        counts,
+                    )?;
+                }
+            }
+        }
+    }
+
+    // Whatever is left in b was added, reported in b's order
+    let mut added: Vec<(usize, StringRecord)> = b_rows.into_values().flatten().collect();
+    added.sort_by_key(|(seq, _)| *seq);
+    for (_, row) in added {
+        sink.emit(DiffEntry::Added(row), counts)?;
+    }
+    Ok(())
+}
+
+/// Hash-partition `path` by key into `partitions` temporary files.
+fn partition_file(
+    path: &str,
+    dialect: &DialectConfig,
+    keys: &[usize],
+    partitions: usize,
+    dir: &tempfile::TempDir,
+    prefix: &str,
+    cancellation: &Cancellation,
+) -> PyResult<Vec<PartitionFile>> {
+    let files: Vec<PartitionFile> = (0..partitions)
+        .map(|i| PartitionFile {
+            path: dir
+                .path()
+                .join(format!("{prefix}_{i}.csv"))
+                .to_string_lossy()
+                .into_owned(),
+            dialect: None,
+            has_header: false,
+        })
+        .collect();
+    let mut writers = files
+        .iter()
+        .map(|f| {
+            WriterBuilder::new()
+                .flexible(true)
+                .from_path(&f.path)
+                .map_err(|e| csv_err(&f.path, e))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let mut reader = open_reader(path, dialect)?;
+    read_header(&mut reader, path)?;
+    let mut record = StringRecord::new();
+    let mut encoded = Vec::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(path, e)),
+        }
+        cancellation.check()?;
+        encode_key(&record, Some(keys), &mut encoded);
+        let mut hasher = DefaultHasher::new();
+        encoded.hash(&mut hasher);
+        let i = (hasher.finish() % partitions as u64) as usize;
+        writers[i]
+            .write_record(&record)
+            .map_err(|e| csv_err(&files[i].path, e))?;
+    }
+    for (writer, file) in writers.iter_mut().zip(&files) {
+        writer
+            .flush()
+            .map_err(|e| CsvIoError::new_err(format!("Failed to write '{}': {e}", file.path)))?;
+    }
+    Ok(files)
+}
+
+/// Compare `a` and `b`, sending every difference to `sink`.
+fn diff_files(
+    a: &str,
+    b: &str,
+    key: &[String],
+    dialect: &DialectConfig,
+    sink: &mut DiffSink,
+    temp_dir: Option<&str>,
+    cancellation: &Cancellation,
+) -> PyResult<(StringRecord, DiffCounts)> {
+    let a_header = read_header(&mut open_reader(a, dialect)?, a)?.unwrap_or_default();
+    let b_header = read_header(&mut open_reader(b, dialect)?, b)?.unwrap_or_default();
+    let a_keys = resolve_columns(key, &a_header, a)?;
+    let b_keys = resolve_columns(key, &b_header, b)?;
+
+    let mut a_columns: Vec<&str> = a_header.iter().collect();
+    let mut b_columns: Vec<&str> = b_header.iter().collect();
+    a_columns.sort_unstable();
+    b_columns.sort_unstable();
+    if a_columns != b_columns {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Files have different columns: '{a}' has {:?}, '{b}' has {:?}",
+            a_header.iter().collect::<Vec<_>>(),
+            b_header.iter().collect::<Vec<_>>()
+        )));
+    }
+    let b_order: Vec<usize> = a_header
+        .iter()
+        .map(|name| b_header.iter().position(|h| h == name).unwrap_or(0))
+        .collect();
+
+    if let DiffSink::Report { writer, path, .. } = sink {
+        writer
+            .write_record(std::iter::once("_change").chain(a_header.iter()))
+            .map_err(|e| csv_err(path, e))?;
+    }
+
+    let size = |path: &str| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let partitions = ((size(a) + size(b)) / PARTITION_BYTES + 1) as usize;
+    let mut counts = DiffCounts::default();
+    if partitions == 1 {
+        let a_file = PartitionFile {
+            path: a.to_string(),
+            dialect: Some(dialect.clone()),
+            has_header: true,
+        };
+        let b_file = PartitionFile {
+            path: b.to_string(),
+            dialect: Some(dialect.clone()),
+            has_header: true,
+        };
+        diff_partition(&a_file, &b_file, &a_keys, &b_order, sink, &mut counts)?;
+    } else {
+        let dir = spill_dir(temp_dir, "diff")?;
+        let a_parts = partition_file(a, dialect, &a_keys, partitions, &dir, "a", cancellation)?;
+        let b_parts = partition_file(b, dialect, &b_keys, partitions, &dir, "b", cancellation)?;
+        for (a_part, b_part) in a_parts.iter().zip(&b_parts) {
+            cancellation.check()?;
+            diff_partition(a_part, b_part, &a_keys, &b_order, sink, &mut counts)?;
+        }
+    }
+    Ok((a_header, counts))
+}
+
+/// Convert a record to a dict, optionally limited to the given columns plus the keys.
+fn record_to_dict<'py>(
+    py: Python<'py>,
+    header: &StringRecord,
+    record: &StringRecord,
+    columns: Option<&[usize]>,
+    keys: &[usize],
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (i, (name, value)) in header.iter().zip(record.iter()).enumerate() {
+        if columns.is_none_or(|columns| columns.contains(&i) || keys.contains(&i)) {
+            dict.set_item(name, value)?;
+        }
+    }
+    Ok(dict)
+}
+
+/// Compare two CSV files by key columns.
+///
+/// # Arguments
+/// * `a` - The "before" CSV file
+/// * `b` - The "after" CSV file
+/// * `key` - Column name or list of names identifying a row
+/// * `changed_columns_only` - Only report the columns that changed (plus keys)
+/// * `dst` - Optional report CSV; when given, differences are streamed there instead of
+///   being returned
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+/// * `temp_dir` - Directory to partition large inputs under (default: None, the
+///   system's temporary directory)
+///
+/// Without `dst`, returns a dict with `added`, `removed` and `changed` lists. With
+/// `dst`, returns a dict of counts.
+#[pyfunction]
+#[pyo3(signature = (a, b, key, changed_columns_only = false, dst = None, delimiter = None, quotechar = None, temp_dir = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn diff(
+    py: Python<'_>,
+    a: String,
+    b: String,
+    key: &Bound<'_, PyAny>,
+    changed_columns_only: bool,
+    dst: Option<String>,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+    temp_dir: Option<String>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&a)?;
+    validate_path(&b)?;
+    if let Some(dst) = &dst {
+        validate_path(dst)?;
+    }
+    check_temp_dir(temp_dir.as_deref())?;
+    let key = extract_columns(key, "key")?;
+    let dialect = simple_dialect(delimiter, quotechar)?;
+
+    spawn_cancellable_py(py, move |cancellation| {
+        let mut sink = match &dst {
+            Some(path) => {
+                check_not_input(path, &[&a, &b])?;
+                let header = read_header(&mut open_reader(&a, &dialect)?, &a)?.unwrap_or_default();
+                DiffSink::Report {
+                    writer: Box::new(create_writer(path, &dialect)?),
+                    path: path.clone(),
+                    changed_columns_only,
+                    keys: resolve_columns(&key, &header, &a)?,
+                }
+            }
+            None => DiffSink::Collect(Vec::new()),
+        };
+        let (header, counts) = diff_files(
+            &a,
+            &b,
+            &key,
+            &dialect,
+            &mut sink,
+            temp_dir.as_deref(),
+            cancellation,
+        )?;
+        let keys = resolve_columns(&key, &header, &a)?;
+
+        Python::attach(|py| -> PyResult<Py<PyAny>> {
+            let result = PyDict::new(py);
+            match sink {
+                DiffSink::Report {
+                    mut writer, path, ..
+                } => {
+                    writer.flush().map_err(|e| {
+                        CsvIoError::new_err(format!("Failed to flush file '{path}': {e}"))
+                    })?;
+                    result.set_item("added", counts.added)?;
+                    result.set_item("removed", counts.removed)?;
+                    result.set_item("changed", counts.changed)?;
+                }
+                DiffSink::Collect(entries) => {
+                    let added = PyList::empty(py);
+                    let removed = PyList::empty(py);
+                    let changed = PyList::empty(py);
+                    for entry in &entries {
+                        match entry {
+                            DiffEntry::Added(record) => {
+                                added.append(record_to_dict(py, &header, record, None, &keys)?)?
+                            }
+                            DiffEntry::Removed(record) => {
+                                removed.append(record_to_dict(py, &header, record, None, &keys)?)?
+                            }
+                            DiffEntry::Changed {
+                                before,
+                                after,
+                                columns,
+                            } => {
+                                let only = changed_columns_only.then_some(columns.as_slice());
+                                let item = PyDict::new(py);
+                                let key_dict = PyDict::new(py);
+                                for &k in &keys {
+                                    key_dict.set_item(&header[k], &before[k])?;
+                                }
+                                item.set_item("key", key_dict)?;
+                                item.set_item(
+                                    "before",
+                                    record_to_dict(py, &header, before, only, &keys)?,
+                                )?;
+                                item.set_item(
+                                    "after",
+                                    record_to_dict(py, &header, after, only, &keys)?,
+                                )?;
+                                changed.append(item)?;
+                            }
+                        }
+                    }
+                    result.set_item("added", added)?;
+                    result.set_item("removed", removed)?;
+                    result.set_item("changed", changed)?;
+                }
+            }
+            Ok(result.into_any().unbind())
+        })
+    })
+}
@@ -0,0 +1,70 @@
+//! Header drift across the files of a stream (`check_headers=` on `Reader`).
+//!
+//! The header row of every file after the first is skipped, so a part file whose
+//! columns were added, dropped or reordered would have its fields returned under the
+//! first file's names. With a check, the header of each file is compared, as written,
+//! with the first file's or with the header given, and one that differs raises
+//! `SchemaError` naming the file and the columns that differ.
+
+use crate::errors::{ErrorContext, SchemaError};
+use pyo3::prelude::*;
+use std::sync::OnceLock;
+
+/// The header every file of a stream must start with.
+#[derive(Debug)]
+pub(crate) struct HeaderCheck {
+    expected: OnceLock<Vec<String>>,
+    given: bool, // `expected` was passed in rather than taken from the first file
+}
+
+impl HeaderCheck {
+    /// A check against `expected`, or against the first header checked if None.
+    pub(crate) fn new(expected: Option<Vec<String>>) -> Self {
+        let given = expected.is_some();
+        HeaderCheck {
+            expected: expected.map(OnceLock::from).unwrap_or_default(),
+            given,
+        }
+    }
+
+    /// Compare `found`, the header of the file at `path`, with the expected one.
+    pub(crate) fn check(&self, path: &str, found: &[String]) -> PyResult<()> {
+        let expected = self.expected.get_or_init(|| found.to_vec());
+        if expected == found {
+            return Ok(());
+        }
+        let missing: Vec<&String> = expected.iter().filter(|n| !found.contains(n)).collect();
+        let extra: Vec<&String> = found.iter().filter(|n| !expected.contains(n)).collect();
+        let mut differences = Vec::new();
+        if !missing.is_empty() {
+            differences.push(format!("missing {missing:?}"));
+        }
+        if !extra.is_empty() {
+            differences.push(format!("unexpected {extra:?}"));
+        }
+        if differences.is_empty() {
+            differences.push(format!("columns in another order, {found:?}"));
+        }
+        // The first position where the names differ
+        let column = expected
+            .iter()
+            .zip(found)
+            .position(|(a, b)| a != b)
+            .unwrap_or(expected.len().min(found.len()));
+        let context = ErrorContext {
+            column: Some(column),
+            byte_offset: Some(0),
+            field: found.get(column).cloned(),
+            ..ErrorContext::default()
+        };
+        let against = if self.given {
+            "the expected header"
+        } else {
+            "the first file's header"
+        };
+        Err(context.attach(SchemaError::new_err(format!(
+            "Header of file '{path}' differs from {against}: {}",
+            differences.join(", ")
+        ))))
+    }
+}
@@ -0,0 +1,317 @@
+//! The rapcsv exception hierarchy.
+//!
+//! ```text
+//! RapcsvError
+//! ├── CsvParseError (also exported as CSVError)
+//! │   ├── CSVFieldCountError
+//! │   ├── SchemaError
+//! │   └── EncodingError (also an OSError)
+//! ├── CsvIoError (also an OSError)
+//! │   ├── FileChangedError
+//! │   └── ChecksumError
+//! └── QuotaExceeded
+//! ```
+//!
+//! Errors that were raised as `IOError` before the hierarchy existed are still
+//! `OSError`s, so existing handlers keep working. A class with two bases can't be
+//! declared with `create_exception!`, so those are created with `type()` the first
+//! time they are needed and raised through the same `new_err()`.
+//!
+//! Every class carries `row`, `line`, `column`, `byte_offset`, `field` and `snippet`
+//! attributes, None unless the error could be placed (see `ErrorContext`). Parse
+//! errors also give the location in their message, so the bad record of a large file
+//! can be found from the message alone.
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyOSError};
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyDict, PyTuple, PyType};
+use pyo3::PyErrArguments;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+create_exception!(
+    _rapcsv,
+    RapcsvError,
+    PyException,
+    "Base class of the errors rapcsv raises."
+);
+create_exception!(
+    _rapcsv,
+    CsvParseError,
+    RapcsvError,
+    "The input is not valid CSV for the dialect."
+);
+create_exception!(
+    _rapcsv,
+    CSVFieldCountError,
+    CsvParseError,
+    "A row has a different number of fields than expected."
+);
+create_exception!(
+    _rapcsv,
+    SchemaError,
+    CsvParseError,
+    "A field cannot be converted to the type its column is declared as."
+);
+create_exception!(
+    _rapcsv,
+    QuotaExceeded,
+    RapcsvError,
+    "A write would take a Writer past its max_rows or max_bytes limit."
+);
+
+/// An exception class created at run time, for classes with more than one base.
+pub(crate) struct DynamicError {
+    name: &'static str,
+    doc: &'static str,
+    bases: fn(Python<'_>) -> PyResult<Bound<'_, PyTuple>>,
+    class: PyOnceLock<Py<PyType>>,
+}
+
+impl DynamicError {
+    /// The class, created on first use.
+    pub(crate) fn type_object<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyType>> {
+        let class = self.class.get_or_try_init(py, || -> PyResult<_> {
+            let namespace = PyDict::new(py);
+            namespace.set_item("__module__", "_rapcsv")?;
+            namespace.set_item("__doc__", self.doc)?;
+            let class = py
+                .get_type::<PyType>()
+                .call1((self.name, (self.bases)(py)?, namespace))?;
+            Ok(class.cast_into::<PyType>()?.unbind())
+        })?;
+        Ok(class.bind(py).clone())
+    }
+
+    /// An error of this class with `args`, as `create_exception!` types' `new_err()`.
+    pub(crate) fn new_err<A>(&'static self, args: A) -> PyErr
+    where
+        A: PyErrArguments + Send + Sync + 'static,
+    {
+        Python::attach(|py| match self.type_object(py) {
+            Ok(class) => PyErr::from_type(class, args),
+            Err(e) => e,
+        })
+    }
+}
+
+pub(crate) static CSV_IO_ERROR: DynamicError = DynamicError {
+    name: "CsvIoError",
+    doc: "Reading or writing a file failed.",
+    bases: |py| {
+        PyTuple::new(
+            py,
+            [py.get_type::<RapcsvError>(), py.get_type::<PyOSError>()],
+        )
+    },
+    class: PyOnceLock::new(),
+};
+
+pub(crate) static ENCODING_ERROR: DynamicError = DynamicError {
+    name: "EncodingError",
+    doc: "The input is not valid in its encoding.",
+    bases: |py| {
+        PyTuple::new(
+            py,
+            [py.get_type::<CsvParseError>(), py.get_type::<PyOSError>()],
+        )
+    },
+    class: PyOnceLock::new(),
+};
+
+pub(crate) static FILE_CHANGED_ERROR: DynamicError = DynamicError {
+    name: "FileChangedError",
+    doc: "A file changed on disk while it was being read.",
+    bases: |py| PyTuple::new(py, [CSV_IO_ERROR.type_object(py)?]),
+    class: PyOnceLock::new(),
+};
+
+pub(crate) static CHECKSUM_ERROR: DynamicError = DynamicError {
+    name: "ChecksumError",
+    doc: "A file's digest does not match the one it must have.",
+    bases: |py| PyTuple::new(py, [CSV_IO_ERROR.type_object(py)?]),
+    class: PyOnceLock::new(),
+};
+
+/// `CsvIoError`: reading or writing a file failed.
+pub(crate) struct CsvIoError;
+
+impl CsvIoError {
+    pub(crate) fn new_err<A: PyErrArguments + Send + Sync + 'static>(args: A) -> PyErr {
+        CSV_IO_ERROR.new_err(args)
+    }
+}
+
+/// `EncodingError`: the input is not valid in its encoding.
+pub(crate) struct EncodingError;
+
+impl EncodingError {
+    pub(crate) fn new_err<A: PyErrArguments + Send + Sync + 'static>(args: A) -> PyErr {
+        ENCODING_ERROR.new_err(args)
+    }
+}
+
+/// `FileChangedError`: a file changed on disk while it was being read.
+pub(crate) struct FileChangedError;
+
+impl FileChangedError {
+    pub(crate) fn new_err<A: PyErrArguments + Send + Sync + 'static>(args: A) -> PyErr {
+        FILE_CHANGED_ERROR.new_err(args)
+    }
+}
+
+/// `ChecksumError`: a file's digest does not match the one it must have.
+pub(crate) struct ChecksumError;
+
+impl ChecksumError {
+    pub(crate) fn new_err<A: PyErrArguments + Send + Sync + 'static>(args: A) -> PyErr {
+        CHECKSUM_ERROR.new_err(args)
+    }
+}
+
+/// Most characters of the offending line kept in `snippet`.
+const SNIPPET_CHARS: usize = 80;
+
+/// The first line of `bytes`, shortened to `SNIPPET_CHARS` characters.
+pub(crate) fn snippet(bytes: &[u8]) -> String {
+    let line = match memchr::memchr(b'\n', bytes) {
+        Some(end) => &bytes[..end],
+        None => bytes,
+    };
+    let line = String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line));
+    match line.char_indices().nth(SNIPPET_CHARS) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line.into_owned(),
+    }
+}
+
+/// The line of `path` starting at `offset`, for errors raised after the data has
+/// gone. None if the file can no longer be read there.
+pub(crate) fn snippet_at(path: &str, offset: u64) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    // Enough bytes for SNIPPET_CHARS characters of up to four bytes each
+    let mut bytes = Vec::new();
+    file.take(SNIPPET_CHARS as u64 * 4)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    Some(snippet(&bytes))
+}
+
+/// Where in the input an error happened, set as attributes of the exception.
+#[derive(Default)]
+pub(crate) struct ErrorContext {
+    pub(crate) row: Option<usize>, // 0-based record index, the header included
+    pub(crate) line: Option<u64>,  // 1-based line the record starts on
+    pub(crate) column: Option<usize>, // 0-based field index
+    pub(crate) byte_offset: Option<u64>, // Of the record in its file
+    pub(crate) field: Option<String>, // The offending field as read
+    pub(crate) snippet: Option<String>, // Start of the offending line, see `snippet()`
+}
+
+impl ErrorContext {
+    /// The location of a csv crate error. For a field count mismatch the column is
+    /// the first field missing or extra.
+    pub(crate) fn from_csv(e: &csv::Error) -> Self {
+        let mut context = ErrorContext::default();
+        if let Some(position) = e.position() {
+            context.row = Some(position.record() as usize);
+            context.line = Some(position.line());
+            context.byte_offset = Some(position.byte());
+        }
+        match e.kind() {
+            csv::ErrorKind::Utf8 { err, .. } => context.column = Some(err.field()),
+            csv::ErrorKind::UnequalLengths {
+                expected_len, len, ..
+            } => context.column = Some((*expected_len).min(*len) as usize),
+            _ => {}
+        }
+        context
+    }
+
+    /// The known parts of the location for a message, as ` (line 3, byte 8, field 2;
+    /// near "a,b,c")`. Empty when nothing is known.
+    pub(crate) fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(line) = self.line {
+            parts.push(format!("line {line}"));
+        }
+        if let Some(byte_offset) = self.byte_offset {
+            parts.push(format!("byte {byte_offset}"));
+        }
+        if let Some(column) = self.column {
+            parts.push(format!("field {column}"));
+        }
+        let mut text = parts.join(", ");
+        if let Some(snippet) = &self.snippet {
+            if !text.is_empty() {
+                text.push_str("; ");
+            }
+            text.push_str(&format!("near {snippet:?}"));
+        }
+        if text.is_empty() {
+            text
+        } else {
+            format!(" ({text})")
+        }
+    }
+
+    /// Set the known parts of the context on `err`.
+    pub(crate) fn attach(&self, err: PyErr) -> PyErr {
+        Python::attach(|py| {
+            let value = err.value(py);
+            let set = || -> PyResult<()> {
+                if let Some(row) = self.row {
+                    value.setattr("row", row)?;
+                }
+                if let Some(line) = self.line {
+                    value.setattr("line", line)?;
+                }
+                if let Some(column) = self.column {
+                    value.setattr("column", column)?;
+                }
+                if let Some(byte_offset) = self.byte_offset {
+                    value.setattr("byte_offset", byte_offset)?;
+                }
+                if let Some(field) = &self.field {
+                    value.setattr("field", field)?;
+                }
+                if let Some(snippet) = &self.snippet {
+                    value.setattr("snippet", snippet)?;
+                }
+                Ok(())
+            };
+            match set() {
+                Ok(()) => err,
+                Err(e) => e,
+            }
+        })
+    }
+}
+
+/// Register the exception classes on the module, with the context attributes every
+/// class inherits from `RapcsvError`.
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    let base = py.get_type::<RapcsvError>();
+    for name in ["row", "line", "column", "byte_offset", "field", "snippet"] {
+        base.setattr(name, py.None())?;
+    }
+    m.add("RapcsvError", base)?;
+    m.add("CsvParseError", py.get_type::<CsvParseError>())?;
+    m.add("CSVError", py.get_type::<CsvParseError>())?;
+    m.add("CSVFieldCountError", py.get_type::<CSVFieldCountError>())?;
+    m.add("SchemaError", py.get_type::<SchemaError>())?;
+    m.add("QuotaExceeded", py.get_type::<QuotaExceeded>())?;
+    for error in [
+        &CSV_IO_ERROR,
+        &ENCODING_ERROR,
+        &FILE_CHANGED_ERROR,
+        &CHECKSUM_ERROR,
+    ] {
+        m.add(error.name, error.type_object(py)?)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+//! Writing without quotes (`quoting=csv.QUOTE_NONE` on `Writer`).
+//!
+//! Fields are never quoted: a delimiter, quote character, escape character or line
+//! break inside a field is written with `escapechar` in front of it instead, as
+//! Python's `csv` module does, for loaders that don't understand quoting. Without an
+//! `escapechar`, quote characters are written as they are, since readers with
+//! `quoting=0` take them literally (as TSV does), and any other character that needs
+//! escaping raises `ValueError`.
+
+use crate::DialectConfig;
+use csv::Terminator;
+use pyo3::prelude::*;
+
+/// Whether `b` must be escaped in a field written with `dialect`.
+fn special(b: u8, dialect: &DialectConfig) -> bool {
+    b == dialect.delimiter
+        || (b == dialect.quotechar && dialect.escapechar.is_some())
+        || Some(b) == dialect.escapechar
+        || b == b'\r'
+        || b == b'\n'
+        || matches!(dialect.lineterminator, Terminator::Any(t) if b == t)
+}
+
+/// Encode `rows` with every special character escaped, line terminators included.
+pub(crate) fn encode_rows(dialect: &DialectConfig, rows: &[Vec<String>]) -> PyResult<Vec<u8>> {
+    let terminator: &[u8] = match dialect.lineterminator {
+        Terminator::Any(b) => &[b],
+        _ => b"\r\n",
+    };
+    let mut out = Vec::new();
+    for row in rows {
+        if let [field] = &row[..] {
+            if field.is_empty() {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "a record of one empty field can't be written without quotes (QUOTE_NONE)",
+                ));
+            }
+        }
+        for (i, field) in row.iter().enumerate() {
+            if i > 0 {
+                out.push(dialect.delimiter);
+            }
+            for &b in field.as_bytes() {
+                if special(b, dialect) {
+                    let Some(escapechar) = dialect.escapechar else {
+                        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                            "field {field:?} needs escaping, but quoting is QUOTE_NONE and no escapechar is set"
+                        )));
+                    };
+                    out.push(escapechar);
+                }
+                out.push(b);
+            }
+        }
+        out.extend_from_slice(terminator);
+    }
+    Ok(out)
+}
@@ -0,0 +1,297 @@
+//! Fixed-width file reading.
+//!
+//! `FixedWidthReader` reads files whose columns sit at fixed character positions, such
+//! as mainframe extracts. It accepts the same path-or-handle argument as the CSV
+//! `Reader` and streams the file in chunks through the shared source helpers; lines are
+//! cut into fields outside the GIL.
+
+use crate::awaitable::future_into_py;
+use crate::decode::{Utf8Decoder, Utf8Mode};
+use crate::paths::FilePath;
+use crate::socket::Conn;
+use crate::{read_chunk, resolve_source, Access, DEFAULT_BUFFER_SIZE};
+use pyo3::exceptions::{PyStopAsyncIteration, PyValueError};
+use pyo3::prelude::*;
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::io::BufReader;
+use tokio::sync::Mutex;
+
+/// Character ranges of the columns: `(start, end)`, with `end` None for the rest of
+/// the line.
+type Columns = Vec<(usize, Option<usize>)>;
+
+/// Build the column ranges from `widths` or `colspecs` (exactly one must be given).
+fn columns(
+    widths: Option<Vec<usize>>,
+    colspecs: Option<Vec<(usize, Option<usize>)>>,
+) -> PyResult<Columns> {
+    match (widths, colspecs) {
+        (Some(widths), None) => {
+            if widths.is_empty() || widths.contains(&0) {
+                return Err(PyValueError::new_err(
+                    "widths must be a non-empty list of positive integers",
+                ));
+            }
+            let mut start = 0;
+            Ok(widths
+                .into_iter()
+                .map(|width| {
+                    start += width;
+                    (start - width, Some(start))
+                })
+                .collect())
+        }
+        (None, Some(colspecs)) => {
+            if colspecs.is_empty() {
+                return Err(PyValueError::new_err("colspecs must not be empty"));
+            }
+            for &(start, end) in &colspecs {
+                if end.is_some_and(|end| end <= start) {
+                    return Err(PyValueError::new_err(format!(
+                        "colspec ({start}, {}) must end after it starts",
+                        end.unwrap_or_default()
+                    )));
+                }
+            }
+            Ok(colspecs)
+        }
+        (Some(_), Some(_)) => Err(PyValueError::new_err(
+            "pass either widths or colspecs, not both",
+        )),
+        (None, None) => Err(PyValueError::new_err(
+            "FixedWidthReader requires widths or colspecs",
+        )),
+    }
+}
+
+/// Cut `line` into fields at the character positions of `columns`. Columns past the end
+/// of a short line are empty.
+fn split_line(line: &str, columns: &Columns, strip: bool) -> Vec<String> {
+    // Character positions are byte positions in ASCII lines; otherwise map them
+    let bounds: Option<Vec<usize>> = (!line.is_ascii()).then(|| {
+        line.char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(line.len()))
+            .collect()
+    });
+    let at = |position: usize| match &bounds {
+        None => position.min(line.len()),
+        Some(bounds) => bounds[position.min(bounds.len() - 1)],
+    };
+    columns
+        .iter()
+        .map(|&(start, end)| {
+            let field = &line[at(start)..end.map_or(line.len(), at)];
+            if strip {
+                field.trim().to_string()
+            } else {
+                field.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Shared reading state of a `FixedWidthReader`.
+#[derive(Clone)]
+struct LineSource {
+    access: Access,
+    path: FilePath,
+    file: Arc<Mutex<Option<BufReader<Conn>>>>,
+    file_handle: Arc<StdMutex<Option<Py<PyAny>>>>,
+    event_loop: Arc<StdMutex<Option<Py<PyAny>>>>,
+    buffer: Arc<Mutex<String>>,
+    line_num: Arc<Mutex<usize>>,
+    read_size: usize,
+    decoder: Arc<Utf8Decoder>,
+    columns: Arc<Columns>,
+    strip: bool,
+}
+
+impl LineSource {
+    /// Read the next non-blank line and cut it into fields. Returns `None` at EOF.
+    async fn next_row(&self) -> PyResult<Option<Vec<String>>> {
+        let mut buffer = self.buffer.lock().await;
+        let mut line_num = self.line_num.lock().await;
+        let mut eof = false;
+        loop {
+            // Take the next complete line, or whatever is left once EOF is reached
+            let line = match buffer.find('\n') {
+                Some(idx) => Some(buffer.drain(..=idx).collect::<String>()),
+                None if eof && !buffer.is_empty() => Some(std::mem::take(&mut *buffer)),
+                None if eof => return Ok(None),
+                None => None,
+            };
+
+            if let Some(line) = line {
+                *line_num += 1;
+                let line = line.trim_end_matches(['\n', '\r']);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Ok(Some(split_line(line, &self.columns, self.strip)));
+            }
+
+            let (chunk, at_eof) = read_chunk(
+                self.access,
+                &self.path,
+                &self.file,
+                &self.file_handle,
+                &self.event_loop,
+                self.read_size,
+                DEFAULT_BUFFER_SIZE,
+                None, // No retries
+                &self.decoder,
+                None, // Bytes are not checked for gzip
+            )
+            .await?;
+            buffer.push_str(&chunk);
+            eof = at_eof;
+        }
+    }
+}
+
+/// Async fixed-width file reader.
+///
+/// Cuts each line into fields at fixed character positions, given as column `widths`
+/// or as `colspecs` ranges. Accepts a file path or an async file-like object, just like
+/// the CSV `Reader`.
+///
+/// # Example
+///
+/// ```python
+/// from rapcsv import FixedWidthReader
+///
+/// async with FixedWidthReader("extract.dat", widths=[8, 20, 10]) as reader:
+///     async for account, name, balance in reader:
+///         print(account, float(balance))
+/// ```
+#[pyclass]
+pub(crate) struct FixedWidthReader {
+    source: LineSource,
+}
+
+#[pymethods]
+impl FixedWidthReader {
+    /// Open a fixed-width file for reading.
+    ///
+    /// # Arguments
+    /// * `path_or_handle` - Path to the file or an async file-like object
+    /// * `widths` - Width in characters of each column, left to right
+    /// * `colspecs` - `(start, end)` character range of each column, 0-based and
+    ///   half-open, with `end` None for the rest of the line; instead of `widths`
+    /// * `strip` - Strip surrounding whitespace from fields (default: true)
+    /// * `read_size` - Buffer size for reading chunks (default: 8192)
+    #[new]
+    #[pyo3(signature = (path_or_handle, widths = None, colspecs = None, strip = true, read_size = None))]
+    fn new(
+        py: Python<'_>,
+        path_or_handle: &Bound<'_, PyAny>,
+        widths: Option<Vec<usize>>,
+        colspecs: Option<Vec<(usize, Option<usize>)>>,
+        strip: bool,
+        read_size: Option<usize>,
+    ) -> PyResult<Self> {
+        let columns = columns(widths, colspecs)?;
+        let (source, path, file_handle, event_loop) = resolve_source(py, path_or_handle)?;
+        Ok(FixedWidthReader {
+            source: LineSource {
+                access: source.access(),
+                path,
+                file: Arc::new(Mutex::new(None)),
+                file_handle,
+                event_loop,
+                buffer: Arc::new(Mutex::new(String::new())),
+                line_num: Arc::new(Mutex::new(0)),
+                read_size: read_size.unwrap_or(8192),
+                decoder: Arc::new(Utf8Decoder::new(Utf8Mode::Strict)),
+                columns: Arc::new(columns),
+                strip,
+            },
+        })
+    }
+
+    /// Get the current line number (1-based).
+    #[getter]
+    fn line_num(&self) -> PyResult<usize> {
+        Ok(*self.source.line_num.try_lock().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Cannot access line_num concurrently")
+        })?)
+    }
+
+    /// Read the next row. Returns None at EOF.
+    fn read_row(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        self_.next_future(false)
+    }
+
+    /// Read up to `n` rows at once.
+    fn read_rows(self_: PyRef<Self>, n: usize) -> PyResult<Py<PyAny>> {
+        let source = self_.source.clone();
+        Python::attach(|py| {
+            let future = async move {
+                let mut rows = Vec::new();
+                while rows.len() < n {
+                    match source.next_row().await? {
+                        Some(row) => rows.push(row),
+                        None => break,
+                    }
+                }
+                Ok(rows)
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// Async iterator protocol - returns self.
+    fn __aiter__(slf: PyRef<Self>) -> PyResult<Py<Self>> {
+        Ok(slf.into())
+    }
+
+    /// Async iterator next - returns the next row or raises StopAsyncIteration.
+    fn __anext__(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        self_.next_future(true)
+    }
+
+    /// Async context manager entry.
+    fn __aenter__(slf: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let slf: Py<Self> = slf.into();
+        Python::attach(|py| {
+            let future = async move { Ok(slf) };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// Async context manager exit.
+    fn __aexit__(
+        &self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_val: Option<&Bound<'_, PyAny>>,
+        _exc_tb: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        let source = self.source.clone();
+        Python::attach(|py| {
+            let future = async move {
+                source.file.lock().await.take();
+                source.buffer.lock().await.clear();
+                Ok(())
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+}
+
+impl FixedWidthReader {
+    /// Build the awaitable for the next row. With `stop_at_eof` the awaitable raises
+    /// StopAsyncIteration at EOF instead of returning None.
+    fn next_future(&self, stop_at_eof: bool) -> PyResult<Py<PyAny>> {
+        let source = self.source.clone();
+        Python::attach(|py| {
+            let future = async move {
+                match source.next_row().await? {
+                    None if stop_at_eof => Err(PyStopAsyncIteration::new_err(())),
+                    row => Ok(row),
+                }
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+}
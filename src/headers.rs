@@ -0,0 +1,167 @@
+//! Header row names (`normalize_headers=` and `duplicate_headers=`).
+//!
+//! The rules rewrite the header row when a reader reads it, so the header row it
+//! returns, names in `columns=` and `schema=`, `Row` objects and `DictReader` keys all
+//! see the same names.
+//!
+//! `normalize_headers=True` trims, lowercases and snake_cases each name (`" First
+//! Name"` and `"firstName"` both become `first_name`); a callable is given each name
+//! instead. Normalizing comes first, so names it makes equal count as duplicates.
+//!
+//! A header such as `id,name,id` has a name that means two columns: "keep_first"
+//! leaves the names as they are and a name refers to its first column, "suffix"
+//! renames the repeats `id_1`, `id_2`, ... (skipping names the header already holds),
+//! and "error" raises `CSVError` when the header is read.
+
+use crate::errors::CsvParseError;
+use pyo3::prelude::*;
+use pyo3::types::PyBool;
+use std::collections::HashSet;
+
+/// How header names are normalized.
+#[derive(Debug)]
+enum Normalize {
+    /// Trimmed, lowercased snake_case.
+    Snake,
+    /// Each name passed through a Python callable.
+    Custom(Py<PyAny>),
+}
+
+/// Trim, lowercase and snake_case a header name: runs of other characters than
+/// letters and digits become one `_`, as do lower-to-upper case changes (`totalAmount`)
+/// and the end of an acronym (`HTTPStatus`).
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.trim().chars().collect();
+    let mut out = String::with_capacity(name.len());
+    let mut separate = false;
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            separate = true;
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                separate = true;
+            }
+        }
+        if separate && !out.is_empty() {
+            out.push('_');
+        }
+        separate = false;
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// What a reader does to the names of its header row.
+#[derive(Debug, Default)]
+pub(crate) struct HeaderRules {
+    normalize: Option<Normalize>,
+    duplicates: DuplicateHeaders,
+}
+
+impl HeaderRules {
+    /// Rules from the `normalize_headers` (True, False/None or a callable) and
+    /// `duplicate_headers` arguments.
+    pub(crate) fn from_args(
+        normalize: Option<&Bound<'_, PyAny>>,
+        duplicates: DuplicateHeaders,
+    ) -> PyResult<Self> {
+        let normalize = match normalize {
+            None => None,
+            Some(value) if value.is_instance_of::<PyBool>() => {
+                value.extract::<bool>()?.then_some(Normalize::Snake)
+            }
+            Some(value) if value.is_callable() => Some(Normalize::Custom(value.clone().unbind())),
+            Some(_) => {
+                return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                    "normalize_headers must be a bool or a callable taking and returning str",
+                ))
+            }
+        };
+        Ok(HeaderRules {
+            normalize,
+            duplicates,
+        })
+    }
+
+    /// Apply the rules to the names of a header row, in place.
+    pub(crate) fn apply(&self, names: &mut [String]) -> PyResult<()> {
+        match &self.normalize {
+            None => {}
+            Some(Normalize::Snake) => {
+                for name in names.iter_mut() {
+                    *name = snake_case(name);
+                }
+            }
+            Some(Normalize::Custom(function)) => Python::attach(|py| {
+                for name in names.iter_mut() {
+                    let normalized = function.call1(py, (name.as_str(),))?;
+                    *name = normalized.extract(py).map_err(|_| {
+                        PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                            "normalize_headers must return str, not for '{name}'"
+                        ))
+                    })?;
+                }
+                Ok::<_, PyErr>(())
+            })?,
+        }
+        self.duplicates.apply(names)
+    }
+}
+
+/// What to do with a name the header holds more than once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum DuplicateHeaders {
+    /// Keep the names; a name refers to its first column.
+    #[default]
+    KeepFirst,
+    /// Rename later repeats with `_1`, `_2`, ... suffixes.
+    Suffix,
+    /// Raise `CSVError`.
+    Error,
+}
+
+impl DuplicateHeaders {
+    pub(crate) fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "keep_first" => Ok(DuplicateHeaders::KeepFirst),
+            "suffix" => Ok(DuplicateHeaders::Suffix),
+            "error" => Ok(DuplicateHeaders::Error),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "duplicate_headers must be 'keep_first', 'suffix' or 'error', got '{other}'"
+            ))),
+        }
+    }
+
+    /// Apply the policy to the names of a header row, in place.
+    pub(crate) fn apply(self, names: &mut [String]) -> PyResult<()> {
+        if self == DuplicateHeaders::KeepFirst {
+            return Ok(());
+        }
+        let taken: HashSet<String> = names.iter().cloned().collect();
+        if taken.len() == names.len() {
+            return Ok(());
+        }
+        let mut seen = HashSet::with_capacity(names.len());
+        for name in names.iter_mut() {
+            if seen.insert(name.clone()) {
+                continue;
+            }
+            if self == DuplicateHeaders::Error {
+                return Err(CsvParseError::new_err(format!(
+                    "Duplicate column name '{name}' in header"
+                )));
+            }
+            let renamed = (1..)
+                .map(|n| format!("{name}_{n}"))
+                .find(|candidate| !taken.contains(candidate) && !seen.contains(candidate))
+                .expect("an unused suffix exists");
+            seen.insert(renamed.clone());
+            *name = renamed;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+//! Record-start index of a file, shared by every Reader in the process.
+//!
+//! `Reader.seek()` needs the byte offset and line number each record starts at. The
+//! index is built by one streaming pass that tracks quotes, like `Reader.split()`, and
+//! kept in a process-wide cache keyed by path and the bytes that delimit records. An
+//! entry is reused only while the file's size and modification time are unchanged,
+//! and dropped once it is found stale. The cache holds at most `CACHE_LIMIT` bytes of
+//! indexes, evicting the least recently used ones to make room.
+//! With the reader's `max_memory`, an index that would take more bytes is not built
+//! (or not used from the cache): `seek()` raises `MemoryError` instead.
+
+use crate::errors::CsvIoError;
+use crate::paths::{self, FilePath};
+use crate::{memory, DialectConfig};
+use csv::Terminator;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use std::time::SystemTime;
+
+/// Where each record starts: its byte offset and the line number before it, as the
+/// parser counts lines. Blank lines are skipped by the parser and belong to the record
+/// after them.
+pub(crate) struct RowIndex {
+    pub(crate) starts: Vec<(u64, usize)>,
+    pub(crate) end: (u64, usize), // Offset and line number after the last record
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+impl RowIndex {
+    /// Bytes of memory the index holds.
+    fn bytes(&self) -> usize {
+        std::mem::size_of::<RowIndex>() + self.starts.capacity() * START_SIZE
+    }
+}
+
+/// Cache key: the file and the bytes its records are delimited by.
+type Key = (PathBuf, Option<u8>, u8);
+
+/// Bytes of indexes the cache keeps, about four million record starts.
+const CACHE_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Cached indexes, each with the tick of its last use.
+#[derive(Default)]
+struct Cache {
+    entries: HashMap<Key, (Arc<RowIndex>, u64)>,
+    bytes: usize,
+    tick: u64,
+}
+
+impl Cache {
+    fn remove(&mut self, key: &Key) {
+        if let Some((index, _)) = self.entries.remove(key) {
+            self.bytes -= index.bytes();
+        }
+    }
+
+    /// Add an index, evicting the least recently used ones to stay within
+    /// `CACHE_LIMIT`. An index larger than that on its own isn't kept.
+    fn insert(&mut self, key: Key, index: Arc<RowIndex>) {
+        self.remove(&key);
+        if index.bytes() > CACHE_LIMIT {
+            return;
+        }
+        while self.bytes + index.bytes() > CACHE_LIMIT {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+        self.tick += 1;
+        self.bytes += index.bytes();
+        self.entries.insert(key, (index, self.tick));
+    }
+}
+
+static CACHE: OnceLock<StdMutex<Cache>> = OnceLock::new();
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+fn cache() -> std::sync::MutexGuard<'static, Cache> {
+    CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// The byte ending a record: the `\n` of `\r\n` unless the dialect sets another.
+pub(crate) fn terminator(dialect: &DialectConfig) -> u8 {
+    match dialect.lineterminator {
+        Terminator::Any(b) => b,
+        _ => b'\n',
+    }
+}
+
+/// Stream the file at `path` once, calling `on_record` with the offset just past each
+/// record, the records so far and the lines so far. Stops early when it returns false.
+/// Returns the file's size.
+pub(crate) fn scan_records(
+    path: &FilePath,
+    dialect: &DialectConfig,
+    mut on_record: impl FnMut(u64, usize, usize) -> bool,
+) -> PyResult<u64> {
+    let io_err =
+        |e: std::io::Error| CsvIoError::new_err(format!("Failed to read file {path}: {e}"));
+    let mut file = std::fs::File::open(paths::native(path)).map_err(io_err)?;
+    let size = file.metadata().map_err(io_err)?.len();
+    let terminator = terminator(dialect);
+    let quote = dialect.read_quote();
+    let mut in_quotes = false;
+    let mut content = false; // The current line holds more than line breaks
+    let mut newlines = 0; // Since the last record ended, as the parser counts lines
+    let (mut records, mut lines) = (0, 0);
+    let mut offset = 0u64;
+    let mut chunk = vec![0u8; 64 * 1024];
+    loop {
+        let n = std::io::Read::read(&mut file, &mut chunk).map_err(io_err)?;
+        if n == 0 {
+            break;
+        }
+        for &b in &chunk[..n] {
+            offset += 1;
+            if b == b'\n' {
+                newlines += 1;
+            }
+            if Some(b) == quote {
+                in_quotes = !in_quotes;
+            } else if b == terminator && !in_quotes {
+                // Blank lines are skipped by the parser and counted with the next record
+                if content {
+                    records += 1;
+                    lines += newlines.max(1);
+                    newlines = 0;
+                    content = false;
+                    if !on_record(offset, records, lines) {
+                        return Ok(size);
+                    }
+                }
+                continue;
+            }
+            if b != b'\r' && b != b'\n' {
+                content = true;
+            }
+        }
+    }
+    if content {
+        on_record(offset, records + 1, lines + newlines.max(1));
+    }
+    Ok(size)
+}
+
+const START_SIZE: usize = std::mem::size_of::<(u64, usize)>();
+
+/// The error `seek()` raises when the index of `path` outgrows `max_memory`.
+fn exceeded(path: &str, limit: usize) -> PyErr {
+    memory::exceeded(
+        &format!("seek() indexing {path}"),
+        limit,
+        "raise max_memory or read the file sequentially",
+    )
+}
+
+fn build(
+    path: &FilePath,
+    dialect: &DialectConfig,
+    metadata: &std::fs::Metadata,
+    max_memory: Option<usize>,
+) -> PyResult<RowIndex> {
+    let mut starts = vec![(0, 0)];
+    let mut over_budget = false;
+    let size = scan_records(path, dialect, |offset, _, lines| {
+        starts.push((offset, lines));
+        // Counted by length, as the vector grows by doubling its capacity
+        over_budget = max_memory.is_some_and(|limit| starts.len() * START_SIZE > limit);
+        !over_budget
+    })?;
+    if let (true, Some(limit)) = (over_budget, max_memory) {
+        return Err(exceeded(path, limit));
+    }
+    // The last entry is the end of the file, not the start of a record
+    let end = starts.pop().unwrap_or((0, 0));
+    Ok(RowIndex {
+        starts,
+        end,
+        size,
+        modified: metadata.modified().ok(),
+    })
+}
+
+/// The index of the file at `path`, from the cache while the file is unchanged. With
+/// `max_memory`, an index whose record starts take more bytes raises `MemoryError`.
+pub(crate) fn row_index(
+    path: &FilePath,
+    dialect: &DialectConfig,
+    max_memory: Option<usize>,
+) -> PyResult<Arc<RowIndex>> {
+    let native = paths::native(path);
+    let metadata = std::fs::metadata(&native)
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+    let key = (
+        std::fs::canonicalize(&native).unwrap_or(native),
+        dialect.read_quote(),
+        terminator(dialect),
+    );
+    let fresh = |index: &RowIndex| {
+        index.size == metadata.len() && index.modified == metadata.modified().ok()
+    };
+    let cached = {
+        let mut cache = cache();
+        cache.tick += 1;
+        let tick = cache.tick;
+        match cache.entries.get_mut(&key) {
+            Some((index, used)) if fresh(index) => {
+                *used = tick;
+                Some(Arc::clone(index))
+            }
+            Some(_) => {
+                cache.remove(&key);
+                None
+            }
+            None => None,
+        }
+    };
+    if let Some(index) = cached {
+        HITS.fetch_add(1, Ordering::Relaxed);
+        if let Some(limit) = max_memory.filter(|&limit| index.starts.len() * START_SIZE > limit) {
+            return Err(exceeded(path, limit));
+        }
+        return Ok(index);
+    }
+    MISSES.fetch_add(1, Ordering::Relaxed);
+    let index = Arc::new(build(path, dialect, &metadata, max_memory)?);
+    cache().insert(key, Arc::clone(&index));
+    Ok(index)
+}
+
+/// Statistics of the shared record index cache used by `Reader.seek()`.
+///
+/// Returns a dict with `entries` (files indexed), `records` (record starts held across
+/// them), `bytes` (memory they take), `hits` and `misses` (lookups that reused or built
+/// an index).
+#[pyfunction]
+pub(crate) fn index_cache_info(py: Python<'_>) -> PyResult<Py<PyAny>> {
+    let cache = cache();
+    let info = PyDict::new(py);
+    info.set_item("entries", cache.entries.len())?;
+    info.set_item(
+        "records",
+        cache
+            .entries
+            .values()
+            .map(|(index, _)| index.starts.len())
+            .sum::<usize>(),
+    )?;
+    info.set_item("bytes", cache.bytes)?;
+    info.set_item("hits", HITS.load(Ordering::Relaxed))?;
+    info.set_item("misses", MISSES.load(Ordering::Relaxed))?;
+    Ok(info.into_any().unbind())
+}
+
+/// Drop every cached record index and reset the counters.
+#[pyfunction]
+pub(crate) fn clear_index_cache() {
+    *cache() = Cache::default();
+    HITS.store(0, Ordering::Relaxed);
+    MISSES.store(0, Ordering::Relaxed);
+}
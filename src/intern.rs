@@ -0,0 +1,188 @@
+//! String interning for repetitive columns (`intern_columns=` on `Reader`).
+//!
+//! Each interned column keeps a table from field text to the Python string first made
+//! for it, and later rows reuse that object instead of allocating their own. A column
+//! of country codes or statuses then holds a handful of strings however many rows are
+//! read. With `intern_columns=True` every column is tried, and a column is given up
+//! once it has shown more than [`AUTO_LIMIT`] distinct values, so ids and free text
+//! don't fill a table that saves nothing.
+
+use crate::project::Column;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyString};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+
+/// Distinct values a column may show under `intern_columns=True` before it is no
+/// longer interned.
+const AUTO_LIMIT: usize = 1024;
+
+/// Which columns are interned.
+#[derive(Debug)]
+enum Columns {
+    /// Every column, up to [`AUTO_LIMIT`] distinct values each.
+    Auto,
+    /// The listed columns, without a limit.
+    Listed(Vec<Column>),
+}
+
+/// Interning state of one column.
+#[derive(Debug, Default)]
+enum Table {
+    /// Not interned.
+    #[default]
+    Off,
+    Strings(HashMap<String, Py<PyString>>),
+}
+
+#[derive(Debug, Default)]
+struct Tables {
+    resolved: bool, // Listed names have been looked up in the header
+    columns: Vec<Table>,
+}
+
+/// Interned strings of one reader.
+#[derive(Debug)]
+pub(crate) struct Interner {
+    columns: Columns,
+    header: Arc<OnceLock<Vec<String>>>, // The reader's header row, once read
+    tables: StdMutex<Tables>,
+}
+
+impl Interner {
+    /// Parse an `intern_columns` argument: True, or a list of header names and 0-based
+    /// positions. Returns `None` for False. `header` is where the reader records its
+    /// header row.
+    pub(crate) fn parse(
+        intern_columns: &Bound<'_, PyAny>,
+        header: Arc<OnceLock<Vec<String>>>,
+    ) -> PyResult<Option<Self>> {
+        let columns = if let Ok(flag) = intern_columns.cast::<PyBool>() {
+            if !flag.is_true() {
+                return Ok(None);
+            }
+            Columns::Auto
+        } else {
+            if intern_columns.is_instance_of::<PyString>() {
+                return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                    "intern_columns must be True or a list of column names or positions, \
+                    not a string",
+                ));
+            }
+            let mut columns = Vec::new();
+            for column in intern_columns.try_iter()? {
+                columns.push(Column::parse(&column?, "intern_columns")?);
+            }
+            Columns::Listed(columns)
+        };
+        Ok(Some(Interner {
+            columns,
+            header,
+            tables: StdMutex::new(Tables::default()),
+        }))
+    }
+
+    /// Whether columns are named, needing a header row.
+    pub(crate) fn has_names(&self) -> bool {
+        matches!(&self.columns, Columns::Listed(columns)
+            if columns.iter().any(|c| matches!(c, Column::Name(_))))
+    }
+
+    /// Set up the tables of listed columns once their positions are known. Names are
+    /// looked up in the header, so until it has been read nothing is interned.
+    fn resolve(&self, tables: &mut Tables) -> PyResult<()> {
+        if tables.resolved {
+            return Ok(());
+        }
+        let Columns::Listed(columns) = &self.columns else {
+            tables.resolved = true;
+            return Ok(());
+        };
+        let header = match self.header.get() {
+            Some(header) => header.as_slice(),
+            None if self.has_names() => return Ok(()),
+            None => &[],
+        };
+        for column in columns {
+            let position = column.position(header)?;
+            if tables.columns.len() <= position {
+                tables.columns.resize_with(position + 1, Table::default);
+            }
+            tables.columns[position] = Table::Strings(HashMap::new());
+        }
+        tables.resolved = true;
+        Ok(())
+    }
+
+    /// The table of column `i`, or `None` when it is not interned.
+    fn table<'a>(
+        &self,
+        tables: &'a mut Tables,
+        i: usize,
+    ) -> Option<&'a mut HashMap<String, Py<PyString>>> {
+        if let Columns::Auto = self.columns {
+            if tables.columns.len() <= i {
+                tables
+                    .columns
+                    .resize_with(i + 1, || Table::Strings(HashMap::new()));
+            }
+        }
+        match tables.columns.get_mut(i)? {
+            Table::Strings(table) => Some(table),
+            Table::Off => None,
+        }
+    }
+
+    /// Stop interning column `i` once it outgrows the automatic limit.
+    fn limit(&self, tables: &mut Tables, i: usize) {
+        if matches!(self.columns, Columns::Auto)
+            && matches!(&tables.columns[i], Table::Strings(table) if table.len() > AUTO_LIMIT)
+        {
+            tables.columns[i] = Table::Off;
+        }
+    }
+
+    /// Convert a row's fields to Python strings, reusing interned ones.
+    pub(crate) fn strings(&self, py: Python<'_>, row: Vec<String>) -> PyResult<Vec<Py<PyAny>>> {
+        let mut tables = self.tables.lock().unwrap_or_else(|e| e.into_inner());
+        self.resolve(&mut tables)?;
+        let mut fields = Vec::with_capacity(row.len());
+        for (i, field) in row.into_iter().enumerate() {
+            let Some(table) = self.table(&mut tables, i) else {
+                fields.push(PyString::new(py, &field).into_any().unbind());
+                continue;
+            };
+            if let Some(string) = table.get(&field) {
+                fields.push(string.clone_ref(py).into_any());
+                continue;
+            }
+            let string = PyString::new(py, &field).unbind();
+            fields.push(string.clone_ref(py).into_any());
+            table.insert(field, string);
+            self.limit(&mut tables, i);
+        }
+        Ok(fields)
+    }
+
+    /// Replace strings among already converted fields (with `schema=`) by interned ones.
+    pub(crate) fn converted(&self, py: Python<'_>, fields: &mut [Py<PyAny>]) -> PyResult<()> {
+        let mut tables = self.tables.lock().unwrap_or_else(|e| e.into_inner());
+        self.resolve(&mut tables)?;
+        for (i, field) in fields.iter_mut().enumerate() {
+            let Ok(string) = field.bind(py).cast_exact::<PyString>() else {
+                continue; // Typed values are left alone
+            };
+            let Some(table) = self.table(&mut tables, i) else {
+                continue;
+            };
+            let text = string.to_str()?;
+            let Some(interned) = table.get(text).map(|s| s.clone_ref(py)) else {
+                table.insert(text.to_string(), string.clone().unbind());
+                self.limit(&mut tables, i);
+                continue;
+            };
+            *field = interned.into_any();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,553 @@
+//! Hash join of two CSV files on key columns.
+//!
+//! The right file is loaded into a hash table keyed by the join columns; the left file
+//! is then streamed row by row, so only one side has to fit in memory. With
+//! `max_memory`, a right side too large for it is joined as a grace hash join instead:
+//! both files are hash-partitioned on the key into temporary files, each pair of
+//! partitions is joined on its own, and the joined rows are merged back into the order
+//! the in-memory join writes them in. The partition files are removed when the
+//! operation ends, including when it fails or the awaiting task is cancelled.
+
+use crate::errors::CsvIoError;
+use crate::ops::{
+    check_not_input, check_temp_dir, create_writer, csv_err, encode_key, extract_columns,
+    open_reader, read_header, resolve_columns, simple_dialect, spawn_cancellable_py, spill_dir,
+    Cancellation,
+};
+use crate::{memory, validate_path, DialectConfig};
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use pyo3::prelude::*;
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Number of partitions both files are split into when the right one doesn't fit.
+const SPILL_PARTITIONS: usize = 64;
+/// Join type, matching the `how=` values used by pandas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum JoinHow {
+    Inner,
+    Left,
+    Right,
+    Outer,
+}
+
+impl JoinHow {
+    fn parse(how: &str) -> PyResult<Self> {
+        match how {
+            "inner" => Ok(JoinHow::Inner),
+            "left" => Ok(JoinHow::Left),
+            "right" => Ok(JoinHow::Right),
+            "outer" => Ok(JoinHow::Outer),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "how must be 'inner', 'left', 'right' or 'outer', got '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Where the columns of the output come from.
+struct Layout {
+    left_keys: Vec<usize>,
+    right_keys: Vec<usize>,
+    left_width: usize,
+    right_extra: Vec<usize>, // Right columns that aren't join keys
+    header: Vec<String>,
+}
+
+impl Layout {
+    fn new(
+        on: &[String],
+        left_header: &StringRecord,
+        right_header: &StringRecord,
+        left: &str,
+        right: &str,
+    ) -> PyResult<Self> {
+        let left_keys = resolve_columns(on, left_header, left)?;
+        let right_keys = resolve_columns(on, right_header, right)?;
+        // Output: every left column, then right columns that aren't join keys
+        let right_extra: Vec<usize> = (0..right_header.len())
+            .filter(|i| !right_keys.contains(i))
+            .collect();
+        let mut header: Vec<String> = left_header.iter().map(String::from).collect();
+        for &i in &right_extra {
+            let name = &right_header[i];
+            if left_header.iter().any(|h| h == name) {
+                header.push(format!("{name}_right"));
+            } else {
+                header.push(name.to_string());
+            }
+        }
+        Ok(Layout {
+            left_keys,
+            right_keys,
+            left_width: left_header.len(),
+            right_extra,
+            header,
+        })
+    }
+
+    /// A left row joined with `right`, or padded with empty right columns.
+    fn joined<'a>(&self, left: &'a StringRecord, right: Option<&'a StringRecord>) -> Vec<&'a str> {
+        let mut row = Vec::with_capacity(self.header.len());
+        row.extend((0..self.left_width).map(|i| left.get(i).unwrap_or("")));
+        row.extend(
+            self.right_extra
+                .iter()
+                .map(|&i| right.and_then(|right| right.get(i)).unwrap_or("")),
+        );
+        row
+    }
+
+    /// An unmatched right row, with its key values placed in the left key columns.
+    fn right_only<'a>(&self, right: &'a StringRecord) -> Vec<&'a str> {
+        let mut row = Vec::with_capacity(self.header.len());
+        row.extend((0..self.left_width).map(|i| {
+            self.left_keys
+                .iter()
+                .position(|&k| k == i)
+                .and_then(|k| right.get(self.right_keys[k]))
+                .unwrap_or("")
+        }));
+        row.extend(self.right_extra.iter().map(|&i| right.get(i).unwrap_or("")));
+        row
+    }
+}
+
+/// Right rows in a hash table by key, each with its position in the right file.
+#[derive(Default)]
+struct Table {
+    rows: Vec<(u64, StringRecord)>,
+    matched: Vec<bool>,
+    index: HashMap<Vec<u8>, Vec<usize>>,
+    bytes: usize,
+}
+
+impl Table {
+    /// Add a row, returning false instead if that would take the table past `limit`.
+    fn insert(
+        &mut self,
+        key: &[u8],
+        seq: u64,
+        record: &StringRecord,
+        limit: Option<usize>,
+    ) -> bool {
+        if let Some(limit) = limit {
+            // The row, its key and its slot in the table
+            self.bytes += memory::record_size(record)
+                + key.len()
+                + std::mem::size_of::<(Vec<u8>, Vec<usize>)>()
+                + std::mem::size_of::<(u64, StringRecord, bool, usize)>();
+            if self.bytes > limit {
+                return false;
+            }
+        }
+        self.index
+            .entry(key.to_vec())
+            .or_default()
+            .push(self.rows.len());
+        self.rows.push((seq, record.clone()));
+        self.matched.push(false);
+        true
+    }
+
+    /// The rows matching `key`, marked as matched, in right file order.
+    fn probe(&mut self, key: &[u8]) -> Vec<usize> {
+        let matches = self.index.get(key).cloned().unwrap_or_default();
+        for &m in &matches {
+            self.matched[m] = true;
+        }
+        matches
+    }
+
+    /// The rows no left row matched.
+    fn unmatched(&self) -> impl Iterator<Item = &(u64, StringRecord)> {
+        self.rows
+            .iter()
+            .zip(&self.matched)
+            .filter(|(_, &matched)| !matched)
+            .map(|(row, _)| row)
+    }
+}
+
+/// Join `left` and `right` on `on` into `dst`. Returns the number of rows written.
+#[allow(clippy::too_many_arguments)] // Internal helper for join()
+fn join_files(
+    left: &str,
+    right: &str,
+    on: &[String],
+    how: JoinHow,
+    dst: &str,
+    max_memory: Option<usize>,
+    dialect: &DialectConfig,
+    temp_dir: Option<&str>,
+    cancellation: &Cancellation,
+) -> PyResult<usize> {
+    check_not_input(dst, &[left, right])?;
+    let mut left_reader = open_reader(left, dialect)?;
+    let mut right_reader = open_reader(right, dialect)?;
+    let left_header = read_header(&mut left_reader, left)?.unwrap_or_default();
+    let right_header = read_header(&mut right_reader, right)?.unwrap_or_default();
+    let layout = Layout::new(on, &left_header, &right_header, left, right)?;
+
+    // Build side: right file
+    let mut table = Table::default();
+    let mut record = StringRecord::new();
+    let mut encoded = Vec::new();
+    let mut seq = 0u64;
+    loop {
+        match right_reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(right, e)),
+        }
+        cancellation.check()?;
+        encode_key(&record, Some(&layout.right_keys), &mut encoded);
+        if !table.insert(&encoded, seq, &record, max_memory) {
+            drop(table);
+            return join_spilled(
+                left,
+                right,
+                &layout,
+                how,
+                dst,
+                max_memory,
+                dialect,
+                temp_dir,
+                cancellation,
+            );
+        }
+        seq += 1;
+    }
+
+    // Probe side: stream the left file
+    let mut writer = create_writer(dst, dialect)?;
+    writer
+        .write_record(&layout.header)
+        .map_err(|e| csv_err(dst, e))?;
+    let mut written = 0usize;
+    loop {
+        match left_reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(left, e)),
+        }
+        cancellation.check()?;
+        encode_key(&record, Some(&layout.left_keys), &mut encoded);
+        let matches = table.probe(&encoded);
+        for &m in &matches {
+            let row = layout.joined(&record, Some(&table.rows[m].1));
+            writer.write_record(&row).map_err(|e| csv_err(dst, e))?;
+            written += 1;
+        }
+        if matches.is_empty() && matches!(how, JoinHow::Left | JoinHow::Outer) {
+            let row = layout.joined(&record, None);
+            writer.write_record(&row).map_err(|e| csv_err(dst, e))?;
+            written += 1;
+        }
+    }
+
+    // Unmatched right rows
+    if matches!(how, JoinHow::Right | JoinHow::Outer) {
+        for (_, right_row) in table.unmatched() {
+            cancellation.check()?;
+            let row = layout.right_only(right_row);
+            writer.write_record(&row).map_err(|e| csv_err(dst, e))?;
+            written += 1;
+        }
+    }
+
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+    Ok(written)
+}
+
+/// Partition a CSV file's data rows by the hash of their key into `paths`, each row
+/// prefixed with its position in the file.
+fn partition_file(
+    src: &str,
+    keys: &[usize],
+    paths: &[String],
+    dialect: &DialectConfig,
+    cancellation: &Cancellation,
+) -> PyResult<()> {
+    let mut reader = open_reader(src, dialect)?;
+    read_header(&mut reader, src)?;
+    let mut partitions = paths
+        .iter()
+        .map(|path| {
+            WriterBuilder::new()
+                .flexible(true)
+                .from_path(path)
+                .map_err(|e| csv_err(path, e))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let mut record = StringRecord::new();
+    let mut encoded = Vec::new();
+    let mut seq = 0u64;
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        cancellation.check()?;
+        encode_key(&record, Some(keys), &mut encoded);
+        let mut hasher = DefaultHasher::new();
+        encoded.hash(&mut hasher);
+        let partition = (hasher.finish() % paths.len() as u64) as usize;
+        let seq_field = seq.to_string();
+        partitions[partition]
+            .write_record(std::iter::once(seq_field.as_str()).chain(record.iter()))
+            .map_err(|e| csv_err(&paths[partition], e))?;
+        seq += 1;
+    }
+    for (partition, path) in partitions.iter_mut().zip(paths) {
+        partition
+            .flush()
+            .map_err(|e| CsvIoError::new_err(format!("Failed to write '{path}': {e}")))?;
+    }
+    Ok(())
+}
+
+/// Open a spilled partition for reading.
+fn open_partition(path: &str) -> PyResult<csv::Reader<std::fs::File>> {
+    ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| csv_err(path, e))
+}
+
+/// Read the next spilled row into `row`, returning the `N` sequence numbers that lead
+/// it, with the rest of its fields in `fields`.
+fn read_sequenced<const N: usize>(
+    reader: &mut csv::Reader<std::fs::File>,
+    row: &mut StringRecord,
+    fields: &mut StringRecord,
+    path: &str,
+) -> PyResult<Option<[u64; N]>> {
+    if !reader.read_record(row).map_err(|e| csv_err(path, e))? {
+        return Ok(None);
+    }
+    let mut seqs = [u64::MAX; N];
+    for (i, seq) in seqs.iter_mut().enumerate() {
+        *seq = row.get(i).and_then(|s| s.parse().ok()).unwrap_or(u64::MAX);
+    }
+    fields.clear();
+    fields.extend(row.iter().skip(N));
+    Ok(Some(seqs))
+}
+
+/// Write the rows of `paths`, each sorted by its `N` leading sequence numbers, to
+/// `writer` in that order, without the sequence numbers.
+fn merge_partitions<const N: usize>(
+    paths: &[String],
+    writer: &mut csv::Writer<std::fs::File>,
+    dst: &str,
+    cancellation: &Cancellation,
+) -> PyResult<usize> {
+    let mut readers = paths
+        .iter()
+        .map(|path| open_partition(path))
+        .collect::<PyResult<Vec<_>>>()?;
+    let mut row = StringRecord::new();
+    let mut heads = vec![StringRecord::new(); paths.len()];
+    let mut heap: BinaryHeap<Reverse<([u64; N], usize)>> = BinaryHeap::new();
+    for (i, reader) in readers.iter_mut().enumerate() {
+        if let Some(seqs) = read_sequenced(reader, &mut row, &mut heads[i], &paths[i])? {
+            heap.push(Reverse((seqs, i)));
+        }
+    }
+    let mut written = 0usize;
+    while let Some(Reverse((_, i))) = heap.pop() {
+        cancellation.check()?;
+        writer
+            .write_record(&heads[i])
+            .map_err(|e| csv_err(dst, e))?;
+        written += 1;
+        if let Some(seqs) = read_sequenced(&mut readers[i], &mut row, &mut heads[i], &paths[i])? {
+            heap.push(Reverse((seqs, i)));
+        }
+    }
+    Ok(written)
+}
+
+/// Grace hash join: partition both files by key hash, join each pair of partitions in
+/// memory, and merge the joined rows back into the order of the in-memory join (left
+/// file order, matches in right file order, then unmatched right rows).
+#[allow(clippy::too_many_arguments)] // Internal helper for join()
+fn join_spilled(
+    left: &str,
+    right: &str,
+    layout: &Layout,
+    how: JoinHow,
+    dst: &str,
+    max_memory: Option<usize>,
+    dialect: &DialectConfig,
+    temp_dir: Option<&str>,
+    cancellation: &Cancellation,
+) -> PyResult<usize> {
+    let spill = spill_dir(temp_dir, "join")?;
+    let paths = |kind: &str| -> Vec<String> {
+        (0..SPILL_PARTITIONS)
+            .map(|i| {
+                Path::new(spill.path())
+                    .join(format!("{kind}_{i}.csv"))
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    };
+    let (left_parts, right_parts) = (paths("left"), paths("right"));
+    let (joined_parts, rest_parts) = (paths("joined"), paths("rest"));
+    partition_file(
+        right,
+        &layout.right_keys,
+        &right_parts,
+        dialect,
+        cancellation,
+    )?;
+    partition_file(left, &layout.left_keys, &left_parts, dialect, cancellation)?;
+
+    let mut row = StringRecord::new();
+    let mut record = StringRecord::new();
+    let mut encoded = Vec::new();
+    for i in 0..SPILL_PARTITIONS {
+        // Joined rows lead with their (left, right) file positions; an unmatched left
+        // row is the only row for its left position, so it takes right position 0
+        let mut table = Table::default();
+        let mut reader = open_partition(&right_parts[i])?;
+        while let Some([seq]) = read_sequenced(&mut reader, &mut row, &mut record, &right_parts[i])?
+        {
+            cancellation.check()?;
+            encode_key(&record, Some(&layout.right_keys), &mut encoded);
+            if !table.insert(&encoded, seq, &record, max_memory) {
+                return Err(memory::exceeded(
+                    &format!("join() holding one partition of the right file {right}"),
+                    max_memory.unwrap_or_default(),
+                    "raise max_memory (rows sharing a key can't be split across partitions)",
+                ));
+            }
+        }
+        std::fs::remove_file(&right_parts[i]).ok();
+
+        let mut joined = WriterBuilder::new()
+            .flexible(true)
+            .from_path(&joined_parts[i])
+            .map_err(|e| csv_err(&joined_parts[i], e))?;
+        let mut reader = open_partition(&left_parts[i])?;
+        while let Some([seq]) = read_sequenced(&mut reader, &mut row, &mut record, &left_parts[i])?
+        {
+            cancellation.check()?;
+            encode_key(&record, Some(&layout.left_keys), &mut encoded);
+            let matches = table.probe(&encoded);
+            let left_seq = seq.to_string();
+            for &m in &matches {
+                let (right_seq, right_row) = &table.rows[m];
+                let right_seq = right_seq.to_string();
+                let out = layout.joined(&record, Some(right_row));
+                joined
+                    .write_record([left_seq.as_str(), &right_seq].into_iter().chain(out))
+                    .map_err(|e| csv_err(&joined_parts[i], e))?;
+            }
+            if matches.is_empty() && matches!(how, JoinHow::Left | JoinHow::Outer) {
+                let out = layout.joined(&record, None);
+                joined
+                    .write_record([left_seq.as_str(), "0"].into_iter().chain(out))
+                    .map_err(|e| csv_err(&joined_parts[i], e))?;
+            }
+        }
+        joined.flush().map_err(|e| {
+            CsvIoError::new_err(format!("Failed to write '{}': {e}", joined_parts[i]))
+        })?;
+        std::fs::remove_file(&left_parts[i]).ok();
+
+        let mut rest = WriterBuilder::new()
+            .flexible(true)
+            .from_path(&rest_parts[i])
+            .map_err(|e| csv_err(&rest_parts[i], e))?;
+        if matches!(how, JoinHow::Right | JoinHow::Outer) {
+            for (seq, right_row) in table.unmatched() {
+                let seq = seq.to_string();
+                let out = layout.right_only(right_row);
+                rest.write_record(std::iter::once(seq.as_str()).chain(out))
+                    .map_err(|e| csv_err(&rest_parts[i], e))?;
+            }
+        }
+        rest.flush().map_err(|e| {
+            CsvIoError::new_err(format!("Failed to write '{}': {e}", rest_parts[i]))
+        })?;
+    }
+
+    let mut writer = create_writer(dst, dialect)?;
+    writer
+        .write_record(&layout.header)
+        .map_err(|e| csv_err(dst, e))?;
+    let written = merge_partitions::<2>(&joined_parts, &mut writer, dst, cancellation)?
+        + merge_partitions::<1>(&rest_parts, &mut writer, dst, cancellation)?;
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+    Ok(written)
+}
+
+/// Join two CSV files on key columns.
+///
+/// # Arguments
+/// * `left` - Left CSV file (streamed)
+/// * `right` - Right CSV file (held in memory as a hash table, or partitioned on disk
+///   with it once it passes `max_memory`)
+/// * `on` - Column name or list of names present in both files
+/// * `dst` - Output CSV file (created or truncated)
+/// * `how` - `"inner"` (default), `"left"`, `"right"` or `"outer"`
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+/// * `max_memory` - Bytes the right file's hash table may take before both files are
+///   partitioned on disk and joined a partition at a time (default: None, no limit)
+/// * `temp_dir` - Directory to create partition files under (default: None, the
+///   system's temporary directory)
+///
+/// Output columns are all left columns followed by the non-key right columns; right
+/// columns whose names clash with a left column get a `_right` suffix. Returns the
+/// number of rows written.
+#[pyfunction]
+#[pyo3(signature = (left, right, on, dst, how = "inner", delimiter = None, quotechar = None, max_memory = None, temp_dir = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn join(
+    py: Python<'_>,
+    left: String,
+    right: String,
+    on: &Bound<'_, PyAny>,
+    dst: String,
+    how: &str,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+    max_memory: Option<usize>,
+    temp_dir: Option<String>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&left)?;
+    validate_path(&right)?;
+    validate_path(&dst)?;
+    check_temp_dir(temp_dir.as_deref())?;
+    let on = extract_columns(on, "on")?;
+    let how = JoinHow::parse(how)?;
+    let max_memory = memory::parse(max_memory)?;
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    spawn_cancellable_py(py, move |cancellation| {
+        join_files(
+            &left,
+            &right,
+            &on,
+            how,
+            &dst,
+            max_memory,
+            &dialect,
+            temp_dir.as_deref(),
+            cancellation,
+        )
+    })
+}
@@ -0,0 +1,528 @@
+//! JSON Lines reading and writing.
+//!
+//! `JsonlReader` and `JsonlWriter` mirror the CSV `Reader` and `Writer`: they accept the
+//! same path-or-handle argument, stream data in chunks through the shared source/sink
+//! helpers, and do all decoding and encoding outside the GIL.
+
+use crate::awaitable::future_into_py;
+use crate::decode::{Utf8Decoder, Utf8Mode};
+use crate::errors::{CsvIoError, EncodingError, ENCODING_ERROR};
+use crate::paths::FilePath;
+use crate::socket::Conn;
+use crate::{read_chunk, resolve_source, write_chunk, Access, FileSource, DEFAULT_BUFFER_SIZE};
+use pyo3::exceptions::{PyStopAsyncIteration, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use serde_json::{Map, Number, Value};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::io::{AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+
+/// Convert a Python object into a JSON value.
+///
+/// Supports the same types as the standard library `json` module: dicts, lists,
+/// tuples, strings, ints, floats, bools and None. Non-string dict keys that are
+/// scalars are converted to strings, as `json.dumps` does.
+pub(crate) fn py_to_json(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if obj.is_none() {
+        return Ok(Value::Null);
+    }
+    // bool must be checked before int (bool is a subclass of int in Python)
+    if obj.is_instance_of::<PyBool>() {
+        return Ok(Value::Bool(obj.extract::<bool>()?));
+    }
+    if obj.is_instance_of::<PyInt>() {
+        if let Ok(v) = obj.extract::<i64>() {
+            return Ok(Value::Number(v.into()));
+        }
+        if let Ok(v) = obj.extract::<u64>() {
+            return Ok(Value::Number(v.into()));
+        }
+        return Err(PyValueError::new_err(
+            "Integer is out of range for JSON serialization",
+        ));
+    }
+    if obj.is_instance_of::<PyFloat>() {
+        let v = obj.extract::<f64>()?;
+        return Number::from_f64(v).map(Value::Number).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "Out of range float values are not JSON compliant: {v}"
+            ))
+        });
+    }
+    if obj.is_instance_of::<PyString>() {
+        return Ok(Value::String(obj.extract::<String>()?));
+    }
+    if let Ok(dict) = obj.cast::<PyDict>() {
+        let mut map = Map::new();
+        for (key, value) in dict.iter() {
+            let key_str = if key.is_instance_of::<PyString>() {
+                key.extract::<String>()?
+            } else if key.is_none() {
+                "null".to_string()
+            } else if key.is_instance_of::<PyBool>() {
+                if key.extract::<bool>()? {
+                    "true"
+                } else {
+                    "false"
+                }
+                .to_string()
+            } else if key.is_instance_of::<PyInt>() || key.is_instance_of::<PyFloat>() {
+                key.str()?.extract::<String>()?
+            } else {
+                return Err(PyTypeError::new_err(format!(
+                    "keys must be str, int, float, bool or None, not {}",
+                    key.get_type().name()?
+                )));
+            };
+            map.insert(key_str, py_to_json(&value)?);
+        }
+        return Ok(Value::Object(map));
+    }
+    if let Ok(list) = obj.cast::<PyList>() {
+        return list
+            .iter()
+            .map(|item| py_to_json(&item))
+            .collect::<PyResult<Vec<_>>>()
+            .map(Value::Array);
+    }
+    if let Ok(tuple) = obj.cast::<PyTuple>() {
+        return tuple
+            .iter()
+            .map(|item| py_to_json(&item))
+            .collect::<PyResult<Vec<_>>>()
+            .map(Value::Array);
+    }
+    Err(PyTypeError::new_err(format!(
+        "Object of type {} is not JSON serializable",
+        obj.get_type().name()?
+    )))
+}
+
+/// Convert a JSON value into the equivalent Python object.
+pub(crate) fn json_to_py(py: Python<'_>, value: &Value) -> PyResult<Py<PyAny>> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => PyBool::new(py, *b).to_owned().into_any().unbind(),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                i.into_pyobject(py)?.into_any().unbind()
+            } else if let Some(u) = n.as_u64() {
+                u.into_pyobject(py)?.into_any().unbind()
+            } else {
+                n.as_f64()
+                    .unwrap_or(f64::NAN)
+                    .into_pyobject(py)?
+                    .into_any()
+                    .unbind()
+            }
+        }
+        Value::String(s) => PyString::new(py, s).into_any().unbind(),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(json_to_py(py, item)?)?;
+            }
+            list.into_any().unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, item) in map {
+                dict.set_item(key, json_to_py(py, item)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+/// Pull the next JSON document out of the buffer, refilling from the source as needed.
+///
+/// Blank lines are skipped. Returns `None` at EOF.
+#[allow(clippy::too_many_arguments)] // Mirrors the shared read_chunk parameters
+async fn next_value(
+    access: Access,
+    path: &FilePath,
+    file: &Arc<Mutex<Option<BufReader<Conn>>>>,
+    file_handle: &Arc<StdMutex<Option<Py<PyAny>>>>,
+    event_loop: &Arc<StdMutex<Option<Py<PyAny>>>>,
+    buffer: &Arc<Mutex<String>>,
+    line_num: &Arc<Mutex<usize>>,
+    decoder: &Utf8Decoder,
+    chunk_size: usize,
+) -> PyResult<Option<Value>> {
+    let mut buffer_guard = buffer.lock().await;
+    let mut line_num_guard = line_num.lock().await;
+    let mut eof = false;
+    loop {
+        // Take the next complete line, or whatever is left once EOF is reached
+        let line = match buffer_guard.find('\n') {
+            Some(idx) => {
+                let line: String = buffer_guard.drain(..=idx).collect();
+                Some(line)
+            }
+            None if eof && !buffer_guard.is_empty() => Some(std::mem::take(&mut *buffer_guard)),
+            None if eof => return Ok(None),
+            None => None,
+        };
+
+        if let Some(line) = line {
+            *line_num_guard += 1;
+            let text = line.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let current_line = *line_num_guard;
+            return serde_json::from_str::<Value>(text).map(Some).map_err(|e| {
+                PyValueError::new_err(format!(
+                    "Invalid JSON on line {current_line} in file '{path}': {e}"
+                ))
+            });
+        }
+
+        let (chunk_str, at_eof) = read_chunk(
+            access,
+            path,
+            file,
+            file_handle,
+            event_loop,
+            chunk_size,
+            DEFAULT_BUFFER_SIZE,
+            None, // No retries
+            decoder,
+            None, // Bytes are not checked for gzip
+        )
+        .await
+        .map_err(|e| {
+            let invalid = Python::attach(|py| {
+                ENCODING_ERROR
+                    .type_object(py)
+                    .is_ok_and(|class| e.is_instance(py, &class))
+            });
+            if invalid {
+                EncodingError::new_err(format!(
+                    "Invalid UTF-8 after line {} in file '{path}'",
+                    *line_num_guard
+                ))
+            } else {
+                e
+            }
+        })?;
+        buffer_guard.push_str(&chunk_str);
+        eof = at_eof;
+    }
+}
+
+/// Async JSON Lines reader.
+///
+/// Reads one JSON document per line, returning the decoded Python object. Accepts a
+/// file path or an async file-like object, just like the CSV `Reader`.
+///
+/// # Example
+///
+/// ```python
+/// from rapcsv import JsonlReader
+///
+/// async with JsonlReader("events.jsonl") as reader:
+///     async for record in reader:
+///         print(record["id"])
+/// ```
+#[pyclass]
+pub(crate) struct JsonlReader {
+    source: FileSource,
+    path: FilePath,
+    file: Arc<Mutex<Option<BufReader<Conn>>>>,
+    file_handle: Arc<StdMutex<Option<Py<PyAny>>>>,
+    event_loop: Arc<StdMutex<Option<Py<PyAny>>>>,
+    buffer: Arc<Mutex<String>>,
+    line_num: Arc<Mutex<usize>>,
+    decoder: Arc<Utf8Decoder>, // Joins characters split between chunks
+    read_size: usize,
+}
+
+#[pymethods]
+impl JsonlReader {
+    /// Open a JSON Lines file for reading.
+    ///
+    /// # Arguments
+    /// * `path_or_handle` - Path to the file or an async file-like object
+    /// * `read_size` - Buffer size for reading chunks (default: 8192)
+    #[new]
+    #[pyo3(signature = (path_or_handle, read_size = None))]
+    fn new(
+        py: Python<'_>,
+        path_or_handle: &Bound<'_, PyAny>,
+        read_size: Option<usize>,
+    ) -> PyResult<Self> {
+        let (source, path, file_handle, event_loop) = resolve_source(py, path_or_handle)?;
+        Ok(JsonlReader {
+            source,
+            path,
+            file: Arc::new(Mutex::new(None)),
+            file_handle,
+            event_loop,
+            buffer: Arc::new(Mutex::new(String::new())),
+            line_num: Arc::new(Mutex::new(0)),
+            decoder: Arc::new(Utf8Decoder::new(Utf8Mode::Strict)),
+            read_size: read_size.unwrap_or(8192),
+        })
+    }
+
+    /// Get the current line number (1-based).
+    #[getter]
+    fn line_num(&self) -> PyResult<usize> {
+        Ok(*self.line_num.try_lock().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Cannot access line_num concurrently")
+        })?)
+    }
+
+    /// Read the next record. Returns None at EOF.
+    fn read_row(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        self_.next_future(false)
+    }
+
+    /// Read up to `n` records at once.
+    fn read_rows(self_: PyRef<Self>, n: usize) -> PyResult<Py<PyAny>> {
+        let path = self_.path.clone();
+        let access = self_.source.access();
+        let file = Arc::clone(&self_.file);
+        let file_handle = Arc::clone(&self_.file_handle);
+        let event_loop = Arc::clone(&self_.event_loop);
+        let buffer = Arc::clone(&self_.buffer);
+        let line_num = Arc::clone(&self_.line_num);
+        let decoder = Arc::clone(&self_.decoder);
+        let chunk_size = self_.read_size;
+        Python::attach(|py| {
+            let future = async move {
+                let mut values = Vec::new();
+                for _ in 0..n {
+                    match next_value(
+                        access,
+                        &path,
+                        &file,
+                        &file_handle,
+                        &event_loop,
+                        &buffer,
+                        &line_num,
+                        &decoder,
+                        chunk_size,
+                    )
+                    .await?
+                    {
+                        Some(value) => values.push(value),
+                        None => break,
+                    }
+                }
+                Python::attach(|py| -> PyResult<Py<PyAny>> {
+                    let list = PyList::empty(py);
+                    for value in &values {
+                        list.append(json_to_py(py, value)?)?;
+                    }
+                    Ok(list.into_any().unbind())
+                })
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// Async iterator protocol - returns self.
+    fn __aiter__(slf: PyRef<Self>) -> PyResult<Py<Self>> {
+        Ok(slf.into())
+    }
+
+    /// Async iterator next - returns the next record or raises StopAsyncIteration.
+    fn __anext__(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        self_.next_future(true)
+    }
+
+    /// Async context manager entry.
+    fn __aenter__(slf: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let slf: Py<Self> = slf.into();
+        Python::attach(|py| {
+            let future = async move { Ok(slf) };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// Async context manager exit.
+    fn __aexit__(
+        &self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_val: Option<&Bound<'_, PyAny>>,
+        _exc_tb: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        let file = Arc::clone(&self.file);
+        let buffer = Arc::clone(&self.buffer);
+        Python::attach(|py| {
+            let future = async move {
+                file.lock().await.take();
+                buffer.lock().await.clear();
+                Ok(())
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+}
+
+impl JsonlReader {
+    /// Build the awaitable for the next record. With `stop_at_eof` the awaitable raises
+    /// StopAsyncIteration at EOF instead of returning None.
+    fn next_future(&self, stop_at_eof: bool) -> PyResult<Py<PyAny>> {
+        let path = self.path.clone();
+        let access = self.source.access();
+        let file = Arc::clone(&self.file);
+        let file_handle = Arc::clone(&self.file_handle);
+        let event_loop = Arc::clone(&self.event_loop);
+        let buffer = Arc::clone(&self.buffer);
+        let line_num = Arc::clone(&self.line_num);
+        let decoder = Arc::clone(&self.decoder);
+        let chunk_size = self.read_size;
+        Python::attach(|py| {
+            let future = async move {
+                let value = next_value(
+                    access,
+                    &path,
+                    &file,
+                    &file_handle,
+                    &event_loop,
+                    &buffer,
+                    &line_num,
+                    &decoder,
+                    chunk_size,
+                )
+                .await?;
+                Python::attach(|py| match value {
+                    Some(value) => json_to_py(py, &value),
+                    None if stop_at_eof => Err(PyStopAsyncIteration::new_err(())),
+                    None => Ok(py.None()),
+                })
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+}
+
+/// Async JSON Lines writer.
+///
+/// Serializes each record as compact JSON followed by a newline. Accepts a file path
+/// or an async file-like object, just like the CSV `Writer`.
+///
+/// # Example
+///
+/// ```python
+/// from rapcsv import JsonlWriter
+///
+/// async with JsonlWriter("events.jsonl") as writer:
+///     await writer.write_row({"id": 1, "tags": ["a", "b"]})
+/// ```
+#[pyclass]
+pub(crate) struct JsonlWriter {
+    source: FileSource,
+    path: FilePath,
+    file: Arc<Mutex<Option<Conn>>>,
+    file_handle: Arc<StdMutex<Option<Py<PyAny>>>>,
+    event_loop: Arc<StdMutex<Option<Py<PyAny>>>>,
+}
+
+#[pymethods]
+impl JsonlWriter {
+    /// Open a JSON Lines file for writing (appending if it already exists).
+    ///
+    /// # Arguments
+    /// * `path_or_handle` - Path to the file or an async file-like object
+    #[new]
+    #[pyo3(signature = (path_or_handle))]
+    fn new(py: Python<'_>, path_or_handle: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let (source, path, file_handle, event_loop) = resolve_source(py, path_or_handle)?;
+        Ok(JsonlWriter {
+            source,
+            path,
+            file: Arc::new(Mutex::new(None)),
+            file_handle,
+            event_loop,
+        })
+    }
+
+    /// Write a single JSON-serializable record.
+    fn write_row(self_: PyRef<Self>, record: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let value = py_to_json(record)?;
+        self_.write_values(vec![value])
+    }
+
+    /// Write multiple JSON-serializable records.
+    fn writerows(self_: PyRef<Self>, records: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let values = records
+            .try_iter()?
+            .map(|item| py_to_json(&item?))
+            .collect::<PyResult<Vec<_>>>()?;
+        self_.write_values(values)
+    }
+
+    /// Close the file handle explicitly.
+    fn close(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let file = Arc::clone(&self_.file);
+        Python::attach(|py| {
+            let future = async move {
+                if let Some(mut f) = file.lock().await.take() {
+                    f.flush().await.map_err(|e| {
+                        CsvIoError::new_err(format!("Failed to flush file during close: {e}"))
+                    })?;
+                }
+                Ok(())
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// Async context manager entry.
+    fn __aenter__(slf: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let slf: Py<Self> = slf.into();
+        Python::attach(|py| {
+            let future = async move { Ok(slf) };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// Async context manager exit.
+    fn __aexit__(
+        slf: PyRef<Self>,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_val: Option<&Bound<'_, PyAny>>,
+        _exc_tb: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        JsonlWriter::close(slf)
+    }
+}
+
+impl JsonlWriter {
+    /// Encode `values` as JSON Lines and write them in one chunk.
+    fn write_values(&self, values: Vec<Value>) -> PyResult<Py<PyAny>> {
+        let path = self.path.clone();
+        let access = self.source.access();
+        let file = Arc::clone(&self.file);
+        let file_handle = Arc::clone(&self.file_handle);
+        let event_loop = Arc::clone(&self.event_loop);
+        Python::attach(|py| {
+            let future = async move {
+                let mut data = Vec::new();
+                for value in &values {
+                    serde_json::to_writer(&mut data, value).map_err(|e| {
+                        PyValueError::new_err(format!("Failed to encode JSON record: {e}"))
+                    })?;
+                    data.push(b'\n');
+                }
+                write_chunk(
+                    access,
+                    &path,
+                    &file,
+                    &file_handle,
+                    &event_loop,
+                    data,
+                    true,
+                    None, // No retries
+                )
+                .await
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+}
@@ -1,34 +1,187 @@
 #![allow(non_local_definitions)] // False positive from pyo3 macros
 
 use csv::{QuoteStyle, ReaderBuilder, Terminator, WriterBuilder};
-use pyo3::create_exception;
-use pyo3::exceptions::PyException;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::{PyBool, PyBytes, PyDict, PyList, PyString, PyTuple, PyType};
 use pyo3::wrap_pyfunction;
-use pyo3_async_runtimes::tokio::future_into_py;
 use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
 use tokio::fs::File;
 use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::sync::Mutex;
+use tracing::Instrument;
+
+mod archive;
+mod awaitable;
+mod binary;
+mod checkpoint;
+mod checksum;
+mod convert;
+mod crypt;
+mod csvtab;
+mod decode;
+mod dedupe;
+mod detect;
+mod dialect;
+mod dictrow;
+mod diff;
+mod drift;
+mod errors;
+mod escape;
+mod fast;
+mod fixed;
+mod gunzip;
+mod headers;
+mod index;
+mod intern;
+mod join;
+mod jsonl;
+mod lint;
+mod lock;
+mod logging;
+mod memory;
+mod nulls;
+mod ops;
+mod output;
+mod partition;
+mod paths;
+mod perms;
+mod pipe;
+mod profile;
+mod progress;
+mod project;
+mod quota;
+mod repair;
+mod retry;
+mod rfc4180;
+mod rotate;
+mod row;
+mod rowhash;
+mod sandbox;
+mod sftp;
+mod socket;
+mod sort;
+mod spans;
+mod sqlite;
+mod stats;
+mod stream;
+mod throttle;
+mod timeout;
+mod transform;
+mod transpose;
+mod typed;
+mod uring;
+mod validate;
+mod values;
+mod whitespace;
+
+use awaitable::future_into_py;
+use binary::BinaryPolicy;
+use checkpoint::{
+    state_from_json, state_to_json, Checkpoint, SavedState, DEFAULT_CHECKPOINT_INTERVAL,
+};
+use checksum::Verifier;
+use decode::{Utf8Decoder, Utf8Mode};
+use dialect::Dialect;
+use dictrow::KeyCache;
+use drift::HeaderCheck;
+use errors::{CsvIoError, FileChangedError};
+use fast::Engine;
+use fixed::FixedWidthReader;
+use gunzip::Gunzip;
+use headers::{DuplicateHeaders, HeaderRules};
+use intern::Interner;
+use jsonl::{json_to_py, py_to_json, JsonlReader, JsonlWriter};
+use lock::LockMode;
+use output::{BufferedOutput, Turnstile, WriteQueue};
+use paths::FilePath;
+use perms::FilePermissions;
+use progress::Progress;
+use project::Projection;
+use quota::Quota;
+use retry::RetryPolicy;
+use rfc4180::Rfc4180;
+use row::{Row, RowFormat, RowType};
+use socket::Conn;
+use stats::Stats;
+use stream::{expand_paths, RecordStream};
+use throttle::Throttle;
+use typed::Schema;
+use values::ValueFormat;
 
 // Exception classes (ABI3 compatible)
-create_exception!(_rapcsv, CSVError, PyException);
-create_exception!(_rapcsv, CSVFieldCountError, PyException);
 
 /// File source enum for supporting both paths and file handles.
 #[allow(dead_code)] // Fields are accessed via pattern matching, not direct field access
 enum FileSource {
-    Path(String),
+    Path(FilePath),
     Handle {
         file: Py<PyAny>,       // Python file-like object with async read/write methods
         event_loop: Py<PyAny>, // Event loop reference for run_coroutine_threadsafe
     },
+    Descriptor(Py<Descriptor>), // From from_fd(), from_socket() or to_socket()
+}
+
+impl FileSource {
+    fn access(&self) -> Access {
+        match self {
+            FileSource::Path(_) => Access::Path,
+            FileSource::Handle { .. } => Access::Handle,
+            FileSource::Descriptor(_) => Access::Descriptor,
+        }
+    }
+}
+
+/// How `read_chunk` and `write_chunk` reach a source's bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Access {
+    Path,       // A file opened from its path on first use
+    Descriptor, // An open file or connection, whose name is not a path
+    Handle,     // A Python file handle
+}
+
+/// An open file descriptor or TCP connection, which `from_fd()`, `from_socket()` and
+/// `to_socket()` pass to the `Reader` and `Writer` constructors in place of a path.
+/// It names no file, so no `allowed_root` applies to it.
+#[pyclass(frozen)]
+struct Descriptor {
+    conn: StdMutex<Option<Conn>>,
+    name: FilePath, // Shown in messages, e.g. `<fd 3>`
+}
+
+impl Descriptor {
+    fn new(conn: impl Into<Conn>, name: String) -> Self {
+        Descriptor {
+            conn: StdMutex::new(Some(conn.into())),
+            name: FilePath::from(name),
+        }
+    }
+
+    /// The descriptor's file or connection, which only one constructor can use.
+    fn take(&self) -> PyResult<Conn> {
+        self.conn
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "{} is already in use",
+                    self.name
+                ))
+            })
+    }
 }
 
 // Note: FileSource::Handle cannot be cloned directly without GIL
 // We'll avoid cloning FileSource and instead clone the path/handle separately where needed
 
+/// What a Python file-like object's `read()` returned: text from a file opened in
+/// text mode, or bytes from a binary stream such as an HTTP response body.
+enum Chunk {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
 /// Read from a Python async file-like object.
 ///
 /// Calls the file's `read(size)` method and awaits the coroutine using spawn_blocking
@@ -38,12 +191,12 @@ async fn read_from_python_file(
     file_handle: Py<PyAny>,
     event_loop: Py<PyAny>, // Event loop reference for run_coroutine_threadsafe
     size: usize,
-) -> PyResult<String> {
+) -> PyResult<Chunk> {
     // Use spawn_blocking to run Python async code without blocking Tokio
     let result = tokio::task::spawn_blocking(move || {
         #[allow(deprecated)]
         // Python::with_gil is still required in blocking contexts (spawn_blocking)
-        Python::with_gil(|py| -> PyResult<String> {
+        Python::with_gil(|py| -> PyResult<Chunk> {
             let handle_bound = file_handle.bind(py);
             let loop_bound = event_loop.bind(py);
 
@@ -55,8 +208,10 @@ async fn read_from_python_file(
             // Call the helper function which schedules read() on the event loop
             // Pass arguments as positional: file_handle, method_name, event_loop, *args
             let result = helper_func.call1((handle_bound, "read", loop_bound, size))?;
-            let result_str = result.extract::<String>()?;
-            Ok(result_str)
+            match result.extract::<String>() {
+                Ok(text) => Ok(Chunk::Text(text)),
+                Err(_) => Ok(Chunk::Bytes(result.extract::<Vec<u8>>()?)),
+            }
         })
     })
     .await
@@ -69,14 +224,23 @@ async fn read_from_python_file(
     Ok(result)
 }
 
+/// Whether `handle` is a `rapcsv.SourceAdapter` or `rapcsv.SinkAdapter`, which deal in
+/// bytes and are closed by the reader or writer using them.
+fn is_adapter(handle: &Bound<'_, PyAny>) -> PyResult<bool> {
+    let rapcsv_mod = handle.py().import("rapcsv")?;
+    Ok(handle.is_instance(&rapcsv_mod.getattr("SourceAdapter")?)?
+        || handle.is_instance(&rapcsv_mod.getattr("SinkAdapter")?)?)
+}
+
 /// Write to a Python async file-like object.
 ///
 /// Calls the file's `write(data)` method and awaits the coroutine using spawn_blocking.
 /// Uses `asyncio.run_coroutine_threadsafe()` to schedule on the original event loop.
+/// A `SinkAdapter` is given `bytes`, other handles `str`.
 async fn write_to_python_file(
     file_handle: Py<PyAny>,
     event_loop: Py<PyAny>, // Event loop reference for run_coroutine_threadsafe
-    data: String,
+    data: Vec<u8>,
 ) -> PyResult<()> {
     // Use spawn_blocking to run Python async code without blocking Tokio
     tokio::task::spawn_blocking(move || {
@@ -93,7 +257,13 @@ async fn write_to_python_file(
 
             // Call the helper function which schedules write() on the event loop
             // Pass arguments as positional: file_handle, method_name, event_loop, *args
-            helper_func.call1((handle_bound, "write", loop_bound, data))?;
+            if is_adapter(handle_bound)? {
+                helper_func.call1((handle_bound, "write", loop_bound, PyBytes::new(py, &data)))?;
+            } else {
+                let data = String::from_utf8(data)
+                    .map_err(|_| CsvIoError::new_err("Invalid UTF-8 in CSV data"))?;
+                helper_func.call1((handle_bound, "write", loop_bound, data))?;
+            }
 
             Ok(())
         })
@@ -108,6 +278,49 @@ async fn write_to_python_file(
     Ok(())
 }
 
+/// Call `close()` on the `SourceAdapter` or `SinkAdapter` a reader or writer uses, once.
+/// Other Python file handles stay open; their lifetime is managed by the caller.
+async fn close_adapter(
+    file_handle: &Arc<StdMutex<Option<Py<PyAny>>>>,
+    event_loop: &Arc<StdMutex<Option<Py<PyAny>>>>,
+) -> PyResult<()> {
+    let file_handle = Arc::clone(file_handle);
+    let event_loop = Arc::clone(event_loop);
+    tokio::task::spawn_blocking(move || {
+        #[allow(deprecated)]
+        // Python::with_gil is still required in blocking contexts (spawn_blocking)
+        Python::with_gil(|py| -> PyResult<()> {
+            let lock_err =
+                |_| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Failed to lock file handle");
+            let handle = {
+                let mut slot = file_handle.lock().map_err(lock_err)?;
+                match slot.as_ref() {
+                    Some(handle) if is_adapter(handle.bind(py))? => slot.take(),
+                    _ => None,
+                }
+            };
+            let Some(handle) = handle else {
+                return Ok(());
+            };
+            let event_loop = event_loop
+                .lock()
+                .map_err(lock_err)?
+                .as_ref()
+                .map(|event_loop| event_loop.clone_ref(py));
+            py.import("rapcsv")?
+                .getattr("_call_file_method_threadsafe")?
+                .call1((handle, "close", event_loop))?;
+            Ok(())
+        })
+    })
+    .await
+    .map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+            "Failed to await Python coroutine: {e}"
+        ))
+    })?
+}
+
 /// Get the event loop for file handle operations.
 /// Returns the stored loop (should always be Some for file handles).
 /// Must be called from async context with Python GIL available.
@@ -163,7 +376,8 @@ fn wrap_awaitable_into_coroutine<'py>(
 }
 
 /// Validate a file path for security and correctness.
-fn validate_path(path: &str) -> PyResult<()> {
+fn validate_path(path: impl Into<FilePath>) -> PyResult<()> {
+    let path = path.into();
     if path.is_empty() {
         return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
             "Path cannot be empty",
@@ -174,6 +388,457 @@ fn validate_path(path: &str) -> PyResult<()> {
             "Path cannot contain null bytes",
         ));
     }
+    if sftp::is_url(&path) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "sftp:// URLs can only be given to Reader and Writer, one at a time: {path}"
+        )));
+    }
+    sandbox::check(&path, None)?;
+    #[cfg(windows)]
+    if let Some(problem) = paths::windows_problem(&path) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{problem}: {path}"
+        )));
+    }
+    Ok(())
+}
+
+/// Convert a `str`, `bytes` or `os.PathLike` path to a `FilePath`, as `os.fsdecode`
+/// does. Returns `None` for anything else.
+fn fspath(obj: &Bound<'_, PyAny>) -> PyResult<Option<FilePath>> {
+    if let Ok(path) = obj.extract::<String>() {
+        return Ok(Some(path.into()));
+    }
+    if obj.is_instance_of::<PyString>()
+        || obj.is_instance_of::<PyBytes>()
+        || obj.hasattr("__fspath__")?
+    {
+        let path = obj.py().import("os")?.call_method1("fsdecode", (obj,))?;
+        return Ok(Some(FilePath::new(path.extract()?)));
+    }
+    Ok(None)
+}
+
+/// Duplicate the descriptor `fd` into a tokio `File` for `from_fd()`. rapcsv owns (and
+/// eventually closes) the duplicate; the caller keeps ownership of `fd`.
+#[cfg(unix)]
+fn file_from_fd(py: Python<'_>, fd: i32) -> PyResult<File> {
+    use std::os::fd::FromRawFd;
+    // os.dup() validates `fd` and returns a new non-inheritable descriptor
+    let duplicate: i32 = py.import("os")?.call_method1("dup", (fd,))?.extract()?;
+    // SAFETY: `duplicate` was just created by dup() and nothing else owns it
+    let file = unsafe { std::fs::File::from_raw_fd(duplicate) };
+    Ok(File::from_std(file))
+}
+
+#[cfg(not(unix))]
+fn file_from_fd(_py: Python<'_>, _fd: i32) -> PyResult<File> {
+    Err(PyErr::new::<pyo3::exceptions::PyNotImplementedError, _>(
+        "from_fd() is only supported on Unix",
+    ))
+}
+
+/// Reject keyword arguments of `from_fd()` or `from_socket()` (`method`) that need a
+/// real path, such as `lock`.
+fn reject_fd_options(
+    kwargs: Option<&Bound<'_, PyDict>>,
+    names: &[&str],
+    method: &str,
+) -> PyResult<()> {
+    let Some(kwargs) = kwargs else {
+        return Ok(());
+    };
+    for name in names {
+        if let Some(value) = kwargs.get_item(name)? {
+            if value.is_truthy()? {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "{name} requires a file path and cannot be used with {method}"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Options of the `Reader` constructor that need a real path.
+const READER_PATH_OPTIONS: &[&str] = &[
+    "lock",
+    "mmap",
+    "verify_checksum",
+    "encryption_key",
+    "allowed_root",
+    "ssh_key",
+];
+
+/// Options of the `Writer` constructor that need a real path.
+const WRITER_PATH_OPTIONS: &[&str] = &[
+    "lock",
+    "checksum",
+    "encryption_key",
+    "allowed_root",
+    "file_mode",
+    "file_group",
+    "ssh_key",
+];
+
+/// Resolved constructor argument: source kind, display path, file handle and event loop.
+type ResolvedSource = (
+    FileSource,
+    FilePath,
+    Arc<StdMutex<Option<Py<PyAny>>>>,
+    Arc<StdMutex<Option<Py<PyAny>>>>,
+);
+
+/// Resolve a `path_or_handle` constructor argument.
+///
+/// Strings, bytes and `os.PathLike` objects are validated and treated as file paths.
+/// Anything else is assumed to be an async file-like object; the running event loop is
+/// captured so the handle's coroutines can later be scheduled with
+/// `run_coroutine_threadsafe`.
+fn resolve_source(py: Python<'_>, path_or_handle: &Bound<'_, PyAny>) -> PyResult<ResolvedSource> {
+    if let Some(path_str) = fspath(path_or_handle)? {
+        validate_path(&path_str)?;
+        return Ok((
+            FileSource::Path(path_str.clone()),
+            path_str,
+            Arc::new(StdMutex::new(None)),
+            Arc::new(StdMutex::new(None)),
+        ));
+    }
+
+    // Assume it's a file-like object
+    let handle = path_or_handle.clone().unbind();
+    // For file handles, use a placeholder path for error messages
+    let placeholder_path = FilePath::from("<file_handle>");
+
+    // Get the running event loop (required for aiofiles/rapfiles handles)
+    // Must be available during construction since we're in Python's context
+    let asyncio = py.import("asyncio")?;
+    let loop_obj = asyncio.call_method0("get_running_loop").map_err(|_| {
+        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+            "No running event loop found. File handles require a running event loop. \
+             Use 'async with' or ensure asyncio.run() has been called.",
+        )
+    })?;
+
+    let handle_clone = handle.clone_ref(py);
+    let loop_clone = loop_obj.unbind();
+    Ok((
+        FileSource::Handle {
+            file: handle_clone.clone_ref(py),
+            event_loop: loop_clone.clone_ref(py),
+        },
+        placeholder_path,
+        Arc::new(StdMutex::new(Some(handle_clone))),
+        Arc::new(StdMutex::new(Some(loop_clone))), // Always store the loop
+    ))
+}
+
+/// Resolve the `path_or_handle` of a `Reader` or `Writer`, which may also be an
+/// `sftp://` URL or a `Descriptor`. The URL is returned parsed as well.
+fn resolve_remote_source(
+    py: Python<'_>,
+    path_or_handle: &Bound<'_, PyAny>,
+) -> PyResult<(ResolvedSource, Option<sftp::Url>)> {
+    if let Ok(descriptor) = path_or_handle.cast::<Descriptor>() {
+        let resolved = (
+            FileSource::Descriptor(descriptor.clone().unbind()),
+            descriptor.get().name.clone(),
+            Arc::new(StdMutex::new(None)),
+            Arc::new(StdMutex::new(None)),
+        );
+        return Ok((resolved, None));
+    }
+    if let Some(path) = fspath(path_or_handle)? {
+        if let Some(url) = sftp::Url::parse(&path)? {
+            sandbox::check(&path, None)?; // A remote file is outside any root
+            let resolved = (
+                FileSource::Path(path.clone()),
+                path,
+                Arc::new(StdMutex::new(None)),
+                Arc::new(StdMutex::new(None)),
+            );
+            return Ok((resolved, Some(url)));
+        }
+    }
+    Ok((resolve_source(py, path_or_handle)?, None))
+}
+
+/// Convert an `ssh_key` argument to the path of the key file.
+fn ssh_key_arg(key: Option<&Bound<'_, PyAny>>, remote: bool) -> PyResult<Option<FilePath>> {
+    let Some(key) = key else {
+        return Ok(None);
+    };
+    if !remote {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "ssh_key requires an sftp:// URL",
+        ));
+    }
+    fspath(key)?.map(Some).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyTypeError, _>("ssh_key must be str, bytes or os.PathLike")
+    })
+}
+
+/// Clone the stored Python file handle and event loop out of their mutexes.
+///
+/// Runs in `spawn_blocking` because cloning the references requires the GIL.
+async fn clone_handle_and_loop(
+    file_handle: &Arc<StdMutex<Option<Py<PyAny>>>>,
+    event_loop: &Arc<StdMutex<Option<Py<PyAny>>>>,
+) -> PyResult<(Py<PyAny>, Py<PyAny>)> {
+    let file_handle_clone = Arc::clone(file_handle);
+    let event_loop_clone = Arc::clone(event_loop);
+    tokio::task::spawn_blocking(move || {
+        #[allow(deprecated)]
+        // Python::with_gil is still required in blocking contexts (spawn_blocking)
+        Python::with_gil(|py| -> PyResult<(Py<PyAny>, Py<PyAny>)> {
+            // Extract file handle
+            let handle_guard = file_handle_clone.lock().map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Failed to lock file handle")
+            })?;
+            let handle = handle_guard
+                .as_ref()
+                .ok_or_else(|| CsvIoError::new_err("File handle not available"))?;
+
+            // Extract event loop
+            let loop_guard = event_loop_clone.lock().map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Failed to lock event loop")
+            })?;
+            let loop_obj = loop_guard.as_ref().ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Event loop not available")
+            })?;
+
+            Ok((handle.clone_ref(py), loop_obj.clone_ref(py)))
+        })
+    })
+    .await
+    .map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+            "Failed to extract file handle or event loop: {e}"
+        ))
+    })?
+}
+
+/// Default size of the OS-level read buffer for path-backed files.
+const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Default `write_size` of a `Writer` to a file path. Handles, descriptors and sockets
+/// are written through, since whoever reads them expects each call's rows.
+const DEFAULT_WRITE_SIZE: usize = 64 * 1024;
+
+/// Fail with `FileChangedError` if the open file shrank below what has already been read
+/// or, when it was opened from `path`, `path` now names a different file (on Unix),
+/// instead of returning garbage or repeating rows. Appends are fine, so modification
+/// times are not compared.
+async fn check_unchanged(path: &FilePath, file: &mut Conn, opened_from_path: bool) -> PyResult<()> {
+    let io_err =
+        |e: std::io::Error| CsvIoError::new_err(format!("Failed to read file {path}: {e}"));
+    let Some(open) = file.metadata().await else {
+        return Ok(()); // A connection
+    };
+    let open = open.map_err(io_err)?;
+    if !open.is_file() {
+        return Ok(()); // Pipes and sockets can't be truncated or replaced
+    }
+    let position = match file.position().await {
+        Some(position) => position.map_err(io_err)?,
+        None => return Ok(()),
+    };
+    if open.len() < position {
+        return Err(FileChangedError::new_err(format!(
+            "File {path} was truncated while being read ({} bytes left, {position} already read)",
+            open.len()
+        )));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        // A missing path is fine: the open file is still readable
+        let current = match opened_from_path {
+            true => tokio::fs::metadata(paths::native(path)).await.ok(),
+            false => None,
+        };
+        if let Some(current) = current {
+            if (current.dev(), current.ino()) != (open.dev(), open.ino()) {
+                return Err(FileChangedError::new_err(format!(
+                    "File {path} was replaced while being read"
+                )));
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = opened_from_path;
+    Ok(())
+}
+
+/// Read the next chunk from a path-backed file or a Python file handle.
+///
+/// Path-backed files are opened lazily on first use, with a read buffer of
+/// `buffer_size` bytes. Returns the chunk and whether EOF was reached. Bytes are
+/// decoded by `decoder`, so a character split between reads is completed by the next
+/// chunk. Bytes read from a Python handle are decompressed by `gunzip` if they are
+/// gzip data.
+#[allow(clippy::too_many_arguments)] // Shared by the CSV and JSON Lines readers
+#[tracing::instrument(level = "debug", name = "refill", skip_all, fields(path = %path, bytes))]
+async fn read_chunk(
+    access: Access,
+    path: &FilePath,
+    file: &Arc<Mutex<Option<BufReader<Conn>>>>,
+    file_handle: &Arc<StdMutex<Option<Py<PyAny>>>>,
+    event_loop: &Arc<StdMutex<Option<Py<PyAny>>>>,
+    chunk_size: usize,
+    buffer_size: usize,
+    retry: Option<&RetryPolicy>,
+    decoder: &Utf8Decoder,
+    gunzip: Option<&Gunzip>,
+) -> PyResult<(String, bool)> {
+    let mut attempt = 0;
+    if access != Access::Handle {
+        // Use Tokio File/BufReader for path-based sources and descriptors
+        let mut file_guard = file.lock().await;
+        if access == Access::Descriptor && file_guard.is_none() {
+            return Err(CsvIoError::new_err(format!("{path} is closed")));
+        }
+        while file_guard.is_none() {
+            match File::open(paths::native(path))
+                .instrument(tracing::debug_span!("open", path = %path))
+                .await
+            {
+                Ok(opened_file) => {
+                    let conn = uring::conn(opened_file).await;
+                    *file_guard = Some(BufReader::with_capacity(buffer_size, conn));
+                }
+                Err(e) => {
+                    let error = CsvIoError::new_err(format!("Failed to open file {path}: {e}"));
+                    attempt += 1;
+                    if !retry::again(retry, attempt, retry::transient_io(&e), &error).await? {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+        let reader = file_guard.as_mut().unwrap();
+        if reader.buffer().is_empty() {
+            // About to read from the OS again: make sure it's still the same file
+            check_unchanged(path, reader.get_mut(), access == Access::Path).await?;
+        }
+        let mut chunk = vec![0u8; chunk_size];
+        loop {
+            match reader.read(&mut chunk).await {
+                Ok(0) => {
+                    // EOF: a character still cut off at this point is invalid
+                    return Ok((decoder.decode(&[], true)?, true));
+                }
+                Ok(n) => {
+                    chunk.truncate(n);
+                    let chunk_str = decoder.decode(&chunk, false)?;
+                    tracing::Span::current().record("bytes", chunk_str.len());
+                    return Ok((chunk_str, false)); // Data read
+                }
+                Err(e) => {
+                    let error = socket::connect_error(&e).unwrap_or_else(|| {
+                        CsvIoError::new_err(format!("Failed to read file {path}: {e}"))
+                    });
+                    attempt += 1;
+                    if !retry::again(retry, attempt, retry::transient_io(&e), &error).await? {
+                        return Err(error);
+                    }
+                    reader.get_mut().reconnect();
+                }
+            }
+        }
+    } else {
+        // Use Python file handle for Handle sources
+        loop {
+            let (handle_py, loop_py) = clone_handle_and_loop(file_handle, event_loop).await?;
+            match read_from_python_file(handle_py, loop_py, chunk_size).await {
+                Ok(Chunk::Text(chunk_str)) => {
+                    tracing::Span::current().record("bytes", chunk_str.len());
+                    let eof = chunk_str.is_empty();
+                    return Ok((chunk_str, eof));
+                }
+                Ok(Chunk::Bytes(chunk)) => {
+                    tracing::Span::current().record("bytes", chunk.len());
+                    let eof = chunk.is_empty();
+                    let chunk = match gunzip {
+                        Some(gunzip) => gunzip.feed(path, chunk, eof)?,
+                        None => chunk,
+                    };
+                    return Ok((decoder.decode(&chunk, eof)?, eof));
+                }
+                Err(error) => {
+                    attempt += 1;
+                    if !retry::again(retry, attempt, retry::transient_py(&error), &error).await? {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Write encoded bytes to a path-backed file or a Python file handle.
+///
+/// Path-backed files are opened lazily in append mode (created if missing), retrying
+/// transient failures to open them under `retry`. Writes themselves are not retried,
+/// since a failed write may have been partly applied. When `flush` is set the file is
+/// flushed after writing.
+#[allow(clippy::too_many_arguments)] // Shared by Writer and JsonlWriter
+#[tracing::instrument(level = "debug", name = "flush", skip_all, fields(path = %path, bytes = data.len()))]
+async fn write_chunk(
+    access: Access,
+    path: &FilePath,
+    file: &Arc<Mutex<Option<Conn>>>,
+    file_handle: &Arc<StdMutex<Option<Py<PyAny>>>>,
+    event_loop: &Arc<StdMutex<Option<Py<PyAny>>>>,
+    data: Vec<u8>,
+    flush: bool,
+    retry: Option<&RetryPolicy>,
+) -> PyResult<()> {
+    if access != Access::Handle {
+        // Use Tokio File for path-based sources and descriptors
+        let mut file_guard = file.lock().await;
+        if access == Access::Descriptor && file_guard.is_none() {
+            return Err(CsvIoError::new_err(format!("{path} is closed")));
+        }
+        let mut attempt = 0;
+        while file_guard.is_none() {
+            use tokio::fs::OpenOptions;
+            // Append mode - creates file if it doesn't exist
+            match OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(paths::native(path))
+                .instrument(tracing::debug_span!("open", path = %path))
+                .await
+            {
+                Ok(opened_file) => *file_guard = Some(uring::conn(opened_file).await),
+                Err(e) => {
+                    let error = CsvIoError::new_err(format!("Failed to open file {path}: {e}"));
+                    attempt += 1;
+                    if !retry::again(retry, attempt, retry::transient_io(&e), &error).await? {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+        let file_ref = file_guard.as_mut().unwrap();
+        file_ref.write_all(&data).await.map_err(|e| {
+            socket::connect_error(&e)
+                .unwrap_or_else(|| CsvIoError::new_err(format!("Failed to write file {path}: {e}")))
+        })?;
+        if flush {
+            file_ref.flush().await.map_err(|e| {
+                socket::connect_error(&e).unwrap_or_else(|| {
+                    CsvIoError::new_err(format!("Failed to flush file {path}: {e}"))
+                })
+            })?;
+        }
+    } else {
+        // Use Python file handle for Handle sources
+        let (handle_py, loop_py) = clone_handle_and_loop(file_handle, event_loop).await?;
+        write_to_python_file(handle_py, loop_py, data).await?;
+    }
     Ok(())
 }
 
@@ -184,12 +849,12 @@ struct DialectConfig {
     delimiter: u8,
     quotechar: u8,
     escapechar: Option<u8>,
-    #[allow(dead_code)] // Stored for dialect configuration, used when applying to reader/writer
     quoting: QuoteStyle,
     lineterminator: Terminator,
     skipinitialspace: bool,
     strict: bool,
     double_quote: bool,
+    delim_whitespace: bool, // Runs of spaces and tabs separate fields (reading only)
 }
 
 impl Default for DialectConfig {
@@ -203,14 +868,17 @@ impl Default for DialectConfig {
             skipinitialspace: false,
             strict: false,
             double_quote: true,
+            delim_whitespace: false,
         }
     }
 }
 
 impl DialectConfig {
-    /// Create dialect config from Python parameters.
+    /// Create dialect config from Python parameters. Options left as None are taken
+    /// from `dialect` (a `Dialect`, preset name or `csv.Dialect`) when one is given.
     #[allow(clippy::too_many_arguments)] // Required for Python API compatibility
     fn from_python(
+        dialect: Option<&Bound<'_, PyAny>>,
         delimiter: Option<&str>,
         quotechar: Option<&str>,
         escapechar: Option<&str>,
@@ -220,13 +888,24 @@ impl DialectConfig {
         strict: Option<bool>,
         double_quote: Option<bool>,
     ) -> PyResult<Self> {
+        let base = dialect.map(Dialect::resolve).transpose()?;
+        let base = base.as_ref();
+        let delimiter = delimiter.or(base.map(|d| d.delimiter.as_str()));
+        let quotechar = quotechar.or(base.map(|d| d.quotechar.as_str()));
+        let escapechar = escapechar.or(base.and_then(|d| d.escapechar.as_deref()));
+        let quoting = quoting.or(base.map(|d| d.quoting));
+        let lineterminator = lineterminator.or(base.map(|d| d.lineterminator.as_str()));
+        let skipinitialspace = skipinitialspace.or(base.map(|d| d.skipinitialspace));
+        let strict = strict.or(base.map(|d| d.strict));
+        let double_quote = double_quote.or(base.map(|d| d.double_quote));
+
         let delimiter = delimiter
             .and_then(|s| s.as_bytes().first().copied())
             .unwrap_or(b',');
 
-        if delimiter.is_ascii_whitespace() {
+        if delimiter == b'\r' || delimiter == b'\n' {
             return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                "delimiter cannot be whitespace",
+                "delimiter cannot be a line break",
             ));
         }
 
@@ -264,6 +943,40 @@ impl DialectConfig {
             skipinitialspace: skipinitialspace.unwrap_or(false),
             strict: strict.unwrap_or(false),
             double_quote: double_quote.unwrap_or(true),
+            delim_whitespace: false,
+        })
+    }
+
+    /// The character that quotes fields when reading, or None with `quoting=0`
+    /// (QUOTE_NONE), where quote characters are read as ordinary characters.
+    fn read_quote(&self) -> Option<u8> {
+        (!matches!(self.quoting, QuoteStyle::Never)).then_some(self.quotechar)
+    }
+
+    /// The dialect as constructor keyword arguments, for saved reader positions.
+    fn to_json(&self) -> serde_json::Value {
+        let quoting = match self.quoting {
+            QuoteStyle::Never => 0,
+            QuoteStyle::Always => 2,
+            QuoteStyle::NonNumeric => 3,
+            _ => 1,
+        };
+        let lineterminator = match self.lineterminator {
+            Terminator::Any(b) => (b as char).to_string(),
+            _ => "\r\n".to_string(),
+        };
+        // Whitespace-delimited readers take no delimiter
+        let delimiter = (!self.delim_whitespace).then(|| (self.delimiter as char).to_string());
+        serde_json::json!({
+            "delimiter": delimiter,
+            "quotechar": (self.quotechar as char).to_string(),
+            "escapechar": self.escapechar.map(|b| (b as char).to_string()),
+            "quoting": quoting,
+            "lineterminator": lineterminator,
+            "skipinitialspace": self.skipinitialspace,
+            "strict": self.strict,
+            "double_quote": self.double_quote,
+            "delim_whitespace": self.delim_whitespace,
         })
     }
 
@@ -272,6 +985,7 @@ impl DialectConfig {
         builder
             .delimiter(self.delimiter)
             .quote(self.quotechar)
+            .quoting(self.read_quote().is_some())
             .terminator(self.lineterminator)
             .flexible(!self.strict);
 
@@ -302,7 +1016,8 @@ impl DialectConfig {
         builder
             .delimiter(self.delimiter)
             .quote(self.quotechar)
-            .terminator(self.lineterminator);
+            .terminator(self.lineterminator)
+            .flexible(!self.strict);
 
         // WriterBuilder uses quote() for quote character and doesn't have separate quote_style()
         // The quoting style is primarily controlled by quote character and double_quote behavior
@@ -329,17 +1044,53 @@ impl DialectConfig {
 /// - Streaming support for large files (reads incrementally, not entire file into memory)
 /// - Context manager support (`async with`)
 /// - aiocsv compatibility (AsyncReader/AsyncWriter aliases)
-/// - CSV-specific exception types (CSVError, CSVFieldCountError)
+/// - A rapcsv exception hierarchy under RapcsvError (CsvParseError, CsvIoError, ...)
 /// - RFC 4180 compliant CSV parsing and writing
-#[pymodule]
-fn _rapcsv(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+/// - Free-threaded CPython support: the module does not need the GIL, as every
+///   class keeps its state behind locks
+#[pymodule(gil_used = false)]
+fn _rapcsv(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Reader>()?;
     m.add_class::<Writer>()?;
     m.add_class::<AsyncDictReader>()?;
     m.add_class::<AsyncDictWriter>()?;
+    m.add_class::<JsonlReader>()?;
+    m.add_class::<JsonlWriter>()?;
+    m.add_class::<rotate::RotatingWriter>()?;
+    m.add_class::<FixedWidthReader>()?;
+    m.add_class::<Row>()?;
+    m.add_class::<Dialect>()?;
+    m.add_class::<sqlite::QueryBatches>()?;
+    m.add_function(wrap_pyfunction!(sqlite::to_sqlite, m)?)?;
+    m.add_function(wrap_pyfunction!(sqlite::query, m)?)?;
+    m.add_function(wrap_pyfunction!(sqlite::query_batches, m)?)?;
+    m.add_function(wrap_pyfunction!(ops::concat, m)?)?;
+    m.add_function(wrap_pyfunction!(ops::split, m)?)?;
+    m.add_function(wrap_pyfunction!(ops::restructure, m)?)?;
+    m.add_function(wrap_pyfunction!(partition::partition, m)?)?;
+    m.add_function(wrap_pyfunction!(convert::convert, m)?)?;
+    m.add_function(wrap_pyfunction!(sort::sort, m)?)?;
+    m.add_function(wrap_pyfunction!(sort::merge_sorted, m)?)?;
+    m.add_function(wrap_pyfunction!(dedupe::dedupe, m)?)?;
+    m.add_function(wrap_pyfunction!(join::join, m)?)?;
+    m.add_function(wrap_pyfunction!(diff::diff, m)?)?;
+    m.add_function(wrap_pyfunction!(profile::profile, m)?)?;
+    m.add_function(wrap_pyfunction!(lint::lint, m)?)?;
+    m.add_function(wrap_pyfunction!(repair::repair, m)?)?;
+    m.add_function(wrap_pyfunction!(validate::validate, m)?)?;
+    m.add_function(wrap_pyfunction!(transform::transform, m)?)?;
+    m.add_function(wrap_pyfunction!(transpose::transpose, m)?)?;
+    m.add_function(wrap_pyfunction!(uring::io_backend, m)?)?;
+    m.add_function(wrap_pyfunction!(archive::list_archive, m)?)?;
+    m.add_function(wrap_pyfunction!(index::index_cache_info, m)?)?;
+    m.add_function(wrap_pyfunction!(index::clear_index_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(rowhash::row_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(logging::set_log_level, m)?)?;
+    m.add_function(wrap_pyfunction!(sandbox::set_allowed_root, m)?)?;
+    m.add_function(wrap_pyfunction!(sandbox::get_allowed_root, m)?)?;
+    logging::install();
     // Register exception classes (required for create_exception! to be accessible from Python)
-    m.add("CSVError", py.get_type::<CSVError>())?;
-    m.add("CSVFieldCountError", py.get_type::<CSVFieldCountError>())?;
+    errors::register(m)?;
 
     // Register the wrapper function (no exec/eval needed - uses pyo3-async-runtimes)
     m.add_function(wrap_pyfunction!(await_wrapper_internal, m)?)?;
@@ -347,6 +1098,34 @@ fn _rapcsv(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     Ok(())
 }
 
+/// The arguments a constructor was called with, by name, kept so the object can be
+/// pickled and built again in another process.
+macro_rules! keyword_args {
+    ($py:expr; $($name:ident),* $(,)?) => {{
+        let options = PyDict::new($py);
+        $(options.set_item(stringify!($name), &$name)?;)*
+        options.unbind()
+    }};
+}
+
+/// Constructor arguments a reader or writer is pickled with, or why it can't be.
+type Pickled = Result<Py<PyDict>, &'static str>;
+
+/// The arguments to build a pickled reader or writer again with, as
+/// `__getnewargs_ex__` returns them.
+fn pickled_args(
+    py: Python<'_>,
+    options: &Pickled,
+    class: &str,
+) -> PyResult<(Py<PyTuple>, Py<PyDict>)> {
+    match options {
+        Ok(options) => Ok((PyTuple::empty(py).unbind(), options.clone_ref(py))),
+        Err(reason) => Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "cannot pickle a {class} {reason}"
+        ))),
+    }
+}
+
 /// Async CSV reader.
 ///
 /// Provides streaming async CSV reading with incremental file processing.
@@ -372,21 +1151,53 @@ fn _rapcsv(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
 /// async with Reader("data.csv") as reader:
 ///     row = await reader.read_row()
 /// ```
-#[pyclass]
+#[pyclass(subclass, module = "rapcsv")]
 struct Reader {
-    source: FileSource, // Either Path(String) or Handle {file, event_loop}
-    path: String,       // Keep for backward compatibility and error messages
-    file: Arc<Mutex<Option<BufReader<File>>>>, // Only used when source is Path
-    file_handle: Arc<StdMutex<Option<Py<PyAny>>>>, // Python file handle when source is Handle (std::sync::Mutex for blocking locks in spawn_blocking)
-    event_loop: Arc<StdMutex<Option<Py<PyAny>>>>, // Event loop reference for run_coroutine_threadsafe
-    buffer: Arc<Mutex<String>>,
-    buffer_start: Arc<Mutex<usize>>, // Start position in buffer for next parse
-    position: Arc<Mutex<usize>>,     // Record index (0-based)
-    line_num: Arc<Mutex<usize>>,     // Line number (1-based, accounting for multi-line records)
-    dialect: DialectConfig,
-    read_size: usize, // Configurable chunk size for reading
-    #[allow(dead_code)] // Captured at instantiation for future validation
-    field_size_limit: Option<usize>, // Maximum field size (captured at instantiation)
+    stream: RecordStream, // Shared streaming state (file handle, buffer, position, line_num)
+    dialect: serde_json::Value, // Dialect keyword arguments, saved by state()
+    options: Pickled,     // Constructor arguments, for pickling
+    checkpoint: Option<Arc<Checkpoint>>, // Sidecar file the position is saved to
+    format: RowFormat,    // Row type and field type of returned rows
+    eof: EndOfFile,       // What read_row() returns at EOF
+}
+
+/// What `read_row()` returns once the input is exhausted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EndOfFile {
+    /// An empty list, as in earlier releases.
+    Empty,
+    /// `None`.
+    Null,
+    /// Raise `EOFError`.
+    Raise,
+    /// Raise `StopAsyncIteration`, for `__anext__`.
+    Stop,
+}
+
+impl EndOfFile {
+    fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "empty" => Ok(EndOfFile::Empty),
+            "none" => Ok(EndOfFile::Null),
+            "raise" => Ok(EndOfFile::Raise),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "eof must be 'empty', 'none' or 'raise', got '{other}'"
+            ))),
+        }
+    }
+
+    /// The value to return at EOF, or the error to raise. `empty` is the empty row
+    /// returned in `Empty` mode (`[]` or `{}`).
+    fn resolve(self, py: Python<'_>, empty: Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        match self {
+            EndOfFile::Empty => Ok(empty.unbind()),
+            EndOfFile::Null => Ok(py.None()),
+            EndOfFile::Raise => Err(PyErr::new::<pyo3::exceptions::PyEOFError, _>(
+                "End of CSV input",
+            )),
+            EndOfFile::Stop => Err(PyErr::new::<pyo3::exceptions::PyStopAsyncIteration, _>(())),
+        }
+    }
 }
 
 #[pymethods]
@@ -394,17 +1205,141 @@ impl Reader {
     /// Open a CSV file for reading.
     ///
     /// # Arguments
-    /// * `path` - Path to the CSV file
+    /// * `path` - Path to the CSV file, a list of paths or a glob pattern read as one
+    ///   stream, or an `sftp://user@host[:port]/path` URL (`sftp` cargo feature)
     /// * `delimiter` - Field delimiter (default: ',')
     /// * `quotechar` - Quote character (default: '"')
     /// * `escapechar` - Escape character (default: None)
     /// * `quoting` - Quoting style: 0=QUOTE_NONE, 1=QUOTE_MINIMAL, 2=QUOTE_ALL, 3=QUOTE_NONNUMERIC, 4=QUOTE_NOTNULL, 6=QUOTE_STRINGS
+    ///   (with 0, quote characters are read as ordinary characters)
     /// * `lineterminator` - Line terminator (default: '\r\n')
     /// * `skipinitialspace` - Skip whitespace after delimiter (default: false)
     /// * `strict` - Strict mode for field count validation (default: false)
     /// * `double_quote` - Handle doubled quotes (default: true)
     /// * `read_size` - Buffer size for reading chunks (default: 8192)
     /// * `field_size_limit` - Maximum field size in bytes (default: None, uses csv crate default)
+    /// * `follow` - At EOF, wait for appended data instead of ending the stream (default: false)
+    /// * `poll_interval` - Seconds between checks for new data in follow mode (default: 0.5)
+    /// * `has_header` - Files start with a header row; with several files, only the first
+    ///   file's header is returned (default: true)
+    /// * `include_filename` - Append the originating file path to every row (default: false)
+    /// * `concurrency` - Files of a multi-file stream parsed at once on worker threads;
+    ///   rows are still returned in file order (default: 1)
+    /// * `engine` - Parser for `read_all()`: "default" or "fast", a SIMD-accelerated
+    ///   parser for well-formed files, only for readers `read_all()` loads whole
+    ///   (default: "default")
+    /// * `mmap` - Parse the file in `read_all()` over a memory map instead of reading it
+    ///   into a buffer; only for file paths, not with `follow`. The file must not change
+    ///   while it is read: a change is reported as an error, but a truncation raises
+    ///   SIGBUS and kills the process (default: false)
+    /// * `buffer_size` - Size in bytes of the read buffer for file paths, i.e. how much is
+    ///   requested from the OS at a time (default: 65536)
+    /// * `raw` - Return fields as `bytes` instead of `str` (default: false)
+    /// * `lock` - "shared" to hold a shared advisory lock on each file while it is read,
+    ///   waiting for writers using `lock=True`; only for file paths (default: None)
+    /// * `progress` - Called as `progress(bytes_read, bytes_total, rows_emitted)` every
+    ///   `progress_interval` rows and at EOF (default: None)
+    /// * `progress_interval` - Rows between progress calls (default: 10000)
+    /// * `timeout` - Seconds each read may take before raising `asyncio.TimeoutError`
+    ///   (default: None, no limit)
+    /// * `retries` - Times to retry opening or reading after a transient IO error such as
+    ///   EAGAIN, a timeout or a dropped connection (default: 0)
+    /// * `retry_backoff` - Seconds before the first retry, doubling after each (default: 0.1)
+    /// * `on_retry` - Called as `on_retry(attempt, error, delay)` before each retry
+    ///   (default: None)
+    /// * `eof` - What `read_row()` returns at EOF: "empty" for `[]`, "none" for `None`
+    ///   or "raise" to raise `EOFError` (default: "empty")
+    /// * `row_type` - "list", "tuple" for immutable, hashable rows, or "row" for `Row`
+    ///   objects with access by column name; "row" consumes the header (default: "list")
+    /// * `checkpoint_path` - File the position is saved to as reading progresses, and
+    ///   resumed from on construction if it exists (default: None)
+    /// * `checkpoint_interval` - Rows returned between checkpoint saves (default: 10000)
+    /// * `schema` - Map of column name or position to "str", "int", "float", "bool",
+    ///   "date" or "datetime"; fields are converted in Rust and empty fields become None.
+    ///   Naming a column consumes the header (default: None)
+    /// * `date_formats` - chrono formats tried in order for "date" columns
+    ///   (default: ["%Y-%m-%d"])
+    /// * `datetime_formats` - chrono formats tried in order for "datetime" columns; a
+    ///   format with an offset gives an aware datetime (default: ISO 8601)
+    /// * `converters` - Map of column name or position to a callable applied to each of
+    ///   its values, after any `schema` conversion (default: None)
+    /// * `utf8` - "strict" raises IOError on invalid UTF-8 in a file; "lossy" replaces
+    ///   each invalid sequence with U+FFFD and counts it in `utf8_replacements`
+    ///   (default: "strict")
+    /// * `normalize_newlines` - Replace "\r\n" and "\r" inside fields with "\n"
+    ///   (default: False)
+    /// * `delim_whitespace` - Separate fields by runs of spaces and tabs instead of
+    ///   `delimiter`, ignoring leading and trailing whitespace and blank lines
+    ///   (default: False)
+    /// * `zip_member` - Member of the ZIP or tar (.tar, .tar.gz, .tgz) archive at the
+    ///   path to read, streamed out without extracting it; also written as
+    ///   "archive.zip::member.csv". A ".zip" path holding a single file reads that
+    ///   file (default: None)
+    /// * `verify_checksum` - Check each file against its digest before parsing it and
+    ///   raise `ChecksumError` on a mismatch: True reads "<path>.sha256" or
+    ///   "<path>.sha512", a hex string is the digest of a single file (default: None)
+    /// * `row_hash` - Append a stable hash of each row's fields (16 hex digits, as
+    ///   `row_hash()` computes) as a final `_hash` column (default: false)
+    /// * `encryption_key` - 32-byte AES-256 key of a file written by
+    ///   `Writer(encryption_key=...)`; it is decrypted in memory as it is read
+    ///   (default: None)
+    /// * `max_memory` - Bytes of rows `read_all()` may hold; beyond that it raises
+    ///   `MemoryError` and the rows read so far are returned by the next read. Also
+    ///   bounds the record index `seek()` builds (default: None, no limit)
+    /// * `prefetch` - Rows to keep parsed ahead on a background task while the current
+    ///   ones are processed, hiding read latency; not available with `follow` or
+    ///   `checkpoint_path` (default: None, rows are read when asked for)
+    /// * `columns` - Header names or 0-based positions of the columns to return, in
+    ///   that order; the other fields are skipped without being decoded (default: None,
+    ///   every column)
+    /// * `intern_columns` - Header names or positions of columns whose repeated values
+    ///   share one Python string object, or True to intern every column that shows at
+    ///   most 1024 distinct values (default: None)
+    /// * `skip_footer` - Records to leave out at the end of every file, such as the
+    ///   totals lines of bank and ERP exports; not available with `follow` or
+    ///   `checkpoint_path` (default: 0)
+    /// * `limit` - Stop after this many records, the header included, without reading
+    ///   the rest of the file (default: None, no limit)
+    /// * `allowed_root` - Directory the file (each file, for several) must resolve
+    ///   inside, following `..` and symlinks; on top of `set_allowed_root()`
+    ///   (default: None)
+    /// * `duplicate_headers` - What to do with a name the header row holds more than
+    ///   once: "keep_first" leaves the names and a name refers to its first column,
+    ///   "suffix" renames the repeats "id_1", "id_2", ... and "error" raises
+    ///   `CSVError`; applies to the header row returned and to names in `columns`,
+    ///   `schema` and `Row` objects (default: "keep_first")
+    /// * `normalize_headers` - True to trim, lowercase and snake_case the names of the
+    ///   header row ("Order ID" becomes "order_id"), or a callable given each name
+    ///   and returning the one to use; applied before `duplicate_headers`
+    ///   (default: None)
+    /// * `true_values` - Strings read as True in "bool" columns of `schema`: a list
+    ///   for every such column, or a dict of column name or position to a list
+    ///   (default: None, "true", "1" and "yes")
+    /// * `false_values` - Strings read as False, given like `true_values`
+    ///   (default: None, "false", "0" and "no")
+    /// * `bool_case_sensitive` - Match `true_values` and `false_values` exactly
+    ///   instead of in any case (default: false)
+    /// * `thousands` - Character separating digit groups in "int" and "float" columns
+    ///   of `schema`, dropped before parsing, e.g. "." for "1.234,56" (default: None)
+    /// * `decimal` - Decimal point of "float" columns, e.g. "," (default: ".")
+    /// * `number_symbols` - Strings such as "$", "€" or "%" removed from before and
+    ///   after the numbers of "int" and "float" columns (default: None)
+    /// * `percent_scale` - Read "float" fields ending in "%" as fractions, "12.5%"
+    ///   as 0.125 (default: false)
+    /// * `strict_rfc4180` - Raise CSVError at the first deviation from RFC 4180, such
+    ///   as a bare LF line ending, a quote in an unquoted field or a blank line
+    ///   (default: false)
+    /// * `empty_as_none` - Read empty fields as None, keeping "" for quoted empty
+    ///   strings: `a,,""` is read as `["a", None, ""]` (default: false)
+    /// * `field_spans` - Return each row as `(row, spans)`, with the `(start, end)`
+    ///   byte offsets of every field in the file, quotes included (default: false)
+    /// * `check_headers` - Raise SchemaError when a file's header differs from the
+    ///   first file's (True) or from a list of names, instead of returning its rows
+    ///   under the wrong names (default: false)
+    /// * `binary` - What to do with NUL and other control characters in fields:
+    ///   "error", "strip" or "replace" with U+FFFD (default: None, kept as read)
+    /// * `ssh_key` - Private key file to log in to the server of an `sftp://` URL with
+    ///   (default: None, the SSH agent's keys and then `~/.ssh/id_*`)
     #[new]
     #[pyo3(signature = (
         path_or_handle,
@@ -417,7 +1352,61 @@ impl Reader {
         strict = None,
         double_quote = None,
         read_size = None,
-        field_size_limit = None
+        field_size_limit = None,
+        follow = false,
+        poll_interval = None,
+        has_header = true,
+        include_filename = false,
+        concurrency = None,
+        engine = "default",
+        mmap = false,
+        buffer_size = None,
+        raw = false,
+        lock = None,
+        progress = None,
+        progress_interval = None,
+        timeout = None,
+        retries = 0,
+        retry_backoff = None,
+        on_retry = None,
+        eof = "empty",
+        row_type = "list",
+        checkpoint_path = None,
+        checkpoint_interval = None,
+        schema = None,
+        date_formats = None,
+        datetime_formats = None,
+        converters = None,
+        utf8 = "strict",
+        normalize_newlines = false,
+        dialect = None,
+        delim_whitespace = false,
+        zip_member = None,
+        verify_checksum = None,
+        row_hash = false,
+        encryption_key = None,
+        max_memory = None,
+        prefetch = None,
+        columns = None,
+        intern_columns = None,
+        skip_footer = 0,
+        limit = None,
+        allowed_root = None,
+        duplicate_headers = "keep_first",
+        normalize_headers = None,
+        true_values = None,
+        false_values = None,
+        bool_case_sensitive = false,
+        thousands = None,
+        decimal = ".",
+        number_symbols = None,
+        percent_scale = false,
+        strict_rfc4180 = false,
+        empty_as_none = false,
+        field_spans = false,
+        check_headers = None,
+        binary = None,
+        ssh_key = None
     ))]
     #[allow(clippy::too_many_arguments)] // Required for Python API compatibility
     fn new(
@@ -433,47 +1422,220 @@ impl Reader {
         double_quote: Option<bool>,
         read_size: Option<usize>,
         field_size_limit: Option<usize>,
+        follow: bool,
+        poll_interval: Option<f64>,
+        has_header: bool,
+        include_filename: bool,
+        concurrency: Option<usize>,
+        engine: &str,
+        mmap: bool,
+        buffer_size: Option<usize>,
+        raw: bool,
+        lock: Option<&str>,
+        progress: Option<Py<PyAny>>,
+        progress_interval: Option<usize>,
+        timeout: Option<f64>,
+        retries: u32,
+        retry_backoff: Option<f64>,
+        on_retry: Option<Py<PyAny>>,
+        eof: &str,
+        row_type: &str,
+        checkpoint_path: Option<&Bound<'_, PyAny>>,
+        checkpoint_interval: Option<usize>,
+        schema: Option<&Bound<'_, PyDict>>,
+        date_formats: Option<Vec<String>>,
+        datetime_formats: Option<Vec<String>>,
+        converters: Option<&Bound<'_, PyDict>>,
+        utf8: &str,
+        normalize_newlines: bool,
+        dialect: Option<&Bound<'_, PyAny>>,
+        delim_whitespace: bool,
+        zip_member: Option<&str>,
+        verify_checksum: Option<&Bound<'_, PyAny>>,
+        row_hash: bool,
+        encryption_key: Option<&[u8]>,
+        max_memory: Option<usize>,
+        prefetch: Option<usize>,
+        columns: Option<&Bound<'_, PyAny>>,
+        intern_columns: Option<&Bound<'_, PyAny>>,
+        skip_footer: usize,
+        limit: Option<usize>,
+        allowed_root: Option<&Bound<'_, PyAny>>,
+        duplicate_headers: &str,
+        normalize_headers: Option<&Bound<'_, PyAny>>,
+        true_values: Option<&Bound<'_, PyAny>>,
+        false_values: Option<&Bound<'_, PyAny>>,
+        bool_case_sensitive: bool,
+        thousands: Option<&str>,
+        decimal: &str,
+        number_symbols: Option<Vec<String>>,
+        percent_scale: bool,
+        strict_rfc4180: bool,
+        empty_as_none: bool,
+        field_spans: bool,
+        check_headers: Option<&Bound<'_, PyAny>>,
+        binary: Option<&str>,
+        ssh_key: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Self> {
-        // Try to extract as string first (file path)
-        let (source, path, file_handle, event_loop) =
-            if let Ok(path_str) = path_or_handle.extract::<String>() {
-                validate_path(&path_str)?;
+        let options = keyword_args!(
+            py;
+            path_or_handle,
+            delimiter,
+            quotechar,
+            escapechar,
+            quoting,
+            lineterminator,
+            skipinitialspace,
+            strict,
+            double_quote,
+            read_size,
+            field_size_limit,
+            follow,
+            poll_interval,
+            has_header,
+            include_filename,
+            concurrency,
+            engine,
+            mmap,
+            buffer_size,
+            raw,
+            lock,
+            progress,
+            progress_interval,
+            timeout,
+            retries,
+            retry_backoff,
+            on_retry,
+            eof,
+            row_type,
+            checkpoint_path,
+            checkpoint_interval,
+            schema,
+            date_formats,
+            datetime_formats,
+            converters,
+            utf8,
+            normalize_newlines,
+            dialect,
+            delim_whitespace,
+            zip_member,
+            verify_checksum,
+            row_hash,
+            max_memory,
+            prefetch,
+            columns,
+            intern_columns,
+            skip_footer,
+            limit,
+            allowed_root,
+            duplicate_headers,
+            normalize_headers,
+            true_values,
+            false_values,
+            bool_case_sensitive,
+            thousands,
+            decimal,
+            number_symbols,
+            percent_scale,
+            strict_rfc4180,
+            empty_as_none,
+            field_spans,
+            check_headers,
+            binary,
+            ssh_key,
+        );
+        // The key is kept out of pickles; a worker passes it to from_state() itself
+        let options = match encryption_key {
+            Some(_) => Err("with encryption_key; pass state() and the key to Reader.from_state()"),
+            None => Ok(options),
+        };
+        let engine = Engine::parse(engine)?;
+        let header_rules = HeaderRules::from_args(
+            normalize_headers,
+            DuplicateHeaders::parse(duplicate_headers)?,
+        )?;
+        let utf8 = Utf8Mode::parse(utf8)?;
+        let eof = EndOfFile::parse(eof)?;
+        let row_type = RowType::parse(row_type)?;
+        if row_type == RowType::Row && !has_header {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "row_type='row' requires a header row (has_header=True)",
+            ));
+        }
+        let mut format = RowFormat::new(raw, row_type);
+        if normalize_newlines {
+            format = format.normalize_newlines();
+        }
+        if empty_as_none {
+            format = format.empty_as_none();
+        }
+        if field_spans {
+            format = format.field_spans();
+        }
+        if schema.is_some() || converters.is_some() {
+            let schema = Schema::from_python(schema, converters, date_formats, datetime_formats)?
+                .bool_values(true_values, false_values, bool_case_sensitive)?
+                .number_format(thousands, decimal)?
+                .number_symbols(number_symbols, percent_scale)?;
+            format = format.schema(schema)?;
+            if format.needs_header() && !has_header {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "naming columns in schema or converters requires a header row (has_header=True); use positions instead",
+                ));
+            }
+        } else if true_values.is_some() || false_values.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "true_values and false_values apply to \"bool\" columns of a schema",
+            ));
+        } else if thousands.is_some() || decimal != "." || number_symbols.is_some() || percent_scale
+        {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "thousands, decimal, number_symbols and percent_scale apply to \"int\" and \"float\" columns of a schema",
+            ));
+        }
+        let lock = match lock {
+            None => None,
+            Some("shared") => Some(LockMode::Shared),
+            Some(other) => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "lock must be 'shared', got '{other}'"
+                )))
+            }
+        };
+        let files = expand_paths(path_or_handle)?;
+        let ((source, path, file_handle, event_loop), remote) = match &files {
+            Some(files) => (
                 (
-                    FileSource::Path(path_str.clone()),
-                    path_str,
+                    FileSource::Path(files[0].clone()),
+                    files[0].clone(),
                     Arc::new(StdMutex::new(None)),
                     Arc::new(StdMutex::new(None)),
-                )
-            } else {
-                // Assume it's a file-like object
-                let handle = path_or_handle.clone().unbind();
-                // For file handles, use a placeholder path for error messages
-                let placeholder_path = "<file_handle>".to_string();
-
-                // Get the running event loop (required for aiofiles/rapfiles handles)
-                // Must be available during construction since we're in Python's context
-                let asyncio = py.import("asyncio")?;
-                let loop_obj = asyncio.call_method0("get_running_loop").map_err(|_| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                        "No running event loop found. File handles require a running event loop. \
-                     Use 'async with' or ensure asyncio.run() has been called.",
-                    )
-                })?;
-
-                let handle_clone = handle.clone_ref(py);
-                let loop_clone = loop_obj.unbind();
-                (
-                    FileSource::Handle {
-                        file: handle_clone.clone_ref(py),
-                        event_loop: loop_clone.clone_ref(py),
-                    },
-                    placeholder_path,
-                    Arc::new(StdMutex::new(Some(handle_clone))),
-                    Arc::new(StdMutex::new(Some(loop_clone))), // Always store the loop
-                )
-            };
+                ),
+                None,
+            ),
+            None => resolve_remote_source(py, path_or_handle)?,
+        };
+        let ssh_key = ssh_key_arg(ssh_key, remote.is_some())?;
+        let allowed_root = allowed_root.map(sandbox::root_arg).transpose()?;
+        if let Some(root) = &allowed_root {
+            match &files {
+                Some(files) => {
+                    for file in files {
+                        sandbox::check(file, Some(root))?;
+                    }
+                }
+                None if matches!(source, FileSource::Path(_)) => sandbox::check(&path, Some(root))?,
+                None => {}
+            }
+        }
 
-        let dialect = DialectConfig::from_python(
+        if delim_whitespace && delimiter.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "delim_whitespace cannot be combined with delimiter",
+            ));
+        }
+        let mut dialect = DialectConfig::from_python(
+            dialect,
             delimiter,
             quotechar,
             escapechar,
@@ -483,929 +1645,1019 @@ impl Reader {
             strict,
             double_quote,
         )?;
-        Ok(Reader {
-            source,
-            path,
-            file: Arc::new(Mutex::new(None)),
+        dialect.delim_whitespace = delim_whitespace;
+        if strict_rfc4180 {
+            Rfc4180::check_dialect(&dialect)
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        }
+        let dialect_json = dialect.to_json();
+        let mut stream = RecordStream::new(
+            &source,
+            path.clone(),
             file_handle,
             event_loop,
-            buffer: Arc::new(Mutex::new(String::new())),
-            buffer_start: Arc::new(Mutex::new(0)),
-            position: Arc::new(Mutex::new(0)),
-            line_num: Arc::new(Mutex::new(0)),
             dialect,
-            read_size: read_size.unwrap_or(8192),
+            read_size.unwrap_or(8192),
             field_size_limit,
-        })
-    }
-
-    /// Get the current line number (1-based).
-    #[getter]
-    fn line_num(&self) -> PyResult<usize> {
-        Python::attach(|_py| {
-            Ok(*self.line_num.try_lock().map_err(|_| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                    "Cannot access line_num concurrently",
-                )
-            })?)
-        })
-    }
-
-    /// Read the next row from the CSV file.
-    fn read_row(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
-        let path = self_.path.clone();
-        let is_path = matches!(self_.source, FileSource::Path(_));
-        let file = Arc::clone(&self_.file);
-        let file_handle = Arc::clone(&self_.file_handle);
-        let event_loop = Arc::clone(&self_.event_loop);
-        let buffer = Arc::clone(&self_.buffer);
-        let buffer_start = Arc::clone(&self_.buffer_start);
-        let position = Arc::clone(&self_.position);
-        let line_num = Arc::clone(&self_.line_num);
-        let dialect = self_.dialect.clone();
-        let chunk_size = self_.read_size;
-        let field_size_limit = self_.field_size_limit;
-        Python::attach(|py| {
-            // For file handles, we'll extract and clone in async block where we can lock
-            // But we can't easily clone Py<PyAny> without GIL in async
-            // Workaround: pass Arc<Mutex> through and handle cloning inside read_from_python_file_arc
-            // But that function has issues too. Let's use a simpler approach:
-            // Extract once per call in the async block, accepting the limitation
-            let future = async move {
-                // Get current position
-                let current_pos = {
-                    let pos_guard = position.lock().await;
-                    *pos_guard
-                };
-
-                // Read data incrementally in chunks until we can parse the next record
-                // This is streaming because we only read chunks, not the entire file
-                let mut buffer_guard = buffer.lock().await;
-                let mut buffer_start_guard = buffer_start.lock().await;
-
-                loop {
-                    // Try to parse CSV from current buffer (starting from buffer_start)
-                    let available_data = if *buffer_start_guard < buffer_guard.len() {
-                        &buffer_guard[*buffer_start_guard..]
-                    } else {
-                        ""
-                    };
-
-                    if !available_data.is_empty() {
-                        let mut csv_reader_builder = ReaderBuilder::new();
-                        csv_reader_builder.has_headers(false);
-                        dialect.apply_to_reader(&mut csv_reader_builder, field_size_limit);
-                        let mut csv_reader =
-                            csv_reader_builder.from_reader(available_data.as_bytes());
-
-                        // available_data starts after buffer_start, which means records before current_pos
-                        // are already consumed. So we should read the next record (at current_pos) directly
-                        // without skipping, since available_data already starts at the right position.
-                        // However, we need to account for the fact that buffer_start might not be exactly
-                        // at a record boundary if the buffer was trimmed. So we still need to skip.
-                        // Actually, let's calculate: if buffer_start > 0, some records were consumed.
-                        // We need to figure out how many records are in the consumed portion.
-                        // Simplest: always parse from buffer_start, and since we're tracking position
-                        // separately, we know we want record at index current_pos.
-                        // But available_data might start in the middle of the buffer, so we need to
-                        // count how many records are before buffer_start.
-
-                        // Simpler approach: parse from the beginning of available_data (which is
-                        // the data we haven't consumed yet), and read the first record (which should
-                        // be at position current_pos in the overall file).
-                        let mut records_iter = csv_reader.records();
-
-                        // Try to read the next record (first record in available_data should be at current_pos)
-                        if let Some(result) = records_iter.next() {
-                            match result {
-                                Ok(record) => {
-                                    let row: Vec<String> =
-                                        record.iter().map(|s| s.to_string()).collect();
-
-                                    // Get the byte position after reading this record
-                                    // The csv_reader position() gives us where we are after the iterator consumed the record
-                                    let consumed_in_slice = csv_reader.position().byte() as usize;
-
-                                    // Count newlines in the consumed record for accurate line_num tracking
-                                    // This handles multi-line records (quoted fields with newlines)
-                                    let record_end = consumed_in_slice.min(available_data.len());
-                                    let record_text = &available_data[..record_end];
-                                    let newline_count = record_text
-                                        .as_bytes()
-                                        .iter()
-                                        .filter(|&&b| b == b'\n')
-                                        .count();
-
-                                    // Update position and line_num
-                                    {
-                                        let mut pos_guard = position.lock().await;
-                                        *pos_guard = current_pos + 1;
-                                    }
-                                    {
-                                        // Increment line_num based on actual newlines in the record
-                                        // For multi-line records, this counts all lines, not just records
-                                        let mut line_num_guard = line_num.lock().await;
-                                        if newline_count > 0 {
-                                            *line_num_guard += newline_count;
-                                        } else {
-                                            // If no newline found, increment by 1 (single-line record)
-                                            *line_num_guard += 1;
-                                        }
-                                    }
-
-                                    // Update buffer_start to track consumed bytes
-                                    *buffer_start_guard += consumed_in_slice;
-
-                                    // Only trim buffer when it gets very large to prevent unbounded growth
-                                    // This is still streaming as we read incrementally
-                                    if buffer_guard.len() > chunk_size * 8 {
-                                        // Trim buffer but reset position tracking
-                                        let new_buffer =
-                                            buffer_guard[*buffer_start_guard..].to_string();
-                                        *buffer_guard = new_buffer;
-                                        // Reset buffer_start and adjust position
-                                        *buffer_start_guard = 0;
-                                        // Note: We keep position as is since we've read that many records
-                                    }
-
-                                    return Ok(row);
-                                }
-                                Err(_e) => {
-                                    // CSV parse error - might be incomplete record or malformed CSV
-                                    // If we have enough data in buffer and still can't parse, it's likely malformed
-                                    // We'll continue reading in case it's incomplete, but track the error
-                                    // If we hit EOF and still have this error, we'll raise it
-                                    // For now, continue reading more data
-                                }
-                            }
-                        }
-                    }
-
-                    // Read more data from file in chunks
-                    let chunk_result: PyResult<(String, bool)> = if is_path {
-                        // Use Tokio File/BufReader for path-based sources
-                        let mut file_guard = file.lock().await;
-                        if file_guard.is_none() {
-                            let opened_file = File::open(&path).await.map_err(|e| {
-                                PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                    "Failed to open file {path}: {e}"
-                                ))
-                            })?;
-                            *file_guard = Some(BufReader::new(opened_file));
-                        }
-                        let reader = file_guard.as_mut().unwrap();
-                        let mut chunk = vec![0u8; chunk_size];
-                        match reader.read(&mut chunk).await {
-                            Ok(0) => Ok(("".to_string(), true)), // EOF
-                            Ok(n) => {
-                                chunk.truncate(n);
-                                let chunk_str = String::from_utf8(chunk).map_err(|_| {
-                                    PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                        "Invalid UTF-8 in CSV file",
-                                    )
-                                })?;
-                                Ok((chunk_str, false)) // Data read
-                            }
-                            Err(e) => Err(PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                "Failed to read file {path}: {e}"
-                            ))),
-                        }
-                    } else {
-                        // Use Python file handle for Handle sources
-                        // Extract both file handle and event loop in a single spawn_blocking
-                        // This ensures we get them before moving into async context
-                        let file_handle_clone = file_handle.clone();
-                        let event_loop_clone = event_loop.clone();
-                        let (handle_py, loop_py) = tokio::task::spawn_blocking(move || {
-                            #[allow(deprecated)]
-                            // Python::with_gil is still required in blocking contexts (spawn_blocking)
-                            Python::with_gil(|py| -> PyResult<(Py<PyAny>, Py<PyAny>)> {
-                                // Extract file handle
-                                let handle_guard = file_handle_clone.lock().map_err(|_| {
-                                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                        "Failed to lock file handle",
-                                    )
-                                })?;
-                                let handle = handle_guard.as_ref().ok_or_else(|| {
-                                    PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                        "File handle not available",
-                                    )
-                                })?;
-
-                                // Extract event loop
-                                let loop_guard = event_loop_clone.lock().map_err(|_| {
-                                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                        "Failed to lock event loop",
-                                    )
-                                })?;
-                                let loop_obj = loop_guard.as_ref().ok_or_else(|| {
-                                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                        "Event loop not available",
-                                    )
-                                })?;
-
-                                Ok((handle.clone_ref(py), loop_obj.clone_ref(py)))
-                            })
-                        })
-                        .await
-                        .map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Failed to extract file handle or event loop: {e}"
-                            ))
-                        })??;
-
-                        // Now use the helper function with the extracted handle and loop
-                        let chunk_str =
-                            read_from_python_file(handle_py, loop_py, chunk_size).await?;
-
-                        if chunk_str.is_empty() {
-                            Ok(("".to_string(), true)) // EOF
-                        } else {
-                            Ok((chunk_str, false)) // Data read
-                        }
-                    };
+        )
+        .utf8(utf8);
+        if follow {
+            let poll_interval = poll_interval.unwrap_or(0.5);
+            if !poll_interval.is_finite() || poll_interval <= 0.0 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "poll_interval must be a positive number of seconds",
+                ));
+            }
+            stream = stream.follow(Duration::from_secs_f64(poll_interval));
+        }
+        let files = files.unwrap_or_else(|| vec![path]);
+        if remote.is_some()
+            && (zip_member.is_some()
+                || encryption_key.is_some()
+                || mmap
+                || lock.is_some()
+                || follow
+                || verify_checksum.is_some())
+        {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "zip_member, encryption_key, mmap, lock, follow and verify_checksum cannot be \
+                 used with an sftp:// URL",
+            ));
+        }
+        let member = match source {
+            FileSource::Path(_) if files.len() == 1 && remote.is_none() => {
+                archive::member_of(&files[0], zip_member)?
+            }
+            _ if zip_member.is_some() => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "zip_member requires a single file path",
+                ))
+            }
+            _ => None,
+        };
+        if member.is_some() && (mmap || lock.is_some() || follow) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "mmap, lock and follow cannot be used with an archive member",
+            ));
+        }
+        let encrypted = match encryption_key.map(crypt::Key::from_bytes).transpose()? {
+            Some(key) => {
+                if !matches!(source, FileSource::Path(_)) || files.len() > 1 || member.is_some() {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "encryption_key requires a single file path, not a file handle or archive member",
+                    ));
+                }
+                if mmap || lock.is_some() || follow {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "mmap, lock and follow cannot be used with an encrypted file",
+                    ));
+                }
+                Some((files[0].clone(), key))
+            }
+            None => None,
+        };
+        if let Some(verifier) = verify_checksum
+            .map(Verifier::from_arg)
+            .transpose()?
+            .flatten()
+        {
+            if !matches!(source, FileSource::Path(_)) || member.is_some() || follow {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "verify_checksum requires file paths, not a file handle, archive member or follow",
+                ));
+            }
+            if verifier.is_given() && files.len() > 1 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "a digest passed as verify_checksum fits a single file; pass True to check \
+                    each file against its checksum file",
+                ));
+            }
+            stream = stream.verify_checksum(verifier);
+        }
+        // Sizes are only known up front for paths
+        let bytes_total = match source {
+            FileSource::Path(_) => Progress::total_size(&files),
+            _ => None,
+        };
+        stream = stream
+            .with_files(files, has_header)
+            .header_rules(header_rules);
+        if include_filename {
+            stream = stream.include_filename();
+        }
+        if row_hash {
+            stream = stream.row_hash();
+        }
+        if let Some(limit) = memory::parse(max_memory)? {
+            stream = stream.max_memory(limit);
+        }
+        if let Some(rows) = prefetch {
+            if rows == 0 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "prefetch must be at least 1",
+                ));
+            }
+            if follow || checkpoint_path.is_some() {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "prefetch cannot be combined with follow or checkpoint_path",
+                ));
+            }
+            stream = stream.prefetch(rows);
+        }
+        if let Some(columns) = columns {
+            let projection = Projection::parse(columns)?;
+            if projection.has_names() && !has_header {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "columns given by name require a header row (has_header=True); use positions instead",
+                ));
+            }
+            stream = stream.columns(projection);
+        }
+        if let Some(intern_columns) = intern_columns {
+            if let Some(interner) = Interner::parse(intern_columns, stream.header_row())? {
+                if interner.has_names() && !has_header {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "intern_columns given by name require a header row (has_header=True); use positions instead",
+                    ));
+                }
+                format = format.intern(interner)?;
+            }
+        }
+        if skip_footer > 0 {
+            if follow || checkpoint_path.is_some() {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "skip_footer cannot be combined with follow or checkpoint_path",
+                ));
+            }
+            stream = stream.skip_footer(skip_footer);
+        }
+        if let Some(rows) = limit {
+            stream = stream.limit(rows);
+        }
+        if let Some(concurrency) = concurrency {
+            if concurrency == 0 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "concurrency must be at least 1",
+                ));
+            }
+            if follow && concurrency > 1 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "follow cannot be combined with concurrency",
+                ));
+            }
+            stream = stream.concurrency(concurrency);
+        }
+        if strict_rfc4180 {
+            if concurrency.is_some_and(|c| c > 1) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "strict_rfc4180 cannot be combined with concurrency",
+                ));
+            }
+            stream = stream.strict_rfc4180();
+        }
+        if empty_as_none {
+            if concurrency.is_some_and(|c| c > 1) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "empty_as_none cannot be combined with concurrency",
+                ));
+            }
+            if delim_whitespace {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "empty_as_none cannot be combined with delim_whitespace",
+                ));
+            }
+            stream = stream.empty_as_none();
+        }
+        if field_spans {
+            if concurrency.is_some_and(|c| c > 1) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "field_spans cannot be combined with concurrency",
+                ));
+            }
+            if delim_whitespace {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "field_spans cannot be combined with delim_whitespace",
+                ));
+            }
+            stream = stream.field_spans();
+        }
+        // True checks against the first file's header, a list against those names
+        let header_check = match check_headers {
+            Some(value) if value.is_instance_of::<PyBool>() => {
+                value.extract::<bool>()?.then(|| HeaderCheck::new(None))
+            }
+            Some(value) => Some(HeaderCheck::new(Some(
+                value.extract::<Vec<String>>().map_err(|_| {
+                    PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                        "check_headers must be a bool or a list of column names",
+                    )
+                })?,
+            ))),
+            None => None,
+        };
+        if let Some(check) = header_check {
+            if !has_header {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "check_headers requires has_header=True",
+                ));
+            }
+            if concurrency.is_some_and(|c| c > 1) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "check_headers cannot be combined with concurrency",
+                ));
+            }
+            stream = stream.check_headers(check);
+        }
+        if let Some(policy) = binary {
+            stream = stream.binary(BinaryPolicy::parse(policy)?);
+        }
+        if mmap {
+            if !matches!(source, FileSource::Path(_)) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "mmap requires a file path, not a file handle",
+                ));
+            }
+            if follow {
+                // A followed file grows, and may be truncated when it is rotated
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "mmap cannot be combined with follow",
+                ));
+            }
+            stream = stream.mmap();
+        }
+        if let Some(buffer_size) = buffer_size {
+            if buffer_size == 0 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "buffer_size must be at least 1",
+                ));
+            }
+            stream = stream.buffer_size(buffer_size);
+        }
+        if let Some(mode) = lock {
+            if !matches!(source, FileSource::Path(_)) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "lock requires a file path, not a file handle",
+                ));
+            }
+            if follow || concurrency.is_some_and(|c| c > 1) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "lock cannot be combined with follow or concurrency",
+                ));
+            }
+            stream = stream.lock(mode);
+        }
+        if let Some(progress) = Progress::from_args(py, progress, progress_interval, bytes_total)? {
+            stream = stream.progress(progress);
+        }
+        let timeout = timeout::parse(timeout)?;
+        if let Some(timeout) = timeout {
+            stream = stream.timeout(timeout);
+        }
+        if let Some(policy) = RetryPolicy::from_args(py, retries, retry_backoff, on_retry)? {
+            stream = stream.retry(policy);
+        }
+        if let FileSource::Descriptor(descriptor) = &source {
+            stream.attach_file(descriptor.get().take()?)?;
+        }
+        if let Some((archive, member)) = member {
+            let (file, member) = archive::open_member(&archive, &member)?;
+            stream = stream.piped(file, member)?;
+        }
+        if let Some((path, key)) = encrypted {
+            let (file, source) = crypt::open_decrypted(&path, key)?;
+            stream = stream.piped(file, source)?;
+        }
+        if let Some(url) = remote {
+            let (file, source) = sftp::open(url, ssh_key, timeout)?;
+            stream = stream.piped(file, source)?;
+        }
+        let checkpoint = match checkpoint_path {
+            Some(checkpoint_path) => {
+                if !matches!(source, FileSource::Path(_)) || !stream.tracks_offset() {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "checkpoint_path requires file paths read one at a time, not a file \
+                        handle, archive member, concurrency above 1 or utf8='lossy'",
+                    ));
+                }
+                let checkpoint_path = fspath(checkpoint_path)?.ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                        "checkpoint_path must be str, bytes or os.PathLike",
+                    )
+                })?;
+                // It is written and renamed over, so it is confined like the input
+                validate_path(&checkpoint_path)?;
+                if let Some(root) = &allowed_root {
+                    sandbox::check(&checkpoint_path, Some(root))?;
+                }
+                let interval = checkpoint_interval.unwrap_or(DEFAULT_CHECKPOINT_INTERVAL);
+                if interval == 0 {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "checkpoint_interval must be at least 1",
+                    ));
+                }
+                Some(Arc::new(Checkpoint::new(
+                    checkpoint_path,
+                    interval,
+                    dialect_json.clone(),
+                )))
+            }
+            None => None,
+        };
+        if engine == Engine::Fast && (!stream.loads_whole() || format.needs_header()) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "engine='fast' requires a single file path that read_all() loads whole, not a \
+                 file handle, several files, an archive member, an encrypted or remote file, \
+                 row_type='row', a schema with column names, or limit, strict_rfc4180, \
+                 empty_as_none, field_spans or check_headers",
+            ));
+        }
+        let reader = Reader {
+            stream: stream.engine(engine),
+            dialect: dialect_json,
+            options: match source {
+                FileSource::Path(_) => options,
+                FileSource::Handle { .. } => Err("over a file handle or adapter"),
+                FileSource::Descriptor(_) => Err("over a file descriptor or socket"),
+            },
+            checkpoint,
+            format,
+            eof,
+        };
+        // Continue from the checkpoint left by an earlier run
+        if let Some(saved) = reader
+            .checkpoint
+            .as_ref()
+            .map(|c| c.load())
+            .transpose()?
+            .flatten()
+        {
+            if saved.stream.files != *reader.stream.files() {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "checkpoint_path holds a checkpoint for different files",
+                ));
+            }
+            reader.resume(saved)?;
+        }
+        Ok(reader)
+    }
 
-                    match chunk_result {
-                        Ok((_chunk_str, true)) => {
-                            // EOF
-                            // EOF - try final parse with remaining buffer
-                            let available_data = if *buffer_start_guard < buffer_guard.len() {
-                                &buffer_guard[*buffer_start_guard..]
-                            } else {
-                                ""
-                            };
-
-                            if available_data.is_empty() {
-                                return Ok(Vec::<String>::new()); // EOF, no more data
-                            }
+    /// Read from an open file descriptor, such as a pipe, a temporary file or a descriptor
+    /// passed in by another process. The descriptor is duplicated, so the caller still
+    /// owns `fd`. Takes the constructor's keyword arguments except `lock`, `mmap`,
+    /// `verify_checksum`, `encryption_key`, `allowed_root` and `ssh_key`.
+    #[classmethod]
+    #[pyo3(signature = (fd, **kwargs))]
+    fn from_fd(
+        cls: &Bound<'_, PyType>,
+        fd: i32,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<Self>> {
+        reject_fd_options(kwargs, READER_PATH_OPTIONS, "from_fd()")?;
+        let descriptor = Descriptor::new(file_from_fd(cls.py(), fd)?, format!("<fd {fd}>"));
+        Ok(cls
+            .call((descriptor,), kwargs)?
+            .cast_into::<Reader>()?
+            .unbind())
+    }
 
-                            // Final parse attempt with all remaining data
-                            let mut csv_reader_builder = ReaderBuilder::new();
-                            csv_reader_builder.has_headers(false);
-                            dialect.apply_to_reader(&mut csv_reader_builder, field_size_limit);
-                            let mut csv_reader =
-                                csv_reader_builder.from_reader(available_data.as_bytes());
-
-                            // available_data starts after consumed records, so read first record
-                            let mut records_iter = csv_reader.records();
-
-                            match records_iter.next() {
-                                Some(Ok(record)) => {
-                                    let row: Vec<String> =
-                                        record.iter().map(|s| s.to_string()).collect();
-
-                                    // Count newlines for accurate line_num tracking
-                                    let csv_position = csv_reader.position();
-                                    let consumed_in_slice = csv_position.byte() as usize;
-                                    let record_end = consumed_in_slice.min(available_data.len());
-                                    let record_text = &available_data[..record_end];
-                                    let newline_count = record_text
-                                        .as_bytes()
-                                        .iter()
-                                        .filter(|&&b| b == b'\n')
-                                        .count();
-
-                                    {
-                                        let mut pos_guard = position.lock().await;
-                                        *pos_guard = current_pos + 1;
-                                    }
-                                    {
-                                        let mut line_num_guard = line_num.lock().await;
-                                        if newline_count > 0 {
-                                            *line_num_guard += newline_count;
-                                        } else {
-                                            *line_num_guard += 1;
-                                        }
-                                    }
-                                    buffer_guard.clear();
-                                    *buffer_start_guard = 0;
-                                    return Ok(row);
-                                }
-                                Some(Err(e)) => {
-                                    // CSV parse error at EOF - malformed CSV
-                                    // Provide detailed error message with context
-                                    let error_msg = format!(
-                                        "CSV parse error at row {current_pos} (0-indexed) in file '{path}': {e}. \
-                                        The CSV file may be malformed or have incomplete records."
-                                    );
-                                    return Err(CSVError::new_err(error_msg));
-                                }
-                                None => {
-                                    return Ok(Vec::<String>::new()); // EOF
+    /// Connect to a TCP server and read the CSV records it sends until it closes the
+    /// connection. Takes the constructor's keyword arguments except those of
+    /// `from_fd()`. The connection is made by the first read, which raises
+    /// ConnectionError if it can't be.
+    ///
+    /// # Arguments
+    /// * `host` - Host name or IP address to connect to
+    /// * `port` - TCP port
+    /// * `connect_timeout` - Seconds to wait for the connection (default: None, the
+    ///   operating system's limit)
+    #[classmethod]
+    #[pyo3(signature = (host, port, connect_timeout = None, **kwargs))]
+    fn from_socket(
+        cls: &Bound<'_, PyType>,
+        host: &str,
+        port: u16,
+        connect_timeout: Option<f64>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<Self>> {
+        reject_fd_options(kwargs, READER_PATH_OPTIONS, "from_socket()")?;
+        let conn = Conn::connect(host, port, connect_timeout)?;
+        let descriptor = Descriptor::new(conn, format!("<tcp {host}:{port}>"));
+        Ok(cls
+            .call((descriptor,), kwargs)?
+            .cast_into::<Reader>()?
+            .unbind())
+    }
+
+    /// Save the reader's position as a small JSON-serializable dict, so another process
+    /// can continue with `Reader.from_state()`. Only for file paths read one at a time.
+    ///
+    /// The dict holds `version`, `paths`, `file_index`, `byte_offset`, `row_index`
+    /// (rows returned so far), `line_num`, `has_header`, `dialect`, `byte_end` (the end
+    /// of a part from `split()`, else None) and, for `row_type="row"`, the `header`
+    /// already consumed.
+    fn state(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let state = state_to_json(
+            self.stream.snapshot()?,
+            &self.dialect,
+            self.format.header_names(),
+        );
+        json_to_py(py, &state)
+    }
+
+    /// Continue reading where `state()` left off. Keyword arguments are passed to the
+    /// constructor, e.g. `row_type` or callbacks, which the state does not store.
+    #[classmethod]
+    #[pyo3(signature = (state, **kwargs))]
+    fn from_state(
+        cls: &Bound<'_, PyType>,
+        state: &Bound<'_, PyDict>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<Self>> {
+        let py = cls.py();
+        let saved = state_from_json(&py_to_json(state.as_any())?)?;
+        let options = json_to_py(py, &serde_json::Value::Object(saved.dialect.clone()))?
+            .into_bound(py)
+            .cast_into::<PyDict>()?;
+        options.set_item("has_header", saved.stream.has_header)?;
+        if let Some(kwargs) = kwargs {
+            options.update(kwargs.as_mapping())?;
+        }
+        let reader = cls
+            .call((saved.stream.files.clone(),), Some(&options))?
+            .cast_into::<Reader>()?;
+        reader.borrow().resume(saved)?;
+        Ok(reader.unbind())
+    }
+
+    /// Build the reader again, in another process, from its constructor arguments.
+    fn __getnewargs_ex__(&self, py: Python<'_>) -> PyResult<(Py<PyTuple>, Py<PyDict>)> {
+        pickled_args(py, &self.options, "Reader")
+    }
+
+    /// Pickle the reader's position, as `state()` saves it.
+    fn __getstate__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.state(py)
+    }
+
+    /// Continue from the position of a pickled reader.
+    fn __setstate__(&self, state: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.resume(state_from_json(&py_to_json(state.as_any())?)?)
+    }
+
+    /// Split an unread file into `n` readers over disjoint ranges of about equal size,
+    /// each starting at a record boundary, for workers to consume in parallel. Reading
+    /// them one after another returns the rows this reader would. Keyword arguments are
+    /// passed to each reader's constructor, as for `from_state()`.
+    ///
+    /// Requires a single file path not read from yet, without `escapechar` or
+    /// `utf8="lossy"`. A file with fewer than `n` records gives fewer readers.
+    #[pyo3(signature = (n, **kwargs))]
+    fn split(
+        self_: PyRef<Self>,
+        n: usize,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        if n == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "n must be at least 1",
+            ));
+        }
+        let py = self_.py();
+        let options = json_to_py(py, &self_.dialect)?
+            .into_bound(py)
+            .cast_into::<PyDict>()?;
+        if let Some(kwargs) = kwargs {
+            options.update(kwargs.as_mapping())?;
+        }
+        let options = options.unbind();
+        let stream = self_.stream.clone();
+        let cls = self_.into_pyobject(py)?.get_type().unbind();
+        crate::ops::spawn_blocking_py(py, move || {
+            let ranges = stream.split(n)?;
+            Python::attach(|py| -> PyResult<Py<PyAny>> {
+                let readers = PyList::empty(py);
+                for state in ranges {
+                    let options = options.bind(py).copy()?;
+                    if !options.contains("has_header")? {
+                        options.set_item("has_header", state.has_header)?;
+                    }
+                    let reader = cls
+                        .bind(py)
+                        .call((state.files[0].clone(),), Some(&options))?
+                        .cast_into::<Reader>()?;
+                    {
+                        let child = reader.borrow();
+                        if let Some(end) = state.end {
+                            child.stream.set_range_end(end);
+                        }
+                        if state.offset > 0 {
+                            child.stream.restore(&state)?;
+                            // The first range consumes the header; the others take it
+                            if child.format.needs_header() {
+                                if let Some(header) = child.stream.header_row().get() {
+                                    child.format.set_header(header.clone())?;
                                 }
                             }
                         }
-                        Ok((chunk_str, false)) => {
-                            // Append chunk to buffer
-                            buffer_guard.push_str(&chunk_str);
-                        }
-                        Err(e) => {
-                            return Err(e);
-                        }
+                    }
+                    readers.append(reader)?;
+                }
+                Ok(readers.into_any().unbind())
+            })
+        })
+    }
+
+    /// Move to a record, counted as `state()` counts `row_index`: from 0 at the start of
+    /// the file, the header included. Its start is looked up in an index of the file
+    /// shared by every reader in the process and rebuilt only when the file changes.
+    ///
+    /// Requires a single file path, without `escapechar`, `utf8="lossy"` or
+    /// `skip_footer`. `row_index` may be the number of records, to move to the end.
+    fn seek(&self, row_index: usize) -> PyResult<Py<PyAny>> {
+        let stream = self.stream.clone();
+        let format = self.format.clone();
+        let ticket = stream.ticket();
+        Python::attach(|py| {
+            let future = async move {
+                // The header consumed by `row_type="row"` is never returned as a row
+                let consumes_header = format.needs_header() && stream.has_header();
+                let row_index = if consumes_header {
+                    row_index.max(1)
+                } else {
+                    row_index
+                };
+                stream.operation(ticket, stream.seek(row_index)).await?;
+                if consumes_header {
+                    if let Some(header) = stream.header_row().get() {
+                        format.set_header(header.clone())?;
                     }
                 }
+                Ok(())
             };
             future_into_py(py, future).map(|bound| bound.unbind())
         })
     }
 
+    /// Write the checkpoint file now instead of waiting for `checkpoint_interval` rows.
+    /// Call it once the rows read so far have been processed.
+    fn checkpoint(&self) -> PyResult<()> {
+        let checkpoint = self.checkpoint.as_ref().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("checkpoint() requires checkpoint_path")
+        })?;
+        checkpoint.save_blocking(&self.stream, self.format.header_names())
+    }
+
+    /// Get the current line number (1-based).
+    #[getter]
+    fn line_num(&self) -> PyResult<usize> {
+        Python::attach(|_py| {
+            Ok(*self.stream.line_num.try_lock().map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                    "Cannot access line_num concurrently",
+                )
+            })?)
+        })
+    }
+
+    /// Byte offset in the current file just past the last row read, i.e. where the next
+    /// row starts. Resets to 0 at each new file of a multi-file stream, and is None when
+    /// files are parsed in parallel (`concurrency` above 1) or with `utf8="lossy"`.
+    #[getter]
+    fn byte_offset(&self) -> PyResult<Option<u64>> {
+        if !self.stream.tracks_offset() {
+            return Ok(None);
+        }
+        Ok(Some(*self.stream.offset.try_lock().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "Cannot access byte_offset concurrently",
+            )
+        })?))
+    }
+
+    /// Read the next row from the CSV file.
+    ///
+    /// Returns an empty list at EOF, or `None` or raises `EOFError` depending on `eof`.
+    /// Records always have at least one field (a line holding only `""` is `[""]`), so
+    /// an empty list only ever means EOF. In follow mode, waits for more rows instead.
+    fn read_row(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        self_.next_future(self_.eof)
+    }
+
+    /// Invalid UTF-8 sequences replaced with U+FFFD so far (always 0 unless
+    /// `utf8="lossy"`).
+    #[getter]
+    fn utf8_replacements(&self) -> u64 {
+        self.stream.utf8_replacements()
+    }
+
+    /// Throughput counters since the reader was created.
+    ///
+    /// Returns a dict with `rows` (rows returned), `bytes` (bytes consumed), `errors`
+    /// (failed reads), `parse_errors` (reads that raised CsvParseError or a subclass),
+    /// `elapsed` (seconds since creation) and `active` (seconds spent inside reads).
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.stream.stats.to_dict(py)
+    }
+
     /// Async iterator protocol - returns self.
     fn __aiter__(slf: PyRef<Self>) -> PyResult<Py<Self>> {
         Ok(slf.into())
     }
 
     /// Async iterator next - returns next row or raises StopAsyncIteration.
-    ///
-    /// Note: For proper StopAsyncIteration handling, this is best used via Python's
-    /// async for syntax, which handles empty results correctly.
     fn __anext__(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
-        // For now, delegate to read_row. A Python wrapper can handle StopAsyncIteration
-        // TODO: Implement proper StopAsyncIteration in Rust when empty row is returned
-        Reader::read_row(self_)
+        self_.next_future(EndOfFile::Stop)
     }
 
     /// Read multiple rows at once.
     fn read_rows(self_: PyRef<Self>, n: usize) -> PyResult<Py<PyAny>> {
-        let path = self_.path.clone();
-        let is_path = matches!(self_.source, FileSource::Path(_));
-        let file = Arc::clone(&self_.file);
-        let file_handle = Arc::clone(&self_.file_handle);
-        let event_loop = Arc::clone(&self_.event_loop);
-        let buffer = Arc::clone(&self_.buffer);
-        let buffer_start = Arc::clone(&self_.buffer_start);
-        let position = Arc::clone(&self_.position);
-        let line_num = Arc::clone(&self_.line_num);
-        let dialect = self_.dialect.clone();
-        let chunk_size = self_.read_size;
-        let field_size_limit = self_.field_size_limit;
+        let stream = self_.stream.clone();
+        let ticket = stream.ticket();
+        let format = self_.format.clone();
+        let checkpoint = self_.checkpoint.clone();
         Python::attach(|py| {
             let future = async move {
-                let mut rows: Vec<Vec<String>> = Vec::new();
-
-                // Get or open the file handle (once) - only for path-based sources
-                if is_path {
-                    let mut file_guard = file.lock().await;
-                    if file_guard.is_none() {
-                        let opened_file = File::open(&path).await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                "Failed to open file {path}: {e}"
-                            ))
-                        })?;
-                        *file_guard = Some(BufReader::new(opened_file));
-                    }
-                    drop(file_guard); // Release lock before loop
-                }
-
-                // Read n rows in a loop
-                for _ in 0..n {
-                    let current_pos = {
-                        let pos_guard = position.lock().await;
-                        *pos_guard
-                    };
-
-                    let mut buffer_guard = buffer.lock().await;
-                    let mut buffer_start_guard = buffer_start.lock().await;
-
-                    let mut row_found = false;
-                    loop {
-                        let available_data = if *buffer_start_guard < buffer_guard.len() {
-                            &buffer_guard[*buffer_start_guard..]
-                        } else {
-                            ""
-                        };
-
-                        if !available_data.is_empty() {
-                            let mut csv_reader_builder = ReaderBuilder::new();
-                            csv_reader_builder.has_headers(false);
-                            dialect.apply_to_reader(&mut csv_reader_builder, field_size_limit);
-                            let mut csv_reader =
-                                csv_reader_builder.from_reader(available_data.as_bytes());
-                            let mut records_iter = csv_reader.records();
-
-                            if let Some(result) = records_iter.next() {
-                                match result {
-                                    Ok(record) => {
-                                        let row: Vec<String> =
-                                            record.iter().map(|s| s.to_string()).collect();
-
-                                        let consumed_in_slice =
-                                            csv_reader.position().byte() as usize;
-
-                                        // Count newlines in the consumed record for accurate line_num tracking
-                                        let record_end =
-                                            consumed_in_slice.min(available_data.len());
-                                        let record_text = &available_data[..record_end];
-                                        let newline_count = record_text
-                                            .as_bytes()
-                                            .iter()
-                                            .filter(|&&b| b == b'\n')
-                                            .count();
-
-                                        {
-                                            let mut pos_guard = position.lock().await;
-                                            *pos_guard = current_pos + 1;
-                                        }
-                                        {
-                                            // Increment line_num based on actual newlines in the record
-                                            let mut line_num_guard = line_num.lock().await;
-                                            if newline_count > 0 {
-                                                *line_num_guard += newline_count;
-                                            } else {
-                                                *line_num_guard += 1;
-                                            }
-                                        }
-
-                                        *buffer_start_guard += consumed_in_slice;
-
-                                        if buffer_guard.len() > chunk_size * 8 {
-                                            let new_buffer =
-                                                buffer_guard[*buffer_start_guard..].to_string();
-                                            *buffer_guard = new_buffer;
-                                            *buffer_start_guard = 0;
-                                        }
-
-                                        rows.push(row);
-                                        row_found = true;
-                                        break;
-                                    }
-                                    Err(_) => {
-                                        // Continue reading
-                                    }
-                                }
-                            }
+                let rows = stream
+                    .operation(ticket, async {
+                        if let Some(checkpoint) = &checkpoint {
+                            checkpoint
+                                .before_read(&stream, format.header_names())
+                                .await?;
+                        }
+                        format.read_header(&stream).await?;
+                        let rows = stream.next_records(n).await?;
+                        if let Some(checkpoint) = &checkpoint {
+                            checkpoint
+                                .after_read(&stream, rows.len(), format.header_names())
+                                .await?;
                         }
+                        Ok(rows)
+                    })
+                    .await?;
+                Python::attach(|py| format.rows(py, rows))
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
 
-                        // Read more data from file
-                        let chunk_result: PyResult<(String, bool)> = if is_path {
-                            // Use Tokio File/BufReader for path-based sources
-                            let mut file_guard = file.lock().await;
-                            if file_guard.is_none() {
-                                let opened_file = File::open(&path).await.map_err(|e| {
-                                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                        "Failed to open file {path}: {e}"
-                                    ))
-                                })?;
-                                *file_guard = Some(BufReader::new(opened_file));
-                            }
-                            let reader = file_guard.as_mut().unwrap();
-                            let mut chunk = vec![0u8; chunk_size];
-                            match reader.read(&mut chunk).await {
-                                Ok(0) => Ok(("".to_string(), true)), // EOF
-                                Ok(n) => {
-                                    chunk.truncate(n);
-                                    let chunk_str = String::from_utf8(chunk).map_err(|_| {
-                                        PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                            "Invalid UTF-8 in CSV file",
-                                        )
-                                    })?;
-                                    Ok((chunk_str, false)) // Data read
-                                }
-                                Err(e) => Err(PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                    format!("Failed to read file {path}: {e}"),
-                                )),
-                            }
-                        } else {
-                            // Use Python file handle for Handle sources
-                            // Extract both file handle and event loop in a single spawn_blocking
-                            // This ensures we get them before moving into async context
-                            let file_handle_clone = file_handle.clone();
-                            let event_loop_clone = event_loop.clone();
-                            let (handle_py, loop_py) = tokio::task::spawn_blocking(move || {
-                                #[allow(deprecated)]
-                                // Python::with_gil is still required in blocking contexts (spawn_blocking)
-                                Python::with_gil(|py| -> PyResult<(Py<PyAny>, Py<PyAny>)> {
-                                    // Extract file handle
-                                    let handle_guard = file_handle_clone.lock().map_err(|_| {
-                                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                            "Failed to lock file handle",
-                                        )
-                                    })?;
-                                    let handle = handle_guard.as_ref().ok_or_else(|| {
-                                        PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                            "File handle not available",
-                                        )
-                                    })?;
-
-                                    // Extract event loop
-                                    let loop_guard = event_loop_clone.lock().map_err(|_| {
-                                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                            "Failed to lock event loop",
-                                        )
-                                    })?;
-                                    let loop_obj = loop_guard.as_ref().ok_or_else(|| {
-                                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                            "Event loop not available",
-                                        )
-                                    })?;
-
-                                    Ok((handle.clone_ref(py), loop_obj.clone_ref(py)))
-                                })
-                            })
-                            .await
-                            .map_err(|e| {
-                                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                    "Failed to extract file handle or event loop: {e}"
-                                ))
-                            })??;
-
-                            let chunk_str =
-                                read_from_python_file(handle_py, loop_py, chunk_size).await?;
-                            Ok((chunk_str.clone(), chunk_str.is_empty()))
+    /// Read rows as columns: a dict mapping each column name of the header to a list of
+    /// its values, with typed values under `schema=`. Without a header (`has_header=False`)
+    /// the keys are column positions. A header not read yet is read first. Short rows
+    /// have None in the columns they lack. Returns an empty dict at EOF.
+    ///
+    /// # Arguments
+    /// * `batch_size` - Read up to this many rows (default: None, every remaining row)
+    #[pyo3(signature = (batch_size = None))]
+    fn read_columns(&self, batch_size: Option<usize>) -> PyResult<Py<PyAny>> {
+        if batch_size == Some(0) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "batch_size must be at least 1",
+            ));
+        }
+        if self.format.has_spans() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "read_columns() cannot be used with field_spans",
+            ));
+        }
+        let stream = self.stream.clone();
+        let format = self.format.clone();
+        let checkpoint = self.checkpoint.clone();
+        let ticket = stream.ticket();
+        Python::attach(|py| {
+            let future = async move {
+                let (names, first_row, rows) = stream
+                    .operation(ticket, async {
+                        if let Some(checkpoint) = &checkpoint {
+                            checkpoint
+                                .before_read(&stream, format.header_names())
+                                .await?;
+                        }
+                        format.read_header(&stream).await?;
+                        let header = stream.header_row();
+                        if stream.has_header() && header.get().is_none() {
+                            stream.next_record().await?; // Recorded as the header row
+                        }
+                        let first_row = *stream.position.lock().await;
+                        let rows = match batch_size {
+                            Some(n) => stream.next_records(n).await?,
+                            None => stream.read_all(1).await?,
                         };
-
-                        match chunk_result {
-                            Ok((_chunk_str, true)) => {
-                                // EOF
-                                let available_data = if *buffer_start_guard < buffer_guard.len() {
-                                    &buffer_guard[*buffer_start_guard..]
-                                } else {
-                                    ""
-                                };
-
-                                if available_data.is_empty() {
-                                    break; // EOF, no more data
-                                }
-
-                                // Final parse attempt
-                                let mut csv_reader_builder = ReaderBuilder::new();
-                                csv_reader_builder.has_headers(false);
-                                dialect.apply_to_reader(&mut csv_reader_builder, None);
-                                let mut csv_reader =
-                                    csv_reader_builder.from_reader(available_data.as_bytes());
-                                let mut records_iter = csv_reader.records();
-
-                                match records_iter.next() {
-                                    Some(Ok(record)) => {
-                                        let row: Vec<String> =
-                                            record.iter().map(|s| s.to_string()).collect();
-
-                                        let consumed_in_slice =
-                                            csv_reader.position().byte() as usize;
-
-                                        {
-                                            let mut pos_guard = position.lock().await;
-                                            *pos_guard = current_pos + 1;
-                                        }
-                                        {
-                                            // Count newlines for accurate line_num tracking
-                                            let record_end =
-                                                consumed_in_slice.min(available_data.len());
-                                            let record_text = &available_data[..record_end];
-                                            let newline_count = record_text
-                                                .as_bytes()
-                                                .iter()
-                                                .filter(|&&b| b == b'\n')
-                                                .count();
-
-                                            let mut line_num_guard = line_num.lock().await;
-                                            if newline_count > 0 {
-                                                *line_num_guard += newline_count;
-                                            } else {
-                                                *line_num_guard += 1;
-                                            }
-                                        }
-                                        buffer_guard.clear();
-                                        *buffer_start_guard = 0;
-                                        rows.push(row);
-                                        row_found = true;
-                                    }
-                                    Some(Err(e)) => {
-                                        let error_msg = format!(
-                                            "CSV parse error at row {current_pos} (0-indexed) in file '{path}': {e}. \
-                                            The CSV file may be malformed or have incomplete records."
-                                        );
-                                        return Err(CSVError::new_err(error_msg));
-                                    }
-                                    None => {
-                                        // EOF
-                                    }
-                                }
-                                break;
-                            }
-                            Ok((chunk_str, false)) => {
-                                // Append chunk to buffer
-                                buffer_guard.push_str(&chunk_str);
-                            }
-                            Err(e) => {
-                                return Err(e);
-                            }
+                        if let Some(checkpoint) = &checkpoint {
+                            checkpoint
+                                .after_read(&stream, rows.len(), format.header_names())
+                                .await?;
                         }
-                    }
+                        let names = format
+                            .header_names()
+                            .or_else(|| header.get().cloned())
+                            .filter(|_| stream.has_header());
+                        Ok((names, first_row, rows))
+                    })
+                    .await?;
+                Python::attach(|py| format.columns(py, names.as_deref(), rows, first_row))
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
 
-                    if !row_found {
-                        break; // EOF reached
-                    }
-                }
+    /// Read up to `n` records as `bytes`, without parsing their fields.
+    ///
+    /// Records are split where a line terminator falls outside quotes, so a quoted
+    /// field spanning lines stays in one record. Each holds the bytes the file does,
+    /// terminator included (the last record of a file may have none); blank lines are
+    /// skipped. Meant for sharding or routing records to other workers, it ignores
+    /// `schema=`, `row_type=` and the header, which is returned as the first record.
+    /// Returns an empty list at EOF.
+    fn read_raw_lines(&self, n: usize) -> PyResult<Py<PyAny>> {
+        let stream = self.stream.clone();
+        let ticket = stream.ticket();
+        Python::attach(|py| {
+            let future = async move {
+                let records = stream
+                    .operation(ticket, async { stream.next_raw_records(n).await })
+                    .await?;
+                Python::attach(|py| {
+                    let records = records.iter().map(|record| PyBytes::new(py, record));
+                    Ok(PyList::new(py, records)?.into_any().unbind())
+                })
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
 
-                Ok(rows)
+    /// Read all remaining rows.
+    ///
+    /// # Arguments
+    /// * `threads` - Parse a file that has not been read from yet on this many threads,
+    ///   splitting it at record boundaries (default: 1)
+    #[pyo3(signature = (threads = None))]
+    fn read_all(self_: PyRef<Self>, threads: Option<usize>) -> PyResult<Py<PyAny>> {
+        let stream = self_.stream.clone();
+        let format = self_.format.clone();
+        let checkpoint = self_.checkpoint.clone();
+        let threads = threads.unwrap_or(1);
+        if threads == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "threads must be at least 1",
+            ));
+        }
+        let ticket = stream.ticket();
+        Python::attach(|py| {
+            let future = async move {
+                let rows = stream
+                    .operation(ticket, async {
+                        if let Some(checkpoint) = &checkpoint {
+                            checkpoint
+                                .before_read(&stream, format.header_names())
+                                .await?;
+                        }
+                        format.read_header(&stream).await?;
+                        let rows = stream.read_all(threads).await?;
+                        if let Some(checkpoint) = &checkpoint {
+                            checkpoint
+                                .after_read(&stream, rows.len(), format.header_names())
+                                .await?;
+                        }
+                        Ok(rows)
+                    })
+                    .await?;
+                Python::attach(|py| format.rows(py, rows))
             };
             future_into_py(py, future).map(|bound| bound.unbind())
         })
     }
 
-    /// Skip multiple rows efficiently without parsing.
+    /// Skip multiple rows efficiently without returning them.
     fn skip_rows(self_: PyRef<Self>, n: usize) -> PyResult<Py<PyAny>> {
-        let path = self_.path.clone();
-        let is_path = matches!(self_.source, FileSource::Path(_));
-        let file = Arc::clone(&self_.file);
-        let file_handle = Arc::clone(&self_.file_handle);
-        let event_loop = Arc::clone(&self_.event_loop);
-        let buffer = Arc::clone(&self_.buffer);
-        let buffer_start = Arc::clone(&self_.buffer_start);
-        let position = Arc::clone(&self_.position);
-        let line_num = Arc::clone(&self_.line_num);
-        let dialect = self_.dialect.clone();
-        let chunk_size = self_.read_size;
-        let field_size_limit = self_.field_size_limit;
+        let stream = self_.stream.clone();
+        let ticket = stream.ticket();
         Python::attach(|py| {
             let future = async move {
-                // Get or open the file handle (once) - only for path-based sources
-                if is_path {
-                    let mut file_guard = file.lock().await;
-                    if file_guard.is_none() {
-                        let opened_file = File::open(&path).await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                "Failed to open file {path}: {e}"
-                            ))
-                        })?;
-                        *file_guard = Some(BufReader::new(opened_file));
-                    }
-                    drop(file_guard); // Release lock before loop
-                }
-
-                // Skip n rows - parse but don't collect data
-                for _ in 0..n {
-                    let current_pos = {
-                        let pos_guard = position.lock().await;
-                        *pos_guard
-                    };
-
-                    let mut buffer_guard = buffer.lock().await;
-                    let mut buffer_start_guard = buffer_start.lock().await;
-
-                    let mut row_found = false;
-                    loop {
-                        let available_data = if *buffer_start_guard < buffer_guard.len() {
-                            &buffer_guard[*buffer_start_guard..]
-                        } else {
-                            ""
-                        };
-
-                        if !available_data.is_empty() {
-                            let mut csv_reader_builder = ReaderBuilder::new();
-                            csv_reader_builder.has_headers(false);
-                            dialect.apply_to_reader(&mut csv_reader_builder, field_size_limit);
-                            let mut csv_reader =
-                                csv_reader_builder.from_reader(available_data.as_bytes());
-                            let mut records_iter = csv_reader.records();
-
-                            if let Some(result) = records_iter.next() {
-                                match result {
-                                    Ok(_record) => {
-                                        // Skip the actual data - just update position tracking
-                                        let consumed_in_slice =
-                                            csv_reader.position().byte() as usize;
-
-                                        // Count newlines in the consumed record for accurate line_num tracking
-                                        let record_end =
-                                            consumed_in_slice.min(available_data.len());
-                                        let record_text = &available_data[..record_end];
-                                        let newline_count = record_text
-                                            .as_bytes()
-                                            .iter()
-                                            .filter(|&&b| b == b'\n')
-                                            .count();
-
-                                        {
-                                            let mut pos_guard = position.lock().await;
-                                            *pos_guard = current_pos + 1;
-                                        }
-                                        {
-                                            // Increment line_num based on actual newlines in the record
-                                            let mut line_num_guard = line_num.lock().await;
-                                            if newline_count > 0 {
-                                                *line_num_guard += newline_count;
-                                            } else {
-                                                *line_num_guard += 1;
-                                            }
-                                        }
-
-                                        *buffer_start_guard += consumed_in_slice;
-
-                                        if buffer_guard.len() > chunk_size * 8 {
-                                            let new_buffer =
-                                                buffer_guard[*buffer_start_guard..].to_string();
-                                            *buffer_guard = new_buffer;
-                                            *buffer_start_guard = 0;
-                                        }
-
-                                        row_found = true;
-                                        break;
-                                    }
-                                    Err(_) => {
-                                        // Continue reading
-                                    }
-                                }
+                stream
+                    .operation(ticket, async {
+                        for _ in 0..n {
+                            if stream.next_record().await?.is_none() {
+                                break; // EOF reached
                             }
                         }
+                        Ok(())
+                    })
+                    .await
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
 
-                        // Read more data from file
-                        let chunk_result: PyResult<(String, bool)> = if is_path {
-                            // Use Tokio File/BufReader for path-based sources
-                            let mut file_guard = file.lock().await;
-                            if file_guard.is_none() {
-                                let opened_file = File::open(&path).await.map_err(|e| {
-                                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                        "Failed to open file {path}: {e}"
-                                    ))
-                                })?;
-                                *file_guard = Some(BufReader::new(opened_file));
-                            }
-                            let reader = file_guard.as_mut().unwrap();
-                            let mut chunk = vec![0u8; chunk_size];
-                            match reader.read(&mut chunk).await {
-                                Ok(0) => Ok(("".to_string(), true)), // EOF
-                                Ok(n) => {
-                                    chunk.truncate(n);
-                                    let chunk_str = String::from_utf8(chunk).map_err(|_| {
-                                        PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                            "Invalid UTF-8 in CSV file",
-                                        )
-                                    })?;
-                                    Ok((chunk_str, false)) // Data read
-                                }
-                                Err(e) => Err(PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                    format!("Failed to read file {path}: {e}"),
-                                )),
-                            }
-                        } else {
-                            // Use Python file handle for Handle sources
-                            // Extract both file handle and event loop in a single spawn_blocking
-                            // This ensures we get them before moving into async context
-                            let file_handle_clone = file_handle.clone();
-                            let event_loop_clone = event_loop.clone();
-                            let (handle_py, loop_py) = tokio::task::spawn_blocking(move || {
-                                #[allow(deprecated)]
-                                // Python::with_gil is still required in blocking contexts (spawn_blocking)
-                                Python::with_gil(|py| -> PyResult<(Py<PyAny>, Py<PyAny>)> {
-                                    // Extract file handle
-                                    let handle_guard = file_handle_clone.lock().map_err(|_| {
-                                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                            "Failed to lock file handle",
-                                        )
-                                    })?;
-                                    let handle = handle_guard.as_ref().ok_or_else(|| {
-                                        PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                            "File handle not available",
-                                        )
-                                    })?;
-
-                                    // Extract event loop
-                                    let loop_guard = event_loop_clone.lock().map_err(|_| {
-                                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                            "Failed to lock event loop",
-                                        )
-                                    })?;
-                                    let loop_obj = loop_guard.as_ref().ok_or_else(|| {
-                                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                            "Event loop not available",
-                                        )
-                                    })?;
-
-                                    Ok((handle.clone_ref(py), loop_obj.clone_ref(py)))
-                                })
-                            })
-                            .await
-                            .map_err(|e| {
-                                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                    "Failed to extract file handle or event loop: {e}"
-                                ))
-                            })??;
-
-                            let chunk_str =
-                                read_from_python_file(handle_py, loop_py, chunk_size).await?;
-                            Ok((chunk_str.clone(), chunk_str.is_empty()))
-                        };
-
-                        match chunk_result {
-                            Ok((_chunk_str, true)) => {
-                                // EOF
-                                let available_data = if *buffer_start_guard < buffer_guard.len() {
-                                    &buffer_guard[*buffer_start_guard..]
-                                } else {
-                                    ""
-                                };
-
-                                if available_data.is_empty() {
-                                    break; // EOF, no more data
-                                }
-
-                                // Final parse attempt
-                                let mut csv_reader_builder = ReaderBuilder::new();
-                                csv_reader_builder.has_headers(false);
-                                dialect.apply_to_reader(&mut csv_reader_builder, None);
-                                let mut csv_reader =
-                                    csv_reader_builder.from_reader(available_data.as_bytes());
-                                let mut records_iter = csv_reader.records();
-
-                                match records_iter.next() {
-                                    Some(Ok(_record)) => {
-                                        // Skip the data, just update position
-                                        let consumed_in_slice =
-                                            csv_reader.position().byte() as usize;
-
-                                        {
-                                            let mut pos_guard = position.lock().await;
-                                            *pos_guard = current_pos + 1;
-                                        }
-                                        {
-                                            // Count newlines for accurate line_num tracking
-                                            let record_end =
-                                                consumed_in_slice.min(available_data.len());
-                                            let record_text = &available_data[..record_end];
-                                            let newline_count = record_text
-                                                .as_bytes()
-                                                .iter()
-                                                .filter(|&&b| b == b'\n')
-                                                .count();
-
-                                            let mut line_num_guard = line_num.lock().await;
-                                            if newline_count > 0 {
-                                                *line_num_guard += newline_count;
-                                            } else {
-                                                *line_num_guard += 1;
-                                            }
-                                        }
-                                        buffer_guard.clear();
-                                        *buffer_start_guard = 0;
-                                        row_found = true;
-                                    }
-                                    Some(Err(e)) => {
-                                        let error_msg = format!(
-                                            "CSV parse error at row {current_pos} (0-indexed) in file '{path}': {e}. \
-                                            The CSV file may be malformed or have incomplete records."
-                                        );
-                                        return Err(CSVError::new_err(error_msg));
-                                    }
-                                    None => {
-                                        // EOF
-                                    }
-                                }
-                                break;
-                            }
-                            Ok((chunk_str, false)) => {
-                                // Append chunk to buffer
-                                buffer_guard.push_str(&chunk_str);
-                            }
-                            Err(e) => {
-                                return Err(e);
-                            }
+    /// Put every remaining row into an `asyncio.Queue` until EOF, for consumers that
+    /// take rows from the queue.
+    ///
+    /// Rows are read `batch_size` at a time (1000 without it) and put with
+    /// `await queue.put()`, so a bounded queue holds the reader back until consumers
+    /// catch up. Returns the number of rows put. Nothing marks the end: put one
+    /// sentinel per consumer after awaiting this. Requires asyncio.
+    ///
+    /// # Arguments
+    /// * `queue` - An `asyncio.Queue` or anything with an awaitable `put()`
+    /// * `batch_size` - Put lists of up to this many rows rather than single rows
+    ///   (default: None, one row per item)
+    #[pyo3(signature = (queue, batch_size = None))]
+    fn into_queue(
+        self_: PyRef<Self>,
+        queue: Py<PyAny>,
+        batch_size: Option<usize>,
+    ) -> PyResult<Py<PyAny>> {
+        let py = self_.py();
+        if batch_size == Some(0) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "batch_size must be at least 1",
+            ));
+        }
+        let event_loop = py
+            .import("asyncio")?
+            .call_method0("get_running_loop")
+            .map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                    "into_queue() requires a running asyncio event loop",
+                )
+            })?
+            .unbind();
+        let queue = Arc::new(queue);
+        let event_loop = Arc::new(event_loop);
+        let stream = self_.stream.clone();
+        let format = self_.format.clone();
+        let checkpoint = self_.checkpoint.clone();
+        let chunk = batch_size.unwrap_or(1000);
+        let future = async move {
+            let mut count = 0usize;
+            loop {
+                let rows = stream
+                    .operation(stream.ticket(), async {
+                        if let Some(checkpoint) = &checkpoint {
+                            checkpoint
+                                .before_read(&stream, format.header_names())
+                                .await?;
                         }
-                    }
-
-                    if !row_found {
-                        break; // EOF reached
-                    }
+                        format.read_header(&stream).await?;
+                        let rows = stream.next_records(chunk).await?;
+                        if let Some(checkpoint) = &checkpoint {
+                            checkpoint
+                                .after_read(&stream, rows.len(), format.header_names())
+                                .await?;
+                        }
+                        Ok(rows)
+                    })
+                    .await?;
+                if rows.is_empty() {
+                    return Ok(count);
                 }
+                count += rows.len();
+                let (queue, event_loop, format) =
+                    (queue.clone(), event_loop.clone(), format.clone());
+                let batched = batch_size.is_some();
+                tokio::task::spawn_blocking(move || {
+                    Python::attach(|py| -> PyResult<()> {
+                        let rows = format.rows(py, rows)?;
+                        py.import("rapcsv")?
+                            .getattr("_put_all_threadsafe")?
+                            .call1((queue.bind(py), event_loop.bind(py), rows, batched))?;
+                        Ok(())
+                    })
+                })
+                .await
+                .map_err(|e| {
+                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                        "Failed to await Python coroutine: {e}"
+                    ))
+                })??;
+            }
+        };
+        future_into_py(py, future).map(|bound| bound.unbind())
+    }
 
-                Ok(())
+    /// Open the reader ahead of the first read, so a bad path, permissions, archive
+    /// member or key, undecodable bytes or an unexpected header fail where the reader
+    /// is set up rather than deep inside a pipeline. Every file is opened and the
+    /// first record is read and checked, but not consumed. `async with` calls this on
+    /// entry; it does nothing once reading has started.
+    fn open(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let stream = self_.stream.clone();
+        let ticket = stream.ticket();
+        Python::attach(|py| {
+            let future = async move { stream.operation(ticket, stream.open()).await };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// The dialect the reader reads with, completed with what the data turned out to
+    /// use, so a pipeline can log or assert the format it is processing. Opens the
+    /// reader first (see `open()`).
+    ///
+    /// Returns the dialect keyword arguments, as in `state()`, with `lineterminator`
+    /// set to the first record's line ending ("\r\n", "\n" or "\r") when it has one,
+    /// plus `bom` (whether the data starts with a UTF-8 byte order mark, None for an
+    /// empty file or if none has been read) and `encoding` ("utf-8-sig" with a BOM,
+    /// else "utf-8").
+    fn dialect(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let stream = self_.stream.clone();
+        let ticket = stream.ticket();
+        let mut report = self_.dialect.clone();
+        Python::attach(|py| {
+            let future = async move {
+                stream.operation(ticket, stream.open()).await?;
+                let detected = stream.detected();
+                if let Some(terminator) = detected.as_ref().and_then(|d| d.terminator.clone()) {
+                    report["lineterminator"] = terminator.into();
+                }
+                let bom = detected.map(|d| d.bom);
+                report["bom"] = bom.into();
+                report["encoding"] = if bom == Some(true) {
+                    "utf-8-sig"
+                } else {
+                    "utf-8"
+                }
+                .into();
+                Python::attach(|py| json_to_py(py, &report))
             };
             future_into_py(py, future).map(|bound| bound.unbind())
         })
     }
 
-    /// Async context manager entry.
+    /// Async context manager entry: opens the reader (see `open()`).
     fn __aenter__(slf: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let stream = slf.stream.clone();
+        let ticket = stream.ticket();
         let slf: Py<Self> = slf.into();
         Python::attach(|py| {
-            let future = async move { Ok(slf) };
+            let future = async move {
+                stream.operation(ticket, stream.open()).await?;
+                Ok(slf)
+            };
             future_into_py(py, future).map(|bound| bound.unbind())
         })
     }
 
     /// Async context manager exit.
     fn __aexit__(
-        &mut self,
+        &self,
         _exc_type: Option<&Bound<'_, PyAny>>,
         _exc_val: Option<&Bound<'_, PyAny>>,
         _exc_tb: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Py<PyAny>> {
-        let file = Arc::clone(&self.file);
-        let buffer = Arc::clone(&self.buffer);
-        let buffer_start = Arc::clone(&self.buffer_start);
+        let stream = self.stream.clone();
+        Python::attach(|py| {
+            let future = async move {
+                // Close file handle and clear buffer
+                stream.close().await;
+                stream.close_adapter().await
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+}
+
+impl Reader {
+    /// Move to a saved position: reopen its file at the offset and restore the counters.
+    fn resume(&self, saved: SavedState) -> PyResult<()> {
+        self.stream.restore(&saved.stream)?;
+        if let Some(end) = saved.stream.end {
+            self.stream.set_range_end(end);
+        }
+        if let Some(header) = saved.header {
+            self.format.set_header(header)?;
+        }
+        Ok(())
+    }
+
+    /// Build the awaitable for the next row, handling EOF as `eof` says.
+    fn next_future(&self, eof: EndOfFile) -> PyResult<Py<PyAny>> {
+        let stream = self.stream.clone();
+        let format = self.format.clone();
+        let checkpoint = self.checkpoint.clone();
+        let ticket = stream.ticket();
         Python::attach(|py| {
             let future = async move {
-                // Close file handle
-                let mut file_guard = file.lock().await;
-                if file_guard.take().is_some() {
-                    // File handle closed
-                }
-                // Clear buffer
-                let mut buffer_guard = buffer.lock().await;
-                buffer_guard.clear();
-                let mut buffer_start_guard = buffer_start.lock().await;
-                *buffer_start_guard = 0;
-                Ok(())
+                let row = stream
+                    .operation(ticket, async {
+                        if let Some(checkpoint) = &checkpoint {
+                            checkpoint
+                                .before_read(&stream, format.header_names())
+                                .await?;
+                        }
+                        format.read_header(&stream).await?;
+                        let row = stream.next_record().await?;
+                        if let Some(checkpoint) = &checkpoint {
+                            let rows = usize::from(row.is_some());
+                            checkpoint
+                                .after_read(&stream, rows, format.header_names())
+                                .await?;
+                        }
+                        Ok(row)
+                    })
+                    .await?;
+                Python::attach(|py| match row {
+                    Some(row) => format.row(py, row),
+                    None => eof.resolve(py, PyList::empty(py).into_any()),
+                })
             };
             future_into_py(py, future).map(|bound| bound.unbind())
         })
@@ -1418,22 +2670,14 @@ impl Reader {
 /// mapping field names to values. Wraps a Reader internally.
 #[pyclass]
 struct AsyncDictReader {
-    #[allow(dead_code)] // Kept for compatibility, but we use stored state directly
-    reader: Py<Reader>, // Keep for compatibility, but we'll use stored state
-    source: FileSource, // Either Path(String) or Handle {file, event_loop}
-    path: String,       // Store separately for direct access
-    file: Arc<Mutex<Option<BufReader<File>>>>, // Store separately - only used when source is Path
-    file_handle: Arc<StdMutex<Option<Py<PyAny>>>>, // Python file handle when source is Handle
-    event_loop: Arc<StdMutex<Option<Py<PyAny>>>>, // Event loop reference for run_coroutine_threadsafe
-    buffer: Arc<Mutex<String>>,                   // Store separately
-    buffer_start: Arc<Mutex<usize>>,              // Store separately
-    position: Arc<Mutex<usize>>,                  // Store separately
-    line_num: Arc<Mutex<usize>>,                  // Store separately
-    dialect: DialectConfig,                       // Store separately
-    read_size: usize,                             // Store separately
+    stream: RecordStream, // Shared streaming state (file handle, buffer, position, line_num)
     fieldnames: Arc<Mutex<Option<Vec<String>>>>,
     restkey: Option<String>,
     restval: Option<String>,
+    eof: EndOfFile,                 // What read_row() returns at EOF
+    header_rules: Arc<HeaderRules>, // Applied to the header row read from the file
+    keep_first: bool,               // The first of repeated names gives the value
+    keys: KeyCache,                 // Key strings shared by the dicts returned
 }
 
 #[pymethods]
@@ -1445,6 +2689,14 @@ impl AsyncDictReader {
     /// * `fieldnames` - Optional list of field names. If None, first row is used as header.
     /// * `restkey` - Key name for extra values when row has more fields than fieldnames
     /// * `restval` - Default value for missing fields when row has fewer fields
+    /// * `eof` - What `read_row()` returns at EOF: "empty" for `{}`, "none" for `None`
+    ///   or "raise" to raise `EOFError` (default: "empty")
+    /// * `duplicate_headers` - What to do with a name the header row holds more than
+    ///   once: "keep_first" keeps the first column's value, "suffix" renames the
+    ///   repeats "id_1", "id_2", ... and "error" raises `CSVError` (default: None, a
+    ///   later column's value replaces an earlier one's, as in `csv.DictReader`)
+    /// * `normalize_headers` - True to trim, lowercase and snake_case the names of the
+    ///   header row, or a callable given each name (default: None)
     /// * All dialect parameters from Reader are supported
     #[new]
     #[pyo3(signature = (
@@ -1460,7 +2712,11 @@ impl AsyncDictReader {
         skipinitialspace = None,
         strict = None,
         double_quote = None,
-        read_size = None
+        read_size = None,
+        eof = "empty",
+        dialect = None,
+        duplicate_headers = None,
+        normalize_headers = None
     ))]
     #[allow(clippy::too_many_arguments)] // Required for Python API compatibility
     fn new(
@@ -1478,46 +2734,20 @@ impl AsyncDictReader {
         strict: Option<bool>,
         double_quote: Option<bool>,
         read_size: Option<usize>,
+        eof: &str,
+        dialect: Option<&Bound<'_, PyAny>>,
+        duplicate_headers: Option<&str>,
+        normalize_headers: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Self> {
-        // Try to extract as string first (file path)
-        let (source, path_clone, file_handle, event_loop) =
-            if let Ok(path_str) = path_or_handle.extract::<String>() {
-                validate_path(&path_str)?;
-                (
-                    FileSource::Path(path_str.clone()),
-                    path_str,
-                    Arc::new(StdMutex::new(None)),
-                    Arc::new(StdMutex::new(None)),
-                )
-            } else {
-                // Assume it's a file-like object
-                let handle = path_or_handle.clone().unbind();
-                let placeholder_path = "<file_handle>".to_string();
-
-                // Get the running event loop (required for aiofiles/rapfiles handles)
-                // Must be available during construction since we're in Python's context
-                let asyncio = py.import("asyncio")?;
-                let loop_obj = asyncio.call_method0("get_running_loop").map_err(|_| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                        "No running event loop found. File handles require a running event loop. \
-                     Use 'async with' or ensure asyncio.run() has been called.",
-                    )
-                })?;
-
-                let handle_clone = handle.clone_ref(py);
-                let loop_clone = loop_obj.unbind();
-                (
-                    FileSource::Handle {
-                        file: handle_clone.clone_ref(py),
-                        event_loop: loop_clone.clone_ref(py),
-                    },
-                    placeholder_path,
-                    Arc::new(StdMutex::new(Some(handle_clone))),
-                    Arc::new(StdMutex::new(Some(loop_clone))), // Always store the loop
-                )
-            };
+        let eof = EndOfFile::parse(eof)?;
+        let duplicate_headers = duplicate_headers.map(DuplicateHeaders::parse).transpose()?;
+        let keep_first = duplicate_headers == Some(DuplicateHeaders::KeepFirst);
+        let header_rules =
+            HeaderRules::from_args(normalize_headers, duplicate_headers.unwrap_or_default())?;
+        let (source, path_clone, file_handle, event_loop) = resolve_source(py, path_or_handle)?;
 
         let dialect = DialectConfig::from_python(
+            dialect,
             delimiter,
             quotechar,
             escapechar,
@@ -1527,395 +2757,37 @@ impl AsyncDictReader {
             strict,
             double_quote,
         )?;
-        let read_size_val = read_size.unwrap_or(8192);
-
-        // Create a Reader for compatibility (even though AsyncDictReader has its own file handling)
-        let reader = Reader::new(
-            py,
-            path_or_handle,
-            delimiter,
-            quotechar,
-            escapechar,
-            quoting,
-            lineterminator,
-            skipinitialspace,
-            strict,
-            double_quote,
-            read_size,
-            None, // field_size_limit - not used in DictReader for now
-        )?;
-
         Ok(AsyncDictReader {
-            reader: Py::new(py, reader)?,
-            source,
-            path: path_clone,
-            file: Arc::new(Mutex::new(None)),
-            file_handle,
-            event_loop,
-            buffer: Arc::new(Mutex::new(String::new())),
-            buffer_start: Arc::new(Mutex::new(0)),
-            position: Arc::new(Mutex::new(0)),
-            line_num: Arc::new(Mutex::new(0)),
-            dialect,
-            read_size: read_size_val,
+            stream: RecordStream::new(
+                &source,
+                path_clone,
+                file_handle,
+                event_loop,
+                dialect,
+                read_size.unwrap_or(8192),
+                None, // field_size_limit - not used in DictReader for now
+            ),
             fieldnames: Arc::new(Mutex::new(fieldnames)),
             restkey,
             restval,
+            eof,
+            header_rules: Arc::new(header_rules),
+            keep_first,
+            keys: KeyCache::default(),
         })
     }
 
     /// Read the next row as a dictionary.
+    ///
+    /// Returns an empty dict at EOF, or `None` or raises `EOFError` depending on `eof`.
     fn read_row(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
-        let path = self_.path.clone();
-        let is_path = matches!(self_.source, FileSource::Path(_));
-        let file = Arc::clone(&self_.file);
-        let file_handle = Arc::clone(&self_.file_handle);
-        let event_loop = Arc::clone(&self_.event_loop);
-        let buffer = Arc::clone(&self_.buffer);
-        let buffer_start = Arc::clone(&self_.buffer_start);
-        let position = Arc::clone(&self_.position);
-        let line_num = Arc::clone(&self_.line_num);
-        let dialect = self_.dialect.clone();
-        let chunk_size = self_.read_size;
-        let fieldnames = Arc::clone(&self_.fieldnames);
-        let restkey = self_.restkey.clone();
-        let restval = self_.restval.clone();
-
-        Python::attach(|py| {
-            let future = async move {
-                // Get or open the file handle - only for path-based sources
-                if is_path {
-                    let mut file_guard = file.lock().await;
-                    if file_guard.is_none() {
-                        let opened_file = File::open(&path).await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                "Failed to open file {path}: {e}"
-                            ))
-                        })?;
-                        *file_guard = Some(BufReader::new(opened_file));
-                    }
-                }
-
-                let mut buffer_guard = buffer.lock().await;
-                let mut buffer_start_guard = buffer_start.lock().await;
-
-                // Main loop: read rows and handle fieldnames lazily
-                // If fieldnames are None, first row becomes fieldnames and we read next row
-                let final_data_row = loop {
-                    // Check if we need to load fieldnames
-                    let need_fieldnames = {
-                        let fieldnames_guard = fieldnames.lock().await;
-                        fieldnames_guard.is_none()
-                    };
-
-                    // Read one row
-                    let current_pos = {
-                        let pos_guard = position.lock().await;
-                        *pos_guard
-                    };
-
-                    #[allow(unused_assignments)]
-                    // row_vec is assigned in inner loop and used in outer loop
-                    let mut row_vec: Option<Vec<String>> = None;
-
-                    // Inner loop to read one CSV record
-                    loop {
-                        let available_data = if *buffer_start_guard < buffer_guard.len() {
-                            &buffer_guard[*buffer_start_guard..]
-                        } else {
-                            ""
-                        };
-
-                        if !available_data.is_empty() {
-                            let mut csv_reader_builder = ReaderBuilder::new();
-                            csv_reader_builder.has_headers(false);
-                            dialect.apply_to_reader(&mut csv_reader_builder, None);
-                            let mut csv_reader =
-                                csv_reader_builder.from_reader(available_data.as_bytes());
-                            let mut records_iter = csv_reader.records();
-
-                            if let Some(result) = records_iter.next() {
-                                match result {
-                                    Ok(record) => {
-                                        let row: Vec<String> =
-                                            record.iter().map(|s| s.to_string()).collect();
-
-                                        let consumed_in_slice =
-                                            csv_reader.position().byte() as usize;
-
-                                        // Count newlines in the consumed record for accurate line_num tracking
-                                        let record_end =
-                                            consumed_in_slice.min(available_data.len());
-                                        let record_text = &available_data[..record_end];
-                                        let newline_count = record_text
-                                            .as_bytes()
-                                            .iter()
-                                            .filter(|&&b| b == b'\n')
-                                            .count();
-
-                                        {
-                                            let mut pos_guard = position.lock().await;
-                                            *pos_guard = current_pos + 1;
-                                        }
-                                        {
-                                            // Increment line_num based on actual newlines in the record
-                                            let mut line_num_guard = line_num.lock().await;
-                                            if newline_count > 0 {
-                                                *line_num_guard += newline_count;
-                                            } else {
-                                                *line_num_guard += 1;
-                                            }
-                                        }
-
-                                        *buffer_start_guard += consumed_in_slice;
-
-                                        if buffer_guard.len() > chunk_size * 8 {
-                                            let new_buffer =
-                                                buffer_guard[*buffer_start_guard..].to_string();
-                                            *buffer_guard = new_buffer;
-                                            *buffer_start_guard = 0;
-                                        }
-
-                                        row_vec = Some(row);
-                                        break;
-                                    }
-                                    Err(_) => {
-                                        // Continue reading
-                                    }
-                                }
-                            }
-                        }
-
-                        // Read more data from file
-                        let chunk_result: PyResult<(String, bool)> = if is_path {
-                            // Use Tokio File/BufReader for path-based sources
-                            let mut file_guard = file.lock().await;
-                            if file_guard.is_none() {
-                                let opened_file = File::open(&path).await.map_err(|e| {
-                                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                        "Failed to open file {path}: {e}"
-                                    ))
-                                })?;
-                                *file_guard = Some(BufReader::new(opened_file));
-                            }
-                            let reader = file_guard.as_mut().unwrap();
-                            let mut chunk = vec![0u8; chunk_size];
-                            match reader.read(&mut chunk).await {
-                                Ok(0) => Ok(("".to_string(), true)), // EOF
-                                Ok(n) => {
-                                    chunk.truncate(n);
-                                    let chunk_str = String::from_utf8(chunk).map_err(|_| {
-                                        PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                            "Invalid UTF-8 in CSV file",
-                                        )
-                                    })?;
-                                    Ok((chunk_str, false)) // Data read
-                                }
-                                Err(e) => Err(PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                    format!("Failed to read file {path}: {e}"),
-                                )),
-                            }
-                        } else {
-                            // Use Python file handle for Handle sources
-                            // Extract both file handle and event loop in a single spawn_blocking
-                            // This ensures we get them before moving into async context
-                            let file_handle_clone = file_handle.clone();
-                            let event_loop_clone = event_loop.clone();
-                            let (handle_py, loop_py) = tokio::task::spawn_blocking(move || {
-                                #[allow(deprecated)]
-                                // Python::with_gil is still required in blocking contexts (spawn_blocking)
-                                Python::with_gil(|py| -> PyResult<(Py<PyAny>, Py<PyAny>)> {
-                                    // Extract file handle
-                                    let handle_guard = file_handle_clone.lock().map_err(|_| {
-                                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                            "Failed to lock file handle",
-                                        )
-                                    })?;
-                                    let handle = handle_guard.as_ref().ok_or_else(|| {
-                                        PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                            "File handle not available",
-                                        )
-                                    })?;
-
-                                    // Extract event loop
-                                    let loop_guard = event_loop_clone.lock().map_err(|_| {
-                                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                            "Failed to lock event loop",
-                                        )
-                                    })?;
-                                    let loop_obj = loop_guard.as_ref().ok_or_else(|| {
-                                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                            "Event loop not available",
-                                        )
-                                    })?;
-
-                                    Ok((handle.clone_ref(py), loop_obj.clone_ref(py)))
-                                })
-                            })
-                            .await
-                            .map_err(|e| {
-                                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                    "Failed to extract file handle or event loop: {e}"
-                                ))
-                            })??;
-
-                            let chunk_str =
-                                read_from_python_file(handle_py, loop_py, chunk_size).await?;
-                            Ok((chunk_str.clone(), chunk_str.is_empty()))
-                        };
-
-                        match chunk_result {
-                            Ok((_chunk_str, true)) => {
-                                // EOF
-                                let available_data = if *buffer_start_guard < buffer_guard.len() {
-                                    &buffer_guard[*buffer_start_guard..]
-                                } else {
-                                    ""
-                                };
-
-                                if available_data.is_empty() {
-                                    row_vec = Some(Vec::<String>::new()); // EOF
-                                    break;
-                                }
-
-                                // Final parse attempt
-                                let mut csv_reader_builder = ReaderBuilder::new();
-                                csv_reader_builder.has_headers(false);
-                                dialect.apply_to_reader(&mut csv_reader_builder, None);
-                                let mut csv_reader =
-                                    csv_reader_builder.from_reader(available_data.as_bytes());
-                                let mut records_iter = csv_reader.records();
-
-                                match records_iter.next() {
-                                    Some(Ok(record)) => {
-                                        let row: Vec<String> =
-                                            record.iter().map(|s| s.to_string()).collect();
-
-                                        let consumed_in_slice =
-                                            csv_reader.position().byte() as usize;
-
-                                        {
-                                            let mut pos_guard = position.lock().await;
-                                            *pos_guard = current_pos + 1;
-                                        }
-                                        {
-                                            // Count newlines for accurate line_num tracking
-                                            let record_end =
-                                                consumed_in_slice.min(available_data.len());
-                                            let record_text = &available_data[..record_end];
-                                            let newline_count = record_text
-                                                .as_bytes()
-                                                .iter()
-                                                .filter(|&&b| b == b'\n')
-                                                .count();
-
-                                            let mut line_num_guard = line_num.lock().await;
-                                            if newline_count > 0 {
-                                                *line_num_guard += newline_count;
-                                            } else {
-                                                *line_num_guard += 1;
-                                            }
-                                        }
-                                        buffer_guard.clear();
-                                        *buffer_start_guard = 0;
-                                        row_vec = Some(row);
-                                    }
-                                    Some(Err(e)) => {
-                                        let error_msg = format!(
-                                            "CSV parse error at row {current_pos} (0-indexed) in file '{path}': {e}. \
-                                            The CSV file may be malformed or have incomplete records."
-                                        );
-                                        return Err(CSVError::new_err(error_msg));
-                                    }
-                                    None => {
-                                        row_vec = Some(Vec::<String>::new()); // EOF
-                                    }
-                                }
-                                break;
-                            }
-                            Ok((chunk_str, false)) => {
-                                // Append chunk to buffer
-                                buffer_guard.push_str(&chunk_str);
-                            }
-                            Err(e) => {
-                                return Err(e);
-                            }
-                        }
-                    }
-
-                    // Handle fieldnames
-                    let mut fieldnames_guard = fieldnames.lock().await;
-
-                    if need_fieldnames {
-                        // First call - use row as fieldnames
-                        match &row_vec {
-                            Some(first_row) if !first_row.is_empty() => {
-                                *fieldnames_guard = Some(first_row.clone());
-                                drop(fieldnames_guard);
-                                // Continue outer loop to read next row for actual data
-                                continue;
-                            }
-                            Some(_) | None => {
-                                // EOF or empty row
-                                drop(fieldnames_guard);
-                                break Vec::<String>::new();
-                            }
-                        }
-                    } else {
-                        // Fieldnames already loaded - use current row as data
-                        drop(fieldnames_guard);
-                        // row_vec should be Some at this point, but handle None just in case
-                        match row_vec {
-                            Some(row) => break row,
-                            None => break Vec::<String>::new(), // EOF
-                        }
-                    }
-                };
-
-                // Get fieldnames for dict conversion
-                let fieldnames_vec = {
-                    let fieldnames_guard = fieldnames.lock().await;
-                    fieldnames_guard.as_ref().unwrap().clone()
-                };
-
-                // Convert Vec<String> to PyDict
-                #[allow(deprecated)] // Python::with_gil required in this async context
-                Python::with_gil(|#[allow(unused_variables)] py| -> PyResult<Py<PyAny>> {
-                    let py_dict = PyDict::new(py);
-
-                    // If EOF (empty row), return empty dict
-                    if final_data_row.is_empty() {
-                        return Ok(py_dict.unbind().into());
-                    }
-
-                    let fieldnames_slice = &fieldnames_vec;
-
-                    // Map fieldnames to values
-                    let restval_default = restval.as_deref().unwrap_or("");
-                    for (i, fieldname) in fieldnames_slice.iter().enumerate() {
-                        let value = if i < final_data_row.len() {
-                            &final_data_row[i]
-                        } else {
-                            // Missing field - use restval
-                            restval_default
-                        };
-                        py_dict.set_item(fieldname, value)?;
-                    }
-
-                    // Handle restkey - extra values beyond fieldnames
-                    if let Some(ref restkey_str) = restkey {
-                        if final_data_row.len() > fieldnames_slice.len() {
-                            let extra_values: Vec<String> =
-                                final_data_row[fieldnames_slice.len()..].to_vec();
-                            py_dict.set_item(restkey_str, extra_values)?;
-                        }
-                    }
+        self_.dicts_future(None, self_.eof)
+    }
 
-                    Ok(py_dict.unbind().into())
-                })
-            };
-            future_into_py(py, future).map(|bound| bound.unbind())
-        })
+    /// Read up to `n` rows as a list of dicts, which share one set of key strings.
+    /// Returns an empty list at EOF.
+    fn read_rows(&self, n: usize) -> PyResult<Py<PyAny>> {
+        self.dicts_future(Some(n), EndOfFile::Empty)
     }
 
     /// Async iterator protocol - returns self.
@@ -1925,11 +2797,7 @@ impl AsyncDictReader {
 
     /// Async iterator next - returns next row as dict or raises StopAsyncIteration.
     fn __anext__(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
-        // Delegate to read_row, but we need to check for empty dict
-        // Since read_row returns a Python awaitable, we'll handle StopAsyncIteration
-        // by wrapping the call. For now, just call read_row - Python's async for
-        // will handle empty results. We'll add proper StopAsyncIteration later if needed.
-        AsyncDictReader::read_row(self_)
+        self_.dicts_future(None, EndOfFile::Stop)
     }
 
     /// Get fieldnames (lazy loaded).
@@ -1938,7 +2806,7 @@ impl AsyncDictReader {
         Python::attach(|py| {
             // Return fieldnames as Vec<String> - Python will convert to list
             // Using Option<Vec<String>> so None can be handled in Python
-            let future = pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let future = future_into_py(py, async move {
                 let fieldnames_guard = fieldnames.lock().await;
                 Ok::<Option<Vec<String>>, PyErr>(fieldnames_guard.clone())
             });
@@ -2020,6 +2888,65 @@ impl AsyncDictReader {
     }
 }
 
+impl AsyncDictReader {
+    /// Take the first record as the fieldnames if there are none yet.
+    async fn read_fieldnames(
+        stream: &RecordStream,
+        fieldnames: &Mutex<Option<Vec<String>>>,
+        header_rules: &HeaderRules,
+    ) -> PyResult<()> {
+        let mut fieldnames = fieldnames.lock().await;
+        if fieldnames.is_none() {
+            if let Some(mut row) = stream.next_record().await? {
+                header_rules.apply(&mut row)?;
+                *fieldnames = Some(row);
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the awaitable for up to `n` rows as a list of dicts, or for the next row
+    /// alone (handling EOF as `eof` says) when `n` is None.
+    fn dicts_future(&self, n: Option<usize>, eof: EndOfFile) -> PyResult<Py<PyAny>> {
+        let stream = self.stream.clone();
+        let fieldnames = Arc::clone(&self.fieldnames);
+        let header_rules = Arc::clone(&self.header_rules);
+        let keys = self.keys.clone();
+        let restkey = self.restkey.clone();
+        let restval = self.restval.clone();
+        let keep_first = self.keep_first;
+        let ticket = stream.ticket();
+
+        Python::attach(|py| {
+            let future = async move {
+                let rows = stream
+                    .operation(ticket, async {
+                        Self::read_fieldnames(&stream, &fieldnames, &header_rules).await?;
+                        stream.next_records(n.unwrap_or(1)).await
+                    })
+                    .await?;
+                let fieldnames = fieldnames.lock().await.clone().unwrap_or_default();
+                Python::attach(|py| {
+                    let restval = restval.as_deref().unwrap_or("");
+                    let keys = keys.get(py, &fieldnames, restkey.as_deref(), restval, keep_first);
+                    if n.is_some() {
+                        let dicts = rows
+                            .into_iter()
+                            .map(|row| keys.dict(py, row))
+                            .collect::<PyResult<Vec<_>>>()?;
+                        return Ok(PyList::new(py, dicts)?.into_any().unbind());
+                    }
+                    match rows.into_iter().next() {
+                        Some(row) => Ok(keys.dict(py, row)?.into_any().unbind()),
+                        None => eof.resolve(py, PyDict::new(py).into_any()),
+                    }
+                })
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+}
+
 /// Async CSV DictWriter.
 ///
 /// Provides dictionary-based CSV writing where rows are written as dictionaries
@@ -2029,14 +2956,75 @@ struct AsyncDictWriter {
     #[allow(dead_code)] // Kept for compatibility, but we use stored state directly
     writer: Py<Writer>,
     source: FileSource, // Either Path(String) or Handle {file, event_loop}
-    path: String,       // Store path separately for writeheader/writerow access
+    path: FilePath,     // Store path separately for writeheader/writerow access
     file: Arc<Mutex<Option<File>>>, // Store file Arc for writeheader/writerow access - only used when source is Path
     file_handle: Arc<StdMutex<Option<Py<PyAny>>>>, // Python file handle when source is Handle
     event_loop: Arc<StdMutex<Option<Py<PyAny>>>>, // Event loop reference for run_coroutine_threadsafe
     dialect: DialectConfig, // Store dialect separately for writeheader/writerow access
-    fieldnames: Vec<String>,
-    extrasaction: String, // "raise" or "ignore"
+    fieldnames: Arc<StdMutex<Option<Vec<String>>>>, // Taken from the first row when not given
+    auto_header: bool,      // Header written with the first row (`fieldnames=None`)
+    extrasaction: String,   // "raise" or "ignore"
     restval: String,
+    sanitize_formulas: bool, // Prefix str values a spreadsheet would run with "'"
+}
+
+impl AsyncDictWriter {
+    /// Order `dict` by fieldnames. With `fieldnames=None`, the first dict fixes the
+    /// fieldnames from its keys, and the header row to write before it is returned too.
+    fn dict_to_row(
+        &self,
+        dict: &Bound<'_, PyDict>,
+    ) -> PyResult<(Option<Vec<String>>, Vec<String>)> {
+        let mut guard = self.fieldnames.lock().unwrap_or_else(|e| e.into_inner());
+        let header = match &*guard {
+            Some(_) => None,
+            None => {
+                let names = dict
+                    .keys()
+                    .iter()
+                    .map(|k| k.extract::<String>())
+                    .collect::<PyResult<Vec<_>>>()?;
+                *guard = Some(names.clone());
+                Some(names)
+            }
+        };
+        let fieldnames = guard.as_deref().unwrap_or_default();
+
+        // Check for extra keys if extrasaction == "raise"
+        if self.extrasaction == "raise" {
+            for key in dict.keys().iter() {
+                let key = key.extract::<String>().unwrap_or_default();
+                if !fieldnames.contains(&key) {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "dict contains fields not in fieldnames: {key}"
+                    )));
+                }
+            }
+        }
+
+        // Build Vec<String> ordered by fieldnames
+        let mut row = Vec::with_capacity(fieldnames.len());
+        for fieldname in fieldnames {
+            match dict.get_item(fieldname) {
+                Ok(Some(value)) => match value.extract::<String>() {
+                    Ok(text) if self.sanitize_formulas => row.push(values::sanitize_formula(text)),
+                    Ok(text) => row.push(text),
+                    Err(_) => row.push(value.to_string()),
+                },
+                Ok(None) | Err(_) => {
+                    // Missing key - use restval
+                    row.push(self.restval.clone());
+                }
+            }
+        }
+        Ok((header, row))
+    }
+
+    /// Forget fieldnames taken from a first row whose header was not written, so the
+    /// next row supplies them, and the header, again.
+    fn reset_fieldnames(fieldnames: &StdMutex<Option<Vec<String>>>) {
+        *fieldnames.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
 }
 
 #[pymethods]
@@ -2045,14 +3033,17 @@ impl AsyncDictWriter {
     ///
     /// # Arguments
     /// * `path` - Path to the CSV file
-    /// * `fieldnames` - Required list of field names defining CSV structure
+    /// * `fieldnames` - List of field names defining CSV structure, or None to take them
+    ///   from the keys of the first row written and write the header with it
     /// * `extrasaction` - Action for extra keys: "raise" (default) or "ignore"
     /// * `restval` - Default value for missing keys (default: "")
+    /// * `sanitize_formulas` - Prefix str values that a spreadsheet would run as
+    ///   formulas with `'`, as for Writer (default: false)
     /// * All dialect parameters from Writer are supported
     #[new]
     #[pyo3(signature = (
         path_or_handle,
-        fieldnames,
+        fieldnames = None,
         extrasaction = "raise",
         restval = "",
         delimiter = None,
@@ -2063,13 +3054,15 @@ impl AsyncDictWriter {
         skipinitialspace = None,
         strict = None,
         double_quote = None,
-        write_size = None
+        write_size = None,
+        dialect = None,
+        sanitize_formulas = false
     ))]
     #[allow(clippy::too_many_arguments)] // Required for Python API compatibility
     fn new(
         #[allow(unused_variables)] _py: Python<'_>,
         path_or_handle: &Bound<'_, PyAny>,
-        fieldnames: Vec<String>,
+        fieldnames: Option<Vec<String>>,
         extrasaction: &str,
         restval: &str,
         delimiter: Option<&str>,
@@ -2081,8 +3074,11 @@ impl AsyncDictWriter {
         #[allow(unused_variables)] strict: Option<bool>,
         double_quote: Option<bool>,
         #[allow(unused_variables)] write_size: Option<usize>,
+        dialect: Option<&Bound<'_, PyAny>>,
+        sanitize_formulas: bool,
     ) -> PyResult<Self> {
-        let dialect = DialectConfig::from_python(
+        let config = DialectConfig::from_python(
+            dialect,
             delimiter,
             quotechar,
             escapechar,
@@ -2093,43 +3089,14 @@ impl AsyncDictWriter {
             double_quote,
         )?;
         Python::attach(|py| {
-            // Try to extract as string first (file path)
-            let (source, path_str, file_handle, event_loop) =
-                if let Ok(path_str) = path_or_handle.extract::<String>() {
-                    validate_path(&path_str)?;
-                    (
-                        FileSource::Path(path_str.clone()),
-                        path_str,
-                        Arc::new(StdMutex::new(None)),
-                        Arc::new(StdMutex::new(None)),
-                    )
-                } else {
-                    // Assume it's a file-like object
-                    let handle = path_or_handle.clone().unbind();
-                    let placeholder_path = "<file_handle>".to_string();
-
-                    // Get the running event loop (required for aiofiles/rapfiles handles)
-                    // Must be available during construction since we're in Python's context
-                    let asyncio = py.import("asyncio")?;
-                    let loop_obj = asyncio.call_method0("get_running_loop").map_err(|_| {
-                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                        "No running event loop found. File handles require a running event loop. \
-                         Use 'async with' or ensure asyncio.run() has been called."
-                    )
-                    })?;
-
-                    let handle_clone = handle.clone_ref(py);
-                    let loop_clone = loop_obj.unbind();
-                    (
-                        FileSource::Handle {
-                            file: handle_clone.clone_ref(py),
-                            event_loop: loop_clone.clone_ref(py),
-                        },
-                        placeholder_path,
-                        Arc::new(StdMutex::new(Some(handle_clone))),
-                        Arc::new(StdMutex::new(Some(loop_clone))), // Always store the loop
-                    )
-                };
+            let (source, path_str, file_handle, event_loop) = resolve_source(py, path_or_handle)?;
+            if matches!(source, FileSource::Path(_))
+                && archive::writer_member_of(&path_str, None)?.is_some()
+            {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "AsyncDictWriter cannot write into a ZIP archive; use Writer(zip_member=...)",
+                ));
+            }
 
             let writer = Writer::new(
                 py,
@@ -2140,7 +3107,35 @@ impl AsyncDictWriter {
                 quoting,
                 lineterminator,
                 double_quote,
-                None, // write_size - use default
+                None,  // write_size - use default
+                None,  // queue_size - write directly
+                false, // lock - no file lock
+                None,  // progress - not reported
+                None,  // progress_interval
+                None,  // timeout - no limit
+                0,     // retries
+                None,  // retry_backoff
+                None,  // on_retry
+                None,  // headers - written by writeheader()
+                None,  // float_precision
+                None,  // true_value
+                None,  // false_value
+                None,  // none_value
+                None,  // float_format
+                None,  // date_format
+                None,  // datetime_format
+                dialect,
+                None, // zip_member - checked above
+                None, // checksum
+                None, // encryption_key
+                None, // max_bytes_per_sec
+                None, // max_rows
+                None, // max_bytes
+                None, // allowed_root
+                None, // file_mode
+                None, // file_group
+                sanitize_formulas,
+                None, // ssh_key
             )?;
             // Create separate file Arc for DictWriter (shares same file, but separate Arc)
             // Note: This means DictWriter and Writer don't share file state, which is acceptable
@@ -2152,10 +3147,12 @@ impl AsyncDictWriter {
                 file: file_arc,
                 file_handle,
                 event_loop,
-                dialect,
-                fieldnames,
+                dialect: config,
+                auto_header: fieldnames.is_none(),
+                fieldnames: Arc::new(StdMutex::new(fieldnames)),
                 extrasaction: extrasaction.to_lowercase(),
                 restval: restval.to_string(),
+                sanitize_formulas,
             })
         })
     }
@@ -2168,16 +3165,26 @@ impl AsyncDictWriter {
         let file_handle = Arc::clone(&self_.file_handle);
         let event_loop = Arc::clone(&self_.event_loop);
         let dialect = self_.dialect.clone();
-        let fieldnames = self_.fieldnames.clone();
+        if self_.auto_header {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "writeheader() requires fieldnames; with fieldnames=None the header is written with the first row",
+            ));
+        }
+        let fieldnames = self_
+            .fieldnames
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .unwrap_or_default();
         Python::attach(|py| {
             // Extract file handle and event loop BEFORE async block (while in Python context)
             let (handle_py_opt, loop_py_opt) = if !is_path {
                 let handle_guard = file_handle.lock().map_err(|_| {
                     PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Failed to lock file handle")
                 })?;
-                let handle = handle_guard.as_ref().ok_or_else(|| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>("File handle not available")
-                })?;
+                let handle = handle_guard
+                    .as_ref()
+                    .ok_or_else(|| CsvIoError::new_err("File handle not available"))?;
 
                 let loop_guard = event_loop.lock().map_err(|_| {
                     PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Failed to lock event loop")
@@ -2197,24 +3204,7 @@ impl AsyncDictWriter {
 
             let future = async move {
                 // Write fieldnames as CSV row
-                let mut writer_builder = WriterBuilder::new();
-                dialect.apply_to_writer(&mut writer_builder);
-                let mut writer = writer_builder.from_writer(Vec::new());
-                writer.write_record(&fieldnames).map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to write CSV record: {e}"
-                    ))
-                })?;
-                writer.flush().map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to flush CSV writer: {e}"
-                    ))
-                })?;
-                let csv_data = writer.into_inner().map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to finalize CSV record: {e}"
-                    ))
-                })?;
+                let csv_data = encode_rows(&dialect, std::slice::from_ref(&fieldnames))?;
 
                 if is_path {
                     // Get or open the file handle
@@ -2225,43 +3215,31 @@ impl AsyncDictWriter {
                             OpenOptions::new()
                                 .create(true)
                                 .append(true)
-                                .open(&path)
+                                .open(paths::native(&path))
                                 .await
                                 .map_err(|e| {
-                                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                        "Failed to open file {path}: {e}"
-                                    ))
+                                    CsvIoError::new_err(format!("Failed to open file {path}: {e}"))
                                 })?,
                         );
                     }
                     let file_ref = file_guard.as_mut().unwrap();
                     file_ref.write_all(&csv_data).await.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to write file {path}: {e}"
-                        ))
+                        CsvIoError::new_err(format!("Failed to write file {path}: {e}"))
                     })?;
                     file_ref.flush().await.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to flush file {path}: {e}"
-                        ))
+                        CsvIoError::new_err(format!("Failed to flush file {path}: {e}"))
                     })?;
                 } else {
-                    // Use Python file handle for Handle sources
-                    let csv_str = String::from_utf8(csv_data).map_err(|_| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>("Invalid UTF-8 in CSV data")
-                    })?;
-
                     // Use the pre-extracted handle and loop
-                    let handle_py = handle_py_for_async.ok_or_else(|| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>("File handle not available")
-                    })?;
+                    let handle_py = handle_py_for_async
+                        .ok_or_else(|| CsvIoError::new_err("File handle not available"))?;
                     let loop_py = loop_py_for_async.ok_or_else(|| {
                         PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
                             "Event loop not available",
                         )
                     })?;
 
-                    write_to_python_file(handle_py, loop_py, csv_str).await?;
+                    write_to_python_file(handle_py, loop_py, csv_data).await?;
                 }
 
                 Ok(())
@@ -2280,46 +3258,11 @@ impl AsyncDictWriter {
         let file_handle = Arc::clone(&self_.file_handle);
         let event_loop = Arc::clone(&self_.event_loop);
         let dialect = self_.dialect.clone();
-        let fieldnames = self_.fieldnames.clone();
-        let extrasaction = self_.extrasaction.clone();
-        let restval = self_.restval.clone();
 
         // Extract dict values in GIL context before async move
-        let row = Python::attach(|#[allow(unused_variables)] py| -> PyResult<Vec<String>> {
-            // Check for extra keys if extrasaction == "raise"
-            if extrasaction == "raise" {
-                let dict_keys: Vec<String> = dict_row
-                    .keys()
-                    .iter()
-                    .map(|k| k.extract::<String>().unwrap_or_default())
-                    .collect();
-                for key in &dict_keys {
-                    if !fieldnames.contains(key) {
-                        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                            "dict contains fields not in fieldnames: {key}"
-                        )));
-                    }
-                }
-            }
-
-            // Build Vec<String> ordered by fieldnames
-            let mut row = Vec::new();
-            for fieldname in &fieldnames {
-                match dict_row.get_item(fieldname) {
-                    Ok(Some(value)) => {
-                        let value_str = value
-                            .extract::<String>()
-                            .unwrap_or_else(|_| value.to_string());
-                        row.push(value_str);
-                    }
-                    Ok(None) | Err(_) => {
-                        // Missing key - use restval
-                        row.push(restval.clone());
-                    }
-                }
-            }
-            Ok(row)
-        })?;
+        let (header, row) = self_.dict_to_row(dict_row)?;
+        let fieldnames = header.is_some().then(|| Arc::clone(&self_.fieldnames));
+        let records: Vec<Vec<String>> = header.into_iter().chain([row]).collect();
 
         Python::attach(|py| {
             // Extract file handle and event loop BEFORE async block (while in Python context)
@@ -2327,9 +3270,9 @@ impl AsyncDictWriter {
                 let handle_guard = file_handle.lock().map_err(|_| {
                     PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Failed to lock file handle")
                 })?;
-                let handle = handle_guard.as_ref().ok_or_else(|| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>("File handle not available")
-                })?;
+                let handle = handle_guard
+                    .as_ref()
+                    .ok_or_else(|| CsvIoError::new_err("File handle not available"))?;
 
                 let loop_guard = event_loop.lock().map_err(|_| {
                     PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Failed to lock event loop")
@@ -2347,26 +3290,9 @@ impl AsyncDictWriter {
             let handle_py_for_async = handle_py_opt;
             let loop_py_for_async = loop_py_opt;
 
-            let future = async move {
-                // Write row with CSV formatting
-                let mut writer_builder = WriterBuilder::new();
-                dialect.apply_to_writer(&mut writer_builder);
-                let mut writer = writer_builder.from_writer(Vec::new());
-                writer.write_record(&row).map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to write CSV record: {e}"
-                    ))
-                })?;
-                writer.flush().map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to flush CSV writer: {e}"
-                    ))
-                })?;
-                let csv_data = writer.into_inner().map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to finalize CSV record: {e}"
-                    ))
-                })?;
+            let write = async move {
+                // Write row (and a header taken from it) with CSV formatting
+                let csv_data = encode_rows(&dialect, &records)?;
 
                 if is_path {
                     // Get or open the file handle
@@ -2377,47 +3303,42 @@ impl AsyncDictWriter {
                             OpenOptions::new()
                                 .create(true)
                                 .append(true)
-                                .open(&path)
+                                .open(paths::native(&path))
                                 .await
                                 .map_err(|e| {
-                                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                        "Failed to open file {path}: {e}"
-                                    ))
+                                    CsvIoError::new_err(format!("Failed to open file {path}: {e}"))
                                 })?,
                         );
                     }
                     let file_ref = file_guard.as_mut().unwrap();
                     file_ref.write_all(&csv_data).await.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to write file {path}: {e}"
-                        ))
+                        CsvIoError::new_err(format!("Failed to write file {path}: {e}"))
                     })?;
                     file_ref.flush().await.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to flush file {path}: {e}"
-                        ))
+                        CsvIoError::new_err(format!("Failed to flush file {path}: {e}"))
                     })?;
                 } else {
-                    // Use Python file handle for Handle sources
-                    let csv_str = String::from_utf8(csv_data).map_err(|_| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>("Invalid UTF-8 in CSV data")
-                    })?;
-
                     // Use the pre-extracted handle and loop
-                    let handle_py = handle_py_for_async.ok_or_else(|| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>("File handle not available")
-                    })?;
+                    let handle_py = handle_py_for_async
+                        .ok_or_else(|| CsvIoError::new_err("File handle not available"))?;
                     let loop_py = loop_py_for_async.ok_or_else(|| {
                         PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
                             "Event loop not available",
                         )
                     })?;
 
-                    write_to_python_file(handle_py, loop_py, csv_str).await?;
+                    write_to_python_file(handle_py, loop_py, csv_data).await?;
                 }
 
                 Ok(())
             };
+            let future = async move {
+                let result: PyResult<()> = write.await;
+                if let (Err(_), Some(fieldnames)) = (&result, fieldnames) {
+                    AsyncDictWriter::reset_fieldnames(&fieldnames);
+                }
+                result
+            };
             future_into_py(py, future).map(|bound| bound.unbind())
         })
     }
@@ -2427,129 +3348,80 @@ impl AsyncDictWriter {
         let path = self_.path.clone();
         let file = Arc::clone(&self_.file);
         let dialect = self_.dialect.clone();
-        let fieldnames = self_.fieldnames.clone();
-        let extrasaction = self_.extrasaction.clone();
-        let restval = self_.restval.clone();
+        let fieldnames = Arc::clone(&self_.fieldnames);
+        // Whether the first row supplies the fieldnames, and a header to write first
+        let fresh = fieldnames
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_none();
 
         // Convert all dicts to Vec<Vec<String>> in GIL context
-        let rows = Python::attach(
-            |#[allow(unused_variables)] py| -> PyResult<Vec<Vec<String>>> {
-                let mut rows = Vec::new();
-
-                // Try to get as PyList
-                #[allow(deprecated)] // TODO: migrate to Bound::cast when available
-                if let Ok(py_list) = dict_rows.downcast::<PyList>() {
-                    for item in py_list.iter() {
-                        #[allow(deprecated)] // TODO: migrate to Bound::cast when available
-                        if let Ok(dict) = item.downcast::<PyDict>() {
-                            // Check for extra keys if extrasaction == "raise"
-                            if extrasaction == "raise" {
-                                let dict_keys: Vec<String> = dict
-                                    .keys()
-                                    .iter()
-                                    .map(|k| k.extract::<String>().unwrap_or_default())
-                                    .collect();
-                                for key in &dict_keys {
-                                    if !fieldnames.contains(key) {
-                                        return Err(
-                                            PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                                                format!(
-                                                    "dict contains fields not in fieldnames: {key}"
-                                                ),
-                                            ),
-                                        );
-                                    }
-                                }
-                            }
-
-                            // Build Vec<String> ordered by fieldnames
-                            let mut row = Vec::new();
-                            for fieldname in &fieldnames {
-                                match dict.get_item(fieldname) {
-                                    Ok(Some(value)) => {
-                                        let value_str = value
-                                            .extract::<String>()
-                                            .unwrap_or_else(|_| value.to_string());
-                                        row.push(value_str);
-                                    }
-                                    Ok(None) | Err(_) => {
-                                        row.push(restval.clone());
-                                    }
-                                }
-                            }
-                            rows.push(row);
-                        } else {
-                            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-                                "writerows() requires a list of dictionaries",
-                            ));
-                        }
-                    }
-                } else {
-                    return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+        let list = dict_rows.cast::<PyList>().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "writerows() requires a list of dictionaries",
+            )
+        })?;
+        let mut rows = Vec::with_capacity(list.len() + 1);
+        for item in list.iter() {
+            let converted = item
+                .cast_into::<PyDict>()
+                .map_err(|_| {
+                    PyErr::new::<pyo3::exceptions::PyTypeError, _>(
                         "writerows() requires a list of dictionaries",
-                    ));
+                    )
+                })
+                .and_then(|dict| self_.dict_to_row(&dict));
+            let (header, row) = converted.inspect_err(|_| {
+                if fresh {
+                    AsyncDictWriter::reset_fieldnames(&fieldnames);
                 }
-
-                Ok(rows)
-            },
-        )?;
+            })?;
+            rows.extend(header);
+            rows.push(row);
+        }
 
         Python::attach(|#[allow(unused_variables)] py| {
             let future = async move {
-                // Get or open the file handle
-                let mut file_guard = file.lock().await;
-                if file_guard.is_none() {
-                    use tokio::fs::OpenOptions;
-                    *file_guard = Some(
-                        OpenOptions::new()
-                            .create(true)
-                            .append(true)
-                            .open(&path)
-                            .await
-                            .map_err(|e| {
-                                PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                    "Failed to open file {path}: {e}"
-                                ))
-                            })?,
-                    );
-                }
-                let file_ref = file_guard.as_mut().unwrap();
-
-                // Write all rows
-                for row in &rows {
-                    let mut writer_builder = WriterBuilder::new();
-                    dialect.apply_to_writer(&mut writer_builder);
-                    let mut writer = writer_builder.from_writer(Vec::new());
-                    writer.write_record(row).map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to write CSV record: {e}"
-                        ))
-                    })?;
-                    writer.flush().map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to flush CSV writer: {e}"
-                        ))
-                    })?;
-                    let csv_data = writer.into_inner().map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to finalize CSV record: {e}"
-                        ))
-                    })?;
-                    file_ref.write_all(&csv_data).await.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to write file {path}: {e}"
-                        ))
-                    })?;
-                }
+                let mut header_written = !fresh;
+                let result: PyResult<()> = async {
+                    // Get or open the file handle
+                    let mut file_guard = file.lock().await;
+                    if file_guard.is_none() {
+                        use tokio::fs::OpenOptions;
+                        *file_guard = Some(
+                            OpenOptions::new()
+                                .create(true)
+                                .append(true)
+                                .open(paths::native(&path))
+                                .await
+                                .map_err(|e| {
+                                    CsvIoError::new_err(format!("Failed to open file {path}: {e}"))
+                                })?,
+                        );
+                    }
+                    let file_ref = file_guard.as_mut().unwrap();
 
-                // Flush to ensure all data is written
-                file_ref.flush().await.map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to flush file {path}: {e}"
-                    ))
-                })?;
+                    // Write all rows
+                    for row in &rows {
+                        let csv_data = encode_rows(&dialect, std::slice::from_ref(row))?;
+                        file_ref.write_all(&csv_data).await.map_err(|e| {
+                            CsvIoError::new_err(format!("Failed to write file {path}: {e}"))
+                        })?;
+                        header_written = true;
+                    }
 
-                Ok(())
+                    // Flush to ensure all data is written
+                    file_ref.flush().await.map_err(|e| {
+                        CsvIoError::new_err(format!("Failed to flush file {path}: {e}"))
+                    })?;
+
+                    Ok(())
+                }
+                .await;
+                if result.is_err() && !header_written {
+                    AsyncDictWriter::reset_fieldnames(&fieldnames);
+                }
+                result
             };
             future_into_py(py, future).map(|bound| bound.unbind())
         })
@@ -2566,9 +3438,7 @@ impl AsyncDictWriter {
                     let mut file_guard = file.lock().await;
                     if let Some(mut f) = file_guard.take() {
                         f.flush().await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                "Failed to flush file during close: {e}"
-                            ))
+                            CsvIoError::new_err(format!("Failed to flush file during close: {e}"))
                         })?;
                     }
                 }
@@ -2606,16 +3476,98 @@ impl AsyncDictWriter {
 ///     await writer.write_row(["name", "age"])
 ///     # File is automatically closed and flushed on exit
 /// ```
-#[pyclass]
-struct Writer {
-    source: FileSource,             // Either Path(String) or Handle {file, event_loop}
-    path: String,                   // Keep for backward compatibility and error messages
-    file: Arc<Mutex<Option<File>>>, // Only used when source is Path
-    file_handle: Arc<StdMutex<Option<Py<PyAny>>>>, // Python file handle when source is Handle
-    event_loop: Arc<StdMutex<Option<Py<PyAny>>>>, // Event loop reference for run_coroutine_threadsafe
+#[pyclass(subclass, module = "rapcsv")]
+struct Writer {
+    output: BufferedOutput, // Destination file or handle plus rows not yet written to it
+    queue: Option<WriteQueue>, // Background writer task, when queue_size is set
+    turnstile: Turnstile,   // Applies writes in the order the calls were made
+    progress: Option<Arc<Progress>>,
+    stats: Arc<Stats>,
+    timeout: Option<Duration>, // Limit on each write, flush and close
     dialect: DialectConfig,
-    #[allow(dead_code)] // Will be used for buffering write operations in future
-    write_size: usize, // Configurable buffer size for writing
+    header: Arc<Mutex<Option<Vec<String>>>>, // `headers` row not yet written or skipped
+    values: ValueFormat,                     // Serialization of non-string fields
+    quota: Option<Arc<Quota>>,               // `max_rows`/`max_bytes` limits
+    options: Pickled,                        // Constructor arguments, for pickling
+}
+
+/// Take the `headers` row if it is still pending and the destination is empty. Called
+/// in turn, so the first write or close decides before any row reaches the file.
+async fn pending_header(
+    header: &Mutex<Option<Vec<String>>>,
+    output: &BufferedOutput,
+) -> PyResult<Option<Vec<String>>> {
+    let mut header = header.lock().await;
+    if header.is_none() || output.has_content().await? {
+        header.take();
+        return Ok(None);
+    }
+    Ok(header.take())
+}
+
+/// Pull up to `size` rows from an async iterator on `event_loop`, converted to fields.
+/// Also returns whether the iterator is exhausted.
+async fn next_batch(
+    iterator: Arc<Py<PyAny>>,
+    event_loop: Arc<Py<PyAny>>,
+    values: ValueFormat,
+    size: usize,
+) -> PyResult<(Vec<Vec<String>>, bool)> {
+    tokio::task::spawn_blocking(move || {
+        Python::attach(|py| -> PyResult<(Vec<Vec<String>>, bool)> {
+            let helper = py.import("rapcsv")?.getattr("_next_batch_threadsafe")?;
+            let (batch, exhausted): (Bound<'_, PyAny>, bool) = helper
+                .call1((iterator.bind(py), event_loop.bind(py), size))?
+                .extract()?;
+            Ok((values.rows(&batch)?, exhausted))
+        })
+    })
+    .await
+    .map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+            "Failed to await Python coroutine: {e}"
+        ))
+    })?
+}
+
+/// Encode `rows` with `dialect`, line terminators included.
+fn encode_rows(dialect: &DialectConfig, rows: &[Vec<String>]) -> PyResult<Vec<u8>> {
+    if matches!(dialect.quoting, QuoteStyle::Never) {
+        return escape::encode_rows(dialect, rows);
+    }
+    let mut writer_builder = WriterBuilder::new();
+    dialect.apply_to_writer(&mut writer_builder);
+    let mut writer = writer_builder.from_writer(Vec::new());
+    for row in rows {
+        writer
+            .write_record(row)
+            .map_err(|e| CsvIoError::new_err(format!("Failed to write CSV record: {e}")))?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to finalize CSV record: {e}")))
+}
+
+/// Records in CSV bytes written as they are, counted by the terminators outside quotes
+/// (and a last record without one) for stats, progress and quotas.
+fn count_records(data: &[u8], dialect: &DialectConfig) -> usize {
+    let terminator = index::terminator(dialect);
+    let quote = dialect.read_quote();
+    let mut in_quotes = false;
+    let mut escaped = false; // The previous byte escapes this one (QUOTE_NONE)
+    let mut records = 0;
+    for &b in data {
+        if escaped {
+            escaped = false;
+        } else if quote.is_none() && Some(b) == dialect.escapechar {
+            escaped = true;
+        } else if Some(b) == quote {
+            in_quotes = !in_quotes;
+        } else if b == terminator && !in_quotes {
+            records += 1;
+        }
+    }
+    records + usize::from(data.last().is_some_and(|&b| b != terminator))
 }
 
 #[pymethods]
@@ -2623,14 +3575,69 @@ impl Writer {
     /// Create a new CSV file for writing.
     ///
     /// # Arguments
-    /// * `path` - Path to the CSV file
+    /// * `path` - Path to the CSV file, or an `sftp://user@host[:port]/path` URL
+    ///   (`sftp` cargo feature)
     /// * `delimiter` - Field delimiter (default: ',')
     /// * `quotechar` - Quote character (default: '"')
     /// * `escapechar` - Escape character (default: None)
     /// * `quoting` - Quoting style: 0=QUOTE_NONE, 1=QUOTE_MINIMAL, 2=QUOTE_ALL, 3=QUOTE_NONNUMERIC, 4=QUOTE_NOTNULL, 6=QUOTE_STRINGS
+    ///   (with 0, fields are never quoted and `escapechar` escapes delimiters, quotes
+    ///   and line breaks in them; without one, quotes are written as they are)
     /// * `lineterminator` - Line terminator (default: '\r\n')
     /// * `double_quote` - Handle doubled quotes (default: true)
-    /// * `write_size` - Buffer size for writing chunks (default: 8192)
+    /// * `write_size` - Bytes of encoded rows buffered before they are written to the
+    ///   file; buffered rows are also written by `flush()` and `close()`, and 0 writes
+    ///   each call's rows through (default: None, 64 KiB for a file path and 0 for a
+    ///   file handle, descriptor or socket)
+    /// * `queue_size` - Hand rows to a background writer task through a queue of this
+    ///   many writes; `write_row()` only waits when the queue is full (default: None,
+    ///   rows are written by the calling task)
+    /// * `lock` - Hold an exclusive advisory lock (flock/LockFileEx) on the file from the
+    ///   first write until `close()`; only for file paths (default: false)
+    /// * `progress` - Called as `progress(bytes_written, None, rows_written)` every
+    ///   `progress_interval` rows and on `close()` (default: None)
+    /// * `progress_interval` - Rows between progress calls (default: 10000)
+    /// * `timeout` - Seconds each write, flush or close may take before raising
+    ///   `asyncio.TimeoutError` (default: None, no limit)
+    /// * `retries` - Times to retry opening the file after a transient IO error; writes
+    ///   are not retried since a failed write may be partly applied (default: 0)
+    /// * `retry_backoff` - Seconds before the first retry, doubling after each (default: 0.1)
+    /// * `on_retry` - Called as `on_retry(attempt, error, delay)` before each retry
+    ///   (default: None)
+    /// * `headers` - Header row written before the first row, unless the file already
+    ///   has content, so appending to an existing file never repeats it (default: None)
+    /// * `float_precision` - Digits after the decimal point for float fields (default:
+    ///   None, the shortest form that reads back to the same float)
+    /// * `true_value` / `false_value` - Fields written for bools (default: "True"/"False")
+    /// * `none_value` - Field written for None (default: "")
+    /// * `float_format` - printf-style conversion for float fields, e.g. "%.4f"; not
+    ///   combined with `float_precision` (default: None)
+    /// * `date_format` / `datetime_format` - strftime-style patterns for `date` and
+    ///   `datetime` fields (default: None, `str()`)
+    /// * `zip_member` - Write the CSV as this member of the ZIP archive at the path,
+    ///   compressed as it is written and added to the archive if it exists; also
+    ///   written as "archive.zip::member.csv". A ".zip" path alone writes a member
+    ///   named after it (default: None)
+    /// * `checksum` - "sha256" or "sha512": on `close()`, hash the file and write the
+    ///   digest to "<path>.sha256" (or ".sha512"), as `sha256sum` does (default: None)
+    /// * `encryption_key` - 32-byte AES-256 key: rows are encrypted (AES-256-GCM) before
+    ///   they reach the file, which must be new or empty (default: None)
+    /// * `max_bytes_per_sec` - Write no faster than this many bytes per second on
+    ///   average, pausing between writes as needed (default: None, no limit)
+    /// * `max_rows` / `max_bytes` - Limits on the rows and bytes this writer writes; a
+    ///   call that would cross one writes none of its rows and raises `QuotaExceeded`
+    ///   (default: None, no limit)
+    /// * `allowed_root` - Directory the path must resolve inside, following `..` and
+    ///   symlinks; on top of `set_allowed_root()` (default: None)
+    /// * `file_mode` - Permission bits, e.g. 0o640, for the file if the writer creates
+    ///   it, regardless of the umask; an existing file keeps its own (default: None)
+    /// * `file_group` - Group name or gid for the file if the writer creates it
+    ///   (default: None)
+    /// * `sanitize_formulas` - Prefix str and bytes fields starting with `=`, `+`, `-`,
+    ///   `@`, tab or carriage return with `'`, so spreadsheets don't run them as
+    ///   formulas (default: false)
+    /// * `ssh_key` - Private key file to log in to the server of an `sftp://` URL with
+    ///   (default: None, the SSH agent's keys and then `~/.ssh/id_*`)
     #[new]
     #[pyo3(signature = (
         path_or_handle,
@@ -2640,7 +3647,35 @@ impl Writer {
         quoting = None,
         lineterminator = None,
         double_quote = None,
-        write_size = None
+        write_size = None,
+        queue_size = None,
+        lock = false,
+        progress = None,
+        progress_interval = None,
+        timeout = None,
+        retries = 0,
+        retry_backoff = None,
+        on_retry = None,
+        headers = None,
+        float_precision = None,
+        true_value = None,
+        false_value = None,
+        none_value = None,
+        float_format = None,
+        date_format = None,
+        datetime_format = None,
+        dialect = None,
+        zip_member = None,
+        checksum = None,
+        encryption_key = None,
+        max_bytes_per_sec = None,
+        max_rows = None,
+        max_bytes = None,
+        allowed_root = None,
+        file_mode = None,
+        file_group = None,
+        sanitize_formulas = false,
+        ssh_key = None
     ))]
     #[allow(clippy::too_many_arguments)] // Required for Python API compatibility
     fn new(
@@ -2653,47 +3688,88 @@ impl Writer {
         lineterminator: Option<&str>,
         double_quote: Option<bool>,
         write_size: Option<usize>,
+        queue_size: Option<usize>,
+        lock: bool,
+        progress: Option<Py<PyAny>>,
+        progress_interval: Option<usize>,
+        timeout: Option<f64>,
+        retries: u32,
+        retry_backoff: Option<f64>,
+        on_retry: Option<Py<PyAny>>,
+        headers: Option<Vec<String>>,
+        float_precision: Option<usize>,
+        true_value: Option<String>,
+        false_value: Option<String>,
+        none_value: Option<String>,
+        float_format: Option<&str>,
+        date_format: Option<&str>,
+        datetime_format: Option<&str>,
+        dialect: Option<&Bound<'_, PyAny>>,
+        zip_member: Option<&str>,
+        checksum: Option<&str>,
+        encryption_key: Option<&[u8]>,
+        max_bytes_per_sec: Option<f64>,
+        max_rows: Option<usize>,
+        max_bytes: Option<u64>,
+        allowed_root: Option<&Bound<'_, PyAny>>,
+        file_mode: Option<u32>,
+        file_group: Option<&Bound<'_, PyAny>>,
+        sanitize_formulas: bool,
+        ssh_key: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Self> {
-        // Try to extract as string first (file path)
-        let (source, path, file_handle, event_loop) =
-            if let Ok(path_str) = path_or_handle.extract::<String>() {
-                validate_path(&path_str)?;
-                (
-                    FileSource::Path(path_str.clone()),
-                    path_str,
-                    Arc::new(StdMutex::new(None)),
-                    Arc::new(StdMutex::new(None)),
-                )
-            } else {
-                // Assume it's a file-like object
-                let handle = path_or_handle.clone().unbind();
-                // For file handles, use a placeholder path for error messages
-                let placeholder_path = "<file_handle>".to_string();
-
-                // Get the running event loop (required for aiofiles/rapfiles handles)
-                // Must be available during construction since we're in Python's context
-                let asyncio = py.import("asyncio")?;
-                let loop_obj = asyncio.call_method0("get_running_loop").map_err(|_| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                        "No running event loop found. File handles require a running event loop. \
-                     Use 'async with' or ensure asyncio.run() has been called.",
-                    )
-                })?;
-
-                let handle_clone = handle.clone_ref(py);
-                let loop_clone = loop_obj.unbind();
-                (
-                    FileSource::Handle {
-                        file: handle_clone.clone_ref(py),
-                        event_loop: loop_clone.clone_ref(py),
-                    },
-                    placeholder_path,
-                    Arc::new(StdMutex::new(Some(handle_clone))),
-                    Arc::new(StdMutex::new(Some(loop_clone))), // Always store the loop
-                )
-            };
+        let options = keyword_args!(
+            py;
+            path_or_handle,
+            delimiter,
+            quotechar,
+            escapechar,
+            quoting,
+            lineterminator,
+            double_quote,
+            write_size,
+            queue_size,
+            lock,
+            progress,
+            progress_interval,
+            timeout,
+            retries,
+            retry_backoff,
+            on_retry,
+            headers,
+            float_precision,
+            true_value,
+            false_value,
+            none_value,
+            float_format,
+            date_format,
+            datetime_format,
+            dialect,
+            zip_member,
+            checksum,
+            max_bytes_per_sec,
+            max_rows,
+            max_bytes,
+            allowed_root,
+            file_mode,
+            file_group,
+            sanitize_formulas,
+            ssh_key,
+        );
+        let options = match encryption_key {
+            Some(_) => Err("with encryption_key, which is kept out of pickles"),
+            None => Ok(options),
+        };
+        let ((source, path, file_handle, event_loop), remote) =
+            resolve_remote_source(py, path_or_handle)?;
+        let ssh_key = ssh_key_arg(ssh_key, remote.is_some())?;
+        if let Some(root) = allowed_root.map(sandbox::root_arg).transpose()? {
+            if matches!(source, FileSource::Path(_)) {
+                sandbox::check(&path, Some(&root))?;
+            }
+        }
 
         let dialect = DialectConfig::from_python(
+            dialect,
             delimiter,
             quotechar,
             escapechar,
@@ -2703,283 +3779,344 @@ impl Writer {
             None, // strict not used for writer
             double_quote,
         )?;
-        Ok(Writer {
-            source,
-            path,
-            file: Arc::new(Mutex::new(None)),
+        if queue_size == Some(0) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "queue_size must be at least 1",
+            ));
+        }
+        let values = ValueFormat::new(float_precision, true_value, false_value, none_value)
+            .float_format(float_format)?
+            .date_formats(date_format, datetime_format)?
+            .sanitize_formulas(sanitize_formulas);
+        let access = source.access();
+        let is_path = access == Access::Path;
+        if lock && !is_path {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "lock requires a file path, not a file handle",
+            ));
+        }
+        if remote.is_some()
+            && (lock
+                || zip_member.is_some()
+                || checksum.is_some()
+                || encryption_key.is_some()
+                || file_mode.is_some()
+                || file_group.is_some())
+        {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "lock, zip_member, checksum, encryption_key, file_mode and file_group cannot \
+                 be used with an sftp:// URL",
+            ));
+        }
+        let member = match source {
+            FileSource::Path(_) if remote.is_none() => {
+                archive::writer_member_of(&path, zip_member)?
+            }
+            _ if zip_member.is_some() => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "zip_member requires a file path, not a file handle",
+                ))
+            }
+            _ => None,
+        };
+        let key = encryption_key.map(crypt::Key::from_bytes).transpose()?;
+        if key.is_some() && (!is_path || member.is_some()) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "encryption_key requires a file path, not a file handle or archive member",
+            ));
+        }
+        if lock && (member.is_some() || key.is_some()) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "lock cannot be used with an archive member or encrypted file",
+            ));
+        }
+        let checksum = checksum.map(checksum::Algorithm::parse).transpose()?;
+        if checksum.is_some() && (!is_path || member.is_some()) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "checksum requires a file path, not a file handle or archive member",
+            ));
+        }
+        let permissions = FilePermissions::from_args(py, file_mode, file_group)?;
+        if permissions.is_some() && !is_path {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "file_mode and file_group require a file path, not a file handle",
+            ));
+        }
+        let mut output = BufferedOutput::new(
+            access,
+            path.clone(),
             file_handle,
             event_loop,
+            write_size.unwrap_or(if is_path { DEFAULT_WRITE_SIZE } else { 0 }),
+        );
+        if lock {
+            output = output.exclusive_lock();
+        }
+        if let Some(algorithm) = checksum {
+            output = output.checksum(algorithm);
+        }
+        if let Some(policy) = RetryPolicy::from_args(py, retries, retry_backoff, on_retry)? {
+            output = output.retry(policy);
+        }
+        if let Some(throttle) = Throttle::parse(max_bytes_per_sec)? {
+            output = output.throttle(throttle);
+        }
+        if let Some(permissions) = permissions {
+            // Archives and encrypted files are created here rather than on first write
+            if member.is_some() || key.is_some() {
+                permissions.create(member.as_ref().map_or(&path, |(archive, _)| archive))?;
+            }
+            output = output.permissions(permissions);
+        }
+        if let Some((archive, member)) = member {
+            let (file, member) = archive::create_member(&archive, &member)?;
+            output = output.piped(file, member)?;
+        }
+        if let Some(key) = key {
+            let (file, sink) = crypt::create_encrypted(&path, key)?;
+            output = output.piped(file, sink)?;
+        }
+        if let FileSource::Descriptor(descriptor) = &source {
+            output.attach_file(descriptor.get().take()?)?;
+        }
+        let timeout = timeout::parse(timeout)?;
+        if let Some(url) = remote {
+            let (file, sink) = sftp::create(url, ssh_key, timeout)?;
+            output = output.piped(file, sink)?;
+        }
+        Ok(Writer {
+            queue: queue_size.map(|capacity| WriteQueue::new(output.clone(), capacity)),
+            output,
+            turnstile: Turnstile::default(),
+            progress: Progress::from_args(py, progress, progress_interval, None)?.map(Arc::new),
+            stats: Arc::new(Stats::new()),
+            timeout,
             dialect,
-            write_size: write_size.unwrap_or(8192),
+            header: Arc::new(Mutex::new(headers)),
+            values,
+            quota: Quota::from_args(max_rows, max_bytes).map(Arc::new),
+            options: match source {
+                FileSource::Path(_) => options,
+                FileSource::Handle { .. } => Err("over a file handle or adapter"),
+                FileSource::Descriptor(_) => Err("over a file descriptor or socket"),
+            },
         })
     }
 
-    /// Write a row to the CSV file.
-    fn write_row(self_: PyRef<Self>, row: Vec<String>) -> PyResult<Py<PyAny>> {
-        let path = self_.path.clone();
-        let is_path = matches!(self_.source, FileSource::Path(_));
-        let file = Arc::clone(&self_.file);
-        let file_handle = Arc::clone(&self_.file_handle);
-        let event_loop = Arc::clone(&self_.event_loop);
-        let dialect = self_.dialect.clone();
-        Python::attach(|py| {
-            let future = async move {
-                // Proper CSV writing with escaping and quoting (RFC 4180 compliant)
-                let mut writer_builder = WriterBuilder::new();
-                dialect.apply_to_writer(&mut writer_builder);
-                let mut writer = writer_builder.from_writer(Vec::new());
-                writer.write_record(&row).map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to write CSV record: {e}"
-                    ))
-                })?;
-                writer.flush().map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to flush CSV writer: {e}"
-                    ))
-                })?;
-                let csv_data = writer.into_inner().map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to finalize CSV record: {e}"
-                    ))
-                })?;
+    /// Write to an open file descriptor, such as a pipe or a descriptor passed in by
+    /// another process. The descriptor is duplicated, so the caller still owns `fd`.
+    /// Takes the constructor's keyword arguments except `lock`, `checksum`,
+    /// `encryption_key`, `allowed_root`, `file_mode`, `file_group` and `ssh_key`.
+    #[classmethod]
+    #[pyo3(signature = (fd, **kwargs))]
+    fn from_fd(
+        cls: &Bound<'_, PyType>,
+        fd: i32,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<Self>> {
+        reject_fd_options(kwargs, WRITER_PATH_OPTIONS, "from_fd()")?;
+        let descriptor = Descriptor::new(file_from_fd(cls.py(), fd)?, format!("<fd {fd}>"));
+        Ok(cls
+            .call((descriptor,), kwargs)?
+            .cast_into::<Writer>()?
+            .unbind())
+    }
 
-                if is_path {
-                    // Use Tokio File for path-based sources
-                    let mut file_guard = file.lock().await;
-                    if file_guard.is_none() {
-                        use tokio::fs::OpenOptions;
-                        // Append mode - creates file if it doesn't exist
-                        *file_guard = Some(
-                            OpenOptions::new()
-                                .create(true)
-                                .append(true)
-                                .open(&path)
-                                .await
-                                .map_err(|e| {
-                                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                        "Failed to open file {path}: {e}"
-                                    ))
-                                })?,
-                        );
-                    }
-                    let file_ref = file_guard.as_mut().unwrap();
+    /// Connect to a TCP server and send it the CSV records written; `close()` closes
+    /// the connection. Takes the constructor's keyword arguments except those of
+    /// `from_fd()`. The connection is made by the first write, which raises
+    /// ConnectionError if it can't be.
+    ///
+    /// # Arguments
+    /// * `host` - Host name or IP address to connect to
+    /// * `port` - TCP port
+    /// * `connect_timeout` - Seconds to wait for the connection (default: None, the
+    ///   operating system's limit)
+    #[classmethod]
+    #[pyo3(signature = (host, port, connect_timeout = None, **kwargs))]
+    fn to_socket(
+        cls: &Bound<'_, PyType>,
+        host: &str,
+        port: u16,
+        connect_timeout: Option<f64>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<Self>> {
+        reject_fd_options(kwargs, WRITER_PATH_OPTIONS, "to_socket()")?;
+        let conn = Conn::connect(host, port, connect_timeout)?;
+        let descriptor = Descriptor::new(conn, format!("<tcp {host}:{port}>"));
+        Ok(cls
+            .call((descriptor,), kwargs)?
+            .cast_into::<Writer>()?
+            .unbind())
+    }
 
-                    file_ref.write_all(&csv_data).await.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to write file {path}: {e}"
-                        ))
-                    })?;
+    /// Build the writer again, in another process, from its constructor arguments. The
+    /// copy opens the file itself; rows buffered in this writer stay here.
+    fn __getnewargs_ex__(&self, py: Python<'_>) -> PyResult<(Py<PyTuple>, Py<PyDict>)> {
+        pickled_args(py, &self.options, "Writer")
+    }
 
-                    // Flush to ensure data is written
-                    file_ref.flush().await.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to flush file {path}: {e}"
-                        ))
-                    })?;
-                } else {
-                    // Use Python file handle for Handle sources
-                    let csv_str = String::from_utf8(csv_data).map_err(|_| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>("Invalid UTF-8 in CSV data")
-                    })?;
+    /// Writers pickle no state besides their constructor arguments.
+    fn __getstate__(&self) -> Option<()> {
+        None
+    }
 
-                    // Extract handle and event loop together (stored during construction)
-                    let file_handle_clone = file_handle.clone();
-                    let event_loop_clone = event_loop.clone();
-                    let (handle_py, loop_py) = tokio::task::spawn_blocking(move || {
-                        #[allow(deprecated)] // Python::with_gil is still required in blocking contexts (spawn_blocking)
-                        Python::with_gil(|py| -> PyResult<(Py<PyAny>, Py<PyAny>)> {
-                            let handle_guard = file_handle_clone.lock().map_err(|_| {
-                                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                    "Failed to lock file handle"
-                                )
-                            })?;
-                            let handle = handle_guard.as_ref().ok_or_else(|| {
-                                PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                    "File handle not available"
-                                )
-                            })?;
-
-                            let loop_guard = event_loop_clone.lock().map_err(|_| {
-                                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                    "Failed to lock event loop"
-                                )
-                            })?;
-                            let loop_ref = loop_guard.as_ref().ok_or_else(|| {
-                                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                    "Event loop not available. This should not happen - file handles require an event loop during construction."
-                                )
-                            })?;
-
-                            Ok((handle.clone_ref(py), loop_ref.clone_ref(py)))
-                        })
-                    })
-                    .await
-                    .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Failed to extract file handle/event loop: {e}"
-                    )))??;
+    /// Write a row to the CSV file.
+    ///
+    /// Fields may be any Python values: strings are written as they are, and ints,
+    /// floats, bools and None are serialized as set by the constructor options.
+    fn write_row(self_: PyRef<Self>, row: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let row = self_.values.row(row)?;
+        self_.submit_rows(vec![row])
+    }
 
-                    write_to_python_file(handle_py, loop_py, csv_str).await?;
-                }
+    /// Write multiple rows to the CSV file.
+    fn writerows(self_: PyRef<Self>, rows: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let rows = self_.values.rows(rows)?;
+        self_.submit_rows(rows)
+    }
 
-                Ok(())
-            };
-            future_into_py(py, future).map(|bound| bound.unbind())
-        })
+    /// Write columns as rows, so column-oriented data needn't be transposed first.
+    ///
+    /// Takes a dict of columns, written in its order without the keys (pass `headers`
+    /// to the constructor for a header row), or a sequence of columns. The columns must
+    /// all have the same length; their values are serialized as for `write_row()`.
+    fn write_columns(&self, columns: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let rows = self.values.columns(columns)?;
+        self.submit_rows(rows)
     }
 
-    /// Write multiple rows to the CSV file.
-    fn writerows(self_: PyRef<Self>, rows: Vec<Vec<String>>) -> PyResult<Py<PyAny>> {
-        let path = self_.path.clone();
-        let is_path = matches!(self_.source, FileSource::Path(_));
-        let file = Arc::clone(&self_.file);
-        let file_handle = Arc::clone(&self_.file_handle);
-        let event_loop = Arc::clone(&self_.event_loop);
-        let dialect = self_.dialect.clone();
+    /// Write CSV bytes as they are, without parsing or re-encoding them.
+    ///
+    /// The bytes go through the writer's buffering, compression and output like
+    /// encoded rows, after the header if it is still pending. They should hold whole
+    /// records in the writer's dialect, each ending with its line terminator.
+    ///
+    /// # Arguments
+    /// * `data` - Encoded records, such as those from `Reader.read_raw_lines()`
+    fn write_raw(&self, data: &[u8]) -> PyResult<Py<PyAny>> {
+        let data = data.to_vec();
+        let rows = count_records(&data, &self.dialect);
+        let sink = self.sink();
+        let stats = self.stats.clone();
+        let timeout = self.timeout;
+        let ticket = self.turnstile.ticket();
         Python::attach(|py| {
             let future = async move {
-                // Write all rows
-                for row in &rows {
-                    let mut writer_builder = WriterBuilder::new();
-                    dialect.apply_to_writer(&mut writer_builder);
-                    let mut writer = writer_builder.from_writer(Vec::new());
-                    writer.write_record(row).map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to write CSV record: {e}"
-                        ))
-                    })?;
-                    writer.flush().map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to flush CSV writer: {e}"
-                        ))
-                    })?;
-                    let csv_data = writer.into_inner().map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to finalize CSV record: {e}"
-                        ))
-                    })?;
-
-                    if is_path {
-                        // Use Tokio File for path-based sources
-                        let mut file_guard = file.lock().await;
-                        if file_guard.is_none() {
-                            use tokio::fs::OpenOptions;
-                            *file_guard = Some(
-                                OpenOptions::new()
-                                    .create(true)
-                                    .append(true)
-                                    .open(&path)
-                                    .await
-                                    .map_err(|e| {
-                                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                            "Failed to open file {path}: {e}"
-                                        ))
-                                    })?,
-                            );
-                        }
-                        let file_ref = file_guard.as_mut().unwrap();
-                        file_ref.write_all(&csv_data).await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                "Failed to write file {path}: {e}"
-                            ))
-                        })?;
-                    } else {
-                        // Use Python file handle for Handle sources
-                        let csv_str = String::from_utf8(csv_data).map_err(|_| {
-                            PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                "Invalid UTF-8 in CSV data",
-                            )
-                        })?;
+                stats
+                    .track(timeout::limit(timeout, async {
+                        ticket.wait().await;
+                        sink.write(data, rows).await
+                    }))
+                    .await
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
 
-                        // Extract both file handle and event loop in a single spawn_blocking
-                        // This ensures we get them before moving into async context
-                        let file_handle_clone = file_handle.clone();
-                        let event_loop_clone = event_loop.clone();
-                        let (handle_py, loop_py) = tokio::task::spawn_blocking(move || {
-                            #[allow(deprecated)]
-                            // Python::with_gil is still required in blocking contexts (spawn_blocking)
-                            Python::with_gil(|py| -> PyResult<(Py<PyAny>, Py<PyAny>)> {
-                                // Extract file handle
-                                let handle_guard = file_handle_clone.lock().map_err(|_| {
-                                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                        "Failed to lock file handle",
-                                    )
-                                })?;
-                                let handle = handle_guard.as_ref().ok_or_else(|| {
-                                    PyErr::new::<pyo3::exceptions::PyIOError, _>(
-                                        "File handle not available",
-                                    )
-                                })?;
-
-                                // Extract event loop
-                                let loop_guard = event_loop_clone.lock().map_err(|_| {
-                                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                        "Failed to lock event loop",
-                                    )
-                                })?;
-                                let loop_obj = loop_guard.as_ref().ok_or_else(|| {
-                                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                                        "Event loop not available",
-                                    )
-                                })?;
-
-                                Ok((handle.clone_ref(py), loop_obj.clone_ref(py)))
-                            })
-                        })
-                        .await
-                        .map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Failed to extract file handle or event loop: {e}"
-                            ))
-                        })??;
-
-                        write_to_python_file(handle_py, loop_py, csv_str).await?;
-                    }
+    /// Write every row of an async iterable, such as an async generator, in one call.
+    ///
+    /// Rows are pulled from the iterable `batch_size` at a time on the event loop and
+    /// written batch by batch, and the output is flushed once the iterable is
+    /// exhausted. Other writes made while it runs wait for it to finish. Returns the
+    /// number of rows written. Requires asyncio.
+    ///
+    /// # Arguments
+    /// * `rows` - Async iterable of rows
+    /// * `batch_size` - Rows pulled from the iterable and written at a time (default: 1000)
+    #[pyo3(signature = (rows, batch_size = 1000))]
+    fn write_from(
+        self_: PyRef<Self>,
+        rows: &Bound<'_, PyAny>,
+        batch_size: usize,
+    ) -> PyResult<Py<PyAny>> {
+        let py = rows.py();
+        if batch_size == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "batch_size must be at least 1",
+            ));
+        }
+        if !rows.hasattr("__aiter__")? {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "write_from() requires an async iterable; use writerows() for other iterables",
+            ));
+        }
+        let event_loop = py
+            .import("asyncio")?
+            .call_method0("get_running_loop")
+            .map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                    "write_from() requires a running asyncio event loop",
+                )
+            })?
+            .unbind();
+        let iterator = Arc::new(rows.call_method0("__aiter__")?.unbind());
+        let event_loop = Arc::new(event_loop);
+        let values = self_.values.clone();
+        let sink = self_.sink();
+        let stats = self_.stats.clone();
+        let timeout = self_.timeout;
+        let ticket = self_.turnstile.ticket();
+        let future = async move {
+            ticket.wait().await;
+            let mut written = 0usize;
+            loop {
+                let (rows, exhausted) = next_batch(
+                    iterator.clone(),
+                    event_loop.clone(),
+                    values.clone(),
+                    batch_size,
+                )
+                .await?;
+                if !rows.is_empty() {
+                    let csv_data = encode_rows(&sink.dialect, &rows)?;
+                    stats
+                        .track(timeout::limit(timeout, sink.write(csv_data, rows.len())))
+                        .await?;
+                    written += rows.len();
                 }
-
-                // Flush to ensure all data is written (only for path-based sources)
-                if is_path {
-                    let mut file_guard = file.lock().await;
-                    if let Some(file_ref) = file_guard.as_mut() {
-                        file_ref.flush().await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                "Failed to flush file {path}: {e}"
-                            ))
-                        })?;
-                    }
+                if exhausted {
+                    stats.track(timeout::limit(timeout, sink.flush())).await?;
+                    return Ok(written);
                 }
+            }
+        };
+        future_into_py(py, future).map(|bound| bound.unbind())
+    }
 
-                Ok(())
-            };
-            future_into_py(py, future).map(|bound| bound.unbind())
-        })
+    /// Throughput counters since the writer was created.
+    ///
+    /// Returns a dict with `rows` and `bytes` written, `errors` (failed operations),
+    /// `parse_errors` (always 0 for writers), `elapsed` (seconds since creation) and
+    /// `active` (seconds spent inside writes, flushes and closes).
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.stats.to_dict(py)
     }
 
-    /// Close the file handle explicitly.
-    fn close(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
-        let is_path = matches!(self_.source, FileSource::Path(_));
-        let file = Arc::clone(&self_.file);
+    /// Write out buffered rows and flush the file.
+    fn flush(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let sink = self_.sink();
+        let stats = self_.stats.clone();
+        let timeout = self_.timeout;
+        let ticket = self_.turnstile.ticket();
         Python::attach(|py| {
             let future = async move {
-                if is_path {
-                    // For path-based sources, close the Tokio File
-                    let mut file_guard = file.lock().await;
-                    if let Some(mut f) = file_guard.take() {
-                        f.flush().await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                "Failed to flush file during close: {e}"
-                            ))
-                        })?;
-                    }
-                }
-                // For file handle sources, closing is managed by Python (context manager)
-                // No explicit close needed
-                Ok(())
+                stats
+                    .track(timeout::limit(timeout, async {
+                        ticket.wait().await;
+                        sink.flush().await
+                    }))
+                    .await
             };
             future_into_py(py, future).map(|bound| bound.unbind())
         })
     }
 
+    /// Close the file handle explicitly, writing out buffered rows first.
+    fn close(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        self_.close_output()
+    }
+
     /// Async context manager entry.
     fn __aenter__(slf: PyRef<Self>) -> PyResult<Py<PyAny>> {
         let slf: Py<Self> = slf.into();
@@ -2991,23 +4128,167 @@ impl Writer {
 
     /// Async context manager exit.
     fn __aexit__(
-        &mut self,
+        &self,
         _exc_type: Option<&Bound<'_, PyAny>>,
         _exc_val: Option<&Bound<'_, PyAny>>,
         _exc_tb: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Py<PyAny>> {
-        let file = Arc::clone(&self.file);
+        self.close_output()
+    }
+}
+
+/// The parts of a `Writer` that rows pass through, for use inside its futures.
+#[derive(Clone)]
+struct RowSink {
+    output: BufferedOutput,
+    queue: Option<WriteQueue>,
+    dialect: DialectConfig,
+    progress: Option<Arc<Progress>>,
+    stats: Arc<Stats>,
+    header: Arc<Mutex<Option<Vec<String>>>>,
+    quota: Option<Arc<Quota>>,
+}
+
+impl RowSink {
+    /// Write `rows`, already encoded as `csv_data`, after the `headers` row if it is
+    /// still pending. Called in turn.
+    async fn write(&self, mut csv_data: Vec<u8>, rows: usize) -> PyResult<()> {
+        let header = pending_header(&self.header, &self.output).await?;
+        if let Some(header) = &header {
+            let mut data = encode_rows(&self.dialect, std::slice::from_ref(header))?;
+            data.append(&mut csv_data);
+            csv_data = data;
+        }
+        let bytes = csv_data.len() as u64;
+        if let Some(quota) = &self.quota {
+            if let Err(e) = quota.charge(rows, bytes) {
+                // The header goes out with the next rows that fit
+                if header.is_some() {
+                    *self.header.lock().await = header;
+                }
+                return Err(e);
+            }
+        }
+        match &self.queue {
+            Some(queue) => queue.push(csv_data).await?,
+            None => self.output.write(csv_data).await?,
+        }
+        self.stats.record(rows, bytes);
+        match &self.progress {
+            Some(progress) => progress.advance(rows, bytes),
+            None => Ok(()),
+        }
+    }
+
+    async fn flush(&self) -> PyResult<()> {
+        match &self.queue {
+            Some(queue) => queue.flush().await,
+            None => self.output.flush().await,
+        }
+    }
+}
+
+impl Drop for Writer {
+    /// A writer dropped without `close()` writes out the rows it still buffers or
+    /// queues and warns with `ResourceWarning`, so an abandoned writer doesn't lose
+    /// them silently.
+    fn drop(&mut self) {
+        let written = match &self.queue {
+            // The background task writes the queue out on its own
+            Some(queue) => queue.release().then_some(true),
+            None => self.output.flush_on_drop(),
+        };
+        let Some(written) = written else {
+            return;
+        };
+        let message = if written {
+            format!(
+                "Writer for {} was not closed; rows it held are written out as it is dropped",
+                self.output.path()
+            )
+        } else {
+            format!(
+                "Writer for {} was not closed; buffered rows were lost, call close()",
+                self.output.path()
+            )
+        };
+        Python::attach(|py| {
+            let category = py.get_type::<pyo3::exceptions::PyResourceWarning>();
+            let message = std::ffi::CString::new(message).unwrap_or_default();
+            if let Err(e) = PyErr::warn(py, category.as_any(), &message, 1) {
+                e.write_unraisable(py, None);
+            }
+        });
+    }
+}
+
+impl Writer {
+    fn sink(&self) -> RowSink {
+        RowSink {
+            output: self.output.clone(),
+            queue: self.queue.clone(),
+            dialect: self.dialect.clone(),
+            progress: self.progress.clone(),
+            stats: self.stats.clone(),
+            header: self.header.clone(),
+            quota: self.quota.clone(),
+        }
+    }
+
+    /// Encode `rows` and hand them to the output in submission order.
+    fn submit_rows(&self, rows: Vec<Vec<String>>) -> PyResult<Py<PyAny>> {
+        let sink = self.sink();
+        let stats = self.stats.clone();
+        let timeout = self.timeout;
+        let ticket = self.turnstile.ticket();
         Python::attach(|py| {
             let future = async move {
-                let mut file_guard = file.lock().await;
-                if let Some(mut f) = file_guard.take() {
-                    f.flush().await.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to flush file during close: {e}"
-                        ))
-                    })?;
-                }
-                Ok(())
+                stats
+                    .track(timeout::limit(timeout, async {
+                        // Proper CSV writing with escaping and quoting (RFC 4180 compliant)
+                        let csv_data = encode_rows(&sink.dialect, &rows)?;
+                        ticket.wait().await;
+                        sink.write(csv_data, rows.len()).await
+                    }))
+                    .await
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// Write out buffered and queued rows and close the output.
+    fn close_output(&self) -> PyResult<Py<PyAny>> {
+        let output = self.output.clone();
+        let queue = self.queue.clone();
+        let progress = self.progress.clone();
+        let stats = self.stats.clone();
+        let timeout = self.timeout;
+        let dialect = self.dialect.clone();
+        let header = self.header.clone();
+        let ticket = self.turnstile.ticket();
+        Python::attach(|py| {
+            let future = async move {
+                stats
+                    .track(timeout::limit(timeout, async {
+                        ticket.wait().await;
+                        // A new file gets its header even if no rows were written
+                        if let Some(header) = pending_header(&header, &output).await? {
+                            let data = encode_rows(&dialect, &[header])?;
+                            match &queue {
+                                Some(queue) => queue.push(data).await?,
+                                None => output.write(data).await?,
+                            }
+                        }
+                        match queue {
+                            Some(queue) => queue.close().await?,
+                            None => output.close().await?,
+                        }
+                        match progress {
+                            Some(progress) => progress.finish(),
+                            None => Ok(()),
+                        }
+                    }))
+                    .await
             };
             future_into_py(py, future).map(|bound| bound.unbind())
         })
@@ -0,0 +1,385 @@
+//! Structural checks before loading a CSV file (`rapcsv.lint()`).
+//!
+//! Two streaming passes on a blocking worker thread: one over the raw bytes for line
+//! endings, NUL bytes and quote balance, one over the parsed records for ragged rows,
+//! duplicate header names and values whose type doesn't match the rest of their
+//! column. Every finding carries the line it was seen on, and the data row and column
+//! where those apply.
+
+use crate::errors::CsvIoError;
+use crate::ops::{csv_err, open_reader, simple_dialect, spawn_blocking_py};
+use crate::validate_path;
+use csv::ByteRecord;
+use memchr::memchr_iter;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::io::{BufRead, BufReader, Read};
+
+/// One problem found. `row` is the 1-based data row (None for file-wide problems and
+/// the header); `line` is the 1-based line the problem starts on.
+struct Finding {
+    kind: &'static str,
+    row: Option<usize>,
+    line: u64,
+    column: Option<String>,
+    message: String,
+}
+
+/// Line ending styles, in the order they are reported.
+const ENDINGS: [&str; 3] = ["CRLF", "LF", "CR"];
+
+/// What the byte pass saw.
+#[derive(Default)]
+pub(crate) struct ByteScan {
+    endings: [u64; 3],                      // Line breaks outside quotes, by style
+    first_ending: [Option<u64>; 3],         // Line each style first ends
+    nul_lines: Vec<u64>,                    // Lines holding NUL bytes, up to the finding limit
+    pub(crate) open_quote: Option<u64>,     // Line of a quote still open at the end of the file
+    pub(crate) open_quote_end: Option<u64>, // Byte offset of the end of that line
+}
+
+impl ByteScan {
+    /// Count a line break of `style` ending `line`. Breaks inside quoted fields are
+    /// data, not line endings.
+    fn line_break(&mut self, style: usize, line: &mut u64, in_quotes: bool) {
+        if !in_quotes {
+            self.endings[style] += 1;
+            self.first_ending[style].get_or_insert(*line);
+        }
+        *line += 1;
+    }
+}
+
+/// Scan the raw bytes of `path`.
+pub(crate) fn scan_bytes(path: &str, quotechar: u8, max_findings: usize) -> PyResult<ByteScan> {
+    let io_err =
+        |e: std::io::Error| CsvIoError::new_err(format!("Failed to read file '{path}': {e}"));
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file '{path}': {e}")))?;
+    let mut scan = ByteScan::default();
+    let mut line = 1;
+    let mut in_quotes = false;
+    let mut quote_line = 0;
+    let mut quote_end = None; // First line break after the last opening quote
+    let mut offset = 0u64;
+    let mut after_cr = false; // A CR whose style depends on the next byte
+    let mut chunk = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut chunk).map_err(io_err)?;
+        if n == 0 {
+            break;
+        }
+        for (i, &b) in chunk[..n].iter().enumerate() {
+            let at = offset + i as u64;
+            if in_quotes && quote_end.is_none() && (b == b'\r' || b == b'\n') {
+                quote_end = Some(at);
+            }
+            if after_cr {
+                after_cr = false;
+                if b == b'\n' {
+                    scan.line_break(0, &mut line, in_quotes);
+                    continue;
+                }
+                scan.line_break(2, &mut line, in_quotes);
+            }
+            match b {
+                b'\r' => after_cr = true,
+                b'\n' => scan.line_break(1, &mut line, in_quotes),
+                0 if scan.nul_lines.last() != Some(&line)
+                    && scan.nul_lines.len() < max_findings =>
+                {
+                    scan.nul_lines.push(line)
+                }
+                _ if b == quotechar => {
+                    // Doubled quotes toggle twice, so parity tracks quoted fields
+                    in_quotes = !in_quotes;
+                    if in_quotes {
+                        quote_line = line;
+                        quote_end = None;
+                    }
+                }
+                _ => {}
+            }
+        }
+        offset += n as u64;
+    }
+    if after_cr {
+        scan.line_break(2, &mut line, in_quotes);
+    }
+    if in_quotes {
+        scan.open_quote = Some(quote_line);
+        scan.open_quote_end = Some(quote_end.unwrap_or(offset));
+    }
+    Ok(scan)
+}
+
+/// Finds the line each record starts on, reading the file alongside the parser.
+///
+/// The parser's own line count stops short of the `\n` of a CRLF until it starts the
+/// next record, so it lags a line behind in CRLF files.
+pub(crate) struct LineCounter {
+    file: BufReader<std::fs::File>,
+    offset: u64,
+    newlines: u64,
+}
+
+impl LineCounter {
+    pub(crate) fn open(path: &str) -> PyResult<Self> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| CsvIoError::new_err(format!("Failed to open file '{path}': {e}")))?;
+        Ok(LineCounter {
+            file: BufReader::new(file),
+            offset: 0,
+            newlines: 0,
+        })
+    }
+
+    /// Line of the record the parser found at byte `start`: past the line breaks it
+    /// left unread before the record.
+    pub(crate) fn line_at(&mut self, start: u64) -> std::io::Result<u64> {
+        loop {
+            let buf = self.file.fill_buf()?;
+            if buf.is_empty() {
+                break;
+            }
+            let n = if self.offset < start {
+                buf.len().min((start - self.offset) as usize)
+            } else if buf[0] == b'\r' || buf[0] == b'\n' {
+                1
+            } else {
+                break;
+            };
+            self.newlines += memchr_iter(b'\n', &buf[..n]).count() as u64;
+            self.offset += n as u64;
+            self.file.consume(n);
+        }
+        Ok(self.newlines + 1)
+    }
+}
+
+/// Kind of value a field holds, for spotting values that don't fit their column.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ValueKind {
+    Number,
+    Boolean,
+    Text,
+}
+
+impl ValueKind {
+    const ALL: [ValueKind; 3] = [ValueKind::Number, ValueKind::Boolean, ValueKind::Text];
+
+    fn of(value: &str) -> Self {
+        let value = value.trim();
+        if value.parse::<f64>().is_ok_and(f64::is_finite) {
+            ValueKind::Number
+        } else if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            ValueKind::Boolean
+        } else {
+            ValueKind::Text
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ValueKind::Number => "number",
+            ValueKind::Boolean => "boolean",
+            ValueKind::Text => "text",
+        }
+    }
+}
+
+/// Value kinds seen in one column, with where the first few of each kind were.
+#[derive(Default)]
+struct ColumnKinds {
+    counts: [usize; 3],
+    samples: [Vec<(usize, u64, String)>; 3], // (row, line, value)
+}
+
+/// Lint `path`, returning the findings in line order and the number of data rows.
+fn lint_file(
+    path: &str,
+    dialect: &crate::DialectConfig,
+    max_findings: usize,
+) -> PyResult<(Vec<Finding>, usize)> {
+    let scan = scan_bytes(path, dialect.quotechar, max_findings)?;
+    let mut findings = Vec::new();
+
+    let styles: Vec<usize> = (0..3).filter(|&i| scan.endings[i] > 0).collect();
+    if styles.len() > 1 {
+        // Point at the first break of the least common style
+        let rarest = *styles
+            .iter()
+            .min_by_key(|&&i| scan.endings[i])
+            .unwrap_or(&0);
+        let counts: Vec<String> = styles
+            .iter()
+            .map(|&i| format!("{} {}", scan.endings[i], ENDINGS[i]))
+            .collect();
+        findings.push(Finding {
+            kind: "mixed_line_endings",
+            row: None,
+            line: scan.first_ending[rarest].unwrap_or(1),
+            column: None,
+            message: format!("line endings are mixed: {}", counts.join(", ")),
+        });
+    }
+    for &line in &scan.nul_lines {
+        findings.push(Finding {
+            kind: "nul_byte",
+            row: None,
+            line,
+            column: None,
+            message: "line contains NUL bytes".to_string(),
+        });
+    }
+    if let Some(line) = scan.open_quote {
+        findings.push(Finding {
+            kind: "unbalanced_quote",
+            row: None,
+            line,
+            column: None,
+            message: "quote opened on this line is never closed".to_string(),
+        });
+    }
+
+    let mut reader = open_reader(path, dialect)?;
+    let mut lines = LineCounter::open(path)?;
+    let mut record = ByteRecord::new();
+    let text = |field: &[u8]| String::from_utf8_lossy(field).into_owned();
+    let header: Vec<String> = match reader.read_byte_record(&mut record) {
+        Ok(true) => record.iter().map(text).collect(),
+        Ok(false) => Vec::new(),
+        Err(e) => return Err(csv_err(path, e)),
+    };
+    for (i, name) in header.iter().enumerate() {
+        let positions: Vec<usize> = (0..header.len()).filter(|&j| header[j] == *name).collect();
+        if positions[0] == i && positions.len() > 1 {
+            let columns: Vec<String> = positions.iter().map(|p| (p + 1).to_string()).collect();
+            findings.push(Finding {
+                kind: "duplicate_header",
+                row: None,
+                line: 1,
+                column: Some(name.clone()),
+                message: format!(
+                    "header name '{name}' appears {} times (columns {})",
+                    positions.len(),
+                    columns.join(", ")
+                ),
+            });
+        }
+    }
+
+    let mut columns: Vec<ColumnKinds> = header.iter().map(|_| ColumnKinds::default()).collect();
+    let mut ragged = 0;
+    let mut rows = 0;
+    loop {
+        match reader.read_byte_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(path, e)),
+        }
+        rows += 1;
+        let start = record.position().map_or(0, |p| p.byte());
+        let line = lines
+            .line_at(start)
+            .map_err(|e| CsvIoError::new_err(format!("Failed to read file '{path}': {e}")))?;
+        if record.len() != header.len() && ragged < max_findings {
+            ragged += 1;
+            findings.push(Finding {
+                kind: "ragged_row",
+                row: Some(rows),
+                line,
+                column: None,
+                message: format!("expected {} fields, found {}", header.len(), record.len()),
+            });
+        }
+        for (column, field) in columns.iter_mut().zip(record.iter()) {
+            if field.is_empty() {
+                continue;
+            }
+            let value = text(field);
+            let kind = ValueKind::of(&value) as usize;
+            column.counts[kind] += 1;
+            if column.samples[kind].len() < max_findings {
+                column.samples[kind].push((rows, line, value));
+            }
+        }
+    }
+
+    for (name, column) in header.iter().zip(&mut columns) {
+        let total: usize = column.counts.iter().sum();
+        // Ties go to text, flagging the numbers or booleans among it
+        let majority = ValueKind::ALL
+            .into_iter()
+            .max_by_key(|&k| (column.counts[k as usize], k == ValueKind::Text))
+            .unwrap_or(ValueKind::Text);
+        for kind in ValueKind::ALL.into_iter().filter(|&k| k != majority) {
+            for (row, line, value) in std::mem::take(&mut column.samples[kind as usize]) {
+                findings.push(Finding {
+                    kind: "inconsistent_type",
+                    row: Some(row),
+                    line,
+                    column: Some(name.clone()),
+                    message: format!(
+                        "{} value '{value}' in a column of {} values ({} of {total})",
+                        kind.name(),
+                        majority.name(),
+                        column.counts[majority as usize]
+                    ),
+                });
+            }
+        }
+    }
+
+    findings.sort_by_key(|finding| finding.line);
+    Ok((findings, rows))
+}
+
+/// Check a CSV file for structural problems before loading it.
+///
+/// # Arguments
+/// * `path` - CSV file (first row is the header)
+/// * `max_findings` - Report at most this many findings (default: 100)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+///
+/// Returns a report dict with `clean`, `rows_checked`, `truncated` and `findings`, a
+/// list of dicts with `kind` ("ragged_row", "unbalanced_quote", "mixed_line_endings",
+/// "nul_byte", "duplicate_header" or "inconsistent_type"), `row`, `line`, `column`
+/// and `message`.
+#[pyfunction]
+#[pyo3(signature = (path, max_findings = 100, delimiter = None, quotechar = None))]
+pub(crate) fn lint(
+    py: Python<'_>,
+    path: String,
+    max_findings: usize,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&path)?;
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    let max_findings = max_findings.max(1);
+    spawn_blocking_py(py, move || {
+        let (mut findings, rows) = lint_file(&path, &dialect, max_findings)?;
+        let truncated = findings.len() > max_findings;
+        findings.truncate(max_findings);
+        Python::attach(|py| -> PyResult<Py<PyAny>> {
+            let list = PyList::empty(py);
+            for finding in &findings {
+                let item = PyDict::new(py);
+                item.set_item("kind", finding.kind)?;
+                item.set_item("row", finding.row)?;
+                item.set_item("line", finding.line)?;
+                item.set_item("column", finding.column.as_deref())?;
+                item.set_item("message", &finding.message)?;
+                list.append(item)?;
+            }
+            let result = PyDict::new(py);
+            result.set_item("clean", findings.is_empty())?;
+            result.set_item("rows_checked", rows)?;
+            result.set_item("truncated", truncated)?;
+            result.set_item("findings", list)?;
+            Ok(result.into_any().unbind())
+        })
+    })
+}
@@ -0,0 +1,42 @@
+//! Advisory whole-file locks for coordinating readers and writers across processes.
+//!
+//! Locks use `flock` on Unix and `LockFileEx` on Windows (through `std::fs::File::lock`).
+//! They are advisory: they only exclude other rapcsv instances and programs that take
+//! the same locks, not plain reads and writes.
+
+use crate::errors::CsvIoError;
+use crate::paths::{self, FilePath};
+use pyo3::prelude::*;
+use std::fs::{File, OpenOptions};
+
+/// Kind of lock held on a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LockMode {
+    /// Shared with other shared holders; excludes exclusive holders.
+    Shared,
+    /// Excludes every other holder.
+    Exclusive,
+}
+
+/// Open `path` with `options` and lock it, waiting on the blocking pool while another
+/// process holds a conflicting lock. The lock is released when the file is dropped.
+pub(crate) async fn open_locked(
+    path: &FilePath,
+    options: OpenOptions,
+    mode: LockMode,
+) -> PyResult<File> {
+    let lock_path = path.clone();
+    tokio::task::spawn_blocking(move || {
+        let file = options
+            .open(paths::native(&lock_path))
+            .map_err(|e| CsvIoError::new_err(format!("Failed to open file {lock_path}: {e}")))?;
+        match mode {
+            LockMode::Shared => file.lock_shared(),
+            LockMode::Exclusive => file.lock(),
+        }
+        .map_err(|e| CsvIoError::new_err(format!("Failed to lock file {lock_path}: {e}")))?;
+        Ok(file)
+    })
+    .await
+    .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))?
+}
@@ -0,0 +1,227 @@
+//! Forwarding of the core's tracing spans and events to Python's `logging`.
+//!
+//! Opening files, refilling the read buffer, parsing batches of records and writing out
+//! the write buffer run in spans (`open`, `refill`, `parse`, `flush`); retries are
+//! logged as warnings. Everything at or above the level set with
+//! `rapcsv.set_log_level()` goes to the `rapcsv` logger: events as they happen and
+//! spans when they end, with their fields and how long they took. The default level is
+//! WARNING, so retries show up in application logs while the per-chunk spans cost no
+//! more than a level check.
+
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Most verbose level forwarded: 0 for none, then ERROR (1) to TRACE (5).
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(2);
+
+/// Python `logging` level of TRACE, below DEBUG.
+const PY_TRACE: u8 = 5;
+
+fn rank(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 1,
+        Level::WARN => 2,
+        Level::INFO => 3,
+        Level::DEBUG => 4,
+        Level::TRACE => 5,
+    }
+}
+
+fn python_level(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 40,
+        Level::WARN => 30,
+        Level::INFO => 20,
+        Level::DEBUG => 10,
+        Level::TRACE => PY_TRACE,
+    }
+}
+
+/// `name=value` pairs of a span or event; the `message` field is kept apart.
+#[derive(Default)]
+struct Fields {
+    message: String,
+    pairs: String,
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.pairs, " {}={value}", field.name());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.pairs, " {}={value:?}", field.name());
+        }
+    }
+}
+
+/// Fields and start time kept with each open span.
+struct SpanData {
+    fields: Fields,
+    start: Instant,
+}
+
+/// The layer handing spans and events to the `rapcsv` logger.
+struct PythonLogger;
+
+impl PythonLogger {
+    fn emit(level: &Level, message: String) {
+        Python::attach(|py| {
+            let logged = py
+                .import("logging")
+                .and_then(|logging| logging.call_method1("getLogger", ("rapcsv",)))
+                .and_then(|logger| {
+                    logger.call_method1(
+                        "log",
+                        (python_level(level), "%s", PyString::new(py, &message)),
+                    )
+                });
+            if let Err(e) = logged {
+                e.write_unraisable(py, None);
+            }
+        });
+    }
+}
+
+impl<S> Layer<S> for PythonLogger
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    // The level can change at any time, so nothing is cached per callsite
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        rank(metadata.level()) <= MAX_LEVEL.load(Ordering::Relaxed)
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanData {
+                fields,
+                start: Instant::now(),
+            });
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+                values.record(&mut data.fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let mut message = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            let names: Vec<_> = scope.from_root().map(|span| span.name()).collect();
+            if !names.is_empty() {
+                let _ = write!(message, "{}: ", names.join(":"));
+            }
+        }
+        message.push_str(&fields.message);
+        message.push_str(&fields.pairs);
+        Self::emit(event.metadata().level(), message);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        let Some(data) = extensions.get::<SpanData>() else {
+            return;
+        };
+        let elapsed = data.start.elapsed().as_secs_f64() * 1000.0;
+        let message = format!("{}{} ({elapsed:.3} ms)", span.name(), data.fields.pairs);
+        Self::emit(span.metadata().level(), message);
+    }
+}
+
+/// Route the core's tracing output to the `rapcsv` logger. Called once, when the
+/// module is imported.
+pub(crate) fn install() {
+    let subscriber = tracing_subscriber::registry().with(PythonLogger);
+    let _ = tracing::subscriber::set_global_default(subscriber); // Already set: keep it
+}
+
+/// Set the most verbose level forwarded to the `rapcsv` logger.
+///
+/// Accepts a `logging` level number or name ("TRACE", "DEBUG", "INFO", "WARNING",
+/// "ERROR"), or None to forward nothing. DEBUG adds the `open`, `refill`, `parse` and
+/// `flush` spans with their durations; the default, WARNING, forwards retries only.
+/// The logger's own level and handlers still decide what is shown.
+///
+/// # Example
+///
+/// ```python
+/// import logging
+/// import rapcsv
+///
+/// logging.basicConfig(level=logging.DEBUG)
+/// rapcsv.set_log_level("DEBUG")
+/// ```
+#[pyfunction]
+pub(crate) fn set_log_level(level: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+    let rank = match level {
+        None => 0,
+        Some(level) => {
+            let number = match level.extract::<i64>() {
+                Ok(number) => number,
+                Err(_) => {
+                    let name = level.extract::<String>().map_err(|_| {
+                        PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                            "level must be a logging level number, a level name or None",
+                        )
+                    })?;
+                    match name.to_ascii_uppercase().as_str() {
+                        "TRACE" => i64::from(PY_TRACE),
+                        "DEBUG" => 10,
+                        "INFO" => 20,
+                        "WARNING" | "WARN" => 30,
+                        "ERROR" => 40,
+                        "CRITICAL" | "OFF" => 50,
+                        _ => {
+                            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                                "unknown log level '{name}'"
+                            )))
+                        }
+                    }
+                }
+            };
+            match number {
+                ..=5 => 5,
+                6..=10 => 4,
+                11..=20 => 3,
+                21..=30 => 2,
+                31..=40 => 1,
+                _ => 0,
+            }
+        }
+    };
+    MAX_LEVEL.store(rank, Ordering::Relaxed);
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! Memory budgets for operations that buffer rows (`max_memory=` on `Reader`, `sort()`
+//! and `join()`).
+//!
+//! Sizes are estimates of the heap memory held for buffered rows: the field text plus
+//! the bookkeeping stored with each field and row. They leave out allocator slack, so
+//! the limit is best set with some headroom below the memory actually available.
+//! Operations that can spill to disk do so when the budget is reached; the others
+//! raise `MemoryError` instead of growing further.
+
+use csv::StringRecord;
+use pyo3::exceptions::{PyMemoryError, PyValueError};
+use pyo3::prelude::*;
+use std::mem::size_of;
+
+/// Validate a `max_memory` argument given in bytes.
+pub(crate) fn parse(max_memory: Option<usize>) -> PyResult<Option<usize>> {
+    match max_memory {
+        Some(0) => Err(PyValueError::new_err("max_memory must be at least 1 byte")),
+        limit => Ok(limit),
+    }
+}
+
+/// Estimated size of a parsed row.
+pub(crate) fn row_size(row: &[String]) -> usize {
+    size_of::<Vec<String>>()
+        + row
+            .iter()
+            .map(|field| size_of::<String>() + field.len())
+            .sum::<usize>()
+}
+
+/// Estimated size of a `csv` record: its text and one field boundary per field.
+pub(crate) fn record_size(record: &StringRecord) -> usize {
+    size_of::<StringRecord>() + record.as_slice().len() + record.len() * size_of::<usize>()
+}
+
+/// The error raised when `what` needs more than `limit` bytes.
+pub(crate) fn exceeded(what: &str, limit: usize, hint: &str) -> PyErr {
+    PyMemoryError::new_err(format!(
+        "{what} needs more than max_memory={limit} bytes; {hint}"
+    ))
+}
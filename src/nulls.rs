@@ -0,0 +1,49 @@
+//! Empty fields read as None (`empty_as_none=True` on `Reader`).
+//!
+//! The parser returns `""` both for a field with nothing between its delimiters and for
+//! a quoted empty string, so the two are told apart from the bytes of the record, as it
+//! is read. The fields that were empty and unquoted are marked in the row itself until
+//! it is handed to Python: such a field becomes `"\0"`, and a field that really starts
+//! with NUL gets one more in front, so no value is lost. [`take`] undoes this.
+
+use crate::spans;
+use crate::DialectConfig;
+
+const MARK: char = '\0';
+
+/// Mark the fields of `row` that were empty and unquoted in `record`, the bytes it was
+/// parsed from. `selected` gives the fields of the record a projected row was taken
+/// from.
+pub(crate) fn mark(
+    row: &mut [String],
+    record: &[u8],
+    dialect: &DialectConfig,
+    selected: Option<&[usize]>,
+) {
+    let fields = spans::fields(record, dialect);
+    let sources = spans::sources(selected, fields.len());
+    for (field, i) in row.iter_mut().zip(sources) {
+        if field.is_empty() && fields.get(i).is_some_and(|range| range.is_empty()) {
+            field.push(MARK);
+        } else if field.starts_with(MARK) {
+            field.insert(0, MARK);
+        }
+    }
+}
+
+/// Undo [`mark`] on a row: returns which fields are None, if any, leaving those
+/// fields empty.
+pub(crate) fn take(row: &mut [String]) -> Option<Vec<bool>> {
+    let row_len = row.len();
+    let mut nulls: Option<Vec<bool>> = None;
+    for (i, field) in row.iter_mut().enumerate() {
+        if !field.starts_with(MARK) {
+            continue;
+        }
+        field.remove(0);
+        if field.is_empty() {
+            nulls.get_or_insert_with(|| vec![false; row_len])[i] = true;
+        }
+    }
+    nulls
+}
@@ -0,0 +1,727 @@
+//! Whole-file CSV operations (concatenate, split, restructure, ...).
+//!
+//! Each operation streams rows with the synchronous `csv` crate on a blocking worker
+//! thread and is exposed to Python as an awaitable, so large files never stall the
+//! event loop or hold the GIL.
+
+use crate::awaitable::future_into_py;
+use crate::errors::{snippet_at, CsvIoError, CsvParseError, EncodingError, ErrorContext};
+use crate::progress::Progress;
+use crate::{validate_path, DialectConfig};
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::fs::File;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Run `f` on the blocking thread pool and wrap the result in a Python awaitable.
+pub(crate) fn spawn_blocking_py<T, F>(py: Python<'_>, f: F) -> PyResult<Py<PyAny>>
+where
+    F: FnOnce() -> PyResult<T> + Send + 'static,
+    T: for<'py> IntoPyObject<'py> + Send + 'static,
+{
+    let future = async move {
+        tokio::task::spawn_blocking(f)
+            .await
+            .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))?
+    };
+    future_into_py(py, future).map(|bound| bound.unbind())
+}
+
+/// Set once the awaitable running a blocking operation is cancelled or dropped, so
+/// the operation can stop early and remove its temporary files instead of running to
+/// completion unobserved.
+#[derive(Clone, Default)]
+pub(crate) struct Cancellation(Arc<AtomicBool>);
+
+impl Cancellation {
+    /// Fail if the operation was cancelled. Cheap enough to call for every row.
+    pub(crate) fn check(&self) -> PyResult<()> {
+        if self.0.load(Ordering::Relaxed) {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "Operation was cancelled",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Cancels the operation when dropped with the future that awaits it.
+struct CancelOnDrop(Cancellation);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0 .0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// `spawn_blocking_py` for operations that stop when the awaiting task is cancelled.
+pub(crate) fn spawn_cancellable_py<T, F>(py: Python<'_>, f: F) -> PyResult<Py<PyAny>>
+where
+    F: FnOnce(&Cancellation) -> PyResult<T> + Send + 'static,
+    T: for<'py> IntoPyObject<'py> + Send + 'static,
+{
+    let cancellation = Cancellation::default();
+    let guard = CancelOnDrop(cancellation.clone());
+    let future = async move {
+        let _guard = guard;
+        tokio::task::spawn_blocking(move || f(&cancellation))
+            .await
+            .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))?
+    };
+    future_into_py(py, future).map(|bound| bound.unbind())
+}
+
+/// Check a `temp_dir` argument: an existing directory to create spill files under.
+pub(crate) fn check_temp_dir(temp_dir: Option<&str>) -> PyResult<()> {
+    if let Some(dir) = temp_dir {
+        validate_path(dir)?;
+        if !std::path::Path::new(dir).is_dir() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "temp_dir '{dir}' is not a directory"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Create a directory for the spill files of `operation` under `temp_dir`, or the
+/// system's temporary directory. It is removed with everything in it when dropped.
+pub(crate) fn spill_dir(temp_dir: Option<&str>, operation: &str) -> PyResult<tempfile::TempDir> {
+    let prefix = format!("rapcsv-{operation}-");
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(&prefix);
+    match temp_dir {
+        Some(dir) => builder.tempdir_in(dir),
+        None => builder.tempdir(),
+    }
+    .map_err(|e| {
+        CsvIoError::new_err(format!(
+            "Failed to create temporary directory for {operation}: {e}"
+        ))
+    })
+}
+
+/// Convert a `csv` error into CsvIoError (I/O errors), EncodingError (invalid UTF-8)
+/// or CsvParseError, with the record it happened at.
+pub(crate) fn csv_err(path: &str, e: csv::Error) -> PyErr {
+    if e.is_io_error() {
+        return CsvIoError::new_err(format!("I/O error in file '{path}': {e}"));
+    }
+    let mut context = ErrorContext::from_csv(&e);
+    if let Some(byte_offset) = context.byte_offset {
+        context.snippet = snippet_at(path, byte_offset);
+    }
+    let location = context.describe();
+    let err = match e.kind() {
+        csv::ErrorKind::Utf8 { .. } => {
+            EncodingError::new_err(format!("Invalid UTF-8 in file '{path}'{location}: {e}"))
+        }
+        _ => CsvParseError::new_err(format!("CSV parse error in file '{path}'{location}: {e}")),
+    };
+    context.attach(err)
+}
+
+/// Open `path` as a headerless CSV reader using `dialect`.
+pub(crate) fn open_reader(path: &str, dialect: &DialectConfig) -> PyResult<csv::Reader<File>> {
+    let mut builder = ReaderBuilder::new();
+    dialect.apply_to_reader(&mut builder, None);
+    builder
+        .has_headers(false)
+        .from_path(path)
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file '{path}': {e}")))
+}
+
+/// Create (or truncate) `path` as a CSV writer using `dialect`.
+pub(crate) fn create_writer(path: &str, dialect: &DialectConfig) -> PyResult<csv::Writer<File>> {
+    let mut builder = WriterBuilder::new();
+    dialect.apply_to_writer(&mut builder);
+    builder
+        .from_path(path)
+        .map_err(|e| CsvIoError::new_err(format!("Failed to create file '{path}': {e}")))
+}
+
+/// Fail if `dst` is the same file as one of `inputs`, which creating it would truncate
+/// before it was read. Files are compared by device and inode on Unix, so a link or
+/// another spelling of an input's path is caught too.
+pub(crate) fn check_not_input<S: AsRef<str>>(dst: &str, inputs: &[S]) -> PyResult<()> {
+    let Ok(target) = std::fs::metadata(dst) else {
+        return Ok(()); // Not created yet, so not an input either
+    };
+    for input in inputs {
+        let input = input.as_ref();
+        if std::fs::metadata(input).is_ok_and(|source| same_file(&target, &source, dst, input)) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Destination '{dst}' is the input file '{input}'; write to another path"
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn same_file(a: &std::fs::Metadata, b: &std::fs::Metadata, _: &str, _: &str) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+#[cfg(not(unix))]
+fn same_file(_: &std::fs::Metadata, _: &std::fs::Metadata, a: &str, b: &str) -> bool {
+    matches!(
+        (std::fs::canonicalize(a), std::fs::canonicalize(b)),
+        (Ok(a), Ok(b)) if a == b
+    )
+}
+
+/// Read the first record of `reader` as the header. Returns `None` for an empty file.
+pub(crate) fn read_header(
+    reader: &mut csv::Reader<File>,
+    path: &str,
+) -> PyResult<Option<StringRecord>> {
+    let mut header = StringRecord::new();
+    match reader.read_record(&mut header) {
+        Ok(true) => Ok(Some(header)),
+        Ok(false) => Ok(None),
+        Err(e) => Err(csv_err(path, e)),
+    }
+}
+
+/// Build a dialect from the `delimiter`/`quotechar` arguments shared by the file operations.
+pub(crate) fn simple_dialect(
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+) -> PyResult<DialectConfig> {
+    DialectConfig::from_python(
+        None, delimiter, quotechar, None, None, None, None, None, None,
+    )
+}
+
+/// Extract a column-list argument given as a single name or a list of names.
+pub(crate) fn extract_columns(value: &Bound<'_, PyAny>, arg_name: &str) -> PyResult<Vec<String>> {
+    let columns: Vec<String> = match value.extract::<String>() {
+        Ok(column) => vec![column],
+        Err(_) => value.extract()?,
+    };
+    if columns.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{arg_name} must name at least one column"
+        )));
+    }
+    Ok(columns)
+}
+
+/// Encode the key fields of `record` into an unambiguous byte string.
+///
+/// With `columns` of `None` the whole record is the key.
+pub(crate) fn encode_key(record: &StringRecord, columns: Option<&[usize]>, out: &mut Vec<u8>) {
+    out.clear();
+    let mut push = |field: &str| {
+        out.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        out.extend_from_slice(field.as_bytes());
+    };
+    match columns {
+        Some(columns) => columns
+            .iter()
+            .for_each(|&i| push(record.get(i).unwrap_or(""))),
+        None => record.iter().for_each(push),
+    }
+}
+
+/// Resolve column names to their positions in `header`.
+pub(crate) fn resolve_columns(
+    columns: &[String],
+    header: &StringRecord,
+    path: &str,
+) -> PyResult<Vec<usize>> {
+    columns
+        .iter()
+        .map(|name| {
+            header.iter().position(|h| h == name).ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Column '{name}' not found in header of '{path}'"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Concatenate several CSV files into one.
+///
+/// The header of the first file becomes the output header. With `align_headers`,
+/// later files may list the same columns in any order and their fields are reordered
+/// to match; otherwise headers must be identical. Returns the number of data rows written.
+fn concat_files(
+    paths: &[String],
+    dst: &str,
+    align_headers: bool,
+    dialect: &DialectConfig,
+    progress: Option<&Progress>,
+) -> PyResult<usize> {
+    check_not_input(dst, paths)?;
+    let mut writer = create_writer(dst, dialect)?;
+    let mut output_header: Option<StringRecord> = None;
+    let mut written = 0usize;
+    let mut record = StringRecord::new();
+    let mut bytes_before = 0u64; // Size of the files already copied
+
+    for path in paths {
+        let mut reader = open_reader(path, dialect)?;
+        let Some(header) = read_header(&mut reader, path)? else {
+            continue; // Empty input contributes nothing
+        };
+
+        // Map output column position -> input column position
+        let mapping: Option<Vec<usize>> = match &output_header {
+            None => {
+                writer.write_record(&header).map_err(|e| csv_err(dst, e))?;
+                output_header = Some(header);
+                None
+            }
+            Some(expected) if expected == &header => None,
+            Some(expected) if align_headers => {
+                let missing: Vec<&str> = expected
+                    .iter()
+                    .filter(|col| !header.iter().any(|h| h == *col))
+                    .collect();
+                let extra: Vec<&str> = header
+                    .iter()
+                    .filter(|col| !expected.iter().any(|h| h == *col))
+                    .collect();
+                if !missing.is_empty() || !extra.is_empty() || expected.len() != header.len() {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Incompatible header in file '{path}': missing columns {missing:?}, \
+                        unexpected columns {extra:?}"
+                    )));
+                }
+                Some(
+                    expected
+                        .iter()
+                        .map(|col| header.iter().position(|h| h == col).unwrap_or(0))
+                        .collect(),
+                )
+            }
+            Some(expected) => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Header of file '{path}' ({:?}) does not match header of '{}' ({:?}); \
+                    pass align_headers=True to reorder columns by name",
+                    header.iter().collect::<Vec<_>>(),
+                    paths[0],
+                    expected.iter().collect::<Vec<_>>()
+                )));
+            }
+        };
+
+        loop {
+            match reader.read_record(&mut record) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(e) => return Err(csv_err(path, e)),
+            }
+            let result = match &mapping {
+                Some(mapping) => {
+                    writer.write_record(mapping.iter().map(|&i| record.get(i).unwrap_or("")))
+                }
+                None => writer.write_record(&record),
+            };
+            result.map_err(|e| csv_err(dst, e))?;
+            written += 1;
+            if let Some(progress) = progress {
+                progress.update(bytes_before + reader.position().byte(), written)?;
+            }
+        }
+        bytes_before += reader.position().byte();
+    }
+
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+    if let Some(progress) = progress {
+        progress.finish()?;
+    }
+    Ok(written)
+}
+
+/// Concatenate multiple CSV files into one output file.
+///
+/// # Arguments
+/// * `paths` - Input CSV files, in order
+/// * `dst` - Output CSV file (created or truncated)
+/// * `align_headers` - Reorder columns of later files by header name (default: True)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+/// * `progress` - Called as `progress(bytes_read, bytes_total, rows_written)` every
+///   `progress_interval` rows and when done (default: None)
+/// * `progress_interval` - Rows between progress calls (default: 10000)
+///
+/// Returns the number of data rows written.
+#[pyfunction]
+#[pyo3(signature = (paths, dst, align_headers = true, delimiter = None, quotechar = None, progress = None, progress_interval = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn concat(
+    py: Python<'_>,
+    paths: Vec<String>,
+    dst: String,
+    align_headers: bool,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+    progress: Option<Py<PyAny>>,
+    progress_interval: Option<usize>,
+) -> PyResult<Py<PyAny>> {
+    for path in &paths {
+        validate_path(path)?;
+    }
+    validate_path(&dst)?;
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    let progress = Progress::from_args(
+        py,
+        progress,
+        progress_interval,
+        Progress::total_size(&paths),
+    )?;
+    spawn_blocking_py(py, move || {
+        concat_files(&paths, &dst, align_headers, &dialect, progress.as_ref())
+    })
+}
+
+/// Write target that hands back whatever the CSV writer flushed into it.
+#[derive(Clone, Default)]
+struct SharedBuf(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+impl std::io::Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Serializes single records to bytes so callers can measure them before writing.
+pub(crate) struct RecordEncoder {
+    writer: csv::Writer<SharedBuf>,
+    buf: SharedBuf,
+}
+
+impl RecordEncoder {
+    pub(crate) fn new(dialect: &DialectConfig) -> Self {
+        let mut builder = WriterBuilder::new();
+        dialect.apply_to_writer(&mut builder);
+        let buf = SharedBuf::default();
+        RecordEncoder {
+            writer: builder.from_writer(buf.clone()),
+            buf,
+        }
+    }
+
+    /// Encode `record` (including the line terminator).
+    pub(crate) fn encode<I, T>(&mut self, record: I) -> PyResult<Vec<u8>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        self.writer
+            .write_record(record)
+            .and_then(|_| self.writer.flush().map_err(csv::Error::from))
+            .map_err(|e| CsvIoError::new_err(format!("Failed to encode CSV record: {e}")))?;
+        Ok(std::mem::take(&mut *self.buf.0.borrow_mut()))
+    }
+}
+
+/// Expand a part-file pattern such as `part_{}.csv` or `part_{:03d}.csv`.
+fn format_part_name(pattern: &str, n: usize) -> PyResult<String> {
+    let invalid = || {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "dst_pattern must contain one '{{}}' or '{{:0Nd}}' placeholder, got '{pattern}'"
+        ))
+    };
+    let start = pattern.find('{').ok_or_else(invalid)?;
+    let end = start + pattern[start..].find('}').ok_or_else(invalid)?;
+    let spec = &pattern[start + 1..end];
+    let number = match spec {
+        "" | ":d" => n.to_string(),
+        _ => {
+            let width = spec
+                .strip_prefix(":0")
+                .and_then(|s| s.strip_suffix('d'))
+                .and_then(|s| s.parse::<usize>().ok())
+                .ok_or_else(invalid)?;
+            format!("{n:0width$}")
+        }
+    };
+    Ok(format!(
+        "{}{number}{}",
+        &pattern[..start],
+        &pattern[end + 1..]
+    ))
+}
+
+/// Split `src` into numbered part files, copying the header into each part.
+///
+/// A new part starts once the current one holds `rows_per_file` data rows, or when the
+/// next row would push it past `max_bytes` (a part always holds at least one row).
+/// Returns the paths of the files written.
+fn split_file(
+    src: &str,
+    dst_pattern: &str,
+    rows_per_file: Option<usize>,
+    max_bytes: Option<u64>,
+    dialect: &DialectConfig,
+    progress: Option<&Progress>,
+) -> PyResult<Vec<String>> {
+    use std::io::Write;
+
+    let io_err = |path: &str, e: std::io::Error| {
+        CsvIoError::new_err(format!("Failed to write file '{path}': {e}"))
+    };
+
+    let mut reader = open_reader(src, dialect)?;
+    let Some(header) = read_header(&mut reader, src)? else {
+        return Ok(Vec::new());
+    };
+    let mut encoder = RecordEncoder::new(dialect);
+    let header_bytes = encoder.encode(&header)?;
+
+    let mut parts: Vec<String> = Vec::new();
+    let mut current: Option<std::io::BufWriter<File>> = None;
+    let mut part_rows = 0usize;
+    let mut part_bytes = 0u64;
+    let mut written = 0usize;
+    let mut record = StringRecord::new();
+
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        let row_bytes = encoder.encode(&record)?;
+
+        let full = current.is_some()
+            && (rows_per_file.is_some_and(|limit| part_rows >= limit)
+                || max_bytes.is_some_and(|limit| part_bytes + row_bytes.len() as u64 > limit));
+        if full || current.is_none() {
+            if let Some(mut part) = current.take() {
+                let path = parts.last().map(String::as_str).unwrap_or_default();
+                part.flush().map_err(|e| io_err(path, e))?;
+            }
+            let path = format_part_name(dst_pattern, parts.len() + 1)?;
+            validate_path(&path)?;
+            check_not_input(&path, &[src])?;
+            let file = File::create(&path).map_err(|e| io_err(&path, e))?;
+            let mut part = std::io::BufWriter::new(file);
+            part.write_all(&header_bytes)
+                .map_err(|e| io_err(&path, e))?;
+            parts.push(path);
+            current = Some(part);
+            part_rows = 0;
+            part_bytes = header_bytes.len() as u64;
+        }
+
+        if let Some(part) = current.as_mut() {
+            let path = parts.last().map(String::as_str).unwrap_or_default();
+            part.write_all(&row_bytes).map_err(|e| io_err(path, e))?;
+        }
+        part_rows += 1;
+        part_bytes += row_bytes.len() as u64;
+        written += 1;
+        if let Some(progress) = progress {
+            progress.update(reader.position().byte(), written)?;
+        }
+    }
+
+    if let Some(mut part) = current.take() {
+        let path = parts.last().map(String::as_str).unwrap_or_default();
+        part.flush().map_err(|e| io_err(path, e))?;
+    }
+    if let Some(progress) = progress {
+        progress.finish()?;
+    }
+    Ok(parts)
+}
+
+/// Split a large CSV file into numbered part files.
+///
+/// # Arguments
+/// * `src` - Input CSV file
+/// * `dst_pattern` - Output path pattern with a `{}` or `{:0Nd}` placeholder for the
+///   1-based part number, e.g. `"out/part_{:03d}.csv"`
+/// * `rows_per_file` - Maximum data rows per part
+/// * `max_bytes` - Maximum size of each part in bytes (header included)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+/// * `progress` - Called as `progress(bytes_read, bytes_total, rows_written)` every
+///   `progress_interval` rows and when done (default: None)
+/// * `progress_interval` - Rows between progress calls (default: 10000)
+///
+/// At least one of `rows_per_file` and `max_bytes` is required. Returns the list of
+/// part file paths.
+#[pyfunction]
+#[pyo3(signature = (src, dst_pattern, rows_per_file = None, max_bytes = None, delimiter = None, quotechar = None, progress = None, progress_interval = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn split(
+    py: Python<'_>,
+    src: String,
+    dst_pattern: String,
+    rows_per_file: Option<usize>,
+    max_bytes: Option<u64>,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+    progress: Option<Py<PyAny>>,
+    progress_interval: Option<usize>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&src)?;
+    format_part_name(&dst_pattern, 1)?;
+    if rows_per_file.is_none() && max_bytes.is_none() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "split() requires rows_per_file or max_bytes",
+        ));
+    }
+    if rows_per_file == Some(0) || max_bytes == Some(0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "rows_per_file and max_bytes must be greater than zero",
+        ));
+    }
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    let bytes_total = Progress::total_size(std::slice::from_ref(&src));
+    let progress = Progress::from_args(py, progress, progress_interval, bytes_total)?;
+    spawn_blocking_py(py, move || {
+        split_file(
+            &src,
+            &dst_pattern,
+            rows_per_file,
+            max_bytes,
+            &dialect,
+            progress.as_ref(),
+        )
+    })
+}
+
+/// Rewrite `src` into `dst` with a new column layout. Returns the number of data rows written.
+///
+/// `drop` and `order` name source columns; `rename` maps source names to output names.
+fn restructure_file(
+    src: &str,
+    dst: &str,
+    rename: &HashMap<String, String>,
+    drop: &[String],
+    order: Option<&[String]>,
+    dialect: &DialectConfig,
+    progress: Option<&Progress>,
+) -> PyResult<usize> {
+    check_not_input(dst, &[src])?;
+    let mut reader = open_reader(src, dialect)?;
+    let mut writer = create_writer(dst, dialect)?;
+    let Some(header) = read_header(&mut reader, src)? else {
+        writer
+            .flush()
+            .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+        return Ok(0);
+    };
+    for name in rename.keys() {
+        resolve_columns(std::slice::from_ref(name), &header, src)?;
+    }
+    let dropped = resolve_columns(drop, &header, src)?;
+
+    // Output column i is taken from source column columns[i]
+    let columns: Vec<usize> = match order {
+        Some(order) => {
+            let columns = resolve_columns(order, &header, src)?;
+            if let Some(name) = order
+                .iter()
+                .zip(&columns)
+                .find(|(_, i)| dropped.contains(i))
+                .map(|(name, _)| name)
+            {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Column '{name}' is listed in both order and drop"
+                )));
+            }
+            columns
+        }
+        None => (0..header.len()).filter(|i| !dropped.contains(i)).collect(),
+    };
+    let output_header: Vec<&str> = columns
+        .iter()
+        .map(|&i| rename.get(&header[i]).map_or(&header[i], String::as_str))
+        .collect();
+    writer
+        .write_record(&output_header)
+        .map_err(|e| csv_err(dst, e))?;
+
+    let mut record = StringRecord::new();
+    let mut written = 0usize;
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        writer
+            .write_record(columns.iter().map(|&i| record.get(i).unwrap_or("")))
+            .map_err(|e| csv_err(dst, e))?;
+        written += 1;
+        if let Some(progress) = progress {
+            progress.update(reader.position().byte(), written)?;
+        }
+    }
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+    if let Some(progress) = progress {
+        progress.finish()?;
+    }
+    Ok(written)
+}
+
+/// Rename, drop and reorder the columns of a CSV file in one streaming pass.
+///
+/// # Arguments
+/// * `src` - Input CSV file (first row is the header)
+/// * `dst` - Output CSV file (created or truncated)
+/// * `rename` - Dict mapping source column names to new names
+/// * `drop` - Source column names to remove
+/// * `order` - Source column names in output order; unlisted columns are removed
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+/// * `progress` - Called as `progress(bytes_read, bytes_total, rows_written)` every
+///   `progress_interval` rows and when done (default: None)
+/// * `progress_interval` - Rows between progress calls (default: 10000)
+///
+/// Unknown column names raise ValueError. Returns the number of data rows written.
+#[pyfunction]
+#[pyo3(signature = (src, dst, rename = None, drop = None, order = None, delimiter = None, quotechar = None, progress = None, progress_interval = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn restructure(
+    py: Python<'_>,
+    src: String,
+    dst: String,
+    rename: Option<HashMap<String, String>>,
+    drop: Option<Vec<String>>,
+    order: Option<Vec<String>>,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+    progress: Option<Py<PyAny>>,
+    progress_interval: Option<usize>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&src)?;
+    validate_path(&dst)?;
+    let rename = rename.unwrap_or_default();
+    let drop = drop.unwrap_or_default();
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    let bytes_total = Progress::total_size(std::slice::from_ref(&src));
+    let progress = Progress::from_args(py, progress, progress_interval, bytes_total)?;
+    spawn_blocking_py(py, move || {
+        restructure_file(
+            &src,
+            &dst,
+            &rename,
+            &drop,
+            order.as_deref(),
+            &dialect,
+            progress.as_ref(),
+        )
+    })
+}
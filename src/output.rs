@@ -0,0 +1,535 @@
+//! Write destinations for the CSV `Writer`.
+//!
+//! `BufferedOutput` batches encoded rows before they reach the file. `WriteQueue`
+//! optionally puts a bounded channel and a background task in front of it, so
+//! producers only wait for disk IO when the queue is full. `Turnstile` keeps concurrent
+//! writes in the order they were submitted.
+
+use crate::checksum::{self, Algorithm};
+use crate::errors::CsvIoError;
+use crate::lock::{self, LockMode};
+use crate::paths::{self, FilePath};
+use crate::perms::FilePermissions;
+use crate::pipe::PipeSink;
+use crate::retry::RetryPolicy;
+use crate::socket::{self, Conn};
+use crate::throttle::Throttle;
+use crate::uring;
+use crate::{close_adapter, write_chunk, Access};
+use pyo3::exceptions::PyConnectionError;
+use pyo3::prelude::*;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{mpsc, oneshot, Mutex, Notify, OwnedMutexGuard};
+
+/// Write destination that collects encoded rows and writes them in batches.
+///
+/// Rows accumulate in `pending` until it holds at least `threshold` bytes, so most
+/// `write_row()` calls never touch the file. `flush()` and `close()` write out
+/// whatever is left. Holding the `pending` lock while writing keeps rows in order.
+///
+/// Chunks are written on a spawned task that owns the `pending` lock, so cancelling the
+/// caller never leaves a chunk half written: its rows are either written in full or,
+/// if the call is cancelled before they were buffered, not at all.
+#[derive(Clone)]
+pub(crate) struct BufferedOutput {
+    access: Access,
+    path: FilePath,
+    file: Arc<Mutex<Option<Conn>>>, // Only used when the destination is a path or descriptor
+    file_handle: Arc<StdMutex<Option<Py<PyAny>>>>, // Python file handle otherwise
+    event_loop: Arc<StdMutex<Option<Py<PyAny>>>>, // Event loop reference for run_coroutine_threadsafe
+    pending: Arc<Mutex<Vec<u8>>>,
+    threshold: usize,
+    lock: bool, // Hold an exclusive lock on a path-backed file while it is open
+    retry: Option<Arc<RetryPolicy>>,
+    pipe: Option<Arc<PipeSink>>, // Archive member, encrypted or SFTP file fed from `file`
+    checksum: Option<Algorithm>, // Digest written next to the file on close
+    throttle: Option<Arc<Throttle>>, // Limit on the rate bytes are written
+    permissions: Option<Arc<FilePermissions>>, // Given to a path-backed file it creates
+}
+
+impl BufferedOutput {
+    pub(crate) fn new(
+        access: Access,
+        path: FilePath,
+        file_handle: Arc<StdMutex<Option<Py<PyAny>>>>,
+        event_loop: Arc<StdMutex<Option<Py<PyAny>>>>,
+        threshold: usize,
+    ) -> Self {
+        BufferedOutput {
+            access,
+            path,
+            file: Arc::new(Mutex::new(None)),
+            file_handle,
+            event_loop,
+            pending: Arc::new(Mutex::new(Vec::new())),
+            threshold,
+            lock: false,
+            retry: None,
+            pipe: None,
+            checksum: None,
+            throttle: None,
+            permissions: None,
+        }
+    }
+
+    /// The file path, or a description of the Python file handle.
+    pub(crate) fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Lock a path-backed file exclusively when it is opened, until `close()`.
+    pub(crate) fn exclusive_lock(mut self) -> Self {
+        self.lock = true;
+        self
+    }
+
+    /// Create a path-backed file that doesn't exist yet with `permissions`.
+    pub(crate) fn permissions(mut self, permissions: FilePermissions) -> Self {
+        self.permissions = Some(Arc::new(permissions));
+        self
+    }
+
+    /// Write a sidecar file holding the `algorithm` digest of the file on `close()`.
+    pub(crate) fn checksum(mut self, algorithm: Algorithm) -> Self {
+        self.checksum = Some(algorithm);
+        self
+    }
+
+    /// Write to an already open `file` instead of opening the path (`from_fd()`).
+    pub(crate) fn attach_file(&self, file: impl Into<Conn>) -> PyResult<()> {
+        let mut slot = self.file.try_lock().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Writer is already in use")
+        })?;
+        *slot = Some(file.into());
+        Ok(())
+    }
+
+    /// Write to `file`, the pipe `sink` encodes an archive member or encrypted file
+    /// from, or uploads to an SFTP server from.
+    pub(crate) fn piped(mut self, file: File, sink: PipeSink) -> PyResult<Self> {
+        self.attach_file(file)?;
+        self.pipe = Some(Arc::new(sink));
+        Ok(self)
+    }
+
+    /// Whether the destination already holds data, so a header row would be a
+    /// duplicate. Python file handles are always treated as empty.
+    pub(crate) async fn has_content(&self) -> PyResult<bool> {
+        if self.access == Access::Handle {
+            return Ok(false);
+        }
+        if let Some(sink) = &self.pipe {
+            return Ok(sink.has_content().await);
+        }
+        let metadata = match self.file.lock().await.as_mut() {
+            Some(conn) => match conn.metadata().await {
+                Some(metadata) => metadata,
+                None => return Ok(false), // A connection
+            },
+            None => tokio::fs::metadata(paths::native(&self.path)).await,
+        };
+        match metadata {
+            Ok(metadata) => Ok(metadata.len() > 0),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(CsvIoError::new_err(format!(
+                "Failed to read file {}: {e}",
+                self.path
+            ))),
+        }
+    }
+
+    /// Retry transient failures to open the file under `policy`.
+    pub(crate) fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(Arc::new(policy));
+        self
+    }
+
+    /// Write no faster than `throttle` allows.
+    pub(crate) fn throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = Some(Arc::new(throttle));
+        self
+    }
+
+    /// Queue encoded rows, writing the buffer out once it reaches the threshold.
+    pub(crate) async fn write(&self, data: Vec<u8>) -> PyResult<()> {
+        let mut pending = self.pending.clone().lock_owned().await;
+        pending.extend_from_slice(&data);
+        if pending.len() >= self.threshold {
+            // A zero threshold means unbuffered: flush every write as before
+            self.write_pending(pending, self.threshold == 0).await?;
+        }
+        Ok(())
+    }
+
+    /// Write out buffered rows and flush the file.
+    pub(crate) async fn flush(&self) -> PyResult<()> {
+        let pending = self.pending.clone().lock_owned().await;
+        if !pending.is_empty() {
+            self.write_pending(pending, true).await?;
+        } else if self.access != Access::Handle {
+            if let Some(file) = self.file.lock().await.as_mut() {
+                file.flush().await.map_err(|e| {
+                    socket::connect_error(&e).unwrap_or_else(|| {
+                        CsvIoError::new_err(format!("Failed to flush file {}: {e}", self.path))
+                    })
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write out the rows of a writer dropped without `close()`, as a Python file object
+    /// does when it is collected. Returns None if nothing was buffered, else whether
+    /// the rows were written: a Python file handle's event loop can't be waited on from
+    /// the writer's destructor, so its rows are lost.
+    pub(crate) fn flush_on_drop(&self) -> Option<bool> {
+        // A locked buffer is being written by a task that finishes on its own
+        let buffered = self
+            .pending
+            .try_lock()
+            .is_ok_and(|pending| !pending.is_empty());
+        if !buffered {
+            return None;
+        }
+        if self.access == Access::Handle {
+            return Some(false);
+        }
+        let output = self.clone();
+        match tokio::runtime::Handle::try_current() {
+            // Dropped on a runtime worker, which must not block
+            Ok(runtime) => {
+                runtime.spawn(async move { output.flush().await });
+                Some(true)
+            }
+            Err(_) => {
+                let runtime = pyo3_async_runtimes::tokio::get_runtime();
+                let flushed = Python::attach(|py| py.detach(|| runtime.block_on(output.flush())));
+                Some(flushed.is_ok())
+            }
+        }
+    }
+
+    /// Flush and release a path-backed file. Python file handles stay open; their
+    /// lifetime is managed by the caller, except that a `SinkAdapter` is closed. A
+    /// piped file is complete, and the checksum file written, once this returns.
+    pub(crate) async fn close(&self) -> PyResult<()> {
+        self.flush().await.map_err(|e| {
+            if Python::attach(|py| e.is_instance_of::<PyConnectionError>(py)) {
+                return e; // Raised by the first write to a connection
+            }
+            CsvIoError::new_err(format!("Failed to flush file during close: {e}"))
+        })?;
+        self.file.lock().await.take();
+        if self.access == Access::Handle {
+            close_adapter(&self.file_handle, &self.event_loop).await?;
+        }
+        if let Some(sink) = &self.pipe {
+            sink.finish().await?;
+        }
+        match self.checksum {
+            Some(algorithm) => checksum::write_sidecar(&self.path, algorithm).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Write out the buffer on a separate task that holds the `pending` lock until the
+    /// chunk is written, whether or not the caller is still waiting.
+    async fn write_pending(
+        &self,
+        mut pending: OwnedMutexGuard<Vec<u8>>,
+        flush: bool,
+    ) -> PyResult<()> {
+        let output = self.clone();
+        tokio::spawn(async move {
+            let chunk = std::mem::take(&mut *pending);
+            output.write_chunk(chunk, flush).await
+        })
+        .await
+        .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))?
+    }
+
+    async fn write_chunk(&self, data: Vec<u8>, flush: bool) -> PyResult<()> {
+        if let Some(permissions) = self
+            .permissions
+            .as_ref()
+            .filter(|_| self.access == Access::Path)
+        {
+            // Before the file is first opened, which would create it with the umask
+            if self.file.lock().await.is_none() {
+                permissions.create(&self.path)?;
+            }
+        }
+        if self.lock && self.access == Access::Path {
+            let mut file = self.file.lock().await;
+            if file.is_none() {
+                let mut options = std::fs::OpenOptions::new();
+                options.create(true).append(true);
+                let locked = lock::open_locked(&self.path, options, LockMode::Exclusive).await?;
+                *file = Some(uring::conn(File::from_std(locked)).await);
+            }
+        }
+        let Some(throttle) = &self.throttle else {
+            return self.write_slice(data, flush).await;
+        };
+        let mut slices = data.chunks(throttle.slice_len()).peekable();
+        while let Some(slice) = slices.next() {
+            throttle.acquire(slice.len()).await;
+            let last = slices.peek().is_none();
+            self.write_slice(slice.to_vec(), flush && last).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_slice(&self, data: Vec<u8>, flush: bool) -> PyResult<()> {
+        write_chunk(
+            self.access,
+            &self.path,
+            &self.file,
+            &self.file_handle,
+            &self.event_loop,
+            data,
+            flush,
+            self.retry.as_deref(),
+        )
+        .await
+        // A broken pipe means the thread stopped; say why
+        .map_err(|e| {
+            self.pipe
+                .as_ref()
+                .and_then(|sink| sink.failure())
+                .unwrap_or(e)
+        })
+    }
+}
+
+/// Operation sent to a background writer task.
+enum QueuedWrite {
+    Rows(Vec<u8>),
+    Flush(oneshot::Sender<PyResult<()>>),
+    Close(oneshot::Sender<PyResult<()>>),
+}
+
+/// Bounded queue of encoded rows drained by a background task that owns the output.
+///
+/// `push()` only waits when `capacity` operations are already queued. The task is
+/// started on first use. A failed write is kept and reported by the next `push()`,
+/// `flush()` or `close()`; later rows are dropped rather than written out of order.
+#[derive(Clone)]
+pub(crate) struct WriteQueue {
+    output: BufferedOutput,
+    capacity: usize,
+    sender: Arc<Mutex<Option<mpsc::Sender<QueuedWrite>>>>,
+    error: Arc<StdMutex<Option<PyErr>>>,
+}
+
+impl WriteQueue {
+    pub(crate) fn new(output: BufferedOutput, capacity: usize) -> Self {
+        WriteQueue {
+            output,
+            capacity,
+            sender: Arc::new(Mutex::new(None)),
+            error: Arc::new(StdMutex::new(None)),
+        }
+    }
+
+    /// Queue encoded rows, waiting only while the queue is full.
+    pub(crate) async fn push(&self, data: Vec<u8>) -> PyResult<()> {
+        self.check_error()?;
+        let sender = self.sender().await;
+        sender
+            .send(QueuedWrite::Rows(data))
+            .await
+            .map_err(|_| closed_error())
+    }
+
+    /// Wait for every queued row to be written, then flush the file.
+    pub(crate) async fn flush(&self) -> PyResult<()> {
+        let sender = self.sender().await;
+        let (done, result) = oneshot::channel();
+        sender
+            .send(QueuedWrite::Flush(done))
+            .await
+            .map_err(|_| closed_error())?;
+        result.await.map_err(|_| closed_error())?
+    }
+
+    /// Write out the queue, close the output and stop the background task.
+    pub(crate) async fn close(&self) -> PyResult<()> {
+        let Some(sender) = self.sender.lock().await.take() else {
+            self.check_error()?;
+            return self.output.close().await;
+        };
+        let (done, result) = oneshot::channel();
+        sender
+            .send(QueuedWrite::Close(done))
+            .await
+            .map_err(|_| closed_error())?;
+        result.await.map_err(|_| closed_error())?
+    }
+
+    /// Let the background task stop, for a writer dropped without `close()`: it writes
+    /// out the queue and flushes the output once the channel is closed. Returns whether
+    /// the task was running.
+    pub(crate) fn release(&self) -> bool {
+        match self.sender.try_lock() {
+            Ok(mut sender) => sender.take().is_some(),
+            Err(_) => {
+                // A push holds the slot; take the sender once it is done
+                let slot = Arc::clone(&self.sender);
+                pyo3_async_runtimes::tokio::get_runtime().spawn(async move {
+                    slot.lock().await.take();
+                });
+                true
+            }
+        }
+    }
+
+    /// The queue's sender, starting the background task if it isn't running.
+    async fn sender(&self) -> mpsc::Sender<QueuedWrite> {
+        let mut sender = self.sender.lock().await;
+        sender
+            .get_or_insert_with(|| {
+                let (sender, receiver) = mpsc::channel(self.capacity);
+                tokio::spawn(self.clone().drain(receiver));
+                sender
+            })
+            .clone()
+    }
+
+    async fn drain(self, mut receiver: mpsc::Receiver<QueuedWrite>) {
+        while let Some(op) = receiver.recv().await {
+            match op {
+                QueuedWrite::Rows(data) => {
+                    if self.check_error().is_ok() {
+                        if let Err(e) = self.output.write(data).await {
+                            *self.error.lock().unwrap_or_else(|e| e.into_inner()) = Some(e);
+                        }
+                    }
+                }
+                QueuedWrite::Flush(done) => {
+                    let result = match self.check_error() {
+                        Ok(()) => self.output.flush().await,
+                        Err(e) => Err(e),
+                    };
+                    let _ = done.send(result);
+                }
+                QueuedWrite::Close(done) => {
+                    let result = self.check_error();
+                    let closed = self.output.close().await;
+                    let _ = done.send(result.and(closed));
+                    return;
+                }
+            }
+        }
+        // Every sender is gone without a close: write out what the output holds
+        if self.check_error().is_ok() {
+            let _ = self.output.flush().await;
+        }
+    }
+
+    /// Report a failed background write.
+    fn check_error(&self) -> PyResult<()> {
+        match self
+            .error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+        {
+            Some(e) => Err(Python::attach(|py| e.clone_ref(py))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn closed_error() -> PyErr {
+    CsvIoError::new_err("Background writer has stopped")
+}
+
+/// Admits operations one at a time in the order they were submitted.
+///
+/// Each Python call to a writer method runs as its own tokio task, and tasks don't
+/// start in call order. Taking a ticket synchronously in the method (while the caller
+/// still holds the GIL) and waiting for it in the task makes rows from concurrent
+/// `write_row()` calls land in the order the calls were made, and concurrent
+/// `read_row()` calls on a reader get consecutive rows in that order.
+#[derive(Clone, Default)]
+pub(crate) struct Turnstile {
+    state: Arc<StdMutex<TurnState>>,
+    turn_changed: Arc<Notify>,
+}
+
+#[derive(Default)]
+struct TurnState {
+    issued: u64,
+    serving: u64,
+    abandoned: BTreeSet<u64>, // Tickets dropped before their turn came
+}
+
+impl Turnstile {
+    /// Take the next ticket.
+    pub(crate) fn ticket(&self) -> Ticket {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let number = state.issued;
+        state.issued += 1;
+        Ticket {
+            turnstile: self.clone(),
+            number,
+        }
+    }
+}
+
+/// A place in a `Turnstile`'s line. Dropping it, served or not, lets later tickets on.
+pub(crate) struct Ticket {
+    turnstile: Turnstile,
+    number: u64,
+}
+
+impl Ticket {
+    /// Wait until every earlier ticket has been served or dropped.
+    pub(crate) async fn wait(&self) {
+        loop {
+            let notified = self.turnstile.turn_changed.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            {
+                let state = self
+                    .turnstile
+                    .state
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner());
+                if state.serving == self.number {
+                    return;
+                }
+            }
+            notified.await;
+        }
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        let mut state = self
+            .turnstile
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if state.serving != self.number {
+            // Cancelled before its turn: skip it when the line gets here
+            state.abandoned.insert(self.number);
+            return;
+        }
+        state.serving += 1;
+        loop {
+            let next = state.serving;
+            if !state.abandoned.remove(&next) {
+                break;
+            }
+            state.serving += 1;
+        }
+        drop(state);
+        self.turnstile.turn_changed.notify_waiters();
+    }
+}
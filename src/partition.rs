@@ -0,0 +1,247 @@
+//! Hive-style partitioned writing (`rapcsv.partition()`).
+//!
+//! Rows are routed to `<dst_dir>/<col>=<value>/.../<filename>` by the values of the
+//! partition columns, in one streaming pass on a blocking worker thread. Only a
+//! bounded number of part files are kept open: when more partitions are live, the
+//! least recently written one is closed and reopened for appending when it gets
+//! another row.
+
+use crate::errors::CsvIoError;
+use crate::ops::{
+    check_not_input, csv_err, extract_columns, open_reader, read_header, resolve_columns,
+    simple_dialect, spawn_blocking_py, RecordEncoder,
+};
+use crate::{validate_path, DialectConfig};
+use csv::StringRecord;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Directory name Hive uses for an empty partition value.
+const DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Escape a partition value for use as a directory name, as Hive does: characters
+/// that are special in paths or in Hive's own syntax become `%XX`.
+fn escape_value(value: &str) -> String {
+    if value.is_empty() {
+        return DEFAULT_PARTITION.to_string();
+    }
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\u{01}'..='\u{1F}'
+            | '\u{7F}'
+            | '"'
+            | '#'
+            | '%'
+            | '\''
+            | '*'
+            | '/'
+            | ':'
+            | '='
+            | '?'
+            | '\\'
+            | '{'
+            | '['
+            | ']'
+            | '^' => escaped.push_str(&format!("%{:02X}", c as u32)),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// One partition's output file.
+struct Part {
+    dir: String, // Relative to the output directory, e.g. "year=2024/month=01"
+    path: PathBuf,
+    rows: usize,
+    file: Option<BufWriter<File>>, // None while closed to stay under the open file limit
+    last_write: usize,             // Row number of the last write, for eviction
+}
+
+/// Route the rows of `src` into partition files under `dst_dir`. Returns each
+/// partition's directory and data row count, in order of first appearance.
+fn partition_file(
+    src: &str,
+    dst_dir: &str,
+    by: &[String],
+    keep_columns: bool,
+    filename: &str,
+    max_open_files: usize,
+    dialect: &DialectConfig,
+) -> PyResult<Vec<(String, usize)>> {
+    let io_err = |path: &Path, e: std::io::Error| {
+        CsvIoError::new_err(format!("Failed to write file '{}': {e}", path.display()))
+    };
+    let mut reader = open_reader(src, dialect)?;
+    let Some(header) = read_header(&mut reader, src)? else {
+        return Ok(Vec::new());
+    };
+    let key_columns = resolve_columns(by, &header, src)?;
+    let kept: Vec<usize> = (0..header.len())
+        .filter(|i| keep_columns || !key_columns.contains(i))
+        .collect();
+    let mut encoder = RecordEncoder::new(dialect);
+    let header_bytes = encoder.encode(kept.iter().map(|&i| &header[i]))?;
+
+    let mut parts: Vec<Part> = Vec::new();
+    let mut index: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut open = 0usize;
+    let mut record = StringRecord::new();
+    let mut row = 0usize;
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        row += 1;
+        let key: Vec<String> = key_columns
+            .iter()
+            .map(|&i| record.get(i).unwrap_or("").to_string())
+            .collect();
+        let n = match index.get(&key) {
+            Some(&n) => n,
+            None => {
+                let dir = by
+                    .iter()
+                    .zip(&key)
+                    .map(|(name, value)| format!("{}={}", escape_value(name), escape_value(value)))
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let path = Path::new(dst_dir).join(&dir).join(filename);
+                index.insert(key, parts.len());
+                parts.push(Part {
+                    dir,
+                    path,
+                    rows: 0,
+                    file: None,
+                    last_write: 0,
+                });
+                parts.len() - 1
+            }
+        };
+
+        if parts[n].file.is_none() {
+            if open == max_open_files {
+                let lru = parts
+                    .iter_mut()
+                    .filter(|part| part.file.is_some())
+                    .min_by_key(|part| part.last_write);
+                if let Some(part) = lru {
+                    if let Some(mut file) = part.file.take() {
+                        file.flush().map_err(|e| io_err(&part.path, e))?;
+                    }
+                    open -= 1;
+                }
+            }
+            let part = &mut parts[n];
+            let file = if part.rows == 0 {
+                if let Some(parent) = part.path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| io_err(parent, e))?;
+                }
+                check_not_input(&part.path.to_string_lossy(), &[src])?;
+                let mut file =
+                    BufWriter::new(File::create(&part.path).map_err(|e| io_err(&part.path, e))?);
+                file.write_all(&header_bytes)
+                    .map_err(|e| io_err(&part.path, e))?;
+                file
+            } else {
+                BufWriter::new(
+                    OpenOptions::new()
+                        .append(true)
+                        .open(&part.path)
+                        .map_err(|e| io_err(&part.path, e))?,
+                )
+            };
+            part.file = Some(file);
+            open += 1;
+        }
+
+        let part = &mut parts[n];
+        let bytes = encoder.encode(kept.iter().map(|&i| record.get(i).unwrap_or("")))?;
+        if let Some(file) = part.file.as_mut() {
+            file.write_all(&bytes).map_err(|e| io_err(&part.path, e))?;
+        }
+        part.rows += 1;
+        part.last_write = row;
+    }
+
+    for part in &mut parts {
+        if let Some(mut file) = part.file.take() {
+            file.flush().map_err(|e| io_err(&part.path, e))?;
+        }
+    }
+    Ok(parts
+        .into_iter()
+        .map(|part| (part.dir, part.rows))
+        .collect())
+}
+
+/// Write the rows of a CSV file into Hive-style partition directories.
+///
+/// # Arguments
+/// * `src` - Input CSV file (first row is the header)
+/// * `dst_dir` - Output directory; each partition is written to
+///   `<dst_dir>/<col>=<value>/.../<filename>`
+/// * `by` - Column name or list of column names to partition by, outermost first
+/// * `keep_columns` - Also write the partition columns into the files (default: False)
+/// * `filename` - Name of the file in each partition directory (default: "data.csv")
+/// * `max_open_files` - Most partition files kept open at once (default: 64)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+///
+/// Returns a dict mapping each partition's directory, relative to `dst_dir`, to the
+/// number of data rows written to it, in order of first appearance.
+#[pyfunction]
+#[pyo3(signature = (src, dst_dir, by, keep_columns = false, filename = "data.csv", max_open_files = 64, delimiter = None, quotechar = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn partition(
+    py: Python<'_>,
+    src: String,
+    dst_dir: String,
+    by: &Bound<'_, PyAny>,
+    keep_columns: bool,
+    filename: &str,
+    max_open_files: usize,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&src)?;
+    validate_path(&dst_dir)?;
+    let by = extract_columns(by, "by")?;
+    if filename.is_empty() || filename.contains(['/', '\\']) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "filename must be a file name without directories",
+        ));
+    }
+    if max_open_files == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "max_open_files must be at least 1",
+        ));
+    }
+    let filename = filename.to_string();
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    spawn_blocking_py(py, move || {
+        let parts = partition_file(
+            &src,
+            &dst_dir,
+            &by,
+            keep_columns,
+            &filename,
+            max_open_files,
+            &dialect,
+        )?;
+        Python::attach(|py| -> PyResult<Py<PyAny>> {
+            let result = PyDict::new(py);
+            for (dir, rows) in parts {
+                result.set_item(dir, rows)?;
+            }
+            Ok(result.into_any().unbind())
+        })
+    })
+}
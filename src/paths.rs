@@ -0,0 +1,245 @@
+//! File names as the operating system spells them.
+//!
+//! rapcsv shows paths as strings, in error messages and wherever a path is handed
+//! back. Two kinds of name don't survive that as they are:
+//!
+//! - Names that aren't valid Unicode: undecodable bytes on Unix, unpaired surrogates
+//!   on Windows. Python hands these over as `bytes` or as `str` with lone surrogates
+//!   (`os.fsdecode`). A `FilePath` keeps the real name to open alongside a readable
+//!   stand-in to show, with each bad byte written `\xNN` (or `\u{NNNN}` for a
+//!   surrogate).
+//! - Windows paths past `MAX_PATH`, on local disks or UNC shares
+//!   (`\\server\share\...`). These are opened through their `\\?\` extended-length
+//!   form, which also lifts the limit where long path support isn't enabled.
+//!
+//! `native()` turns a `FilePath` into the path to open.
+
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use std::convert::Infallible;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::ops::Deref;
+use std::path::PathBuf;
+
+/// A path to open, shown as text. Derefs to that text, so it reads like a `String`
+/// everywhere but where the file is opened.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct FilePath {
+    text: String,
+    os: Option<OsString>, // The real name, when it isn't valid Unicode
+}
+
+impl FilePath {
+    /// Carry an OS path, keeping the real name if it isn't Unicode.
+    pub(crate) fn new(path: OsString) -> Self {
+        match path.into_string() {
+            Ok(text) => FilePath { text, os: None },
+            Err(os) => FilePath {
+                text: stand_in(&os),
+                os: Some(os),
+            },
+        }
+    }
+
+    /// Whether the name is valid Unicode, and so is the same as its text.
+    pub(crate) fn is_unicode(&self) -> bool {
+        self.os.is_none()
+    }
+
+    /// Split the path around the first `separator`, as for `archive.zip::member.csv`.
+    pub(crate) fn split_once(&self, separator: &str) -> Option<(FilePath, FilePath)> {
+        let (text_before, text_after) = self.text.split_once(separator)?;
+        let Some(os) = &self.os else {
+            return Some((text_before.into(), text_after.into()));
+        };
+        let bytes = os.as_encoded_bytes();
+        let at = bytes
+            .windows(separator.len())
+            .position(|window| window == separator.as_bytes())?;
+        // SAFETY: both halves are split next to `separator`, which is valid UTF-8
+        let (before, after) = unsafe {
+            (
+                OsStr::from_encoded_bytes_unchecked(&bytes[..at]),
+                OsStr::from_encoded_bytes_unchecked(&bytes[at + separator.len()..]),
+            )
+        };
+        let half = |text: &str, os: &OsStr| FilePath {
+            text: text.to_string(),
+            os: os.to_str().is_none().then(|| os.to_os_string()),
+        };
+        Some((half(text_before, before), half(text_after, after)))
+    }
+
+    /// This path with `suffix` added to its name, as for a sidecar file.
+    pub(crate) fn with_suffix(&self, suffix: &str) -> Self {
+        FilePath {
+            text: format!("{}{suffix}", self.text),
+            os: self.os.as_ref().map(|os| {
+                let mut os = os.clone();
+                os.push(suffix);
+                os
+            }),
+        }
+    }
+}
+
+impl Deref for FilePath {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for FilePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// The name Python would give the path: `str`, with lone surrogates for a name that
+/// isn't Unicode, as `os.fsdecode` returns it.
+impl<'py> IntoPyObject<'py> for FilePath {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        match self.os {
+            Some(os) => os.into_pyobject(py),
+            None => self.text.into_pyobject(py),
+        }
+    }
+}
+
+impl From<String> for FilePath {
+    fn from(text: String) -> Self {
+        FilePath { text, os: None }
+    }
+}
+
+impl From<&str> for FilePath {
+    fn from(text: &str) -> Self {
+        FilePath::from(text.to_string())
+    }
+}
+
+impl From<&String> for FilePath {
+    fn from(text: &String) -> Self {
+        FilePath::from(text.clone())
+    }
+}
+
+impl From<&FilePath> for FilePath {
+    fn from(path: &FilePath) -> Self {
+        path.clone()
+    }
+}
+
+impl From<PathBuf> for FilePath {
+    fn from(path: PathBuf) -> Self {
+        FilePath::new(path.into_os_string())
+    }
+}
+
+#[cfg(unix)]
+fn stand_in(path: &OsStr) -> String {
+    use std::os::unix::ffi::OsStrExt;
+    let mut text = String::new();
+    for chunk in path.as_bytes().utf8_chunks() {
+        text.push_str(chunk.valid());
+        for byte in chunk.invalid() {
+            text.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    text
+}
+
+#[cfg(windows)]
+fn stand_in(path: &OsStr) -> String {
+    use std::os::windows::ffi::OsStrExt;
+    char::decode_utf16(path.encode_wide())
+        .map(|c| match c {
+            Ok(c) => c.to_string(),
+            Err(e) => format!("\\u{{{:04x}}}", e.unpaired_surrogate()),
+        })
+        .collect()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn stand_in(path: &OsStr) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// The path to open for `path`.
+pub(crate) fn native(path: &FilePath) -> PathBuf {
+    extended_length(match &path.os {
+        Some(os) => PathBuf::from(os),
+        None => PathBuf::from(&path.text),
+    })
+}
+
+#[cfg(not(windows))]
+fn extended_length(path: PathBuf) -> PathBuf {
+    path
+}
+
+/// Longest path opened as it is: `MAX_PATH` less the room CreateDirectory keeps for
+/// a file name.
+#[cfg(windows)]
+const SHORT_PATH: usize = 248;
+
+/// The `\\?\` form of a long Windows path: `\\?\C:\...` for a drive and
+/// `\\?\UNC\server\share\...` for a share. Verbatim paths skip normalisation, so the
+/// path is made absolute (resolving `.`, `..` and `/`) first.
+#[cfg(windows)]
+fn extended_length(path: PathBuf) -> PathBuf {
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+    use std::path::{Component, Prefix};
+
+    if path.as_os_str().len() < SHORT_PATH {
+        return path;
+    }
+    let Ok(absolute) = std::path::absolute(&path) else {
+        return path;
+    };
+    let wide: Vec<u16> = absolute.as_os_str().encode_wide().collect();
+    let extended: Vec<u16> = match absolute.components().next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(_) => r"\\?\".encode_utf16().chain(wide).collect(),
+            // Drop the leading `\\` of `\\server\share`
+            Prefix::UNC(..) => r"\\?\UNC\"
+                .encode_utf16()
+                .chain(wide.into_iter().skip(2))
+                .collect(),
+            // Already verbatim, or a device path
+            _ => return absolute,
+        },
+        _ => return absolute,
+    };
+    PathBuf::from(OsString::from_wide(&extended))
+}
+
+/// Why a Windows path can't be opened, for paths whose problem would otherwise show
+/// up as a puzzling OS error.
+#[cfg(windows)]
+pub(crate) fn windows_problem(path: &str) -> Option<&'static str> {
+    if let Some(rest) = path.strip_prefix(r"\\?\") {
+        if rest.contains('/') {
+            return Some(r"Extended-length paths (\\?\) must use backslashes, not '/'");
+        }
+        return None;
+    }
+    let unc = path
+        .strip_prefix(r"\\")
+        .or_else(|| path.strip_prefix("//"))?;
+    if unc.starts_with(['.', '?']) {
+        return None; // Device paths
+    }
+    let mut parts = unc.split(['\\', '/']);
+    match (parts.next(), parts.next()) {
+        (Some(server), Some(share)) if !server.is_empty() && !share.is_empty() => None,
+        _ => Some(r"UNC paths must name a server and a share (\\server\share\...)"),
+    }
+}
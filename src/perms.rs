@@ -0,0 +1,104 @@
+//! Permissions of the files a `Writer` creates (`file_mode=` and `file_group=`).
+//!
+//! A new file normally gets the mode the process umask leaves, which on a shared host
+//! is often readable by everyone. With `file_mode` the file is created exclusively and
+//! given exactly that mode (umask aside) and, with `file_group`, that group before any
+//! row is written to it. A file that already exists is appended to as it is, its
+//! permissions untouched.
+
+use crate::paths::FilePath;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Mode and group given to a newly created file.
+#[derive(Debug)]
+pub(crate) struct FilePermissions {
+    #[cfg_attr(not(unix), allow(dead_code))]
+    mode: Option<u32>,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    group: Option<u32>, // Group id
+}
+
+impl FilePermissions {
+    /// Validate the `file_mode` and `file_group` arguments; `None` when neither is set.
+    /// A group may be given as a gid or a group name.
+    pub(crate) fn from_args(
+        py: Python<'_>,
+        mode: Option<u32>,
+        group: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Option<Self>> {
+        if mode.is_none() && group.is_none() {
+            return Ok(None);
+        }
+        if !cfg!(unix) {
+            return Err(PyValueError::new_err(
+                "file_mode and file_group are only supported on Unix",
+            ));
+        }
+        if mode.is_some_and(|mode| mode > 0o7777) {
+            return Err(PyValueError::new_err(
+                "file_mode must be a permission mode such as 0o640",
+            ));
+        }
+        let group = match group {
+            None => None,
+            Some(group) => Some(match group.extract::<u32>() {
+                Ok(gid) => gid,
+                Err(_) => {
+                    let name: String = group.extract().map_err(|_| {
+                        PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                            "file_group must be a group name or a gid",
+                        )
+                    })?;
+                    py.import("grp")?
+                        .call_method1("getgrnam", (&name,))
+                        .map_err(|_| PyValueError::new_err(format!("unknown group '{name}'")))?
+                        .getattr("gr_gid")?
+                        .extract()?
+                }
+            }),
+        };
+        Ok(Some(FilePermissions { mode, group }))
+    }
+
+    /// Create `path` with these permissions, unless it already exists. The file is
+    /// created empty and the writer then opens it as usual.
+    #[cfg(unix)]
+    pub(crate) fn create(&self, path: &FilePath) -> PyResult<()> {
+        use crate::errors::CsvIoError;
+        use crate::paths;
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        if let Some(mode) = self.mode {
+            options.mode(mode);
+        }
+        let file = match options.open(paths::native(path)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => return Ok(()),
+            Err(e) => {
+                return Err(CsvIoError::new_err(format!(
+                    "Failed to create file {path}: {e}"
+                )))
+            }
+        };
+        let failed = |e: std::io::Error| {
+            CsvIoError::new_err(format!("Failed to set permissions of file {path}: {e}"))
+        };
+        if let Some(group) = self.group {
+            std::os::unix::fs::fchown(&file, None, Some(group)).map_err(failed)?;
+        }
+        // The umask applied on creation; chown may also have cleared setgid
+        if let Some(mode) = self.mode {
+            file.set_permissions(std::fs::Permissions::from_mode(mode))
+                .map_err(failed)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn create(&self, _path: &FilePath) -> PyResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+//! Files read or written through a pipe and a helper thread.
+//!
+//! Archive members, encrypted files and files on SFTP servers aren't plain bytes on
+//! disk, so a thread converts or fetches them: when reading it decodes the file into
+//! a pipe whose reading end the stream reads like any other file, and when writing it
+//! encodes whatever the `Writer` writes into the pipe. Buffering, decoding, parsing
+//! and encoding all work unchanged, and nothing is written to disk in between. The
+//! thread's outcome is checked when the pipe reaches EOF or the writer closes, so a
+//! failure never looks like a short file.
+
+use crate::errors::CsvIoError;
+use pyo3::prelude::*;
+use std::io::{PipeReader, PipeWriter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::fs::File;
+use tokio::sync::{oneshot, Mutex};
+
+/// A thread decoding into the pipe a stream reads from.
+#[derive(Debug)]
+pub(crate) struct PipeSource {
+    outcome: Arc<StdMutex<Option<Result<(), String>>>>, // Set by the thread before it closes the pipe
+}
+
+impl PipeSource {
+    /// Raise the error the decoding thread stopped with, if any. Called at EOF.
+    pub(crate) fn finish(&self) -> PyResult<()> {
+        match &*self.outcome.lock().unwrap_or_else(|e| e.into_inner()) {
+            Some(Err(e)) => Err(CsvIoError::new_err(e.clone())),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A thread encoding what a `Writer` writes into the pipe.
+#[derive(Debug)]
+pub(crate) struct PipeSink {
+    done: Mutex<Option<oneshot::Receiver<Result<(), String>>>>, // Until the thread reports
+    outcome: StdMutex<Result<(), String>>,
+    existing: Mutex<Option<oneshot::Receiver<u64>>>, // Until the destination's length is known
+    has_content: AtomicBool,
+}
+
+impl PipeSink {
+    /// Learn from `receiver` how long the destination was before anything was written,
+    /// for a thread that appends to it.
+    #[cfg_attr(not(feature = "sftp"), allow(dead_code))]
+    pub(crate) fn appending(mut self, receiver: oneshot::Receiver<u64>) -> Self {
+        self.existing = Mutex::new(Some(receiver));
+        self
+    }
+
+    /// Whether the destination already held data, waiting for the thread to find out.
+    /// False when the thread doesn't append, or stopped before it could tell.
+    pub(crate) async fn has_content(&self) -> bool {
+        let mut existing = self.existing.lock().await;
+        if let Some(receiver) = existing.as_mut() {
+            let len = receiver.await.unwrap_or(0);
+            self.has_content.store(len > 0, Ordering::Relaxed);
+            *existing = None;
+        }
+        self.has_content.load(Ordering::Relaxed)
+    }
+
+    /// The error the thread stopped with, if it has stopped with one. A write to the
+    /// pipe fails once the thread is gone, and this is the reason.
+    pub(crate) fn failure(&self) -> Option<PyErr> {
+        if let Ok(mut done) = self.done.try_lock() {
+            if let Some(result) = done.as_mut().and_then(|receiver| receiver.try_recv().ok()) {
+                *self.outcome.lock().unwrap_or_else(|e| e.into_inner()) = result;
+                *done = None;
+            }
+        }
+        let outcome = self.outcome.lock().unwrap_or_else(|e| e.into_inner());
+        outcome.clone().err().map(CsvIoError::new_err)
+    }
+
+    /// Wait for the thread to finish after the pipe was closed, raising the error it
+    /// stopped with, if any.
+    pub(crate) async fn finish(&self) -> PyResult<()> {
+        let mut done = self.done.lock().await;
+        if let Some(receiver) = done.as_mut() {
+            let result = receiver
+                .await
+                .unwrap_or_else(|_| Err("pipe writer thread stopped".to_string()));
+            *self.outcome.lock().unwrap_or_else(|e| e.into_inner()) = result;
+            *done = None;
+        }
+        let outcome = self.outcome.lock().unwrap_or_else(|e| e.into_inner());
+        outcome.clone().map_err(CsvIoError::new_err)
+    }
+}
+
+/// One end of a pipe as a file.
+#[cfg(unix)]
+fn pipe_file(end: impl Into<std::os::fd::OwnedFd>) -> File {
+    File::from_std(std::fs::File::from(end.into()))
+}
+
+/// One end of a pipe as a file.
+#[cfg(windows)]
+fn pipe_file(end: impl Into<std::os::windows::io::OwnedHandle>) -> File {
+    File::from_std(std::fs::File::from(end.into()))
+}
+
+fn pipe() -> PyResult<(PipeReader, PipeWriter)> {
+    std::io::pipe().map_err(|e| CsvIoError::new_err(format!("Failed to create pipe: {e}")))
+}
+
+/// Run `copy` on its own thread, writing into a pipe. Returns the file to read what it
+/// writes from.
+pub(crate) fn spawn_reader<F>(copy: F) -> PyResult<(File, PipeSource)>
+where
+    F: FnOnce(&mut PipeWriter) -> Result<(), String> + Send + 'static,
+{
+    let (reader, mut writer) = pipe()?;
+    let outcome = Arc::new(StdMutex::new(None));
+    let thread_outcome = outcome.clone();
+    std::thread::spawn(move || {
+        let result = copy(&mut writer);
+        *thread_outcome.lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+        drop(writer); // EOF for the reader, now that the outcome is known
+    });
+    Ok((pipe_file(reader), PipeSource { outcome }))
+}
+
+/// Run `copy` on its own thread, reading from a pipe until the writing end is closed.
+/// Returns the file to write to.
+pub(crate) fn spawn_writer<F>(copy: F) -> PyResult<(File, PipeSink)>
+where
+    F: FnOnce(&mut PipeReader) -> Result<(), String> + Send + 'static,
+{
+    let (mut reader, writer) = pipe()?;
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(copy(&mut reader)); // The writer may be gone
+    });
+    Ok((
+        pipe_file(writer),
+        PipeSink {
+            done: Mutex::new(Some(receiver)),
+            outcome: StdMutex::new(Ok(())),
+            existing: Mutex::new(None),
+            has_content: AtomicBool::new(false),
+        },
+    ))
+}
@@ -0,0 +1,220 @@
+//! Single-pass column profiling.
+//!
+//! Distinct counts are exact up to a small cardinality and then switch to a
+//! HyperLogLog estimate, so profiling uses bounded memory regardless of file size.
+
+use crate::ops::{csv_err, open_reader, read_header, simple_dialect, spawn_blocking_py};
+use crate::validate_path;
+use csv::StringRecord;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// Distinct values tracked exactly before falling back to the HyperLogLog estimate.
+const EXACT_DISTINCT_LIMIT: usize = 10_000;
+
+/// HyperLogLog precision (2^14 registers, ~0.8% standard error).
+const HLL_PRECISION: u32 = 14;
+
+/// Approximate distinct counter: exact for small cardinalities, HyperLogLog beyond.
+struct DistinctCounter {
+    exact: Option<HashSet<u64>>,
+    registers: Vec<u8>,
+}
+
+impl DistinctCounter {
+    fn new() -> Self {
+        DistinctCounter {
+            exact: Some(HashSet::new()),
+            registers: vec![0; 1 << HLL_PRECISION],
+        }
+    }
+
+    fn insert(&mut self, value: &str) {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let index = (hash >> (64 - HLL_PRECISION)) as usize;
+        let rank = ((hash << HLL_PRECISION) | (1 << (HLL_PRECISION - 1))).leading_zeros() as u8 + 1;
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+
+        if let Some(exact) = &mut self.exact {
+            exact.insert(hash);
+            if exact.len() > EXACT_DISTINCT_LIMIT {
+                self.exact = None;
+            }
+        }
+    }
+
+    fn estimate(&self) -> u64 {
+        if let Some(exact) = &self.exact {
+            return exact.len() as u64;
+        }
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let estimate = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            // Small-range correction (linear counting)
+            (m * (m / zeros as f64).ln()).round() as u64
+        } else {
+            estimate.round() as u64
+        }
+    }
+}
+
+/// Running statistics for one column.
+struct ColumnProfile {
+    count: u64,
+    nulls: u64,
+    distinct: DistinctCounter,
+    max_length: usize,
+    numeric: bool,
+    integer: bool,
+    sum: f64,
+    min_number: f64,
+    max_number: f64,
+    min_text: Option<String>,
+    max_text: Option<String>,
+}
+
+impl ColumnProfile {
+    fn new() -> Self {
+        ColumnProfile {
+            count: 0,
+            nulls: 0,
+            distinct: DistinctCounter::new(),
+            max_length: 0,
+            numeric: true,
+            integer: true,
+            sum: 0.0,
+            min_number: f64::INFINITY,
+            max_number: f64::NEG_INFINITY,
+            min_text: None,
+            max_text: None,
+        }
+    }
+
+    fn update(&mut self, field: Option<&str>) {
+        self.count += 1;
+        let value = match field {
+            Some(value) if !value.is_empty() => value,
+            _ => {
+                self.nulls += 1;
+                return;
+            }
+        };
+        self.distinct.insert(value);
+        self.max_length = self.max_length.max(value.chars().count());
+
+        if self.numeric {
+            let trimmed = value.trim();
+            match trimmed.parse::<f64>() {
+                Ok(n) if n.is_finite() => {
+                    self.integer &= trimmed.parse::<i64>().is_ok();
+                    self.sum += n;
+                    self.min_number = self.min_number.min(n);
+                    self.max_number = self.max_number.max(n);
+                }
+                _ => self.numeric = false,
+            }
+        }
+        if self.min_text.as_deref().is_none_or(|min| value < min) {
+            self.min_text = Some(value.to_string());
+        }
+        if self.max_text.as_deref().is_none_or(|max| value > max) {
+            self.max_text = Some(value.to_string());
+        }
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        let non_null = self.count - self.nulls;
+        let numeric = self.numeric && non_null > 0;
+        let kind = match (numeric, self.integer) {
+            _ if non_null == 0 => "empty",
+            (true, true) => "integer",
+            (true, false) => "float",
+            (false, _) => "string",
+        };
+        dict.set_item("type", kind)?;
+        dict.set_item("count", self.count)?;
+        dict.set_item("nulls", self.nulls)?;
+        dict.set_item("distinct", self.distinct.estimate())?;
+        if numeric {
+            if self.integer {
+                dict.set_item("min", self.min_number as i64)?;
+                dict.set_item("max", self.max_number as i64)?;
+            } else {
+                dict.set_item("min", self.min_number)?;
+                dict.set_item("max", self.max_number)?;
+            }
+            dict.set_item("mean", self.sum / non_null as f64)?;
+        } else {
+            dict.set_item("min", self.min_text.as_deref())?;
+            dict.set_item("max", self.max_text.as_deref())?;
+            dict.set_item("mean", py.None())?;
+        }
+        dict.set_item("max_length", self.max_length)?;
+        Ok(dict)
+    }
+}
+
+/// Compute a profile for every column of `path`.
+fn profile_file(
+    path: &str,
+    dialect: &crate::DialectConfig,
+) -> PyResult<(StringRecord, Vec<ColumnProfile>)> {
+    let mut reader = open_reader(path, dialect)?;
+    let header = read_header(&mut reader, path)?.unwrap_or_default();
+    let mut columns: Vec<ColumnProfile> = header.iter().map(|_| ColumnProfile::new()).collect();
+    let mut record = StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(path, e)),
+        }
+        for (i, column) in columns.iter_mut().enumerate() {
+            column.update(record.get(i));
+        }
+    }
+    Ok((header, columns))
+}
+
+/// Profile every column of a CSV file in one pass.
+///
+/// # Arguments
+/// * `path` - CSV file (first row is the header)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+///
+/// Returns a dict mapping each column name to its statistics: `type`, `count`,
+/// `nulls`, `distinct`, `min`, `max`, `mean` (numeric columns only) and `max_length`.
+#[pyfunction]
+#[pyo3(signature = (path, delimiter = None, quotechar = None))]
+pub(crate) fn profile(
+    py: Python<'_>,
+    path: String,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&path)?;
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    spawn_blocking_py(py, move || {
+        let (header, columns) = profile_file(&path, &dialect)?;
+        Python::attach(|py| -> PyResult<Py<PyAny>> {
+            let result = PyDict::new(py);
+            for (name, column) in header.iter().zip(&columns) {
+                result.set_item(name, column.to_dict(py)?)?;
+            }
+            Ok(result.into_any().unbind())
+        })
+    })
+}
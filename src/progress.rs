@@ -0,0 +1,131 @@
+//! Progress reporting to a Python callback (`progress=` on readers, writers and the
+//! file utilities).
+//!
+//! The callback is called as `callback(bytes_read, bytes_total, rows_emitted)` every
+//! `progress_interval` rows and once more when the job finishes. `bytes_total` is `None`
+//! when the size isn't known up front (file handles, writers).
+
+use crate::paths::{self, FilePath};
+use pyo3::prelude::*;
+use std::sync::Mutex as StdMutex;
+
+/// Rows between progress callbacks when `progress_interval` is not given.
+pub(crate) const DEFAULT_PROGRESS_INTERVAL: usize = 10_000;
+
+#[derive(Default)]
+struct ProgressState {
+    bytes: u64,
+    rows: usize,
+    next_report: usize,
+    reported: Option<(u64, usize)>, // Values passed to the last callback
+}
+
+/// Calls a Python progress callback as rows are processed.
+pub(crate) struct Progress {
+    callback: Py<PyAny>,
+    interval: usize,
+    bytes_total: Option<u64>,
+    state: StdMutex<ProgressState>,
+}
+
+impl Progress {
+    /// Validate the `progress`/`progress_interval` arguments; `None` when no callback is given.
+    pub(crate) fn from_args(
+        py: Python<'_>,
+        callback: Option<Py<PyAny>>,
+        interval: Option<usize>,
+        bytes_total: Option<u64>,
+    ) -> PyResult<Option<Self>> {
+        let Some(callback) = callback else {
+            return Ok(None);
+        };
+        if !callback.bind(py).is_callable() {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "progress must be callable",
+            ));
+        }
+        let interval = interval.unwrap_or(DEFAULT_PROGRESS_INTERVAL);
+        if interval == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "progress_interval must be at least 1",
+            ));
+        }
+        Ok(Some(Progress {
+            callback,
+            interval,
+            bytes_total,
+            state: StdMutex::new(ProgressState {
+                next_report: interval,
+                ..ProgressState::default()
+            }),
+        }))
+    }
+
+    /// Total size of `paths`, or `None` if any of them can't be inspected.
+    pub(crate) fn total_size<P>(files: &[P]) -> Option<u64>
+    where
+        for<'a> &'a P: Into<FilePath>,
+    {
+        files
+            .iter()
+            .map(|path| {
+                std::fs::metadata(paths::native(&path.into()))
+                    .ok()
+                    .map(|m| m.len())
+            })
+            .sum()
+    }
+
+    /// Record `rows` more rows and `bytes` more bytes, calling back once an interval is
+    /// crossed.
+    pub(crate) fn advance(&self, rows: usize, bytes: u64) -> PyResult<()> {
+        self.step(|state| {
+            state.bytes += bytes;
+            state.rows += rows;
+        })
+    }
+
+    /// Set the totals so far, calling back once an interval is crossed.
+    pub(crate) fn update(&self, bytes: u64, rows: usize) -> PyResult<()> {
+        self.step(|state| {
+            state.bytes = bytes;
+            state.rows = rows;
+        })
+    }
+
+    fn step(&self, apply: impl FnOnce(&mut ProgressState)) -> PyResult<()> {
+        let due = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            apply(&mut state);
+            if state.rows < state.next_report {
+                return Ok(());
+            }
+            state.next_report = (state.rows / self.interval + 1) * self.interval;
+            state.reported = Some((state.bytes, state.rows));
+            (state.bytes, state.rows)
+        };
+        self.call(due)
+    }
+
+    /// Report the final totals, unless the last callback already reported them.
+    pub(crate) fn finish(&self) -> PyResult<()> {
+        let due = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let current = (state.bytes, state.rows);
+            if state.reported == Some(current) {
+                return Ok(());
+            }
+            state.reported = Some(current);
+            current
+        };
+        self.call(due)
+    }
+
+    fn call(&self, (bytes, rows): (u64, usize)) -> PyResult<()> {
+        Python::attach(|py| {
+            self.callback
+                .call1(py, (bytes, self.bytes_total, rows))
+                .map(drop)
+        })
+    }
+}
@@ -0,0 +1,167 @@
+//! Column projection (`columns=` on `Reader`).
+//!
+//! Columns are chosen by position or by header name; names are resolved against the
+//! first file's header row when it is read. Once the positions are known the parsers
+//! only decode the selected fields: the others stay byte ranges in the parser's output,
+//! never checked as UTF-8 field by field or copied into strings, so reading a few
+//! columns of a wide file allocates no more than a file holding only those columns.
+
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use std::sync::OnceLock;
+
+/// A column as the caller named it.
+#[derive(Debug)]
+pub(crate) enum Column {
+    Name(String),
+    Index(usize),
+}
+
+impl Column {
+    /// Parse one entry of a column list: a header name or a 0-based position.
+    /// `option` names the argument in errors.
+    pub(crate) fn parse(column: &Bound<'_, PyAny>, option: &str) -> PyResult<Self> {
+        if let Ok(index) = column.extract::<usize>() {
+            Ok(Column::Index(index))
+        } else if let Ok(name) = column.extract::<String>() {
+            Ok(Column::Name(name))
+        } else {
+            Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                "{option} must hold column names (str) or non-negative positions (int)"
+            )))
+        }
+    }
+
+    /// Position of the column in `header`, erroring for a name it doesn't hold.
+    pub(crate) fn position(&self, header: &[String]) -> PyResult<usize> {
+        match self {
+            Column::Index(index) => Ok(*index),
+            Column::Name(name) => header.iter().position(|h| h == name).ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "column '{name}' is not in the header"
+                ))
+            }),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Column::Name(name) => format!("'{name}'"),
+            Column::Index(index) => index.to_string(),
+        }
+    }
+}
+
+/// The columns a reader returns, in the order they were given.
+pub(crate) struct Projection {
+    columns: Vec<Column>,
+    indices: OnceLock<Vec<usize>>,
+    names: OnceLock<Vec<String>>, // Header names of the selected columns, when given by name
+}
+
+impl Projection {
+    /// Parse a `columns` argument: a non-empty list of header names and 0-based
+    /// positions.
+    pub(crate) fn parse(columns: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if columns.is_instance_of::<PyString>() {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "columns must be a list of column names or positions, not a string",
+            ));
+        }
+        let mut parsed = Vec::new();
+        for column in columns.try_iter()? {
+            parsed.push(Column::parse(&column?, "columns")?);
+        }
+        if parsed.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "columns must name at least one column",
+            ));
+        }
+        let projection = Projection {
+            columns: parsed,
+            indices: OnceLock::new(),
+            names: OnceLock::new(),
+        };
+        // Positions alone need no header
+        if !projection.has_names() {
+            projection.resolve(&[])?;
+        }
+        Ok(projection)
+    }
+
+    /// Whether any column is given by name, needing a header row to resolve.
+    pub(crate) fn has_names(&self) -> bool {
+        self.columns.iter().any(|c| matches!(c, Column::Name(_)))
+    }
+
+    /// Positions of the selected columns, once known.
+    pub(crate) fn indices(&self) -> Option<&[usize]> {
+        self.indices.get().map(Vec::as_slice)
+    }
+
+    /// Header names of the selected columns, once resolved from a header row.
+    pub(crate) fn names(&self) -> Option<&[String]> {
+        self.names.get().map(Vec::as_slice)
+    }
+
+    /// Resolve names against `header`, the first file's header row.
+    pub(crate) fn resolve(&self, header: &[String]) -> PyResult<()> {
+        if self.indices.get().is_some() {
+            return Ok(());
+        }
+        let indices = self
+            .columns
+            .iter()
+            .map(|column| column.position(header))
+            .collect::<PyResult<Vec<_>>>()?;
+        let names: Vec<String> = indices
+            .iter()
+            .filter_map(|&i| header.get(i).cloned())
+            .collect();
+        self.set(indices)?;
+        if self.has_names() {
+            let _ = self.names.set(names);
+        }
+        Ok(())
+    }
+
+    fn set(&self, indices: Vec<usize>) -> PyResult<()> {
+        for (i, index) in indices.iter().enumerate() {
+            if indices[..i].contains(index) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "columns selects column {} more than once",
+                    self.columns[i].describe()
+                )));
+            }
+        }
+        let _ = self.indices.set(indices); // Resolved by a concurrent read: same result
+        Ok(())
+    }
+}
+
+/// Take the fields at `indices` out of a parsed row. Positions past the end of a short
+/// row are left out.
+pub(crate) fn select(indices: &[usize], mut row: Vec<String>) -> Vec<String> {
+    indices
+        .iter()
+        .filter_map(|&i| row.get_mut(i).map(std::mem::take))
+        .collect()
+}
+
+/// Decode the fields at `indices` from parser output: `ends[i]` is the end of field
+/// `i` in `output`. Only these fields are checked and copied.
+pub(crate) fn select_fields(
+    indices: &[usize],
+    output: &[u8],
+    ends: &[usize],
+) -> Result<Vec<String>, std::str::Utf8Error> {
+    let mut row = Vec::with_capacity(indices.len());
+    for &i in indices {
+        let Some(&end) = ends.get(i) else {
+            continue;
+        };
+        let start = if i == 0 { 0 } else { ends[i - 1] };
+        row.push(std::str::from_utf8(&output[start..end])?.to_string());
+    }
+    Ok(row)
+}
@@ -0,0 +1,49 @@
+//! Output limits (`max_rows=` and `max_bytes=` on `Writer`).
+//!
+//! Services that generate exports for users to download need a hard stop on runaway
+//! output. A write that would take the writer past a limit writes none of its rows
+//! and raises `QuotaExceeded`; everything written before it stays in place.
+
+use crate::errors::QuotaExceeded;
+use pyo3::prelude::*;
+use std::sync::Mutex as StdMutex;
+
+#[derive(Debug)]
+pub(crate) struct Quota {
+    max_rows: Option<usize>,
+    max_bytes: Option<u64>,
+    used: StdMutex<(usize, u64)>, // Data rows and bytes (the header included) written
+}
+
+impl Quota {
+    /// Validate the `max_rows`/`max_bytes` arguments; `None` when neither is set.
+    pub(crate) fn from_args(max_rows: Option<usize>, max_bytes: Option<u64>) -> Option<Self> {
+        if max_rows.is_none() && max_bytes.is_none() {
+            return None;
+        }
+        Some(Quota {
+            max_rows,
+            max_bytes,
+            used: StdMutex::new((0, 0)),
+        })
+    }
+
+    /// Count `rows` rows of `bytes` bytes against the limits, or raise
+    /// `QuotaExceeded` without counting them if they don't fit.
+    pub(crate) fn charge(&self, rows: usize, bytes: u64) -> PyResult<()> {
+        let mut used = self.used.lock().unwrap_or_else(|e| e.into_inner());
+        let (total_rows, total_bytes) = (used.0 + rows, used.1 + bytes);
+        if let Some(max) = self.max_rows.filter(|&max| total_rows > max) {
+            return Err(QuotaExceeded::new_err(format!(
+                "Writing {rows} more rows would make {total_rows}, over max_rows={max}"
+            )));
+        }
+        if let Some(max) = self.max_bytes.filter(|&max| total_bytes > max) {
+            return Err(QuotaExceeded::new_err(format!(
+                "Writing {bytes} more bytes would make {total_bytes}, over max_bytes={max}"
+            )));
+        }
+        *used = (total_rows, total_bytes);
+        Ok(())
+    }
+}
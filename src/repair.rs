@@ -0,0 +1,280 @@
+//! Best-effort repair of broken CSV files (`rapcsv.repair()`).
+//!
+//! The file is rewritten record by record on a blocking worker thread, applying the
+//! chosen fixes and noting each change with the line it was made on. A quote still open
+//! at the end of the file (found by the same byte scan as `lint()`) is closed at the
+//! end of the line it was opened on, by splicing a quote into the input stream before
+//! it is parsed. Fixes that weren't asked for leave the records as parsed.
+
+use crate::errors::CsvIoError;
+use crate::lint::{scan_bytes, LineCounter};
+use crate::ops::{check_not_input, csv_err, simple_dialect, spawn_blocking_py};
+use crate::{validate_path, DialectConfig};
+use csv::{ByteRecord, ReaderBuilder, WriterBuilder};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::borrow::Cow;
+use std::io::{Read, Seek, SeekFrom};
+
+/// Fixes `repair()` can apply.
+#[derive(Clone, Copy)]
+struct Fixes {
+    quotes: bool,   // Close a quote left open at the end of the file
+    ragged: bool,   // Pad short rows and truncate long ones to the header's width
+    encoding: bool, // Replace invalid UTF-8 with U+FFFD
+}
+
+impl Fixes {
+    /// Parse a `fixes` argument: a fix name or a list of them. None means all.
+    fn parse(fixes: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let Some(fixes) = fixes else {
+            return Ok(Fixes {
+                quotes: true,
+                ragged: true,
+                encoding: true,
+            });
+        };
+        let names: Vec<String> = match fixes.extract::<String>() {
+            Ok(name) => vec![name],
+            Err(_) => fixes.extract()?,
+        };
+        let mut parsed = Fixes {
+            quotes: false,
+            ragged: false,
+            encoding: false,
+        };
+        for name in names {
+            match name.as_str() {
+                "quotes" => parsed.quotes = true,
+                "ragged" => parsed.ragged = true,
+                "encoding" => parsed.encoding = true,
+                _ => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Unknown fix '{name}'; expected 'quotes', 'ragged' or 'encoding'"
+                    )))
+                }
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// One change made. `row` is the 1-based data row (None for the header and file-wide
+/// changes); `line` is the 1-based line of the input it was made on.
+struct Change {
+    fix: &'static str,
+    row: Option<usize>,
+    line: u64,
+    message: String,
+}
+
+/// What `repair()` did.
+#[derive(Default)]
+struct Report {
+    rows: usize,
+    quotes_closed: usize,
+    rows_padded: usize,
+    rows_truncated: usize,
+    fields_reencoded: usize,
+    changes: Vec<Change>,
+    truncated: bool, // More changes were made than are listed
+}
+
+impl Report {
+    fn note(&mut self, change: Change, max_changes: usize) {
+        if self.changes.len() < max_changes {
+            self.changes.push(change);
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+/// Rewrite `src` to `dst` applying `fixes`.
+fn repair_file(
+    src: &str,
+    dst: &str,
+    fixes: Fixes,
+    dialect: &DialectConfig,
+    max_changes: usize,
+) -> PyResult<Report> {
+    check_not_input(dst, &[src])?;
+    let open_err =
+        |e: std::io::Error| CsvIoError::new_err(format!("Failed to open file '{src}': {e}"));
+    let mut report = Report::default();
+
+    // Where a closing quote goes, as a byte offset of the input
+    let mut splice = None;
+    if fixes.quotes {
+        let scan = scan_bytes(src, dialect.quotechar, 1)?;
+        if let (Some(line), Some(end)) = (scan.open_quote, scan.open_quote_end) {
+            splice = Some(end);
+            report.quotes_closed = 1;
+            report.note(
+                Change {
+                    fix: "quotes",
+                    row: None,
+                    line,
+                    message: "closed a quote left open at the end of the file at the end \
+                              of the line it opens on"
+                        .to_string(),
+                },
+                max_changes,
+            );
+        }
+    }
+    let file = std::fs::File::open(src).map_err(open_err)?;
+    let input: Box<dyn Read> = match splice {
+        Some(end) => {
+            let mut rest = std::fs::File::open(src).map_err(open_err)?;
+            rest.seek(SeekFrom::Start(end)).map_err(open_err)?;
+            let quote = std::io::Cursor::new([dialect.quotechar]);
+            Box::new(file.take(end).chain(quote).chain(rest))
+        }
+        None => Box::new(file),
+    };
+
+    let mut builder = ReaderBuilder::new();
+    dialect.apply_to_reader(&mut builder, None);
+    let mut reader = builder.has_headers(false).flexible(true).from_reader(input);
+    let mut builder = WriterBuilder::new();
+    dialect.apply_to_writer(&mut builder);
+    let mut writer = builder
+        .flexible(true)
+        .from_path(dst)
+        .map_err(|e| CsvIoError::new_err(format!("Failed to create file '{dst}': {e}")))?;
+
+    let mut lines = LineCounter::open(src)?;
+    let mut record = ByteRecord::new();
+    let mut width = None; // Fields in the header
+    loop {
+        match reader.read_byte_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        let row = width.map(|_| report.rows + 1);
+        let mut start = record.position().map_or(0, |p| p.byte());
+        if splice.is_some_and(|end| start > end) {
+            start -= 1; // Past the spliced quote, which the file doesn't hold
+        }
+        let line = lines
+            .line_at(start)
+            .map_err(|e| CsvIoError::new_err(format!("Failed to read file '{src}': {e}")))?;
+
+        let mut fields: Vec<Cow<'_, [u8]>> = record.iter().map(Cow::Borrowed).collect();
+        if fixes.encoding {
+            for (i, field) in fields.iter_mut().enumerate() {
+                if std::str::from_utf8(field).is_ok() {
+                    continue;
+                }
+                *field = Cow::Owned(String::from_utf8_lossy(field).into_owned().into_bytes());
+                report.fields_reencoded += 1;
+                report.note(
+                    Change {
+                        fix: "encoding",
+                        row,
+                        line,
+                        message: format!("replaced invalid UTF-8 in field {}", i + 1),
+                    },
+                    max_changes,
+                );
+            }
+        }
+        match width {
+            None => width = Some(fields.len()),
+            Some(width) if fixes.ragged && fields.len() != width => {
+                let found = fields.len();
+                let message = if found < width {
+                    report.rows_padded += 1;
+                    fields.resize(width, Cow::Borrowed(&[]));
+                    format!("padded from {found} to {width} fields")
+                } else {
+                    report.rows_truncated += 1;
+                    let dropped: Vec<String> = fields
+                        .drain(width..)
+                        .map(|field| format!("'{}'", String::from_utf8_lossy(&field)))
+                        .collect();
+                    format!(
+                        "truncated from {found} to {width} fields, dropping {}",
+                        dropped.join(", ")
+                    )
+                };
+                report.note(
+                    Change {
+                        fix: "ragged",
+                        row,
+                        line,
+                        message,
+                    },
+                    max_changes,
+                );
+            }
+            Some(_) => {}
+        }
+
+        writer.write_record(&fields).map_err(|e| csv_err(dst, e))?;
+        if row.is_some() {
+            report.rows += 1;
+        }
+    }
+
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+    Ok(report)
+}
+
+/// Rewrite a broken CSV file, applying best-effort fixes.
+///
+/// # Arguments
+/// * `src` - Input CSV file (first row is the header)
+/// * `dst` - Output CSV file (created or truncated)
+/// * `fixes` - Fixes to apply: "quotes", "ragged" and/or "encoding" (default: all)
+/// * `max_changes` - List at most this many changes (default: 100)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+///
+/// Returns a report dict with `rows_written`, `quotes_closed`, `rows_padded`,
+/// `rows_truncated`, `fields_reencoded`, `truncated` and `changes`, a list of dicts
+/// with `fix`, `row`, `line` and `message`.
+#[pyfunction]
+#[pyo3(signature = (src, dst, fixes = None, max_changes = 100, delimiter = None, quotechar = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn repair(
+    py: Python<'_>,
+    src: String,
+    dst: String,
+    fixes: Option<&Bound<'_, PyAny>>,
+    max_changes: usize,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&src)?;
+    validate_path(&dst)?;
+    let fixes = Fixes::parse(fixes)?;
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    spawn_blocking_py(py, move || {
+        let report = repair_file(&src, &dst, fixes, &dialect, max_changes)?;
+        Python::attach(|py| -> PyResult<Py<PyAny>> {
+            let changes = PyList::empty(py);
+            for change in &report.changes {
+                let item = PyDict::new(py);
+                item.set_item("fix", change.fix)?;
+                item.set_item("row", change.row)?;
+                item.set_item("line", change.line)?;
+                item.set_item("message", &change.message)?;
+                changes.append(item)?;
+            }
+            let result = PyDict::new(py);
+            result.set_item("rows_written", report.rows)?;
+            result.set_item("quotes_closed", report.quotes_closed)?;
+            result.set_item("rows_padded", report.rows_padded)?;
+            result.set_item("rows_truncated", report.rows_truncated)?;
+            result.set_item("fields_reencoded", report.fields_reencoded)?;
+            result.set_item("truncated", report.truncated)?;
+            result.set_item("changes", changes)?;
+            Ok(result.into_any().unbind())
+        })
+    })
+}
@@ -0,0 +1,121 @@
+//! Retries with exponential backoff for transient IO errors (`retries=` on readers and
+//! writers).
+//!
+//! An error is transient when repeating the call may succeed: EAGAIN, EINTR, timeouts,
+//! dropped connections, busy files and stale NFS handles, or for Python file handles
+//! `BlockingIOError`, `InterruptedError`, `TimeoutError` and `ConnectionError`. Other
+//! errors are raised straight away. The delay doubles after every attempt.
+
+use pyo3::exceptions::{PyBlockingIOError, PyConnectionError, PyInterruptedError, PyTimeoutError};
+use pyo3::prelude::*;
+use std::io::ErrorKind;
+use std::time::Duration;
+
+/// Delay before the first retry when `retry_backoff` is not given.
+const DEFAULT_RETRY_BACKOFF: f64 = 0.1;
+
+/// Longest delay between two attempts, however many retries came before.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// How often and how patiently to retry a failed IO call.
+pub(crate) struct RetryPolicy {
+    retries: u32,
+    backoff: Duration,
+    on_retry: Option<Py<PyAny>>, // Called as on_retry(attempt, error, delay)
+}
+
+impl RetryPolicy {
+    /// Validate the `retries`/`retry_backoff`/`on_retry` arguments; `None` when no
+    /// retries are requested.
+    pub(crate) fn from_args(
+        py: Python<'_>,
+        retries: u32,
+        backoff: Option<f64>,
+        on_retry: Option<Py<PyAny>>,
+    ) -> PyResult<Option<Self>> {
+        if on_retry.as_ref().is_some_and(|f| !f.bind(py).is_callable()) {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "on_retry must be callable",
+            ));
+        }
+        let backoff = backoff.unwrap_or(DEFAULT_RETRY_BACKOFF);
+        if !backoff.is_finite() || backoff < 0.0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "retry_backoff must be a non-negative number of seconds",
+            ));
+        }
+        if retries == 0 {
+            return Ok(None);
+        }
+        Ok(Some(RetryPolicy {
+            retries,
+            backoff: Duration::from_secs_f64(backoff),
+            on_retry,
+        }))
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(MAX_RETRY_DELAY)
+    }
+}
+
+/// Whether an OS-level error is worth retrying.
+pub(crate) fn transient_io(error: &std::io::Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::WouldBlock
+            | ErrorKind::Interrupted
+            | ErrorKind::TimedOut
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::ConnectionRefused
+            | ErrorKind::BrokenPipe
+            | ErrorKind::ResourceBusy
+            | ErrorKind::StaleNetworkFileHandle
+    )
+}
+
+/// Whether an exception raised by a Python file handle is worth retrying.
+pub(crate) fn transient_py(error: &PyErr) -> bool {
+    Python::attach(|py| {
+        error.is_instance_of::<PyBlockingIOError>(py)
+            || error.is_instance_of::<PyInterruptedError>(py)
+            || error.is_instance_of::<PyTimeoutError>(py)
+            || error.is_instance_of::<PyConnectionError>(py)
+    })
+}
+
+/// Decide whether to try again after failed attempt number `attempt` (1-based).
+///
+/// For a `transient` error with retries left, reports it to `on_retry`, sleeps for the
+/// backoff and returns `true`. Returns `false` when the error should be raised.
+pub(crate) async fn again(
+    policy: Option<&RetryPolicy>,
+    attempt: u32,
+    transient: bool,
+    error: &PyErr,
+) -> PyResult<bool> {
+    let Some(policy) = policy else {
+        return Ok(false);
+    };
+    if !transient || attempt > policy.retries {
+        return Ok(false);
+    }
+    let delay = policy.delay(attempt);
+    tracing::warn!(
+        attempt,
+        delay = delay.as_secs_f64(),
+        "retrying after transient error: {error}"
+    );
+    if let Some(on_retry) = &policy.on_retry {
+        Python::attach(|py| {
+            on_retry
+                .call1(py, (attempt, error.value(py), delay.as_secs_f64()))
+                .map(drop)
+        })?;
+    }
+    tokio::time::sleep(delay).await;
+    Ok(true)
+}
@@ -0,0 +1,125 @@
+//! Strict RFC 4180 conformance (`strict_rfc4180=True` on `Reader`).
+//!
+//! The parser accepts the usual deviations from RFC 4180: LF or CR line endings, quotes
+//! inside unquoted fields, text after a closing quote, blank lines and records of
+//! different lengths. In strict mode the bytes of each record are checked as it is
+//! read, and the first deviation raises `CSVError` naming it, with the row, line and
+//! field where it was found. Only the RFC's dialect applies: comma-separated fields,
+//! `"` quotes escaped by doubling and CRLF line endings.
+
+use crate::DialectConfig;
+use csv::Terminator;
+use std::sync::OnceLock;
+
+/// A deviation from RFC 4180 in a record.
+#[derive(Debug)]
+pub(crate) struct Violation {
+    pub(crate) at: usize,     // Byte of the record where it was found
+    pub(crate) column: usize, // 0-based field
+    pub(crate) message: String,
+}
+
+/// Where the checker is within a record.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    QuoteInQuoted, // A quote in a quoted field: closing it, or the first of a pair
+}
+
+/// Checks made on every record of a stream.
+#[derive(Debug, Default)]
+pub(crate) struct Rfc4180 {
+    fields: OnceLock<usize>, // Fields of the first record, which every record must have
+}
+
+impl Rfc4180 {
+    /// Reject dialects other than RFC 4180's.
+    pub(crate) fn check_dialect(dialect: &DialectConfig) -> Result<(), String> {
+        let deviation = if dialect.delimiter != b',' {
+            "a delimiter other than ','"
+        } else if dialect.quotechar != b'"' {
+            "a quotechar other than '\"'"
+        } else if dialect.escapechar.is_some() {
+            "an escapechar"
+        } else if !dialect.double_quote {
+            "double_quote=False"
+        } else if dialect.skipinitialspace {
+            "skipinitialspace"
+        } else if dialect.delim_whitespace {
+            "delim_whitespace"
+        } else if !matches!(dialect.lineterminator, Terminator::CRLF) {
+            "a lineterminator other than '\\r\\n'"
+        } else {
+            return Ok(());
+        };
+        Err(format!("strict_rfc4180 cannot be used with {deviation}"))
+    }
+
+    /// Check the bytes of one record, from the end of the record before it through its
+    /// CRLF. The last record of a file may end without one.
+    pub(crate) fn check(&self, bytes: &[u8]) -> Result<(), Violation> {
+        let mut state = State::FieldStart;
+        let mut column = 0;
+        let violation = |at: usize, column: usize, message: String| {
+            Err(Violation {
+                at,
+                column,
+                message,
+            })
+        };
+        let mut i = 0;
+        while i < bytes.len() {
+            let b = bytes[i];
+            match (state, b) {
+                (State::Quoted, b'"') => state = State::QuoteInQuoted,
+                (State::Quoted, _) => {}
+                (State::QuoteInQuoted, b'"') => state = State::Quoted,
+                (State::FieldStart, b'"') => state = State::Quoted,
+                (State::Unquoted, b'"') => {
+                    return violation(i, column, "quote inside an unquoted field".to_string())
+                }
+                (_, b',') => {
+                    state = State::FieldStart;
+                    column += 1;
+                }
+                (_, b'\r') if bytes.get(i + 1) == Some(&b'\n') => {
+                    if column == 0 && state == State::FieldStart {
+                        return violation(i, column, "blank line".to_string());
+                    }
+                    i += 2;
+                    break;
+                }
+                (_, b'\r') => {
+                    return violation(i, column, "bare CR outside quotes (not CRLF)".to_string())
+                }
+                (_, b'\n') => {
+                    return violation(i, column, "bare LF outside quotes (not CRLF)".to_string())
+                }
+                (State::QuoteInQuoted, _) => {
+                    return violation(
+                        i,
+                        column,
+                        "text after the closing quote of a field".to_string(),
+                    )
+                }
+                (State::FieldStart | State::Unquoted, _) => state = State::Unquoted,
+            }
+            i += 1;
+        }
+        if state == State::Quoted {
+            return violation(i, column, "quoted field is never closed".to_string());
+        }
+        let fields = column + 1;
+        let expected = *self.fields.get_or_init(|| fields);
+        if fields != expected {
+            return violation(
+                0,
+                fields.min(expected),
+                format!("record has {fields} fields, the first record has {expected}"),
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,399 @@
+//! Writing to a series of files that roll over at a size, row or time threshold.
+//!
+//! `RotatingWriter` names its parts after the path it is given (`out.csv` is written
+//! as `out-0001.csv`, `out-0002.csv`, ...) and repeats the header at the top of each.
+//! A part is opened by the first row written to it, so no empty part is left behind,
+//! and thresholds are checked as rows are written rather than on a timer. Numbering
+//! continues after the highest part already beside the path, so a restarted collector
+//! never overwrites the parts of an earlier run.
+
+use crate::awaitable::future_into_py;
+use crate::errors::CsvIoError;
+use crate::values::ValueFormat;
+use crate::{encode_rows, validate_path, DialectConfig};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::path::Path;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// When to move on to the next part.
+#[derive(Clone, Copy)]
+struct Limits {
+    bytes: Option<u64>,
+    rows: Option<usize>,
+    age: Option<Duration>,
+}
+
+/// The part being written.
+struct Part {
+    file: File,
+    path: String,
+    bytes: u64,
+    rows: usize, // Data rows, the header left out
+    opened: Instant,
+}
+
+struct Rotation {
+    part: Option<Part>,
+    last: Option<usize>, // Number of the last part, once the directory has been looked at
+}
+
+/// Paths of the parts, kept apart from the rotation so they can be read mid-write.
+#[derive(Default)]
+struct Parts {
+    current: Option<String>,
+    paths: Vec<String>, // Every part opened so far
+}
+
+/// Path of part `n` (1-based) of `base`: the number goes before the extension.
+fn part_path(base: &str, n: usize) -> String {
+    let path = Path::new(base);
+    let stem = path
+        .file_stem()
+        .map_or(String::new(), |s| s.to_string_lossy().into_owned());
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}-{n:04}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{n:04}"),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+/// Number of the highest part of `base` that already exists, or 0.
+async fn last_existing_part(base: &str) -> PyResult<usize> {
+    let path = Path::new(base);
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let stem = path
+        .file_stem()
+        .map_or(String::new(), |s| s.to_string_lossy().into_owned());
+    let prefix = format!("{stem}-");
+    let suffix = path
+        .extension()
+        .map_or(String::new(), |ext| format!(".{}", ext.to_string_lossy()));
+    let list_err = |e: std::io::Error| {
+        CsvIoError::new_err(format!("Failed to list directory '{}': {e}", dir.display()))
+    };
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(list_err(e)),
+    };
+    let mut last = 0;
+    while let Some(entry) = entries.next_entry().await.map_err(list_err)? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let number = name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(&suffix))
+            .filter(|digits| digits.len() >= 4 && digits.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|digits| digits.parse::<usize>().ok());
+        last = last.max(number.unwrap_or(0));
+    }
+    Ok(last)
+}
+
+/// Create the part after `last`, skipping numbers taken since the directory was
+/// looked at. Returns the file, its path and its number.
+async fn create_part(base: &str, mut last: usize) -> PyResult<(File, String, usize)> {
+    loop {
+        last += 1;
+        let path = part_path(base, last);
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+        {
+            Ok(file) => return Ok((file, path, last)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                return Err(CsvIoError::new_err(format!(
+                    "Failed to create file '{path}': {e}"
+                )))
+            }
+        }
+    }
+}
+
+impl Limits {
+    /// Whether `part` is full before a row of `len` bytes. A part always takes at
+    /// least one row, however large.
+    fn reached(&self, part: &Part, len: u64) -> bool {
+        part.rows > 0
+            && (self.bytes.is_some_and(|max| part.bytes + len > max)
+                || self.rows.is_some_and(|max| part.rows >= max)
+                || self.age.is_some_and(|max| part.opened.elapsed() >= max))
+    }
+}
+
+/// Async CSV writer that rolls over to a new file when a size, row-count or time
+/// threshold is reached, writing the header at the top of every part.
+///
+/// # Example
+///
+/// ```python
+/// from rapcsv import RotatingWriter
+///
+/// async with RotatingWriter("events.csv", headers=["ts", "event"], max_rows=100_000) as writer:
+///     async for event in events():
+///         await writer.write_row(event)
+/// ```
+#[pyclass]
+pub(crate) struct RotatingWriter {
+    base: String,
+    dialect: DialectConfig,
+    values: ValueFormat,
+    header: Option<Vec<u8>>, // Encoded header row
+    limits: Limits,
+    rotation: Arc<Mutex<Rotation>>,
+    parts: Arc<StdMutex<Parts>>,
+}
+
+#[pymethods]
+impl RotatingWriter {
+    /// Create a writer for the parts of `path`.
+    ///
+    /// # Arguments
+    /// * `path` - Base path; part n is written to `<stem>-<nnnn>.<ext>` beside it,
+    ///   numbered on from the highest part already there
+    /// * `headers` - Header row written at the top of every part (default: None)
+    /// * `max_bytes` - Start a new part before a row would take the current one past
+    ///   this size, the header included
+    /// * `max_rows` - Data rows per part
+    /// * `max_seconds` - Start a new part for rows written this long after the current
+    ///   one was opened
+    /// * `dialect` - Dialect to write in: a `Dialect`, preset name or `csv.Dialect`
+    /// * `delimiter`, `quotechar`, `escapechar`, `quoting`, `lineterminator`,
+    ///   `double_quote` - As for `Writer`, overriding `dialect`
+    ///
+    /// At least one of `max_bytes`, `max_rows` and `max_seconds` is required.
+    #[new]
+    #[pyo3(signature = (
+        path,
+        headers = None,
+        max_bytes = None,
+        max_rows = None,
+        max_seconds = None,
+        dialect = None,
+        delimiter = None,
+        quotechar = None,
+        escapechar = None,
+        quoting = None,
+        lineterminator = None,
+        double_quote = None
+    ))]
+    #[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+    fn new(
+        path: String,
+        headers: Option<Vec<String>>,
+        max_bytes: Option<u64>,
+        max_rows: Option<usize>,
+        max_seconds: Option<f64>,
+        dialect: Option<&Bound<'_, PyAny>>,
+        delimiter: Option<&str>,
+        quotechar: Option<&str>,
+        escapechar: Option<&str>,
+        quoting: Option<u32>,
+        lineterminator: Option<&str>,
+        double_quote: Option<bool>,
+    ) -> PyResult<Self> {
+        validate_path(&path)?;
+        if max_bytes.is_none() && max_rows.is_none() && max_seconds.is_none() {
+            return Err(PyValueError::new_err(
+                "RotatingWriter requires max_bytes, max_rows or max_seconds",
+            ));
+        }
+        if max_bytes == Some(0) || max_rows == Some(0) {
+            return Err(PyValueError::new_err(
+                "max_bytes and max_rows must be at least 1",
+            ));
+        }
+        let age = match max_seconds {
+            Some(seconds) if !(seconds.is_finite() && seconds > 0.0) => {
+                return Err(PyValueError::new_err("max_seconds must be positive"))
+            }
+            seconds => seconds.map(Duration::from_secs_f64),
+        };
+        let dialect = DialectConfig::from_python(
+            dialect,
+            delimiter,
+            quotechar,
+            escapechar,
+            quoting,
+            lineterminator,
+            None,
+            None,
+            double_quote,
+        )?;
+        let header = headers
+            .map(|headers| encode_rows(&dialect, &[headers]))
+            .transpose()?;
+        Ok(RotatingWriter {
+            base: path,
+            dialect,
+            values: ValueFormat::default(),
+            header,
+            limits: Limits {
+                bytes: max_bytes,
+                rows: max_rows,
+                age,
+            },
+            rotation: Arc::new(Mutex::new(Rotation {
+                part: None,
+                last: None,
+            })),
+            parts: Arc::default(),
+        })
+    }
+
+    /// Path of the part being written, or None before the first row.
+    #[getter]
+    fn path(&self) -> Option<String> {
+        self.parts().current.clone()
+    }
+
+    /// Paths of every part opened so far, in order.
+    #[getter]
+    fn paths(&self) -> Vec<String> {
+        self.parts().paths.clone()
+    }
+
+    /// Write a row, first moving on to a new part if the current one is full.
+    fn write_row(&self, row: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let row = self.values.row(row)?;
+        self.write_rows(vec![row])
+    }
+
+    /// Write multiple rows, moving on to new parts as they fill.
+    fn writerows(&self, rows: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let rows = self.values.rows(rows)?;
+        self.write_rows(rows)
+    }
+
+    /// Start a new part with the next row, whatever the thresholds.
+    fn rotate(&self) -> PyResult<Py<PyAny>> {
+        let rotation = Arc::clone(&self.rotation);
+        let parts = Arc::clone(&self.parts);
+        Python::attach(|py| {
+            let future = async move { close_part(&mut *rotation.lock().await, &parts).await };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// Close the current part.
+    fn close(&self) -> PyResult<Py<PyAny>> {
+        self.rotate()
+    }
+
+    /// Async context manager entry.
+    fn __aenter__(slf: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let slf: Py<Self> = slf.into();
+        Python::attach(|py| {
+            let future = async move { Ok(slf) };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+
+    /// Async context manager exit.
+    fn __aexit__(
+        &self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_val: Option<&Bound<'_, PyAny>>,
+        _exc_tb: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        self.close()
+    }
+}
+
+impl RotatingWriter {
+    fn parts(&self) -> std::sync::MutexGuard<'_, Parts> {
+        self.parts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Encode `rows` and write them, rolling over between rows as the limits say.
+    fn write_rows(&self, rows: Vec<Vec<String>>) -> PyResult<Py<PyAny>> {
+        let encoded = rows
+            .iter()
+            .map(|row| encode_rows(&self.dialect, std::slice::from_ref(row)))
+            .collect::<PyResult<Vec<_>>>()?;
+        let base = self.base.clone();
+        let header = self.header.clone();
+        let limits = self.limits;
+        let rotation = Arc::clone(&self.rotation);
+        let parts = Arc::clone(&self.parts);
+        Python::attach(|py| {
+            let future = async move {
+                let mut rotation = rotation.lock().await;
+                let mut pending = Vec::new();
+                for row in encoded {
+                    let len = row.len() as u64;
+                    if rotation
+                        .part
+                        .as_ref()
+                        .is_some_and(|part| limits.reached(part, len))
+                    {
+                        write_part(&mut rotation, &mut pending).await?;
+                        close_part(&mut rotation, &parts).await?;
+                    }
+                    if rotation.part.is_none() {
+                        let last = match rotation.last {
+                            Some(last) => last,
+                            None => last_existing_part(&base).await?,
+                        };
+                        let (file, path, number) = create_part(&base, last).await?;
+                        rotation.last = Some(number);
+                        let mut part = Part {
+                            file,
+                            path: path.clone(),
+                            bytes: 0,
+                            rows: 0,
+                            opened: Instant::now(),
+                        };
+                        if let Some(header) = &header {
+                            pending.extend_from_slice(header);
+                            part.bytes = header.len() as u64;
+                        }
+                        rotation.part = Some(part);
+                        let mut parts = parts.lock().unwrap_or_else(|e| e.into_inner());
+                        parts.current = Some(path.clone());
+                        parts.paths.push(path);
+                    }
+                    if let Some(part) = rotation.part.as_mut() {
+                        part.bytes += len;
+                        part.rows += 1;
+                    }
+                    pending.extend_from_slice(&row);
+                }
+                write_part(&mut rotation, &mut pending).await
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+}
+
+/// Write `pending` to the current part and clear it.
+async fn write_part(rotation: &mut Rotation, pending: &mut Vec<u8>) -> PyResult<()> {
+    if let Some(part) = rotation.part.as_mut() {
+        part.file.write_all(pending).await.map_err(|e| {
+            CsvIoError::new_err(format!("Failed to write file '{}': {e}", part.path))
+        })?;
+    }
+    pending.clear();
+    Ok(())
+}
+
+/// Flush and close the current part, if any.
+async fn close_part(rotation: &mut Rotation, parts: &StdMutex<Parts>) -> PyResult<()> {
+    parts.lock().unwrap_or_else(|e| e.into_inner()).current = None;
+    if let Some(mut part) = rotation.part.take() {
+        part.file.flush().await.map_err(|e| {
+            CsvIoError::new_err(format!("Failed to flush file '{}': {e}", part.path))
+        })?;
+    }
+    Ok(())
+}
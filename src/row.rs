@@ -0,0 +1,536 @@
+//! How parsed records are handed to Python (`raw=` and `row_type=` on `Reader`).
+//!
+//! `row_type="row"` returns [`Row`] objects: the fields in a tuple plus a pointer to a
+//! header shared by every row of the reader, so `row["amount"]` and `row.amount` work
+//! without building a dict per row.
+//!
+//! With `schema=`, fields are converted to typed values (see [`crate::typed`]) before
+//! being put into the list, tuple or `Row`. `normalize_newlines=True` rewrites line
+//! breaks inside fields first, and `intern_columns=` (see [`crate::intern`]) shares
+//! the string objects of repeated values last. With `empty_as_none=True` the fields
+//! the stream marked as empty and unquoted (see [`crate::nulls`]) become None, and
+//! `field_spans=True` pairs each row with the byte spans of its fields (see
+//! [`crate::spans`]).
+//!
+//! `Reader.read_columns()` returns batches as columns instead: a dict of one list per
+//! column, keyed by the header's names, built without a Python object per row.
+
+use crate::errors::CSVFieldCountError;
+use crate::intern::Interner;
+use crate::nulls;
+use crate::spans;
+use crate::stream::RecordStream;
+use crate::typed::{Converter, Schema};
+use pyo3::conversion::IntoPyObjectExt;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList, PyString, PyTuple};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex};
+
+/// Python type of each returned row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RowType {
+    /// A `list` of fields.
+    List,
+    /// An immutable, hashable `tuple` of fields.
+    Tuple,
+    /// A [`Row`] with access by position, column name or attribute.
+    Row,
+}
+
+impl RowType {
+    pub(crate) fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "list" => Ok(RowType::List),
+            "tuple" => Ok(RowType::Tuple),
+            "row" => Ok(RowType::Row),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "row_type must be 'list', 'tuple' or 'row', got '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Column names shared by all rows of a reader.
+#[derive(Debug, Default)]
+struct Header {
+    names: Vec<String>,
+    index: HashMap<String, usize>, // First column wins for duplicate names
+}
+
+impl Header {
+    fn new(names: Vec<String>) -> Self {
+        let mut index = HashMap::with_capacity(names.len());
+        for (i, name) in names.iter().enumerate() {
+            index.entry(name.clone()).or_insert(i);
+        }
+        Header { names, index }
+    }
+}
+
+/// Conversion applied to every row a reader returns.
+#[derive(Clone, Debug)]
+pub(crate) struct RowFormat {
+    raw: bool, // Fields as bytes instead of str
+    row_type: RowType,
+    header: Arc<StdMutex<Option<Arc<Header>>>>, // Set from the first record for `Row`
+    schema: Option<Arc<Schema>>,
+    converter: Arc<StdMutex<Option<Arc<Converter>>>>, // Schema resolved to positions
+    normalize_newlines: bool,                         // `\r\n` and `\r` in fields become `\n`
+    intern: Option<Arc<Interner>>,
+    nulls: bool, // Rows carry empty fields marked by `empty_as_none`
+    spans: bool, // Rows end with the spans of their fields
+}
+
+/// The converter and header rows are built with, looked up once per batch rather than
+/// once per row.
+struct Resolved {
+    converter: Option<Arc<Converter>>,
+    header: Arc<Header>,
+}
+
+/// Replace `\r\n` and lone `\r` line breaks in a field with `\n`.
+fn normalize_newlines(field: &mut String) {
+    if field.contains('\r') {
+        *field = field.replace("\r\n", "\n").replace('\r', "\n");
+    }
+}
+
+/// Convert a row's fields to bytes for `raw=True` readers (moves the buffers, no copy).
+fn raw_row(row: Vec<String>) -> Vec<Vec<u8>> {
+    row.into_iter().map(String::into_bytes).collect()
+}
+
+impl RowFormat {
+    pub(crate) fn new(raw: bool, row_type: RowType) -> Self {
+        RowFormat {
+            raw,
+            row_type,
+            header: Arc::new(StdMutex::new(None)),
+            schema: None,
+            converter: Arc::new(StdMutex::new(None)),
+            normalize_newlines: false,
+            intern: None,
+            nulls: false,
+            spans: false,
+        }
+    }
+
+    /// Normalize line breaks inside fields to `\n`.
+    pub(crate) fn normalize_newlines(mut self) -> Self {
+        self.normalize_newlines = true;
+        self
+    }
+
+    /// Return the fields marked as empty and unquoted as None.
+    pub(crate) fn empty_as_none(mut self) -> Self {
+        self.nulls = true;
+        self
+    }
+
+    /// Return each row as a `(row, spans)` pair, taking the spans from the last field.
+    pub(crate) fn field_spans(mut self) -> Self {
+        self.spans = true;
+        self
+    }
+
+    /// Whether rows are paired with the spans of their fields.
+    pub(crate) fn has_spans(&self) -> bool {
+        self.spans
+    }
+
+    /// Reuse one Python string per distinct value in the columns of `interner`.
+    pub(crate) fn intern(mut self, interner: Interner) -> PyResult<Self> {
+        if self.raw {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "intern_columns cannot be combined with raw=True",
+            ));
+        }
+        self.intern = Some(Arc::new(interner));
+        Ok(self)
+    }
+
+    /// Convert fields with `schema` (types and converters). A schema naming columns is
+    /// resolved once the header has been read, one using only positions right away.
+    pub(crate) fn schema(mut self, schema: Schema) -> PyResult<Self> {
+        if self.raw {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "schema and converters cannot be combined with raw=True",
+            ));
+        }
+        if !schema.needs_header() {
+            *self.converter.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some(Arc::new(schema.converter(None)?));
+        }
+        self.schema = Some(Arc::new(schema));
+        Ok(self)
+    }
+
+    /// Whether the first record is consumed as the header rather than returned.
+    pub(crate) fn needs_header(&self) -> bool {
+        self.row_type == RowType::Row || self.schema.as_ref().is_some_and(|s| s.needs_header())
+    }
+
+    fn converter(&self) -> Option<Arc<Converter>> {
+        self.converter
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn header(&self) -> Option<Arc<Header>> {
+        self.header
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Column names `Row` objects are built with, once the header has been read.
+    pub(crate) fn header_names(&self) -> Option<Vec<String>> {
+        self.header().map(|header| header.names.clone())
+    }
+
+    /// Use `names` as the header instead of reading it from the stream.
+    pub(crate) fn set_header(&self, names: Vec<String>) -> PyResult<()> {
+        if let Some(schema) = self.schema.as_ref().filter(|s| s.needs_header()) {
+            *self.converter.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some(Arc::new(schema.converter(Some(&names))?));
+        }
+        *self.header.lock().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(Header::new(names)));
+        Ok(())
+    }
+
+    /// For `row_type="row"` or a schema naming columns, consume the header record if it
+    /// has not been read yet.
+    pub(crate) async fn read_header(&self, stream: &RecordStream) -> PyResult<()> {
+        if !self.needs_header() || self.header().is_some() {
+            return Ok(());
+        }
+        if let Some(mut names) = stream.next_record().await? {
+            if self.spans {
+                names.pop();
+            }
+            if self.normalize_newlines {
+                names.iter_mut().for_each(normalize_newlines);
+            }
+            self.set_header(names)?;
+        }
+        Ok(())
+    }
+
+    fn tuple<'py>(&self, py: Python<'py>, row: Vec<String>) -> PyResult<Bound<'py, PyTuple>> {
+        if self.raw {
+            PyTuple::new(py, raw_row(row))
+        } else {
+            PyTuple::new(py, row)
+        }
+    }
+
+    fn resolved(&self) -> Resolved {
+        Resolved {
+            converter: self.converter(),
+            header: self.header().unwrap_or_default(),
+        }
+    }
+
+    /// Convert one row.
+    pub(crate) fn row(&self, py: Python<'_>, mut row: Vec<String>) -> PyResult<Py<PyAny>> {
+        if self.normalize_newlines {
+            row.iter_mut().for_each(normalize_newlines);
+        }
+        self.convert(py, row, &self.resolved())
+    }
+
+    /// Convert one row whose line breaks are already normalized, paired with the spans
+    /// of its fields for `field_spans`.
+    fn convert(
+        &self,
+        py: Python<'_>,
+        mut row: Vec<String>,
+        resolved: &Resolved,
+    ) -> PyResult<Py<PyAny>> {
+        if !self.spans {
+            return self.value(py, row, resolved);
+        }
+        let spans = spans::take(&mut row);
+        (self.value(py, row, resolved)?, spans).into_py_any(py)
+    }
+
+    fn value(
+        &self,
+        py: Python<'_>,
+        mut row: Vec<String>,
+        resolved: &Resolved,
+    ) -> PyResult<Py<PyAny>> {
+        if self.nulls {
+            let nulls = nulls::take(&mut row);
+            let mut fields = self.fields(py, row, resolved.converter.as_deref())?;
+            for (field, null) in fields.iter_mut().zip(nulls.into_iter().flatten()) {
+                if null {
+                    *field = py.None();
+                }
+            }
+            return self.build(py, fields, resolved);
+        }
+        if let Some(converter) = &resolved.converter {
+            let mut fields = converter.convert(py, row)?;
+            if let Some(interner) = &self.intern {
+                interner.converted(py, &mut fields)?;
+            }
+            return self.build(py, fields, resolved);
+        }
+        if let Some(interner) = &self.intern {
+            let fields = interner.strings(py, row)?;
+            return self.build(py, fields, resolved);
+        }
+        match (self.row_type, self.raw) {
+            (RowType::List, false) => row.into_py_any(py),
+            (RowType::List, true) => raw_row(row).into_py_any(py),
+            (RowType::Tuple, _) => self.tuple(py, row)?.into_py_any(py),
+            (RowType::Row, _) => Row {
+                header: Arc::clone(&resolved.header),
+                fields: self.tuple(py, row)?.unbind(),
+            }
+            .into_py_any(py),
+        }
+    }
+
+    /// Put converted fields into the row type.
+    fn build(
+        &self,
+        py: Python<'_>,
+        fields: Vec<Py<PyAny>>,
+        resolved: &Resolved,
+    ) -> PyResult<Py<PyAny>> {
+        match self.row_type {
+            RowType::List => fields.into_py_any(py),
+            RowType::Tuple => PyTuple::new(py, fields)?.into_py_any(py),
+            RowType::Row => Row {
+                header: Arc::clone(&resolved.header),
+                fields: PyTuple::new(py, fields)?.unbind(),
+            }
+            .into_py_any(py),
+        }
+    }
+
+    /// Convert a batch of rows into a list, inside the one GIL acquisition the caller
+    /// holds. The converter and header are looked up once for the batch, and plain
+    /// rows go straight into lists sized up front.
+    pub(crate) fn rows(&self, py: Python<'_>, mut rows: Vec<Vec<String>>) -> PyResult<Py<PyAny>> {
+        if self.normalize_newlines {
+            rows.iter_mut().flatten().for_each(normalize_newlines);
+        }
+        if let (RowType::List, None, None, false, false) = (
+            self.row_type,
+            &self.schema,
+            &self.intern,
+            self.nulls,
+            self.spans,
+        ) {
+            return if self.raw {
+                rows.into_iter()
+                    .map(raw_row)
+                    .collect::<Vec<_>>()
+                    .into_py_any(py)
+            } else {
+                rows.into_py_any(py)
+            };
+        }
+        let resolved = self.resolved();
+        let mut converted = Vec::with_capacity(rows.len());
+        for row in rows {
+            converted.push(self.convert(py, row, &resolved)?);
+        }
+        PyList::new(py, converted)?.into_py_any(py)
+    }
+}
+
+impl RowFormat {
+    /// Convert a row's fields to Python values, as they are put into a row.
+    fn fields(
+        &self,
+        py: Python<'_>,
+        row: Vec<String>,
+        converter: Option<&Converter>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        if let Some(converter) = converter {
+            let mut fields = converter.convert(py, row)?;
+            if let Some(interner) = &self.intern {
+                interner.converted(py, &mut fields)?;
+            }
+            return Ok(fields);
+        }
+        if let Some(interner) = &self.intern {
+            return interner.strings(py, row);
+        }
+        Ok(if self.raw {
+            row.iter()
+                .map(|field| PyBytes::new(py, field.as_bytes()).into_any().unbind())
+                .collect()
+        } else {
+            row.iter()
+                .map(|field| PyString::new(py, field).into_any().unbind())
+                .collect()
+        })
+    }
+
+    /// Convert a batch of rows into a dict of columns: one list per name of `names`,
+    /// or per position (keys 0, 1, ...) without a header. A short row has None in the
+    /// columns it lacks; a row longer than the header raises `CSVFieldCountError`.
+    /// Of repeated names, the first column is kept, and an empty batch is an empty
+    /// dict. `first_row` is the 0-based index of the batch's first row, for errors.
+    pub(crate) fn columns(
+        &self,
+        py: Python<'_>,
+        names: Option<&[String]>,
+        mut rows: Vec<Vec<String>>,
+        first_row: usize,
+    ) -> PyResult<Py<PyAny>> {
+        if rows.is_empty() {
+            return PyDict::new(py).into_py_any(py);
+        }
+        if self.normalize_newlines {
+            rows.iter_mut().flatten().for_each(normalize_newlines);
+        }
+        let converter = self.converter();
+        let width = match names {
+            Some(names) => names.len(),
+            None => rows.iter().map(Vec::len).max().unwrap_or(0),
+        };
+        let mut columns: Vec<Vec<Py<PyAny>>> =
+            (0..width).map(|_| Vec::with_capacity(rows.len())).collect();
+        for (i, mut row) in rows.into_iter().enumerate() {
+            let len = row.len();
+            if len > width {
+                return Err(CSVFieldCountError::new_err(format!(
+                    "Row {} has {len} fields, the header has {width}",
+                    first_row + i
+                )));
+            }
+            let nulls = if self.nulls {
+                nulls::take(&mut row)
+            } else {
+                None
+            };
+            let fields = self.fields(py, row, converter.as_deref())?;
+            for (j, (column, field)) in columns.iter_mut().zip(fields).enumerate() {
+                match &nulls {
+                    Some(nulls) if nulls[j] => column.push(py.None()),
+                    _ => column.push(field),
+                }
+            }
+            for column in &mut columns[len..] {
+                column.push(py.None());
+            }
+        }
+        let dict = PyDict::new(py);
+        for (i, column) in columns.into_iter().enumerate() {
+            let column = PyList::new(py, column)?;
+            match names {
+                Some(names) if dict.contains(&names[i])? => {}
+                Some(names) => dict.set_item(&names[i], column)?,
+                None => dict.set_item(i, column)?,
+            }
+        }
+        dict.into_py_any(py)
+    }
+}
+
+/// A row returned by `Reader(row_type="row")`.
+///
+/// Behaves like a tuple of fields (indexing, slicing, `len()`, iteration, equality and
+/// hashing), and also looks fields up by column name: `row["amount"]`, or `row.amount`
+/// for names that are valid identifiers and don't clash with a method.
+#[pyclass(frozen, module = "rapcsv")]
+pub(crate) struct Row {
+    header: Arc<Header>,
+    fields: Py<PyTuple>,
+}
+
+impl Row {
+    fn field(&self, py: Python<'_>, name: &str) -> Option<PyResult<Py<PyAny>>> {
+        let i = *self.header.index.get(name)?;
+        let fields = self.fields.bind(py);
+        // A short row has no value for trailing columns
+        (i < fields.len()).then(|| fields.get_item(i).map(Bound::unbind))
+    }
+}
+
+#[pymethods]
+impl Row {
+    /// Field by position, slice of fields, or field by column name.
+    fn __getitem__(&self, py: Python<'_>, key: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        if let Ok(name) = key.cast::<PyString>() {
+            return self.field(py, name.to_str()?).unwrap_or_else(|| {
+                Err(PyErr::new::<pyo3::exceptions::PyKeyError, _>(
+                    name.clone().unbind(),
+                ))
+            });
+        }
+        Ok(self.fields.bind(py).as_any().get_item(key)?.unbind())
+    }
+
+    /// Field by column name, as an attribute.
+    fn __getattr__(&self, py: Python<'_>, name: &str) -> PyResult<Py<PyAny>> {
+        self.field(py, name).unwrap_or_else(|| {
+            Err(PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                format!("Row has no field '{name}'"),
+            ))
+        })
+    }
+
+    fn __len__(&self, py: Python<'_>) -> usize {
+        self.fields.bind(py).len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(self.fields.bind(py).as_any().try_iter()?.into_any())
+    }
+
+    /// Rows compare equal to rows and tuples with the same fields.
+    fn __eq__(&self, py: Python<'_>, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+        match other.cast::<Row>() {
+            Ok(other) => self.fields.bind(py).eq(other.get().fields.bind(py)),
+            Err(_) => self.fields.bind(py).eq(other),
+        }
+    }
+
+    fn __hash__(&self, py: Python<'_>) -> PyResult<isize> {
+        self.fields.bind(py).hash()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let mut parts = Vec::new();
+        for (i, value) in self.fields.bind(py).iter().enumerate() {
+            let value = value.repr()?;
+            parts.push(match self.header.names.get(i) {
+                Some(name) => format!("{name}={value}"),
+                None => value.to_string(),
+            });
+        }
+        Ok(format!("Row({})", parts.join(", ")))
+    }
+
+    /// Column names from the header.
+    fn keys(&self) -> Vec<String> {
+        self.header.names.clone()
+    }
+
+    /// Field by column name, or `default` if there is no such column.
+    #[pyo3(signature = (name, default = None))]
+    fn get(&self, py: Python<'_>, name: &str, default: Option<Py<PyAny>>) -> PyResult<Py<PyAny>> {
+        self.field(py, name)
+            .unwrap_or_else(|| Ok(default.unwrap_or_else(|| py.None())))
+    }
+
+    /// The row as a new `{column: field}` dict.
+    fn asdict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for (name, value) in self.header.names.iter().zip(self.fields.bind(py).iter()) {
+            if !dict.contains(name)? {
+                dict.set_item(name, value)?;
+            }
+        }
+        Ok(dict)
+    }
+}
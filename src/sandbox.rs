@@ -0,0 +1,141 @@
+//! Confining file access to a directory (`set_allowed_root()` and `allowed_root=`).
+//!
+//! A service that opens files named by its users can set a root that every path must
+//! resolve inside. Paths are resolved the way the OS will open them: relative paths
+//! against the working directory, `..` components, and symlinks in every part of the
+//! path that exists. A path that doesn't exist yet, such as a Writer's output, is
+//! resolved through its longest existing parent. A dangling symlink is refused, since
+//! writing through it would create its target wherever it points.
+//!
+//! The module-wide root applies to every function and class that takes a path, through
+//! `validate_path`; `Reader` and `Writer` also take a root of their own. SQL run by
+//! `query()` is kept from attaching databases or writing files while a root is set. Paths are
+//! checked when they are given, so a symlink created inside the root afterwards is not
+//! seen.
+
+use crate::fspath;
+use crate::paths::{self, FilePath};
+use crate::sftp;
+use pyo3::exceptions::{PyPermissionError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+
+/// The module-wide root, canonicalized.
+static ALLOWED_ROOT: StdMutex<Option<PathBuf>> = StdMutex::new(None);
+
+/// Resolve an `allowed_root` argument to the canonical directory it names.
+pub(crate) fn root_arg(root: &Bound<'_, PyAny>) -> PyResult<PathBuf> {
+    let root = fspath(root)?.ok_or_else(|| {
+        PyTypeError::new_err("allowed_root must be str, bytes, os.PathLike or None")
+    })?;
+    let canonical = paths::native(&root).canonicalize().map_err(|e| {
+        PyValueError::new_err(format!("allowed_root '{root}' cannot be resolved: {e}"))
+    })?;
+    if !canonical.is_dir() {
+        return Err(PyValueError::new_err(format!(
+            "allowed_root '{root}' is not a directory"
+        )));
+    }
+    Ok(canonical)
+}
+
+/// Where `path` leads once `..` and symlinks are followed. `None` when that can't be
+/// told, as for a dangling symlink.
+fn resolve(path: &Path) -> Option<PathBuf> {
+    let absolute = std::path::absolute(path).ok()?;
+    let components: Vec<Component> = absolute.components().collect();
+    // The longest prefix that exists, resolved by the OS
+    for existing in (1..=components.len()).rev() {
+        let prefix: PathBuf = components[..existing].iter().collect();
+        match prefix.canonicalize() {
+            Ok(mut resolved) => {
+                for component in &components[existing..] {
+                    match component {
+                        Component::ParentDir => {
+                            resolved.pop();
+                        }
+                        Component::Normal(name) => resolved.push(name),
+                        _ => {}
+                    }
+                }
+                return Some(resolved);
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                // A link whose target is missing: the OS would follow it on create
+                if prefix.symlink_metadata().is_ok() {
+                    return None;
+                }
+            }
+            Err(_) => return None,
+        }
+    }
+    None
+}
+
+/// Fail unless `path` resolves inside `root`, or the module-wide root when `root` is
+/// None. Paths pass when no root is set.
+pub(crate) fn check(path: &FilePath, root: Option<&Path>) -> PyResult<()> {
+    let global;
+    let root = match root {
+        Some(root) => root,
+        None => {
+            global = ALLOWED_ROOT
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone();
+            match &global {
+                Some(root) => root.as_path(),
+                None => return Ok(()),
+            }
+        }
+    };
+    // A file on an SFTP server resolves nowhere on the local filesystem
+    let resolved = (!sftp::is_url(path)).then(|| resolve(&paths::native(path)));
+    match resolved.flatten() {
+        Some(resolved) if resolved.starts_with(root) => Ok(()),
+        _ => Err(PyPermissionError::new_err(format!(
+            "Path '{path}' is outside the allowed root '{}'",
+            root.display()
+        ))),
+    }
+}
+
+/// Whether the module-wide root is set.
+pub(crate) fn confined() -> bool {
+    ALLOWED_ROOT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some()
+}
+
+/// Confine every path rapcsv opens to a directory, or lift the limit with None.
+///
+/// Paths that resolve outside `root`, through `..` or symlinks, raise
+/// `PermissionError` when they are given to a function or class. `Reader` and `Writer`
+/// also take an `allowed_root=` of their own, checked as well as this one.
+///
+/// # Example
+///
+/// ```python
+/// import rapcsv
+///
+/// rapcsv.set_allowed_root("/srv/uploads")
+/// ```
+#[pyfunction]
+pub(crate) fn set_allowed_root(root: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+    let root = root.map(root_arg).transpose()?;
+    *ALLOWED_ROOT.lock().unwrap_or_else(|e| e.into_inner()) = root;
+    Ok(())
+}
+
+/// The directory set by `set_allowed_root()`, resolved, or None.
+#[pyfunction]
+pub(crate) fn get_allowed_root() -> Option<String> {
+    ALLOWED_ROOT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|root| root.to_string_lossy().into_owned())
+}
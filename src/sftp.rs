@@ -0,0 +1,356 @@
+//! Files on SFTP servers (`sftp://user@host[:port]/path` given to `Reader` or `Writer`).
+//!
+//! The SSH session runs on its own thread next to a pipe (see [`crate::pipe`]): when
+//! reading it downloads the file into the pipe, and when writing it uploads whatever
+//! the `Writer` writes, appending to the remote file as a local `Writer` would. The
+//! connection is made on that thread too, so constructing a reader or writer never
+//! blocks; a connection, host key or login failure is raised by the first read or
+//! write. Connecting and every SSH call after it are bounded by the reader's or
+//! writer's `timeout=`, or [`DEFAULT_TIMEOUT`], so an unreachable host can't hang the
+//! thread. Hosts must be listed in `~/.ssh/known_hosts`, and only key-based logins
+//! are made: the `ssh_key=` file, or else the SSH agent and the default `~/.ssh/id_*`
+//! keys. The client is the `sftp` cargo feature; without it the URLs are refused.
+
+use crate::paths::FilePath;
+use crate::pipe::{PipeSink, PipeSource};
+use pyo3::prelude::*;
+use std::time::Duration;
+use tokio::fs::File;
+
+/// Port used when the URL names none.
+const DEFAULT_PORT: u16 = 22;
+
+/// Limit on connecting and on each SSH call when no `timeout` is given.
+#[cfg_attr(not(feature = "sftp"), allow(dead_code))]
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Whether `path` is an `sftp://` URL rather than a local path.
+pub(crate) fn is_url(path: &str) -> bool {
+    path.get(..7)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("sftp://"))
+}
+
+/// Decode the `%XX` escapes of a URL component.
+fn unescape(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// A parsed `sftp://` URL.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "sftp"), allow(dead_code))]
+pub(crate) struct Url {
+    url: FilePath, // As given, for error messages
+    user: String,
+    host: String,
+    port: u16,
+    path: String, // Relative to the login directory when the URL starts it with "~/"
+}
+
+impl Url {
+    /// Parse `path` if it is an `sftp://` URL. Returns `None` for local paths.
+    ///
+    /// The user defaults to the local one. A path starting with `/~/` is relative to
+    /// the login directory; any other path is absolute.
+    pub(crate) fn parse(path: &FilePath) -> PyResult<Option<Url>> {
+        if !is_url(path) {
+            return Ok(None);
+        }
+        let invalid = |problem: &str| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "{problem} in sftp:// URL {path}"
+            ))
+        };
+        let (authority, remote) = path[7..]
+            .split_once('/')
+            .ok_or_else(|| invalid("no file path"))?;
+        let (user, address) = match authority.rsplit_once('@') {
+            Some((user, address)) => (Some(user), address),
+            None => (None, authority),
+        };
+        if user.is_some_and(|user| user.contains(':')) {
+            // Not repeated in the message, which may well be logged
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "sftp:// URLs take no password; only key-based logins are made",
+            ));
+        }
+        let user = match user {
+            Some(user) => unescape(user).ok_or_else(|| invalid("an invalid user name"))?,
+            None => std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .map_err(|_| invalid("no user name"))?,
+        };
+        // An IPv6 address is written in brackets: [::1]:2222
+        let (host, port) = match address.strip_prefix('[') {
+            Some(bracketed) => {
+                let (host, rest) = bracketed
+                    .split_once(']')
+                    .ok_or_else(|| invalid("an unclosed '['"))?;
+                (host, rest.strip_prefix(':'))
+            }
+            None => match address.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (address, None),
+            },
+        };
+        if host.is_empty() || user.is_empty() {
+            return Err(invalid(if host.is_empty() {
+                "no host"
+            } else {
+                "no user name"
+            }));
+        }
+        let port = match port {
+            Some(port) => port
+                .parse::<u16>()
+                .ok()
+                .filter(|&port| port > 0)
+                .ok_or_else(|| invalid("an invalid port"))?,
+            None => DEFAULT_PORT,
+        };
+        let remote = unescape(remote).ok_or_else(|| invalid("an invalid file path"))?;
+        let file = match remote.strip_prefix("~/") {
+            Some(relative) => relative.to_string(),
+            None => format!("/{remote}"),
+        };
+        if file.contains('\0') {
+            return Err(invalid("a null byte"));
+        }
+        if file.is_empty() || file.ends_with('/') {
+            return Err(invalid("no file name"));
+        }
+        Ok(Some(Url {
+            url: path.clone(),
+            user,
+            host: host.to_string(),
+            port,
+            path: file,
+        }))
+    }
+}
+
+/// Start downloading the file `url` names. Returns the file to read it from.
+#[cfg_attr(not(feature = "sftp"), allow(unused_variables))]
+pub(crate) fn open(
+    url: Url,
+    key: Option<FilePath>,
+    timeout: Option<Duration>,
+) -> PyResult<(File, PipeSource)> {
+    #[cfg(feature = "sftp")]
+    return client::open(url, key, timeout.unwrap_or(DEFAULT_TIMEOUT));
+    #[cfg(not(feature = "sftp"))]
+    Err(unsupported(&url))
+}
+
+/// Start appending to the file `url` names, creating it if needed. Returns the file to
+/// write to.
+#[cfg_attr(not(feature = "sftp"), allow(unused_variables))]
+pub(crate) fn create(
+    url: Url,
+    key: Option<FilePath>,
+    timeout: Option<Duration>,
+) -> PyResult<(File, PipeSink)> {
+    #[cfg(feature = "sftp")]
+    return client::create(url, key, timeout.unwrap_or(DEFAULT_TIMEOUT));
+    #[cfg(not(feature = "sftp"))]
+    Err(unsupported(&url))
+}
+
+#[cfg(not(feature = "sftp"))]
+fn unsupported(url: &Url) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+        "sftp:// URLs need rapcsv built with the sftp feature: {}",
+        url.url
+    ))
+}
+
+#[cfg(feature = "sftp")]
+mod client {
+    use super::Url;
+    use crate::paths::{self, FilePath};
+    use crate::pipe::{spawn_reader, spawn_writer, PipeSink, PipeSource};
+    use pyo3::prelude::*;
+    use ssh2::{CheckResult, KnownHostFileKind, OpenFlags, OpenType, Session, Sftp};
+    use std::io::{BufReader, BufWriter, Read, Write};
+    use std::net::{TcpStream, ToSocketAddrs};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+    use tokio::fs::File;
+    use tokio::sync::oneshot;
+
+    /// Bytes asked of the server per read and sent per write.
+    const TRANSFER_SIZE: usize = 256 * 1024;
+
+    /// Keys tried, in `~/.ssh`, when no `ssh_key` is given and the agent has none that
+    /// work.
+    const DEFAULT_KEYS: &[&str] = &["id_ed25519", "id_ecdsa", "id_rsa"];
+
+    /// Mode of a remote file the writer creates, before the server's umask.
+    const CREATE_MODE: i32 = 0o644;
+
+    fn home() -> Option<PathBuf> {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(PathBuf::from)
+    }
+
+    /// Fail unless the server's host key is the one `~/.ssh/known_hosts` lists for it.
+    fn check_host_key(session: &Session, url: &Url) -> Result<(), String> {
+        let (key, _) = session.host_key().ok_or("the server sent no host key")?;
+        let mut known = session.known_hosts().map_err(|e| e.to_string())?;
+        let file = home().map(|home| home.join(".ssh").join("known_hosts"));
+        if let Some(file) = file.filter(|file| file.exists()) {
+            known
+                .read_file(&file, KnownHostFileKind::OpenSSH)
+                .map_err(|e| format!("cannot read {}: {e}", file.display()))?;
+        }
+        match known.check_port(&url.host, url.port, key) {
+            CheckResult::Match => Ok(()),
+            CheckResult::Mismatch => Err(format!(
+                "the host key of {} does not match the one in ~/.ssh/known_hosts",
+                url.host
+            )),
+            CheckResult::NotFound => Err(format!(
+                "{} is not in ~/.ssh/known_hosts; add its host key (ssh-keyscan) first",
+                url.host
+            )),
+            CheckResult::Failure => Err(format!("cannot check the host key of {}", url.host)),
+        }
+    }
+
+    /// Log in with `key`, or else with the agent's keys and then the default ones.
+    fn log_in(session: &Session, url: &Url, key: Option<&Path>) -> Result<(), String> {
+        let user = url.user.as_str();
+        if let Some(key) = key {
+            return session
+                .userauth_pubkey_file(user, None, key, None)
+                .map_err(|e| format!("login as {user} with {} failed: {e}", key.display()));
+        }
+        if session.userauth_agent(user).is_ok() && session.authenticated() {
+            return Ok(());
+        }
+        let ssh_dir = home().map(|home| home.join(".ssh"));
+        for name in DEFAULT_KEYS {
+            let Some(key) = ssh_dir.as_ref().map(|dir| dir.join(name)) else {
+                break;
+            };
+            if key.exists() && session.userauth_pubkey_file(user, None, &key, None).is_ok() {
+                return Ok(());
+            }
+        }
+        Err(format!(
+            "login as {user} failed: the server accepted no key from the SSH agent or \
+             ~/.ssh ({}); pass ssh_key",
+            DEFAULT_KEYS.join(", ")
+        ))
+    }
+
+    /// Open a TCP connection to the first of the host's addresses that accepts one
+    /// within `timeout`.
+    fn connect_tcp(url: &Url, timeout: Duration) -> Result<TcpStream, String> {
+        let failed =
+            |e: std::io::Error| format!("cannot connect to {}:{}: {e}", url.host, url.port);
+        let addresses = (url.host.as_str(), url.port)
+            .to_socket_addrs()
+            .map_err(failed)?;
+        let mut last_error = None;
+        for address in addresses {
+            match TcpStream::connect_timeout(&address, timeout) {
+                Ok(tcp) => return Ok(tcp),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(failed(last_error.unwrap_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "no address found")
+        })))
+    }
+
+    /// Connect to the server, check its host key and log in. Each SSH call after the
+    /// TCP connection gives up after `timeout` as well.
+    fn connect(url: &Url, key: Option<&Path>, timeout: Duration) -> Result<Sftp, String> {
+        let tcp = connect_tcp(url, timeout)?;
+        let mut session = Session::new().map_err(|e| e.to_string())?;
+        session.set_timeout(u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX));
+        session.set_tcp_stream(tcp);
+        session
+            .handshake()
+            .map_err(|e| format!("SSH handshake with {} failed: {e}", url.host))?;
+        check_host_key(&session, url)?;
+        log_in(&session, url, key)?;
+        session
+            .sftp()
+            .map_err(|e| format!("cannot start SFTP: {e}"))
+    }
+
+    /// Copy the remote file into `output`.
+    fn download(
+        url: &Url,
+        key: Option<&Path>,
+        timeout: Duration,
+        output: &mut impl Write,
+    ) -> Result<(), String> {
+        let sftp = connect(url, key, timeout)?;
+        let file = sftp.open(Path::new(&url.path)).map_err(|e| e.to_string())?;
+        let mut remote = BufReader::with_capacity(TRANSFER_SIZE, file);
+        std::io::copy(&mut remote, output).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Append `input` to the remote file until `input` reaches EOF, first sending the
+    /// file's length to `existing`.
+    fn upload(
+        url: &Url,
+        key: Option<&Path>,
+        timeout: Duration,
+        input: &mut impl Read,
+        existing: oneshot::Sender<u64>,
+    ) -> Result<(), String> {
+        let sftp = connect(url, key, timeout)?;
+        let flags = OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::APPEND;
+        let mut file = sftp
+            .open_mode(Path::new(&url.path), flags, CREATE_MODE, OpenType::File)
+            .map_err(|e| e.to_string())?;
+        let len = file.stat().map_err(|e| e.to_string())?.size.unwrap_or(0);
+        let _ = existing.send(len); // The writer may be gone
+        let mut remote = BufWriter::with_capacity(TRANSFER_SIZE, file);
+        std::io::copy(input, &mut remote).map_err(|e| e.to_string())?;
+        remote.flush().map_err(|e| e.to_string())
+    }
+
+    pub(super) fn open(
+        url: Url,
+        key: Option<FilePath>,
+        timeout: Duration,
+    ) -> PyResult<(File, PipeSource)> {
+        spawn_reader(move |writer| {
+            let key = key.as_ref().map(paths::native);
+            download(&url, key.as_deref(), timeout, writer)
+                .map_err(|e| format!("Failed to read file {}: {e}", url.url))
+        })
+    }
+
+    pub(super) fn create(
+        url: Url,
+        key: Option<FilePath>,
+        timeout: Duration,
+    ) -> PyResult<(File, PipeSink)> {
+        let (existing, reported) = oneshot::channel();
+        let (file, sink) = spawn_writer(move |reader| {
+            let key = key.as_ref().map(paths::native);
+            upload(&url, key.as_deref(), timeout, reader, existing)
+                .map_err(|e| format!("Failed to write file {}: {e}", url.url))
+        })?;
+        Ok((file, sink.appending(reported)))
+    }
+}
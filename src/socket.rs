@@ -0,0 +1,206 @@
+//! TCP connections read by `Reader.from_socket()` and written by `Writer.to_socket()`.
+//!
+//! Readers and writers hold a `Conn`: an open file, read or written through io_uring
+//! when that is enabled (see [`crate::uring`]), or a tokio `TcpStream`. A
+//! connection is made asynchronously by the first read or write rather than in the
+//! constructor, so creating one never blocks the event loop, and its reads and writes
+//! wait on tokio's reactor instead of tying up a blocking-pool thread. A connection that
+//! can't be made raises `ConnectionError` from that first read or write, and is tried
+//! again only when `retries=` retries the read.
+
+use crate::timeout;
+use pyo3::prelude::*;
+use std::fs::Metadata;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncSeekExt, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+
+type Connecting = Pin<Box<dyn Future<Output = io::Result<TcpStream>> + Send>>;
+
+/// What a reader reads from or a writer writes to.
+pub(crate) enum Conn {
+    File(File),
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    Ring(crate::uring::RingFile), // A file read or written through io_uring
+    Connecting(Address, Connecting),
+    Tcp(TcpStream),
+    Failed(Address, io::ErrorKind, String), // The connection couldn't be made
+}
+
+/// Where a connection goes, kept to make it again after a failure.
+#[derive(Clone)]
+pub(crate) struct Address {
+    host: String,
+    port: u16,
+    limit: Option<Duration>, // connect_timeout
+}
+
+/// Marks an I/O error as a failure to connect, raised as `ConnectionError`.
+#[derive(Debug)]
+struct ConnectFailed(String);
+
+impl std::fmt::Display for ConnectFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ConnectFailed {}
+
+impl From<File> for Conn {
+    fn from(file: File) -> Self {
+        Conn::File(file)
+    }
+}
+
+impl Conn {
+    /// A connection to `host:port`, trying every address `host` resolves to, made by the
+    /// first read or write. `connect_timeout` is in seconds.
+    pub(crate) fn connect(host: &str, port: u16, connect_timeout: Option<f64>) -> PyResult<Self> {
+        let limit = timeout::parse(connect_timeout)?;
+        Ok(Conn::connecting(Address {
+            host: host.to_string(),
+            port,
+            limit,
+        }))
+    }
+
+    fn connecting(address: Address) -> Self {
+        let Address { host, port, limit } = address.clone();
+        Conn::Connecting(
+            address,
+            Box::pin(async move {
+                connect(&host, port, limit).await.map_err(|e| {
+                    let message = format!("Failed to connect to {host}:{port}: {e}");
+                    io::Error::new(e.kind(), ConnectFailed(message))
+                })
+            }),
+        )
+    }
+
+    /// Start connecting again if the connection couldn't be made, so a retried read
+    /// doesn't just get the same failure back.
+    pub(crate) fn reconnect(&mut self) {
+        if let Conn::Failed(address, ..) = self {
+            *self = Conn::connecting(address.clone());
+        }
+    }
+
+    /// The open file's metadata, unless this is a connection.
+    pub(crate) async fn metadata(&mut self) -> Option<io::Result<Metadata>> {
+        match self {
+            Conn::File(file) => Some(file.metadata().await),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Conn::Ring(file) => Some(file.metadata()),
+            _ => None,
+        }
+    }
+
+    /// The open file's position, unless this is a connection.
+    pub(crate) async fn position(&mut self) -> Option<io::Result<u64>> {
+        match self {
+            Conn::File(file) => Some(file.stream_position().await),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Conn::Ring(file) => Some(Ok(file.position())),
+            _ => None,
+        }
+    }
+
+    /// Wait for the connection to be made, if it is being made.
+    fn poll_connected(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if let Conn::Connecting(address, connecting) = self {
+            *self = match ready!(connecting.as_mut().poll(cx)) {
+                Ok(stream) => Conn::Tcp(stream),
+                Err(e) => Conn::Failed(address.clone(), e.kind(), e.to_string()),
+            };
+        }
+        match self {
+            Conn::Failed(_, kind, message) => {
+                Poll::Ready(Err(io::Error::new(*kind, ConnectFailed(message.clone()))))
+            }
+            _ => Poll::Ready(Ok(())),
+        }
+    }
+}
+
+async fn connect(host: &str, port: u16, limit: Option<Duration>) -> io::Result<TcpStream> {
+    match limit {
+        Some(limit) => tokio::time::timeout(limit, TcpStream::connect((host, port)))
+            .await
+            .unwrap_or_else(|_| Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"))),
+        None => TcpStream::connect((host, port)).await,
+    }
+}
+
+/// `ConnectionError` for an I/O error that comes from a failure to connect, else None.
+pub(crate) fn connect_error(e: &io::Error) -> Option<PyErr> {
+    let failed = e.get_ref()?.downcast_ref::<ConnectFailed>()?;
+    Some(PyErr::new::<pyo3::exceptions::PyConnectionError, _>(
+        failed.0.clone(),
+    ))
+}
+
+impl AsyncRead for Conn {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_connected(cx))?;
+        match this {
+            Conn::File(file) => Pin::new(file).poll_read(cx, buf),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Conn::Ring(file) => Pin::new(file).poll_read(cx, buf),
+            Conn::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            Conn::Connecting(..) | Conn::Failed(..) => unreachable!("connection not made"),
+        }
+    }
+}
+
+impl AsyncWrite for Conn {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_connected(cx))?;
+        match this {
+            Conn::File(file) => Pin::new(file).poll_write(cx, buf),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Conn::Ring(file) => Pin::new(file).poll_write(cx, buf),
+            Conn::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            Conn::Connecting(..) | Conn::Failed(..) => unreachable!("connection not made"),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_connected(cx))?;
+        match this {
+            Conn::File(file) => Pin::new(file).poll_flush(cx),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Conn::Ring(file) => Pin::new(file).poll_flush(cx),
+            Conn::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            Conn::Connecting(..) | Conn::Failed(..) => unreachable!("connection not made"),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_connected(cx))?;
+        match this {
+            Conn::File(file) => Pin::new(file).poll_shutdown(cx),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Conn::Ring(file) => Pin::new(file).poll_shutdown(cx),
+            Conn::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+            Conn::Connecting(..) | Conn::Failed(..) => unreachable!("connection not made"),
+        }
+    }
+}
@@ -0,0 +1,514 @@
+//! External-memory sorting of CSV files.
+//!
+//! Rows are sorted in bounded chunks, by row count and optionally by `max_memory`;
+//! chunks that don't fit in memory are spilled to temporary CSV files and combined
+//! with a streaming k-way merge. At most `MERGE_FAN_IN` runs are open at once; more
+//! are merged in several passes, each combining groups of runs into longer ones. The
+//! spill files are removed when the sort ends,
+//! including when it fails or the awaiting task is cancelled.
+//!
+//! `merge_sorted()` runs the same merge over files that are already sorted, such as
+//! daily extracts of a time series, without sorting them again.
+
+use crate::errors::CsvIoError;
+use crate::ops::{
+    check_not_input, check_temp_dir, create_writer, csv_err, extract_columns, open_reader,
+    read_header, simple_dialect, spawn_cancellable_py, spill_dir, Cancellation,
+};
+use crate::{memory, validate_path, DialectConfig};
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use pyo3::prelude::*;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fs::File;
+
+/// Default number of rows sorted in memory before spilling a run to disk.
+const DEFAULT_CHUNK_ROWS: usize = 500_000;
+
+/// Most spilled runs merged at once, keeping open files well below common limits.
+const MERGE_FAN_IN: usize = 64;
+
+/// A single column value as it participates in ordering.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum KeyValue {
+    /// Empty field - sorts before everything else.
+    Empty,
+    /// Numeric field (only when sorting with `typed=True`).
+    Number(f64),
+    /// Any other field, compared as a string.
+    Text(String),
+}
+
+impl Eq for KeyValue {}
+
+impl Ord for KeyValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (KeyValue::Empty, KeyValue::Empty) => Ordering::Equal,
+            (KeyValue::Empty, _) => Ordering::Less,
+            (_, KeyValue::Empty) => Ordering::Greater,
+            (KeyValue::Number(a), KeyValue::Number(b)) => a.total_cmp(b),
+            (KeyValue::Number(_), KeyValue::Text(_)) => Ordering::Less,
+            (KeyValue::Text(_), KeyValue::Number(_)) => Ordering::Greater,
+            (KeyValue::Text(a), KeyValue::Text(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for KeyValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// One component of a sort key, with its direction applied.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum KeyPart {
+    Asc(KeyValue),
+    Desc(Reverse<KeyValue>),
+}
+
+/// Sort key for a whole row.
+pub(crate) type SortKey = Vec<KeyPart>;
+
+/// Estimated size of a sort key, for `max_memory`.
+fn key_size(key: &SortKey) -> usize {
+    let text = |value: &KeyValue| match value {
+        KeyValue::Text(text) => text.len(),
+        _ => 0,
+    };
+    std::mem::size_of::<SortKey>()
+        + key
+            .iter()
+            .map(|part| {
+                std::mem::size_of::<KeyPart>()
+                    + match part {
+                        KeyPart::Asc(value) | KeyPart::Desc(Reverse(value)) => text(value),
+                    }
+            })
+            .sum::<usize>()
+}
+
+/// Resolved `by=` specification: column index and direction for each key column.
+#[derive(Debug, Clone)]
+pub(crate) struct SortSpec {
+    columns: Vec<(usize, bool)>,
+    typed: bool,
+}
+
+impl SortSpec {
+    /// Resolve column names (prefix `-` for descending) against `header`.
+    pub(crate) fn new(
+        by: &[String],
+        header: &StringRecord,
+        path: &str,
+        typed: bool,
+    ) -> PyResult<Self> {
+        let columns = by
+            .iter()
+            .map(|spec| {
+                let (name, descending) = match spec.strip_prefix('-') {
+                    Some(name) if !header.iter().any(|h| h == spec) => (name, true),
+                    _ => (spec.as_str(), false),
+                };
+                header
+                    .iter()
+                    .position(|h| h == name)
+                    .map(|index| (index, descending))
+                    .ok_or_else(|| {
+                        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                            "Sort column '{name}' not found in header of '{path}'"
+                        ))
+                    })
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(SortSpec { columns, typed })
+    }
+
+    /// Build the sort key for `record`.
+    pub(crate) fn key(&self, record: &StringRecord) -> SortKey {
+        self.columns
+            .iter()
+            .map(|&(index, descending)| {
+                let field = record.get(index).unwrap_or("");
+                let value = if field.is_empty() {
+                    KeyValue::Empty
+                } else if let Some(n) = self
+                    .typed
+                    .then(|| field.trim().parse::<f64>().ok())
+                    .flatten()
+                {
+                    KeyValue::Number(n)
+                } else {
+                    KeyValue::Text(field.to_string())
+                };
+                if descending {
+                    KeyPart::Desc(Reverse(value))
+                } else {
+                    KeyPart::Asc(value)
+                }
+            })
+            .collect()
+    }
+}
+
+/// A source of already-sorted records for the k-way merge.
+pub(crate) struct SortedRun {
+    reader: csv::Reader<File>,
+    path: String,
+    rows: usize, // Records read so far
+}
+
+impl SortedRun {
+    pub(crate) fn new(reader: csv::Reader<File>, path: String) -> Self {
+        SortedRun {
+            reader,
+            path,
+            rows: 0,
+        }
+    }
+
+    fn next_record(&mut self) -> PyResult<Option<StringRecord>> {
+        let mut record = StringRecord::new();
+        match self.reader.read_record(&mut record) {
+            Ok(true) => {
+                self.rows += 1;
+                Ok(Some(record))
+            }
+            Ok(false) => Ok(None),
+            Err(e) => Err(csv_err(&self.path, e)),
+        }
+    }
+}
+
+/// Merge sorted runs into `writer`. Ties are broken by run order, so the merge is stable.
+/// A run found out of order fails the merge rather than producing unsorted output.
+/// Returns the number of records written.
+pub(crate) fn merge_runs(
+    runs: &mut [SortedRun],
+    spec: &SortSpec,
+    writer: &mut csv::Writer<File>,
+    dst: &str,
+    cancellation: &Cancellation,
+) -> PyResult<usize> {
+    let mut heads: Vec<Option<StringRecord>> = Vec::with_capacity(runs.len());
+    let mut heap: BinaryHeap<Reverse<(SortKey, usize)>> = BinaryHeap::new();
+    for (index, run) in runs.iter_mut().enumerate() {
+        let head = run.next_record()?;
+        if let Some(record) = &head {
+            heap.push(Reverse((spec.key(record), index)));
+        }
+        heads.push(head);
+    }
+
+    let mut written = 0usize;
+    while let Some(Reverse((previous, index))) = heap.pop() {
+        cancellation.check()?;
+        if let Some(record) = heads[index].take() {
+            writer.write_record(&record).map_err(|e| csv_err(dst, e))?;
+            written += 1;
+        }
+        if let Some(record) = runs[index].next_record()? {
+            let key = spec.key(&record);
+            if key < previous {
+                let run = &runs[index];
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "'{}' is not sorted by the merge columns: record {} sorts before the one preceding it",
+                    run.path, run.rows
+                )));
+            }
+            heap.push(Reverse((key, index)));
+            heads[index] = Some(record);
+        }
+    }
+    Ok(written)
+}
+
+/// Sort a chunk in place by its precomputed keys (stable).
+fn sort_chunk(rows: &mut [(SortKey, StringRecord)]) {
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+}
+
+/// Write a sorted chunk to a new temporary run file.
+fn spill_run(
+    rows: &mut Vec<(SortKey, StringRecord)>,
+    temp_dir: &tempfile::TempDir,
+    index: usize,
+) -> PyResult<String> {
+    let path = temp_dir.path().join(format!("run_{index}.csv"));
+    let path = path.to_string_lossy().into_owned();
+    let mut writer = WriterBuilder::new()
+        .flexible(true)
+        .from_path(&path)
+        .map_err(|e| csv_err(&path, e))?;
+    for (_, record) in rows.drain(..) {
+        writer
+            .write_record(&record)
+            .map_err(|e| csv_err(&path, e))?;
+    }
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to write spill file '{path}': {e}")))?;
+    Ok(path)
+}
+
+/// Open a spilled run for merging.
+fn open_run(path: &str) -> PyResult<SortedRun> {
+    ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map(|reader| SortedRun::new(reader, path.to_string()))
+        .map_err(|e| csv_err(path, e))
+}
+
+/// Merge consecutive groups of `MERGE_FAN_IN` runs into longer runs until at most that
+/// many are left. Groups keep the order of the runs, so the sort stays stable.
+fn merge_passes(
+    mut run_paths: Vec<String>,
+    spec: &SortSpec,
+    temp_dir: &tempfile::TempDir,
+    cancellation: &Cancellation,
+) -> PyResult<Vec<String>> {
+    let mut next_run = run_paths.len();
+    while run_paths.len() > MERGE_FAN_IN {
+        let mut merged = Vec::with_capacity(run_paths.len().div_ceil(MERGE_FAN_IN));
+        for group in run_paths.chunks(MERGE_FAN_IN) {
+            let path = temp_dir.path().join(format!("run_{next_run}.csv"));
+            let path = path.to_string_lossy().into_owned();
+            next_run += 1;
+            let mut runs = group
+                .iter()
+                .map(|path| open_run(path))
+                .collect::<PyResult<Vec<_>>>()?;
+            let mut writer = WriterBuilder::new()
+                .flexible(true)
+                .from_path(&path)
+                .map_err(|e| csv_err(&path, e))?;
+            merge_runs(&mut runs, spec, &mut writer, &path, cancellation)?;
+            writer.flush().map_err(|e| {
+                CsvIoError::new_err(format!("Failed to write spill file '{path}': {e}"))
+            })?;
+            // Free the disk space of the merged runs before the next group
+            for path in group {
+                std::fs::remove_file(path).ok();
+            }
+            merged.push(path);
+        }
+        run_paths = merged;
+    }
+    Ok(run_paths)
+}
+
+/// Sort `src` into `dst` with at most `chunk_rows` rows, and at most `max_memory`
+/// bytes of rows, in memory at once.
+#[allow(clippy::too_many_arguments)] // Mirrors the options of the sort() function
+fn sort_file(
+    src: &str,
+    dst: &str,
+    by: &[String],
+    typed: bool,
+    chunk_rows: usize,
+    max_memory: Option<usize>,
+    dialect: &DialectConfig,
+    temp_dir: Option<&str>,
+    cancellation: &Cancellation,
+) -> PyResult<usize> {
+    let mut reader = open_reader(src, dialect)?;
+    let Some(header) = read_header(&mut reader, src)? else {
+        create_writer(dst, dialect)?;
+        return Ok(0);
+    };
+    let spec = SortSpec::new(by, &header, src, typed)?;
+
+    let mut spill: Option<tempfile::TempDir> = None;
+    let mut run_paths: Vec<String> = Vec::new();
+    let mut chunk: Vec<(SortKey, StringRecord)> = Vec::new();
+    let mut chunk_bytes = 0;
+    let mut record = StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        cancellation.check()?;
+        let key = spec.key(&record);
+        if max_memory.is_some() {
+            chunk_bytes += key_size(&key) + memory::record_size(&record);
+        }
+        chunk.push((key, record.clone()));
+        if chunk.len() >= chunk_rows || max_memory.is_some_and(|limit| chunk_bytes >= limit) {
+            chunk_bytes = 0;
+            sort_chunk(&mut chunk);
+            if spill.is_none() {
+                spill = Some(spill_dir(temp_dir, "sort")?);
+            }
+            if let Some(dir) = &spill {
+                run_paths.push(spill_run(&mut chunk, dir, run_paths.len())?);
+            }
+        }
+    }
+    sort_chunk(&mut chunk);
+
+    let mut writer = create_writer(dst, dialect)?;
+    writer.write_record(&header).map_err(|e| csv_err(dst, e))?;
+    let written = if run_paths.is_empty() {
+        // Everything fit in memory - no merge needed
+        for (_, record) in &chunk {
+            writer.write_record(record).map_err(|e| csv_err(dst, e))?;
+        }
+        chunk.len()
+    } else {
+        if let Some(dir) = &spill {
+            if !chunk.is_empty() {
+                run_paths.push(spill_run(&mut chunk, dir, run_paths.len())?);
+            }
+            run_paths = merge_passes(run_paths, &spec, dir, cancellation)?;
+        }
+        let mut runs = run_paths
+            .iter()
+            .map(|path| open_run(path))
+            .collect::<PyResult<Vec<_>>>()?;
+        merge_runs(&mut runs, &spec, &mut writer, dst, cancellation)?
+    };
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+    Ok(written)
+}
+
+/// Sort a CSV file by one or more columns using an external merge sort.
+///
+/// # Arguments
+/// * `src` - Input CSV file (first row is the header)
+/// * `dst` - Output CSV file (created or truncated)
+/// * `by` - Column name or list of names; prefix a name with `-` for descending order
+/// * `typed` - Compare numeric fields as numbers (default: True)
+/// * `chunk_rows` - Rows sorted in memory before spilling to disk (default: 500000)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+/// * `max_memory` - Also spill once the rows held take this many bytes (default: None)
+/// * `temp_dir` - Directory to create spill files under (default: None, the system's
+///   temporary directory)
+///
+/// Returns the number of data rows written.
+#[pyfunction]
+#[pyo3(signature = (src, dst, by, typed = true, chunk_rows = None, delimiter = None, quotechar = None, max_memory = None, temp_dir = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn sort(
+    py: Python<'_>,
+    src: String,
+    dst: String,
+    by: &Bound<'_, PyAny>,
+    typed: bool,
+    chunk_rows: Option<usize>,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+    max_memory: Option<usize>,
+    temp_dir: Option<String>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&src)?;
+    validate_path(&dst)?;
+    check_temp_dir(temp_dir.as_deref())?;
+    let by = extract_columns(by, "by")?;
+    let chunk_rows = chunk_rows.unwrap_or(DEFAULT_CHUNK_ROWS).max(1);
+    let max_memory = memory::parse(max_memory)?;
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    spawn_cancellable_py(py, move |cancellation| {
+        sort_file(
+            &src,
+            &dst,
+            &by,
+            typed,
+            chunk_rows,
+            max_memory,
+            &dialect,
+            temp_dir.as_deref(),
+            cancellation,
+        )
+    })
+}
+
+/// Merge already-sorted files with the same header into `dst`.
+fn merge_files(
+    paths: &[String],
+    dst: &str,
+    by: &[String],
+    typed: bool,
+    dialect: &DialectConfig,
+    cancellation: &Cancellation,
+) -> PyResult<usize> {
+    check_not_input(dst, paths)?;
+    let mut header: Option<(StringRecord, &str)> = None;
+    let mut runs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let mut reader = open_reader(path, dialect)?;
+        // Empty files have no rows to merge
+        let Some(file_header) = read_header(&mut reader, path)? else {
+            continue;
+        };
+        match &header {
+            Some((first, first_path)) if *first != file_header => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Header of '{path}' differs from the header of '{first_path}'"
+                )));
+            }
+            Some(_) => {}
+            None => header = Some((file_header, path)),
+        }
+        runs.push(SortedRun::new(reader, path.clone()));
+    }
+
+    let mut writer = create_writer(dst, dialect)?;
+    let written = match &header {
+        Some((header, path)) => {
+            let spec = SortSpec::new(by, header, path, typed)?;
+            writer.write_record(header).map_err(|e| csv_err(dst, e))?;
+            merge_runs(&mut runs, &spec, &mut writer, dst, cancellation)?
+        }
+        None => 0,
+    };
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+    Ok(written)
+}
+
+/// Merge CSV files that are each sorted by the same columns into one sorted file.
+///
+/// A streaming k-way merge holds one row per input in memory. Rows that compare equal
+/// keep the order of `paths`. Inputs must share a header; an input found out of order
+/// raises `ValueError`.
+///
+/// # Arguments
+/// * `paths` - Input CSV files, each sorted by `by` (first row is the header)
+/// * `dst` - Output CSV file (created or truncated)
+/// * `by` - Column name or list of names the inputs are sorted by; prefix a name with
+///   `-` for descending order
+/// * `typed` - Compare numeric fields as numbers, as `sort()` does (default: True)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+///
+/// Returns the number of data rows written.
+#[pyfunction]
+#[pyo3(signature = (paths, dst, by, typed = true, delimiter = None, quotechar = None))]
+pub(crate) fn merge_sorted(
+    py: Python<'_>,
+    paths: Vec<String>,
+    dst: String,
+    by: &Bound<'_, PyAny>,
+    typed: bool,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+) -> PyResult<Py<PyAny>> {
+    for path in &paths {
+        validate_path(path)?;
+    }
+    validate_path(&dst)?;
+    let by = extract_columns(by, "by")?;
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    spawn_cancellable_py(py, move |cancellation| {
+        merge_files(&paths, &dst, &by, typed, &dialect, cancellation)
+    })
+}
@@ -0,0 +1,102 @@
+//! Byte spans of fields (`field_spans=True` on `Reader`).
+//!
+//! The parser keeps only the unquoted contents of fields, so where each field lies in
+//! the file is found again from the bytes of the record, as it is read. A span covers
+//! the field as written, quotes and escapes included and the delimiter left out, so
+//! replacing those bytes patches the field in place. The spans travel with the row as
+//! one extra last field until it is handed to Python, where [`take`] removes it.
+
+use crate::DialectConfig;
+use csv::Terminator;
+use std::ops::Range;
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Where each field of the record in `bytes` lies in it. `bytes` may start with a
+/// byte order mark and the blank lines the parser skipped before the record; an empty
+/// range is an empty, unquoted field.
+pub(crate) fn fields(bytes: &[u8], dialect: &DialectConfig) -> Vec<Range<usize>> {
+    let ends = |b: u8| match dialect.lineterminator {
+        Terminator::Any(t) => b == t,
+        _ => b == b'\r' || b == b'\n',
+    };
+    let mut i = if bytes.starts_with(BOM) { BOM.len() } else { 0 };
+    i += bytes[i..].iter().take_while(|&&b| ends(b)).count();
+    let mut fields = Vec::new();
+    loop {
+        let start = i;
+        if bytes
+            .get(i)
+            .is_some_and(|&b| Some(b) == dialect.read_quote())
+        {
+            i += 1;
+            while let Some(&b) = bytes.get(i) {
+                if Some(b) == dialect.escapechar {
+                    i += 2;
+                } else if b == dialect.quotechar {
+                    i += 1;
+                    if bytes.get(i) != Some(&dialect.quotechar) {
+                        break; // Closing quote; a doubled one is a literal quote
+                    }
+                    i += 1;
+                } else {
+                    i += 1;
+                }
+            }
+            i = i.min(bytes.len());
+        }
+        // The unquoted field, or any text after the closing quote
+        while bytes
+            .get(i)
+            .is_some_and(|&b| b != dialect.delimiter && !ends(b))
+        {
+            i += 1;
+        }
+        fields.push(start..i);
+        match bytes.get(i) {
+            Some(&b) if b == dialect.delimiter => i += 1,
+            _ => break,
+        }
+    }
+    fields
+}
+
+/// The fields of a record of `count` fields a row is made of: those at `selected` for
+/// a row of `columns=`, leaving out any past the end of the record.
+pub(crate) fn sources(selected: Option<&[usize]>, count: usize) -> Vec<usize> {
+    match selected {
+        Some(indices) => indices.iter().copied().filter(|&i| i < count).collect(),
+        None => (0..count).collect(),
+    }
+}
+
+/// The spans of the fields of a row parsed from `record`, which starts at `offset`
+/// in the file, as the field [`take`] reads them back from.
+pub(crate) fn encode(
+    record: &[u8],
+    offset: u64,
+    dialect: &DialectConfig,
+    selected: Option<&[usize]>,
+) -> String {
+    let fields = fields(record, dialect);
+    let spans: Vec<String> = sources(selected, fields.len())
+        .into_iter()
+        .map(|i| {
+            let Range { start, end } = &fields[i];
+            format!("{}:{}", offset + *start as u64, offset + *end as u64)
+        })
+        .collect();
+    spans.join(" ")
+}
+
+/// Remove the spans [`encode`] added to the end of a row, as `(start, end)` pairs.
+pub(crate) fn take(row: &mut Vec<String>) -> Vec<(u64, u64)> {
+    let spans = row.pop().unwrap_or_default();
+    spans
+        .split_whitespace()
+        .filter_map(|span| {
+            let (start, end) = span.split_once(':')?;
+            Some((start.parse().ok()?, end.parse().ok()?))
+        })
+        .collect()
+}
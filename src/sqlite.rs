@@ -0,0 +1,823 @@
+//! Loading CSV files into SQLite, and SQL queries over CSV files.
+//!
+//! Parsing, inserting and querying all happen on a blocking worker thread, so a large
+//! load or query never stalls the event loop or holds the GIL. Queries read their files
+//! through the virtual table in `crate::csvtab` rather than loading them.
+
+use crate::awaitable::future_into_py;
+use crate::csvtab;
+use crate::errors::{CsvIoError, CsvParseError};
+use crate::ops::csv_err;
+use crate::progress::Progress;
+use crate::{sandbox, validate_path, DialectConfig};
+use csv::{ReaderBuilder, StringRecord};
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList, PyModule};
+use rusqlite::hooks::{AuthAction, AuthContext, Authorization};
+use rusqlite::types::Value;
+use rusqlite::{ffi, params_from_iter, Connection, ErrorCode};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+
+/// Default number of rows inserted per transaction.
+const DEFAULT_BATCH_SIZE: usize = 10_000;
+
+/// Quote an SQL identifier (table or column name).
+pub(crate) fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Keywords that start a column constraint rather than continue a type name.
+const COLUMN_CONSTRAINTS: &[&str] = &[
+    "AS",
+    "CHECK",
+    "COLLATE",
+    "CONSTRAINT",
+    "DEFAULT",
+    "GENERATED",
+    "NOT",
+    "NULL",
+    "PRIMARY",
+    "REFERENCES",
+    "UNIQUE",
+];
+
+/// Whether fields of a column declared as `ty` are stored as text, by SQLite's rules for
+/// deriving a column's type affinity. Raises ValueError for anything but a type name,
+/// optionally with a size such as `VARCHAR(20)` or `DECIMAL(10, 2)`, so a schema can't
+/// smuggle other SQL into `CREATE TABLE`.
+fn text_affinity(column: &str, ty: &str) -> PyResult<bool> {
+    let (name, size) = match ty.split_once('(') {
+        Some((name, size)) => (name, Some(size)),
+        None => (ty, None),
+    };
+    let name_ok = !name.trim().is_empty()
+        && name.split_whitespace().all(|word| {
+            word.starts_with(|c: char| c.is_ascii_alphabetic())
+                && word.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+                && !COLUMN_CONSTRAINTS.contains(&word.to_ascii_uppercase().as_str())
+        });
+    let size_ok = size.is_none_or(|size| {
+        size.strip_suffix(')').is_some_and(|numbers| {
+            let numbers: Vec<&str> = numbers.split(',').map(str::trim).collect();
+            numbers.len() <= 2
+                && numbers.iter().all(|n| {
+                    let digits = n.strip_prefix(['+', '-']).unwrap_or(n);
+                    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+                })
+        })
+    });
+    if !(name_ok && size_ok) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid SQL type '{ty}' for column '{column}'; use a type name such as \
+             TEXT, INTEGER, REAL, NUMERIC, BLOB or VARCHAR(20)"
+        )));
+    }
+    let name = name.to_ascii_uppercase();
+    Ok(!name.contains("INT") && ["CHAR", "CLOB", "TEXT"].iter().any(|t| name.contains(t)))
+}
+
+/// Check that an existing `table` has a column for each of `headers`, which are
+/// inserted by name, so a table created for another file can't take rows in the
+/// wrong columns.
+fn check_table_columns(
+    conn: &Connection,
+    db: &str,
+    table: &str,
+    headers: &[String],
+) -> PyResult<()> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM pragma_table_info(?1)")
+        .map_err(|e| sqlite_err(db, e))?;
+    let columns: Vec<String> = stmt
+        .query_map([table], |row| row.get(0))
+        .and_then(|rows| rows.collect())
+        .map_err(|e| sqlite_err(db, e))?;
+    let missing: Vec<&str> = headers
+        .iter()
+        .filter(|h| !columns.iter().any(|c| c.eq_ignore_ascii_case(h)))
+        .map(String::as_str)
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+        "Table '{table}' in database '{db}' has no column {}; its columns are {}",
+        missing.join(", "),
+        columns.join(", ")
+    )))
+}
+
+/// Convert an SQLite error into a Python IOError with the database path for context.
+pub(crate) fn sqlite_err(db: &str, e: rusqlite::Error) -> PyErr {
+    CsvIoError::new_err(format!("SQLite error in database '{db}': {e}"))
+}
+
+/// Create `table` from the CSV header and insert every row in batches.
+///
+/// `schema` maps column names to SQL types; columns it doesn't mention are `TEXT`.
+/// Empty fields in columns without `TEXT` affinity are stored as NULL. Rows are inserted
+/// by column name, into a table that may already exist with more columns.
+/// Returns the number of rows inserted.
+#[allow(clippy::too_many_arguments)] // Internal helper for to_sqlite
+fn load_csv(
+    conn: &mut Connection,
+    db: &str,
+    path: &str,
+    table: &str,
+    schema: &[(String, String)],
+    batch_size: usize,
+    dialect: &DialectConfig,
+    progress: Option<&Progress>,
+) -> PyResult<usize> {
+    let mut builder = ReaderBuilder::new();
+    dialect.apply_to_reader(&mut builder, None);
+    let mut reader = builder
+        .has_headers(true)
+        .from_path(path)
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file '{path}': {e}")))?;
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| csv_err(path, e))?
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    if headers.is_empty() {
+        return Err(CsvParseError::new_err(format!(
+            "File '{path}' has no header row"
+        )));
+    }
+
+    for (column, _) in schema {
+        if !headers.contains(column) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Schema column '{column}' not found in header of '{path}'"
+            )));
+        }
+    }
+    let types: Vec<String> = headers
+        .iter()
+        .map(|h| {
+            schema
+                .iter()
+                .find(|(column, _)| column == h)
+                .map(|(_, ty)| ty.clone())
+                .unwrap_or_else(|| "TEXT".to_string())
+        })
+        .collect();
+    let nullable: Vec<bool> = headers
+        .iter()
+        .zip(&types)
+        .map(|(h, ty)| text_affinity(h, ty).map(|text| !text))
+        .collect::<PyResult<_>>()?;
+
+    let columns = headers
+        .iter()
+        .zip(&types)
+        .map(|(h, ty)| format!("{} {ty}", quote_ident(h)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {} ({columns})",
+            quote_ident(table)
+        ),
+        [],
+    )
+    .map_err(|e| sqlite_err(db, e))?;
+    check_table_columns(conn, db, table, &headers)?;
+
+    let names = headers
+        .iter()
+        .map(|h| quote_ident(h))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let placeholders = vec!["?"; headers.len()].join(", ");
+    let insert_sql = format!(
+        "INSERT INTO {} ({names}) VALUES ({placeholders})",
+        quote_ident(table)
+    );
+
+    let batch_size = batch_size.max(1);
+    let mut inserted = 0usize;
+    let mut record = StringRecord::new();
+    loop {
+        let tx = conn.transaction().map_err(|e| sqlite_err(db, e))?;
+        let mut batch_rows = 0usize;
+        {
+            let mut stmt = tx
+                .prepare_cached(&insert_sql)
+                .map_err(|e| sqlite_err(db, e))?;
+            while reader
+                .read_record(&mut record)
+                .map_err(|e| csv_err(path, e))?
+            {
+                // Pad short rows with NULL and drop extra fields so ragged files still load
+                let values = (0..headers.len()).map(|i| match record.get(i) {
+                    Some("") if nullable[i] => None,
+                    Some(field) => Some(field),
+                    None => None,
+                });
+                stmt.execute(params_from_iter(values))
+                    .map_err(|e| sqlite_err(db, e))?;
+                batch_rows += 1;
+                if let Some(progress) = progress {
+                    progress.update(reader.position().byte(), inserted + batch_rows)?;
+                }
+                if batch_rows == batch_size {
+                    break;
+                }
+            }
+        }
+        tx.commit().map_err(|e| sqlite_err(db, e))?;
+        inserted += batch_rows;
+        if batch_rows < batch_size {
+            if let Some(progress) = progress {
+                progress.finish()?;
+            }
+            return Ok(inserted);
+        }
+    }
+}
+
+/// Load a CSV file into an SQLite table.
+///
+/// The first row of the CSV is used as the column names. The table is created if it
+/// doesn't exist and rows are inserted in transactions of `batch_size` rows.
+///
+/// # Arguments
+/// * `path` - Path to the CSV file
+/// * `db` - Path to the SQLite database (created if missing)
+/// * `table` - Name of the table to create and fill
+/// * `schema` - Optional mapping of column name to SQL type (default: all `TEXT`)
+/// * `batch_size` - Rows per transaction (default: 10000)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+/// * `progress` - Called as `progress(bytes_read, bytes_total, rows_inserted)` every
+///   `progress_interval` rows and when done (default: None)
+/// * `progress_interval` - Rows between progress calls (default: 10000)
+///
+/// Returns the number of rows inserted.
+#[pyfunction]
+#[pyo3(signature = (path, db, table, schema = None, batch_size = None, delimiter = None, quotechar = None, progress = None, progress_interval = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn to_sqlite(
+    py: Python<'_>,
+    path: String,
+    db: String,
+    table: String,
+    schema: Option<&Bound<'_, PyDict>>,
+    batch_size: Option<usize>,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+    progress: Option<Py<PyAny>>,
+    progress_interval: Option<usize>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&path)?;
+    validate_path(&db)?;
+    if table.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Table name cannot be empty",
+        ));
+    }
+    let schema: Vec<(String, String)> = match schema {
+        Some(schema) => schema
+            .iter()
+            .map(|(k, v)| Ok((k.extract::<String>()?, v.extract::<String>()?)))
+            .collect::<PyResult<_>>()?,
+        None => Vec::new(),
+    };
+    let dialect = DialectConfig::from_python(
+        None, delimiter, quotechar, None, None, None, None, None, None,
+    )?;
+    let batch_size = batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+    let bytes_total = Progress::total_size(std::slice::from_ref(&path));
+    let progress = Progress::from_args(py, progress, progress_interval, bytes_total)?;
+
+    let future = async move {
+        tokio::task::spawn_blocking(move || {
+            let mut conn = Connection::open(&db).map_err(|e| sqlite_err(&db, e))?;
+            load_csv(
+                &mut conn,
+                &db,
+                &path,
+                &table,
+                &schema,
+                batch_size,
+                &dialect,
+                progress.as_ref(),
+            )
+        })
+        .await
+        .map_err(|e| CsvIoError::new_err(format!("SQLite load task failed: {e}")))?
+    };
+    future_into_py(py, future).map(|bound| bound.unbind())
+}
+
+/// Find CSV files referenced as `FROM 'file.csv'` / `JOIN 'file.csv'` in `sql`.
+///
+/// Returns the rewritten SQL, with each file literal replaced by a table name, and the
+/// list of `(table, path)` pairs to load. The same file referenced twice maps to one table.
+pub(crate) fn rewrite_file_references(sql: &str) -> (String, Vec<(String, String)>) {
+    let mut out = String::with_capacity(sql.len());
+    let mut files: Vec<(String, String)> = Vec::new();
+    let mut last_word = String::new();
+    let mut word = String::new();
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            out.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            last_word = std::mem::take(&mut word).to_ascii_uppercase();
+        }
+        match c {
+            '\'' => {
+                // String literal, with '' as an escaped quote
+                let mut literal = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\'') if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            literal.push('\'');
+                        }
+                        Some('\'') | None => break,
+                        Some(ch) => literal.push(ch),
+                    }
+                }
+                if last_word == "FROM" || last_word == "JOIN" {
+                    let table = match files.iter().find(|(_, path)| *path == literal) {
+                        Some((table, _)) => table.clone(),
+                        None => {
+                            let table = format!("_rapcsv_{}", files.len());
+                            files.push((table.clone(), literal));
+                            table
+                        }
+                    };
+                    out.push_str(&quote_ident(&table));
+                } else {
+                    out.push('\'');
+                    out.push_str(&literal.replace('\'', "''"));
+                    out.push('\'');
+                }
+                last_word.clear();
+            }
+            '"' | '`' | '[' => {
+                // Quoted identifier - copy verbatim
+                let close = if c == '[' { ']' } else { c };
+                out.push(c);
+                for ch in chars.by_ref() {
+                    out.push(ch);
+                    if ch == close {
+                        break;
+                    }
+                }
+                last_word.clear();
+            }
+            c if c.is_whitespace() => out.push(c),
+            _ => {
+                out.push(c);
+                last_word.clear();
+            }
+        }
+    }
+    (out, files)
+}
+
+/// Keep SQL from opening files outside the allowed root (see `crate::sandbox`): an
+/// authorizer denies every `ATTACH`, and only statements that don't write may run,
+/// which rules out `VACUUM INTO` a file of the query's choosing.
+fn confine(conn: &Connection) -> PyResult<()> {
+    conn.authorizer(Some(|ctx: AuthContext<'_>| match ctx.action {
+        AuthAction::Attach { .. } => Authorization::Deny,
+        _ => Authorization::Allow,
+    }))
+    .map_err(|e| CsvIoError::new_err(format!("SQLite error in query: {e}")))
+}
+
+fn confined_err() -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyPermissionError, _>(
+        "query() cannot attach databases or write files while an allowed root is set",
+    )
+}
+
+/// Convert an SQLite value into the equivalent Python object.
+fn sql_value_to_py(py: Python<'_>, value: &Value) -> PyResult<Py<PyAny>> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Integer(i) => i.into_pyobject(py)?.into_any().unbind(),
+        Value::Real(f) => f.into_pyobject(py)?.into_any().unbind(),
+        Value::Text(s) => s.into_pyobject(py)?.into_any().unbind(),
+        Value::Blob(b) => PyBytes::new(py, b).into_any().unbind(),
+    })
+}
+
+/// How `query()` and `query_batches()` hand rows back.
+#[derive(Clone, Copy)]
+enum Output {
+    Lists,
+    Dicts,
+    Arrow,
+}
+
+impl Output {
+    fn new(as_dict: bool, as_arrow: bool) -> PyResult<Self> {
+        match (as_dict, as_arrow) {
+            (true, true) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "as_dict and as_arrow cannot both be set",
+            )),
+            (true, false) => Ok(Output::Dicts),
+            (false, true) => Ok(Output::Arrow),
+            (false, false) => Ok(Output::Lists),
+        }
+    }
+}
+
+/// Import pyarrow, which only Arrow output needs.
+fn pyarrow(py: Python<'_>) -> PyResult<Bound<'_, PyModule>> {
+    py.import("pyarrow").map_err(|_| {
+        PyErr::new::<pyo3::exceptions::PyImportError, _>(
+            "as_arrow=True requires pyarrow (pip install pyarrow)",
+        )
+    })
+}
+
+/// Name of the pyarrow type for a column holding `values`: `int64` or `float64` for
+/// numbers, `string` once any is text (numbers are then written out as text),
+/// `binary` once any is a blob, and `null` when all are NULL.
+fn arrow_type<'a>(values: impl Iterator<Item = &'a Value>) -> &'static str {
+    let (mut integer, mut real, mut text) = (false, false, false);
+    for value in values {
+        match value {
+            Value::Null => {}
+            Value::Integer(_) => integer = true,
+            Value::Real(_) => real = true,
+            Value::Text(_) => text = true,
+            Value::Blob(_) => return "binary",
+        }
+    }
+    match (integer, real, text) {
+        (_, _, true) => "string",
+        (_, true, _) => "float64",
+        (true, _, _) => "int64",
+        _ => "null",
+    }
+}
+
+/// Convert an SQLite value into a Python object for an Arrow column of type `ty`.
+fn arrow_value(py: Python<'_>, value: &Value, ty: &str) -> PyResult<Py<PyAny>> {
+    let text = match value {
+        Value::Integer(i) if ty != "int64" && ty != "float64" => i.to_string(),
+        Value::Real(f) if ty != "float64" => f.to_string(),
+        Value::Integer(i) if ty == "float64" => {
+            return Ok((*i as f64).into_pyobject(py)?.into_any().unbind())
+        }
+        Value::Text(s) if ty == "binary" => s.clone(),
+        value => return sql_value_to_py(py, value),
+    };
+    Ok(if ty == "binary" {
+        PyBytes::new(py, text.as_bytes()).into_any().unbind()
+    } else {
+        text.into_pyobject(py)?.into_any().unbind()
+    })
+}
+
+/// Rows of a query's result, with the names of its columns.
+struct Batch {
+    columns: Arc<[String]>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Batch {
+    /// Append the rows to `result` as lists, or as dicts keyed by column name.
+    fn append_to(&self, py: Python<'_>, result: &Bound<'_, PyList>, as_dict: bool) -> PyResult<()> {
+        for row in &self.rows {
+            if as_dict {
+                let dict = PyDict::new(py);
+                for (column, value) in self.columns.iter().zip(row) {
+                    dict.set_item(column, sql_value_to_py(py, value)?)?;
+                }
+                result.append(dict)?;
+            } else {
+                let values = row
+                    .iter()
+                    .map(|value| sql_value_to_py(py, value))
+                    .collect::<PyResult<Vec<_>>>()?;
+                result.append(PyList::new(py, values)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The rows as a `pyarrow.RecordBatch`, typed by the values in each column.
+    fn to_arrow<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let pa = pyarrow(py)?;
+        let arrays = (0..self.columns.len())
+            .map(|i| {
+                let ty = arrow_type(self.rows.iter().map(|row| &row[i]));
+                let values = self
+                    .rows
+                    .iter()
+                    .map(|row| arrow_value(py, &row[i], ty))
+                    .collect::<PyResult<Vec<_>>>()?;
+                pa.call_method1("array", (values, pa.getattr(ty)?.call0()?))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        pa.getattr("RecordBatch")?
+            .call_method1("from_arrays", (arrays, self.columns.to_vec()))
+    }
+
+    /// The rows as `output` asks for: a list of rows, or a `pyarrow.RecordBatch`.
+    fn to_py(&self, py: Python<'_>, output: Output) -> PyResult<Py<PyAny>> {
+        match output {
+            Output::Arrow => Ok(self.to_arrow(py)?.unbind()),
+            Output::Lists | Output::Dicts => {
+                let result = PyList::empty(py);
+                self.append_to(py, &result, matches!(output, Output::Dicts))?;
+                Ok(result.into_any().unbind())
+            }
+        }
+    }
+}
+
+/// A query over CSV files, checked and ready to run on a blocking worker thread.
+struct Query {
+    sql: String,                  // With file references replaced by table names
+    files: Vec<(String, String)>, // (table, path)
+    params: Vec<Value>,
+    dialect: DialectConfig,
+    has_delimiter: bool,
+    confined: bool,
+}
+
+impl Query {
+    fn new(
+        py: Python<'_>,
+        sql: &str,
+        params: Option<Vec<Py<PyAny>>>,
+        delimiter: Option<&str>,
+    ) -> PyResult<Self> {
+        let (sql, files) = rewrite_file_references(sql);
+        if files.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Query does not reference any CSV file (use FROM 'path.csv')",
+            ));
+        }
+        for (_, path) in &files {
+            validate_path(path)?;
+        }
+        let params = params
+            .unwrap_or_default()
+            .iter()
+            .map(|p| py_to_sql_value(p.bind(py)))
+            .collect::<PyResult<_>>()?;
+        Ok(Query {
+            sql,
+            files,
+            params,
+            dialect: DialectConfig::from_python(
+                None, delimiter, None, None, None, None, None, None, None,
+            )?,
+            has_delimiter: delimiter.is_some(),
+            confined: sandbox::confined(),
+        })
+    }
+
+    /// Run the query over tables that read the files as it goes, handing its rows to
+    /// `emit` `batch_size` at a time. Stops early when `emit` returns false. Returns
+    /// the names of the result's columns.
+    fn run(
+        self,
+        batch_size: usize,
+        mut emit: impl FnMut(Batch) -> bool,
+    ) -> PyResult<Arc<[String]>> {
+        let conn = Connection::open_in_memory()
+            .map_err(|e| CsvIoError::new_err(format!("SQLite error in query: {e}")))?;
+        if self.confined {
+            confine(&conn)?;
+        }
+        let files = self
+            .files
+            .iter()
+            .map(|(table, path)| {
+                let mut dialect = self.dialect.clone();
+                if !self.has_delimiter && path.to_ascii_lowercase().ends_with(".tsv") {
+                    dialect.delimiter = b'\t';
+                }
+                (table.clone(), path.clone(), dialect)
+            })
+            .collect();
+        let sources = csvtab::create(&conn, files)?;
+        let query_err = |e: rusqlite::Error| match e {
+            // only the authorizer from `confine` denies statements
+            rusqlite::Error::SqliteFailure(
+                ffi::Error {
+                    code: ErrorCode::AuthorizationForStatementDenied,
+                    ..
+                },
+                _,
+            ) if self.confined => confined_err(),
+            e => sources.error(e),
+        };
+
+        let mut stmt = conn.prepare(&self.sql).map_err(query_err)?;
+        if self.confined && !stmt.readonly() {
+            return Err(confined_err());
+        }
+        let columns: Arc<[String]> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+        let mut rows = stmt
+            .query(params_from_iter(self.params.iter()))
+            .map_err(query_err)?;
+        let batch_size = batch_size.max(1);
+        let mut batch = Vec::new();
+        while let Some(row) = rows.next().map_err(query_err)? {
+            batch.push(
+                (0..columns.len())
+                    .map(|i| row.get::<_, Value>(i))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(query_err)?,
+            );
+            if batch.len() == batch_size {
+                let rows = std::mem::take(&mut batch);
+                if !emit(Batch {
+                    columns: Arc::clone(&columns),
+                    rows,
+                }) {
+                    return Ok(columns);
+                }
+            }
+        }
+        if !batch.is_empty() {
+            emit(Batch {
+                columns: Arc::clone(&columns),
+                rows: batch,
+            });
+        }
+        Ok(columns)
+    }
+}
+
+/// Run an SQL query over one or more CSV files.
+///
+/// CSV files are referenced by quoting their path after `FROM` or `JOIN`, e.g.
+/// `SELECT a, sum(b) FROM 'big.csv' GROUP BY a`. Each file becomes a virtual table
+/// that SQLite reads as the query runs, without copying the file anywhere (columns
+/// have `NUMERIC` affinity, so numbers compare and aggregate as numbers), and the
+/// query runs entirely in Rust.
+///
+/// # Arguments
+/// * `sql` - SQL query (SQLite dialect)
+/// * `params` - Optional positional parameters bound to `?` placeholders
+/// * `as_dict` - Return rows as dicts keyed by column name (default: False)
+/// * `delimiter` - Field delimiter (default: ',', or tab for `.tsv` files)
+/// * `as_arrow` - Return a `pyarrow.Table` instead of a list (default: False)
+///
+/// Returns a list of rows; `query_batches` returns them as they are produced.
+#[pyfunction]
+#[pyo3(signature = (sql, params = None, as_dict = false, delimiter = None, as_arrow = false))]
+pub(crate) fn query(
+    py: Python<'_>,
+    sql: String,
+    params: Option<Vec<Py<PyAny>>>,
+    as_dict: bool,
+    delimiter: Option<&str>,
+    as_arrow: bool,
+) -> PyResult<Py<PyAny>> {
+    let output = Output::new(as_dict, as_arrow)?;
+    if let Output::Arrow = output {
+        pyarrow(py)?;
+    }
+    let query = Query::new(py, &sql, params, delimiter)?;
+    let future = async move {
+        let (columns, batches) = tokio::task::spawn_blocking(move || {
+            let mut batches = Vec::new();
+            let columns = query.run(DEFAULT_BATCH_SIZE, |batch| {
+                batches.push(batch);
+                true
+            })?;
+            Ok::<_, PyErr>((columns, batches))
+        })
+        .await
+        .map_err(|e| CsvIoError::new_err(format!("Query task failed: {e}")))??;
+
+        Python::attach(|py| -> PyResult<Py<PyAny>> {
+            if let Output::Arrow = output {
+                let all = Batch {
+                    columns,
+                    rows: batches.into_iter().flat_map(|batch| batch.rows).collect(),
+                };
+                let batch = all.to_arrow(py)?;
+                let table = pyarrow(py)?
+                    .getattr("Table")?
+                    .call_method1("from_batches", (vec![batch],))?;
+                return Ok(table.unbind());
+            }
+            let result = PyList::empty(py);
+            for batch in &batches {
+                batch.append_to(py, &result, matches!(output, Output::Dicts))?;
+            }
+            Ok(result.into_any().unbind())
+        })
+    };
+    future_into_py(py, future).map(|bound| bound.unbind())
+}
+
+/// Async iterator over the result of `query_batches()`, a list of rows or a
+/// `pyarrow.RecordBatch` at a time.
+///
+/// The query runs on a blocking worker thread that stays at most one batch ahead of
+/// the consumer, so a large result never has to fit in memory. Dropping the iterator
+/// stops the query.
+#[pyclass]
+pub(crate) struct QueryBatches {
+    receiver: Arc<Mutex<mpsc::Receiver<PyResult<Batch>>>>,
+    output: Output,
+}
+
+#[pymethods]
+impl QueryBatches {
+    fn __aiter__(slf: PyRef<Self>) -> PyResult<Py<Self>> {
+        Ok(slf.into())
+    }
+
+    /// Async iterator next - returns the next batch or raises StopAsyncIteration.
+    fn __anext__(self_: PyRef<Self>) -> PyResult<Py<PyAny>> {
+        let receiver = Arc::clone(&self_.receiver);
+        let output = self_.output;
+        Python::attach(|py| {
+            let future = async move {
+                let batch = receiver.lock().await.recv().await.transpose()?;
+                Python::attach(|py| match batch {
+                    Some(batch) => batch.to_py(py, output),
+                    None => Err(PyStopAsyncIteration::new_err(())),
+                })
+            };
+            future_into_py(py, future).map(|bound| bound.unbind())
+        })
+    }
+}
+
+/// Run an SQL query over one or more CSV files, returning its rows as they are produced.
+///
+/// Takes the same arguments as `query`, plus:
+/// * `batch_size` - Rows per batch (default: 10000)
+///
+/// Returns an async iterator of lists of rows, or of `pyarrow.RecordBatch` objects
+/// with `as_arrow`.
+#[pyfunction]
+#[pyo3(signature = (sql, params = None, as_dict = false, delimiter = None, as_arrow = false, batch_size = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn query_batches(
+    py: Python<'_>,
+    sql: String,
+    params: Option<Vec<Py<PyAny>>>,
+    as_dict: bool,
+    delimiter: Option<&str>,
+    as_arrow: bool,
+    batch_size: Option<usize>,
+) -> PyResult<QueryBatches> {
+    let output = Output::new(as_dict, as_arrow)?;
+    if let Output::Arrow = output {
+        pyarrow(py)?;
+    }
+    let query = Query::new(py, &sql, params, delimiter)?;
+    let batch_size = batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+    let (sender, receiver) = mpsc::channel(1);
+    pyo3_async_runtimes::tokio::get_runtime().spawn_blocking(move || {
+        let result = query.run(batch_size, |batch| sender.blocking_send(Ok(batch)).is_ok());
+        if let Err(e) = result {
+            let _ = sender.blocking_send(Err(e));
+        }
+    });
+    Ok(QueryBatches {
+        receiver: Arc::new(Mutex::new(receiver)),
+        output,
+    })
+}
+
+/// Convert a Python query parameter into an SQLite value.
+fn py_to_sql_value(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if obj.is_none() {
+        Ok(Value::Null)
+    } else if let Ok(b) = obj.extract::<bool>() {
+        Ok(Value::Integer(b as i64))
+    } else if let Ok(i) = obj.extract::<i64>() {
+        Ok(Value::Integer(i))
+    } else if let Ok(f) = obj.extract::<f64>() {
+        Ok(Value::Real(f))
+    } else if let Ok(s) = obj.extract::<String>() {
+        Ok(Value::Text(s))
+    } else if let Ok(b) = obj.extract::<Vec<u8>>() {
+        Ok(Value::Blob(b))
+    } else {
+        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "Unsupported query parameter type: {}",
+            obj.get_type().name()?
+        )))
+    }
+}
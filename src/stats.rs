@@ -0,0 +1,69 @@
+//! Throughput counters behind `Reader.stats()` and `Writer.stats()`.
+
+use crate::errors::CsvParseError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Rows, bytes, errors and time spent in operations since a reader or writer was created.
+pub(crate) struct Stats {
+    started: Instant,
+    rows: AtomicU64,
+    bytes: AtomicU64,
+    errors: AtomicU64,       // Failed operations
+    parse_errors: AtomicU64, // Failed operations that raised CsvParseError or CSVFieldCountError
+    active_nanos: AtomicU64, // Time spent inside operations
+}
+
+impl Stats {
+    pub(crate) fn new() -> Self {
+        Stats {
+            started: Instant::now(),
+            rows: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            parse_errors: AtomicU64::new(0),
+            active_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Count `rows` rows and `bytes` bytes processed.
+    pub(crate) fn record(&self, rows: usize, bytes: u64) {
+        self.rows.fetch_add(rows as u64, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Run `operation`, adding its duration to the active time and counting a failure.
+    pub(crate) async fn track<T>(
+        &self,
+        operation: impl Future<Output = PyResult<T>>,
+    ) -> PyResult<T> {
+        let start = Instant::now();
+        let result = operation.await;
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.active_nanos.fetch_add(nanos, Ordering::Relaxed);
+        if let Err(e) = &result {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+            let parse_error = Python::attach(|py| e.is_instance_of::<CsvParseError>(py));
+            if parse_error {
+                self.parse_errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        result
+    }
+
+    /// Snapshot as a dict; times are in seconds.
+    pub(crate) fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("rows", self.rows.load(Ordering::Relaxed))?;
+        dict.set_item("bytes", self.bytes.load(Ordering::Relaxed))?;
+        dict.set_item("errors", self.errors.load(Ordering::Relaxed))?;
+        dict.set_item("parse_errors", self.parse_errors.load(Ordering::Relaxed))?;
+        dict.set_item("elapsed", self.started.elapsed().as_secs_f64())?;
+        let active = self.active_nanos.load(Ordering::Relaxed) as f64 / 1e9;
+        dict.set_item("active", active)?;
+        Ok(dict)
+    }
+}
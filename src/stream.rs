@@ -0,0 +1,2243 @@
+//! Incremental record parsing shared by the streaming readers.
+//!
+//! Chunks are appended to a text buffer and parsed one record at a time. A record is
+//! only accepted once it is known to be complete: either more data follows it in the
+//! buffer, or the source is exhausted. This keeps rows that straddle a chunk boundary
+//! intact and lets follow mode wait for a writer to finish a partially written line.
+//!
+//! A stream may span several files read one after another (a list of paths or a glob
+//! pattern); the header row of every file after the first is skipped, after an
+//! optional check that it matches the first (see crate::drift). With a
+//! `concurrency` above one, those files are parsed on the blocking thread pool several
+//! at a time and their rows are delivered in file order.
+
+use crate::binary::BinaryPolicy;
+use crate::checksum::Verifier;
+use crate::decode::{Utf8Decoder, Utf8Mode};
+use crate::detect::{self, Detected, Detector};
+use crate::drift::HeaderCheck;
+use crate::errors::{snippet, CsvIoError, CsvParseError, ErrorContext, FileChangedError};
+use crate::fast::{self, Engine};
+use crate::gunzip::Gunzip;
+use crate::headers::HeaderRules;
+use crate::index;
+use crate::lock::{self, LockMode};
+use crate::memory;
+use crate::nulls;
+use crate::output::{Ticket, Turnstile};
+use crate::paths::{self, FilePath};
+use crate::pipe::PipeSource;
+use crate::progress::Progress;
+use crate::project::{self, Projection};
+use crate::retry::{self, RetryPolicy};
+use crate::rfc4180::Rfc4180;
+use crate::rowhash::{self, HASH_COLUMN};
+use crate::sandbox;
+use crate::sftp;
+use crate::socket::Conn;
+use crate::spans;
+use crate::stats::Stats;
+use crate::timeout;
+use crate::uring;
+use crate::whitespace;
+use crate::{
+    fspath, read_chunk, validate_path, Access, DialectConfig, FileSource, DEFAULT_BUFFER_SIZE,
+};
+use csv::{ByteRecord, ReaderBuilder, StringRecord, Terminator};
+use memchr::memchr_iter;
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyTuple};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io::SeekFrom;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use std::time::Duration;
+use tokio::fs::File;
+use tokio::io::{AsyncSeekExt, BufReader};
+use tokio::sync::{mpsc, Mutex};
+use tracing::Instrument;
+
+/// Expand a `path_or_handle` argument naming several files: a list of paths or a glob
+/// pattern such as `"data/part-*.csv"`. Returns `None` for a single path or a file handle.
+pub(crate) fn expand_paths(path_or_handle: &Bound<'_, PyAny>) -> PyResult<Option<Vec<FilePath>>> {
+    if let Some(pattern) = fspath(path_or_handle)? {
+        // An existing file whose name happens to contain glob characters is read as-is
+        if !pattern.contains(['*', '?', '['])
+            || sftp::is_url(&pattern)
+            || paths::native(&pattern).exists()
+        {
+            return Ok(None);
+        }
+        validate_path(&pattern)?;
+        let paths: Vec<FilePath> = glob::glob(&pattern)
+            .map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Invalid glob pattern '{pattern}': {e}"
+                ))
+            })?
+            .filter_map(Result::ok)
+            .filter(|path| path.is_file())
+            .map(FilePath::from)
+            .collect();
+        for path in &paths {
+            sandbox::check(path, None)?;
+        }
+        if paths.is_empty() {
+            return Err(CsvIoError::new_err(format!(
+                "No files match pattern '{pattern}'"
+            )));
+        }
+        return Ok(Some(paths));
+    }
+    if path_or_handle.is_instance_of::<PyList>() || path_or_handle.is_instance_of::<PyTuple>() {
+        let paths = path_or_handle
+            .try_iter()?
+            .map(|item| {
+                fspath(&item?)?.ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                        "paths must be str, bytes or os.PathLike",
+                    )
+                })
+            })
+            .collect::<PyResult<Vec<FilePath>>>()?;
+        if paths.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "At least one path is required",
+            ));
+        }
+        for path in &paths {
+            validate_path(path)?;
+        }
+        return Ok(Some(paths));
+    }
+    Ok(None)
+}
+
+/// Which file of a multi-file stream is being read.
+struct FileCursor {
+    index: usize,      // Index into `files` of the current file
+    skip_header: bool, // Discard the next record (the header of a later file)
+}
+
+/// Where a path-backed stream is, as saved by `Reader.state()`.
+pub(crate) struct StreamState {
+    pub(crate) files: Vec<FilePath>,
+    pub(crate) file_index: usize,
+    pub(crate) offset: u64, // Byte offset in `files[file_index]` of the next record
+    pub(crate) position: usize, // Rows returned so far
+    pub(crate) line_num: usize,
+    pub(crate) has_header: bool,
+    pub(crate) header: Option<Vec<String>>, // First file's header, checked when resuming
+    pub(crate) end: Option<u64>,            // Offset where a stream from `split()` stops
+}
+
+/// Rows parsed from one file, each with the number of lines it spans.
+type ParsedFile = Vec<(Vec<String>, usize)>;
+
+/// Build a headerless `csv` reader for `dialect`.
+fn reader_builder(dialect: &DialectConfig, field_size_limit: Option<usize>) -> ReaderBuilder {
+    let mut builder = ReaderBuilder::new();
+    builder.has_headers(false);
+    dialect.apply_to_reader(&mut builder, field_size_limit);
+    builder
+}
+
+/// End of a record the parser stopped reading at `end`, including the `\n` of a
+/// `\r\n` terminator. The parser ends the record at the `\r` and would only skip the
+/// `\n` when starting the next one, crediting that line and byte to the wrong row.
+fn record_end(bytes: &[u8], end: usize, dialect: &DialectConfig) -> usize {
+    let crlf = matches!(dialect.lineterminator, Terminator::CRLF);
+    if crlf && end > 0 && bytes[end - 1] == b'\r' && bytes.get(end) == Some(&b'\n') {
+        end + 1
+    } else {
+        end
+    }
+}
+
+/// Find the first record of `bytes` by scanning for a terminator outside quotes,
+/// without parsing its fields. Returns where it starts, after any blank lines, and
+/// where it ends, past its terminator; at `eof` a last record may have none.
+fn raw_record(bytes: &[u8], dialect: &DialectConfig, eof: bool) -> Option<(usize, usize)> {
+    let terminator = index::terminator(dialect);
+    let quote = dialect.read_quote();
+    let mut in_quotes = false;
+    let mut content = false; // The current line holds more than line breaks
+    let mut start = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        if Some(b) == quote {
+            in_quotes = !in_quotes;
+        } else if b == terminator && !in_quotes {
+            if content {
+                return Some((start, i + 1));
+            }
+            start = i + 1;
+            continue;
+        }
+        if b != b'\r' && b != b'\n' {
+            content = true;
+        }
+    }
+    (eof && content).then_some((start, bytes.len()))
+}
+
+/// Parse every record of `text`, optionally dropping the first one (a header) and
+/// keeping only the fields at `selected`. Lines are counted from the bytes each record
+/// consumed, so a range split from the middle of a file counts the same lines as
+/// parsing it whole.
+fn parse_records(
+    text: &str,
+    path: &str,
+    dialect: &DialectConfig,
+    field_size_limit: Option<usize>,
+    skip_header: bool,
+    selected: Option<&[usize]>,
+) -> PyResult<ParsedFile> {
+    let bytes = text.as_bytes();
+    let mut reader = reader_builder(dialect, field_size_limit).from_reader(bytes);
+    let mut rows = Vec::new();
+    let mut record = ByteRecord::new();
+    let mut start = 0;
+    let mut pending_lines = 0; // Lines of a skipped header, credited to the next row
+    let mut skip = skip_header;
+    loop {
+        let next = if dialect.delim_whitespace {
+            whitespace::parse_first(&bytes[start..], dialect).map(|result| {
+                result
+                    .map(|(row, consumed)| match selected {
+                        Some(indices) => (project::select(indices, row), start + consumed),
+                        None => (row, start + consumed),
+                    })
+                    .map_err(|e| (e, Box::default()))
+            })
+        } else {
+            match reader.read_byte_record(&mut record) {
+                Ok(true) => {
+                    let end = record_end(bytes, reader.position().byte() as usize, dialect);
+                    // Fields were split from valid UTF-8 at ASCII delimiters and quotes
+                    let field = |i| {
+                        record
+                            .get(i)
+                            .map(|f| String::from_utf8_lossy(f).into_owned())
+                    };
+                    let row = match selected {
+                        Some(indices) => indices.iter().filter_map(|&i| field(i)).collect(),
+                        None => (0..record.len()).filter_map(field).collect(),
+                    };
+                    Some(Ok((row, end)))
+                }
+                Ok(false) => None,
+                Err(e) => Some(Err((e.to_string(), Box::new(ErrorContext::from_csv(&e))))),
+            }
+        };
+        let (row, end) = match next {
+            Some(Ok(next)) => next,
+            Some(Err((e, mut context))) => {
+                let at = context.byte_offset.map_or(start, |offset| offset as usize);
+                context.row = context
+                    .row
+                    .or(Some(rows.len() + usize::from(skip_header && !skip)));
+                context.line = context
+                    .line
+                    .or(Some(memchr_iter(b'\n', &bytes[..at]).count() as u64 + 1));
+                context.byte_offset = Some(at as u64);
+                context.snippet = Some(snippet(bytes.get(at..).unwrap_or_default()));
+                return Err(context.attach(CsvParseError::new_err(format!(
+                    "CSV parse error at row {} (0-indexed) in file '{path}'{}: {e}. \
+                    The CSV file may be malformed or have incomplete records.",
+                    rows.len(),
+                    context.describe()
+                ))));
+            }
+            None => break,
+        };
+        pending_lines += memchr_iter(b'\n', &bytes[start..end]).count().max(1);
+        start = end;
+        if skip {
+            skip = false;
+            continue;
+        }
+        rows.push((row, pending_lines));
+        pending_lines = 0;
+    }
+    Ok(rows)
+}
+
+/// Parse a whole file synchronously. Used by the parallel multi-file mode.
+fn parse_file(
+    path: &FilePath,
+    dialect: &DialectConfig,
+    field_size_limit: Option<usize>,
+    skip_header: bool,
+    decoder: &Utf8Decoder,
+    selected: Option<&[usize]>,
+) -> PyResult<ParsedFile> {
+    let data = std::fs::read(paths::native(path))
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+    let text = decoder.decode_all(data)?;
+    parse_records(
+        &text,
+        path,
+        dialect,
+        field_size_limit,
+        skip_header,
+        selected,
+    )
+}
+
+/// Find offsets that split `data` into about `parts` ranges at record boundaries.
+///
+/// A terminator only ends a record outside quotes. Doubled quotes toggle the state
+/// twice, so tracking parity is enough as long as no escape character is in use.
+fn split_points(data: &[u8], dialect: &DialectConfig, parts: usize) -> Vec<usize> {
+    let terminator = match dialect.lineterminator {
+        Terminator::Any(b) => b,
+        _ => b'\n',
+    };
+    if parts <= 1 {
+        return vec![0, data.len()];
+    }
+    let step = data.len() / parts;
+    let mut points = vec![0];
+    let mut target = step;
+    let quote = dialect.read_quote();
+    let mut in_quotes = false;
+    for (i, &b) in data.iter().enumerate() {
+        if Some(b) == quote {
+            in_quotes = !in_quotes;
+        } else if b == terminator && !in_quotes && i + 1 >= target && i + 1 < data.len() {
+            points.push(i + 1);
+            if points.len() == parts {
+                break;
+            }
+            target = i + 1 + step;
+        }
+    }
+    points.push(data.len());
+    points
+}
+
+/// Find the starts of `parts` ranges of the file at `path` of about equal size at
+/// record boundaries, with the records and lines before each, as `split_points` does
+/// for text in memory. The first range starts at 0.
+fn record_boundaries(
+    path: &FilePath,
+    dialect: &DialectConfig,
+    parts: usize,
+) -> PyResult<Vec<(u64, usize, usize)>> {
+    let size = std::fs::metadata(paths::native(path))
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?
+        .len();
+    let step = (size / parts as u64).max(1);
+    let mut starts = vec![(0, 0, 0)];
+    let mut target = step;
+    index::scan_records(path, dialect, |offset, records, lines| {
+        if offset >= target && offset < size {
+            starts.push((offset, records, lines));
+            target = offset + step;
+        }
+        starts.len() < parts
+    })?;
+    Ok(starts)
+}
+
+/// Parse one range of records with `engine`.
+fn parse_range(
+    chunk: &str,
+    path: &str,
+    dialect: &DialectConfig,
+    field_size_limit: Option<usize>,
+    engine: Engine,
+    selected: Option<&[usize]>,
+) -> PyResult<ParsedFile> {
+    match engine {
+        Engine::Fast => {
+            let parsed = fast::parse(chunk, dialect)
+                .ok_or_else(|| CsvParseError::new_err("fast engine could not parse this range"))?;
+            Ok(match selected {
+                Some(indices) => parsed
+                    .into_iter()
+                    .map(|(row, lines)| (project::select(indices, row), lines))
+                    .collect(),
+                None => parsed,
+            })
+        }
+        Engine::Default => parse_records(chunk, path, dialect, field_size_limit, false, selected),
+    }
+}
+
+/// Parse `text` on up to `threads` threads, one contiguous range of records each.
+fn parse_ranges(
+    text: &str,
+    path: &str,
+    dialect: &DialectConfig,
+    field_size_limit: Option<usize>,
+    threads: usize,
+    engine: Engine,
+    selected: Option<&[usize]>,
+) -> PyResult<ParsedFile> {
+    let points = split_points(text.as_bytes(), dialect, threads);
+    let results: Vec<PyResult<ParsedFile>> = if points.len() <= 2 {
+        vec![parse_range(
+            text,
+            path,
+            dialect,
+            field_size_limit,
+            engine,
+            selected,
+        )]
+    } else {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = points
+                .windows(2)
+                .map(|range| {
+                    let chunk = &text[range[0]..range[1]];
+                    scope.spawn(move || {
+                        parse_range(chunk, path, dialect, field_size_limit, engine, selected)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| {
+                        Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                            "CSV parser thread panicked",
+                        ))
+                    })
+                })
+                .collect()
+        })
+    };
+    if results.iter().any(Result::is_err) {
+        // Re-parse sequentially with the csv crate so the error reports the row index
+        // within the whole file (or, for the fast engine, so lenient input still parses)
+        return parse_records(text, path, dialect, field_size_limit, false, selected);
+    }
+    Ok(results
+        .into_iter()
+        .flat_map(Result::unwrap_or_default)
+        .collect())
+}
+
+/// Resolve `columns=` names from the header at the start of `text`, before it is
+/// parsed in bulk. Returns the positions to decode, once known.
+fn resolve_from_text(
+    projection: Option<&Projection>,
+    has_header: bool,
+    header_rules: &HeaderRules,
+    text: &str,
+    dialect: &DialectConfig,
+) -> PyResult<Option<Vec<usize>>> {
+    let Some(projection) = projection else {
+        return Ok(None);
+    };
+    if projection.indices().is_none() && has_header {
+        let first = if dialect.delim_whitespace {
+            whitespace::parse_first(text.as_bytes(), dialect)
+        } else {
+            RecordScratch::new(dialect).parse_first(text.as_bytes(), None)
+        };
+        // An empty or malformed file is left for the parser to report
+        if let Some(Ok((mut header, _))) = first {
+            header_rules.apply(&mut header)?;
+            projection.resolve(&header)?;
+        }
+    }
+    Ok(projection.indices().map(<[usize]>::to_vec))
+}
+
+/// Read the header row of the file at `path`.
+fn read_header(path: &FilePath, dialect: &DialectConfig) -> PyResult<Vec<String>> {
+    let file = std::fs::File::open(paths::native(path))
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+    if dialect.delim_whitespace {
+        return read_whitespace_header(file, path, dialect);
+    }
+    let mut record = StringRecord::new();
+    reader_builder(dialect, None)
+        .from_reader(file)
+        .read_record(&mut record)
+        .map_err(|e| {
+            CsvParseError::new_err(format!("CSV parse error in header of file '{path}': {e}"))
+        })?;
+    Ok(record.iter().map(String::from).collect())
+}
+
+/// `read_header` for `delim_whitespace=True`, which the `csv` crate can't parse.
+fn read_whitespace_header(
+    mut file: std::fs::File,
+    path: &str,
+    dialect: &DialectConfig,
+) -> PyResult<Vec<String>> {
+    let mut data = Vec::new();
+    let mut chunk = vec![0u8; 64 * 1024];
+    loop {
+        let n = std::io::Read::read(&mut file, &mut chunk)
+            .map_err(|e| CsvIoError::new_err(format!("Failed to read file {path}: {e}")))?;
+        data.extend_from_slice(&chunk[..n]);
+        match whitespace::parse_first(&data, dialect) {
+            Some(Ok((row, consumed))) if consumed < data.len() || n == 0 => return Ok(row),
+            Some(Err(e)) if n == 0 => {
+                return Err(CsvParseError::new_err(format!(
+                    "CSV parse error in header of file '{path}': {e}"
+                )))
+            }
+            None if n == 0 => return Ok(Vec::new()),
+            _ => {}
+        }
+    }
+}
+
+/// A memory map of a file, with the file's size and modification time when it was
+/// mapped.
+struct MappedFile {
+    map: memmap2::Mmap,
+    file: std::fs::File,
+    stamp: (u64, Option<std::time::SystemTime>),
+}
+
+impl MappedFile {
+    /// Fail if the file was written to since it was mapped, so rows parsed from a file
+    /// that changed underneath the map are never returned.
+    fn check_unchanged(&self, path: &FilePath) -> PyResult<()> {
+        let metadata = self
+            .file
+            .metadata()
+            .map_err(|e| CsvIoError::new_err(format!("Failed to read file {path}: {e}")))?;
+        if (metadata.len(), metadata.modified().ok()) != self.stamp {
+            return Err(CsvIoError::new_err(format!(
+                "File {path} changed while it was read with mmap=True"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Memory-map `path` for reading.
+fn map_file(path: &FilePath) -> PyResult<MappedFile> {
+    let file = std::fs::File::open(paths::native(path))
+        .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+    let metadata = file
+        .metadata()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to read file {path}: {e}")))?;
+    // SAFETY: the map is only read while parsing, and the file must not change meanwhile,
+    // which callers opt in to with mmap=True. A file that grows or is rewritten in place
+    // is caught by check_unchanged() afterwards, but one truncated by another process
+    // while it is mapped makes reads past its new end raise SIGBUS, which kills the
+    // process; nothing here can recover from that.
+    let map = unsafe { memmap2::Mmap::map(&file) }
+        .map_err(|e| CsvIoError::new_err(format!("Failed to map file {path}: {e}")))?;
+    Ok(MappedFile {
+        map,
+        file,
+        stamp: (metadata.len(), metadata.modified().ok()),
+    })
+}
+
+/// Record parser and output buffers reused for every record of a stream, so reading a
+/// row only allocates the strings handed back to Python.
+struct RecordScratch {
+    parser: csv_core::Reader,
+    output: Vec<u8>,
+    ends: Vec<usize>,
+}
+
+impl RecordScratch {
+    fn new(dialect: &DialectConfig) -> Self {
+        let terminator = match dialect.lineterminator {
+            Terminator::Any(b) => csv_core::Terminator::Any(b),
+            _ => csv_core::Terminator::CRLF,
+        };
+        let parser = csv_core::ReaderBuilder::new()
+            .delimiter(dialect.delimiter)
+            .quote(dialect.quotechar)
+            .quoting(dialect.read_quote().is_some())
+            .escape(dialect.escapechar)
+            .terminator(terminator)
+            .build();
+        RecordScratch {
+            parser,
+            output: vec![0; 1024],
+            ends: vec![0; 64],
+        }
+    }
+
+    /// Parse the first record of `input`, returning its fields (only those at
+    /// `selected`, if given) and the bytes consumed. The end of `input` counts as the
+    /// end of the data.
+    fn parse_first(
+        &mut self,
+        input: &[u8],
+        selected: Option<&[usize]>,
+    ) -> Option<Result<(Vec<String>, usize), String>> {
+        self.parser.reset();
+        let (mut consumed, mut out_len, mut ends_len) = (0, 0, 0);
+        loop {
+            let (result, nin, nout, nend) = self.parser.read_record(
+                &input[consumed..],
+                &mut self.output[out_len..],
+                &mut self.ends[ends_len..],
+            );
+            consumed += nin;
+            out_len += nout;
+            ends_len += nend;
+            match result {
+                // Input exhausted: the next call, with empty input, signals end of data
+                csv_core::ReadRecordResult::InputEmpty => {}
+                csv_core::ReadRecordResult::OutputFull => {
+                    let len = self.output.len();
+                    self.output.resize(len * 2, 0);
+                }
+                csv_core::ReadRecordResult::OutputEndsFull => {
+                    let len = self.ends.len();
+                    self.ends.resize(len * 2, 0);
+                }
+                csv_core::ReadRecordResult::Record => break,
+                csv_core::ReadRecordResult::End => return None,
+            }
+        }
+        if let Some(indices) = selected {
+            return Some(
+                project::select_fields(indices, &self.output, &self.ends[..ends_len])
+                    .map(|row| (row, consumed))
+                    .map_err(|e| format!("invalid UTF-8 in field: {e}")),
+            );
+        }
+        let mut row = Vec::with_capacity(ends_len);
+        let mut start = 0;
+        for &end in &self.ends[..ends_len] {
+            match std::str::from_utf8(&self.output[start..end]) {
+                Ok(field) => row.push(field.to_string()),
+                Err(e) => return Some(Err(format!("invalid UTF-8 in field: {e}"))),
+            }
+            start = end;
+        }
+        Some(Ok((row, consumed)))
+    }
+}
+
+/// Parallel multi-file state: parsed files arrive in order over `receiver`.
+struct ParallelFiles {
+    receiver: mpsc::Receiver<PyResult<(usize, ParsedFile)>>,
+    rows: std::vec::IntoIter<(Vec<String>, usize)>,
+    file_index: usize,
+    projected: bool, // Rows were parsed with the `columns` selection applied
+}
+
+/// The error `read_all()` raises when its rows outgrow `max_memory`.
+fn read_all_exceeded(limit: usize) -> PyErr {
+    memory::exceeded(
+        "read_all()",
+        limit,
+        "iterate over the reader or use read_rows() to process the rows in parts",
+    )
+}
+
+/// Rows gathered by `next_records()`. If they are dropped before being returned (the
+/// read was cancelled or failed part way) they go back to the front of the stream.
+struct PendingRows<'a> {
+    rows: Vec<Vec<String>>,
+    unread: &'a StdMutex<VecDeque<Vec<String>>>,
+}
+
+impl Drop for PendingRows<'_> {
+    fn drop(&mut self) {
+        let mut unread = self.unread.lock().unwrap_or_else(|e| e.into_inner());
+        for row in self.rows.drain(..).rev() {
+            unread.push_front(row);
+        }
+    }
+}
+
+/// Read-ahead state (`prefetch=` on `Reader`).
+///
+/// A background task parses rows into the stream's `unread` queue while the caller
+/// works through the rows already there, and holds `running` until it finishes. Reads
+/// that find the queue empty take `running` before parsing themselves, so rows are
+/// always returned in file order. An error stops the task and is raised once the rows
+/// parsed before it have been returned.
+struct Prefetch {
+    rows: usize, // Rows kept parsed ahead
+    running: Arc<Mutex<()>>,
+    error: StdMutex<Option<PyErr>>,
+}
+
+/// Trailing records held back (`skip_footer=` on `Reader`).
+///
+/// A record is only returned once `rows` more records of the same file have been
+/// parsed after it, so the last `rows` records of every file, its footer, never are.
+struct Footer {
+    rows: usize,
+    held: StdMutex<VecDeque<(usize, Vec<String>)>>, // (file index, row)
+}
+
+/// Most records a stream returns (`limit=` on `Reader`).
+struct RowLimit {
+    rows: usize,
+    parsed: AtomicUsize, // Records parsed towards the limit
+}
+
+/// Streaming state for one CSV source. Cloning shares the underlying state.
+#[derive(Clone)]
+pub(crate) struct RecordStream {
+    files: Arc<Vec<FilePath>>, // Display path, or every path of a multi-file stream
+    cursor: Arc<Mutex<FileCursor>>,
+    access: Access,
+    pub(crate) file: Arc<Mutex<Option<BufReader<Conn>>>>,
+    file_handle: Arc<StdMutex<Option<Py<PyAny>>>>,
+    event_loop: Arc<StdMutex<Option<Py<PyAny>>>>,
+    pub(crate) buffer: Arc<Mutex<String>>,
+    pub(crate) buffer_start: Arc<Mutex<usize>>, // Start position in buffer for next parse
+    pub(crate) position: Arc<Mutex<usize>>,     // Record index (0-based)
+    pub(crate) line_num: Arc<Mutex<usize>>, // Line number (1-based, accounting for multi-line records)
+    pub(crate) offset: Arc<Mutex<u64>>,     // Bytes of the current file consumed so far
+    dialect: DialectConfig,
+    read_size: usize,
+    buffer_size: usize, // Capacity of the file's read buffer
+    field_size_limit: Option<usize>,
+    follow: Option<Duration>, // Poll interval when waiting at EOF for appended data
+    has_header: bool,
+    header_rules: Arc<HeaderRules>, // Normalizing and duplicate names of the header row
+    include_filename: bool,         // Append the originating file path to every row
+    row_hash: bool,                 // Append a hash of the row's fields to every row
+    concurrency: usize,             // Files parsed at once in multi-file mode
+    engine: Engine,                 // Parser used by read_all()
+    mmap: bool,                     // Memory-map the file in read_all()
+    lock: Option<LockMode>,         // Advisory lock taken on each file while it is read
+    held_lock: Arc<Mutex<Option<(FilePath, std::fs::File)>>>,
+    progress: Option<Arc<Progress>>,
+    pub(crate) stats: Arc<Stats>,
+    timeout: Option<Duration>, // Limit on each read operation
+    retry: Option<Arc<RetryPolicy>>,
+    parallel: Arc<Mutex<Option<ParallelFiles>>>,
+    unread: Arc<StdMutex<VecDeque<Vec<String>>>>, // Rows handed back by a cancelled read
+    scratch: Arc<StdMutex<RecordScratch>>,
+    decoder: Arc<Utf8Decoder>,
+    gunzip: Arc<Gunzip>, // Decompresses a gzip byte stream from a Python handle
+    pipe: Option<Arc<PipeSource>>, // Archive member, encrypted or SFTP file fed into `file`
+    checksum: Option<Arc<Verifier>>, // Digest each file must match before parsing
+    max_memory: Option<usize>, // Budget for the rows read_all() and seek()'s index hold
+    prefetch: Option<Arc<Prefetch>>,
+    projection: Option<Arc<Projection>>, // Columns returned, from `columns=`
+    header_row: Arc<OnceLock<Vec<String>>>, // First file's header, as returned
+    footer: Option<Arc<Footer>>,
+    row_limit: Option<Arc<RowLimit>>,
+    rfc4180: Option<Arc<Rfc4180>>, // Strict RFC 4180 checks of every record
+    empty_as_none: bool,           // Mark unquoted empty fields (see crate::nulls)
+    field_spans: bool,             // Add the spans of the fields (see crate::spans)
+    header_check: Option<Arc<HeaderCheck>>, // Header every file must start with
+    opened: Arc<AtomicBool>,       // `open()` opened the first file and buffered its start
+    binary: Option<BinaryPolicy>,  // Control characters in fields (see crate::binary)
+    detected: Arc<OnceLock<Detected>>, // Found at the start of the data (see crate::detect)
+    detector: Arc<StdMutex<Detector>>, // Looks at the data until `detected` is set
+    range_end: Arc<OnceLock<u64>>, // Byte offset where a stream from `Reader.split()` stops
+    turns: Turnstile,              // Runs reads in the order the calls were made
+}
+
+impl RecordStream {
+    pub(crate) fn new(
+        source: &FileSource,
+        path: FilePath,
+        file_handle: Arc<StdMutex<Option<Py<PyAny>>>>,
+        event_loop: Arc<StdMutex<Option<Py<PyAny>>>>,
+        dialect: DialectConfig,
+        read_size: usize,
+        field_size_limit: Option<usize>,
+    ) -> Self {
+        let scratch = Arc::new(StdMutex::new(RecordScratch::new(&dialect)));
+        RecordStream {
+            files: Arc::new(vec![path]),
+            cursor: Arc::new(Mutex::new(FileCursor {
+                index: 0,
+                skip_header: false,
+            })),
+            access: source.access(),
+            file: Arc::new(Mutex::new(None)),
+            file_handle,
+            event_loop,
+            buffer: Arc::new(Mutex::new(String::new())),
+            buffer_start: Arc::new(Mutex::new(0)),
+            position: Arc::new(Mutex::new(0)),
+            line_num: Arc::new(Mutex::new(0)),
+            offset: Arc::new(Mutex::new(0)),
+            dialect,
+            read_size,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            field_size_limit,
+            follow: None,
+            has_header: true,
+            header_rules: Arc::new(HeaderRules::default()),
+            include_filename: false,
+            row_hash: false,
+            concurrency: 1,
+            engine: Engine::Default,
+            mmap: false,
+            lock: None,
+            held_lock: Arc::new(Mutex::new(None)),
+            progress: None,
+            stats: Arc::new(Stats::new()),
+            timeout: None,
+            retry: None,
+            parallel: Arc::new(Mutex::new(None)),
+            unread: Arc::new(StdMutex::new(VecDeque::new())),
+            scratch,
+            decoder: Arc::new(Utf8Decoder::new(Utf8Mode::Strict)),
+            gunzip: Arc::new(Gunzip::new()),
+            pipe: None,
+            checksum: None,
+            max_memory: None,
+            prefetch: None,
+            projection: None,
+            header_row: Arc::new(OnceLock::new()),
+            footer: None,
+            row_limit: None,
+            rfc4180: None,
+            empty_as_none: false,
+            field_spans: false,
+            header_check: None,
+            opened: Arc::new(AtomicBool::new(false)),
+            binary: None,
+            detected: Arc::new(OnceLock::new()),
+            detector: Arc::default(),
+            range_end: Arc::new(OnceLock::new()),
+            turns: Turnstile::default(),
+        }
+    }
+
+    /// Parse up to `concurrency` files of a multi-file stream at the same time.
+    pub(crate) fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Parse whole files in `read_all()` with `engine`.
+    pub(crate) fn engine(mut self, engine: Engine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Request `buffer_size` bytes from the OS per read of a path-backed file.
+    pub(crate) fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Parse whole files in `read_all()` over a memory map of the file.
+    pub(crate) fn mmap(mut self) -> Self {
+        self.mmap = true;
+        self
+    }
+
+    /// Hold a `mode` lock on each file from its first read until its end or `close()`.
+    pub(crate) fn lock(mut self, mode: LockMode) -> Self {
+        self.lock = Some(mode);
+        self
+    }
+
+    /// Decode files with `mode` (`utf8="lossy"` replaces invalid sequences).
+    pub(crate) fn utf8(mut self, mode: Utf8Mode) -> Self {
+        self.decoder = Arc::new(Utf8Decoder::new(mode));
+        self
+    }
+
+    /// Invalid UTF-8 sequences replaced with U+FFFD so far.
+    pub(crate) fn utf8_replacements(&self) -> u64 {
+        self.decoder.replacements()
+    }
+
+    /// Report bytes consumed and rows returned to `progress`.
+    pub(crate) fn progress(mut self, progress: Progress) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Read from an already open `file` instead of opening the path (`from_fd()`).
+    pub(crate) fn attach_file(&self, file: impl Into<Conn>) -> PyResult<()> {
+        let mut slot = self.file.try_lock().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Reader is already in use")
+        })?;
+        *slot = Some(BufReader::with_capacity(self.buffer_size, file.into()));
+        Ok(())
+    }
+
+    /// Read from `file`, the pipe `source` decodes an archive member or encrypted file
+    /// into, or downloads a file from an SFTP server into.
+    pub(crate) fn piped(mut self, file: File, source: PipeSource) -> PyResult<Self> {
+        self.attach_file(file)?;
+        self.pipe = Some(Arc::new(source));
+        Ok(self)
+    }
+
+    /// Raise `MemoryError` from `read_all()` rather than hold more than `limit` bytes
+    /// of rows.
+    pub(crate) fn max_memory(mut self, limit: usize) -> Self {
+        self.max_memory = Some(limit);
+        self
+    }
+
+    /// Check every record for deviations from RFC 4180 (`strict_rfc4180=True`).
+    pub(crate) fn strict_rfc4180(mut self) -> Self {
+        self.rfc4180 = Some(Arc::new(Rfc4180::default()));
+        self
+    }
+
+    /// Mark the unquoted empty fields of every record but the header, to be read as
+    /// None (`empty_as_none=True`).
+    pub(crate) fn empty_as_none(mut self) -> Self {
+        self.empty_as_none = true;
+        self
+    }
+
+    /// Add the byte spans of the fields to every record, as a last field.
+    pub(crate) fn field_spans(mut self) -> Self {
+        self.field_spans = true;
+        self
+    }
+
+    /// Catch or clean control characters in fields under `policy`.
+    pub(crate) fn binary(mut self, policy: BinaryPolicy) -> Self {
+        self.binary = Some(policy);
+        self
+    }
+
+    /// Check the header of every file against `check` before reading past it.
+    pub(crate) fn check_headers(mut self, check: HeaderCheck) -> Self {
+        self.header_check = Some(Arc::new(check));
+        self
+    }
+
+    /// Keep up to `rows` rows parsed ahead of the caller, on a background task.
+    pub(crate) fn prefetch(mut self, rows: usize) -> Self {
+        self.prefetch = Some(Arc::new(Prefetch {
+            rows,
+            running: Arc::new(Mutex::new(())),
+            error: StdMutex::new(None),
+        }));
+        self
+    }
+
+    /// Leave out the last `rows` records of every file.
+    pub(crate) fn skip_footer(mut self, rows: usize) -> Self {
+        self.footer = Some(Arc::new(Footer {
+            rows,
+            held: StdMutex::new(VecDeque::new()),
+        }));
+        self
+    }
+
+    /// Stop after `rows` records.
+    pub(crate) fn limit(mut self, rows: usize) -> Self {
+        self.row_limit = Some(Arc::new(RowLimit {
+            rows,
+            parsed: AtomicUsize::new(0),
+        }));
+        self
+    }
+
+    /// Return only the columns of `projection`.
+    pub(crate) fn columns(mut self, projection: Projection) -> Self {
+        self.projection = Some(Arc::new(projection));
+        self
+    }
+
+    /// Check every file against its expected digest before parsing it.
+    pub(crate) fn verify_checksum(mut self, verifier: Arc<Verifier>) -> Self {
+        self.checksum = Some(verifier);
+        self
+    }
+
+    /// Paths of the files the stream reads, in order.
+    pub(crate) fn files(&self) -> &[FilePath] {
+        &self.files
+    }
+
+    /// What the start of the data turned out to use, once it has been read.
+    pub(crate) fn detected(&self) -> Option<Detected> {
+        self.detected.get().cloned()
+    }
+
+    /// Whether the first record of the first file is a header.
+    pub(crate) fn has_header(&self) -> bool {
+        self.has_header
+    }
+
+    /// The first file's header row as the reader returns it, set once it has been read.
+    pub(crate) fn header_row(&self) -> Arc<OnceLock<Vec<String>>> {
+        Arc::clone(&self.header_row)
+    }
+
+    /// Whether rows handed back by a cancelled read are waiting to be returned.
+    pub(crate) fn has_unread(&self) -> bool {
+        !self
+            .unread
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    /// Capture the position of a path-backed stream for `Reader.state()`.
+    pub(crate) fn snapshot(&self) -> PyResult<StreamState> {
+        if self.access != Access::Path || !self.tracks_offset() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "state() requires file paths read one at a time, not a file handle, \
+                archive member, concurrency above 1 or utf8='lossy'",
+            ));
+        }
+        if self.prefetch.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "state() is unavailable with prefetch, which reads past the rows returned",
+            ));
+        }
+        if self.footer.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "state() is unavailable with skip_footer, which reads past the rows returned",
+            ));
+        }
+        if !self.files.iter().all(FilePath::is_unicode) {
+            // The saved state is JSON, which can't hold the real name
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "state() requires file names that are valid Unicode",
+            ));
+        }
+        if self.has_unread() {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "state() is unavailable until rows kept from a cancelled read are read",
+            ));
+        }
+        let busy =
+            |_| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Reader is already in use");
+        let cursor = self.cursor.try_lock().map_err(busy)?;
+        Ok(StreamState {
+            files: self.files.to_vec(),
+            file_index: cursor.index,
+            offset: *self.offset.try_lock().map_err(busy)?,
+            position: *self.position.try_lock().map_err(busy)?,
+            line_num: *self.line_num.try_lock().map_err(busy)?,
+            has_header: self.has_header,
+            header: self.header_row.get().cloned(),
+            end: self.range_end.get().copied(),
+        })
+    }
+
+    /// Positions splitting an unread single-file stream into `parts` ranges of about
+    /// equal size at record boundaries, each with the offset where it ends. Fewer are
+    /// returned for a file with fewer records.
+    pub(crate) fn split(&self, parts: usize) -> PyResult<Vec<StreamState>> {
+        self.check_scannable("split()")?;
+        let busy =
+            |_| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Reader is already in use");
+        if *self.position.try_lock().map_err(busy)? > 0
+            || (self.file.try_lock().map_err(busy)?.is_some()
+                && !self.opened.load(Ordering::Relaxed))
+            || self.range_end.get().is_some()
+        {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "split() requires a reader that has not been read from",
+            ));
+        }
+        let path = &self.files[0];
+        let size = std::fs::metadata(paths::native(path))
+            .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?
+            .len();
+        let starts = record_boundaries(path, &self.dialect, parts)?;
+        let ends = starts.iter().skip(1).map(|&(offset, ..)| offset);
+        Ok(starts
+            .iter()
+            .zip(ends.chain(std::iter::once(size)))
+            .map(|(&(offset, position, line_num), end)| StreamState {
+                files: self.files.to_vec(),
+                file_index: 0,
+                offset,
+                position,
+                line_num,
+                has_header: self.has_header,
+                header: None,
+                end: Some(end),
+            })
+            .collect())
+    }
+
+    /// Reject streams whose record boundaries can't be found by scanning the file's
+    /// bytes for `operation`.
+    fn check_scannable(&self, operation: &str) -> PyResult<()> {
+        if self.access != Access::Path || self.files.len() > 1 || !self.tracks_offset() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "{operation} requires a single file path, not a file handle, archive member, \
+                 several files or utf8='lossy'"
+            )));
+        }
+        if self.dialect.escapechar.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "{operation} cannot find record boundaries in a file using an escapechar"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Move to the start of record `row` (0-based, the header included), found in the
+    /// file's shared record index. `row` may be the record count, to move to the end.
+    pub(crate) async fn seek(&self, row: usize) -> PyResult<()> {
+        self.check_scannable("seek()")?;
+        if self.footer.is_some() || self.range_end.get().is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "seek() cannot be used with skip_footer or on a reader from split()",
+            ));
+        }
+        let path = self.files[0].clone();
+        let dialect = self.dialect.clone();
+        let max_memory = self.max_memory;
+        let index =
+            tokio::task::spawn_blocking(move || index::row_index(&path, &dialect, max_memory))
+                .await
+                .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))??;
+        let (offset, line_num) = match index.starts.get(row) {
+            Some(&start) => start,
+            None if row == index.starts.len() => index.end,
+            None => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "row_index {row} is past the end of {} ({} records)",
+                    self.files[0],
+                    index.starts.len()
+                )))
+            }
+        };
+        self.close().await;
+        self.unread
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        self.decoder.reset();
+        self.restore(&StreamState {
+            files: self.files.to_vec(),
+            file_index: 0,
+            offset,
+            position: row,
+            line_num,
+            has_header: self.has_header,
+            header: None,
+            end: None,
+        })
+    }
+
+    /// Stop at byte `end` of the file, a record boundary found by `split()`.
+    pub(crate) fn set_range_end(&self, end: u64) {
+        let _ = self.range_end.set(end);
+    }
+
+    /// Continue a new stream from `state`, opening its file at the saved offset.
+    pub(crate) fn restore(&self, state: &StreamState) -> PyResult<()> {
+        let path = self.files.get(state.file_index).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "file_index {} is out of range for {} file(s)",
+                state.file_index,
+                self.files.len()
+            ))
+        })?;
+        let mut file = std::fs::File::open(paths::native(path))
+            .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if state.offset > len {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "byte_offset {} is past the end of {path} ({len} bytes)",
+                state.offset
+            )));
+        }
+        std::io::Seek::seek(&mut file, SeekFrom::Start(state.offset))
+            .map_err(|e| CsvIoError::new_err(format!("Failed to read file {path}: {e}")))?;
+        // The header won't be read again: take it from the first file to resolve names
+        // in `columns` and record it for `header_row()`. A header that differs from the
+        // saved one means the file was replaced, and its rows no longer line up
+        if state.position > 0 && self.has_header {
+            let needed = state.header.is_some()
+                || self.header_check.is_some()
+                || self
+                    .projection
+                    .as_ref()
+                    .is_some_and(|p| p.indices().is_none());
+            match read_header(&self.files[0], &self.dialect) {
+                Ok(mut header) => {
+                    if let Some(check) = &self.header_check {
+                        check.check(&self.files[0], &header)?;
+                    }
+                    self.resolve_columns(0, &header)?;
+                    self.finish_row(&mut 0, &mut header, &self.files[0], false)?;
+                    if let Some(saved) = state.header.as_ref().filter(|&saved| *saved != header) {
+                        return Err(FileChangedError::new_err(format!(
+                            "Header of {} changed since its position was saved: {saved:?} \
+                             became {header:?}",
+                            self.files[0]
+                        )));
+                    }
+                }
+                Err(e) if needed => return Err(e),
+                Err(_) => {} // Names in intern_columns are then left alone
+            }
+        }
+        self.attach_file(File::from_std(file))?;
+        let busy =
+            |_| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Reader is already in use");
+        let mut cursor = self.cursor.try_lock().map_err(busy)?;
+        cursor.index = state.file_index;
+        // A later file that was opened but not read from still has its header ahead
+        cursor.skip_header = self.has_header && state.file_index > 0 && state.offset == 0;
+        *self.offset.try_lock().map_err(busy)? = state.offset;
+        *self.position.try_lock().map_err(busy)? = state.position;
+        *self.line_num.try_lock().map_err(busy)? = state.line_num;
+        Ok(())
+    }
+
+    /// Raise `asyncio.TimeoutError` from read operations that take longer than `timeout`.
+    pub(crate) fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry transient failures to open and read files under `policy`.
+    pub(crate) fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(Arc::new(policy));
+        self
+    }
+
+    /// Take a place in line for a read. Taken synchronously when a read method is
+    /// called, so concurrent reads on one reader each get the next rows in call order.
+    pub(crate) fn ticket(&self) -> Ticket {
+        self.turns.ticket()
+    }
+
+    /// Run a read operation once every read called before it is done, under the
+    /// stream's timeout (which includes the wait), counting it in `stats`.
+    pub(crate) async fn operation<T>(
+        &self,
+        ticket: Ticket,
+        operation: impl Future<Output = PyResult<T>>,
+    ) -> PyResult<T> {
+        let operation = async move {
+            ticket.wait().await;
+            operation.await
+        };
+        self.stats
+            .track(timeout::limit(self.timeout, operation))
+            .await
+    }
+
+    /// Read `files` one after another as a single stream.
+    ///
+    /// With `has_header`, the first record of every file after the first is skipped and
+    /// the first file's header is treated as such by `include_filename`.
+    pub(crate) fn with_files(mut self, files: Vec<FilePath>, has_header: bool) -> Self {
+        self.files = Arc::new(files);
+        self.has_header = has_header;
+        self
+    }
+
+    /// Rewrite the names of the header row with `rules`.
+    pub(crate) fn header_rules(mut self, rules: HeaderRules) -> Self {
+        self.header_rules = Arc::new(rules);
+        self
+    }
+
+    /// Append the path of the file each row came from as an extra final field.
+    ///
+    /// With a header, the header row gets a `_file` column name instead.
+    pub(crate) fn include_filename(mut self) -> Self {
+        self.include_filename = true;
+        self
+    }
+
+    /// Append a hash of each row's fields as an extra final field, after the file path
+    /// of `include_filename`. With a header, the header row gets a `_hash` column name.
+    pub(crate) fn row_hash(mut self) -> Self {
+        self.row_hash = true;
+        self
+    }
+
+    /// Keep polling for appended data at EOF instead of ending the stream.
+    pub(crate) fn follow(mut self, poll_interval: Duration) -> Self {
+        self.follow = Some(poll_interval);
+        self
+    }
+
+    /// Whether `text` ends with the dialect's line terminator.
+    fn ends_with_terminator(&self, text: &str) -> bool {
+        match (self.dialect.lineterminator, text.as_bytes().last()) {
+            (Terminator::Any(b), Some(&last)) => last == b,
+            (_, Some(&last)) => last == b'\n' || last == b'\r',
+            (_, None) => false,
+        }
+    }
+
+    /// Take the configured lock on `path`, releasing the lock on any previous file.
+    async fn hold_lock(&self, path: &FilePath) -> PyResult<()> {
+        let Some(mode) = self.lock else {
+            return Ok(());
+        };
+        let mut held = self.held_lock.lock().await;
+        if held.as_ref().is_some_and(|(locked, _)| locked == path) {
+            return Ok(());
+        }
+        held.take();
+        let mut options = std::fs::OpenOptions::new();
+        options.read(true);
+        *held = Some((path.clone(), lock::open_locked(path, options, mode).await?));
+        Ok(())
+    }
+
+    /// Append the next chunk of the file at `path` to `buffer`, opening and locking it
+    /// first if needed. Returns whether the file is exhausted.
+    async fn refill(&self, path: &FilePath, buffer: &mut String) -> PyResult<bool> {
+        if self.access == Access::Path {
+            if let Some(verifier) = &self.checksum {
+                verifier.verify(path).await?;
+            }
+            self.hold_lock(path).await?;
+        }
+        let (chunk, eof) = read_chunk(
+            self.access,
+            path,
+            &self.file,
+            &self.file_handle,
+            &self.event_loop,
+            self.read_size,
+            self.buffer_size,
+            self.retry.as_deref(),
+            &self.decoder,
+            Some(&self.gunzip),
+        )
+        .await?;
+        if let Some(source) = self.pipe.as_ref().filter(|_| eof) {
+            source.finish()?;
+        }
+        if self.detected.get().is_none() {
+            let at_file_start = *self.offset.lock().await == 0;
+            let mut detector = self.detector.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(detected) =
+                detector.feed(chunk.as_bytes(), &self.dialect, at_file_start, eof)
+            {
+                let _ = self.detected.set(detected);
+            }
+        }
+        buffer.push_str(&chunk);
+        Ok(eof)
+    }
+
+    /// Open the stream ahead of the first read (`Reader.open()`): check that every file
+    /// can be opened, then read the first file up to the end of its first record and
+    /// check that record, so a missing or unreadable file, a bad archive member, key or
+    /// checksum, undecodable bytes, a malformed header, `columns` it lacks or a
+    /// `check_headers` mismatch raise here. Nothing is consumed: the record stays
+    /// buffered for the first read. Does nothing once reading has started.
+    pub(crate) async fn open(&self) -> PyResult<()> {
+        let mut buffer = self.buffer.lock().await;
+        let buffer_start = self.buffer_start.lock().await;
+        let cursor = self.cursor.lock().await;
+        if *self.position.lock().await > 0 || !buffer.is_empty() {
+            return Ok(());
+        }
+        if self.access == Access::Path && self.pipe.is_none() {
+            // Later files are only opened when reached; make sure they can be
+            for path in &self.files[cursor.index + 1..] {
+                File::open(paths::native(path))
+                    .await
+                    .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+            }
+        }
+        let path = &self.files[cursor.index];
+        let fresh = self.file.lock().await.is_none();
+        let mut eof = false;
+        loop {
+            let available = buffer.get(*buffer_start..).unwrap_or("");
+            match self.parse_first(available, self.selected()) {
+                Some(Ok((row, consumed))) if consumed < available.len() || eof => {
+                    if self.has_header {
+                        if let Some(check) = &self.header_check {
+                            let full = self.parse_first(available, None).and_then(Result::ok);
+                            check.check(path, &full.map_or(row.clone(), |(full, _)| full))?;
+                        }
+                        self.resolve_columns(0, &row)?;
+                    }
+                    break;
+                }
+                Some(Err(e)) if eof => {
+                    return Err(CsvParseError::new_err(format!(
+                        "CSV parse error at row 0 (0-indexed) in file '{path}': {e}"
+                    )));
+                }
+                None if eof => break, // An empty file
+                _ => {}
+            }
+            eof = self.refill(path, &mut buffer).await?;
+        }
+        // With nothing else read, read_all() may still load the whole file at once
+        self.opened.store(fresh, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn release_lock(&self) {
+        self.held_lock.lock().await.take();
+    }
+
+    /// Count rows returned and bytes consumed in the stats and the progress callback.
+    fn count(&self, rows: usize, bytes: u64) -> PyResult<()> {
+        self.stats.record(rows, bytes);
+        match &self.progress {
+            Some(progress) => progress.advance(rows, bytes),
+            None => Ok(()),
+        }
+    }
+
+    fn finish_progress(&self) -> PyResult<()> {
+        match &self.progress {
+            Some(progress) => progress.finish(),
+            None => Ok(()),
+        }
+    }
+
+    /// Parse the first record of `data`, returning it with the number of bytes consumed.
+    /// Only the fields at `selected` are decoded, if given.
+    fn parse_first(
+        &self,
+        data: &str,
+        selected: Option<&[usize]>,
+    ) -> Option<Result<(Vec<String>, usize), String>> {
+        if self.dialect.delim_whitespace {
+            return whitespace::parse_first(data.as_bytes(), &self.dialect).map(|result| {
+                result.map(|(row, consumed)| match selected {
+                    Some(indices) => (project::select(indices, row), consumed),
+                    None => (row, consumed),
+                })
+            });
+        }
+        let mut scratch = self.scratch.lock().unwrap_or_else(|e| e.into_inner());
+        scratch.parse_first(data.as_bytes(), selected)
+    }
+
+    /// Positions of the `columns=` selection, once known.
+    fn selected(&self) -> Option<&[usize]> {
+        self.projection.as_ref().and_then(|p| p.indices())
+    }
+
+    /// Resolve `columns=` names from the first file's header row, at `position` 0.
+    fn resolve_columns(&self, position: usize, row: &[String]) -> PyResult<()> {
+        match &self.projection {
+            Some(projection) if position == 0 && self.has_header => {
+                let mut header = row.to_vec();
+                self.header_rules.apply(&mut header)?;
+                projection.resolve(&header)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Read the next record, or `None` once the source is exhausted.
+    ///
+    /// In follow mode this waits for more data instead of returning `None`; callers
+    /// stop following by cancelling the awaiting task.
+    pub(crate) async fn next_record(&self) -> PyResult<Option<Vec<String>>> {
+        if let Some(row) = self.pop_unread() {
+            return Ok(Some(row));
+        }
+        let Some(prefetch) = &self.prefetch else {
+            return self.parse_record().await;
+        };
+        // Wait for rows being parsed ahead, and keep the task from starting again while
+        // parsing here
+        let running = Arc::clone(&prefetch.running).lock_owned().await;
+        if let Some(row) = self.pop_unread() {
+            return Ok(Some(row));
+        }
+        let pending = prefetch
+            .error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(e) = pending {
+            return Err(e);
+        }
+        let row = self.parse_record().await?;
+        drop(running);
+        if row.is_some() {
+            self.start_prefetch();
+        }
+        Ok(row)
+    }
+
+    fn pop_unread(&self) -> Option<Vec<String>> {
+        let mut unread = self.unread.lock().unwrap_or_else(|e| e.into_inner());
+        let row = unread.pop_front()?;
+        // Refill once half the rows parsed ahead have been taken
+        if self
+            .prefetch
+            .as_ref()
+            .is_some_and(|prefetch| unread.len() * 2 <= prefetch.rows)
+        {
+            drop(unread);
+            self.start_prefetch();
+        }
+        Some(row)
+    }
+
+    /// Start parsing ahead unless that is already under way or an error is pending.
+    fn start_prefetch(&self) {
+        let Some(prefetch) = &self.prefetch else {
+            return;
+        };
+        let Ok(running) = Arc::clone(&prefetch.running).try_lock_owned() else {
+            return;
+        };
+        if prefetch
+            .error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+        {
+            return;
+        }
+        let stream = self.clone();
+        let rows = prefetch.rows;
+        tokio::spawn(async move {
+            let _running = running;
+            loop {
+                let queued = stream
+                    .unread
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .len();
+                if queued >= rows {
+                    break;
+                }
+                match stream.parse_record().await {
+                    Ok(Some(row)) => stream
+                        .unread
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .push_back(row),
+                    Ok(None) => break,
+                    Err(e) => {
+                        if let Some(prefetch) = &stream.prefetch {
+                            *prefetch.error.lock().unwrap_or_else(|e| e.into_inner()) = Some(e);
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Parse the next record from the files, stopping at `limit`.
+    async fn parse_record(&self) -> PyResult<Option<Vec<String>>> {
+        let Some(limit) = &self.row_limit else {
+            return self.parse_trimmed().await;
+        };
+        if limit.parsed.load(Ordering::Relaxed) >= limit.rows {
+            // Nothing more is read: let go of the file lock and files parsed in parallel
+            self.release_lock().await;
+            self.parallel.lock().await.take();
+            return Ok(None);
+        }
+        let row = self.parse_trimmed().await?;
+        // A concurrent read may have taken the last record meanwhile
+        if row.is_some() && limit.parsed.fetch_add(1, Ordering::Relaxed) >= limit.rows {
+            return Ok(None);
+        }
+        Ok(row)
+    }
+
+    /// Parse the next record, holding back the footer of each file with `skip_footer`.
+    async fn parse_trimmed(&self) -> PyResult<Option<Vec<String>>> {
+        let Some(footer) = &self.footer else {
+            return Ok(self.parse_indexed().await?.map(|(_, row)| row));
+        };
+        loop {
+            {
+                let mut held = footer.held.lock().unwrap_or_else(|e| e.into_inner());
+                if held.len() > footer.rows {
+                    return Ok(held.pop_front().map(|(_, row)| row));
+                }
+            }
+            let parsed = self.parse_indexed().await?;
+            let mut held = footer.held.lock().unwrap_or_else(|e| e.into_inner());
+            let Some((file, row)) = parsed else {
+                held.clear();
+                return Ok(None);
+            };
+            // A row of the next file: what is held of the last one is its footer
+            if held.front().is_some_and(|(index, _)| *index != file) {
+                held.clear();
+            }
+            held.push_back((file, row));
+        }
+    }
+
+    /// Parse the next record from the files, with the index of the file it is from.
+    async fn parse_indexed(&self) -> PyResult<Option<(usize, Vec<String>)>> {
+        if self.concurrency > 1 && self.files.len() > 1 {
+            return self.next_parallel().await;
+        }
+        let mut buffer = self.buffer.lock().await;
+        let mut buffer_start = self.buffer_start.lock().await;
+        let mut cursor = self.cursor.lock().await;
+        let mut eof = false;
+
+        loop {
+            if let Some(&end) = self.range_end.get() {
+                if *self.offset.lock().await >= end {
+                    buffer.clear();
+                    *buffer_start = 0;
+                    self.release_lock().await;
+                    self.finish_progress()?;
+                    return Ok(None);
+                }
+            }
+            let path = &self.files[cursor.index];
+            let last_file = cursor.index + 1 == self.files.len();
+            let following = self.follow.is_some() && last_file;
+            let available = buffer.get(*buffer_start..).unwrap_or("");
+            if !available.is_empty() {
+                let selected = self.selected();
+                match self.parse_first(available, selected) {
+                    Some(Ok((mut row, consumed))) => {
+                        let consumed = consumed.min(available.len());
+                        let complete = consumed < available.len()
+                            || (eof
+                                && (!following
+                                    || self.ends_with_terminator(&available[..consumed])));
+                        if complete {
+                            let consumed =
+                                record_end(available.as_bytes(), consumed, &self.dialect);
+                            let newline_count = available[..consumed]
+                                .bytes()
+                                .filter(|&b| b == b'\n')
+                                .count();
+                            // Take the counters before changing anything: with no await
+                            // points left, a cancelled read either returns this row or
+                            // leaves the stream untouched
+                            let mut line_num = self.line_num.lock().await;
+                            let mut position = self.position.lock().await;
+                            let mut offset = self.offset.lock().await;
+                            if let Some(rfc4180) = &self.rfc4180 {
+                                let record = &available.as_bytes()[..consumed];
+                                if let Err(violation) = rfc4180.check(record) {
+                                    let context = ErrorContext {
+                                        row: Some(*position),
+                                        line: Some(*line_num as u64 + 1),
+                                        column: Some(violation.column),
+                                        byte_offset: Some(*offset),
+                                        snippet: Some(snippet(&record[violation.at..])),
+                                        ..ErrorContext::default()
+                                    };
+                                    return Err(context.attach(CsvParseError::new_err(format!(
+                                        "RFC 4180 violation at row {} (0-indexed) in file \
+                                         '{path}'{}: {}",
+                                        *position,
+                                        context.describe(),
+                                        violation.message
+                                    ))));
+                                }
+                            }
+                            let header = cursor.skip_header || (*position == 0 && self.has_header);
+                            if let (Some(check), true) = (&self.header_check, header) {
+                                // Compared whole, before `columns=` picks fields from it
+                                let full = match selected {
+                                    Some(_) => self
+                                        .parse_first(&available[..consumed], None)
+                                        .and_then(Result::ok)
+                                        .map_or_else(|| row.clone(), |(full, _)| full),
+                                    None => row.clone(),
+                                };
+                                check.check(path, &full)?;
+                            }
+                            if !cursor.skip_header {
+                                self.resolve_columns(*position, &row)?;
+                            }
+                            if self.empty_as_none {
+                                // Before the NUL marks go in, which finish_row would see
+                                if let Some(binary) = self.binary {
+                                    binary.apply(&mut row, *position, path)?;
+                                }
+                                if !(*position == 0 && self.has_header) {
+                                    let record = &available.as_bytes()[..consumed];
+                                    nulls::mark(&mut row, record, &self.dialect, selected);
+                                }
+                            }
+                            let spans = self.field_spans.then(|| {
+                                let record = &available.as_bytes()[..consumed];
+                                spans::encode(record, *offset, &self.dialect, self.selected())
+                            });
+                            // Multi-line records advance line_num by every line they span
+                            *line_num += newline_count.max(1);
+                            *offset += consumed as u64;
+
+                            *buffer_start += consumed;
+                            // Only trim buffer when it gets very large to prevent unbounded growth
+                            if buffer.len() > self.read_size * 8 {
+                                buffer.drain(..*buffer_start);
+                                *buffer_start = 0;
+                            }
+                            if cursor.skip_header {
+                                cursor.skip_header = false;
+                                self.count(0, consumed as u64)?;
+                                continue;
+                            }
+
+                            let projected = selected.is_some();
+                            self.finish_row(&mut position, &mut row, path, projected)?;
+                            row.extend(spans);
+                            self.count(1, consumed as u64)?;
+                            return Ok(Some((cursor.index, row)));
+                        }
+                    }
+                    Some(Err(e)) if eof && !following => {
+                        let current_pos = *self.position.lock().await;
+                        let context = ErrorContext {
+                            row: Some(current_pos),
+                            line: Some(*self.line_num.lock().await as u64 + 1),
+                            byte_offset: Some(*self.offset.lock().await),
+                            snippet: Some(snippet(available.as_bytes())),
+                            ..ErrorContext::default()
+                        };
+                        return Err(context.attach(CsvParseError::new_err(format!(
+                            "CSV parse error at row {current_pos} (0-indexed) in file '{path}'{}: {e}. \
+                            The CSV file may be malformed or have incomplete records.",
+                            context.describe()
+                        ))));
+                    }
+                    // Incomplete or malformed record: read more data before deciding
+                    _ => {}
+                }
+            }
+
+            if eof {
+                if !last_file {
+                    // Continue with the next file, skipping its header
+                    self.file.lock().await.take();
+                    self.decoder.reset();
+                    *self.offset.lock().await = 0;
+                    buffer.clear();
+                    *buffer_start = 0;
+                    cursor.index += 1;
+                    cursor.skip_header = self.has_header;
+                    eof = false;
+                    continue;
+                }
+                match self.follow {
+                    Some(poll_interval) => tokio::time::sleep(poll_interval).await,
+                    None => {
+                        buffer.clear();
+                        *buffer_start = 0;
+                        self.release_lock().await;
+                        self.finish_progress()?;
+                        return Ok(None);
+                    }
+                }
+            }
+
+            eof = self.refill(path, &mut buffer).await?;
+        }
+    }
+
+    /// Read up to `n` records as the bytes they span in the file, terminators included,
+    /// found by scanning for record ends outside quotes rather than parsing fields.
+    /// Nothing is consumed until the records are returned, so a cancelled call leaves
+    /// them to the next read.
+    pub(crate) async fn next_raw_records(&self, n: usize) -> PyResult<Vec<Vec<u8>>> {
+        self.check_raw()?;
+        let mut buffer = self.buffer.lock().await;
+        let mut buffer_start = self.buffer_start.lock().await;
+        let cursor = self.cursor.lock().await;
+        let path = &self.files[cursor.index];
+        let start_offset = *self.offset.lock().await;
+        let range_end = self.range_end.get().copied();
+        let mut records = Vec::new();
+        let mut taken = 0; // Bytes of the buffer spanned by `records`
+        let mut lines = 0;
+        let mut eof = false;
+        loop {
+            if records.len() >= n || range_end.is_some_and(|end| start_offset + taken as u64 >= end)
+            {
+                break;
+            }
+            let available = &buffer.as_bytes()[*buffer_start + taken..];
+            if let Some((start, end)) = raw_record(available, &self.dialect, eof) {
+                records.push(available[start..end].to_vec());
+                lines += memchr_iter(b'\n', &available[..end]).count().max(1);
+                taken += end;
+                continue;
+            }
+            if eof {
+                // Only blank lines are left
+                taken += available.len();
+                break;
+            }
+            eof = self.refill(path, &mut buffer).await?;
+        }
+
+        // Take the counters before changing anything, as parse_indexed does
+        let mut line_num = self.line_num.lock().await;
+        let mut position = self.position.lock().await;
+        let mut offset = self.offset.lock().await;
+        *line_num += lines;
+        *position += records.len();
+        *offset += taken as u64;
+        *buffer_start += taken;
+        if buffer.len() > self.read_size * 8 {
+            buffer.drain(..*buffer_start);
+            *buffer_start = 0;
+        }
+        self.count(records.len(), taken as u64)?;
+        if records.len() < n {
+            buffer.clear();
+            *buffer_start = 0;
+            self.release_lock().await;
+            self.finish_progress()?;
+        }
+        Ok(records)
+    }
+
+    /// Reject options that work on parsed rows, which raw records never become.
+    fn check_raw(&self) -> PyResult<()> {
+        let unsupported = [
+            (self.files.len() > 1, "several files"),
+            (self.follow.is_some(), "follow"),
+            (self.dialect.escapechar.is_some(), "escapechar"),
+            (self.dialect.delim_whitespace, "delim_whitespace"),
+            (self.projection.is_some(), "columns"),
+            (self.include_filename, "include_filename"),
+            (self.row_hash, "row_hash"),
+            (self.footer.is_some(), "skip_footer"),
+            (self.row_limit.is_some(), "limit"),
+            (self.prefetch.is_some(), "prefetch"),
+            (self.rfc4180.is_some(), "strict_rfc4180"),
+            (self.header_check.is_some(), "check_headers"),
+            (self.binary.is_some(), "binary"),
+            (self.has_unread(), "rows read ahead"),
+        ];
+        match unsupported.iter().find(|(used, _)| *used) {
+            Some((_, option)) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "read_raw_lines() cannot be used with {option}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Read up to `n` records. Records already read when the call is cancelled or fails
+    /// are returned by the next read instead of being lost.
+    #[tracing::instrument(level = "debug", name = "parse", skip_all, fields(rows))]
+    pub(crate) async fn next_records(&self, n: usize) -> PyResult<Vec<Vec<String>>> {
+        let mut pending = PendingRows {
+            rows: Vec::new(),
+            unread: &self.unread,
+        };
+        while pending.rows.len() < n {
+            match self.next_record().await? {
+                Some(row) => pending.rows.push(row),
+                None => break, // EOF reached
+            }
+        }
+        tracing::Span::current().record("rows", pending.rows.len());
+        Ok(std::mem::take(&mut pending.rows))
+    }
+
+    /// Read every remaining record, failing once they take more than `limit` bytes.
+    /// The records read by then are returned by the next read.
+    async fn records_within(&self, limit: usize) -> PyResult<Vec<Vec<String>>> {
+        let mut pending = PendingRows {
+            rows: Vec::new(),
+            unread: &self.unread,
+        };
+        let mut used = 0;
+        while let Some(row) = self.next_record().await? {
+            used += memory::row_size(&row);
+            pending.rows.push(row);
+            if used > limit {
+                return Err(read_all_exceeded(limit));
+            }
+        }
+        Ok(std::mem::take(&mut pending.rows))
+    }
+
+    /// Whether `read_all()` can load the file whole while nothing has been read yet:
+    /// a single file opened from its path, without options that look at records one
+    /// at a time. With a limit only the records needed are read.
+    pub(crate) fn loads_whole(&self) -> bool {
+        self.access == Access::Path
+            && self.pipe.is_none()
+            && self.files.len() == 1
+            && self.row_limit.is_none()
+            && self.rfc4180.is_none()
+            && !self.empty_as_none
+            && !self.field_spans
+            && self.header_check.is_none()
+    }
+
+    /// Read every remaining record.
+    ///
+    /// A single path-backed file that has not been read from yet is loaded whole and
+    /// parsed on the blocking thread pool, so a large file never ties up an async worker
+    /// (and never holds the GIL). With `threads` above one it is split into ranges at
+    /// record boundaries that are parsed on separate threads; with `mmap` it is parsed
+    /// directly over a memory map instead of a copy read into memory. Other streams are
+    /// read record by record, and refuse `threads` above one.
+    pub(crate) async fn read_all(&self, threads: usize) -> PyResult<Vec<Vec<String>>> {
+        if self.follow.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "read_all() cannot be used in follow mode",
+            ));
+        }
+        let opened = self.opened.load(Ordering::Relaxed) && self.decoder.replacements() == 0;
+        let untouched = *self.position.lock().await == 0
+            && *self.offset.lock().await == 0
+            && (opened || self.file.lock().await.is_none());
+        let fast = self.engine == Engine::Fast && fast::supports(&self.dialect);
+        let mut whole_file = untouched && self.loads_whole() && self.range_end.get().is_none();
+        if let (true, Some(limit)) = (whole_file, self.max_memory) {
+            // A file larger than the budget is read in chunks up to the limit instead
+            let size = tokio::fs::metadata(paths::native(&self.files[0]))
+                .await
+                .map(|m| m.len());
+            whole_file = size.is_ok_and(|size| size <= limit as u64);
+        }
+        if !whole_file {
+            if threads > 1 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "read_all(threads=...) requires a single file path read whole from its start, \
+                     not a file handle, several files, an archive member, a file larger than \
+                     max_memory, rows already read (including a header read for row_type='row' \
+                     or a schema) or limit, strict_rfc4180, empty_as_none, field_spans or \
+                     check_headers",
+                ));
+            }
+            return match self.max_memory {
+                Some(limit) => self.records_within(limit).await,
+                None => self.next_records(usize::MAX).await,
+            };
+        }
+
+        // Range splitting doesn't understand escapes, so escaped files use one range
+        let threads = if self.dialect.escapechar.is_some() {
+            1
+        } else {
+            threads
+        };
+        let path = self.files[0].clone();
+        if let Some(verifier) = &self.checksum {
+            verifier.verify(&path).await?;
+        }
+        self.hold_lock(&path).await?;
+        let dialect = self.dialect.clone();
+        let field_size_limit = self.field_size_limit;
+        let engine = if fast { Engine::Fast } else { Engine::Default };
+        let projection = self.projection.clone();
+        let has_header = self.has_header;
+        let header_rules = Arc::clone(&self.header_rules);
+        let (mut parsed, end, projected) = if self.mmap {
+            let parse_path = path.clone();
+            let decoder = Arc::clone(&self.decoder);
+            let detected = Arc::clone(&self.detected);
+            tokio::task::spawn_blocking(move || {
+                let mapped = map_file(&parse_path)?;
+                let map = &mapped.map;
+                if let Some(found) = detect::detect(map, &dialect) {
+                    let _ = detected.set(found);
+                }
+                // Invalid input is only copied out of the map to be replaced
+                let text = match std::str::from_utf8(map) {
+                    Ok(text) => std::borrow::Cow::Borrowed(text),
+                    Err(_) => std::borrow::Cow::Owned(decoder.decode_all(map.to_vec())?),
+                };
+                let selected = resolve_from_text(
+                    projection.as_deref(),
+                    has_header,
+                    &header_rules,
+                    &text,
+                    &dialect,
+                )?;
+                let parsed = parse_ranges(
+                    &text,
+                    &parse_path,
+                    &dialect,
+                    field_size_limit,
+                    threads,
+                    engine,
+                    selected.as_deref(),
+                )?;
+                mapped.check_unchanged(&parse_path)?;
+                Ok::<_, PyErr>((parsed, map.len() as u64, selected.is_some()))
+            })
+            .instrument(tracing::debug_span!("parse", path = %path, threads))
+            .await
+            .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))??
+        } else {
+            let mut attempt = 0;
+            let data = loop {
+                match uring::read_file(&path).await {
+                    Ok(data) => break data,
+                    Err(e) => {
+                        let error = CsvIoError::new_err(format!("Failed to open file {path}: {e}"));
+                        attempt += 1;
+                        let transient = retry::transient_io(&e);
+                        if !retry::again(self.retry.as_deref(), attempt, transient, &error).await? {
+                            return Err(error);
+                        }
+                    }
+                }
+            };
+            let end = data.len() as u64;
+            let parse_path = path.clone();
+            let decoder = Arc::clone(&self.decoder);
+            let detected = Arc::clone(&self.detected);
+            let (parsed, projected) = tokio::task::spawn_blocking(move || {
+                if let Some(found) = detect::detect(&data, &dialect) {
+                    let _ = detected.set(found);
+                }
+                // Decoding walks the whole file, so it stays off the event loop too
+                let text = decoder.decode_all(data)?;
+                let selected = resolve_from_text(
+                    projection.as_deref(),
+                    has_header,
+                    &header_rules,
+                    &text,
+                    &dialect,
+                )?;
+                let parsed = parse_ranges(
+                    &text,
+                    &parse_path,
+                    &dialect,
+                    field_size_limit,
+                    threads,
+                    engine,
+                    selected.as_deref(),
+                )?;
+                Ok::<_, PyErr>((parsed, selected.is_some()))
+            })
+            .instrument(tracing::debug_span!("parse", path = %path, threads))
+            .await
+            .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))??;
+            (parsed, end, projected)
+        };
+
+        if let Some(footer) = &self.footer {
+            parsed.truncate(parsed.len().saturating_sub(footer.rows));
+        }
+        if let Some(limit) = self.max_memory {
+            let used: usize = parsed.iter().map(|(row, _)| memory::row_size(row)).sum();
+            if used > limit {
+                return Err(read_all_exceeded(limit));
+            }
+        }
+
+        // Leave the file positioned at the end so later reads only see appended data
+        let mut reopened = File::open(paths::native(&path))
+            .await
+            .map_err(|e| CsvIoError::new_err(format!("Failed to open file {path}: {e}")))?;
+        reopened
+            .seek(SeekFrom::Start(end))
+            .await
+            .map_err(|e| CsvIoError::new_err(format!("Failed to read file {path}: {e}")))?;
+
+        // Nothing below awaits, so a cancelled read_all() leaves the reader untouched
+        let mut buffer = self.buffer.lock().await;
+        let mut buffer_start = self.buffer_start.lock().await;
+        let mut file = self.file.lock().await;
+        let mut held_lock = self.held_lock.lock().await;
+        let mut line_num = self.line_num.lock().await;
+        let mut position = self.position.lock().await;
+        let mut offset = self.offset.lock().await;
+        *file = Some(BufReader::with_capacity(self.buffer_size, reopened.into()));
+        held_lock.take();
+        *offset = end;
+        // Drop what open() buffered, which the whole file was parsed again with
+        buffer.clear();
+        *buffer_start = 0;
+        self.decoder.reset();
+        self.opened.store(false, Ordering::Relaxed);
+
+        self.count(0, end)?;
+        let mut rows = Vec::with_capacity(parsed.len());
+        for (mut row, lines) in parsed {
+            *line_num += lines;
+            self.finish_row(&mut position, &mut row, &path, projected)?;
+            self.count(1, 0)?;
+            rows.push(row);
+        }
+        self.finish_progress()?;
+        Ok(rows)
+    }
+
+    /// Count a row about to be returned and apply `columns`, unless the parser already
+    /// did (`projected`), `include_filename` and `row_hash`.
+    fn finish_row(
+        &self,
+        position: &mut usize,
+        row: &mut Vec<String>,
+        path: &str,
+        projected: bool,
+    ) -> PyResult<()> {
+        let header = *position == 0 && self.has_header;
+        // Columns chosen by name were resolved against the whole header, renamed
+        let names = match &self.projection {
+            Some(projection) if header => projection.names(),
+            _ => None,
+        };
+        if let Some(names) = names {
+            *row = names.to_vec();
+        } else {
+            if let (false, Some(indices)) = (projected, self.selected()) {
+                *row = project::select(indices, std::mem::take(row));
+            }
+            if header {
+                self.header_rules.apply(row)?;
+            }
+        }
+        if let (Some(binary), false) = (self.binary, self.empty_as_none) {
+            binary.apply(row, *position, path)?;
+        }
+        *position += 1;
+        let hash = self.row_hash.then(|| {
+            if header {
+                HASH_COLUMN.to_string()
+            } else {
+                rowhash::hash_fields(row)
+            }
+        });
+        if self.include_filename {
+            if header {
+                row.push("_file".to_string());
+            } else {
+                row.push(path.to_string());
+            }
+        }
+        row.extend(hash);
+        if header {
+            let _ = self.header_row.set(row.clone()); // Already set by a resumed stream
+        }
+        Ok(())
+    }
+
+    /// Start parsing files in the background, at most `concurrency` at a time.
+    fn start_parallel(&self) -> ParallelFiles {
+        let (sender, receiver) = mpsc::channel(self.concurrency);
+        let files = Arc::clone(&self.files);
+        let dialect = self.dialect.clone();
+        let field_size_limit = self.field_size_limit;
+        let has_header = self.has_header;
+        let concurrency = self.concurrency;
+        let decoder = Arc::clone(&self.decoder);
+        let checksum = self.checksum.clone();
+        // Names are resolved from the first file's rows as they are returned
+        let selected = self.selected().map(<[usize]>::to_vec);
+        let projected = selected.is_some();
+        tokio::spawn(async move {
+            let mut pending = VecDeque::new();
+            let mut next = 0;
+            loop {
+                while pending.len() < concurrency && next < files.len() {
+                    let path = files[next].clone();
+                    let dialect = dialect.clone();
+                    let decoder = Arc::clone(&decoder);
+                    let checksum = checksum.clone();
+                    let selected = selected.clone();
+                    let skip_header = has_header && next > 0;
+                    pending.push_back((
+                        next,
+                        tokio::task::spawn_blocking(move || {
+                            if let Some(verifier) = checksum {
+                                verifier.check(&path)?;
+                            }
+                            parse_file(
+                                &path,
+                                &dialect,
+                                field_size_limit,
+                                skip_header,
+                                &decoder,
+                                selected.as_deref(),
+                            )
+                        }),
+                    ));
+                    next += 1;
+                }
+                // Await files in order so rows are delivered in file order
+                let Some((index, handle)) = pending.pop_front() else {
+                    break;
+                };
+                let result = handle
+                    .await
+                    .map_err(|e| CsvIoError::new_err(format!("Background task failed: {e}")))
+                    .and_then(|rows| rows.map(|rows| (index, rows)));
+                let failed = result.is_err();
+                // Stop when the reader was closed or after reporting an error
+                if sender.send(result).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        ParallelFiles {
+            receiver,
+            rows: Vec::new().into_iter(),
+            file_index: 0,
+            projected,
+        }
+    }
+
+    /// `parse_indexed` for multi-file streams parsed in parallel.
+    async fn next_parallel(&self) -> PyResult<Option<(usize, Vec<String>)>> {
+        let mut guard = self.parallel.lock().await;
+        let state = guard.get_or_insert_with(|| self.start_parallel());
+        loop {
+            if state.rows.len() > 0 {
+                // Lock the counters before taking the row so cancelling can't drop it
+                let mut line_num = self.line_num.lock().await;
+                let mut position = self.position.lock().await;
+                if let Some((mut row, lines)) = state.rows.next() {
+                    self.resolve_columns(*position, &row)?;
+                    *line_num += lines;
+                    let path = &self.files[state.file_index];
+                    self.finish_row(&mut position, &mut row, path, state.projected)?;
+                    self.count(1, 0)?;
+                    return Ok(Some((state.file_index, row)));
+                }
+            }
+            match state.receiver.recv().await {
+                Some(Ok((index, rows))) => {
+                    state.file_index = index;
+                    state.rows = rows.into_iter();
+                    // Rows of parallel files arrive a whole file at a time
+                    let size = Progress::total_size(&self.files[index..=index]);
+                    self.count(0, size.unwrap_or(0))?;
+                }
+                Some(Err(e)) => return Err(e),
+                None => {
+                    self.finish_progress()?;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    /// Whether byte offsets are tracked: files parsed in parallel are read whole, and
+    /// replacing invalid UTF-8 changes the length of the text.
+    pub(crate) fn tracks_offset(&self) -> bool {
+        !(self.concurrency > 1 && self.files.len() > 1)
+            && self.decoder.mode() == Utf8Mode::Strict
+            && self.pipe.is_none()
+    }
+
+    /// Close the `SourceAdapter` the stream reads from, if it is one.
+    pub(crate) async fn close_adapter(&self) -> PyResult<()> {
+        crate::close_adapter(&self.file_handle, &self.event_loop).await
+    }
+
+    /// Drop the open file and any buffered data.
+    pub(crate) async fn close(&self) {
+        // Let rows being parsed ahead finish first, so the file is not reopened
+        let _running = match &self.prefetch {
+            Some(prefetch) => Some(prefetch.running.lock().await),
+            None => None,
+        };
+        self.file.lock().await.take();
+        self.parallel.lock().await.take();
+        self.buffer.lock().await.clear();
+        *self.buffer_start.lock().await = 0;
+        self.release_lock().await;
+    }
+}
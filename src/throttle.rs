@@ -0,0 +1,55 @@
+//! Write rate limiting (`max_bytes_per_sec=` on `Writer`).
+//!
+//! Bulk exports to shared storage can starve other users of an NFS mount or hit an
+//! object store's request limits. A `Throttle` paces the bytes handed to the
+//! destination: each chunk is written no sooner than the previous chunks' bytes allow
+//! at the configured rate, and chunks are cut into slices of a tenth of a second's
+//! budget so a large `write_size` doesn't turn into long bursts and long pauses.
+
+use pyo3::prelude::*;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Most of a second's budget written in one go.
+const SLICES_PER_SECOND: f64 = 10.0;
+
+#[derive(Debug)]
+pub(crate) struct Throttle {
+    bytes_per_sec: f64,
+    next: StdMutex<Option<Instant>>, // When the next bytes may be written
+}
+
+impl Throttle {
+    /// Validate a `max_bytes_per_sec` argument; `None` when no limit is set.
+    pub(crate) fn parse(max_bytes_per_sec: Option<f64>) -> PyResult<Option<Self>> {
+        match max_bytes_per_sec {
+            None => Ok(None),
+            Some(rate) if rate.is_finite() && rate > 0.0 => Ok(Some(Throttle {
+                bytes_per_sec: rate,
+                next: StdMutex::new(None),
+            })),
+            Some(_) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "max_bytes_per_sec must be a positive number",
+            )),
+        }
+    }
+
+    /// Largest slice of a chunk to write at once.
+    pub(crate) fn slice_len(&self) -> usize {
+        ((self.bytes_per_sec / SLICES_PER_SECOND) as usize).max(1)
+    }
+
+    /// Wait until `len` more bytes may be written, and reserve the time they take.
+    pub(crate) async fn acquire(&self, len: usize) {
+        let start = {
+            let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            // Time left unused while the writer was idle is not saved up
+            let start = next.map_or(now, |next| next.max(now));
+            *next = Some(start + Duration::from_secs_f64(len as f64 / self.bytes_per_sec));
+            start
+        };
+        tokio::time::sleep_until(start).await;
+    }
+}
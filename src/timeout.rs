@@ -0,0 +1,46 @@
+//! Per-operation time limits (`timeout=` on readers and writers).
+//!
+//! An operation that runs past its limit is dropped, with the same effect as cancelling
+//! it from Python, and `asyncio.TimeoutError` is raised. This keeps a hung mount or a
+//! stalled file handle from blocking a pipeline forever.
+
+use pyo3::prelude::*;
+use std::future::Future;
+use std::time::Duration;
+
+/// Validate a `timeout` argument given in seconds.
+pub(crate) fn parse(timeout: Option<f64>) -> PyResult<Option<Duration>> {
+    match timeout {
+        None => Ok(None),
+        Some(seconds) if seconds.is_finite() && seconds > 0.0 => {
+            Ok(Some(Duration::from_secs_f64(seconds)))
+        }
+        Some(_) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "timeout must be a positive number of seconds",
+        )),
+    }
+}
+
+/// Run `operation`, raising `asyncio.TimeoutError` if it takes longer than `timeout`.
+pub(crate) async fn limit<T>(
+    timeout: Option<Duration>,
+    operation: impl Future<Output = PyResult<T>>,
+) -> PyResult<T> {
+    let Some(timeout) = timeout else {
+        return operation.await;
+    };
+    match tokio::time::timeout(timeout, operation).await {
+        Ok(result) => result,
+        Err(_) => Err(Python::attach(|py| {
+            let message = format!("Operation timed out after {}s", timeout.as_secs_f64());
+            match py
+                .import("asyncio")
+                .and_then(|asyncio| asyncio.getattr("TimeoutError"))
+                .and_then(|error| error.call1((message,)))
+            {
+                Ok(error) => PyErr::from_value(error),
+                Err(e) => e,
+            }
+        })),
+    }
+}
@@ -0,0 +1,174 @@
+//! Streaming row transforms driven by a Python callback.
+//!
+//! Reading, parsing, encoding and writing happen in Rust on a blocking worker thread;
+//! the GIL is only taken to call the user's function, once per row or once per batch.
+
+use crate::errors::CsvIoError;
+use crate::ops::{
+    check_not_input, create_writer, csv_err, open_reader, read_header, simple_dialect,
+    spawn_blocking_py,
+};
+use crate::progress::Progress;
+use crate::{validate_path, DialectConfig};
+use csv::StringRecord;
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+/// Convert a row returned by the callback into fields (None becomes an empty field,
+/// other non-string values are converted with `str()`).
+fn row_to_fields(row: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    row.try_iter()?
+        .map(|value| {
+            let value = value?;
+            if value.is_none() {
+                Ok(String::new())
+            } else if let Ok(s) = value.extract::<String>() {
+                Ok(s)
+            } else {
+                Ok(value.str()?.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Apply `func` to the rows of `src` and write the results to `dst`.
+///
+/// Returns the number of data rows written.
+fn transform_file(
+    src: &str,
+    dst: &str,
+    func: &Py<PyAny>,
+    batch_size: Option<usize>,
+    header: bool,
+    dialect: &DialectConfig,
+    progress: Option<&Progress>,
+) -> PyResult<usize> {
+    check_not_input(dst, &[src])?;
+    let mut reader = open_reader(src, dialect)?;
+    let mut writer = create_writer(dst, dialect)?;
+    if header {
+        if let Some(header) = read_header(&mut reader, src)? {
+            writer.write_record(&header).map_err(|e| csv_err(dst, e))?;
+        }
+    }
+
+    let batched = batch_size.is_some();
+    let batch_size = batch_size.unwrap_or(1).max(1);
+    let mut batch: Vec<StringRecord> = Vec::with_capacity(batch_size);
+    let mut written = 0usize;
+    let mut done = false;
+    while !done {
+        batch.clear();
+        let mut record = StringRecord::new();
+        while batch.len() < batch_size {
+            match reader.read_record(&mut record) {
+                Ok(true) => batch.push(record.clone()),
+                Ok(false) => {
+                    done = true;
+                    break;
+                }
+                Err(e) => return Err(csv_err(src, e)),
+            }
+        }
+        if batch.is_empty() {
+            break;
+        }
+
+        let output: Vec<Vec<String>> = Python::attach(|py| -> PyResult<_> {
+            let to_list =
+                |record: &StringRecord| PyList::new(py, record.iter().collect::<Vec<_>>());
+            if !batched {
+                let result = func.call1(py, (to_list(&batch[0])?,))?;
+                let result = result.bind(py);
+                if result.is_none() {
+                    return Ok(Vec::new());
+                }
+                return Ok(vec![row_to_fields(result)?]);
+            }
+            let rows = PyList::empty(py);
+            for record in &batch {
+                rows.append(to_list(record)?)?;
+            }
+            let result = func.call1(py, (rows,))?;
+            let result = result.bind(py);
+            if result.is_none() {
+                return Ok(Vec::new());
+            }
+            result
+                .try_iter()?
+                .filter(|row| row.as_ref().map_or(true, |row| !row.is_none()))
+                .map(|row| row_to_fields(&row?))
+                .collect()
+        })?;
+
+        for row in &output {
+            writer.write_record(row).map_err(|e| csv_err(dst, e))?;
+        }
+        written += output.len();
+        if let Some(progress) = progress {
+            progress.update(reader.position().byte(), written)?;
+        }
+    }
+
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+    if let Some(progress) = progress {
+        progress.finish()?;
+    }
+    Ok(written)
+}
+
+/// Stream a CSV file through a Python function.
+///
+/// # Arguments
+/// * `src` - Input CSV file
+/// * `dst` - Output CSV file (created or truncated)
+/// * `fn` - Called with each row (a list of strings); returns the new row or None to drop it.
+///   With `batch_size`, called with a list of rows and returns a list of rows.
+/// * `batch_size` - Rows per callback invocation (default: one row per call)
+/// * `header` - Copy the first row unchanged instead of passing it to `fn` (default: True)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+/// * `progress` - Called as `progress(bytes_read, bytes_total, rows_written)` every
+///   `progress_interval` rows and when done (default: None)
+/// * `progress_interval` - Rows between progress calls (default: 10000)
+///
+/// Returns the number of data rows written.
+#[pyfunction]
+#[pyo3(signature = (src, dst, r#fn, batch_size = None, header = true, delimiter = None, quotechar = None, progress = None, progress_interval = None))]
+#[allow(clippy::too_many_arguments)] // Required for Python API compatibility
+pub(crate) fn transform(
+    py: Python<'_>,
+    src: String,
+    dst: String,
+    r#fn: Py<PyAny>,
+    batch_size: Option<usize>,
+    header: bool,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+    progress: Option<Py<PyAny>>,
+    progress_interval: Option<usize>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&src)?;
+    validate_path(&dst)?;
+    if !r#fn.bind(py).is_callable() {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            "fn must be callable",
+        ));
+    }
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    let bytes_total = Progress::total_size(std::slice::from_ref(&src));
+    let progress = Progress::from_args(py, progress, progress_interval, bytes_total)?;
+    spawn_blocking_py(py, move || {
+        transform_file(
+            &src,
+            &dst,
+            &r#fn,
+            batch_size,
+            header,
+            &dialect,
+            progress.as_ref(),
+        )
+    })
+}
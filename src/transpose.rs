@@ -0,0 +1,88 @@
+//! Transposition of small, matrix-style CSV files (`rapcsv.transpose()`).
+//!
+//! The last input row holds the first field of every output row, so the whole file is
+//! buffered. A cell count taken while reading stops files that would not fit before
+//! they are held in memory.
+
+use crate::errors::CsvIoError;
+use crate::ops::{create_writer, csv_err, open_reader, simple_dialect, spawn_blocking_py};
+use crate::{validate_path, DialectConfig};
+use csv::StringRecord;
+use pyo3::exceptions::{PyMemoryError, PyValueError};
+use pyo3::prelude::*;
+
+/// Default limit on the number of cells buffered.
+const DEFAULT_MAX_CELLS: usize = 10_000_000;
+
+/// Write the columns of `src` as the rows of `dst`. Rows shorter than the longest
+/// are padded with empty fields. Returns the number of rows written.
+fn transpose_file(
+    src: &str,
+    dst: &str,
+    max_cells: usize,
+    dialect: &DialectConfig,
+) -> PyResult<usize> {
+    let mut reader = open_reader(src, dialect)?;
+    let mut rows: Vec<StringRecord> = Vec::new();
+    let mut cells = 0usize;
+    let mut width = 0usize;
+    let mut record = StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(src, e)),
+        }
+        cells += record.len();
+        if cells > max_cells {
+            return Err(PyMemoryError::new_err(format!(
+                "'{src}' has more than max_cells={max_cells} cells; raise max_cells to \
+                 transpose it"
+            )));
+        }
+        width = width.max(record.len());
+        rows.push(record.clone());
+    }
+
+    let mut writer = create_writer(dst, dialect)?;
+    let mut output = Vec::with_capacity(rows.len());
+    for column in 0..width {
+        output.clear();
+        output.extend(rows.iter().map(|row| row.get(column).unwrap_or("")));
+        writer.write_record(&output).map_err(|e| csv_err(dst, e))?;
+    }
+    writer
+        .flush()
+        .map_err(|e| CsvIoError::new_err(format!("Failed to flush file '{dst}': {e}")))?;
+    Ok(width)
+}
+
+/// Swap the rows and columns of a CSV file.
+///
+/// # Arguments
+/// * `src` - Input CSV file (the header, if any, becomes the first column)
+/// * `dst` - Output CSV file (created or truncated)
+/// * `max_cells` - Most fields to buffer before raising MemoryError (default: 10000000)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+///
+/// Returns the number of rows written, one per input column.
+#[pyfunction]
+#[pyo3(signature = (src, dst, max_cells = None, delimiter = None, quotechar = None))]
+pub(crate) fn transpose(
+    py: Python<'_>,
+    src: String,
+    dst: String,
+    max_cells: Option<usize>,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&src)?;
+    validate_path(&dst)?;
+    let max_cells = match max_cells {
+        Some(0) => return Err(PyValueError::new_err("max_cells must be at least 1")),
+        max_cells => max_cells.unwrap_or(DEFAULT_MAX_CELLS),
+    };
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    spawn_blocking_py(py, move || transpose_file(&src, &dst, max_cells, &dialect))
+}
@@ -0,0 +1,618 @@
+//! Typed reads (`schema=` and `converters=` on `Reader`).
+//!
+//! A schema maps columns, by header name or position, to a type. Fields of those
+//! columns are converted in Rust and handed to Python as `int`, `float`, `bool`,
+//! `datetime.date` or `datetime.datetime`; empty fields become `None`. Dates and
+//! datetimes are parsed with chrono against a list of formats, first match wins.
+//! Booleans are matched against lists of true and false strings, which
+//! `true_values=` and `false_values=` replace for every `bool` column or for the
+//! columns they name. Numbers may be written with a thousands separator and a decimal
+//! comma (`thousands=` and `decimal=`), as in `1.234,56`, and surrounded by currency
+//! or percent symbols (`number_symbols=`), with `12.5%` optionally read as `0.125`
+//! (`percent_scale=`).
+//!
+//! Converters are Python callables applied to a column's values after that. They run
+//! while the rows of a read are turned into Python objects, so a batch from
+//! `read_rows()` or `read_all()` takes the GIL once rather than once per cell.
+
+use crate::errors::{ErrorContext, SchemaError};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use pyo3::conversion::IntoPyObjectExt;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Formats tried for `date` columns unless `date_formats` is given.
+const DEFAULT_DATE_FORMATS: &[&str] = &["%Y-%m-%d"];
+
+/// Formats tried for `datetime` columns unless `datetime_formats` is given (ISO 8601,
+/// with `T` or a space, optional fractional seconds and optional UTC offset). RFC 3339
+/// is tried first, which also accepts `Z` for UTC.
+const DEFAULT_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f%:z",
+    "%Y-%m-%d %H:%M:%S%.f%:z",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+];
+
+/// Strings read as True in `bool` columns unless `true_values` is given.
+const DEFAULT_TRUE_VALUES: &[&str] = &["true", "1", "yes"];
+
+/// Strings read as False in `bool` columns unless `false_values` is given.
+const DEFAULT_FALSE_VALUES: &[&str] = &["false", "0", "no"];
+
+/// Python type a column's fields are converted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ColumnType {
+    Str,
+    Int,
+    Float,
+    Bool,
+    Date,
+    DateTime,
+}
+
+impl ColumnType {
+    fn parse(name: &str, column: &str) -> PyResult<Self> {
+        match name {
+            "str" | "string" => Ok(ColumnType::Str),
+            "int" | "integer" => Ok(ColumnType::Int),
+            "float" | "number" => Ok(ColumnType::Float),
+            "bool" | "boolean" => Ok(ColumnType::Bool),
+            "date" => Ok(ColumnType::Date),
+            "datetime" => Ok(ColumnType::DateTime),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown type '{other}' for column '{column}' \
+                (expected 'str', 'int', 'float', 'bool', 'date' or 'datetime')"
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ColumnType::Str => "str",
+            ColumnType::Int => "int",
+            ColumnType::Float => "float",
+            ColumnType::Bool => "bool",
+            ColumnType::Date => "date",
+            ColumnType::DateTime => "datetime",
+        }
+    }
+}
+
+/// A schema key: a column name from the header, or a 0-based position.
+#[derive(Clone, Debug)]
+enum ColumnKey {
+    Name(String),
+    Index(usize),
+}
+
+impl ColumnKey {
+    fn from_python(key: &Bound<'_, PyAny>, option: &str) -> PyResult<Self> {
+        match key.cast::<PyString>() {
+            Ok(name) => Ok(ColumnKey::Name(name.to_str()?.to_string())),
+            Err(_) => Ok(ColumnKey::Index(key.extract().map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                    "{option} keys must be column names (str) or positions (int)"
+                ))
+            })?)),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            ColumnKey::Name(name) => name.clone(),
+            ColumnKey::Index(i) => i.to_string(),
+        }
+    }
+
+    /// Position of the column, looking names up in `header`.
+    fn position(&self, header: Option<&[String]>) -> PyResult<usize> {
+        match self {
+            ColumnKey::Index(i) => Ok(*i),
+            ColumnKey::Name(name) => header
+                .and_then(|header| header.iter().position(|h| h == name))
+                .ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "column '{name}' is not in the header"
+                    ))
+                }),
+        }
+    }
+}
+
+/// A `true_values` or `false_values` argument.
+#[derive(Debug, Default)]
+enum BoolStrings {
+    /// The built-in strings.
+    #[default]
+    Default,
+    /// The same strings for every `bool` column.
+    All(Vec<String>),
+    /// Strings for the named columns; the others keep the built-in ones.
+    Columns(Vec<(ColumnKey, Vec<String>)>),
+}
+
+impl BoolStrings {
+    /// Parse a list of strings, or a dict of column name or position to such a list.
+    fn from_python(value: Option<&Bound<'_, PyAny>>, option: &str) -> PyResult<Self> {
+        let strings = |value: &Bound<'_, PyAny>| {
+            if value.is_instance_of::<PyString>() {
+                return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                    "{option} must be a list of strings, not a string"
+                )));
+            }
+            value.extract::<Vec<String>>()
+        };
+        let Some(value) = value else {
+            return Ok(BoolStrings::Default);
+        };
+        match value.cast::<PyDict>() {
+            Ok(columns) => columns
+                .iter()
+                .map(|(key, value)| Ok((ColumnKey::from_python(&key, option)?, strings(&value)?)))
+                .collect::<PyResult<_>>()
+                .map(BoolStrings::Columns),
+            Err(_) => strings(value).map(BoolStrings::All),
+        }
+    }
+
+    /// The strings for every column not named on its own.
+    fn all(&self, default: &[&str]) -> Vec<String> {
+        match self {
+            BoolStrings::All(strings) => strings.clone(),
+            _ => default.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Columns named on their own.
+    fn keys(&self) -> &[(ColumnKey, Vec<String>)] {
+        match self {
+            BoolStrings::Columns(columns) => columns,
+            _ => &[],
+        }
+    }
+}
+
+/// Strings a `bool` column reads as True and False.
+#[derive(Clone, Debug)]
+struct BoolValues {
+    true_values: HashSet<String>,
+    false_values: HashSet<String>,
+    case_sensitive: bool,
+}
+
+impl BoolValues {
+    fn new(
+        true_values: Vec<String>,
+        false_values: Vec<String>,
+        case_sensitive: bool,
+    ) -> PyResult<Self> {
+        let fold = |values: Vec<String>| -> HashSet<String> {
+            values
+                .into_iter()
+                .map(|v| if case_sensitive { v } else { v.to_lowercase() })
+                .collect()
+        };
+        let (true_values, false_values) = (fold(true_values), fold(false_values));
+        if let Some(both) = true_values.intersection(&false_values).next() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "'{both}' is in both true_values and false_values"
+            )));
+        }
+        Ok(BoolValues {
+            true_values,
+            false_values,
+            case_sensitive,
+        })
+    }
+
+    fn parse(&self, text: &str) -> Option<bool> {
+        let folded;
+        let text = if self.case_sensitive {
+            text
+        } else {
+            folded = text.to_lowercase();
+            &folded
+        };
+        if self.true_values.contains(text) {
+            Some(true)
+        } else if self.false_values.contains(text) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// How `int` and `float` columns write numbers (`thousands=`, `decimal=`,
+/// `number_symbols=` and `percent_scale=`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct NumberFormat {
+    thousands: Option<char>,
+    decimal: char,
+    symbols: Vec<String>, // Longest first, so "US$" is tried before "$"
+    percent_scale: bool,  // A trailing `%` divides the value by 100
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            thousands: None,
+            decimal: '.',
+            symbols: Vec::new(),
+            percent_scale: false,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// `text` without a symbol before and after the number, keeping a sign written
+    /// ahead of the symbol (`-$5`). The flag tells whether a `%` to scale by was removed.
+    fn strip_symbols<'a>(&self, text: &'a str) -> (std::borrow::Cow<'a, str>, bool) {
+        if self.symbols.is_empty() && !self.percent_scale {
+            return (std::borrow::Cow::Borrowed(text), false);
+        }
+        let (sign, mut rest) = match text.strip_prefix(['-', '+']) {
+            Some(rest) => (&text[..1], rest),
+            None => ("", text),
+        };
+        let mut percent = false;
+        if self.percent_scale {
+            if let Some(number) = rest.strip_suffix('%') {
+                rest = number.trim_end();
+                percent = true;
+            }
+        }
+        if let Some(number) = self
+            .symbols
+            .iter()
+            .find_map(|s| rest.strip_prefix(s.as_str()))
+        {
+            rest = number.trim_start();
+        }
+        if let Some(number) = self
+            .symbols
+            .iter()
+            .find_map(|s| rest.strip_suffix(s.as_str()))
+        {
+            rest = number.trim_end();
+        }
+        (std::borrow::Cow::Owned(format!("{sign}{rest}")), percent)
+    }
+
+    /// `text` as Rust parses numbers: without symbols or thousands separators, with a
+    /// `.` for the decimal point. Borrowed when written that way already. The flag
+    /// tells whether the value is a percentage to divide by 100.
+    fn plain<'a>(&self, text: &'a str) -> (std::borrow::Cow<'a, str>, bool) {
+        let (text, percent) = self.strip_symbols(text);
+        if self.thousands.is_none() && self.decimal == '.' {
+            return (text, percent);
+        }
+        let mut plain = String::with_capacity(text.len());
+        for c in text.chars() {
+            if Some(c) == self.thousands {
+                continue;
+            }
+            // A `.` that isn't the decimal point must not pass for one
+            plain.push(match c {
+                c if c == self.decimal => '.',
+                '.' => ',',
+                c => c,
+            });
+        }
+        (std::borrow::Cow::Owned(plain), percent)
+    }
+}
+
+/// Column types and converters as given to `Reader(schema=..., converters=...)`.
+#[derive(Debug)]
+pub(crate) struct Schema {
+    columns: Vec<(ColumnKey, ColumnType)>,
+    callbacks: Vec<(ColumnKey, Arc<Py<PyAny>>)>,
+    date_formats: Vec<String>,
+    datetime_formats: Vec<String>,
+    rfc3339: bool, // Try RFC 3339 before `datetime_formats` (the default formats)
+    true_values: BoolStrings,
+    false_values: BoolStrings,
+    bool_case_sensitive: bool,
+    numbers: NumberFormat,
+}
+
+impl Schema {
+    pub(crate) fn from_python(
+        schema: Option<&Bound<'_, PyDict>>,
+        converters: Option<&Bound<'_, PyDict>>,
+        date_formats: Option<Vec<String>>,
+        datetime_formats: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let mut columns = Vec::new();
+        for (key, value) in schema.iter().flat_map(|schema| schema.iter()) {
+            let key = ColumnKey::from_python(&key, "schema")?;
+            let column_type = ColumnType::parse(&value.extract::<String>()?, &key.label())?;
+            columns.push((key, column_type));
+        }
+        let mut callbacks = Vec::new();
+        for (key, value) in converters.iter().flat_map(|converters| converters.iter()) {
+            let key = ColumnKey::from_python(&key, "converters")?;
+            if !value.is_callable() {
+                return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                    "converter for column '{}' is not callable",
+                    key.label()
+                )));
+            }
+            callbacks.push((key, Arc::new(value.unbind())));
+        }
+        let formats = |given: Option<Vec<String>>, default: &[&str]| {
+            given.unwrap_or_else(|| default.iter().map(|f| f.to_string()).collect())
+        };
+        Ok(Schema {
+            columns,
+            callbacks,
+            rfc3339: datetime_formats.is_none(),
+            date_formats: formats(date_formats, DEFAULT_DATE_FORMATS),
+            datetime_formats: formats(datetime_formats, DEFAULT_DATETIME_FORMATS),
+            true_values: BoolStrings::Default,
+            false_values: BoolStrings::Default,
+            bool_case_sensitive: false,
+            numbers: NumberFormat::default(),
+        })
+    }
+
+    /// Read `int` and `float` columns with `thousands` separating digit groups and
+    /// `decimal` as the decimal point, each a single character.
+    pub(crate) fn number_format(
+        mut self,
+        thousands: Option<&str>,
+        decimal: &str,
+    ) -> PyResult<Self> {
+        let single = |value: &str, option: &str| {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if !c.is_ascii_digit() && c != '-' && c != '+' => Ok(c),
+                _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "{option} must be a single character other than a digit or sign, got '{value}'"
+                ))),
+            }
+        };
+        let thousands = thousands.map(|t| single(t, "thousands")).transpose()?;
+        let decimal = single(decimal, "decimal")?;
+        if thousands == Some(decimal) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "thousands and decimal must be different characters",
+            ));
+        }
+        self.numbers.thousands = thousands;
+        self.numbers.decimal = decimal;
+        Ok(self)
+    }
+
+    /// Strip `symbols` (such as "$" or "%") from before and after the numbers of `int`
+    /// and `float` columns and, with `percent_scale`, read `float` values ending in
+    /// "%" as a fraction of 100.
+    pub(crate) fn number_symbols(
+        mut self,
+        symbols: Option<Vec<String>>,
+        percent_scale: bool,
+    ) -> PyResult<Self> {
+        let mut symbols = symbols.unwrap_or_default();
+        if symbols.iter().any(String::is_empty) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "number_symbols must not hold an empty string",
+            ));
+        }
+        symbols.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        self.numbers.symbols = symbols;
+        self.numbers.percent_scale = percent_scale;
+        Ok(self)
+    }
+
+    /// Read `bool` columns with the strings of `true_values` and `false_values`, each a
+    /// list for every column or a dict of lists by column, matched regardless of case
+    /// unless `case_sensitive`.
+    pub(crate) fn bool_values(
+        mut self,
+        true_values: Option<&Bound<'_, PyAny>>,
+        false_values: Option<&Bound<'_, PyAny>>,
+        case_sensitive: bool,
+    ) -> PyResult<Self> {
+        self.true_values = BoolStrings::from_python(true_values, "true_values")?;
+        self.false_values = BoolStrings::from_python(false_values, "false_values")?;
+        self.bool_case_sensitive = case_sensitive;
+        Ok(self)
+    }
+
+    /// Whether columns are named, so the header must be read to find them.
+    pub(crate) fn needs_header(&self) -> bool {
+        let keys = self.columns.iter().map(|(key, _)| key);
+        keys.chain(self.callbacks.iter().map(|(key, _)| key))
+            .chain(self.true_values.keys().iter().map(|(key, _)| key))
+            .chain(self.false_values.keys().iter().map(|(key, _)| key))
+            .any(|key| matches!(key, ColumnKey::Name(_)))
+    }
+
+    /// Resolve `true_values` and `false_values` to the strings of every `bool` column,
+    /// and those of the columns named on their own.
+    fn resolve_bools(
+        &self,
+        header: Option<&[String]>,
+        types: &[ColumnType],
+    ) -> PyResult<(BoolValues, Vec<Option<BoolValues>>)> {
+        let case_sensitive = self.bool_case_sensitive;
+        let all_true = self.true_values.all(DEFAULT_TRUE_VALUES);
+        let all_false = self.false_values.all(DEFAULT_FALSE_VALUES);
+        // The strings `strings` gives column `i` on its own, if any
+        let own = |strings: &BoolStrings, i: usize| -> PyResult<Option<Vec<String>>> {
+            for (key, values) in strings.keys() {
+                if key.position(header)? == i {
+                    return Ok(Some(values.clone()));
+                }
+            }
+            Ok(None)
+        };
+        let mut columns = vec![None; types.len()];
+        for (option, strings) in [
+            ("true_values", &self.true_values),
+            ("false_values", &self.false_values),
+        ] {
+            for (key, _) in strings.keys() {
+                let i = key.position(header)?;
+                if types.get(i) != Some(&ColumnType::Bool) {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "{option} names column '{}', which the schema does not type as bool",
+                        key.label()
+                    )));
+                }
+                if columns[i].is_none() {
+                    columns[i] = Some(BoolValues::new(
+                        own(&self.true_values, i)?.unwrap_or_else(|| all_true.clone()),
+                        own(&self.false_values, i)?.unwrap_or_else(|| all_false.clone()),
+                        case_sensitive,
+                    )?);
+                }
+            }
+        }
+        Ok((
+            BoolValues::new(all_true, all_false, case_sensitive)?,
+            columns,
+        ))
+    }
+
+    /// Resolve the schema against `header` (None when every key is a position).
+    pub(crate) fn converter(&self, header: Option<&[String]>) -> PyResult<Converter> {
+        let mut types = Vec::new();
+        for (key, column_type) in &self.columns {
+            let i = key.position(header)?;
+            if types.len() <= i {
+                types.resize(i + 1, ColumnType::Str);
+            }
+            types[i] = *column_type;
+        }
+        let mut callbacks = Vec::new();
+        for (key, callback) in &self.callbacks {
+            let i = key.position(header)?;
+            if callbacks.len() <= i {
+                callbacks.resize(i + 1, None);
+            }
+            callbacks[i] = Some(callback.clone());
+        }
+        let mut names = header.map(<[String]>::to_vec).unwrap_or_default();
+        if names.len() < types.len() {
+            names.extend((names.len()..types.len()).map(|i| i.to_string()));
+        }
+        let (bools, column_bools) = self.resolve_bools(header, &types)?;
+        Ok(Converter {
+            types,
+            callbacks,
+            names,
+            bools,
+            column_bools,
+            date_formats: self.date_formats.clone(),
+            datetime_formats: self.datetime_formats.clone(),
+            rfc3339: self.rfc3339,
+            numbers: self.numbers.clone(),
+        })
+    }
+}
+
+/// A schema resolved to column positions.
+#[derive(Debug)]
+pub(crate) struct Converter {
+    types: Vec<ColumnType>,
+    callbacks: Vec<Option<Arc<Py<PyAny>>>>,
+    names: Vec<String>, // For error messages
+    bools: BoolValues,
+    column_bools: Vec<Option<BoolValues>>, // Columns with strings of their own
+    date_formats: Vec<String>,
+    datetime_formats: Vec<String>,
+    rfc3339: bool,
+    numbers: NumberFormat,
+}
+
+impl Converter {
+    fn date(&self, field: &str) -> Option<NaiveDate> {
+        self.date_formats
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(field, format).ok())
+    }
+
+    /// Parse an aware datetime where the format has an offset, else a naive one.
+    fn datetime(&self, py: Python<'_>, field: &str) -> Option<PyResult<Py<PyAny>>> {
+        if self.rfc3339 {
+            if let Ok(dt) = DateTime::parse_from_rfc3339(field) {
+                return Some(dt.into_py_any(py));
+            }
+        }
+        self.datetime_formats.iter().find_map(|format| {
+            if let Ok(dt) = DateTime::parse_from_str(field, format) {
+                return Some(dt.into_py_any(py));
+            }
+            NaiveDateTime::parse_from_str(field, format)
+                .ok()
+                .map(|dt| dt.into_py_any(py))
+        })
+    }
+
+    /// Convert field `i` to its column type, then apply the column's converter.
+    fn value(&self, py: Python<'_>, i: usize, field: String) -> PyResult<Py<PyAny>> {
+        let value = self.typed_value(py, i, field)?;
+        match self.callbacks.get(i) {
+            Some(Some(callback)) => callback.call1(py, (value,)),
+            _ => Ok(value),
+        }
+    }
+
+    fn typed_value(&self, py: Python<'_>, i: usize, field: String) -> PyResult<Py<PyAny>> {
+        let column_type = self.types.get(i).copied().unwrap_or(ColumnType::Str);
+        if column_type == ColumnType::Str {
+            return field.into_py_any(py);
+        }
+        if field.is_empty() {
+            return Ok(py.None());
+        }
+        let text = field.trim();
+        let value = match column_type {
+            ColumnType::Str => unreachable!(),
+            ColumnType::Int => match self.numbers.plain(text) {
+                (number, false) => number.parse::<i64>().ok().map(|n| n.into_py_any(py)),
+                (_, true) => None, // A percentage is no whole number
+            },
+            ColumnType::Float => {
+                let (number, percent) = self.numbers.plain(text);
+                let scale = if percent { 100.0 } else { 1.0 };
+                number
+                    .parse::<f64>()
+                    .ok()
+                    .map(|n| (n / scale).into_py_any(py))
+            }
+            ColumnType::Bool => match self.column_bools.get(i) {
+                Some(Some(bools)) => bools.parse(text),
+                _ => self.bools.parse(text),
+            }
+            .map(|value| value.into_py_any(py)),
+            ColumnType::Date => self.date(text).map(|date| date.into_py_any(py)),
+            ColumnType::DateTime => self.datetime(py, text),
+        };
+        value.unwrap_or_else(|| {
+            let context = ErrorContext {
+                column: Some(i),
+                field: Some(field.clone()),
+                ..ErrorContext::default()
+            };
+            Err(context.attach(SchemaError::new_err(format!(
+                "Cannot convert '{field}' in column '{}' to {}",
+                self.names[i],
+                column_type.name()
+            ))))
+        })
+    }
+
+    /// Convert the fields of one row.
+    pub(crate) fn convert(&self, py: Python<'_>, row: Vec<String>) -> PyResult<Vec<Py<PyAny>>> {
+        row.into_iter()
+            .enumerate()
+            .map(|(i, field)| self.value(py, i, field))
+            .collect()
+    }
+}
@@ -0,0 +1,347 @@
+//! Optional io_uring backend for local file reads and writes (Linux, `io-uring` cargo
+//! feature).
+//!
+//! tokio-uring drives its ring from its own current-thread runtime, so file operations
+//! are sent to a dedicated thread that owns the ring and results come back over oneshot
+//! channels. `read_all()` reads whole files there in one go. Files opened for streaming
+//! reads and for writing are handed to that thread too, and a `RingFile` sends each of
+//! their reads and writes to it; pipes and other files that aren't regular stay on
+//! tokio. When the feature is off, the platform is not Linux, or the kernel refuses to
+//! create a ring (old kernels, seccomp-restricted containers), everything falls back to
+//! `tokio::fs`.
+
+use crate::paths::{self, FilePath};
+use crate::socket::Conn;
+use pyo3::prelude::*;
+use std::io;
+use tokio::fs::File;
+
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+pub(crate) use ring::RingFile;
+
+/// Read the whole file at `path`.
+pub(crate) async fn read_file(path: &FilePath) -> io::Result<Vec<u8>> {
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    if let Some(result) = ring::read_file(path).await {
+        return result;
+    }
+    tokio::fs::read(paths::native(path)).await
+}
+
+/// The `Conn` to stream a file just opened through: its reads and writes go through
+/// the ring when it is available.
+pub(crate) async fn conn(file: File) -> Conn {
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    let file = match RingFile::adopt(file).await {
+        Ok(ring) => return Conn::Ring(ring),
+        Err(file) => file,
+    };
+    file.into()
+}
+
+/// Name of the backend used for local file reads and writes: `"io_uring"` or `"tokio"`.
+pub(crate) fn backend_name() -> &'static str {
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    if ring::available() {
+        return "io_uring";
+    }
+    "tokio"
+}
+
+/// Report which IO backend this build uses for local file reads and writes.
+///
+/// Returns `"io_uring"` when rapcsv was built with the `io-uring` feature and the kernel
+/// supports it, otherwise `"tokio"`.
+#[pyfunction]
+pub(crate) fn io_backend() -> &'static str {
+    backend_name()
+}
+
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+mod ring {
+    use crate::paths::FilePath;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::future::Future;
+    use std::io;
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::OnceLock;
+    use std::task::{ready, Context, Poll};
+    use tokio::fs::File;
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+    use tokio::sync::{mpsc, oneshot};
+
+    type Job = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()>>> + Send>;
+
+    /// Read size per io_uring submission.
+    const READ_SIZE: usize = 1 << 20;
+
+    static RING: OnceLock<Option<mpsc::UnboundedSender<Job>>> = OnceLock::new();
+
+    /// Ids given to the files handed to the ring thread.
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    thread_local! {
+        /// The ring thread's files, by id.
+        static FILES: RefCell<HashMap<u64, Rc<tokio_uring::fs::File>>> =
+            RefCell::new(HashMap::new());
+    }
+
+    /// Start the ring thread on first use; `None` if io_uring is unavailable.
+    fn ring() -> Option<&'static mpsc::UnboundedSender<Job>> {
+        RING.get_or_init(|| {
+            let (sender, mut receiver) = mpsc::unbounded_channel::<Job>();
+            let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+            std::thread::Builder::new()
+                .name("rapcsv-io-uring".to_string())
+                .spawn(move || {
+                    // tokio_uring::start panics if the ring cannot be created; the dropped
+                    // ready_tx then tells the caller to fall back
+                    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        tokio_uring::start(async move {
+                            let _ = ready_tx.send(());
+                            while let Some(job) = receiver.recv().await {
+                                tokio_uring::spawn(job());
+                            }
+                        })
+                    }));
+                })
+                .ok()?;
+            ready_rx.recv().ok().map(|()| sender)
+        })
+        .as_ref()
+    }
+
+    pub(super) fn available() -> bool {
+        ring().is_some()
+    }
+
+    /// Read `path` on the ring thread; `None` if io_uring is unavailable.
+    pub(super) async fn read_file(path: &FilePath) -> Option<io::Result<Vec<u8>>> {
+        let sender = ring()?;
+        let path = path.clone();
+        let (result_tx, result_rx) = oneshot::channel();
+        let job: Job = Box::new(move || {
+            Box::pin(async move {
+                let _ = result_tx.send(read_all(&path).await);
+            })
+        });
+        sender.send(job).ok()?;
+        result_rx.await.ok()
+    }
+
+    async fn read_all(path: &FilePath) -> io::Result<Vec<u8>> {
+        let file = tokio_uring::fs::File::open(crate::paths::native(path)).await?;
+        let mut data = Vec::new();
+        let mut buf = vec![0u8; READ_SIZE];
+        loop {
+            let (result, returned) = file.read_at(buf, data.len() as u64).await;
+            buf = returned;
+            let n = result?;
+            if n == 0 {
+                break;
+            }
+            data.extend_from_slice(&buf[..n]);
+        }
+        file.close().await?;
+        Ok(data)
+    }
+
+    /// Run `job` on the ring thread. Jobs start in the order they are sent: each is
+    /// called on the ring thread when received, and the future it returns is spawned.
+    fn send(job: impl FnOnce() -> Pin<Box<dyn Future<Output = ()>>> + Send + 'static) -> bool {
+        ring().is_some_and(|sender| sender.send(Box::new(job)).is_ok())
+    }
+
+    fn stopped() -> io::Error {
+        io::Error::other("io_uring thread stopped")
+    }
+
+    /// The file with `id` on the ring thread.
+    fn file(id: u64) -> io::Result<Rc<tokio_uring::fs::File>> {
+        FILES
+            .with(|files| files.borrow().get(&id).cloned())
+            .ok_or_else(stopped)
+    }
+
+    /// An operation sent to the ring and not yet finished.
+    enum Pending {
+        Read(oneshot::Receiver<io::Result<Vec<u8>>>),
+        Write(oneshot::Receiver<io::Result<usize>>),
+    }
+
+    /// A regular file whose reads and writes run on the ring thread, at its own offset.
+    /// A file is either read or written, so one offset follows both: each read or write
+    /// that finishes moves it past the bytes it covered. Files opened for appending
+    /// start at 0, which the kernel ignores for them, so they still write at the end.
+    pub(crate) struct RingFile {
+        id: u64,
+        file: std::fs::File, // The same open file, for metadata
+        offset: u64,         // Where the next read or write starts
+        unread: Vec<u8>,     // Read but not yet returned, when a read came back larger
+        pending: Option<Pending>,
+    }
+
+    impl RingFile {
+        /// Hand `file` to the ring thread, or give it back if it can't be: io_uring is
+        /// unavailable or `file` isn't a regular file.
+        pub(crate) async fn adopt(file: File) -> Result<Self, File> {
+            if ring().is_none() {
+                return Err(file);
+            }
+            let mut file = file.into_std().await;
+            let regular = file.metadata().is_ok_and(|metadata| metadata.is_file());
+            let offset = std::io::Seek::stream_position(&mut file);
+            let (true, Ok(offset), Ok(duplicate)) = (regular, offset, file.try_clone()) else {
+                return Err(File::from_std(file));
+            };
+            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+            let adopted = send(move || {
+                let duplicate = Rc::new(tokio_uring::fs::File::from_std(duplicate));
+                FILES.with(|files| files.borrow_mut().insert(id, duplicate));
+                Box::pin(async {})
+            });
+            if !adopted {
+                return Err(File::from_std(file));
+            }
+            Ok(RingFile {
+                id,
+                file,
+                offset,
+                unread: Vec::new(),
+                pending: None,
+            })
+        }
+
+        pub(crate) fn metadata(&self) -> io::Result<std::fs::Metadata> {
+            self.file.metadata()
+        }
+
+        /// Bytes of the file returned by reads, or written, so far.
+        pub(crate) fn position(&self) -> u64 {
+            self.offset - self.unread.len() as u64
+        }
+
+        /// Finish the pending operation, returning its byte count. Data read is kept in
+        /// `unread`.
+        fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+            let result = match &mut self.pending {
+                None => return Poll::Ready(Ok(0)),
+                Some(Pending::Read(receiver)) => ready!(Pin::new(receiver).poll(cx))
+                    .unwrap_or_else(|_| Err(stopped()))
+                    .map(|data| {
+                        self.offset += data.len() as u64;
+                        self.unread.extend_from_slice(&data);
+                        data.len()
+                    }),
+                Some(Pending::Write(receiver)) => ready!(Pin::new(receiver).poll(cx))
+                    .unwrap_or_else(|_| Err(stopped()))
+                    .inspect(|&n| self.offset += n as u64),
+            };
+            self.pending = None;
+            Poll::Ready(result)
+        }
+    }
+
+    impl Drop for RingFile {
+        fn drop(&mut self) {
+            // An operation still in flight holds the file until it finishes
+            let id = self.id;
+            send(move || {
+                FILES.with(|files| files.borrow_mut().remove(&id));
+                Box::pin(async {})
+            });
+        }
+    }
+
+    impl AsyncRead for RingFile {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            let this = self.get_mut();
+            loop {
+                if !this.unread.is_empty() {
+                    let n = this.unread.len().min(buf.remaining());
+                    buf.put_slice(&this.unread[..n]);
+                    this.unread.drain(..n);
+                    return Poll::Ready(Ok(()));
+                }
+                if this.pending.is_some() {
+                    let reading = matches!(this.pending, Some(Pending::Read(_)));
+                    if ready!(this.poll_pending(cx))? == 0 && reading {
+                        return Poll::Ready(Ok(())); // EOF
+                    }
+                    continue;
+                }
+                if buf.remaining() == 0 {
+                    return Poll::Ready(Ok(()));
+                }
+                let (id, offset, len) = (this.id, this.offset, buf.remaining());
+                let (result_tx, result_rx) = oneshot::channel();
+                let sent = send(move || {
+                    let file = file(id);
+                    Box::pin(async move {
+                        let result = match file {
+                            Ok(file) => {
+                                let (result, data) =
+                                    file.read_at(Vec::with_capacity(len), offset).await;
+                                result.map(|_| data)
+                            }
+                            Err(e) => Err(e),
+                        };
+                        let _ = result_tx.send(result);
+                    })
+                });
+                if !sent {
+                    return Poll::Ready(Err(stopped()));
+                }
+                this.pending = Some(Pending::Read(result_rx));
+            }
+        }
+    }
+
+    impl AsyncWrite for RingFile {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            // A write that returned pending is retried with the same bytes, so its
+            // result answers the retry
+            if let Some(Pending::Write(_)) = this.pending {
+                return this.poll_pending(cx);
+            }
+            ready!(this.poll_pending(cx))?;
+            let (id, offset, data) = (this.id, this.offset, buf.to_vec());
+            let (result_tx, result_rx) = oneshot::channel();
+            let sent = send(move || {
+                let file = file(id);
+                Box::pin(async move {
+                    let result = match file {
+                        Ok(file) => file.write_at(data, offset).await.0,
+                        Err(e) => Err(e),
+                    };
+                    let _ = result_tx.send(result);
+                })
+            });
+            if !sent {
+                return Poll::Ready(Err(stopped()));
+            }
+            this.pending = Some(Pending::Write(result_rx));
+            this.poll_pending(cx)
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            self.get_mut().poll_pending(cx).map_ok(|_| ())
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            self.poll_flush(cx)
+        }
+    }
+}
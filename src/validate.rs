@@ -0,0 +1,262 @@
+//! Schema validation with a structured violation report.
+
+use crate::ops::{csv_err, open_reader, read_header, simple_dialect, spawn_blocking_py};
+use crate::validate_path;
+use csv::StringRecord;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use regex::Regex;
+
+/// Expected type of a column's values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ValueType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+}
+
+impl ValueType {
+    fn parse(name: &str, column: &str) -> PyResult<Self> {
+        match name {
+            "str" | "string" => Ok(ValueType::String),
+            "int" | "integer" => Ok(ValueType::Integer),
+            "float" | "number" => Ok(ValueType::Float),
+            "bool" | "boolean" => Ok(ValueType::Boolean),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown type '{other}' for column '{column}' \
+                (expected 'string', 'integer', 'float' or 'boolean')"
+            ))),
+        }
+    }
+}
+
+/// Validation rules for one column.
+#[derive(Clone, Debug)]
+struct ColumnRule {
+    name: String,
+    value_type: ValueType,
+    required: bool,
+    nullable: bool,
+    pattern: Option<Regex>,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl ColumnRule {
+    /// Parse a rule from its schema entry, e.g. `{"type": "integer", "min": 0}`.
+    fn from_python(name: String, spec: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let mut rule = ColumnRule {
+            name,
+            value_type: ValueType::String,
+            required: true,
+            nullable: true,
+            pattern: None,
+            min: None,
+            max: None,
+        };
+        // A bare string is shorthand for {"type": ...}
+        if let Ok(type_name) = spec.extract::<String>() {
+            rule.value_type = ValueType::parse(&type_name, &rule.name)?;
+            return Ok(rule);
+        }
+        let spec = spec.cast::<PyDict>()?;
+        for (key, value) in spec.iter() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "type" => {
+                    rule.value_type = ValueType::parse(&value.extract::<String>()?, &rule.name)?
+                }
+                "required" => rule.required = value.extract()?,
+                "nullable" => rule.nullable = value.extract()?,
+                "pattern" => {
+                    let pattern: String = value.extract()?;
+                    rule.pattern = Some(Regex::new(&format!("^(?:{pattern})$")).map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                            "Invalid pattern for column '{}': {e}",
+                            rule.name
+                        ))
+                    })?);
+                }
+                "min" => rule.min = Some(value.extract()?),
+                "max" => rule.max = Some(value.extract()?),
+                other => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Unknown schema key '{other}' for column '{}'",
+                        rule.name
+                    )))
+                }
+            }
+        }
+        Ok(rule)
+    }
+
+    /// Check a single value, returning a message describing the violation if any.
+    fn check(&self, value: &str) -> Option<String> {
+        if value.is_empty() {
+            return (!self.nullable).then(|| "value is required".to_string());
+        }
+        let number = match self.value_type {
+            ValueType::String => None,
+            ValueType::Integer => match value.trim().parse::<i64>() {
+                Ok(n) => Some(n as f64),
+                Err(_) => return Some("expected integer".to_string()),
+            },
+            ValueType::Float => match value.trim().parse::<f64>() {
+                Ok(n) => Some(n),
+                Err(_) => return Some("expected float".to_string()),
+            },
+            ValueType::Boolean => {
+                let lower = value.trim().to_ascii_lowercase();
+                if !matches!(lower.as_str(), "true" | "false" | "1" | "0" | "yes" | "no") {
+                    return Some("expected boolean".to_string());
+                }
+                None
+            }
+        };
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(value) {
+                return Some(format!("does not match pattern '{}'", pattern.as_str()));
+            }
+        }
+        // Numeric columns compare by value; string columns compare by length
+        let measure = number.unwrap_or(value.chars().count() as f64);
+        let what = if number.is_some() { "value" } else { "length" };
+        if let Some(min) = self.min {
+            if measure < min {
+                return Some(format!("{what} {measure} is less than minimum {min}"));
+            }
+        }
+        if let Some(max) = self.max {
+            if measure > max {
+                return Some(format!("{what} {measure} is greater than maximum {max}"));
+            }
+        }
+        None
+    }
+}
+
+/// One schema violation. `row` is the 1-based data row (None for header problems).
+struct Violation {
+    row: Option<usize>,
+    column: String,
+    value: Option<String>,
+    message: String,
+}
+
+/// Result of validating a file.
+struct Report {
+    rows_checked: usize,
+    violations: Vec<Violation>,
+    truncated: bool,
+}
+
+/// Validate `path` against `rules`, stopping after `max_errors` violations.
+fn validate_file(
+    path: &str,
+    rules: &[ColumnRule],
+    max_errors: usize,
+    dialect: &crate::DialectConfig,
+) -> PyResult<Report> {
+    let mut reader = open_reader(path, dialect)?;
+    let header = read_header(&mut reader, path)?.unwrap_or_default();
+    let mut report = Report {
+        rows_checked: 0,
+        violations: Vec::new(),
+        truncated: false,
+    };
+
+    let mut checks: Vec<(usize, &ColumnRule)> = Vec::new();
+    for rule in rules {
+        match header.iter().position(|h| h == rule.name) {
+            Some(index) => checks.push((index, rule)),
+            None if rule.required => report.violations.push(Violation {
+                row: None,
+                column: rule.name.clone(),
+                value: None,
+                message: "required column is missing".to_string(),
+            }),
+            None => {}
+        }
+    }
+    if report.violations.len() >= max_errors {
+        report.violations.truncate(max_errors);
+        report.truncated = true;
+        return Ok(report);
+    }
+
+    let mut record = StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_err(path, e)),
+        }
+        report.rows_checked += 1;
+        for &(index, rule) in &checks {
+            let value = record.get(index).unwrap_or("");
+            if let Some(message) = rule.check(value) {
+                if report.violations.len() >= max_errors {
+                    report.truncated = true;
+                    return Ok(report);
+                }
+                report.violations.push(Violation {
+                    row: Some(report.rows_checked),
+                    column: rule.name.clone(),
+                    value: Some(value.to_string()),
+                    message,
+                });
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Validate a CSV file against a per-column schema.
+///
+/// # Arguments
+/// * `path` - CSV file (first row is the header)
+/// * `schema` - Dict mapping column names to a type name or a rule dict with keys
+///   `type`, `required`, `nullable`, `pattern`, `min` and `max`
+/// * `max_errors` - Stop after this many violations (default: 100)
+/// * `delimiter` - Field delimiter (default: ',')
+/// * `quotechar` - Quote character (default: '"')
+///
+/// Returns a report dict with `valid`, `rows_checked`, `truncated` and `errors`.
+#[pyfunction]
+#[pyo3(signature = (path, schema, max_errors = 100, delimiter = None, quotechar = None))]
+pub(crate) fn validate(
+    py: Python<'_>,
+    path: String,
+    schema: &Bound<'_, PyDict>,
+    max_errors: usize,
+    delimiter: Option<&str>,
+    quotechar: Option<&str>,
+) -> PyResult<Py<PyAny>> {
+    validate_path(&path)?;
+    let rules = schema
+        .iter()
+        .map(|(name, spec)| ColumnRule::from_python(name.extract()?, &spec))
+        .collect::<PyResult<Vec<_>>>()?;
+    let dialect = simple_dialect(delimiter, quotechar)?;
+    spawn_blocking_py(py, move || {
+        let report = validate_file(&path, &rules, max_errors.max(1), &dialect)?;
+        Python::attach(|py| -> PyResult<Py<PyAny>> {
+            let errors = PyList::empty(py);
+            for violation in &report.violations {
+                let error = PyDict::new(py);
+                error.set_item("row", violation.row)?;
+                error.set_item("column", &violation.column)?;
+                error.set_item("value", violation.value.as_deref())?;
+                error.set_item("message", &violation.message)?;
+                errors.append(error)?;
+            }
+            let result = PyDict::new(py);
+            result.set_item("valid", report.violations.is_empty())?;
+            result.set_item("rows_checked", report.rows_checked)?;
+            result.set_item("truncated", report.truncated)?;
+            result.set_item("errors", errors)?;
+            Ok(result.into_any().unbind())
+        })
+    })
+}
@@ -0,0 +1,608 @@
+//! Conversion of Python values passed to `Writer.write_row()` into CSV fields.
+//!
+//! Rows may hold any Python scalars, not just strings. Strings, ints, floats, bools and
+//! None are serialized here without a round trip through `str()`; other objects
+//! (`Decimal`, ...) fall back to `str()` like the standard `csv` module.
+//!
+//! `float_format` takes a printf-style conversion (`%.4f`, `%e`, `%.3g`) and
+//! `date_format`/`datetime_format` a strftime-style pattern; both are parsed once when
+//! the writer is created and applied in Rust, so a bad pattern fails early.
+//!
+//! `sanitize_formulas` guards exports of user-supplied text against CSV injection:
+//! spreadsheets run a field starting with `=`, `+`, `-` or `@` (or a tab or carriage
+//! return before one) as a formula, so such text fields get a leading `'`, as OWASP
+//! recommends. Numbers are written as they are, so `-5` stays a number.
+
+use pyo3::prelude::*;
+use pyo3::types::{
+    PyBool, PyBytes, PyDate, PyDateAccess, PyDateTime, PyDict, PyFloat, PyInt, PyString,
+    PyTimeAccess,
+};
+
+fn invalid(message: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(message)
+}
+
+/// How values are written (`float_precision`, `float_format`, `true_value`,
+/// `false_value`, `none_value`, `date_format`, `datetime_format` and
+/// `sanitize_formulas` on `Writer`).
+#[derive(Clone, Debug)]
+pub(crate) struct ValueFormat {
+    float_precision: Option<usize>, // Digits after the point; None for shortest round trip
+    float_format: Option<FloatFormat>,
+    true_value: String,
+    false_value: String,
+    none_value: String,
+    date_format: Option<Strftime>,
+    datetime_format: Option<Strftime>,
+    sanitize_formulas: bool, // Prefix text fields a spreadsheet would run with "'"
+}
+
+impl Default for ValueFormat {
+    fn default() -> Self {
+        ValueFormat {
+            float_precision: None,
+            float_format: None,
+            true_value: "True".to_string(),
+            false_value: "False".to_string(),
+            none_value: String::new(),
+            date_format: None,
+            datetime_format: None,
+            sanitize_formulas: false,
+        }
+    }
+}
+
+/// Characters that make a spreadsheet read a field as a formula (OWASP's CSV injection
+/// list).
+const FORMULA_STARTS: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Prefix `field` with `'` if a spreadsheet would run it as a formula.
+pub(crate) fn sanitize_formula(field: String) -> String {
+    if field.starts_with(FORMULA_STARTS) {
+        format!("'{field}")
+    } else {
+        field
+    }
+}
+
+/// Rewrite Rust's exponent notation (`1.5e3`) the way Python writes it (`1.5e+03`).
+fn python_exponent(text: &str, upper: bool) -> String {
+    let (mantissa, exponent) = text.split_once('e').unwrap_or((text, "0"));
+    let (sign, digits) = match exponent.strip_prefix('-') {
+        Some(digits) => ('-', digits),
+        None => ('+', exponent),
+    };
+    let e = if upper { 'E' } else { 'e' };
+    format!("{mantissa}{e}{sign}{digits:0>2}")
+}
+
+/// Python's spelling of NaN and the infinities.
+fn non_finite(value: f64) -> &'static str {
+    if value.is_nan() {
+        "nan"
+    } else if value > 0.0 {
+        "inf"
+    } else {
+        "-inf"
+    }
+}
+
+/// Python's `repr()` of a float: the shortest string that reads back to the same
+/// value, with a signed two-digit exponent outside 1e-4..1e16.
+fn shortest_float(value: f64) -> String {
+    if !value.is_finite() {
+        return non_finite(value).to_string();
+    }
+    let abs = value.abs();
+    if abs != 0.0 && !(1e-4..1e16).contains(&abs) {
+        return python_exponent(&format!("{value:e}"), false);
+    }
+    format!("{value:?}")
+}
+
+/// Drop trailing zeros after the decimal point, and the point itself if bare.
+fn strip_zeros(digits: &str) -> &str {
+    if digits.contains('.') {
+        digits.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        digits
+    }
+}
+
+/// A printf-style float conversion: `[text]%[+][0][width][.precision](f|e|E|g|G)[text]`.
+#[derive(Clone, Debug)]
+struct FloatFormat {
+    prefix: String,
+    suffix: String,
+    plus: bool, // Always write a sign
+    zero: bool, // Pad with zeros instead of spaces
+    width: usize,
+    precision: usize,
+    conversion: char,
+}
+
+impl FloatFormat {
+    fn parse(format: &str) -> PyResult<Self> {
+        let bad = || {
+            invalid(format!(
+                "float_format must contain one %f, %e or %g conversion, got '{format}'"
+            ))
+        };
+        let mut chars = format.chars().peekable();
+        let mut prefix = String::new();
+        loop {
+            match chars.next() {
+                Some('%') if chars.peek() == Some(&'%') => {
+                    chars.next();
+                    prefix.push('%');
+                }
+                Some('%') => break,
+                Some(c) => prefix.push(c),
+                None => return Err(bad()),
+            }
+        }
+        let (mut plus, mut zero) = (false, false);
+        while let Some(&c) = chars.peek() {
+            match c {
+                '+' => plus = true,
+                '0' => zero = true,
+                _ => break,
+            }
+            chars.next();
+        }
+        let number = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+            let mut digits = String::new();
+            while let Some(c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                digits.push(*c);
+                chars.next();
+            }
+            digits.parse::<usize>().ok()
+        };
+        let width = number(&mut chars).unwrap_or(0);
+        let precision = if chars.peek() == Some(&'.') {
+            chars.next();
+            number(&mut chars).unwrap_or(0)
+        } else {
+            6
+        };
+        let conversion = match chars.next() {
+            Some(c @ ('f' | 'F' | 'e' | 'E' | 'g' | 'G')) => c,
+            _ => return Err(bad()),
+        };
+        let mut suffix = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '%' if chars.next() == Some('%') => suffix.push('%'),
+                '%' => return Err(bad()),
+                c => suffix.push(c),
+            }
+        }
+        Ok(FloatFormat {
+            prefix,
+            suffix,
+            plus,
+            zero,
+            width,
+            precision,
+            conversion,
+        })
+    }
+
+    /// Digits of `value` without sign or padding.
+    fn digits(&self, value: f64) -> String {
+        let precision = self.precision;
+        let upper = self.conversion.is_ascii_uppercase();
+        match self.conversion {
+            'f' | 'F' => format!("{value:.precision$}"),
+            'e' | 'E' => python_exponent(&format!("{value:.precision$e}"), upper),
+            _ => {
+                // %g: scientific notation only for exponents below -4 or at least the
+                // precision, with trailing zeros removed either way
+                let significant = precision.max(1);
+                let scientific = format!("{value:.*e}", significant - 1);
+                let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+                let exponent: i64 = exponent.parse().unwrap_or(0);
+                if exponent < -4 || exponent >= significant as i64 {
+                    let text = format!("{}e{exponent}", strip_zeros(mantissa));
+                    python_exponent(&text, upper)
+                } else {
+                    let decimals = (significant as i64 - 1 - exponent) as usize;
+                    strip_zeros(&format!("{value:.decimals$}")).to_string()
+                }
+            }
+        }
+    }
+
+    fn format(&self, value: f64) -> String {
+        let digits = if value.is_finite() {
+            self.digits(value.abs())
+        } else if self.conversion.is_ascii_uppercase() {
+            non_finite(value.abs()).to_ascii_uppercase()
+        } else {
+            non_finite(value.abs()).to_string()
+        };
+        let sign = if value.is_sign_negative() && !value.is_nan() {
+            "-"
+        } else if self.plus {
+            "+"
+        } else {
+            ""
+        };
+        let pad = self.width.saturating_sub(sign.len() + digits.len());
+        let body = if self.zero {
+            format!("{sign}{}{digits}", "0".repeat(pad))
+        } else {
+            format!("{}{sign}{digits}", " ".repeat(pad))
+        };
+        format!("{}{body}{}", self.prefix, self.suffix)
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// Fields of a `date` or `datetime`, plus its UTC offset and zone name if aware.
+struct DateParts {
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    microsecond: u32,
+    offset: Option<i64>, // Seconds east of UTC
+    zone: String,
+}
+
+impl DateParts {
+    fn is_leap(&self) -> bool {
+        (self.year % 4 == 0 && self.year % 100 != 0) || self.year % 400 == 0
+    }
+
+    /// Day of the week, 0 for Sunday (Sakamoto's method).
+    fn weekday(&self) -> usize {
+        const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let year = if self.month < 3 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        let day = year + year / 4 - year / 100
+            + year / 400
+            + OFFSETS[self.month as usize - 1]
+            + self.day as i32;
+        day.rem_euclid(7) as usize
+    }
+
+    /// Day of the year, from 1.
+    fn yearday(&self) -> u32 {
+        const DAYS: [u32; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+        let leap = u32::from(self.is_leap() && self.month > 2);
+        DAYS[self.month as usize - 1] + self.day as u32 + leap
+    }
+}
+
+/// A strftime-style pattern, checked when the writer is created.
+#[derive(Clone, Debug)]
+struct Strftime {
+    pieces: Vec<Piece>,
+}
+
+#[derive(Clone, Debug)]
+enum Piece {
+    Text(String),
+    Directive(char),
+}
+
+impl Strftime {
+    const DIRECTIVES: &'static str = "aAbBdfFHIjmMpSTyYzZ%";
+
+    fn parse(option: &str, format: &str) -> PyResult<Self> {
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some(d) if Self::DIRECTIVES.contains(d) => {
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut text)));
+                    }
+                    pieces.push(Piece::Directive(d));
+                }
+                Some(d) => {
+                    return Err(invalid(format!(
+                        "{option} has an unsupported directive '%{d}'"
+                    )))
+                }
+                None => return Err(invalid(format!("{option} ends with a lone '%'"))),
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        Ok(Strftime { pieces })
+    }
+
+    fn format(&self, parts: &DateParts) -> String {
+        use std::fmt::Write;
+        let mut out = String::new();
+        for piece in &self.pieces {
+            let directive = match piece {
+                Piece::Text(text) => {
+                    out.push_str(text);
+                    continue;
+                }
+                Piece::Directive(d) => *d,
+            };
+            let month = MONTHS[parts.month as usize - 1];
+            let weekday = WEEKDAYS[parts.weekday()];
+            let hour12 = match parts.hour % 12 {
+                0 => 12,
+                h => h,
+            };
+            let _ = match directive {
+                'a' => write!(out, "{}", &weekday[..3]),
+                'A' => write!(out, "{weekday}"),
+                'b' => write!(out, "{}", &month[..3]),
+                'B' => write!(out, "{month}"),
+                'd' => write!(out, "{:02}", parts.day),
+                'f' => write!(out, "{:06}", parts.microsecond),
+                'F' => write!(out, "{:04}-{:02}-{:02}", parts.year, parts.month, parts.day),
+                'H' => write!(out, "{:02}", parts.hour),
+                'I' => write!(out, "{hour12:02}"),
+                'j' => write!(out, "{:03}", parts.yearday()),
+                'm' => write!(out, "{:02}", parts.month),
+                'M' => write!(out, "{:02}", parts.minute),
+                'p' => write!(out, "{}", if parts.hour < 12 { "AM" } else { "PM" }),
+                'S' => write!(out, "{:02}", parts.second),
+                'T' => write!(
+                    out,
+                    "{:02}:{:02}:{:02}",
+                    parts.hour, parts.minute, parts.second
+                ),
+                'y' => write!(out, "{:02}", parts.year % 100),
+                'Y' => write!(out, "{:04}", parts.year),
+                'z' => match parts.offset {
+                    Some(offset) => {
+                        let sign = if offset < 0 { '-' } else { '+' };
+                        let offset = offset.abs();
+                        write!(out, "{sign}{:02}{:02}", offset / 3600, offset / 60 % 60)
+                    }
+                    None => Ok(()),
+                },
+                'Z' => write!(out, "{}", parts.zone),
+                _ => write!(out, "%"),
+            };
+        }
+        out
+    }
+}
+
+/// Read the fields of a `datetime` (including its UTC offset and zone name).
+fn datetime_parts(value: &Bound<'_, PyDateTime>) -> PyResult<DateParts> {
+    let offset = value.call_method0("utcoffset")?;
+    let offset = if offset.is_none() {
+        None
+    } else {
+        Some(offset.call_method0("total_seconds")?.extract::<f64>()? as i64)
+    };
+    let zone = value.call_method0("tzname")?;
+    Ok(DateParts {
+        year: value.get_year(),
+        month: value.get_month(),
+        day: value.get_day(),
+        hour: value.get_hour(),
+        minute: value.get_minute(),
+        second: value.get_second(),
+        microsecond: value.get_microsecond(),
+        offset,
+        zone: if zone.is_none() {
+            String::new()
+        } else {
+            zone.extract()?
+        },
+    })
+}
+
+fn date_parts(value: &Bound<'_, PyDate>) -> DateParts {
+    DateParts {
+        year: value.get_year(),
+        month: value.get_month(),
+        day: value.get_day(),
+        hour: 0,
+        minute: 0,
+        second: 0,
+        microsecond: 0,
+        offset: None,
+        zone: String::new(),
+    }
+}
+
+impl ValueFormat {
+    pub(crate) fn new(
+        float_precision: Option<usize>,
+        true_value: Option<String>,
+        false_value: Option<String>,
+        none_value: Option<String>,
+    ) -> Self {
+        let default = ValueFormat::default();
+        ValueFormat {
+            float_precision,
+            true_value: true_value.unwrap_or(default.true_value),
+            false_value: false_value.unwrap_or(default.false_value),
+            none_value: none_value.unwrap_or(default.none_value),
+            ..default
+        }
+    }
+
+    /// Format floats with a printf-style conversion such as `%.4f`.
+    pub(crate) fn float_format(mut self, format: Option<&str>) -> PyResult<Self> {
+        if let Some(format) = format {
+            if self.float_precision.is_some() {
+                return Err(invalid(
+                    "float_format and float_precision cannot both be set".to_string(),
+                ));
+            }
+            self.float_format = Some(FloatFormat::parse(format)?);
+        }
+        Ok(self)
+    }
+
+    /// Format dates and datetimes with strftime-style patterns.
+    pub(crate) fn date_formats(
+        mut self,
+        date_format: Option<&str>,
+        datetime_format: Option<&str>,
+    ) -> PyResult<Self> {
+        self.date_format = date_format
+            .map(|format| Strftime::parse("date_format", format))
+            .transpose()?;
+        self.datetime_format = datetime_format
+            .map(|format| Strftime::parse("datetime_format", format))
+            .transpose()?;
+        Ok(self)
+    }
+
+    /// Neutralize text fields that start like a formula.
+    pub(crate) fn sanitize_formulas(mut self, sanitize: bool) -> Self {
+        self.sanitize_formulas = sanitize;
+        self
+    }
+
+    /// A text field, sanitized if set.
+    fn text(&self, text: String) -> String {
+        if self.sanitize_formulas {
+            sanitize_formula(text)
+        } else {
+            text
+        }
+    }
+
+    fn float(&self, value: f64) -> String {
+        if let Some(format) = &self.float_format {
+            return format.format(value);
+        }
+        match self.float_precision {
+            Some(digits) if value.is_finite() => format!("{value:.digits$}"),
+            _ => shortest_float(value),
+        }
+    }
+
+    /// Convert one value into a field.
+    pub(crate) fn field(&self, value: &Bound<'_, PyAny>) -> PyResult<String> {
+        if let Ok(s) = value.cast::<PyString>() {
+            return Ok(self.text(s.to_str()?.to_string()));
+        }
+        if value.is_none() {
+            return Ok(self.none_value.clone());
+        }
+        // bool is a subclass of int, so it is checked first
+        if let Ok(b) = value.cast::<PyBool>() {
+            return Ok(if b.is_true() {
+                self.true_value.clone()
+            } else {
+                self.false_value.clone()
+            });
+        }
+        if value.is_instance_of::<PyInt>() {
+            if let Ok(n) = value.extract::<i64>() {
+                return Ok(n.to_string());
+            }
+        } else if let Ok(f) = value.cast::<PyFloat>() {
+            return Ok(self.float(f.value()));
+        } else if let Ok(b) = value.cast::<PyBytes>() {
+            return String::from_utf8(b.as_bytes().to_vec())
+                .map(|text| self.text(text))
+                .map_err(|_| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "bytes field is not valid UTF-8",
+                    )
+                });
+        } else if let Ok(dt) = value.cast::<PyDateTime>() {
+            // datetime is a subclass of date, so it is checked first
+            if let Some(format) = &self.datetime_format {
+                return Ok(format.format(&datetime_parts(dt)?));
+            }
+        } else if let Ok(date) = value.cast::<PyDate>() {
+            if let Some(format) = &self.date_format {
+                return Ok(format.format(&date_parts(date)));
+            }
+        }
+        Ok(value.str()?.to_string())
+    }
+
+    /// Convert a row (any iterable of values) into fields.
+    pub(crate) fn row(&self, row: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+        if row.is_instance_of::<PyString>() {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "a row must be a sequence of fields, not str",
+            ));
+        }
+        row.try_iter()?.map(|value| self.field(&value?)).collect()
+    }
+
+    /// Convert an iterable of rows.
+    pub(crate) fn rows(&self, rows: &Bound<'_, PyAny>) -> PyResult<Vec<Vec<String>>> {
+        rows.try_iter()?.map(|row| self.row(&row?)).collect()
+    }
+
+    /// Convert columns into rows: a dict of columns (its values, in order) or a
+    /// sequence of columns, each an iterable of values of the same length.
+    pub(crate) fn columns(&self, columns: &Bound<'_, PyAny>) -> PyResult<Vec<Vec<String>>> {
+        let columns = match columns.cast::<PyDict>() {
+            Ok(dict) => dict.values().into_any(),
+            Err(_) => columns.clone(),
+        };
+        let mut fields = Vec::new();
+        for column in columns.try_iter()? {
+            let column = column?;
+            if column.is_instance_of::<PyString>() {
+                return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                    "a column must be a sequence of fields, not str",
+                ));
+            }
+            let column = column.try_iter()?.map(|value| self.field(&value?));
+            fields.push(column.collect::<PyResult<Vec<_>>>()?);
+        }
+        let len = fields.first().map_or(0, Vec::len);
+        if let Some(i) = fields.iter().position(|column| column.len() != len) {
+            return Err(invalid(format!(
+                "columns must have the same length: column {i} has {}, column 0 has {len}",
+                fields[i].len()
+            )));
+        }
+        let mut rows = vec![Vec::with_capacity(fields.len()); len];
+        for column in fields {
+            for (row, field) in rows.iter_mut().zip(column) {
+                row.push(field);
+            }
+        }
+        Ok(rows)
+    }
+}
@@ -0,0 +1,97 @@
+//! Whitespace-delimited records (`delim_whitespace=True` on `Reader`).
+//!
+//! Log-style and scientific tables line their columns up with any number of spaces or
+//! tabs. Here a run of them separates two fields, whitespace at the start and end of a
+//! line is ignored and blank lines are skipped. Quoted fields may still hold spaces,
+//! doubled quotes, escapes and line breaks, as in delimited files.
+
+use crate::DialectConfig;
+use csv::Terminator;
+
+fn is_blank(b: u8) -> bool {
+    b == b' ' || b == b'\t'
+}
+
+fn field(bytes: Vec<u8>) -> Result<String, String> {
+    String::from_utf8(bytes).map_err(|e| format!("invalid UTF-8 in field: {e}"))
+}
+
+/// Parse the first record of `input`, returning its fields and the bytes consumed,
+/// blank lines before it included. The end of `input` counts as the end of the data.
+pub(crate) fn parse_first(
+    input: &[u8],
+    dialect: &DialectConfig,
+) -> Option<Result<(Vec<String>, usize), String>> {
+    let crlf = matches!(dialect.lineterminator, Terminator::CRLF);
+    let ends_record = |b: u8| match dialect.lineterminator {
+        Terminator::Any(t) => b == t,
+        _ => b == b'\r' || b == b'\n',
+    };
+    let escape = |i: usize| dialect.escapechar == Some(input[i]) && i + 1 < input.len();
+
+    let mut row = Vec::new();
+    let mut current = Vec::new();
+    let mut in_field = false; // A field has started, possibly empty and quoted
+    let mut quoted = false;
+    let mut i = 0;
+    while i < input.len() {
+        let b = input[i];
+        if quoted {
+            if escape(i) {
+                current.push(input[i + 1]);
+                i += 2;
+                continue;
+            }
+            if b != dialect.quotechar {
+                current.push(b);
+            } else if dialect.double_quote && input.get(i + 1) == Some(&dialect.quotechar) {
+                current.push(b);
+                i += 1;
+            } else {
+                quoted = false;
+            }
+            i += 1;
+            continue;
+        }
+        if ends_record(b) {
+            i += 1;
+            if crlf && b == b'\r' && input.get(i) == Some(&b'\n') {
+                i += 1;
+            }
+            if in_field {
+                row.push(std::mem::take(&mut current));
+                in_field = false;
+            }
+            if !row.is_empty() {
+                return Some(finish(row, i));
+            }
+            continue; // Blank line
+        }
+        if is_blank(b) {
+            if in_field {
+                row.push(std::mem::take(&mut current));
+                in_field = false;
+            }
+        } else if escape(i) {
+            current.push(input[i + 1]);
+            in_field = true;
+            i += 1;
+        } else if b == dialect.quotechar && !in_field {
+            quoted = true;
+            in_field = true;
+        } else {
+            current.push(b);
+            in_field = true;
+        }
+        i += 1;
+    }
+    if in_field {
+        row.push(current);
+    }
+    (!row.is_empty()).then(|| finish(row, input.len()))
+}
+
+fn finish(row: Vec<Vec<u8>>, consumed: usize) -> Result<(Vec<String>, usize), String> {
+    let row = row.into_iter().map(field).collect::<Result<_, _>>()?;
+    Ok((row, consumed))
+}
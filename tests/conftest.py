@@ -0,0 +1,26 @@
+"""Shared fixtures for the rapcsv tests."""
+
+import itertools
+
+import pytest
+
+
+@pytest.fixture
+def write_csv(tmp_path):
+    """Return a function that writes CSV content to a new file and returns its path.
+
+    Text is written as UTF-8 without newline translation, and bytes as given. Files go under
+    ``tmp_path``, as ``name`` if given and otherwise numbered with ``suffix``.
+    """
+    numbers = itertools.count(1)
+
+    def write(content, name=None, suffix=".csv"):
+        path = tmp_path / (name or f"data-{next(numbers)}{suffix}")
+        if isinstance(content, bytes):
+            path.write_bytes(content)
+        else:
+            with open(path, "w", encoding="utf-8", newline="") as f:
+                f.write(content)
+        return str(path)
+
+    return write
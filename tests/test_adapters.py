@@ -0,0 +1,72 @@
+"""Test plugging Python byte sources and sinks into Reader and Writer."""
+
+import gzip
+
+import pytest
+
+from rapcsv import Reader, SinkAdapter, SourceAdapter, Writer
+
+
+class MemorySource(SourceAdapter):
+    """Serves bytes in small pieces, as a network backend would."""
+
+    def __init__(self, data):
+        self.data = data
+        self.closed = 0
+
+    async def read(self, n):
+        piece, self.data = self.data[: min(n, 5)], self.data[min(n, 5) :]
+        return piece
+
+    async def close(self):
+        self.closed += 1
+
+
+class MemorySink(SinkAdapter):
+    def __init__(self):
+        self.chunks = []
+        self.closed = 0
+
+    async def write(self, data):
+        assert isinstance(data, bytes)
+        self.chunks.append(data)
+
+    async def close(self):
+        self.closed += 1
+
+
+@pytest.mark.asyncio
+async def test_source_adapter():
+    """Test reading bytes from a source, which is closed when the reader exits."""
+    source = MemorySource('id,name\r\n1,"Zoë\r\nB"\r\n2,c\r\n'.encode())
+    async with Reader(source) as reader:
+        assert await reader.read_all() == [["id", "name"], ["1", "Zoë\r\nB"], ["2", "c"]]
+    assert source.closed == 1
+
+
+@pytest.mark.asyncio
+async def test_gzipped_source():
+    """Test that a source serving gzip bytes is decompressed."""
+    source = MemorySource(gzip.compress(b"a,b\n1,2\n"))
+    async with Reader(source) as reader:
+        assert await reader.read_all() == [["a", "b"], ["1", "2"]]
+
+
+@pytest.mark.asyncio
+async def test_sink_adapter():
+    """Test that a sink gets bytes and is closed once, after the last chunk."""
+    sink = MemorySink()
+    async with Writer(sink, headers=["id", "name"]) as writer:
+        await writer.writerows([[1, "Zoë"], [2, "b"]])
+    assert b"".join(sink.chunks) == "id,name\r\n1,Zoë\r\n2,b\r\n".encode()
+    assert sink.closed == 1
+
+
+def test_adapters_are_abstract():
+    """Test that subclasses must implement read() or write()."""
+
+    class Incomplete(SourceAdapter):
+        pass
+
+    with pytest.raises(TypeError):
+        Incomplete()
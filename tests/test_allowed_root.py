@@ -0,0 +1,139 @@
+"""Test confining paths to a directory with set_allowed_root() and allowed_root=."""
+
+import os
+import sys
+import tempfile
+
+import pytest
+
+import rapcsv
+from rapcsv import Reader, Writer, query, sort
+
+needs_symlinks = pytest.mark.skipif(sys.platform == "win32", reason="symlinks need privileges")
+
+
+def _write(path, content):
+    with open(path, "w") as f:
+        f.write(content)
+
+
+def _dirs():
+    """A sandbox directory holding data.csv, and a secret file outside it."""
+    base = tempfile.mkdtemp()
+    root = os.path.join(base, "root")
+    os.mkdir(root)
+    _write(os.path.join(root, "data.csv"), "a,b\n1,2\n")
+    _write(os.path.join(base, "secret.csv"), "x\ny\n")
+    return base, root
+
+
+@pytest.mark.asyncio
+async def test_instance_root_allows_paths_inside():
+    """Test that paths inside allowed_root work, including ones that don't exist yet."""
+    _, root = _dirs()
+    reader = Reader(os.path.join(root, "data.csv"), allowed_root=root)
+    assert await reader.read_all() == [["a", "b"], ["1", "2"]]
+
+    out = os.path.join(root, ".", "out.csv")
+    async with Writer(out, allowed_root=root) as writer:
+        await writer.write_row(["z"])
+    assert os.path.exists(os.path.join(root, "out.csv"))
+
+
+def test_instance_root_rejects_parent_dir():
+    """Test that `..` cannot leave allowed_root."""
+    _, root = _dirs()
+    escape = os.path.join(root, "..", "secret.csv")
+    with pytest.raises(PermissionError, match="outside the allowed root"):
+        Reader(escape, allowed_root=root)
+    with pytest.raises(PermissionError):
+        Writer(os.path.join(root, "..", "new.csv"), allowed_root=root)
+    with pytest.raises(PermissionError):
+        Reader([os.path.join(root, "data.csv"), escape], allowed_root=root)
+
+
+@needs_symlinks
+def test_instance_root_follows_symlinks():
+    """Test that symlinks, including dangling ones, cannot point out of allowed_root."""
+    base, root = _dirs()
+    os.symlink(os.path.join(base, "secret.csv"), os.path.join(root, "link.csv"))
+    os.symlink(base, os.path.join(root, "up"))
+    os.symlink(os.path.join(base, "missing.csv"), os.path.join(root, "dangling.csv"))
+
+    with pytest.raises(PermissionError):
+        Reader(os.path.join(root, "link.csv"), allowed_root=root)
+    with pytest.raises(PermissionError):
+        Writer(os.path.join(root, "up", "new.csv"), allowed_root=root)
+    with pytest.raises(PermissionError):
+        Writer(os.path.join(root, "dangling.csv"), allowed_root=root)
+    with pytest.raises(PermissionError):
+        Reader(os.path.join(root, "*.csv"), allowed_root=root)
+
+
+def test_allowed_root_must_be_a_directory():
+    """Test that allowed_root must name an existing directory."""
+    base, root = _dirs()
+    with pytest.raises(ValueError, match="not a directory"):
+        Reader(os.path.join(root, "data.csv"), allowed_root=os.path.join(root, "data.csv"))
+    with pytest.raises(ValueError, match="cannot be resolved"):
+        rapcsv.set_allowed_root(os.path.join(base, "missing"))
+    assert rapcsv.get_allowed_root() is None
+
+
+@pytest.mark.asyncio
+async def test_module_root_applies_everywhere():
+    """Test that set_allowed_root() confines classes and functions alike."""
+    base, root = _dirs()
+    rapcsv.set_allowed_root(root)
+    try:
+        assert rapcsv.get_allowed_root() == os.path.realpath(root)
+        assert await Reader(os.path.join(root, "data.csv")).read_all() == [["a", "b"], ["1", "2"]]
+        with pytest.raises(PermissionError):
+            Reader(os.path.join(base, "secret.csv"))
+        with pytest.raises(PermissionError):
+            Writer(os.path.join(base, "new.csv"))
+        with pytest.raises(PermissionError):
+            await sort(os.path.join(root, "data.csv"), os.path.join(base, "sorted.csv"), by="a")
+        assert not os.path.exists(os.path.join(base, "sorted.csv"))
+    finally:
+        rapcsv.set_allowed_root(None)
+    assert rapcsv.get_allowed_root() is None
+    Reader(os.path.join(base, "secret.csv"))
+
+
+@pytest.mark.skipif(sys.platform == "win32", reason="from_fd() is Unix only")
+@pytest.mark.asyncio
+async def test_descriptors_are_not_paths():
+    """Test that from_fd() works under a module root and rejects allowed_root=."""
+    base, root = _dirs()
+    rapcsv.set_allowed_root(root)
+    try:
+        with open(os.path.join(base, "secret.csv")) as f:
+            assert await Reader.from_fd(f.fileno()).read_all() == [["x"], ["y"]]
+            with pytest.raises(ValueError):
+                Reader.from_fd(f.fileno(), allowed_root=root)
+    finally:
+        rapcsv.set_allowed_root(None)
+
+
+@pytest.mark.asyncio
+async def test_checkpoints_and_queries_are_confined():
+    """Test that checkpoint_path and SQL given to query() cannot reach outside the root."""
+    base, root = _dirs()
+    data = os.path.join(root, "data.csv")
+    with pytest.raises(PermissionError):
+        Reader(data, allowed_root=root, checkpoint_path=os.path.join(base, "ckpt.json"))
+    Reader(data, allowed_root=root, checkpoint_path=os.path.join(root, "ckpt.json"))
+    rapcsv.set_allowed_root(root)
+    try:
+        with pytest.raises(PermissionError):
+            Reader(data, checkpoint_path=os.path.join(base, "ckpt.json"))
+        assert await query(f"SELECT count(*) FROM '{data}'") == [[1]]
+        secret = os.path.join(base, "secret.db")
+        with pytest.raises(PermissionError, match="attach"):
+            await query(f"ATTACH DATABASE '{secret}' AS s -- FROM '{data}'")
+        with pytest.raises(PermissionError, match="attach"):
+            await query(f"VACUUM INTO '{secret}' -- FROM '{data}'")
+        assert not os.path.exists(secret)
+    finally:
+        rapcsv.set_allowed_root(None)
@@ -0,0 +1,43 @@
+"""Test that batches from read_rows() and read_all() match rows read one at a time."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+CONTENT = 'id,name,score\n1,"a\r\nb",2.5\n2,c,\n3,d,4\n'
+
+OPTIONS = [
+    {},
+    {"raw": True},
+    {"row_type": "tuple"},
+    {"row_type": "row"},
+    {"schema": {"id": "int", "score": "float"}},
+    {"schema": {"id": "int"}, "row_type": "tuple", "normalize_newlines": True},
+    {"intern_columns": ["name"], "normalize_newlines": True},
+    {"converters": {2: lambda v: v or None}},
+]
+
+
+async def _one_at_a_time(path, options):
+    reader = Reader(path, **options)
+    rows = []
+    while row := await reader.read_row():
+        rows.append(row)
+    return rows
+
+
+@pytest.mark.asyncio
+async def test_batches_match_single_rows(write_csv):
+    """Test every row type and conversion through the batch and single-row paths."""
+    path = write_csv(CONTENT)
+    try:
+        for options in OPTIONS:
+            expected = await _one_at_a_time(path, options)
+            assert await Reader(path, **options).read_all() == expected, options
+            reader = Reader(path, **options)
+            batches = await reader.read_rows(2) + await reader.read_rows(10)
+            assert batches == expected, options
+    finally:
+        os.unlink(path)
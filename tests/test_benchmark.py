@@ -0,0 +1,44 @@
+"""Test rapcsv.benchmark()."""
+
+import os
+
+import pytest
+
+from rapcsv import benchmark
+
+CONTENT = "id,name\n" + "".join(f"{i},name {i}\n" for i in range(500))
+
+
+@pytest.mark.asyncio
+async def test_benchmark_read_and_write(write_csv):
+    """Test that read and write runs report rows, bytes and rates."""
+    path = write_csv(CONTENT)
+    try:
+        result = await benchmark(path, engine="fast", repeat=2)
+        assert result["mode"] == "read"
+        assert result["rows"] == 501
+        assert result["bytes"] == os.path.getsize(path)
+        assert len(result["runs"]) == 2
+        assert result["seconds"] == min(result["runs"])
+        assert result["rows_per_sec"] > 0 and result["mb_per_sec"] > 0
+        assert result["options"] == {"engine": "fast", "batch_size": None}
+
+        batched = await benchmark(path, batch_size=64)
+        assert batched["rows"] == 501
+
+        written = await benchmark(path, "write", batch_size=100, lineterminator="\n")
+        assert written["rows"] == 501
+        assert written["bytes"] == os.path.getsize(path)
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_benchmark_invalid_arguments():
+    """Test that invalid modes and counts are rejected."""
+    with pytest.raises(ValueError, match="mode"):
+        await benchmark("missing.csv", mode="append")
+    with pytest.raises(ValueError, match="repeat"):
+        await benchmark("missing.csv", repeat=0)
+    with pytest.raises(ValueError, match="engine"):
+        await benchmark("missing.csv", batch_size=10, engine="fast")
@@ -0,0 +1,60 @@
+"""Test binary=, the policy for NUL bytes and other control characters in fields."""
+
+import os
+
+import pytest
+
+from rapcsv import CsvParseError, Reader
+
+DATA = b"id,name\r\n1,Al\x00ice\r\n2,\x07B\x1bob\x7f\r\n3,Zo\xc3\xab\tx\r\n"
+
+
+@pytest.mark.asyncio
+async def test_strip_and_replace(write_csv):
+    """Test that control characters are removed or replaced, keeping tabs and text."""
+    path = write_csv(DATA)
+    try:
+        async with Reader(path, binary="strip") as reader:
+            rows = await reader.read_all()
+        assert [row[1] for row in rows] == ["name", "Alice", "Bob", "Zoë\tx"]
+        reader = Reader(path, binary="replace")
+        assert [row[1] for row in await reader.read_rows(4)] == [
+            "name",
+            "Al�ice",
+            "�B�ob�",
+            "Zoë\tx",
+        ]
+        # Without a policy the characters are kept
+        assert (await Reader(path).read_all())[1] == ["1", "Al\x00ice"]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_error_locates_field(write_csv):
+    """Test that binary="error" raises at the first control character with its place."""
+    path = write_csv(DATA)
+    try:
+        async with Reader(path, binary="error") as reader:
+            assert await reader.read_row() == ["id", "name"]
+            with pytest.raises(CsvParseError, match="U\\+0000 in field 1 of row 1") as excinfo:
+                await reader.read_row()
+        assert excinfo.value.row == 1
+        assert excinfo.value.column == 1
+        with pytest.raises(CsvParseError):
+            await Reader(path, binary="error", engine="fast").read_all()
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_with_empty_as_none_and_control_delimiter(write_csv):
+    """Test that None fields survive stripping and a control delimiter still splits."""
+    path = write_csv(b"a\x01b\x01c\n\x00x\x01\x01\x00\n")
+    try:
+        async with Reader(path, delimiter="\x01", binary="strip", empty_as_none=True) as reader:
+            assert await reader.read_all() == [["a", "b", "c"], ["x", None, ""]]
+        with pytest.raises(ValueError, match="binary must be"):
+            Reader(path, binary="drop")
+    finally:
+        os.unlink(path)
@@ -0,0 +1,91 @@
+"""Test configuring the strings bool schema columns accept."""
+
+import os
+
+import pytest
+
+from rapcsv import CSVError, Reader
+
+CONTENT = "active,member\nY,yes\nn,NO\n"
+
+
+@pytest.mark.asyncio
+async def test_default_strings(write_csv):
+    """Test the built-in true/false, 1/0 and yes/no strings, in any case."""
+    path = write_csv("flag\nTRUE\n0\nYes\n\n")
+    try:
+        rows = await Reader(path, schema={"flag": "bool"}).read_all()
+        assert rows == [[True], [False], [True]]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_global_strings(write_csv):
+    """Test that lists replace the built-in strings for every bool column."""
+    path = write_csv(CONTENT)
+    try:
+        reader = Reader(
+            path,
+            schema={"active": "bool"},
+            true_values=["Y"],
+            false_values=["N"],
+        )
+        assert await reader.read_all() == [[True, "yes"], [False, "NO"]]
+
+        reader = Reader(
+            path,
+            schema={"active": "bool", "member": "bool"},
+            true_values=["y"],
+            false_values=["n"],
+        )
+        with pytest.raises(CSVError, match="member"):
+            await reader.read_all()
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_per_column_strings(write_csv):
+    """Test that a dict gives strings to the columns it names, others keep the defaults."""
+    path = write_csv(CONTENT)
+    try:
+        reader = Reader(
+            path,
+            schema={"active": "bool", "member": "bool"},
+            true_values={"active": ["Y"]},
+            false_values={"active": ["N"]},
+        )
+        assert await reader.read_all() == [[True, True], [False, False]]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_case_sensitive(write_csv):
+    """Test that bool_case_sensitive=True matches the strings exactly."""
+    path = write_csv(CONTENT)
+    try:
+        reader = Reader(
+            path,
+            schema={"active": "bool"},
+            true_values=["Y"],
+            false_values=["N"],
+            bool_case_sensitive=True,
+        )
+        with pytest.raises(CSVError, match="Cannot convert 'n'"):
+            await reader.read_all()
+    finally:
+        os.unlink(path)
+
+
+def test_invalid_configuration():
+    """Test that conflicting or misplaced strings are rejected up front."""
+    with pytest.raises(ValueError, match="both true_values and false_values"):
+        Reader("data.csv", schema={0: "bool"}, true_values=["x"], false_values=["X"])
+    with pytest.raises(ValueError, match="does not type as bool"):
+        Reader("data.csv", schema={0: "int"}, true_values={0: ["Y"]})
+    with pytest.raises(ValueError, match="schema"):
+        Reader("data.csv", true_values=["Y"])
+    with pytest.raises(TypeError, match="not a string"):
+        Reader("data.csv", schema={0: "bool"}, true_values="Y")
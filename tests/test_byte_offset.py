@@ -0,0 +1,49 @@
+"""Test Reader.byte_offset."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_byte_offset_tracks_rows(write_csv):
+    """Test that byte_offset points at the start of the next row."""
+    content = 'a,b\n"x\ny",2\nlast,3\n'
+    test_file = write_csv(content)
+    try:
+        reader = Reader(test_file)
+        assert reader.byte_offset == 0
+        await reader.read_row()
+        assert reader.byte_offset == 4
+        await reader.read_row()
+        assert (reader.byte_offset, reader.line_num) == (12, 3)
+        with open(test_file, "rb") as f:
+            f.seek(reader.byte_offset)
+            assert f.read() == b"last,3\n"
+        await reader.read_row()
+        assert reader.byte_offset == len(content)
+
+        whole = Reader(test_file)
+        await whole.read_all()
+        assert whole.byte_offset == len(content)
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_byte_offset_multi_file(write_csv):
+    """Test that byte_offset restarts for each file and is None in parallel mode."""
+    first = write_csv("h\n1\n")
+    second = write_csv("h\n22\n")
+    try:
+        reader = Reader([first, second])
+        assert await reader.read_rows(2) == [["h"], ["1"]]
+        assert reader.byte_offset == 4
+        assert await reader.read_row() == ["22"]
+        assert reader.byte_offset == 5
+        assert Reader([first, second], concurrency=2).byte_offset is None
+    finally:
+        os.unlink(first)
+        os.unlink(second)
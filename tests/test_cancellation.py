@@ -0,0 +1,71 @@
+"""Test that cancelling Reader and Writer calls leaves them in a predictable state."""
+
+import asyncio
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+
+@pytest.mark.asyncio
+async def test_cancelled_read_rows_keeps_rows(write_csv):
+    """Test that rows read before read_rows() was cancelled are returned by the next read."""
+    test_file = write_csv("a,b\n1,2\n3,4\n")
+    try:
+        reader = Reader(test_file, follow=True, poll_interval=0.01)
+        # Waits at EOF for more rows until the timeout cancels it
+        with pytest.raises(asyncio.TimeoutError):
+            await asyncio.wait_for(reader.read_rows(10), timeout=0.2)
+
+        with open(test_file, "a", newline="") as f:
+            f.write("5,6\n7,8\n")
+        rows = await asyncio.wait_for(reader.read_rows(5), timeout=5)
+        assert rows == [["a", "b"], ["1", "2"], ["3", "4"], ["5", "6"], ["7", "8"]]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_cancelled_read_row_resumes(write_csv):
+    """Test that a cancelled read_row() does not skip a row."""
+    test_file = write_csv("a,b\n")
+    try:
+        reader = Reader(test_file, follow=True, poll_interval=0.01)
+        assert await reader.read_row() == ["a", "b"]
+        with pytest.raises(asyncio.TimeoutError):
+            await asyncio.wait_for(reader.read_row(), timeout=0.1)
+
+        with open(test_file, "a", newline="") as f:
+            f.write("1,2\n")
+        assert await asyncio.wait_for(reader.read_row(), timeout=5) == ["1", "2"]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("write_size", [0, 64])
+async def test_cancelled_writes_are_all_or_nothing(write_size):
+    """Test that cancelled write_row() calls write whole rows, in order, or nothing."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        test_file = f.name
+    try:
+        writer = Writer(test_file, write_size=write_size)
+        submitted = [[str(i), "x" * (i % 40)] for i in range(300)]
+        calls = [asyncio.ensure_future(writer.write_row(row)) for row in submitted]
+        for call in calls[1::2]:
+            call.cancel()
+        await asyncio.gather(*calls, return_exceptions=True)
+
+        # A write that raced its cancellation may have gone through; later writes still land
+        await writer.write_row(["end", "row"])
+        await writer.close()
+
+        rows = await Reader(test_file).read_all()
+        assert rows[-1] == ["end", "row"]
+        written = iter(submitted)
+        assert all(row in written for row in rows[:-1])  # An in-order subsequence
+        assert all(row in rows for row in submitted[::2])
+    finally:
+        os.unlink(test_file)
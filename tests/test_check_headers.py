@@ -0,0 +1,51 @@
+"""Test check_headers=, which catches header drift between the files of a stream."""
+
+import pytest
+
+from rapcsv import Reader, SchemaError
+
+
+@pytest.mark.asyncio
+async def test_matching_headers(tmp_path, write_csv):
+    """Test that files with the first file's header are read as usual."""
+    write_csv("id,name\n1,a\n", name="part-1.csv")
+    write_csv("id,name\n2,b\n", name="part-2.csv")
+    pattern = str(tmp_path / "part-*.csv")
+    async with Reader(pattern, check_headers=True) as reader:
+        assert await reader.read_all() == [["id", "name"], ["1", "a"], ["2", "b"]]
+
+
+@pytest.mark.asyncio
+async def test_drift_names_file_and_columns(tmp_path, write_csv):
+    """Test that a reordered or changed header raises SchemaError before its rows."""
+    write_csv("id,name\n1,a\n", name="part-1.csv")
+    second = write_csv("name,id\nb,2\n", name="part-2.csv")
+    pattern = str(tmp_path / "part-*.csv")
+    async with Reader(pattern, check_headers=True) as reader:
+        assert await reader.read_rows(2) == [["id", "name"], ["1", "a"]]
+        with pytest.raises(SchemaError, match="another order") as excinfo:
+            await reader.read_row()
+    assert second in str(excinfo.value)
+    assert excinfo.value.column == 0
+    assert excinfo.value.field == "name"
+
+    write_csv("id,label,extra\n2,b,x\n", name="part-2.csv")
+    drift = r'missing \["name"\], unexpected \["label", "extra"\]'
+    with pytest.raises(SchemaError, match=drift):
+        await Reader(pattern, check_headers=True, columns=["id"]).read_all()
+    # Without the check the rows come back under the first file's names
+    assert len(await Reader(pattern).read_all()) == 3
+
+
+@pytest.mark.asyncio
+async def test_expected_header(write_csv):
+    """Test checking every file, the first included, against given names."""
+    path = write_csv("id,name\n1,a\n")
+    async with Reader(path, check_headers=["id", "name"]) as reader:
+        assert await reader.read_all() == [["id", "name"], ["1", "a"]]
+    with pytest.raises(SchemaError, match="expected header: missing"):
+        await Reader(path, check_headers=["id", "name", "email"]).read_all()
+    with pytest.raises(ValueError, match="has_header"):
+        Reader(path, check_headers=True, has_header=False)
+    with pytest.raises(ValueError, match="concurrency"):
+        Reader([path, path], check_headers=True, concurrency=2)
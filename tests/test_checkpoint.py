@@ -0,0 +1,59 @@
+"""Test Reader(checkpoint_path=...), which saves and resumes the read position."""
+
+import json
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_checkpoint_resumes_after_restart(write_csv):
+    """Test that a new reader continues from the last saved checkpoint."""
+    test_file = write_csv("".join(f"{i}\n" for i in range(10)))
+    checkpoint = test_file + ".ckpt"
+    try:
+        reader = Reader(test_file, checkpoint_path=checkpoint, checkpoint_interval=3)
+        assert await reader.read_rows(4) == [[str(i)] for i in range(4)]
+        assert not os.path.exists(checkpoint)  # Saved at the start of the next read
+        assert await reader.read_row() == ["4"]
+        with open(checkpoint) as f:
+            assert json.load(f)["row_index"] == 4
+
+        # Simulated crash: row 4 was returned but not covered by the checkpoint
+        resumed = Reader(test_file, checkpoint_path=checkpoint, checkpoint_interval=3)
+        assert resumed.line_num == 4
+        assert await resumed.read_row() == ["4"]
+        assert await resumed.read_all() == [[str(i)] for i in range(5, 10)]
+        assert await resumed.read_row() == []  # EOF saves the final position
+
+        finished = Reader(test_file, checkpoint_path=checkpoint)
+        assert await finished.read_all() == []
+    finally:
+        for path in (test_file, checkpoint):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_explicit_checkpoint(write_csv):
+    """Test Reader.checkpoint() and rejected configurations."""
+    test_file = write_csv("a\nb\nc\n")
+    checkpoint = test_file + ".ckpt"
+    try:
+        reader = Reader(test_file, checkpoint_path=checkpoint)
+        await reader.read_rows(2)
+        reader.checkpoint()
+        assert await Reader(test_file, checkpoint_path=checkpoint).read_all() == [["c"]]
+
+        with pytest.raises(ValueError, match="checkpoint_path"):
+            Reader(test_file).checkpoint()
+        with pytest.raises(ValueError, match="different files"):
+            Reader([test_file, test_file], checkpoint_path=checkpoint)
+        with pytest.raises(ValueError, match="checkpoint_interval"):
+            Reader(test_file, checkpoint_path=checkpoint, checkpoint_interval=0)
+    finally:
+        for path in (test_file, checkpoint):
+            if os.path.exists(path):
+                os.unlink(path)
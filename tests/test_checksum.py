@@ -0,0 +1,103 @@
+"""Test checksum files written by Writer and verified by Reader."""
+
+import hashlib
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import ChecksumError, Reader, Writer
+
+CSV = "id,name\r\n1,Alice\r\n2,Bob\r\n"
+
+
+def _temp_path():
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        return f.name
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("algorithm", ["sha256", "sha512"])
+async def test_writer_checksum(algorithm):
+    """Test that close() writes a sha256sum-style file next to the CSV."""
+    path = _temp_path()
+    try:
+        async with Writer(path, checksum=algorithm) as writer:
+            await writer.writerows([["id", "name"], ["1", "Alice"], ["2", "Bob"]])
+        with open(path, "rb") as f:
+            digest = hashlib.new(algorithm, f.read()).hexdigest()
+        with open(f"{path}.{algorithm}") as f:
+            assert f.read() == f"{digest}  {os.path.basename(path)}\n"
+
+        rows = await Reader(path, verify_checksum=True).read_all()
+        assert rows == [["id", "name"], ["1", "Alice"], ["2", "Bob"]]
+    finally:
+        for p in (path, f"{path}.{algorithm}"):
+            if os.path.exists(p):
+                os.unlink(p)
+
+
+@pytest.mark.asyncio
+async def test_reader_verify_checksum():
+    """Test verifying against a given digest and a sidecar, streaming and whole-file."""
+    path = _temp_path()
+    with open(path, "w", newline="") as f:
+        f.write(CSV)
+    digest = hashlib.sha256(CSV.encode()).hexdigest()
+    try:
+        reader = Reader(path, verify_checksum=digest.upper())
+        assert [row async for row in reader] == [["id", "name"], ["1", "Alice"], ["2", "Bob"]]
+
+        with pytest.raises(ChecksumError, match="No checksum file"):
+            await Reader(path, verify_checksum=True).read_row()
+
+        # BSD-style checksum files are accepted too
+        with open(f"{path}.sha256", "w") as f:
+            f.write(f"SHA256 ({os.path.basename(path)}) = {digest}\n")
+        assert await Reader(path, verify_checksum=True).read_row() == ["id", "name"]
+
+        with open(path, "a", newline="") as f:
+            f.write("3,Carol\r\n")
+        for read in ("read_row", "read_all"):
+            reader = Reader(path, verify_checksum=True)
+            with pytest.raises(ChecksumError, match="sha256 checksum mismatch") as info:
+                await getattr(reader, read)()
+            assert isinstance(info.value, IOError)
+
+        with pytest.raises(ValueError, match="hex digest"):
+            Reader(path, verify_checksum="abc")
+        with pytest.raises(ValueError, match="verify_checksum requires file paths"):
+            Reader(path, verify_checksum=True, follow=True)
+        with pytest.raises(ValueError, match="checksum must be 'sha256' or 'sha512'"):
+            Writer(path, checksum="md5")
+    finally:
+        for p in (path, f"{path}.sha256"):
+            if os.path.exists(p):
+                os.unlink(p)
+
+
+@pytest.mark.asyncio
+async def test_verify_checksum_multiple_files():
+    """Test that each file of a multi-file reader is checked against its own sidecar."""
+    paths = [_temp_path(), _temp_path()]
+    try:
+        for path in paths:
+            async with Writer(path, checksum="sha256") as writer:
+                await writer.writerows([["id"], [os.path.basename(path)]])
+        rows = await Reader(paths, verify_checksum=True, concurrency=2).read_all()
+        assert len(rows) == 3
+
+        with open(paths[1], "a") as f:
+            f.write("tampered\r\n")
+        reader = Reader(paths, verify_checksum=True)
+        assert len(await reader.read_rows(2)) == 2
+        with pytest.raises(ChecksumError, match="mismatch"):
+            await reader.read_row()
+
+        with pytest.raises(ValueError, match="fits a single file"):
+            Reader(paths, verify_checksum="0" * 64)
+    finally:
+        for path in paths:
+            for p in (path, f"{path}.sha256"):
+                if os.path.exists(p):
+                    os.unlink(p)
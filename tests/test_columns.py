@@ -0,0 +1,76 @@
+"""Test column projection with Reader(columns=...)."""
+
+import pytest
+
+from rapcsv import Reader
+
+
+WIDE = 'id,name,note,score\n1,ann,"a, b",10\n2,bob,"multi\nline",20\n3,cy,,30\n'
+
+
+@pytest.mark.asyncio
+async def test_columns_by_name_and_position(write_csv):
+    """Test that rows hold the selected columns in the order given."""
+    path = write_csv(WIDE)
+    expected = [["score", "id"], ["10", "1"], ["20", "2"], ["30", "3"]]
+    for columns in (["score", "id"], [3, 0], ["score", 0]):
+        reader = Reader(path, columns=columns)
+        assert [row async for row in reader] == expected
+        assert await Reader(path, columns=columns).read_all() == expected
+
+    # Every read_all() parser applies the selection
+    for options in ({"engine": "fast"}, {"mmap": True}, {"threads": 2}):
+        threads = options.pop("threads", None)
+        reader = Reader(path, columns=["note"], **options)
+        assert await reader.read_all(threads) == [["note"], ["a, b"], ["multi\nline"], [""]]
+
+    # A partial read resolves names before read_all() takes over
+    reader = Reader(path, columns=["name"], row_type="row")
+    first = await reader.read_row()
+    assert first["name"] == "ann"
+    assert [row["name"] for row in await reader.read_all()] == ["bob", "cy"]
+
+
+@pytest.mark.asyncio
+async def test_columns_before_added_columns(write_csv):
+    """Test that include_filename and row_hash are added after the selection."""
+    path = write_csv("a,b\n1,x\n2,y\n")
+    reader = Reader(path, columns=[1], include_filename=True)
+    assert await reader.read_rows(2) == [["b", "_file"], ["x", path]]
+    hashed = await Reader(path, columns=["b"], row_hash=True).read_all()
+    assert hashed[0] == ["b", "_hash"]
+    assert [row[0] for row in hashed[1:]] == ["x", "y"]
+
+
+@pytest.mark.asyncio
+async def test_columns_across_files_and_resume(write_csv):
+    """Test projection over several files, in parallel, and after from_state()."""
+    first = write_csv("k,v,w\n1,a,x\n2,b,y\n")
+    second = write_csv("k,v,w\n3,c,z\n")
+    expected = [["w", "k"], ["x", "1"], ["y", "2"], ["z", "3"]]
+    for concurrency in (1, 2):
+        reader = Reader([first, second], columns=["w", "k"], concurrency=concurrency)
+        assert [row async for row in reader] == expected
+
+    reader = Reader([first, second], columns=["w", "k"])
+    assert await reader.read_rows(2) == expected[:2]
+    resumed = Reader.from_state(reader.state(), columns=["w", "k"])
+    assert [row async for row in resumed] == expected[2:]
+
+
+@pytest.mark.asyncio
+async def test_columns_errors(write_csv):
+    """Test that bad selections are rejected."""
+    path = write_csv(WIDE)
+    with pytest.raises(ValueError, match="'missing' is not in the header"):
+        await Reader(path, columns=["missing"]).read_row()
+    with pytest.raises(ValueError, match="more than once"):
+        await Reader(path, columns=["id", 0]).read_all()
+    with pytest.raises(ValueError, match="header row"):
+        Reader(path, columns=["id"], has_header=False)
+    with pytest.raises(ValueError, match="at least one"):
+        Reader(path, columns=[])
+    with pytest.raises(TypeError, match="not a string"):
+        Reader(path, columns="id")
+    with pytest.raises(TypeError):
+        Reader(path, columns=[1.5])
@@ -0,0 +1,58 @@
+"""Test dialect and encoding conversion with convert()."""
+
+import pytest
+
+from rapcsv import Dialect, convert
+
+
+def _read(path):
+    with open(path, "rb") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_convert_dialect_and_encoding(write_csv):
+    """Test semicolon, CRLF and Latin-1 into comma, LF and UTF-8."""
+    src = write_csv('name;city;note\r\nJos\xe9;K\xf6ln;"a;b"\r\nAnn;Paris;1,5\r\n'.encode("latin-1"))
+    dst = src + ".out"
+    count = await convert(
+        src,
+        dst,
+        from_dialect=Dialect(delimiter=";"),
+        to_dialect=Dialect(lineterminator="\n"),
+        from_encoding="latin-1",
+    )
+    assert count == 3
+    assert _read(dst).decode("utf-8") == 'name,city,note\nJosé,Köln,a;b\nAnn,Paris,"1,5"\n'
+
+    # And back again, quoting every field with the unix preset
+    back = dst + ".back"
+    await convert(dst, back, to_dialect="unix", to_encoding="cp1252")
+    assert _read(back) == '"name","city","note"\n"José","Köln","a;b"\n"Ann","Paris","1,5"\n'.encode(
+        "cp1252"
+    )
+
+
+@pytest.mark.asyncio
+async def test_convert_bom_and_cp1252(write_csv):
+    """Test byte order marks and the cp1252 characters Latin-1 lacks."""
+    src = write_csv(b"\xef\xbb\xbfprice\r\n\xe2\x82\xac5\r\n")
+    dst = src + ".out"
+    await convert(src, dst, from_encoding="utf-8-sig", to_encoding="windows-1252")
+    assert _read(dst) == b"price\r\n\x805\r\n"
+    await convert(dst, src, from_encoding="cp1252", to_encoding="UTF_8_SIG")
+    assert _read(src) == b"\xef\xbb\xbfprice\r\n\xe2\x82\xac5\r\n"
+
+
+@pytest.mark.asyncio
+async def test_convert_errors(write_csv):
+    """Test unsupported encodings and unencodable characters."""
+    src = write_csv("a\n€\n".encode())
+    dst = src + ".out"
+    with pytest.raises(ValueError, match="Unsupported encoding 'utf-16'"):
+        await convert(src, dst, to_encoding="utf-16")
+    with pytest.raises(ValueError, match="record 2 cannot be encoded in latin-1"):
+        await convert(src, dst, to_encoding="latin-1")
+    with pytest.raises(ValueError, match="is the input file"):
+        await convert(src, src, to_dialect="excel-tab")
+    assert _read(src) == "a\n€\n".encode()
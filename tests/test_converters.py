@@ -0,0 +1,43 @@
+"""Test Reader(converters=...), which applies Python callables per column."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_converters_by_name_and_position(write_csv):
+    """Test converters alone and applied after schema conversion."""
+    test_file = write_csv("sku,qty,name\n ab-1 ,2,x\ncd-2,,y\n")
+    try:
+        reader = Reader(test_file, converters={"sku": lambda v: v.strip().upper()})
+        assert await reader.read_all() == [["AB-1", "2", "x"], ["CD-2", "", "y"]]
+
+        reader = Reader(
+            test_file,
+            schema={"qty": "int"},
+            converters={1: lambda v: 0 if v is None else v * 10, "name": str.upper},
+            row_type="tuple",
+        )
+        assert await reader.read_rows(10) == [(" ab-1 ", 20, "X"), ("cd-2", 0, "Y")]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_converter_errors(write_csv):
+    """Test that non-callables are rejected and converter exceptions propagate."""
+    with pytest.raises(TypeError, match="not callable"):
+        Reader("data.csv", converters={"a": "upper"})
+
+    def fail(value):
+        raise KeyError(value)
+
+    test_file = write_csv("a\n1\n")
+    try:
+        with pytest.raises(KeyError):
+            await Reader(test_file, converters={"a": fail}).read_row()
+    finally:
+        os.unlink(test_file)
@@ -0,0 +1,97 @@
+"""Test streaming row deduplication with dedupe()."""
+
+import os
+
+import pytest
+
+from rapcsv import dedupe
+
+
+def _read_rows(path):
+    with open(path, newline="") as f:
+        return [line.split(",") for line in f.read().split("\r\n") if line]
+
+
+@pytest.mark.asyncio
+async def test_dedupe_full_row(write_csv):
+    """Test removing exact duplicate rows while keeping order."""
+    src = write_csv("a,b\n1,x\n2,y\n1,x\n3,z\n2,y\n")
+    dst = src + ".out.csv"
+
+    try:
+        removed = await dedupe(src, dst)
+        assert removed == 2
+        assert _read_rows(dst) == [["a", "b"], ["1", "x"], ["2", "y"], ["3", "z"]]
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_dedupe_by_key(write_csv):
+    """Test that only key columns decide duplicates; the first row wins."""
+    src = write_csv("id,value\n1,first\n2,other\n1,second\n")
+    dst = src + ".out.csv"
+
+    try:
+        removed = await dedupe(src, dst, key="id")
+        assert removed == 1
+        assert _read_rows(dst) == [["id", "value"], ["1", "first"], ["2", "other"]]
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_dedupe_spill_matches_in_memory(write_csv):
+    """Test that the spill strategy gives the same result as the hash set."""
+    rows = [(i * 7 % 50, i % 3) for i in range(500)]
+    src = write_csv("k,v\n" + "".join(f"{k},{v}\n" for k, v in rows))
+    in_memory = src + ".mem.csv"
+    spilled = src + ".spill.csv"
+
+    try:
+        removed_mem = await dedupe(src, in_memory, key=["k", "v"])
+        removed_spill = await dedupe(src, spilled, key=["k", "v"], max_keys=10)
+        assert removed_mem == removed_spill == 500 - len(set(rows))
+        assert _read_rows(in_memory) == _read_rows(spilled)
+    finally:
+        for path in (src, in_memory, spilled):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_dedupe_keep_last(write_csv):
+    """Test keeping the last occurrence of each key, in memory and spilled."""
+    src = write_csv("id,value\n1,old\n2,only\n1,mid\n3,a\n1,new\n3,b\n")
+    in_memory = src + ".mem.csv"
+    spilled = src + ".spill.csv"
+
+    try:
+        removed = await dedupe(src, in_memory, key="id", keep="last")
+        assert removed == 3
+        assert _read_rows(in_memory) == [["id", "value"], ["2", "only"], ["1", "new"], ["3", "b"]]
+        assert await dedupe(src, spilled, key="id", keep="last", max_keys=1) == 3
+        assert _read_rows(spilled) == _read_rows(in_memory)
+        with pytest.raises(ValueError, match="keep must be 'first' or 'last'"):
+            await dedupe(src, in_memory, keep="latest")
+    finally:
+        for path in (src, in_memory, spilled):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_dedupe_in_place_is_refused(write_csv):
+    """Test that dedupe(p, p) raises instead of truncating the input."""
+    src = write_csv("a\n1\n1\n")
+    try:
+        with pytest.raises(ValueError, match="is the input file"):
+            await dedupe(src, src)
+        with open(src) as f:
+            assert f.read() == "a\n1\n1\n"
+    finally:
+        os.unlink(src)
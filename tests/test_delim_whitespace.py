@@ -0,0 +1,47 @@
+"""Test Reader(delim_whitespace=True)."""
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_runs_of_spaces_and_tabs_separate_fields(write_csv):
+    """Test aligned columns, blank lines, quoted fields and every read mode."""
+    content = (
+        b"  id   name         temp\r\n"
+        b"\r\n"
+        b"   1   \"New York\"   21.5\t\r\n"
+        b"2\t\t\"a \"\"b\"\"\"\t-3\r\n"
+        b"3 \xc3\xa9t\xc3\xa9 \"\"\n"
+    )
+    test_file = write_csv(content, suffix=".txt")
+    expected = [
+        ["id", "name", "temp"],
+        ["1", "New York", "21.5"],
+        ["2", 'a "b"', "-3"],
+        ["3", "été", ""],
+    ]
+    assert await Reader(test_file, delim_whitespace=True).read_all() == expected
+
+    reader = Reader(test_file, delim_whitespace=True, read_size=4)
+    assert [row async for row in reader] == expected
+    assert reader.line_num == 5
+
+    reader = Reader(test_file, delim_whitespace=True, engine="fast")
+    assert await reader.read_all(threads=2) == expected
+
+    reader = Reader(test_file, delim_whitespace=True, row_type="row")
+    assert (await reader.read_row()).temp == "21.5"
+
+    # Restored readers keep splitting on whitespace
+    reader = Reader(test_file, delim_whitespace=True)
+    await reader.read_rows(2)
+    resumed = Reader.from_state(reader.state())
+    assert await resumed.read_all() == expected[2:]
+
+
+def test_delim_whitespace_excludes_delimiter():
+    """Test that an explicit delimiter cannot be combined with delim_whitespace."""
+    with pytest.raises(ValueError, match="delim_whitespace"):
+        Reader("data.txt", delimiter=";", delim_whitespace=True)
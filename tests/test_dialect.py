@@ -0,0 +1,93 @@
+"""Test the Dialect class, its presets and dialect= on the CSV classes."""
+
+import csv
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import AsyncDictReader, AsyncDictWriter, Dialect, Reader, Writer
+
+
+def _temp_path():
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        path = f.name
+    os.unlink(path)
+    return path
+
+
+def test_presets_mirror_csv_module():
+    """Test the preset attributes, lookup by name and value semantics."""
+    assert Dialect.excel == Dialect() == Dialect.preset("excel")
+    assert Dialect.excel_tab.delimiter == "\t"
+    assert Dialect.preset("excel-tab") == Dialect.excel_tab
+    assert Dialect.unix.lineterminator == "\n"
+    assert Dialect.unix.quoting == 2  # QUOTE_ALL, as in csv.unix_dialect
+    assert Dialect.excel.doublequote is Dialect.excel.double_quote is True
+
+    pipes = Dialect(delimiter="|", quotechar="'")
+    assert pipes.replace(delimiter=";") == Dialect(delimiter=";", quotechar="'")
+    assert pipes.delimiter == "|"  # replace() returns a copy
+    assert pipes.to_dict()["quotechar"] == "'"
+    assert repr(pipes).startswith("Dialect(delimiter='|', quotechar=\"'\"")
+
+    with pytest.raises(ValueError, match="Unknown dialect 'nope'"):
+        Dialect.preset("nope")
+    with pytest.raises(ValueError, match="line break"):
+        Dialect(delimiter="\n")
+
+
+@pytest.mark.asyncio
+async def test_dialect_argument_on_reader_and_writer():
+    """Test Dialect objects, preset names and explicit overrides."""
+    path = _temp_path()
+    try:
+        writer = Writer(path, dialect="excel-tab")
+        await writer.write_row(["a", "b c"])
+        await writer.close()
+        with open(path, "rb") as f:
+            assert f.read() == b"a\tb c\r\n"
+
+        assert await Reader(path, dialect=Dialect.excel_tab).read_all() == [["a", "b c"]]
+        # An explicit option overrides the dialect's value
+        assert await Reader(path, dialect="excel-tab", delimiter=",").read_all() == [
+            ["a\tb c"]
+        ]
+        with pytest.raises(ValueError, match="Unknown dialect"):
+            Reader(path, dialect="excel_tabs")
+        with pytest.raises(TypeError, match="dialect must be"):
+            Reader(path, dialect=5)
+    finally:
+        if os.path.exists(path):
+            os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_csv_module_dialects_and_dict_classes():
+    """Test csv.Dialect objects, with quoting translated, and the dict classes."""
+    path = _temp_path()
+    try:
+        class Semicolons(csv.Dialect):
+            delimiter = ";"
+            quotechar = '"'
+            doublequote = True
+            skipinitialspace = False
+            lineterminator = "\n"
+            quoting = csv.QUOTE_MINIMAL
+
+        writer = AsyncDictWriter(path, fieldnames=["name", "city"], dialect=Semicolons)
+        await writer.writeheader()
+        await writer.writerow({"name": "Ann", "city": "Oslo; Norway"})
+        await writer.close()
+        with open(path, "rb") as f:
+            assert f.read() == b'name;city\nAnn;"Oslo; Norway"\n'
+
+        reader = AsyncDictReader(path, dialect=Semicolons())
+        assert await reader.read_row() == {"name": "Ann", "city": "Oslo; Norway"}
+        assert await Reader(path, dialect=csv.excel_tab).read_all() == [
+            ["name;city"],
+            ['Ann;"Oslo; Norway"'],
+        ]
+    finally:
+        if os.path.exists(path):
+            os.unlink(path)
@@ -0,0 +1,104 @@
+"""Test Reader.dialect(), the report of the format the data turned out to use."""
+
+import gzip
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_line_endings(write_csv):
+    """Test that the first record's line ending is reported, quoted breaks skipped."""
+    cases = [
+        (b"id,name\r\n1,a\r\n", "\r\n"),
+        (b'"i\r\nd",name\n1,a\n', "\n"),
+        (b"id,name\r1,a\r", "\r"),
+    ]
+    for content, terminator in cases:
+        path = write_csv(content)
+        try:
+            report = await Reader(path).dialect()
+            assert report["lineterminator"] == terminator
+            assert report["bom"] is False
+            assert report["encoding"] == "utf-8"
+        finally:
+            os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_bom_and_delimiter(write_csv):
+    """Test reporting a byte order mark and the configured dialect."""
+    path = write_csv(b"\xef\xbb\xbfid;name\n1;a\n")
+    try:
+        async with Reader(path, delimiter=";") as reader:
+            report = await reader.dialect()
+            assert await reader.read_row() == ["id", "name"]
+        assert report["bom"] is True
+        assert report["encoding"] == "utf-8-sig"
+        assert report["delimiter"] == ";"
+        assert report["quotechar"] == '"'
+        assert report["quoting"] == 1
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_after_reading(write_csv):
+    """Test that the report is the same once rows have been read, or all of them."""
+    path = write_csv(b"id,name\n1,a\n2,b\n")
+    try:
+        reader = Reader(path)
+        await reader.read_row()
+        assert (await reader.dialect())["lineterminator"] == "\n"
+        for mmap in (False, True):
+            reader = Reader(path, mmap=mmap)
+            assert len(await reader.read_all()) == 3
+            assert (await reader.dialect())["lineterminator"] == "\n"
+        # Nothing to look at in an empty file
+        empty = write_csv(b"")
+        try:
+            report = await Reader(empty).dialect()
+            assert report["lineterminator"] == "\r\n"
+            assert report["bom"] is None
+        finally:
+            os.unlink(empty)
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_first_record_across_chunks(write_csv):
+    """Test a first record longer than read_size and a CRLF split between chunks."""
+    long_record = b"id," + b"x" * 50 + b"\r\n1,a\r\n"
+    path = write_csv(long_record)
+    try:
+        for read_size in (8, 52, 53, 54):
+            report = await Reader(path, read_size=read_size).dialect()
+            assert report["lineterminator"] == "\r\n", read_size
+            reader = Reader(path, read_size=read_size)
+            assert await reader.read_row() == ["id", "x" * 50]
+            assert (await reader.dialect())["lineterminator"] == "\r\n"
+    finally:
+        os.unlink(path)
+
+
+class _ByteStream:
+    """Async binary stream returning at most `size` bytes per read."""
+
+    def __init__(self, content):
+        self.content = content
+
+    async def read(self, size):
+        chunk, self.content = self.content[:size], self.content[size:]
+        return chunk
+
+
+@pytest.mark.asyncio
+async def test_gzip_stream_with_empty_first_chunk():
+    """Test a gzip stream whose first chunk decompresses to nothing."""
+    body = gzip.compress(b"\xef\xbb\xbfid,name\r\n1,a\r\n")
+    report = await Reader(_ByteStream(body), read_size=4).dialect()
+    assert report["lineterminator"] == "\r\n"
+    assert report["bom"] is True
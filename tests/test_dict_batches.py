@@ -0,0 +1,66 @@
+"""Test AsyncDictReader.read_rows() and the key strings its dicts share."""
+
+import os
+
+import pytest
+
+from rapcsv import AsyncDictReader
+
+
+@pytest.mark.asyncio
+async def test_read_rows_in_batches(write_csv):
+    """Test batches of dicts, ending with an empty list at EOF."""
+    path = write_csv("id,name\n" + "".join(f"{i},n{i}\n" for i in range(5)))
+    try:
+        reader = AsyncDictReader(path)
+        assert await reader.read_rows(3) == [{"id": str(i), "name": f"n{i}"} for i in range(3)]
+        assert await reader.read_row() == {"id": "3", "name": "n3"}
+        assert await reader.read_rows(3) == [{"id": "4", "name": "n4"}]
+        assert await reader.read_rows(3) == []
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_dicts_share_key_objects(write_csv):
+    """Test that every dict uses the same key string objects, across calls."""
+    columns = [f"column_{i}" for i in range(200)]
+    path = write_csv(",".join(columns) + "\n" + ("1," * 199 + "1\n") * 10)
+    try:
+        reader = AsyncDictReader(path)
+        rows = await reader.read_rows(5) + [await reader.read_row()]
+        first = list(rows[0])
+        assert first == columns
+        for row in rows[1:]:
+            assert all(a is b for a, b in zip(first, row))
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_short_and_long_rows(write_csv):
+    """Test restval, restkey and keep_first in batches."""
+    path = write_csv("a,b,a\n1\n1,2,3,4,5\n")
+    try:
+        reader = AsyncDictReader(
+            path, restkey="extra", restval="-", duplicate_headers="keep_first"
+        )
+        assert await reader.read_rows(10) == [
+            {"a": "1", "b": "-"},
+            {"a": "1", "b": "2", "extra": ["4", "5"]},
+        ]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_changed_fieldnames(write_csv):
+    """Test that keys follow fieldnames renamed between reads."""
+    path = write_csv("id,name\n1,a\n2,b\n")
+    try:
+        reader = AsyncDictReader(path)
+        assert await reader.read_rows(1) == [{"id": "1", "name": "a"}]
+        await reader.rename_field("name", "label")
+        assert await reader.read_rows(1) == [{"id": "2", "label": "b"}]
+    finally:
+        os.unlink(path)
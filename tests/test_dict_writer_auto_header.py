@@ -0,0 +1,55 @@
+"""Test AsyncDictWriter without fieldnames, which takes the header from the first row."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import AsyncDictWriter, CsvIoError
+
+
+@pytest.mark.asyncio
+async def test_header_from_first_row():
+    """Test that the first dict fixes the column order and writes the header."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "out.csv")
+        writer = AsyncDictWriter(path, lineterminator="\n", restval="-")
+        await writer.writerow({"name": "Alice", "age": "30"})
+        await writer.writerow({"age": "25", "name": "Bob"})
+        await writer.writerows([{"name": "Carol"}])
+        with pytest.raises(ValueError, match="not in fieldnames: city"):
+            await writer.writerow({"name": "Dan", "city": "NYC"})
+        with pytest.raises(ValueError, match="writeheader"):
+            await writer.writeheader()
+        await writer.close()
+        with open(path) as f:
+            assert f.read() == "name,age\nAlice,30\nBob,25\nCarol,-\n"
+
+
+@pytest.mark.asyncio
+async def test_header_from_writerows():
+    """Test that writerows() writes the derived header before its first row."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "out.csv")
+        writer = AsyncDictWriter(path, lineterminator="\n", extrasaction="ignore")
+        await writer.writerows([{"a": "1", "b": "2"}, {"b": "3", "c": "4"}])
+        await writer.close()
+        with open(path) as f:
+            assert f.read() == "a,b\n1,2\n,3\n"
+
+
+@pytest.mark.asyncio
+async def test_failed_first_row_leaves_fieldnames_unset():
+    """Test that fieldnames are only kept once the header taken with them is written."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "missing", "out.csv")
+        writer = AsyncDictWriter(path, lineterminator="\n")
+        with pytest.raises(CsvIoError):
+            await writer.writerow({"a": "1"})
+        with pytest.raises(TypeError):
+            await writer.writerows([{"b": "2"}, "not a dict"])
+        os.mkdir(os.path.join(tmp, "missing"))
+        await writer.writerow({"c": "3"})
+        await writer.close()
+        with open(path) as f:
+            assert f.read() == "c\n3\n"
@@ -0,0 +1,103 @@
+"""Test key-based file comparison with diff()."""
+
+import os
+
+import pytest
+
+from rapcsv import diff
+
+
+def _cleanup(*paths):
+    for path in paths:
+        if os.path.exists(path):
+            os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_diff_added_removed_changed(write_csv):
+    """Test all three kinds of difference, with reordered columns in b."""
+    a = write_csv("id,name,city\n1,Alice,NYC\n2,Bob,LA\n3,Carol,SF\n")
+    b = write_csv("city,id,name\nNYC,1,Alice\nBoston,2,Bob\nParis,4,Dan\n")
+
+    try:
+        result = await diff(a, b, key="id")
+        assert result["added"] == [{"id": "4", "name": "Dan", "city": "Paris"}]
+        assert result["removed"] == [{"id": "3", "name": "Carol", "city": "SF"}]
+        assert result["changed"] == [
+            {
+                "key": {"id": "2"},
+                "before": {"id": "2", "name": "Bob", "city": "LA"},
+                "after": {"id": "2", "name": "Bob", "city": "Boston"},
+            }
+        ]
+    finally:
+        _cleanup(a, b)
+
+
+@pytest.mark.asyncio
+async def test_diff_changed_columns_only(write_csv):
+    """Test limiting changed rows to the differing columns."""
+    a = write_csv("id,name,city\n1,Alice,NYC\n")
+    b = write_csv("id,name,city\n1,Alice,LA\n")
+
+    try:
+        result = await diff(a, b, key=["id"], changed_columns_only=True)
+        assert result["changed"] == [
+            {"key": {"id": "1"}, "before": {"id": "1", "city": "NYC"}, "after": {"id": "1", "city": "LA"}}
+        ]
+    finally:
+        _cleanup(a, b)
+
+
+@pytest.mark.asyncio
+async def test_diff_report_file(write_csv):
+    """Test streaming differences to a report CSV."""
+    a = write_csv("id,v\n1,a\n2,b\n")
+    b = write_csv("id,v\n2,c\n3,d\n")
+    dst = a + ".diff.csv"
+
+    try:
+        counts = await diff(a, b, key="id", dst=dst)
+        assert counts == {"added": 1, "removed": 1, "changed": 1}
+        with open(dst, newline="") as f:
+            lines = [line for line in f.read().split("\r\n") if line]
+        assert lines == [
+            "_change,id,v",
+            "removed,1,a",
+            "before,2,b",
+            "after,2,c",
+            "added,3,d",
+        ]
+    finally:
+        _cleanup(a, b, dst)
+
+
+@pytest.mark.asyncio
+async def test_diff_different_columns(write_csv):
+    """Test that differing column sets raise ValueError."""
+    a = write_csv("id,v\n1,a\n")
+    b = write_csv("id,w\n1,a\n")
+
+    try:
+        with pytest.raises(ValueError, match="different columns"):
+            await diff(a, b, key="id")
+    finally:
+        _cleanup(a, b)
+
+
+@pytest.mark.asyncio
+async def test_diff_report_onto_input_is_refused(write_csv):
+    """Test that a report path naming either input raises instead of truncating it."""
+    a = write_csv("id,v\n1,a\n")
+    b = write_csv("id,v\n1,b\n")
+
+    try:
+        for dst in (a, b):
+            with pytest.raises(ValueError, match="is the input file"):
+                await diff(a, b, key="id", dst=dst)
+        with open(a) as f:
+            assert f.read() == "id,v\n1,a\n"
+        with open(b) as f:
+            assert f.read() == "id,v\n1,b\n"
+    finally:
+        _cleanup(a, b)
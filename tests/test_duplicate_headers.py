@@ -0,0 +1,104 @@
+"""Test the duplicate_headers= policy for headers that name a column twice."""
+
+import os
+
+import pytest
+
+from rapcsv import AsyncDictReader, CSVError, Reader
+
+CONTENT = "id,name,id,id_1\n1,a,2,3\n"
+
+
+@pytest.mark.asyncio
+async def test_keep_first_is_the_default(write_csv):
+    """Test that names are left alone and a name refers to its first column."""
+    path = write_csv(CONTENT)
+    try:
+        reader = Reader(path, row_type="row")
+        row = await reader.read_row()
+        assert row["id"] == "1"
+        assert await Reader(path, columns=["id"]).read_all() == [["id"], ["1"]]
+        assert await Reader(path).read_row() == ["id", "name", "id", "id_1"]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_suffix_renames_repeats(write_csv):
+    """Test that repeats are renamed, skipping suffixes the header already uses."""
+    path = write_csv(CONTENT)
+    try:
+        expected = ["id", "name", "id_2", "id_1"]
+        assert await Reader(path, duplicate_headers="suffix").read_row() == expected
+        rows = await Reader(path, duplicate_headers="suffix").read_all()
+        assert rows == [expected, ["1", "a", "2", "3"]]
+
+        reader = Reader(path, duplicate_headers="suffix", row_type="row")
+        row = await reader.read_row()
+        assert (row["id"], row["id_2"], row["id_1"]) == ("1", "2", "3")
+
+        typed = Reader(path, duplicate_headers="suffix", schema={"id_2": "int"})
+        assert await typed.read_row() == ["1", "a", 2, "3"]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_suffix_with_columns(write_csv):
+    """Test that columns= resolves renamed names and returns them in the header."""
+    path = write_csv(CONTENT)
+    try:
+        expected = [["id_2", "name"], ["2", "a"]]
+        reader = Reader(path, duplicate_headers="suffix", columns=["id_2", "name"])
+        assert await reader.read_all() == expected
+        reader = Reader(path, duplicate_headers="suffix", columns=["id_2", "name"])
+        assert [await reader.read_row(), await reader.read_row()] == expected
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_error_policy(write_csv):
+    """Test that "error" raises CSVError when the header is read."""
+    path = write_csv(CONTENT)
+    try:
+        with pytest.raises(CSVError, match="Duplicate column name 'id'"):
+            await Reader(path, duplicate_headers="error").read_row()
+        with pytest.raises(CSVError):
+            await Reader(path, duplicate_headers="error").read_all()
+        with pytest.raises(CSVError):
+            await AsyncDictReader(path, duplicate_headers="error").read_row()
+
+        unique = write_csv("a,b\n1,2\n")
+        try:
+            assert await Reader(unique, duplicate_headers="error").read_all() == [
+                ["a", "b"],
+                ["1", "2"],
+            ]
+        finally:
+            os.unlink(unique)
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_dict_reader(write_csv):
+    """Test DictReader keys under each policy."""
+    path = write_csv("id,name,id\n1,a,2\n")
+    try:
+        assert await AsyncDictReader(path).read_row() == {"id": "2", "name": "a"}
+        reader = AsyncDictReader(path, duplicate_headers="keep_first")
+        assert await reader.read_row() == {"id": "1", "name": "a"}
+        reader = AsyncDictReader(path, duplicate_headers="suffix")
+        assert await reader.read_row() == {"id": "1", "name": "a", "id_1": "2"}
+        assert await reader.get_fieldnames() == ["id", "name", "id_1"]
+    finally:
+        os.unlink(path)
+
+
+def test_invalid_policy():
+    """Test that an unknown policy is rejected."""
+    with pytest.raises(ValueError, match="duplicate_headers"):
+        Reader("data.csv", duplicate_headers="rename")
+    with pytest.raises(ValueError, match="duplicate_headers"):
+        AsyncDictReader("data.csv", duplicate_headers="rename")
@@ -0,0 +1,56 @@
+"""Test quoted fields containing LF and CRLF newlines across every read path."""
+
+import pytest
+
+from rapcsv import Reader
+
+
+CASES = [
+    # LF terminators with LF, CRLF and a blank line inside quotes
+    (b'id,note\n1,"a\nb"\n2,"c\r\nd"\n3,"e\n\nf"\n', ["a\nb", "c\r\nd", "e\n\nf"]),
+    # CRLF terminators with the same fields
+    (b'id,note\r\n1,"a\nb"\r\n2,"c\r\nd"\r\n3,"e\n\nf"\r\n', ["a\nb", "c\r\nd", "e\n\nf"]),
+    # A lone CR inside quotes, and a newline right after the opening quote
+    (b'id,note\r\n1,"\r"\r\n2,"\nx"\r\n', ["\r", "\nx"]),
+]
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("content,notes", CASES)
+async def test_streaming_keeps_quoted_newlines(content, notes, write_csv):
+    """Test that the record parser, not line splitting, ends records at any chunk size."""
+    test_file = write_csv(content)
+    expected = [["id", "note"]] + [[str(i), note] for i, note in enumerate(notes, 1)]
+    for read_size in (1, 2, 3, 4, 7, 8192):
+        reader = Reader(test_file, read_size=read_size)
+        rows = [row async for row in reader]
+        assert rows == expected, read_size
+        assert reader.line_num == content.count(b"\n")
+        assert reader.byte_offset == len(content)
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("terminator", [b"\n", b"\r\n"])
+async def test_read_all_counts_lines_inside_quotes(terminator, write_csv):
+    """Test whole-file, range-split, fast-engine and multi-file parsing agree."""
+    records = [b'%d,"line one%sline two\nline three"' % (i, terminator) for i in range(500)]
+    content = terminator.join([b"id,text"] + records) + terminator
+    test_file = write_csv(content)
+    expected = await Reader(test_file, read_size=5).read_rows(1000)
+    assert len(expected) == 501
+    assert expected[1] == ["0", "line one%sline two\nline three" % terminator.decode()]
+    lines = content.count(b"\n")
+
+    for kwargs, threads in [
+        ({}, None),
+        ({}, 4),
+        ({"engine": "fast"}, 4),
+        ({"mmap": True}, 3),
+    ]:
+        reader = Reader(test_file, **kwargs)
+        assert await reader.read_all(threads=threads) == expected
+        assert reader.line_num == lines, (kwargs, threads)
+
+    reader = Reader([test_file, test_file], concurrency=2)
+    assert await reader.read_all() == expected + expected[1:]
+    assert reader.line_num == 2 * lines
@@ -0,0 +1,63 @@
+"""Test telling empty fields from quoted empty strings with empty_as_none=True."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_empty_and_quoted_empty(write_csv):
+    """Test that empty fields are None, quoted empty strings "" and the header untouched."""
+    path = write_csv('id,a,b\r\n1,,""\r\n2,"",\r\n,"x""y",\x00z\r\n')
+    try:
+        rows = await Reader(path, empty_as_none=True).read_all()
+        assert rows == [
+            ["id", "a", "b"],
+            ["1", None, ""],
+            ["2", "", None],
+            [None, 'x"y', "\x00z"],
+        ]
+        # Without the option both read as ""
+        assert (await Reader(path).read_all())[1] == ["1", "", ""]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_row_types_and_columns(write_csv):
+    """Test that rows, raw rows, projected columns and read_columns() keep the None."""
+    path = write_csv('id,a,b\n1,,""\n2,"",\n')
+    try:
+        reader = Reader(path, empty_as_none=True, row_type="row")
+        assert [(row.a, row.b) async for row in reader] == [(None, ""), ("", None)]
+        reader = Reader(path, empty_as_none=True, raw=True)
+        assert (await reader.read_all())[1:] == [[b"1", None, b""], [b"2", b"", None]]
+        reader = Reader(path, empty_as_none=True, columns=["b", "id"])
+        assert (await reader.read_all())[1:] == [["", "1"], [None, "2"]]
+        columns = await Reader(path, empty_as_none=True).read_columns()
+        assert columns == {"id": ["1", "2"], "a": [None, ""], "b": ["", None]}
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_schema_and_dialect(write_csv):
+    """Test typed columns and a dialect with another delimiter and quote."""
+    path = write_csv("n;s\n;''\n7;\n")
+    try:
+        reader = Reader(
+            path, empty_as_none=True, delimiter=";", quotechar="'", schema={"n": "int"}
+        )
+        assert await reader.read_all() == [[None, ""], [7, None]]
+    finally:
+        os.unlink(path)
+
+
+def test_unsupported_options():
+    """Test that options parsing without the record's bytes are rejected."""
+    with pytest.raises(ValueError, match="empty_as_none"):
+        Reader(["a.csv", "b.csv"], empty_as_none=True, concurrency=2)
+    with pytest.raises(ValueError, match="empty_as_none"):
+        Reader("data.csv", empty_as_none=True, delim_whitespace=True)
@@ -0,0 +1,74 @@
+"""Test encrypted files written by Writer and read by Reader."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+KEY = bytes(range(32))
+
+
+def _temp_path():
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv.enc") as f:
+        return f.name
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("count", [0, 2, 20000])
+async def test_encrypted_round_trip(count):
+    """Test that rows survive encryption across segment boundaries, with no plaintext on disk."""
+    path = _temp_path()
+    os.unlink(path)
+    rows = [["id", "email"]] + [[str(i), f"user{i}@example.com"] for i in range(count)]
+    try:
+        async with Writer(path, encryption_key=KEY) as writer:
+            await writer.writerows(rows)
+        with open(path, "rb") as f:
+            data = f.read()
+        assert data.startswith(b"RAPCSV\x00\x01")
+        assert b"example.com" not in data and b"email" not in data
+
+        reader = Reader(path, encryption_key=KEY, read_size=1000)
+        assert [row async for row in reader] == rows
+        assert await Reader(path, encryption_key=KEY).read_all() == rows
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_encryption_errors():
+    """Test wrong keys, tampering and unsupported combinations."""
+    path = _temp_path()
+    os.unlink(path)
+    try:
+        async with Writer(path, encryption_key=KEY) as writer:
+            await writer.writerows([["a", "b"], ["1", "2"]])
+
+        with pytest.raises(IOError, match="wrong key or damaged file"):
+            await Reader(path, encryption_key=bytes(32)).read_all()
+
+        with open(path, "rb") as f:
+            data = bytearray(f.read())
+        data[-1] ^= 1
+        with open(path, "wb") as f:
+            f.write(data)
+        with pytest.raises(IOError, match="wrong key or damaged file"):
+            await Reader(path, encryption_key=KEY).read_row()
+
+        with pytest.raises(ValueError, match="cannot append to encrypted file"):
+            Writer(path, encryption_key=KEY)
+        with pytest.raises(ValueError, match="must be 32 bytes"):
+            Reader(path, encryption_key=b"short")
+        with pytest.raises(ValueError, match="cannot be used with an encrypted file"):
+            Reader(path, encryption_key=KEY, mmap=True)
+        with pytest.raises(ValueError, match="encrypted file"):
+            Writer(path, encryption_key=KEY, lock=True)
+
+        with open(path, "w") as f:
+            f.write("a,b\r\n")
+        with pytest.raises(IOError, match="not a file encrypted by rapcsv"):
+            await Reader(path, encryption_key=KEY).read_all()
+    finally:
+        os.unlink(path)
@@ -0,0 +1,65 @@
+"""Test end-of-file signaling for Reader and AsyncDictReader."""
+
+import os
+
+import pytest
+
+from rapcsv import AsyncDictReader, Reader
+
+
+@pytest.mark.asyncio
+async def test_async_for_stops_at_eof(write_csv):
+    """Test that async for ends at EOF without checking for empty rows."""
+    test_file = write_csv("a,b\n1,2\n")
+    try:
+        assert [row async for row in Reader(test_file)] == [["a", "b"], ["1", "2"]]
+        assert [row async for row in AsyncDictReader(test_file)] == [{"a": "1", "b": "2"}]
+
+        reader = Reader(test_file)
+        await reader.read_rows(2)
+        with pytest.raises(StopAsyncIteration):
+            await reader.__anext__()
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_empty_field_is_not_eof(write_csv):
+    """Test that a line holding only an empty field is distinct from EOF."""
+    test_file = write_csv('a\n""\n\nb\n')
+    try:
+        reader = Reader(test_file, eof="none")
+        rows = []
+        while (row := await reader.read_row()) is not None:
+            rows.append(row)
+        assert rows == [["a"], [""], ["b"]]
+        assert await reader.read_row() is None
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_eof_modes(write_csv):
+    """Test the empty, none and raise values of eof=."""
+    test_file = write_csv("a,b\n")
+    try:
+        reader = Reader(test_file)
+        await reader.read_row()
+        assert await reader.read_row() == []
+
+        reader = Reader(test_file, eof="raise", raw=True)
+        assert await reader.read_row() == [b"a", b"b"]
+        with pytest.raises(EOFError):
+            await reader.read_row()
+
+        reader = AsyncDictReader(test_file, eof="none")
+        assert await reader.read_row() is None
+        reader = AsyncDictReader(test_file, eof="raise")
+        with pytest.raises(EOFError):
+            await reader.read_row()
+        assert await AsyncDictReader(test_file).read_row() == {}
+
+        with pytest.raises(ValueError, match="eof must be"):
+            Reader(test_file, eof="stop")
+    finally:
+        os.unlink(test_file)
@@ -0,0 +1,40 @@
+"""Test the line, byte offset, field and snippet reported for parse errors."""
+
+import pytest
+
+from rapcsv import CsvParseError, EncodingError, Reader, sort
+
+
+@pytest.mark.asyncio
+async def test_parse_error_location(write_csv):
+    """Test that the location is given as attributes and in the message."""
+    test_file = write_csv(b'a,b\n1,2\n"x\ny",z\n3,4,5\n')
+    with pytest.raises(CsvParseError) as info:
+        await Reader(test_file, strict=True).read_all()
+    error = info.value
+    assert (error.row, error.line, error.byte_offset, error.column) == (3, 5, 16, 2)
+    assert error.snippet == "3,4,5"
+    assert '(line 5, byte 16, field 2; near "3,4,5")' in str(error)
+
+
+@pytest.mark.asyncio
+async def test_snippet_is_truncated(write_csv):
+    """Test that a long offending line is shortened in the snippet."""
+    test_file = write_csv(b"a,b\n1,2\n" + b"x," * 100 + b"\n")
+    with pytest.raises(CsvParseError) as info:
+        await Reader(test_file, strict=True).read_all()
+    assert info.value.snippet == "x," * 40 + "..."
+    assert info.value.line == 3
+
+
+@pytest.mark.asyncio
+async def test_encoding_error_location(write_csv):
+    """Test an invalid UTF-8 field found by a whole-file operation."""
+    test_file = write_csv(b"a,b\n1,2\n3,J\xe9\n")
+    out = test_file + ".sorted"
+    with pytest.raises(EncodingError) as info:
+        await sort(test_file, out, by="a")
+    error = info.value
+    assert (error.row, error.line, error.byte_offset, error.column) == (2, 3, 8, 1)
+    assert error.snippet == "3,J�"
+    assert "line 3, byte 8, field 1" in str(error)
@@ -0,0 +1,62 @@
+"""Test the rapcsv exception hierarchy and the location attributes of errors."""
+
+import pytest
+
+import rapcsv
+from rapcsv import (
+    ChecksumError,
+    CsvIoError,
+    CsvParseError,
+    CSVError,
+    CSVFieldCountError,
+    EncodingError,
+    FileChangedError,
+    RapcsvError,
+    Reader,
+    SchemaError,
+)
+
+
+def test_hierarchy():
+    """Test the base classes, the CSVError alias and the default attributes."""
+    assert CSVError is CsvParseError
+    assert issubclass(CsvParseError, RapcsvError)
+    assert issubclass(CSVFieldCountError, CsvParseError)
+    assert issubclass(SchemaError, CsvParseError)
+    assert issubclass(EncodingError, CsvParseError) and issubclass(EncodingError, OSError)
+    assert issubclass(CsvIoError, RapcsvError) and issubclass(CsvIoError, OSError)
+    assert issubclass(FileChangedError, CsvIoError)
+    assert issubclass(ChecksumError, CsvIoError)
+    assert CsvIoError.__module__ == "_rapcsv"
+    for name in ("row", "column", "byte_offset", "field"):
+        assert getattr(CsvIoError("x"), name) is None
+    assert rapcsv.CsvIoError is CsvIoError
+
+
+@pytest.mark.asyncio
+async def test_io_and_parse_errors(write_csv):
+    """Test a missing file and a malformed row, with the row and byte offset it is at."""
+    with pytest.raises(CsvIoError) as info:
+        await Reader("/nonexistent/data.csv").read_row()
+    assert isinstance(info.value, OSError)
+
+    test_file = write_csv(b"a,b\n1,2\n3,4,5\n")
+    with pytest.raises(CsvParseError) as info:
+        await Reader(test_file, strict=True).read_all()
+    assert info.value.row == 2
+    assert info.value.byte_offset == 8
+
+
+@pytest.mark.asyncio
+async def test_encoding_and_schema_errors(write_csv):
+    """Test that invalid UTF-8 and failed conversions name the field."""
+    test_file = write_csv(b"id,name\n1,Jos\xe9\n")
+    with pytest.raises(EncodingError) as info:
+        await Reader(test_file).read_all()
+    assert isinstance(info.value, OSError)
+
+    test_file = write_csv(b"id,name\n1,a\nabc,b\n")
+    with pytest.raises(SchemaError) as info:
+        await Reader(test_file, schema={"id": "int"}).read_all()
+    assert info.value.column == 0
+    assert info.value.field == "abc"
@@ -0,0 +1,75 @@
+"""Test returning the byte spans of fields with field_spans=True."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_spans_cover_raw_fields(write_csv):
+    """Test that each span is the field's raw bytes, quotes included."""
+    content = 'id,name,note\r\n1,"Zoë, B",\r\n\r\n2,x,"a""b\r\nc"\r\n3,,""'.encode()
+    path = write_csv(content)
+    try:
+        rows = await Reader(path, field_spans=True).read_all()
+        assert [row for row, _ in rows] == [
+            ["id", "name", "note"],
+            ["1", "Zoë, B", ""],
+            ["2", "x", 'a"b\r\nc'],
+            ["3", "", ""],
+        ]
+        raw = [[content[start:end] for start, end in spans] for _, spans in rows]
+        assert raw == [
+            [b"id", b"name", b"note"],
+            [b"1", '"Zoë, B"'.encode(), b""],
+            [b"2", b"x", b'"a""b\r\nc"'],
+            [b"3", b"", b'""'],
+        ]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_patch_in_place(write_csv):
+    """Test replacing a field's bytes using its span."""
+    content = b"sku,qty\nA1,5\nB2,7\n"
+    path = write_csv(content)
+    try:
+        async for row, spans in Reader(path, field_spans=True, row_type="row"):
+            if row.sku == "B2":
+                start, end = spans[1]
+        patched = content[:start] + b"9" + content[end:]
+        assert patched == b"sku,qty\nA1,5\nB2,9\n"
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_columns_schema_and_bom(write_csv):
+    """Test projected columns, typed fields and a file starting with a byte order mark."""
+    path = write_csv(b"\xef\xbb\xbfa,b,c\n1,2,3\n")
+    try:
+        reader = Reader(path, field_spans=True, columns=["c", "a"])
+        assert await reader.read_all() == [
+            (["c", "a"], [(7, 8), (3, 4)]),
+            (["3", "1"], [(13, 14), (9, 10)]),
+        ]
+        reader = Reader(path, field_spans=True, schema={"b": "int"})
+        assert await reader.read_rows(5) == [(["1", 2, "3"], [(9, 10), (11, 12), (13, 14)])]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_unsupported(write_csv):
+    """Test that read_columns() and parallel parsing are rejected."""
+    path = write_csv(b"a\n1\n")
+    try:
+        with pytest.raises(ValueError, match="field_spans"):
+            await Reader(path, field_spans=True).read_columns()
+        with pytest.raises(ValueError, match="field_spans"):
+            Reader([path, path], field_spans=True, concurrency=2)
+    finally:
+        os.unlink(path)
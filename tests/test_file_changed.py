@@ -0,0 +1,58 @@
+"""Test that readers detect files truncated or replaced mid-read."""
+
+import os
+import sys
+
+import pytest
+
+from rapcsv import FileChangedError, Reader
+
+
+CONTENT = "".join(f"{i},value {i}\n" for i in range(100))
+
+
+@pytest.mark.asyncio
+async def test_truncation_detected(write_csv):
+    """Test that a file shrinking below the read position raises FileChangedError."""
+    test_file = write_csv(CONTENT)
+    try:
+        reader = Reader(test_file, read_size=16, buffer_size=16)
+        assert await reader.read_row() == ["0", "value 0"]
+        assert await reader.read_row() == ["1", "value 1"]
+        with open(test_file, "r+") as f:
+            f.truncate(4)
+        with pytest.raises(FileChangedError, match="truncated"):
+            await reader.read_rows(100)
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+@pytest.mark.skipif(sys.platform == "win32", reason="file identity is only checked on Unix")
+async def test_replacement_detected(write_csv):
+    """Test that the path being replaced by another file raises FileChangedError."""
+    test_file = write_csv(CONTENT)
+    replacement = write_csv(CONTENT)
+    try:
+        reader = Reader(test_file, read_size=16, buffer_size=16)
+        assert await reader.read_row() == ["0", "value 0"]
+        os.replace(replacement, test_file)
+        with pytest.raises(FileChangedError, match="replaced"):
+            await reader.read_rows(100)
+        assert issubclass(FileChangedError, IOError)
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_append_is_not_a_change(write_csv):
+    """Test that data appended mid-read is read normally."""
+    test_file = write_csv("a,b\n1,2\n")
+    try:
+        reader = Reader(test_file, read_size=4, buffer_size=4)
+        assert await reader.read_row() == ["a", "b"]
+        with open(test_file, "a", newline="") as f:
+            f.write("3,4\n")
+        assert await reader.read_rows(10) == [["1", "2"], ["3", "4"]]
+    finally:
+        os.unlink(test_file)
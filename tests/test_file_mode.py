@@ -0,0 +1,96 @@
+"""Test the permissions of files a Writer creates (file_mode= and file_group=)."""
+
+import os
+import stat
+import sys
+import tempfile
+
+import pytest
+
+from rapcsv import Writer
+
+pytestmark = pytest.mark.skipif(sys.platform == "win32", reason="Unix permissions")
+
+
+def _mode(path):
+    return stat.S_IMODE(os.stat(path).st_mode)
+
+
+@pytest.mark.asyncio
+async def test_file_mode_ignores_umask():
+    """Test that a new file gets exactly file_mode, whatever the umask."""
+    with tempfile.TemporaryDirectory() as d:
+        path = os.path.join(d, "out.csv")
+        old = os.umask(0o077)
+        try:
+            async with Writer(path, file_mode=0o640) as writer:
+                await writer.write_row(["a", "b"])
+        finally:
+            os.umask(old)
+        assert _mode(path) == 0o640
+        with open(path, newline="") as f:
+            assert f.read() == "a,b\r\n"
+
+
+@pytest.mark.asyncio
+async def test_existing_file_keeps_its_mode():
+    """Test that appending to an existing file leaves its permissions alone."""
+    with tempfile.TemporaryDirectory() as d:
+        path = os.path.join(d, "out.csv")
+        with open(path, "w") as f:
+            f.write("a\r\n")
+        os.chmod(path, 0o600)
+        async with Writer(path, file_mode=0o644) as writer:
+            await writer.write_row(["b"])
+        assert _mode(path) == 0o600
+        with open(path, newline="") as f:
+            assert f.read() == "a\r\nb\r\n"
+
+
+@pytest.mark.asyncio
+async def test_file_mode_for_encrypted_and_archive_files():
+    """Test that files created up front, not on first write, get file_mode too."""
+    with tempfile.TemporaryDirectory() as d:
+        encrypted = os.path.join(d, "out.csv.enc")
+        async with Writer(encrypted, encryption_key=b"k" * 32, file_mode=0o600) as writer:
+            await writer.write_row(["a"])
+        assert _mode(encrypted) == 0o600
+
+        archive = os.path.join(d, "out.zip")
+        async with Writer(archive, zip_member="out.csv", file_mode=0o640) as writer:
+            await writer.write_row(["a"])
+        assert _mode(archive) == 0o640
+
+
+@pytest.mark.asyncio
+async def test_file_group():
+    """Test that file_group accepts a gid or a group name."""
+    import grp
+
+    gid = os.getgid()
+    with tempfile.TemporaryDirectory() as d:
+        by_gid = os.path.join(d, "gid.csv")
+        async with Writer(by_gid, file_group=gid) as writer:
+            await writer.write_row(["a"])
+        assert os.stat(by_gid).st_gid == gid
+
+        by_name = os.path.join(d, "name.csv")
+        async with Writer(by_name, file_group=grp.getgrgid(gid).gr_name) as writer:
+            await writer.write_row(["a"])
+        assert os.stat(by_name).st_gid == gid
+
+
+def test_invalid_permissions():
+    """Test that bad modes and unknown groups are rejected up front."""
+    with pytest.raises(ValueError, match="file_mode"):
+        Writer("out.csv", file_mode=0o17777)
+    with pytest.raises(ValueError, match="unknown group"):
+        Writer("out.csv", file_group="no-such-group-rapcsv")
+    r, w = os.pipe()
+    try:
+        with pytest.raises(ValueError):
+            Writer.from_fd(w, file_mode=0o600)
+    finally:
+        os.close(r)
+        os.close(w)
+    assert not os.path.exists("out.csv")
@@ -0,0 +1,46 @@
+"""Test FixedWidthReader."""
+
+import pytest
+
+from rapcsv import FixedWidthReader
+
+DATA = (
+    "00000001Alice Smith         0000123.45\r\n"
+    "\r\n"
+    "00000002Bjørn Ødegård       0000067.80\r\n"
+    "00000003Short\n"
+)
+
+
+@pytest.mark.asyncio
+async def test_widths_and_colspecs(write_csv):
+    """Test cutting columns by widths and by ranges, counting characters."""
+    test_file = write_csv(DATA, suffix=".dat")
+    expected = [
+        ["00000001", "Alice Smith", "0000123.45"],
+        ["00000002", "Bjørn Ødegård", "0000067.80"],
+        ["00000003", "Short", ""],
+    ]
+    reader = FixedWidthReader(test_file, widths=[8, 20, 10], read_size=7)
+    assert [row async for row in reader] == expected
+    assert reader.line_num == 4
+
+    reader = FixedWidthReader(test_file, colspecs=[(0, 8), (28, None)], strip=False)
+    assert await reader.read_row() == ["00000001", "0000123.45"]
+    assert await reader.read_rows(5) == [["00000002", "0000067.80"], ["00000003", ""]]
+    assert await reader.read_row() is None
+
+    async with FixedWidthReader(test_file, widths=[8, 5], strip=False) as reader:
+        assert (await reader.read_row())[1] == "Alice"
+
+
+def test_column_arguments_are_validated():
+    """Test that exactly one of widths and colspecs, with non-empty columns, is needed."""
+    with pytest.raises(ValueError, match="requires widths or colspecs"):
+        FixedWidthReader("data.dat")
+    with pytest.raises(ValueError, match="not both"):
+        FixedWidthReader("data.dat", widths=[1], colspecs=[(0, 1)])
+    with pytest.raises(ValueError, match="positive"):
+        FixedWidthReader("data.dat", widths=[3, 0])
+    with pytest.raises(ValueError, match="must end after it starts"):
+        FixedWidthReader("data.dat", colspecs=[(5, 5)])
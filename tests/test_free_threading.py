@@ -0,0 +1,96 @@
+"""Test sharing readers and writers between threads, as on free-threaded CPython."""
+
+import asyncio
+import os
+import sys
+import sysconfig
+import threading
+
+import pytest
+
+import rapcsv
+from rapcsv import Reader, Writer
+
+THREADS = 8
+
+
+def _run_threads(target):
+    """Run `target(k)` on THREADS threads at once, re-raising the first error."""
+    errors = []
+    barrier = threading.Barrier(THREADS)
+
+    def run(k):
+        try:
+            barrier.wait()
+            target(k)
+        except BaseException as e:  # noqa: BLE001 - reported on the main thread
+            errors.append(e)
+
+    threads = [threading.Thread(target=run, args=(k,)) for k in range(THREADS)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    if errors:
+        raise errors[0]
+
+
+@pytest.mark.skipif(
+    not sysconfig.get_config_var("Py_GIL_DISABLED"), reason="needs a free-threaded build"
+)
+def test_import_keeps_gil_disabled():
+    """Test that importing rapcsv does not re-enable the GIL."""
+    assert rapcsv.Reader is Reader
+    assert not sys._is_gil_enabled()
+
+
+def test_concurrent_read_row(write_csv):
+    """Test that threads sharing a Reader each get whole rows, every row once."""
+    path = write_csv("".join(f"{i},value{i}\n" for i in range(2000)))
+    reader = Reader(path)
+    seen = [[] for _ in range(THREADS)]
+
+    def read(k):
+        async def go():
+            while True:
+                row = await reader.read_row()
+                if not row:
+                    break
+                assert row[1] == f"value{row[0]}"
+                seen[k].append(int(row[0]))
+
+        asyncio.run(go())
+
+    try:
+        _run_threads(read)
+        assert sorted(i for rows in seen for i in rows) == list(range(2000))
+    finally:
+        os.unlink(path)
+
+
+def test_concurrent_write_row(write_csv):
+    """Test that rows written to a shared Writer from threads are never interleaved."""
+    path = write_csv("")
+    writer = Writer(path)
+
+    def write(k):
+        async def go():
+            for i in range(250):
+                await writer.write_row([str(k), str(i), "x" * 40])
+
+        asyncio.run(go())
+
+    async def close():
+        await writer.close()
+
+    try:
+        _run_threads(write)
+        asyncio.run(close())
+        with open(path, newline="") as f:
+            lines = f.read().split("\r\n")[:-1]
+        assert len(lines) == THREADS * 250
+        rows = sorted(tuple(map(int, line.split(",")[:2])) for line in lines)
+        assert rows == [(k, i) for k in range(THREADS) for i in range(250)]
+        assert all(line.endswith("," + "x" * 40) for line in lines)
+    finally:
+        os.unlink(path)
@@ -0,0 +1,98 @@
+"""Test Reader.from_fd() and Writer.from_fd()."""
+
+import os
+import pickle
+import sys
+import tempfile
+
+import pytest
+
+import rapcsv
+from rapcsv import Reader, Writer
+
+requires_unix = pytest.mark.skipif(sys.platform == "win32", reason="from_fd() is Unix-only")
+
+
+@requires_unix
+@pytest.mark.asyncio
+async def test_round_trip_through_pipe():
+    """Test writing into one end of a pipe and reading from the other."""
+    read_fd, write_fd = os.pipe()
+    try:
+        writer = Writer.from_fd(write_fd, lineterminator="\n")
+        await writer.writerows([["name", "age"], ["Alice", "30"]])
+        await writer.close()
+        os.close(write_fd)  # Our descriptor: the writer closed only its duplicate
+        write_fd = None
+
+        reader = Reader.from_fd(read_fd)
+        assert await reader.read_all() == [["name", "age"], ["Alice", "30"]]
+        assert await reader.read_row() == []
+    finally:
+        os.close(read_fd)
+        if write_fd is not None:
+            os.close(write_fd)
+
+
+@requires_unix
+@pytest.mark.asyncio
+async def test_named_temporary_file():
+    """Test reading a NamedTemporaryFile through its descriptor, leaving it open."""
+    with tempfile.NamedTemporaryFile(mode="w+", suffix=".csv", newline="") as f:
+        f.write("a;b\n1;2\n")
+        f.flush()
+        f.seek(0)
+        reader = Reader.from_fd(f.fileno(), delimiter=";")
+        assert await reader.read_all() == [["a", "b"], ["1", "2"]]
+        async with reader:
+            pass
+        os.fstat(f.fileno())  # Still open
+
+
+@requires_unix
+def test_sync_from_fd():
+    """Test the blocking API's from_fd()."""
+    read_fd, write_fd = os.pipe()
+    try:
+        with rapcsv.sync.Writer.from_fd(write_fd) as writer:
+            writer.write_row(["x", "y"])
+        os.close(write_fd)
+        write_fd = None
+        assert rapcsv.sync.Reader.from_fd(read_fd).read_all() == [["x", "y"]]
+    finally:
+        os.close(read_fd)
+        if write_fd is not None:
+            os.close(write_fd)
+
+
+@requires_unix
+def test_from_fd_errors():
+    """Test invalid descriptors and options that need a path."""
+    with pytest.raises(OSError):
+        Reader.from_fd(-1)
+    read_fd, write_fd = os.pipe()
+    try:
+        with pytest.raises(ValueError, match="lock"):
+            Reader.from_fd(read_fd, lock="shared")
+        with pytest.raises(ValueError, match="lock"):
+            Writer.from_fd(write_fd, lock=True)
+    finally:
+        os.close(read_fd)
+        os.close(write_fd)
+
+
+@requires_unix
+def test_descriptors_are_not_paths():
+    """Test that readers and writers over a descriptor can't be pickled or saved."""
+    read_fd, write_fd = os.pipe()
+    try:
+        reader = Reader.from_fd(read_fd)
+        with pytest.raises(TypeError, match="file descriptor"):
+            pickle.dumps(reader)
+        with pytest.raises(ValueError, match="state"):
+            reader.state()
+        with pytest.raises(TypeError, match="file descriptor"):
+            pickle.dumps(Writer.from_fd(write_fd))
+    finally:
+        os.close(read_fd)
+        os.close(write_fd)
@@ -0,0 +1,52 @@
+"""Test gzip byte streams from file-like sources, such as HTTP response bodies."""
+
+import gzip
+
+import pytest
+
+from rapcsv import CsvIoError, Reader
+
+
+class _ByteStream:
+    """Async binary stream returning at most `size` bytes per read, like aiohttp's
+    `response.content`."""
+
+    def __init__(self, content):
+        self.content = content
+
+    async def read(self, size):
+        chunk, self.content = self.content[:size], self.content[size:]
+        return chunk
+
+
+CSV = "id,name\n" + "".join(f"{i},Zoë {i}\n" for i in range(500))
+
+
+@pytest.mark.asyncio
+async def test_gzip_stream_is_decompressed():
+    """Test that a gzip body parses like the plain text, however the reads split it."""
+    expected = [line.split(",") for line in CSV.splitlines()]
+    body = gzip.compress(CSV.encode())
+    for read_size in (1, 7, 1 << 16):
+        reader = Reader(_ByteStream(body), read_size=read_size)
+        assert await reader.read_all() == expected
+
+    # Concatenated members, as written by appending to a .gz file
+    half = len(CSV) // 2
+    cut = CSV.rindex("\n", 0, half) + 1
+    body = gzip.compress(CSV[:cut].encode()) + gzip.compress(CSV[cut:].encode())
+    rows = [row async for row in Reader(_ByteStream(body), read_size=100)]
+    assert rows == expected
+
+
+@pytest.mark.asyncio
+async def test_plain_and_truncated_byte_streams():
+    """Test that uncompressed bytes pass through and a cut-off gzip body fails."""
+    reader = Reader(_ByteStream(CSV.encode()), read_size=5)
+    assert len(await reader.read_all()) == 501
+    reader = Reader(_ByteStream(b"a\n"), read_size=1)
+    assert await reader.read_all() == [["a"]]
+
+    body = gzip.compress(CSV.encode())
+    with pytest.raises(CsvIoError, match="Invalid gzip data"):
+        await Reader(_ByteStream(body[: len(body) // 2])).read_all()
@@ -0,0 +1,47 @@
+"""Test reading only the rows appended since the last run with checkpoint_path=."""
+
+import os
+
+import pytest
+
+from rapcsv import FileChangedError, Reader
+
+
+async def _run(path, checkpoint):
+    """One run of an incremental export job: read what is new, then stop at EOF."""
+    reader = Reader(path, has_header=True, checkpoint_path=checkpoint)
+    return [row async for row in reader]
+
+
+@pytest.mark.asyncio
+async def test_appended_rows_only(write_csv):
+    """Test that each run returns the header once, then only appended rows."""
+    path = write_csv("id,name\n1,a\n2,b\n")
+    checkpoint = path + ".ckpt"
+    try:
+        assert await _run(path, checkpoint) == [["id", "name"], ["1", "a"], ["2", "b"]]
+        assert await _run(path, checkpoint) == []
+        with open(path, "a", newline="") as f:
+            f.write("3,c\n4,d\n")
+        assert await _run(path, checkpoint) == [["3", "c"], ["4", "d"]]
+    finally:
+        for p in (path, checkpoint):
+            if os.path.exists(p):
+                os.unlink(p)
+
+
+@pytest.mark.asyncio
+async def test_changed_header_is_rejected(write_csv):
+    """Test that a file rewritten with other columns raises FileChangedError."""
+    path = write_csv("id,name\n1,a\n")
+    checkpoint = path + ".ckpt"
+    try:
+        await _run(path, checkpoint)
+        with open(path, "w", newline="") as f:
+            f.write("id,email\n1,a@example.com\n2,b@example.com\n")
+        with pytest.raises(FileChangedError, match="Header"):
+            Reader(path, has_header=True, checkpoint_path=checkpoint)
+    finally:
+        for p in (path, checkpoint):
+            if os.path.exists(p):
+                os.unlink(p)
@@ -0,0 +1,89 @@
+"""Test Reader.seek() and the record index shared across Reader instances."""
+
+import pytest
+
+from rapcsv import Reader, clear_index_cache, index_cache_info
+
+
+@pytest.mark.asyncio
+async def test_seek_shares_the_index(write_csv):
+    """Test that readers of one file build its index once, and seek anywhere in it."""
+    path = write_csv("id,note\n" + "".join(f'{i},"a\nb{i}"\n' for i in range(100)))
+    clear_index_cache()
+    first = Reader(path)
+    await first.seek(51)
+    assert await first.read_row() == ["50", "a\nb50"]
+    assert first.line_num == 103
+    info = index_cache_info()
+    assert info["bytes"] >= 101 * 16
+    del info["bytes"]
+    assert info == {"entries": 1, "records": 101, "hits": 0, "misses": 1}
+
+    second = Reader(path)
+    await second.seek(1)
+    assert await second.read_row() == ["0", "a\nb0"]
+    await second.seek(0)  # Backwards, after reading
+    assert await second.read_row() == ["id", "note"]
+    assert index_cache_info()["hits"] == 2
+
+    await second.seek(101)
+    assert await second.read_row() == []
+    with pytest.raises(ValueError, match="past the end"):
+        await second.seek(102)
+
+    # Rows by name keep the header, which is never returned as a row
+    reader = Reader(path, row_type="row")
+    await reader.seek(0)
+    assert (await reader.read_row())["note"] == "a\nb0"
+    await reader.seek(100)
+    assert (await reader.read_row())["id"] == "99"
+
+
+@pytest.mark.asyncio
+async def test_index_rebuilt_when_file_changes(write_csv):
+    """Test that a changed file is indexed again, and what cannot seek."""
+    path = write_csv("a\n1\n")
+    clear_index_cache()
+    reader = Reader(path)
+    await reader.seek(2)
+    assert await reader.read_row() == []
+
+    with open(path, "a") as f:
+        f.write("2\n")
+    reader = Reader(path)
+    await reader.seek(2)
+    assert await reader.read_row() == ["2"]
+    assert index_cache_info()["misses"] == 2
+
+    # A stale index is dropped even when no new one replaces it
+    with open(path, "a") as f:
+        f.write("3\n" * 100)
+    with pytest.raises(MemoryError):
+        await Reader(path, max_memory=100).seek(1)
+    assert index_cache_info()["entries"] == 0
+    assert index_cache_info()["bytes"] == 0
+
+    with pytest.raises(ValueError, match="escapechar"):
+        await Reader(path, escapechar="\\").seek(1)
+    with pytest.raises(ValueError, match="skip_footer"):
+        await Reader(path, skip_footer=1).seek(1)
+    with pytest.raises(ValueError, match="single file path"):
+        await Reader([path, path]).seek(1)
+
+
+@pytest.mark.asyncio
+async def test_seek_index_within_max_memory(write_csv):
+    """Test that seek() raises MemoryError for an index larger than max_memory."""
+    path = write_csv("id\n" + "".join(f"{i}\n" for i in range(1000)))
+    clear_index_cache()
+    reader = Reader(path, max_memory=1000)
+    with pytest.raises(MemoryError, match="seek"):
+        await reader.seek(10)
+    assert index_cache_info()["entries"] == 0
+    assert await reader.read_row() == ["id"]
+
+    # An index already cached is held to the budget too
+    await Reader(path).seek(10)
+    with pytest.raises(MemoryError, match="max_memory=1000"):
+        await Reader(path, max_memory=1000).seek(10)
+    await Reader(path, max_memory=1 << 20).seek(10)
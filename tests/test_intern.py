@@ -0,0 +1,62 @@
+"""Test string interning with Reader(intern_columns=...)."""
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_intern_listed_columns(write_csv):
+    """Test that repeated values of listed columns are the same object."""
+    path = write_csv("id,country,status\n" + "".join(f"{i},DE-x,open-x\n" for i in range(50)))
+    for columns in (["country"], [1]):
+        rows = await Reader(path, intern_columns=columns).read_all()
+        assert rows[1] == [str(0), "DE-x", "open-x"]
+        assert all(row[1] is rows[1][1] for row in rows[1:])
+        assert rows[1][2] is not rows[2][2]  # Not listed
+
+    # Every row type and read method shares the same strings
+    reader = Reader(path, intern_columns=["status"], row_type="row")
+    first = await reader.read_row()
+    rest = await reader.read_rows(10)
+    assert all(row.status is first.status for row in rest)
+    tuples = [row async for row in Reader(path, intern_columns=[2], row_type="tuple")]
+    assert tuples[5][2] is tuples[6][2]
+
+
+@pytest.mark.asyncio
+async def test_intern_auto_gives_up_on_unique_columns(write_csv):
+    """Test that intern_columns=True stops interning columns with many distinct values."""
+    unique = "".join(f"code-{i % 5},id-{i}\n" for i in range(1100))
+    path = write_csv("code,id\n" + unique + "code-2,same-x\ncode-2,same-x\n")
+    rows = await Reader(path, intern_columns=True).read_all()
+    assert rows[-1] == rows[-2] == ["code-2", "same-x"]
+    assert rows[-1][0] is rows[3][0]
+    assert rows[-1][1] is not rows[-2][1]  # Column given up after 1024 values
+
+
+@pytest.mark.asyncio
+async def test_intern_with_schema_and_projection(write_csv):
+    """Test interning of string fields after typed conversion and column selection."""
+    path = write_csv("n,kind,extra\n1,small-x,a\n2,small-x,b\n3,large-x,c\n")
+    reader = Reader(
+        path,
+        schema={"n": "int"},
+        columns=["kind", "n"],
+        intern_columns=["kind"],
+    )
+    rows = await reader.read_all()
+    assert rows == [["small-x", 1], ["small-x", 2], ["large-x", 3]]
+    assert rows[0][0] is rows[1][0]
+
+
+def test_intern_validation(write_csv):
+    """Test that invalid intern_columns values are rejected."""
+    path = write_csv("a\n1\n")
+    Reader(path, intern_columns=False)
+    with pytest.raises(TypeError, match="not a string"):
+        Reader(path, intern_columns="a")
+    with pytest.raises(ValueError, match="header row"):
+        Reader(path, intern_columns=["a"], has_header=False)
+    with pytest.raises(ValueError, match="raw=True"):
+        Reader(path, intern_columns=True, raw=True)
@@ -0,0 +1,54 @@
+"""Test Reader.into_queue()."""
+
+import asyncio
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+CONTENT = "id,name\n" + "".join(f"{i},name {i}\n" for i in range(250))
+
+
+@pytest.mark.asyncio
+async def test_into_queue_fan_out(write_csv):
+    """Test that consumers of a bounded queue receive every row exactly once."""
+    csv_path = write_csv(CONTENT)
+    queue = asyncio.Queue(maxsize=8)
+    received = []
+
+    async def consume():
+        while (row := await queue.get()) is not None:
+            received.append(row)
+
+    consumers = [asyncio.create_task(consume()) for _ in range(3)]
+    try:
+        count = await Reader(csv_path).into_queue(queue)
+    finally:
+        os.unlink(csv_path)
+    for _ in consumers:
+        await queue.put(None)
+    await asyncio.gather(*consumers)
+
+    assert count == 251
+    assert len(received) == 251
+    assert ["0", "name 0"] in received
+
+
+@pytest.mark.asyncio
+async def test_into_queue_batches(write_csv):
+    """Test batch_size puts lists of rows, continuing from the current position."""
+    csv_path = write_csv(CONTENT)
+    queue = asyncio.Queue()
+    try:
+        reader = Reader(csv_path, row_type="tuple")
+        assert await reader.read_row() == ("id", "name")
+        assert await reader.into_queue(queue, batch_size=100) == 250
+        sizes = [len(queue.get_nowait()) for _ in range(queue.qsize())]
+        assert sizes == [100, 100, 50]
+        assert await reader.into_queue(queue) == 0
+
+        with pytest.raises(ValueError, match="batch_size"):
+            reader.into_queue(queue, batch_size=0)
+    finally:
+        os.unlink(csv_path)
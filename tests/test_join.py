@@ -0,0 +1,81 @@
+"""Test hash joins with join()."""
+
+import os
+
+import pytest
+
+from rapcsv import join
+
+
+def _read_rows(path):
+    with open(path, newline="") as f:
+        return [line.split(",") for line in f.read().split("\r\n") if line]
+
+
+def _join_inputs(write_csv):
+    left = write_csv("id,name,score\n1,Alice,10\n2,Bob,20\n3,Carol,30\n1,Alice2,40\n")
+    right = write_csv("id,city,score\n1,NYC,x\n3,LA,y\n4,SF,z\n")
+    return left, right, left + ".joined.csv"
+
+
+def _cleanup(*paths):
+    for path in paths:
+        if os.path.exists(path):
+            os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_join_inner(write_csv):
+    """Test an inner join with a clashing column name."""
+    left, right, dst = _join_inputs(write_csv)
+
+    try:
+        count = await join(left, right, on="id", dst=dst)
+        assert count == 3
+        assert _read_rows(dst) == [
+            ["id", "name", "score", "city", "score_right"],
+            ["1", "Alice", "10", "NYC", "x"],
+            ["3", "Carol", "30", "LA", "y"],
+            ["1", "Alice2", "40", "NYC", "x"],
+        ]
+    finally:
+        _cleanup(left, right, dst)
+
+@pytest.mark.asyncio
+async def test_join_left(write_csv):
+    """Test that a left join keeps unmatched left rows."""
+    left, right, dst = _join_inputs(write_csv)
+
+    try:
+        count = await join(left, right, on=["id"], how="left", dst=dst)
+        assert count == 4
+        assert ["2", "Bob", "20", "", ""] in _read_rows(dst)
+    finally:
+        _cleanup(left, right, dst)
+
+@pytest.mark.asyncio
+async def test_join_outer(write_csv):
+    """Test that an outer join adds unmatched right rows with their key."""
+    left, right, dst = _join_inputs(write_csv)
+
+    try:
+        count = await join(left, right, on="id", how="outer", dst=dst)
+        assert count == 5
+        assert _read_rows(dst)[-1] == ["4", "", "", "SF", "z"]
+    finally:
+        _cleanup(left, right, dst)
+
+@pytest.mark.asyncio
+async def test_join_invalid_how(write_csv):
+    """Test validation of the how argument."""
+    left, right, dst = _join_inputs(write_csv)
+
+    try:
+        with pytest.raises(ValueError, match="how"):
+            await join(left, right, on="id", how="cross", dst=dst)
+        with pytest.raises(ValueError, match="is the input file"):
+            await join(left, right, on="id", dst=right)
+        with open(right) as f:
+            assert f.read() == "id,city,score\n1,NYC,x\n3,LA,y\n4,SF,z\n"
+    finally:
+        _cleanup(left, right, dst)
@@ -0,0 +1,143 @@
+"""Test JsonlReader and JsonlWriter."""
+
+import json
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import EncodingError, JsonlReader, JsonlWriter, Reader
+
+
+@pytest.mark.asyncio
+async def test_jsonl_roundtrip():
+    """Test writing and reading back nested JSON records."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".jsonl") as f:
+        test_file = f.name
+    os.unlink(test_file)
+
+    records = [
+        {"id": 1, "name": "Alice", "tags": ["a", "b"], "score": 1.5},
+        {"id": 2, "name": "Bob", "active": False, "extra": None},
+        [1, 2, 3],
+        "plain string",
+    ]
+
+    try:
+        async with JsonlWriter(test_file) as writer:
+            await writer.write_row(records[0])
+            await writer.writerows(records[1:])
+
+        with open(test_file) as f:
+            lines = f.read().splitlines()
+        assert [json.loads(line) for line in lines] == records
+
+        reader = JsonlReader(test_file)
+        assert await reader.read_row() == records[0]
+        assert await reader.read_rows(10) == records[1:]
+        assert await reader.read_row() is None
+    finally:
+        if os.path.exists(test_file):
+            os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_jsonl_reader_skips_blank_lines():
+    """Test that blank lines and CRLF endings are tolerated."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".jsonl") as f:
+        test_file = f.name
+        f.write('{"a": 1}\r\n\n   \n{"a": 2}')
+
+    try:
+        reader = JsonlReader(test_file, read_size=4)
+        records = [record async for record in reader]
+        assert records == [{"a": 1}, {"a": 2}]
+        assert reader.line_num == 4
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_jsonl_reader_non_ascii_across_chunks():
+    """Test that characters split between read_size chunks are joined again."""
+    records = [{"name": "Zoë " * 3000, "city": "Łódź 東京"}, {"emoji": "🙂" * 5000}]
+    with tempfile.NamedTemporaryFile(mode="wb", delete=False, suffix=".jsonl") as f:
+        test_file = f.name
+        f.write(b"".join(json.dumps(r, ensure_ascii=False).encode() + b"\n" for r in records))
+
+    try:
+        for read_size in (7, 8192):
+            reader = JsonlReader(test_file, read_size=read_size)
+            assert await reader.read_rows(3) == records
+        with open(test_file, "ab") as f:
+            f.write(b'{"bad": "\xff"}\n')
+        reader = JsonlReader(test_file, read_size=7)
+        assert await reader.read_rows(2) == records
+        with pytest.raises(EncodingError, match="after line 2"):
+            await reader.read_row()
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_jsonl_reader_invalid_json():
+    """Test that invalid lines raise ValueError with the line number."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".jsonl") as f:
+        test_file = f.name
+        f.write('{"a": 1}\n{not json}\n')
+
+    try:
+        reader = JsonlReader(test_file)
+        assert await reader.read_row() == {"a": 1}
+        with pytest.raises(ValueError, match="line 2"):
+            await reader.read_row()
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_jsonl_writer_rejects_unserializable():
+    """Test that non-JSON values raise before anything is written."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".jsonl") as f:
+        test_file = f.name
+    os.unlink(test_file)
+
+    try:
+        writer = JsonlWriter(test_file)
+        with pytest.raises(TypeError):
+            await writer.write_row({"value": object()})
+        with pytest.raises(ValueError):
+            await writer.write_row({"value": float("nan")})
+        await writer.close()
+        assert not os.path.exists(test_file)
+    finally:
+        if os.path.exists(test_file):
+            os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_csv_to_jsonl_pipeline():
+    """Test converting CSV rows to JSONL records in one pipeline."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        csv_file = f.name
+        f.write("name,age\nAlice,30\nBob,25\n")
+    jsonl_file = csv_file + ".jsonl"
+
+    try:
+        reader = Reader(csv_file)
+        header = await reader.read_row()
+        async with JsonlWriter(jsonl_file) as writer:
+            async for row in reader:
+                if not row:
+                    break
+                await writer.write_row(dict(zip(header, row)))
+
+        records = [record async for record in JsonlReader(jsonl_file)]
+        assert records == [
+            {"name": "Alice", "age": "30"},
+            {"name": "Bob", "age": "25"},
+        ]
+    finally:
+        os.unlink(csv_file)
+        if os.path.exists(jsonl_file):
+            os.unlink(jsonl_file)
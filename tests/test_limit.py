@@ -0,0 +1,53 @@
+"""Test stopping after a number of records with Reader(limit=...)."""
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_limit_every_read_method(write_csv):
+    """Test that reads stop after the limit, the header included."""
+    path = write_csv("id,name\n" + "".join(f"{i},n{i}\n" for i in range(1000)))
+    assert await Reader(path, limit=3).read_all() == [["id", "name"], ["0", "n0"], ["1", "n1"]]
+    assert len([row async for row in Reader(path, limit=50, read_size=16)]) == 50
+    assert await Reader(path, limit=0).read_all() == []
+
+    reader = Reader(path, limit=4)
+    assert len(await reader.read_rows(3)) == 3
+    assert await reader.read_rows(3) == [["2", "n2"]]
+    assert await reader.read_row() == []
+    assert await reader.read_all() == []
+
+    # The limit counts the rows returned, after the footer is left out
+    rows = await Reader(path, limit=2, skip_footer=10).read_all()
+    assert rows == [["id", "name"], ["0", "n0"]]
+    assert await Reader(path, limit=10, skip_footer=995).read_all() == [
+        ["id", "name"],
+        ["0", "n0"],
+        ["1", "n1"],
+        ["2", "n2"],
+        ["3", "n3"],
+        ["4", "n4"],
+    ]
+
+
+@pytest.mark.asyncio
+async def test_limit_stops_reading(write_csv):
+    """Test that the file is not read past the records returned."""
+    path = write_csv("a\n" + "1\n" * 100_000)
+    reader = Reader(path, limit=2, read_size=64)
+    assert await reader.read_all() == [["a"], ["1"]]
+    assert reader.byte_offset < 1024
+
+
+@pytest.mark.asyncio
+async def test_limit_multiple_files(write_csv):
+    """Test the limit across files, read one after another or in parallel."""
+    first = write_csv("a\n1\n2\n")
+    second = write_csv("a\n3\n4\n")
+    for concurrency in (1, 2):
+        rows = await Reader([first, second], limit=4, concurrency=concurrency).read_all()
+        assert rows == [["a"], ["1"], ["2"], ["3"]]
+    reader = Reader([first, second], limit=3, prefetch=2)
+    assert [row async for row in reader] == [["a"], ["1"], ["2"]]
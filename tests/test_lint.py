@@ -0,0 +1,69 @@
+"""Test rapcsv.lint()."""
+
+import os
+
+import pytest
+
+from rapcsv import lint
+
+
+def _kinds(report):
+    return [(f["kind"], f["row"], f["line"], f["column"]) for f in report["findings"]]
+
+
+@pytest.mark.asyncio
+async def test_lint_clean_file(write_csv):
+    """Test that a well-formed file has no findings."""
+    path = write_csv(b'id,name,active\n1,"Ann, A.",true\n2,"multi\nline",false\n')
+    report = await lint(path)
+    assert report == {"clean": True, "rows_checked": 2, "truncated": False, "findings": []}
+
+
+@pytest.mark.asyncio
+async def test_lint_reports_locations(write_csv):
+    """Test that each kind of problem is reported with its row, line and column."""
+    path = write_csv(
+        b"id,name,id\r\n"
+        b"1,ann,7\r\n"
+        b"2,bob\r\n"  # Ragged
+        b"x,c\x00y,9\n"  # NUL byte, LF ending, text among numbers
+        b"4,dan,8\r\n"
+    )
+    report = await lint(path)
+    assert not report["clean"]
+    assert report["rows_checked"] == 4
+    assert _kinds(report) == [
+        ("duplicate_header", None, 1, "id"),
+        ("ragged_row", 2, 3, None),
+        ("mixed_line_endings", None, 4, None),
+        ("nul_byte", None, 4, None),
+        ("inconsistent_type", 3, 4, "id"),
+    ]
+    messages = [f["message"] for f in report["findings"]]
+    assert "columns 1, 3" in messages[0]
+    assert messages[1] == "expected 3 fields, found 2"
+    assert messages[2] == "line endings are mixed: 4 CRLF, 1 LF"
+    assert "text value 'x' in a column of number values (3 of 4)" == messages[4]
+
+
+@pytest.mark.asyncio
+async def test_lint_unbalanced_quote_and_limit(write_csv):
+    """Test an unclosed quote and truncation at max_findings."""
+    path = write_csv(b'a,b\n1,2\n3,"open\n4,5\n' + b"".join(b"%d\n" % i for i in range(10)))
+    report = await lint(path)
+    assert ("unbalanced_quote", None, 3, None) in _kinds(report)
+
+    report = await lint(path, max_findings=1)
+    assert report["truncated"] is True
+    assert len(report["findings"]) == 1
+
+
+@pytest.mark.asyncio
+async def test_lint_delimiter_and_missing_file(write_csv):
+    """Test the delimiter option and a missing file."""
+    path = write_csv(b"a;b\n1;2\n3;4;5\n")
+    report = await lint(path, delimiter=";")
+    assert _kinds(report) == [("ragged_row", 2, 3, None)]
+    os.unlink(path)
+    with pytest.raises(IOError):
+        await lint(path)
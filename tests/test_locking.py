@@ -0,0 +1,113 @@
+"""Test advisory file locking: Writer(lock=True) and Reader(lock="shared")."""
+
+import asyncio
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+try:
+    import fcntl
+
+    FCNTL_AVAILABLE = True
+except ImportError:  # Windows
+    FCNTL_AVAILABLE = False
+
+requires_fcntl = pytest.mark.skipif(not FCNTL_AVAILABLE, reason="fcntl not available")
+
+
+def _temp_path(content=""):
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv", newline="") as f:
+        f.write(content)
+        return f.name
+
+
+def _can_lock(path, operation):
+    """Try to take a lock from a separate open file, as another process would."""
+    with open(path) as f:
+        try:
+            fcntl.flock(f, operation | fcntl.LOCK_NB)
+        except BlockingIOError:
+            return False
+        fcntl.flock(f, fcntl.LOCK_UN)
+        return True
+
+
+@requires_fcntl
+@pytest.mark.asyncio
+async def test_writer_lock_held_until_close():
+    """Test that a locking writer excludes other lockers until it is closed."""
+    test_file = _temp_path()
+    try:
+        writer = Writer(test_file, lock=True, write_size=0)
+        await writer.write_row(["a", "b"])
+        assert not _can_lock(test_file, fcntl.LOCK_SH)
+        await writer.close()
+        assert _can_lock(test_file, fcntl.LOCK_EX)
+        with open(test_file, newline="") as f:
+            assert f.read() == "a,b\r\n"
+    finally:
+        os.unlink(test_file)
+
+
+@requires_fcntl
+@pytest.mark.asyncio
+async def test_writer_waits_for_lock():
+    """Test that a locking writer waits while another process holds the lock."""
+    test_file = _temp_path()
+    try:
+        with open(test_file) as holder:
+            fcntl.flock(holder, fcntl.LOCK_EX)
+            writer = Writer(test_file, lock=True, write_size=0)
+            task = asyncio.ensure_future(writer.write_row(["a"]))
+            await asyncio.sleep(0.2)
+            assert not task.done()
+            assert os.path.getsize(test_file) == 0
+            fcntl.flock(holder, fcntl.LOCK_UN)
+            await asyncio.wait_for(task, timeout=5)
+        await writer.close()
+        with open(test_file, newline="") as f:
+            assert f.read() == "a\r\n"
+    finally:
+        os.unlink(test_file)
+
+
+@requires_fcntl
+@pytest.mark.asyncio
+@pytest.mark.parametrize("read_all", [False, True])
+async def test_reader_shared_lock(read_all):
+    """Test that a shared-locking reader coexists with readers but excludes writers."""
+    test_file = _temp_path("a,b\n1,2\n")
+    try:
+        reader = Reader(test_file, lock="shared")
+        if read_all:
+            assert await reader.read_all() == [["a", "b"], ["1", "2"]]
+            # The whole file was read, so the lock is already released
+            assert _can_lock(test_file, fcntl.LOCK_EX)
+        else:
+            assert await reader.read_row() == ["a", "b"]
+            assert _can_lock(test_file, fcntl.LOCK_SH)
+            assert not _can_lock(test_file, fcntl.LOCK_EX)
+            assert await reader.read_row() == ["1", "2"]
+            assert await reader.read_row() == []
+            assert _can_lock(test_file, fcntl.LOCK_EX)
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_lock_arguments():
+    """Test validation of the lock arguments."""
+    test_file = _temp_path("a\n")
+    try:
+        with pytest.raises(ValueError, match="lock"):
+            Reader(test_file, lock="exclusive")
+        with pytest.raises(ValueError, match="lock"):
+            Reader(test_file, lock="shared", follow=True)
+        with open(test_file) as f:
+            with pytest.raises(ValueError, match="lock"):
+                Reader(f, lock="shared")
+    finally:
+        os.unlink(test_file)
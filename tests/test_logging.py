@@ -0,0 +1,93 @@
+"""Test forwarding of core spans and events to the rapcsv logger."""
+
+import logging
+import os
+import tempfile
+
+import pytest
+
+import rapcsv
+from rapcsv import Reader, Writer
+
+
+class _Capture(logging.Handler):
+    def __init__(self):
+        super().__init__(level=logging.NOTSET)
+        self.records = []
+
+    def emit(self, record):
+        self.records.append(record)
+
+
+def _capture():
+    logger = logging.getLogger("rapcsv")
+    handler = _Capture()
+    logger.addHandler(handler)
+    logger.setLevel(1)
+    return logger, handler
+
+
+@pytest.mark.asyncio
+async def test_debug_spans_are_logged():
+    """Test that open, refill, parse and flush spans reach the logger at DEBUG."""
+    logger, handler = _capture()
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        path = f.name
+    try:
+        rapcsv.set_log_level("DEBUG")
+        async with Writer(path) as writer:
+            await writer.writerows([["a", "b"], ["1", "2"]])
+        reader = Reader(path)
+        assert await reader.read_rows(10) == [["a", "b"], ["1", "2"]]
+    finally:
+        rapcsv.set_log_level(logging.WARNING)
+        logger.removeHandler(handler)
+        os.unlink(path)
+
+    messages = [record.getMessage() for record in handler.records]
+    assert all(record.levelno == logging.DEBUG for record in handler.records)
+    assert any(m.startswith("open path=") for m in messages)
+    assert any(m.startswith("flush path=") and "bytes=10" in m for m in messages)
+    assert any(m.startswith("refill path=") and "bytes=10" in m for m in messages)
+    assert any(m.startswith("parse rows=2") and m.endswith(" ms)") for m in messages)
+
+
+@pytest.mark.asyncio
+async def test_retries_are_logged_as_warnings():
+    """Test that retries are logged at the default level, and nothing when disabled."""
+
+    class Flaky:
+        def __init__(self, failures):
+            self.failures = failures
+            self.content = "a\n"
+
+        async def read(self, size):
+            if self.failures:
+                self.failures -= 1
+                raise ConnectionResetError("reset")
+            chunk, self.content = self.content[:size], self.content[size:]
+            return chunk
+
+    logger, handler = _capture()
+    try:
+        assert await Reader(Flaky(1), retries=2, retry_backoff=0).read_all() == [["a"]]
+        rapcsv.set_log_level(None)
+        assert await Reader(Flaky(1), retries=2, retry_backoff=0).read_all() == [["a"]]
+    finally:
+        rapcsv.set_log_level("WARNING")
+        logger.removeHandler(handler)
+
+    assert len(handler.records) == 1
+    record = handler.records[0]
+    assert record.levelno == logging.WARNING
+    assert record.getMessage() == (
+        "retrying after transient error: ConnectionResetError: reset attempt=1 delay=0.0"
+    )
+
+
+def test_set_log_level_validation():
+    """Test that unknown levels are rejected."""
+    with pytest.raises(ValueError, match="unknown log level"):
+        rapcsv.set_log_level("LOUD")
+    with pytest.raises(TypeError):
+        rapcsv.set_log_level(1.5)
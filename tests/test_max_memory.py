@@ -0,0 +1,69 @@
+"""Test max_memory on Reader.read_all(), sort() and join()."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, join, sort
+
+
+@pytest.mark.asyncio
+async def test_read_all_within_and_over_budget(write_csv):
+    """Test that read_all() raises MemoryError over budget and keeps the rows read."""
+    rows = [[str(i), f"name {i}"] for i in range(200)]
+    path = write_csv("".join(f"{a},{b}\n" for a, b in rows))
+    assert await Reader(path, has_header=False, max_memory=1 << 20).read_all() == rows
+
+    # Larger than the budget on disk: read in chunks until the limit is hit
+    reader = Reader(path, has_header=False, max_memory=1000)
+    with pytest.raises(MemoryError, match="max_memory=1000"):
+        await reader.read_all()
+    assert [row async for row in reader] == rows
+
+    # Small on disk, but not once parsed: the reader is left untouched
+    reader = Reader(path, has_header=False, max_memory=os.path.getsize(path))
+    with pytest.raises(MemoryError, match="read_rows"):
+        await reader.read_all()
+    assert await reader.read_rows(300) == rows
+
+    with pytest.raises(ValueError, match="max_memory"):
+        Reader(path, max_memory=0)
+
+
+@pytest.mark.asyncio
+async def test_sort_spills_at_budget(write_csv):
+    """Test that sort() spills runs once the budget is reached and still sorts."""
+    src = write_csv("id,value\n" + "".join(f"{i},{(i * 37) % 101}\n" for i in range(300)))
+    dst = src + ".sorted"
+    assert await sort(src, dst, "value", max_memory=2000) == 300
+    async with Reader(dst) as reader:
+        values = [int(row[1]) for row in (await reader.read_all())[1:]]
+    assert values == sorted(values)
+
+
+@pytest.mark.asyncio
+async def test_join_spills_over_budget(write_csv):
+    """Test that join() partitions to disk over budget and writes the same rows."""
+    left = write_csv("id,a\n" + "".join(f"{i % 120},x{i}\n" for i in range(0, 300, 7)))
+    right = write_csv("id,b\n" + "".join(f"{i % 100},v{i}\n" for i in range(150)))
+    skewed = write_csv("id,b\n" + "1,v\n" * 100)
+    dst = left + ".joined"
+    with tempfile.TemporaryDirectory() as temp_dir:
+        for how in ("inner", "left", "right", "outer"):
+            count = await join(left, right, "id", dst, how=how)
+            with open(dst) as f:
+                in_memory = f.read()
+            spilled = await join(
+                left, right, "id", dst, how=how, max_memory=3000, temp_dir=temp_dir
+            )
+            assert spilled == count
+            with open(dst) as f:
+                assert f.read() == in_memory
+        assert os.listdir(temp_dir) == []
+
+    # Rows sharing a key land in one partition, which can't be made smaller
+    with pytest.raises(MemoryError, match="one partition"):
+        await join(left, skewed, "id", dst, max_memory=3000)
+    with pytest.raises(ValueError, match="temp_dir"):
+        await join(left, right, "id", dst, temp_dir=left)
@@ -0,0 +1,75 @@
+"""Test merging already-sorted files with merge_sorted()."""
+
+import os
+
+import pytest
+
+from rapcsv import merge_sorted
+
+
+def _read_rows(path):
+    with open(path, newline="") as f:
+        return [line.split(",") for line in f.read().split("\r\n") if line]
+
+
+@pytest.mark.asyncio
+async def test_merge_time_series(write_csv):
+    """Test that rows interleave by key, ties keeping the order of the inputs."""
+    parts = [
+        write_csv("ts,value\n1,a\n4,b\n10,c\n"),
+        write_csv("ts,value\n2,d\n4,e\n"),
+        write_csv(""),
+        write_csv("ts,value\n3,f\n"),
+    ]
+    dst = parts[0] + ".merged.csv"
+    try:
+        assert await merge_sorted(parts, dst, by=["ts"]) == 6
+        assert _read_rows(dst) == [
+            ["ts", "value"],
+            ["1", "a"],
+            ["2", "d"],
+            ["3", "f"],
+            ["4", "b"],
+            ["4", "e"],
+            ["10", "c"],
+        ]
+    finally:
+        for path in [*parts, dst]:
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_descending_keys(write_csv):
+    """Test inputs sorted in descending order."""
+    parts = [write_csv("n\n9\n5\n"), write_csv("n\n7\n1\n")]
+    dst = parts[0] + ".merged.csv"
+    try:
+        await merge_sorted(parts, dst, by="-n")
+        assert _read_rows(dst) == [["n"], ["9"], ["7"], ["5"], ["1"]]
+    finally:
+        for path in [*parts, dst]:
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_rejected_inputs(write_csv):
+    """Test that unsorted inputs and differing headers raise ValueError."""
+    sorted_part = write_csv("ts,value\n1,a\n2,b\n")
+    unsorted = write_csv("ts,value\n5,c\n3,d\n")
+    other = write_csv("time,value\n1,a\n")
+    dst = sorted_part + ".merged.csv"
+    try:
+        with pytest.raises(ValueError, match="not sorted"):
+            await merge_sorted([sorted_part, unsorted], dst, by="ts")
+        with pytest.raises(ValueError, match="differs"):
+            await merge_sorted([sorted_part, other], dst, by="ts")
+        with pytest.raises(ValueError, match="is the input file"):
+            await merge_sorted([sorted_part, unsorted], sorted_part, by="ts")
+        with open(sorted_part) as f:
+            assert f.read() == "ts,value\n1,a\n2,b\n"
+    finally:
+        for path in (sorted_part, unsorted, other, dst):
+            if os.path.exists(path):
+                os.unlink(path)
@@ -0,0 +1,139 @@
+"""Test OS-native paths: path objects, names that aren't Unicode, and long paths."""
+
+import os
+import pathlib
+import sys
+import tempfile
+
+import pytest
+
+from rapcsv import CsvIoError, Reader, Writer
+
+# macOS and Windows only store names that are valid Unicode
+needs_bytes_names = pytest.mark.skipif(
+    sys.platform in ("darwin", "win32"), reason="filesystem requires Unicode names"
+)
+windows_only = pytest.mark.skipif(sys.platform != "win32", reason="Windows path rules")
+
+
+def _write_bytes(path, content):
+    with open(path, "wb") as f:
+        f.write(content)
+
+
+def _read(path):
+    with open(path, "rb") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_pathlib_paths():
+    """Test that pathlib.Path objects are accepted by Reader and Writer."""
+    with tempfile.TemporaryDirectory() as d:
+        path = pathlib.Path(d) / "data.csv"
+        async with Writer(path) as writer:
+            await writer.writerows([["a", "b"], ["1", "2"]])
+        assert await Reader(path).read_all() == [["a", "b"], ["1", "2"]]
+
+
+@needs_bytes_names
+@pytest.mark.asyncio
+async def test_undecodable_names():
+    """Test files whose names aren't valid UTF-8, given as bytes or as fsdecode()d str."""
+    with tempfile.TemporaryDirectory() as d:
+        path = os.path.join(os.fsencode(d), b"caf\xe9.csv")
+        _write_bytes(path, b"a,b\n1,2\n")
+        assert await Reader(path).read_all() == [["a", "b"], ["1", "2"]]
+        assert await Reader(os.fsdecode(path)).read_all() == [["a", "b"], ["1", "2"]]
+
+        out = os.path.join(os.fsencode(d), b"out\xff.csv")
+        async with Writer(os.fsdecode(out)) as writer:
+            await writer.write_row(["x", "y"])
+        assert _read(out) == b"x,y\r\n"
+
+
+@needs_bytes_names
+@pytest.mark.asyncio
+async def test_undecodable_name_in_errors():
+    """Test that an undecodable name reads as \\xNN escapes in error messages."""
+    with tempfile.TemporaryDirectory() as d:
+        path = os.path.join(os.fsencode(d), b"missing\xfe.csv")
+        with pytest.raises(CsvIoError, match=r"missing\\xfe\.csv"):
+            await Reader(path).read_row()
+
+
+@needs_bytes_names
+@pytest.mark.asyncio
+async def test_undecodable_name_with_options():
+    """Test undecodable names with an encryption key, archive members and seek()."""
+    rows = [["a", "b"], ["1", "2"], ["3", "4"]]
+    with tempfile.TemporaryDirectory() as d:
+        encrypted = os.path.join(os.fsencode(d), b"secret\xff.csv")
+        async with Writer(encrypted, encryption_key=bytes(32)) as writer:
+            await writer.writerows(rows)
+        assert await Reader(encrypted, encryption_key=bytes(32)).read_all() == rows
+
+        bundle = os.path.join(os.fsencode(d), b"bundle\xfe.zip")
+        async with Writer(bundle, zip_member="data.csv") as writer:
+            await writer.writerows(rows)
+        assert await Reader(bundle + b"::data.csv").read_all() == rows
+        assert await Reader(bundle, zip_member="data.csv").read_all() == rows
+
+        path = os.path.join(os.fsencode(d), b"seek\xfd.csv")
+        _write_bytes(path, b"a,b\n1,2\n3,4\n")
+        reader = Reader(path)
+        await reader.seek(2)
+        assert await reader.read_row() == ["3", "4"]
+
+
+@needs_bytes_names
+@pytest.mark.asyncio
+async def test_undecodable_name_and_its_stand_in():
+    """Test that a file literally named like an undecodable name's escapes is a
+    different file."""
+    with tempfile.TemporaryDirectory() as d:
+        undecodable = os.path.join(os.fsencode(d), b"a\xff.csv")
+        lookalike = os.path.join(d, "a\\xff.csv")
+        _write_bytes(undecodable, b"x\nundecodable\n")
+        _write_bytes(lookalike, b"x\nlookalike\n")
+        assert await Reader(undecodable).read_all() == [["x"], ["undecodable"]]
+        assert await Reader(lookalike).read_all() == [["x"], ["lookalike"]]
+        assert await Reader(undecodable).read_all() == [["x"], ["undecodable"]]
+
+        reader = Reader(undecodable)
+        await reader.read_row()
+        with pytest.raises(ValueError, match="valid Unicode"):
+            reader.state()
+
+
+@pytest.mark.asyncio
+async def test_long_paths():
+    """Test paths longer than Windows' MAX_PATH of 260 characters."""
+    with tempfile.TemporaryDirectory() as d:
+        directory = os.path.join(d, *["directory-name-" + "x" * 30] * 6)
+        try:
+            os.makedirs(directory)
+        except OSError:
+            pytest.skip("filesystem does not allow long paths")
+        path = os.path.join(directory, "data.csv")
+        assert len(path) > 260
+
+        async with Writer(path) as writer:
+            await writer.writerows([["a", "b"], ["1", "2"]])
+        assert await Reader(path).read_all() == [["a", "b"], ["1", "2"]]
+
+
+@windows_only
+def test_unc_path_needs_share():
+    """Test that a UNC path without a share is rejected with a clear message."""
+    with pytest.raises(ValueError, match="server and a share"):
+        Reader(r"\\fileserver")
+    with pytest.raises(ValueError, match="server and a share"):
+        Writer("\\\\fileserver\\")
+
+
+@windows_only
+def test_extended_length_path_needs_backslashes():
+    """Test that a \\\\?\\ path with forward slashes is rejected."""
+    with pytest.raises(ValueError, match="backslashes"):
+        Reader("\\\\?\\C:/data/file.csv")
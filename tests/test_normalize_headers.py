@@ -0,0 +1,79 @@
+"""Test normalizing header names with normalize_headers=."""
+
+import os
+
+import pytest
+
+from rapcsv import AsyncDictReader, CSVError, Reader
+
+CONTENT = " First Name ,orderId,HTTPStatus,Unit-Price (USD),Q1Total\nAda,7,200,1.5,9\n"
+NORMALIZED = ["first_name", "order_id", "http_status", "unit_price_usd", "q1_total"]
+
+
+@pytest.mark.asyncio
+async def test_snake_case(write_csv):
+    """Test that names are trimmed, lowercased and snake_cased."""
+    path = write_csv(CONTENT)
+    try:
+        assert await Reader(path, normalize_headers=True).read_row() == NORMALIZED
+        rows = await Reader(path, normalize_headers=True).read_all()
+        assert rows[0] == NORMALIZED
+        header = await Reader(path, normalize_headers=False).read_row()
+        assert header[0] == " First Name "
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_names_are_used_everywhere(write_csv):
+    """Test that columns=, schema=, Row objects and dict keys see normalized names."""
+    path = write_csv(CONTENT)
+    try:
+        reader = Reader(path, normalize_headers=True, columns=["order_id", "first_name"])
+        assert await reader.read_all() == [["order_id", "first_name"], ["7", "Ada"]]
+
+        reader = Reader(path, normalize_headers=True, row_type="row", schema={"order_id": "int"})
+        row = await reader.read_row()
+        assert row.order_id == 7
+        assert row["http_status"] == "200"
+
+        reader = AsyncDictReader(path, normalize_headers=True)
+        row = await reader.read_row()
+        assert list(row) == NORMALIZED
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_custom_callable(write_csv):
+    """Test that a callable is given each name instead."""
+    path = write_csv("a,b\n1,2\n")
+    try:
+        reader = Reader(path, normalize_headers=str.upper)
+        assert await reader.read_row() == ["A", "B"]
+
+        reader = Reader(path, normalize_headers=lambda name: 1)
+        with pytest.raises(TypeError, match="must return str"):
+            await reader.read_row()
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_normalized_duplicates(write_csv):
+    """Test that names made equal by normalizing are handled by duplicate_headers."""
+    path = write_csv("ID,id \n1,2\n")
+    try:
+        reader = Reader(path, normalize_headers=True, duplicate_headers="suffix")
+        assert await reader.read_row() == ["id", "id_1"]
+        reader = Reader(path, normalize_headers=True, duplicate_headers="error")
+        with pytest.raises(CSVError):
+            await reader.read_row()
+    finally:
+        os.unlink(path)
+
+
+def test_invalid_argument():
+    """Test that normalize_headers must be a bool or a callable."""
+    with pytest.raises(TypeError, match="normalize_headers"):
+        Reader("data.csv", normalize_headers="snake")
@@ -0,0 +1,28 @@
+"""Test Reader(normalize_newlines=True)."""
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_normalize_newlines_in_fields(write_csv):
+    """Test that CRLF, CR and LF inside fields all come back as LF in every read mode."""
+    content = b'id,"a\r\nnote"\r\n1,"x\r\ny"\r\n2,"p\rq\r\rr"\r\n3,"s\nt"\r\n'
+    test_file = write_csv(content)
+    expected = [["id", "a\nnote"], ["1", "x\ny"], ["2", "p\nq\n\nr"], ["3", "s\nt"]]
+    reader = Reader(test_file, normalize_newlines=True)
+    assert await reader.read_all() == expected
+
+    reader = Reader(test_file, normalize_newlines=True, read_size=3)
+    assert [row async for row in reader] == expected
+
+    reader = Reader(test_file, normalize_newlines=True, row_type="row")
+    row = await reader.read_row()
+    assert row["a\nnote"] == "x\ny"
+
+    reader = Reader(test_file, normalize_newlines=True, raw=True)
+    assert (await reader.read_rows(3))[2] == [b"2", b"p\nq\n\nr"]
+
+    # Off by default
+    assert (await Reader(test_file).read_rows(2))[1] == ["1", "x\r\ny"]
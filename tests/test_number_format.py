@@ -0,0 +1,92 @@
+"""Test thousands separators and decimal commas in typed reads."""
+
+import os
+
+import pytest
+
+from rapcsv import CSVError, Reader
+
+
+@pytest.mark.asyncio
+async def test_european_numbers(write_csv):
+    """Test that 1.234,56 reads as 1234.56 with thousands="." and decimal=","."""
+    path = write_csv("amount;count\n1.234,56;1.000\n-0,5;7\n")
+    try:
+        reader = Reader(
+            path,
+            delimiter=";",
+            schema={"amount": "float", "count": "int"},
+            thousands=".",
+            decimal=",",
+        )
+        assert await reader.read_all() == [[1234.56, 1000], [-0.5, 7]]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_thousands_only(write_csv):
+    """Test a thousands separator with the usual decimal point."""
+    path = write_csv('amount\n"1,234,567.5"\n')
+    try:
+        reader = Reader(path, schema={"amount": "float"}, thousands=",")
+        assert await reader.read_all() == [[1234567.5]]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_point_is_not_a_decimal_comma(write_csv):
+    """Test that a stray "." doesn't pass as the decimal point under decimal=","."""
+    path = write_csv("amount\n1.5\n")
+    try:
+        reader = Reader(path, schema={"amount": "float"}, decimal=",")
+        with pytest.raises(CSVError, match="Cannot convert '1.5'"):
+            await reader.read_all()
+    finally:
+        os.unlink(path)
+
+
+def test_invalid_options():
+    """Test that bad characters and options without a schema are rejected."""
+    with pytest.raises(ValueError, match="different"):
+        Reader("data.csv", schema={0: "float"}, thousands=",", decimal=",")
+    with pytest.raises(ValueError, match="single character"):
+        Reader("data.csv", schema={0: "float"}, decimal="1")
+    with pytest.raises(ValueError, match="schema"):
+        Reader("data.csv", decimal=",")
+
+
+@pytest.mark.asyncio
+async def test_currency_symbols(write_csv):
+    """Test that symbols before and after numbers are removed, keeping the sign."""
+    path = write_csv('price,count\n"$1,234.50",5 pcs\n-$5,10 pcs\n12 €,$-3\n')
+    try:
+        reader = Reader(
+            path,
+            schema={"price": "float", "count": "int"},
+            thousands=",",
+            number_symbols=["$", "€", "pcs"],
+        )
+        assert await reader.read_all() == [[1234.5, 5], [-5.0, 10], [12.0, -3]]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_percent_scale(write_csv):
+    """Test that percent_scale reads 12.5% as 0.125 and leaves bare numbers alone."""
+    path = write_csv("rate,count\n12.5%,3\n0.5,4%\n")
+    try:
+        reader = Reader(path, schema={"rate": "float"}, percent_scale=True)
+        assert await reader.read_all() == [[0.125, "3"], [0.5, "4%"]]
+
+        reader = Reader(path, schema={"count": "int"}, percent_scale=True)
+        assert await reader.read_row() == ["12.5%", 3]
+        with pytest.raises(CSVError, match="4%"):
+            await reader.read_row()
+
+        reader = Reader(path, schema={"rate": "float"}, number_symbols=["%"])
+        assert await reader.read_all() == [[12.5, "3"], [0.5, "4%"]]
+    finally:
+        os.unlink(path)
@@ -0,0 +1,63 @@
+"""Test Reader.open(), which surfaces file and header problems before the first read."""
+
+import os
+
+import pytest
+
+from rapcsv import CsvIoError, EncodingError, Reader
+
+
+@pytest.mark.asyncio
+async def test_open_consumes_nothing(write_csv):
+    """Test that rows, line_num and read_all() are as without open()."""
+    path = write_csv("id,name\n1,a\n2,b\n")
+    try:
+        reader = Reader(path)
+        await reader.open()
+        await reader.open()
+        assert reader.line_num == 0
+        assert await reader.read_row() == ["id", "name"]
+        assert await reader.read_all() == [["1", "a"], ["2", "b"]]
+        async with Reader(path) as reader:
+            assert await reader.read_all() == [["id", "name"], ["1", "a"], ["2", "b"]]
+            assert await reader.read_row() == []
+        async with Reader(path, row_type="row") as reader:
+            assert len(await reader.split(2)) == 2
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_missing_files_fail_on_entry(write_csv):
+    """Test that a missing file, even a later one of a list, raises before any row."""
+    path = write_csv("id\n1\n")
+    missing = path + ".missing"
+    try:
+        with pytest.raises(CsvIoError, match="missing"):
+            await Reader(missing).open()
+        reader = Reader([path, missing])
+        with pytest.raises(CsvIoError, match="missing"):
+            async with reader:
+                pass
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_header_is_checked(write_csv):
+    """Test that undecodable bytes and unknown columns raise from open()."""
+    path = write_csv(b"id,n\xffme\n1,a\n")
+    try:
+        with pytest.raises(EncodingError):
+            await Reader(path).open()
+    finally:
+        os.unlink(path)
+    path = write_csv("id,name\n1,a\n")
+    try:
+        with pytest.raises(ValueError, match="email"):
+            async with Reader(path, columns=["email"]):
+                pass
+        async with Reader(path, columns=["name"]) as reader:
+            assert await reader.read_all() == [["name"], ["a"]]
+    finally:
+        os.unlink(path)
@@ -0,0 +1,229 @@
+"""Test whole-file CSV operations."""
+
+import os
+
+import pytest
+
+from rapcsv import concat, restructure, split
+
+
+def _read(path):
+    with open(path, newline="") as f:
+        return f.read()
+
+
+# ============================================================================
+# concat
+# ============================================================================
+
+
+@pytest.mark.asyncio
+async def test_concat_aligns_headers(write_csv):
+    """Test that later files are reordered to the first file's header."""
+    first = write_csv("a,b,c\n1,2,3\n")
+    second = write_csv("c,a,b\n6,4,5\n9,7,8\n")
+    dst = first + ".out.csv"
+
+    try:
+        count = await concat([first, second], dst)
+        assert count == 3
+        assert _read(dst) == "a,b,c\r\n1,2,3\r\n4,5,6\r\n7,8,9\r\n"
+    finally:
+        for path in (first, second, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_concat_ragged_rows(write_csv):
+    """Test that rows the reader accepts with too few or many fields are written as read."""
+    first = write_csv("a,b\n1\n2,3,4\n")
+    second = write_csv("a,b\n5,6\n")
+    dst = first + ".out.csv"
+
+    try:
+        assert await concat([first, second], dst) == 3
+        assert _read(dst) == "a,b\r\n1\r\n2,3,4\r\n5,6\r\n"
+    finally:
+        for path in (first, second, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_concat_incompatible_headers(write_csv):
+    """Test that files with different columns raise a clear error."""
+    first = write_csv("a,b\n1,2\n")
+    second = write_csv("a,x\n3,4\n")
+    dst = first + ".out.csv"
+
+    try:
+        with pytest.raises(ValueError, match="missing columns"):
+            await concat([first, second], dst)
+    finally:
+        for path in (first, second, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_concat_without_alignment(write_csv):
+    """Test that align_headers=False requires identical headers."""
+    first = write_csv("a,b\n1,2\n")
+    same = write_csv("a,b\n3,4\n")
+    reordered = write_csv("b,a\n6,5\n")
+    dst = first + ".out.csv"
+
+    try:
+        assert await concat([first, same], dst, align_headers=False) == 2
+        assert _read(dst) == "a,b\r\n1,2\r\n3,4\r\n"
+        with pytest.raises(ValueError, match="align_headers"):
+            await concat([first, reordered], dst, align_headers=False)
+    finally:
+        for path in (first, same, reordered, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_concat_into_an_input(write_csv):
+    """Test that writing over one of the inputs is refused before it is truncated."""
+    first = write_csv("a,b\n1,2\n")
+    second = write_csv("a,b\n3,4\n")
+    link = first + ".link.csv"
+
+    try:
+        os.link(first, link)
+        respelled = os.path.join(os.path.dirname(second), ".", os.path.basename(second))
+        for dst in (first, link, respelled):
+            with pytest.raises(ValueError, match="is the input file"):
+                await concat([first, second], dst)
+        assert _read(first) == "a,b\n1,2\n"
+    finally:
+        for path in (first, second, link):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+# ============================================================================
+# split
+# ============================================================================
+
+
+@pytest.mark.asyncio
+async def test_split_rows_per_file(write_csv):
+    """Test splitting by row count with the header in every part."""
+    src = write_csv("id,name\n" + "".join(f"{i},n{i}\n" for i in range(5)))
+    pattern = src + ".part_{:02d}.csv"
+
+    parts = []
+    try:
+        parts = await split(src, pattern, rows_per_file=2)
+        assert parts == [pattern.format(1), pattern.format(2), pattern.format(3)]
+        assert _read(parts[0]) == "id,name\r\n0,n0\r\n1,n1\r\n"
+        assert _read(parts[2]) == "id,name\r\n4,n4\r\n"
+    finally:
+        for path in [src, *parts]:
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_split_max_bytes(write_csv):
+    """Test splitting by size; every part stays within max_bytes."""
+    src = write_csv("a,b\n" + "".join(f"{i},{'x' * 10}\n" for i in range(20)))
+    pattern = src + ".part_{}.csv"
+
+    parts = []
+    try:
+        parts = await split(src, pattern, max_bytes=60)
+        assert len(parts) > 1
+        rows = []
+        for part in parts:
+            assert os.path.getsize(part) <= 60
+            lines = _read(part).split("\r\n")
+            assert lines[0] == "a,b"
+            rows.extend(line for line in lines[1:] if line)
+        assert rows == [f"{i},{'x' * 10}" for i in range(20)]
+    finally:
+        for path in [src, *parts]:
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_split_onto_source_is_refused(tmp_path, write_csv):
+    """Test that a part path naming the source raises instead of truncating it."""
+    src = write_csv("a,b\n1,2\n3,4\n", name="data_2.csv")
+
+    with pytest.raises(ValueError, match="is the input file"):
+        await split(src, str(tmp_path / "data_{}.csv"), rows_per_file=1)
+    with open(src) as f:
+        assert f.read() == "a,b\n1,2\n3,4\n"
+
+
+@pytest.mark.asyncio
+async def test_split_requires_limit():
+    """Test argument validation."""
+    with pytest.raises(ValueError):
+        await split("in.csv", "out_{}.csv")
+    with pytest.raises(ValueError, match="placeholder"):
+        await split("in.csv", "out.csv", rows_per_file=10)
+
+
+# ============================================================================
+# restructure
+# ============================================================================
+
+
+@pytest.mark.asyncio
+async def test_restructure_rename_and_drop(write_csv):
+    """Test renaming and dropping columns while keeping source order."""
+    src = write_csv("id,name,secret,age\n1,alice,x,30\n2,bob,y,25\n")
+    dst = src + ".out.csv"
+    try:
+        written = await restructure(
+            src, dst, rename={"name": "full_name"}, drop=["secret"]
+        )
+        assert written == 2
+        assert _read(dst) == "id,full_name,age\r\n1,alice,30\r\n2,bob,25\r\n"
+    finally:
+        os.unlink(src)
+        if os.path.exists(dst):
+            os.unlink(dst)
+
+
+@pytest.mark.asyncio
+async def test_restructure_order(write_csv):
+    """Test that order selects and reorders columns by source name."""
+    src = write_csv("a,b,c\n1,2,3\n4,5\n")
+    dst = src + ".out.csv"
+    try:
+        written = await restructure(src, dst, rename={"a": "A"}, order=["c", "a"])
+        assert written == 2
+        assert _read(dst) == "c,A\r\n3,1\r\n,4\r\n"
+    finally:
+        os.unlink(src)
+        if os.path.exists(dst):
+            os.unlink(dst)
+
+
+@pytest.mark.asyncio
+async def test_restructure_unknown_column(write_csv):
+    """Test that unknown or conflicting column names raise ValueError."""
+    src = write_csv("a,b\n1,2\n")
+    dst = src + ".out.csv"
+    try:
+        with pytest.raises(ValueError, match="'missing' not found"):
+            await restructure(src, dst, drop=["missing"])
+        with pytest.raises(ValueError, match="not found"):
+            await restructure(src, dst, rename={"zzz": "y"})
+        with pytest.raises(ValueError, match="both order and drop"):
+            await restructure(src, dst, drop=["a"], order=["a", "b"])
+        with pytest.raises(ValueError, match="is the input file"):
+            await restructure(src, src, drop=["b"])
+        assert _read(src) == "a,b\n1,2\n"
+    finally:
+        os.unlink(src)
+        if os.path.exists(dst):
+            os.unlink(dst)
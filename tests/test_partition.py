@@ -0,0 +1,64 @@
+"""Test rapcsv.partition()."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import partition
+
+
+def _read(path):
+    with open(path, "rb") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_partition_by_columns(write_csv):
+    """Test nested partition directories, with and without the partition columns."""
+    src = write_csv(
+        "year,month,amount\n"
+        "2024,01,10\n"
+        "2024,02,20\n"
+        "2023,12,30\n"
+        "2024,01,40\n"
+    )
+    with tempfile.TemporaryDirectory() as dst:
+        counts = await partition(src, dst, by=["year", "month"])
+        assert counts == {"year=2024/month=01": 2, "year=2024/month=02": 1, "year=2023/month=12": 1}
+        path = os.path.join(dst, "year=2024", "month=01", "data.csv")
+        assert _read(path) == b"amount\r\n10\r\n40\r\n"
+
+        counts = await partition(src, dst, by="year", keep_columns=True, filename="part.csv")
+        assert counts == {"year=2024": 3, "year=2023": 1}
+        path = os.path.join(dst, "year=2023", "part.csv")
+        assert _read(path) == b"year,month,amount\r\n2023,12,30\r\n"
+
+
+@pytest.mark.asyncio
+async def test_partition_escaping_and_open_file_limit(write_csv):
+    """Test escaped values, empty values and reopening files closed to stay under the limit."""
+    src = write_csv("k;v\na/b;1\n;2\nc;3\na/b;4\n;5\nc;6\n")
+    with tempfile.TemporaryDirectory() as dst:
+        counts = await partition(src, dst, by="k", max_open_files=1, delimiter=";")
+        assert counts == {"k=a%2Fb": 2, "k=__HIVE_DEFAULT_PARTITION__": 2, "k=c": 2}
+        assert _read(os.path.join(dst, "k=a%2Fb", "data.csv")) == b"v\r\n1\r\n4\r\n"
+        assert _read(os.path.join(dst, "k=c", "data.csv")) == b"v\r\n3\r\n6\r\n"
+
+        with pytest.raises(ValueError, match="Column 'missing' not found"):
+            await partition(src, dst, by="missing", delimiter=";")
+        with pytest.raises(ValueError, match="max_open_files"):
+            await partition(src, dst, by="k", max_open_files=0)
+        with pytest.raises(ValueError, match="filename"):
+            await partition(src, dst, by="k", filename="a/b.csv")
+
+
+@pytest.mark.asyncio
+async def test_partition_onto_source_is_refused(tmp_path, write_csv):
+    """Test that a part file that is the source raises instead of truncating it."""
+    (tmp_path / "date=2024-01-01").mkdir()
+    src = write_csv("date,v\n2024-01-01,1\n", name="date=2024-01-01/data.csv")
+
+    with pytest.raises(ValueError, match="is the input file"):
+        await partition(src, str(tmp_path), by="date")
+    assert _read(src) == b"date,v\n2024-01-01,1\n"
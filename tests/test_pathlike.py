@@ -0,0 +1,52 @@
+"""Test that constructors accept os.PathLike and bytes paths."""
+
+import os
+import pathlib
+import tempfile
+
+import pytest
+
+from rapcsv import AsyncDictReader, JsonlWriter, Reader, Writer
+
+
+@pytest.mark.asyncio
+async def test_pathlib_paths():
+    """Test reading and writing through pathlib.Path objects."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = pathlib.Path(tmp) / "data.csv"
+        async with Writer(path) as writer:
+            await writer.writerows([["name", "age"], ["Alice", "30"]])
+        assert await Reader(path).read_all() == [["name", "age"], ["Alice", "30"]]
+        assert await AsyncDictReader(path).read_row() == {"name": "Alice", "age": "30"}
+
+        jsonl = pathlib.Path(tmp) / "data.jsonl"
+        async with JsonlWriter(jsonl) as writer:
+            await writer.write_row({"a": 1})
+        assert jsonl.read_text() == '{"a":1}\n'
+
+
+@pytest.mark.asyncio
+async def test_bytes_paths():
+    """Test that bytes paths are decoded like os.fsdecode()."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.fsencode(os.path.join(tmp, "data.csv"))
+        async with Writer(path) as writer:
+            await writer.write_row(["x"])
+        assert await Reader(path).read_all() == [["x"]]
+
+
+@pytest.mark.asyncio
+async def test_pathlike_lists_and_globs():
+    """Test lists of Path objects and Path glob patterns as multi-file readers."""
+    with tempfile.TemporaryDirectory() as tmp:
+        paths = [pathlib.Path(tmp) / f"part-{i}.csv" for i in range(2)]
+        for i, path in enumerate(paths):
+            path.write_text(f"col\n{i}\n")
+        assert await Reader(paths).read_all() == [["col"], ["0"], ["1"]]
+        assert await Reader(pathlib.Path(tmp) / "part-*.csv").read_all() == [
+            ["col"],
+            ["0"],
+            ["1"],
+        ]
+        with pytest.raises(TypeError, match="os.PathLike"):
+            Reader([paths[0], 1])
@@ -0,0 +1,70 @@
+"""Test pickling Reader and Writer objects for process pools."""
+
+import os
+import pickle
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+
+@pytest.mark.asyncio
+async def test_reader_keeps_options_and_position(write_csv):
+    """Test that a reader pickled mid-read continues at the same row."""
+    path = write_csv("id;name\n1;a\n2;b\n3;c\n")
+    try:
+        reader = Reader(path, delimiter=";", row_type="row")
+        assert (await reader.read_row())["name"] == "a"
+        copy = pickle.loads(pickle.dumps(reader))
+        assert [row["id"] for row in await copy.read_all()] == ["2", "3"]
+        assert [row["id"] for row in await reader.read_all()] == ["2", "3"]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_split_parts_keep_their_ranges(write_csv):
+    """Test that pickled parts from split() read only their own rows."""
+    path = write_csv("id\n" + "".join(f"{i}\n" for i in range(100)))
+    try:
+        parts = await Reader(path, row_type="row").split(3, row_type="row")
+        copies = [pickle.loads(pickle.dumps(part)) for part in parts]
+        rows = [row["id"] for part in copies for row in await part.read_all()]
+        assert rows == [str(i) for i in range(100)]
+        assert all(part.state()["byte_end"] is not None for part in copies[:-1])
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_writer_appends_in_copy(write_csv):
+    """Test that a pickled writer writes to the same file with the same options."""
+    path = write_csv("")
+    try:
+        writer = Writer(path, delimiter="|", headers=["a", "b"])
+        await writer.write_row([1, 2])
+        await writer.close()
+        copy = pickle.loads(pickle.dumps(Writer(path, delimiter="|", headers=["a", "b"])))
+        await copy.write_row([3, 4])
+        await copy.close()
+        with open(path, newline="") as f:
+            assert f.read() == "a|b\r\n1|2\r\n3|4\r\n"
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_unpicklable_sources(write_csv):
+    """Test that encryption keys and file handles refuse to be pickled."""
+    path = write_csv("a\n1\n")
+    try:
+        with pytest.raises(TypeError, match="encryption_key"):
+            pickle.dumps(Reader(path, encryption_key=bytes(32)))
+        with pytest.raises(TypeError, match="encryption_key"):
+            pickle.dumps(Writer(path + ".enc", encryption_key=bytes(32)))
+        with open(path) as f, pytest.raises(TypeError, match="file handle"):
+            pickle.dumps(Reader(f))
+    finally:
+        os.unlink(path)
+        if os.path.exists(path + ".enc"):
+            os.unlink(path + ".enc")
@@ -0,0 +1,62 @@
+"""Test read-ahead with Reader(prefetch=N)."""
+
+import asyncio
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_prefetch_keeps_file_order(write_csv):
+    """Test that rows read ahead come back in order through every read method."""
+    rows = [[str(i), f"name {i}"] for i in range(500)]
+    path = write_csv("".join(f"{a},{b}\n" for a, b in rows))
+    for prefetch in (1, 7, 64, 1000):
+        reader = Reader(path, has_header=False, prefetch=prefetch)
+        assert [row async for row in reader] == rows
+
+    reader = Reader(path, has_header=False, read_size=64, prefetch=50)
+    got = [await reader.read_row()]
+    await asyncio.sleep(0.05)  # Let the background task parse ahead
+    got += await reader.read_rows(120)
+    got += [await reader.read_row() for _ in range(3)]
+    got += await reader.read_all()
+    assert got == rows
+    assert await reader.read_row() == []
+
+
+@pytest.mark.asyncio
+async def test_prefetch_runs_ahead_of_caller(write_csv):
+    """Test that rows are parsed in the background between reads."""
+    path = write_csv("".join(f"{i}\n" for i in range(100)))
+    async with Reader(path, has_header=False, prefetch=20) as reader:
+        assert await reader.read_row() == ["0"]
+        await asyncio.sleep(0.05)
+        assert reader.line_num == 21
+        with pytest.raises(ValueError, match="prefetch"):
+            reader.state()
+        assert len([row async for row in reader]) == 99
+
+
+@pytest.mark.asyncio
+async def test_prefetch_error_after_preceding_rows(write_csv):
+    """Test that a parse error is raised only once the rows before it are returned."""
+    path = write_csv(b"a,b\n1,2\n3,4\n5,6\n7,8\n9,\xff\n")
+    reader = Reader(path, read_size=4, prefetch=10)
+    assert await reader.read_row() == ["a", "b"]
+    await asyncio.sleep(0.05)
+    assert await reader.read_rows(3) == [["1", "2"], ["3", "4"], ["5", "6"]]
+    with pytest.raises(IOError, match="UTF-8"):
+        await reader.read_row()
+
+
+def test_prefetch_validation(write_csv):
+    """Test that prefetch rejects zero and options it cannot honour."""
+    path = write_csv("a\n1\n")
+    with pytest.raises(ValueError, match="at least 1"):
+        Reader(path, prefetch=0)
+    with pytest.raises(ValueError, match="follow"):
+        Reader(path, prefetch=10, follow=True)
+    with pytest.raises(ValueError, match="checkpoint_path"):
+        Reader(path, prefetch=10, checkpoint_path=path + ".ckpt")
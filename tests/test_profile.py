@@ -0,0 +1,63 @@
+"""Test column profiling with profile()."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import profile
+
+
+@pytest.mark.asyncio
+async def test_profile_column_types():
+    """Test statistics for integer, float, string and empty columns."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        f.write("id,price,name,blank\n")
+        f.write("1,2.5,apple,\n")
+        f.write("2,,banana,\n")
+        f.write("3,3.5,apple,\n")
+
+    try:
+        stats = await profile(test_file)
+        assert list(stats) == ["id", "price", "name", "blank"]
+
+        assert stats["id"]["type"] == "integer"
+        assert stats["id"]["min"] == 1
+        assert stats["id"]["max"] == 3
+        assert stats["id"]["mean"] == pytest.approx(2.0)
+        assert stats["id"]["distinct"] == 3
+
+        assert stats["price"]["type"] == "float"
+        assert stats["price"]["nulls"] == 1
+        assert stats["price"]["count"] == 3
+        assert stats["price"]["mean"] == pytest.approx(3.0)
+
+        assert stats["name"]["type"] == "string"
+        assert stats["name"]["min"] == "apple"
+        assert stats["name"]["max"] == "banana"
+        assert stats["name"]["distinct"] == 2
+        assert stats["name"]["max_length"] == 6
+        assert stats["name"]["mean"] is None
+
+        assert stats["blank"]["type"] == "empty"
+        assert stats["blank"]["nulls"] == 3
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_profile_distinct_estimate():
+    """Test that the distinct estimate stays close for high cardinality."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        f.write("value\n")
+        for i in range(50_000):
+            f.write(f"v{i % 25_000}\n")
+
+    try:
+        stats = await profile(test_file)
+        assert stats["value"]["count"] == 50_000
+        assert abs(stats["value"]["distinct"] - 25_000) < 25_000 * 0.05
+    finally:
+        os.unlink(test_file)
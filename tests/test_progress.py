@@ -0,0 +1,111 @@
+"""Test progress callbacks on Reader, Writer and the file utilities."""
+
+import os
+import sqlite3
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, Writer, concat, restructure, split, to_sqlite, transform
+
+CONTENT = "id,name\n" + "".join(f"{i},name {i}\n" for i in range(25))
+
+
+def _temp_path(suffix=".csv"):
+    with tempfile.NamedTemporaryFile(delete=False, suffix=suffix) as f:
+        return f.name
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("read_all", [False, True])
+async def test_reader_progress(read_all, write_csv):
+    """Test that Reader reports every progress_interval rows and once at EOF."""
+    test_file = write_csv(CONTENT)
+    calls = []
+    try:
+        reader = Reader(
+            test_file,
+            progress=lambda *args: calls.append(args),
+            progress_interval=10,
+        )
+        if read_all:
+            await reader.read_all()
+        else:
+            while await reader.read_row():
+                pass
+            await reader.read_row()  # A second EOF doesn't repeat the final report
+        total = len(CONTENT)
+        assert [rows for _, _, rows in calls] == [10, 20, 26]
+        assert all(bytes_total == total for _, bytes_total, _ in calls)
+        assert calls[-1][0] == total
+        if not read_all:
+            assert calls[0][0] < calls[1][0] < total
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_writer_progress():
+    """Test that Writer reports bytes and rows written, with no total."""
+    test_file = _temp_path()
+    calls = []
+    try:
+        writer = Writer(test_file, progress=lambda *args: calls.append(args), progress_interval=2)
+        await writer.write_row(["a"])
+        await writer.writerows([["b"], ["c"]])
+        await writer.write_row(["d"])
+        await writer.close()
+        assert calls == [(9, None, 3), (12, None, 4)]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_progress_errors(write_csv):
+    """Test validation of progress arguments and propagation of callback errors."""
+    test_file = write_csv(CONTENT)
+
+    def fail(*args):
+        raise RuntimeError("stop")
+
+    try:
+        with pytest.raises(TypeError, match="progress"):
+            Reader(test_file, progress="not callable")
+        with pytest.raises(ValueError, match="progress_interval"):
+            Reader(test_file, progress=print, progress_interval=0)
+        reader = Reader(test_file, progress=fail, progress_interval=1)
+        with pytest.raises(RuntimeError, match="stop"):
+            await reader.read_row()
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_utility_progress(write_csv):
+    """Test that the file utilities report progress ending at the full input size."""
+    src = write_csv(CONTENT)
+    dst = _temp_path()
+    db = _temp_path(".db")
+    parts = os.path.join(tempfile.mkdtemp(), "part_{}.csv")
+    total = len(CONTENT)
+    try:
+        jobs = {
+            "transform": lambda cb: transform(src, dst, lambda row: row, progress=cb),
+            "restructure": lambda cb: restructure(src, dst, drop=["name"], progress=cb),
+            "split": lambda cb: split(src, parts, rows_per_file=10, progress=cb),
+            "to_sqlite": lambda cb: to_sqlite(src, db, "t", progress=cb),
+        }
+        for name, job in jobs.items():
+            calls = []
+            await job(lambda *args: calls.append(args))
+            assert calls == [(total, total, 25)], name
+
+        calls = []
+        await concat([src, src], dst, progress=lambda *args: calls.append(args), progress_interval=20)
+        assert [rows for _, _, rows in calls] == [20, 40, 50]
+        assert calls[-1][:2] == (2 * total, 2 * total)
+        with sqlite3.connect(db) as conn:
+            assert conn.execute("SELECT COUNT(*) FROM t").fetchone() == (25,)
+    finally:
+        for path in (src, dst, db):
+            os.unlink(path)
@@ -0,0 +1,169 @@
+"""Test SQL queries over CSV files with query()."""
+
+import os
+
+import pytest
+
+from rapcsv import CSVError, query, query_batches
+
+try:
+    import pyarrow as pa
+
+    PYARROW_AVAILABLE = True
+except ImportError:
+    PYARROW_AVAILABLE = False
+
+
+@pytest.mark.asyncio
+async def test_query_group_by(write_csv):
+    """Test aggregating a CSV file with GROUP BY."""
+    test_file = write_csv("a,b\nx,1\ny,2\nx,3\ny,4.5\n")
+
+    try:
+        rows = await query(f"SELECT a, sum(b) FROM '{test_file}' GROUP BY a ORDER BY a")
+        assert rows == [["x", 4], ["y", 6.5]]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_query_as_dict_and_params(write_csv):
+    """Test dict rows and bound parameters with numeric comparison."""
+    test_file = write_csv("name,age\nAlice,30\nBob,9\nCarol,41\n")
+
+    try:
+        rows = await query(
+            f"SELECT name, age FROM '{test_file}' WHERE age > ? ORDER BY age",
+            params=[10],
+            as_dict=True,
+        )
+        assert rows == [{"name": "Alice", "age": 30}, {"name": "Carol", "age": 41}]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_query_join_and_string_literals(write_csv):
+    """Test joining two files; other string literals are left untouched."""
+    orders = write_csv("id,customer\n1,10\n2,20\n3,10\n")
+    customers = write_csv("id\tname\n10\tAcme\n20\tO'Brien\n", suffix=".tsv")
+
+    try:
+        rows = await query(
+            f"SELECT o.id, c.name, 'FROM x' FROM '{orders}' o "
+            f"JOIN '{customers}' c ON o.customer = c.id "
+            "WHERE c.name != 'nobody' ORDER BY o.id"
+        )
+        assert rows == [
+            [1, "Acme", "FROM x"],
+            [2, "O'Brien", "FROM x"],
+            [3, "Acme", "FROM x"],
+        ]
+    finally:
+        os.unlink(orders)
+        os.unlink(customers)
+
+
+@pytest.mark.asyncio
+async def test_query_requires_file():
+    """Test that a query without a file reference raises ValueError."""
+    with pytest.raises(ValueError):
+        await query("SELECT 1")
+
+
+@pytest.mark.asyncio
+async def test_query_batches(write_csv):
+    """Test a result yielded in batches, and stopping early."""
+    test_file = write_csv("n\n" + "".join(f"{i}\n" for i in range(25)))
+
+    try:
+        sql = f"SELECT n, n * 2 AS d FROM '{test_file}' ORDER BY n"
+        sizes = []
+        async for rows in query_batches(sql, as_dict=True, batch_size=10):
+            sizes.append(len(rows))
+        assert sizes == [10, 10, 5]
+        assert rows[-1] == {"n": 24, "d": 48}
+
+        # Leaving the loop early stops the query
+        async for rows in query_batches(sql, batch_size=1):
+            assert rows == [[0, 0]]
+            break
+        assert await query(sql) == [[i, i * 2] for i in range(25)]
+
+        with pytest.raises(IOError):
+            async for _ in query_batches(f"SELECT nope FROM '{test_file}'"):
+                pass
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_query_join_looks_rows_up(write_csv):
+    """Test a join of two larger files, matching numbers written differently."""
+    left = write_csv("id,v\n" + "".join(f"{i},{i * 3}\n" for i in range(2000)))
+    right = write_csv("key,w\n" + "".join(f"{i}.0,x{i}\n" for i in range(0, 2000, 7)) + ",empty\n")
+
+    try:
+        rows = await query(
+            f"SELECT l.id, l.v, r.w FROM '{left}' l JOIN '{right}' r ON r.key = l.id ORDER BY l.id"
+        )
+        assert rows == [[i, i * 3, f"x{i}"] for i in range(0, 2000, 7)]
+        assert await query(f"SELECT count(*) FROM '{right}' WHERE key IS NULL") == [[1]]
+    finally:
+        os.unlink(left)
+        os.unlink(right)
+
+
+@pytest.mark.asyncio
+async def test_query_reads_files_in_place(write_csv):
+    """Test that each query reads the file as it is now, and CSV errors keep their type."""
+    test_file = write_csv("a\n1\n")
+
+    try:
+        assert await query(f"SELECT sum(a) FROM '{test_file}'") == [[1]]
+        with open(test_file, "a") as f:
+            f.write("2\n")
+        assert await query(f"SELECT sum(a) FROM '{test_file}'") == [[3]]
+
+        with open(test_file, "wb") as f:
+            f.write(b"a,b\n1,\xff\n")
+        with pytest.raises(CSVError):
+            await query(f"SELECT * FROM '{test_file}'")
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_query_as_dict_and_as_arrow_conflict():
+    """Test that as_dict and as_arrow cannot both be set."""
+    with pytest.raises(ValueError, match="as_arrow"):
+        await query("SELECT * FROM 'x.csv'", as_dict=True, as_arrow=True)
+
+
+@pytest.mark.asyncio
+@pytest.mark.skipif(not PYARROW_AVAILABLE, reason="pyarrow not installed")
+async def test_query_as_arrow(write_csv):
+    """Test Arrow output, typed by each column's values."""
+    test_file = write_csv("a,b,c\nx,1,2\ny,2,2.5\nz,,3\n")
+
+    try:
+        table = await query(f"SELECT a, b, c, NULL AS n FROM '{test_file}'", as_arrow=True)
+        assert isinstance(table, pa.Table)
+        assert table.column_names == ["a", "b", "c", "n"]
+        assert [str(field.type) for field in table.schema] == ["string", "int64", "double", "null"]
+        assert table.to_pydict() == {
+            "a": ["x", "y", "z"],
+            "b": [1, 2, None],
+            "c": [2.0, 2.5, 3.0],
+            "n": [None, None, None],
+        }
+
+        sql = f"SELECT a FROM '{test_file}'"
+        batches = [batch async for batch in query_batches(sql, as_arrow=True, batch_size=2)]
+        assert [batch.num_rows for batch in batches] == [2, 1]
+        assert all(isinstance(batch, pa.RecordBatch) for batch in batches)
+
+        empty = await query(f"SELECT a FROM '{test_file}' WHERE 0", as_arrow=True)
+        assert empty.num_rows == 0 and empty.column_names == ["a"]
+    finally:
+        os.unlink(test_file)
@@ -0,0 +1,67 @@
+"""Test writing without quotes, escaping special characters, with quoting=0 (QUOTE_NONE)."""
+
+import csv
+import io
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import AsyncDictWriter, Writer
+
+
+def _read(path):
+    with open(path, newline="") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_escapes_like_csv_module():
+    """Test that delimiters, quotes, escape characters and line breaks are escaped."""
+    rows = [["a,b", 'q"x', "back\\slash", "line\nbreak\rcr"], ["1", "", "3", "4"]]
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    os.close(fd)
+    try:
+        async with Writer(path, quoting=0, escapechar="\\") as writer:
+            await writer.writerows(rows)
+        expected = io.StringIO()
+        csv.writer(expected, quoting=csv.QUOTE_NONE, escapechar="\\").writerows(rows)
+        assert _read(path) == expected.getvalue()
+        assert _read(path).startswith('a\\,b,q\\"x,back\\\\slash,line\\\nbreak')
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_dict_writer_and_dialect():
+    """Test AsyncDictWriter, another delimiter and a one-character line terminator."""
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    os.close(fd)
+    try:
+        writer = AsyncDictWriter(
+            path, ["x", "y"], quoting=0, escapechar="^", delimiter="|", lineterminator="\n"
+        )
+        await writer.writeheader()
+        await writer.writerow({"x": "1|2", "y": "a,b"})
+        await writer.writerows([{"x": "a\nb", "y": "^"}])
+        await writer.close()
+        assert _read(path) == "x|y\n1^|2|a,b\na^\nb|^^\n"
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_no_escapechar():
+    """Test that plain fields are written as they are, and ones needing escapes raise."""
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    os.close(fd)
+    try:
+        async with Writer(path, quoting=0) as writer:
+            await writer.write_row(["plain", 1, "text"])
+            with pytest.raises(ValueError, match="no escapechar"):
+                await writer.write_row(["a,b"])
+            with pytest.raises(ValueError, match="one empty field"):
+                await writer.write_row([""])
+        assert _read(path) == "plain,1,text\r\n"
+    finally:
+        os.unlink(path)
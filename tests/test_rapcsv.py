@@ -1,5 +1,6 @@
 """Test rapcsv async functionality."""
 
+import gc
 import os
 import tempfile
 
@@ -17,6 +18,7 @@ async def test_write_row():
     try:
         writer = Writer(test_file)
         await writer.write_row(["col1", "col2", "col3"])
+        await writer.close()
 
         # Verify file was written
         assert os.path.exists(test_file), "CSV file should exist"
@@ -43,6 +45,7 @@ async def test_write_multiple_rows():
         await writer.write_row(["name", "age", "city"])
         await writer.write_row(["Alice", "30", "New York"])
         await writer.write_row(["Bob", "25", "London"])
+        await writer.close()
 
         # Verify content
         with open(test_file) as f:
@@ -108,6 +111,7 @@ async def test_write_read_roundtrip():
         writer = Writer(test_file)
         await writer.write_row(["name", "age"])
         await writer.write_row(["Alice", "30"])
+        await writer.close()
 
         # Read rows
         reader = Reader(test_file)
@@ -131,6 +135,7 @@ async def test_csv_escaping():
         writer = Writer(test_file)
         # Test comma, quote, and newline in data
         await writer.write_row(["value,with,commas", 'value"with"quotes', "value\nwith\nnewlines"])
+        await writer.close()
 
         reader = Reader(test_file)
         row = await reader.read_row()
@@ -208,6 +213,7 @@ async def test_quoted_fields_with_commas():
     try:
         writer = Writer(test_file)
         await writer.write_row(['"value,with,commas"', "normal", '"another,one"'])
+        await writer.close()
 
         reader = Reader(test_file)
         row = await reader.read_row()
@@ -228,6 +234,7 @@ async def test_quoted_fields_with_newlines():
     try:
         writer = Writer(test_file)
         await writer.write_row(["normal", "value\nwith\nnewlines", "another"])
+        await writer.close()
 
         reader = Reader(test_file)
         row = await reader.read_row()
@@ -248,6 +255,7 @@ async def test_quoted_fields_with_quotes():
     try:
         writer = Writer(test_file)
         await writer.write_row(['value"with"quotes', "normal"])
+        await writer.close()
 
         reader = Reader(test_file)
         row = await reader.read_row()
@@ -303,6 +311,7 @@ async def test_aiocsv_compatibility_async_writer():
         # Should work with AsyncWriter alias
         writer = AsyncWriter(test_file)
         await writer.write_row(["col1", "col2"])
+        await writer.close()
 
         with open(test_file) as f:
             content = f.read()
@@ -358,6 +367,7 @@ async def test_concurrent_operations():
             write_rows(writer, 0, 10),
             write_rows(writer, 10, 10),
         )
+        await writer.close()
 
         # Verify all rows written
         reader = Reader(test_file)
@@ -372,3 +382,93 @@ async def test_concurrent_operations():
     finally:
         if os.path.exists(test_file):
             os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_read_rows_across_chunk_boundaries():
+    """Test that rows straddling read_size chunk boundaries stay intact."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        for i in range(500):
+            f.write(f"{i},value_{i},{i * 3}\n")
+
+    try:
+        reader = Reader(test_file, read_size=64)
+        rows = await reader.read_rows(1000)
+        assert len(rows) == 500
+        assert all(len(row) == 3 for row in rows)
+        assert rows[-1] == ["499", "value_499", "1497"]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_buffer_size():
+    """Test that small and large read buffers give the same rows."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        for i in range(500):
+            f.write(f'{i},"value {i}",{i * 3}\n')
+
+    try:
+        expected = await Reader(test_file).read_rows(1000)
+        for buffer_size in (7, 4 * 1024 * 1024):
+            reader = Reader(test_file, buffer_size=buffer_size, read_size=50)
+            assert await reader.read_rows(1000) == expected
+        with pytest.raises(ValueError, match="buffer_size"):
+            Reader(test_file, buffer_size=0)
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_writer_buffers_until_threshold():
+    """Test that rows are buffered until write_size bytes or flush()."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+
+    try:
+        writer = Writer(test_file, write_size=30)
+        await writer.write_row(["a", "b"])
+        assert os.path.getsize(test_file) == 0
+        await writer.writerows([["1" * 10, "2" * 10], ["3", "4"]])  # Reaches threshold
+        await writer.write_row(["5", "6"])
+        await writer.flush()
+        with open(test_file, newline="") as f:
+            assert f.read() == "a,b\r\n" + "1" * 10 + "," + "2" * 10 + "\r\n3,4\r\n5,6\r\n"
+        await writer.close()
+
+        unbuffered = Writer(test_file, write_size=0)  # Written through
+        await unbuffered.write_row(["7", "8"])
+        with open(test_file, newline="") as f:
+            assert f.read().endswith("5,6\r\n7,8\r\n")
+        await unbuffered.close()
+
+        default = Writer(test_file)  # Buffered for a file path by default
+        await default.write_row(["9", "0"])
+        with open(test_file, newline="") as f:
+            assert f.read().endswith("7,8\r\n")
+        await default.close()
+        with open(test_file, newline="") as f:
+            assert f.read().endswith("7,8\r\n9,0\r\n")
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_dropped_writer_writes_buffered_rows():
+    """Test that a buffering writer dropped without close() writes out its rows."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+
+    try:
+        writer = Writer(test_file, write_size=1 << 20)
+        await writer.write_row(["a", "b"])
+        assert os.path.getsize(test_file) == 0
+        with pytest.warns(ResourceWarning, match="not closed"):
+            del writer
+            gc.collect()
+        with open(test_file, newline="") as f:
+            assert f.read() == "a,b\r\n"
+    finally:
+        os.unlink(test_file)
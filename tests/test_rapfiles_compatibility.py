@@ -134,7 +134,7 @@ async def test_rapcsv_dictwriter_rapfiles_read():
         await writer.writeheader()
         await writer.writerow({"name": "Alice", "age": "30", "city": "New York"})
         await writer.writerow({"name": "Bob", "age": "25", "city": "London"})
-        # Note: AsyncDictWriter doesn't have close() yet, but file should be flushed
+        await writer.close()
 
         # Read using rapfiles and verify
         async with rapfiles.open(test_file, "r") as f:
@@ -243,6 +243,7 @@ async def test_rapfiles_roundtrip_dictreader():
         await writer.writeheader()
         for row in rows:
             await writer.writerow(row)
+        await writer.close()
 
         # Step 4: Verify with rapfiles
         async with rapfiles.open(output_file, "r") as f:
@@ -0,0 +1,67 @@
+"""Test reading undecoded records with Reader.read_raw_lines()."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+CONTENT = 'id,note\r\n1,"two\r\nlines"\r\n\r\n2,plain\r\n3,"a ""quoted"" word"'
+
+
+@pytest.mark.asyncio
+async def test_records_keep_their_bytes(write_csv):
+    """Test that quoted line breaks stay in a record and blank lines are skipped."""
+    path = write_csv(CONTENT)
+    try:
+        reader = Reader(path, has_header=True, schema={"id": "int"})
+        assert await reader.read_raw_lines(10) == [
+            b"id,note\r\n",
+            b'1,"two\r\nlines"\r\n',
+            b"2,plain\r\n",
+            b'3,"a ""quoted"" word"',
+        ]
+        assert await reader.read_raw_lines(10) == []
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_small_reads_and_batches(write_csv):
+    """Test records split across reads and batches smaller than the file."""
+    path = write_csv("".join(f'{i},"x\n{i}"\n' for i in range(500)))
+    try:
+        reader = Reader(path, read_size=16)
+        records = []
+        while batch := await reader.read_raw_lines(7):
+            assert len(batch) <= 7
+            records.extend(batch)
+        assert records == [f'{i},"x\n{i}"\n'.encode() for i in range(500)]
+        assert reader.line_num == 1000
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_mixed_with_parsed_rows(write_csv):
+    """Test that parsing picks up after the raw records."""
+    path = write_csv("a,b\n1,2\n3,4\n")
+    try:
+        reader = Reader(path)
+        assert await reader.read_raw_lines(1) == [b"a,b\n"]
+        assert await reader.read_row() == ["1", "2"]
+        assert await reader.read_raw_lines(5) == [b"3,4\n"]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_rejected_options(write_csv):
+    """Test that options working on parsed rows are rejected."""
+    path = write_csv("a,b\n1,2\n")
+    try:
+        for options in ({"columns": ["a"]}, {"limit": 1}, {"escapechar": "\\"}):
+            with pytest.raises(ValueError, match="read_raw_lines"):
+                await Reader(path, **options).read_raw_lines(1)
+    finally:
+        os.unlink(path)
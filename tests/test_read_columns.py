@@ -0,0 +1,70 @@
+"""Test reading batches as columns with Reader.read_columns()."""
+
+import os
+
+import pytest
+
+from rapcsv import CSVFieldCountError, Reader
+
+
+@pytest.mark.asyncio
+async def test_batches_of_columns(write_csv):
+    """Test batches keyed by the header, ending with an empty dict at EOF."""
+    path = write_csv("id,name\n1,a\n2,b\n3,c\n")
+    try:
+        reader = Reader(path)
+        assert await reader.read_columns(batch_size=2) == {"id": ["1", "2"], "name": ["a", "b"]}
+        assert await reader.read_columns(batch_size=2) == {"id": ["3"], "name": ["c"]}
+        assert await reader.read_columns(batch_size=2) == {}
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_whole_file_typed(write_csv):
+    """Test every remaining row at once, converted by the schema."""
+    path = write_csv("id,price,note\n1,2.5,x\n2,3.0\n")
+    try:
+        reader = Reader(path, schema={"id": "int", "price": "float"})
+        assert await reader.read_columns() == {
+            "id": [1, 2],
+            "price": [2.5, 3.0],
+            "note": ["x", None],
+        }
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_after_reading_rows(write_csv):
+    """Test that the header read earlier by read_row() names the columns."""
+    path = write_csv("a,b\n1,2\n3,4\n")
+    try:
+        reader = Reader(path)
+        assert await reader.read_row() == ["a", "b"]
+        assert await reader.read_row() == ["1", "2"]
+        assert await reader.read_columns() == {"a": ["3"], "b": ["4"]}
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_without_header(write_csv):
+    """Test that columns are keyed by position without a header."""
+    path = write_csv("1,2\n3\n")
+    try:
+        reader = Reader(path, has_header=False)
+        assert await reader.read_columns() == {0: ["1", "3"], 1: ["2", None]}
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_row_longer_than_header(write_csv):
+    """Test that a row with more fields than the header raises."""
+    path = write_csv("a,b\n1,2\n3,4,5\n")
+    try:
+        with pytest.raises(CSVFieldCountError, match="Row 2 has 3 fields"):
+            await Reader(path).read_columns()
+    finally:
+        os.unlink(path)
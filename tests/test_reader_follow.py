@@ -0,0 +1,77 @@
+"""Test Reader follow mode for growing files."""
+
+import asyncio
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader
+
+
+def _append(path, text):
+    with open(path, "a") as f:
+        f.write(text)
+
+
+@pytest.mark.asyncio
+async def test_follow_yields_appended_rows():
+    """Test that rows appended after EOF are returned instead of ending."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        f.write("id,event\n1,start\n")
+
+    try:
+        reader = Reader(test_file, follow=True, poll_interval=0.01)
+        assert await reader.read_row() == ["id", "event"]
+        assert await reader.read_row() == ["1", "start"]
+
+        pending = asyncio.ensure_future(reader.read_row())
+        await asyncio.sleep(0.05)
+        assert not pending.done()
+
+        _append(test_file, "2,stop\n")
+        assert await asyncio.wait_for(pending, timeout=5) == ["2", "stop"]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_follow_waits_for_complete_line():
+    """Test that a partially written line is not returned until it is terminated."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        f.write("a,b\n")
+
+    try:
+        reader = Reader(test_file, follow=True, poll_interval=0.01)
+        assert await reader.read_row() == ["a", "b"]
+
+        _append(test_file, "hello,wor")
+        pending = asyncio.ensure_future(reader.read_row())
+        await asyncio.sleep(0.05)
+        assert not pending.done()
+
+        _append(test_file, "ld\n")
+        assert await asyncio.wait_for(pending, timeout=5) == ["hello", "world"]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_follow_timeout_and_validation():
+    """Test stopping a follow read with a timeout and rejecting bad poll intervals."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        f.write("x\n")
+
+    try:
+        reader = Reader(test_file, follow=True, poll_interval=0.01)
+        assert await reader.read_row() == ["x"]
+        with pytest.raises(asyncio.TimeoutError):
+            await asyncio.wait_for(reader.read_row(), timeout=0.1)
+
+        with pytest.raises(ValueError, match="poll_interval"):
+            Reader(test_file, follow=True, poll_interval=0)
+    finally:
+        os.unlink(test_file)
@@ -0,0 +1,119 @@
+"""Test reading several files as one stream with Reader."""
+
+import os
+import shutil
+import tempfile
+
+import pytest
+
+from rapcsv import Reader
+
+
+def _make_parts(directory, parts):
+    paths = []
+    for i, content in enumerate(parts):
+        path = os.path.join(directory, f"part-{i:02d}.csv")
+        with open(path, "w") as f:
+            f.write(content)
+        paths.append(path)
+    return paths
+
+
+@pytest.mark.asyncio
+async def test_reader_list_of_paths_skips_later_headers():
+    """Test that only the first file's header is returned."""
+    directory = tempfile.mkdtemp()
+    try:
+        paths = _make_parts(directory, ["id,v\n1,a\n", "id,v\n2,b\n3,c", "id,v\n"])
+        reader = Reader(paths)
+        rows = await reader.read_rows(10)
+        assert rows == [["id", "v"], ["1", "a"], ["2", "b"], ["3", "c"]]
+    finally:
+        shutil.rmtree(directory)
+
+
+@pytest.mark.asyncio
+async def test_reader_glob_pattern():
+    """Test that a glob pattern is expanded in sorted order."""
+    directory = tempfile.mkdtemp()
+    try:
+        _make_parts(directory, ["n\n1\n", "n\n2\n", "n\n3\n"])
+        reader = Reader(os.path.join(directory, "part-*.csv"))
+        rows = await reader.read_rows(10)
+        assert rows == [["n"], ["1"], ["2"], ["3"]]
+
+        with pytest.raises(IOError, match="No files match"):
+            Reader(os.path.join(directory, "missing-*.csv"))
+    finally:
+        shutil.rmtree(directory)
+
+
+@pytest.mark.asyncio
+async def test_reader_include_filename():
+    """Test that rows carry the path of the file they came from."""
+    directory = tempfile.mkdtemp()
+    try:
+        paths = _make_parts(directory, ["n\n1\n", "n\n2\n"])
+        reader = Reader(paths, include_filename=True)
+        rows = await reader.read_rows(10)
+        assert rows == [["n", "_file"], ["1", paths[0]], ["2", paths[1]]]
+    finally:
+        shutil.rmtree(directory)
+
+
+@pytest.mark.asyncio
+async def test_reader_headerless_files():
+    """Test has_header=False keeps the first row of every file."""
+    directory = tempfile.mkdtemp()
+    try:
+        paths = _make_parts(directory, ["1,a\n", "2,b\n"])
+        reader = Reader(paths, has_header=False)
+        assert await reader.read_rows(10) == [["1", "a"], ["2", "b"]]
+
+        with pytest.raises(ValueError):
+            Reader([])
+    finally:
+        shutil.rmtree(directory)
+
+
+@pytest.mark.asyncio
+async def test_reader_concurrency_preserves_file_order():
+    """Test that parallel parsing returns the same rows in file order."""
+    directory = tempfile.mkdtemp()
+    try:
+        parts = [
+            "id,part\n" + "".join(f"{p * 100 + i},{p}\n" for i in range(100))
+            for p in range(20)
+        ]
+        paths = _make_parts(directory, parts)
+
+        serial = await Reader(paths).read_rows(10_000)
+        parallel_reader = Reader(paths, concurrency=4, include_filename=True)
+        parallel = await parallel_reader.read_rows(10_000)
+
+        assert len(parallel) == 2001
+        assert [row[:-1] for row in parallel] == serial
+        assert parallel[-1] == ["1999", "19", paths[-1]]
+        assert parallel_reader.line_num == 2020  # Skipped headers still count as lines
+    finally:
+        shutil.rmtree(directory)
+
+
+@pytest.mark.asyncio
+async def test_reader_concurrency_errors():
+    """Test that missing files raise and bad options are rejected."""
+    directory = tempfile.mkdtemp()
+    try:
+        paths = _make_parts(directory, ["n\n1\n"])
+        reader = Reader(paths + [os.path.join(directory, "gone.csv")], concurrency=2)
+        assert await reader.read_row() == ["n"]
+        assert await reader.read_row() == ["1"]
+        with pytest.raises(IOError):
+            await reader.read_row()
+
+        with pytest.raises(ValueError):
+            Reader(paths, concurrency=0)
+        with pytest.raises(ValueError, match="follow"):
+            Reader(paths, concurrency=2, follow=True)
+    finally:
+        shutil.rmtree(directory)
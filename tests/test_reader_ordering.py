@@ -0,0 +1,80 @@
+"""Test that concurrent Reader calls each get the next rows, in call order."""
+
+import asyncio
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import AsyncDictReader, Reader
+
+
+def _rows(count):
+    return "".join(f"{i},{'x' * (i % 40)}\n" for i in range(count))
+
+
+@pytest.mark.asyncio
+async def test_concurrent_read_row_keeps_call_order(write_csv):
+    """Test that read_row() calls started together get consecutive rows, once each."""
+    path = write_csv(_rows(500))
+    try:
+        reader = Reader(path, read_size=64)
+        # Start every call before awaiting any of them
+        rows = await asyncio.gather(*[reader.read_row() for _ in range(510)])
+        assert [int(row[0]) for row in rows[:500]] == list(range(500))
+        assert rows[500:] == [[]] * 10
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_workers_share_rows_exactly_once(write_csv):
+    """Test a work queue: tasks reading rows and batches until EOF see every row once."""
+    path = write_csv(_rows(2000))
+    try:
+        reader = Reader(path, read_size=128)
+
+        async def worker(batched):
+            seen = []
+            while True:
+                rows = await reader.read_rows(7) if batched else [await reader.read_row()]
+                rows = [row for row in rows if row]
+                if not rows:
+                    return seen
+                seen.extend(int(row[0]) for row in rows)
+                await asyncio.sleep(0)
+
+        results = await asyncio.gather(*[worker(i % 2 == 0) for i in range(8)])
+        seen = [n for result in results for n in result]
+        assert sorted(seen) == list(range(2000))
+        for result in results:
+            assert result == sorted(result)
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_concurrent_reads_with_limit(write_csv):
+    """Test that a limit is never filled by a later row than an earlier call's."""
+    path = write_csv(_rows(300))
+    try:
+        reader = Reader(path, limit=100, skip_footer=5)
+        rows = await asyncio.gather(*[reader.read_row() for _ in range(120)])
+        assert [int(row[0]) for row in rows[:100]] == list(range(100))
+        assert rows[100:] == [[]] * 20
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_concurrent_dict_reads_take_header_first():
+    """Test that the first call reads the header even when calls start together."""
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    with os.fdopen(fd, "w", newline="") as f:
+        f.write("id,name\n" + "".join(f"{i},n{i}\n" for i in range(50)))
+    try:
+        reader = AsyncDictReader(path)
+        rows = await asyncio.gather(*[reader.read_row() for _ in range(50)])
+        assert rows == [{"id": str(i), "name": f"n{i}"} for i in range(50)]
+    finally:
+        os.unlink(path)
@@ -0,0 +1,36 @@
+"""Test Reader(raw=True), which returns fields as bytes."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_raw_fields_are_bytes(write_csv):
+    """Test read_row, read_rows and read_all in raw mode."""
+    test_file = write_csv('id,name\n1,"a, b"\n2,café\n3,x\n')
+    try:
+        reader = Reader(test_file, raw=True)
+        assert await reader.read_row() == [b"id", b"name"]
+        assert await reader.read_rows(1) == [[b"1", b"a, b"]]
+        assert await reader.read_all() == [[b"2", "café".encode()], [b"3", b"x"]]
+        assert await reader.read_row() == []
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_raw_iteration(write_csv):
+    """Test async iteration in raw mode."""
+    test_file = write_csv("a,b\n1,2\n")
+    try:
+        rows = []
+        async for row in Reader(test_file, raw=True):
+            if not row:
+                break
+            rows.append(row)
+        assert rows == [[b"a", b"b"], [b"1", b"2"]]
+    finally:
+        os.unlink(test_file)
@@ -0,0 +1,218 @@
+"""Test Reader.read_all() and parallel chunked parsing."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader, Writer, io_backend
+
+
+@pytest.mark.asyncio
+async def test_read_all_sequential(write_csv):
+    """Test that read_all returns every remaining row."""
+    test_file = write_csv("a,b\n1,2\n3,4\n")
+    try:
+        reader = Reader(test_file)
+        assert await reader.read_row() == ["a", "b"]
+        assert await reader.read_all() == [["1", "2"], ["3", "4"]]
+        assert await reader.read_row() == []
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_read_all_threads_matches_sequential(write_csv):
+    """Test that threaded parsing keeps order and quoted newlines intact."""
+    lines = ["id,text\n"]
+    for i in range(2000):
+        if i % 7 == 0:
+            lines.append(f'{i},"multi\nline, ""quoted"" {i}"\n')
+        else:
+            lines.append(f"{i},plain {i}\r\n")
+    test_file = write_csv("".join(lines))
+    try:
+        expected = await Reader(test_file).read_all()
+        reader = Reader(test_file)
+        rows = await reader.read_all(threads=4)
+        assert rows == expected
+        assert len(rows) == 2001
+        assert rows[1] == ["0", 'multi\nline, "quoted" 0']
+
+        sequential = Reader(test_file)
+        await sequential.read_all()
+        assert reader.line_num == sequential.line_num
+
+        # The reader is left at EOF
+        assert await reader.read_row() == []
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_read_all_threads_errors(write_csv):
+    """Test argument validation for read_all()."""
+    test_file = write_csv("a,b\n1,2\n")
+    try:
+        with pytest.raises(ValueError):
+            await Reader(test_file).read_all(threads=0)
+        with pytest.raises(ValueError, match="follow"):
+            await Reader(test_file, follow=True).read_all()
+        # A stream that can't be split refuses threads rather than ignoring them
+        reader = Reader(test_file)
+        await reader.read_row()
+        with pytest.raises(ValueError, match="threads"):
+            await reader.read_all(threads=2)
+        assert await reader.read_all() == [["1", "2"]]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("threads", [1, 4])
+async def test_read_all_fast_engine_matches_default(threads, write_csv):
+    """Test that engine="fast" parses quoting, blank lines and CRLF like the default."""
+    lines = ["id,text,empty\n"]
+    for i in range(1000):
+        if i % 5 == 0:
+            lines.append(f'{i},"a ""b"", c\r\nd",""\r\n')
+        elif i % 11 == 0:
+            lines.append(f"{i},x,\n\n")
+        else:
+            lines.append(f"{i},plain {i},\n")
+    lines.append("last,no,newline")
+    test_file = write_csv("".join(lines))
+    try:
+        default = Reader(test_file)
+        expected = await default.read_all()
+        fast = Reader(test_file, engine="fast")
+        rows = await fast.read_all(threads=threads)
+        assert rows == expected
+        assert rows[1] == ["0", 'a "b", c\r\nd', ""]
+        assert fast.line_num == default.line_num
+        assert await fast.read_row() == []
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_read_all_fast_engine_falls_back(write_csv):
+    """Test that input the fast parser rejects is still parsed by the default engine."""
+    test_file = write_csv('a,b\n"x"y,2\n"unterminated\n')
+    try:
+        expected = await Reader(test_file).read_all()
+        assert await Reader(test_file, engine="fast").read_all() == expected
+        with pytest.raises(ValueError, match="engine"):
+            Reader(test_file, engine="simd")
+        # Readers that read_all() streams can't use the fast engine
+        for options in ({"limit": 2}, {"row_type": "row"}, {"strict_rfc4180": True}):
+            with pytest.raises(ValueError, match="engine='fast'"):
+                Reader(test_file, engine="fast", **options)
+        with pytest.raises(ValueError, match="engine='fast'"):
+            Reader([test_file, test_file], engine="fast")
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("engine", ["default", "fast"])
+async def test_read_all_mmap(engine, write_csv):
+    """Test that mmap=True parses the same rows and leaves the reader at EOF."""
+    test_file = write_csv("a,b\n" + "".join(f'{i},"v {i}"\n' for i in range(500)))
+    try:
+        expected = await Reader(test_file).read_all()
+        reader = Reader(test_file, mmap=True, engine=engine)
+        assert await reader.read_all(threads=2) == expected
+        assert reader.line_num == 501
+        assert await reader.read_row() == []
+
+        empty = write_csv("")
+        try:
+            assert await Reader(empty, mmap=True).read_all() == []
+        finally:
+            os.unlink(empty)
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_mmap_requires_path(write_csv):
+    """Test that mmap=True is rejected for file handles and followed files."""
+    test_file = write_csv("a,b\n1,2\n")
+    try:
+        with open(test_file) as f:
+            with pytest.raises(ValueError, match="mmap"):
+                Reader(f, mmap=True)
+        with pytest.raises(ValueError, match="mmap cannot be combined with follow"):
+            Reader(test_file, mmap=True, follow=True)
+    finally:
+        os.unlink(test_file)
+
+
+def test_io_backend():
+    """Test that the reported IO backend is one of the known values."""
+    assert io_backend() in ("io_uring", "tokio")
+
+
+@pytest.mark.asyncio
+async def test_streaming_through_io_backend(write_csv):
+    """Test small streaming reads and appended writes, whichever backend serves them."""
+    test_file = write_csv("id,value\n")
+    try:
+        rows = [[str(i), f"value {i}"] for i in range(5000)]
+        async with Writer(test_file, write_size=1000) as writer:
+            await writer.writerows(rows[:2500])
+        async with Writer(test_file) as writer:
+            await writer.writerows(rows[2500:])
+        reader = Reader(test_file, read_size=100, buffer_size=1000)
+        assert [row async for row in reader] == [["id", "value"]] + rows
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_read_all_keeps_event_loop_responsive(write_csv):
+    """Test that parsing a large file neither blocks the event loop nor holds the GIL."""
+    import asyncio
+    import threading
+
+    test_file = write_csv("".join(f'{i},"value {i}",{i * 2}\n' for i in range(200_000)))
+    ticks = 0
+    thread_ticks = 0
+    done = threading.Event()
+
+    async def ticker():
+        nonlocal ticks
+        while not done.is_set():
+            ticks += 1
+            await asyncio.sleep(0)
+
+    def spinner():
+        nonlocal thread_ticks
+        while not done.is_set():
+            thread_ticks += 1
+
+    try:
+        thread = threading.Thread(target=spinner)
+        thread.start()
+        task = asyncio.create_task(ticker())
+        rows = await Reader(test_file).read_all()
+        done.set()
+        await task
+        thread.join()
+        assert len(rows) == 200_000
+        assert ticks > 1
+        assert thread_ticks > 0
+    finally:
+        done.set()
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_read_all_with_escapechar(write_csv):
+    """Test that escaped files are parsed whole, ignoring threads."""
+    test_file = write_csv('a,b\n"x\\"y",2\n3,4\n')
+    try:
+        expected = [["a", "b"], ['x"y', "2"], ["3", "4"]]
+        assert await Reader(test_file, escapechar="\\").read_all(threads=4) == expected
+    finally:
+        os.unlink(test_file)
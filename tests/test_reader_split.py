@@ -0,0 +1,63 @@
+"""Test forking a reader into readers over ranges of a file with Reader.split()."""
+
+import pytest
+
+from rapcsv import Reader
+
+
+async def _read_parts(parts):
+    rows = []
+    for part in parts:
+        rows.extend(await part.read_all())
+    return rows
+
+
+@pytest.mark.asyncio
+async def test_split_covers_the_file(write_csv):
+    """Test that the parts together return the rows of one reader, in order."""
+    content = "id,note\r\n" + "".join(
+        f'{i},"line one\r\nline ""{i}"""\r\n' if i % 7 == 0 else f"{i},plain\r\n"
+        for i in range(500)
+    )
+    path = write_csv(content + "\r\n\r\n500,last")
+    expected = await Reader(path).read_all()
+    for n in (1, 2, 3, 8):
+        parts = await Reader(path).split(n)
+        assert len(parts) == n
+        assert await _read_parts(parts) == expected
+
+    # Each part starts at a record boundary with the counters of a single reader
+    parts = await Reader(path).split(4)
+    first = await parts[2].read_row()
+    assert first[1] in ("plain", 'line one\r\nline "{}"'.format(first[0]))
+    reader = Reader(path)
+    while (await reader.read_row()) != first:
+        pass
+    assert parts[2].line_num == reader.line_num
+
+    # Rows by name: later parts take the header the first one consumes
+    parts = await Reader(path).split(3, row_type="row")
+    rows = await _read_parts(parts)
+    assert len(rows) == 501
+    assert rows[-1]["id"] == "500"
+    assert rows[250]["note"] == "plain"
+
+
+@pytest.mark.asyncio
+async def test_split_small_file_and_errors(write_csv):
+    """Test that a short file gives fewer parts, and what cannot be split."""
+    path = write_csv("a,b\n1,2\n")
+    parts = await Reader(path).split(5, delimiter=",")
+    assert len(parts) == 2
+    assert await _read_parts(parts) == [["a", "b"], ["1", "2"]]
+
+    reader = Reader(path)
+    await reader.read_row()
+    with pytest.raises(ValueError, match="has not been read from"):
+        await reader.split(2)
+    with pytest.raises(ValueError, match="escapechar"):
+        await Reader(path, escapechar="\\").split(2)
+    with pytest.raises(ValueError, match="at least 1"):
+        await Reader(path).split(0)
+    with pytest.raises(ValueError, match="single file path"):
+        await Reader([path, path]).split(2)
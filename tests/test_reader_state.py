@@ -0,0 +1,65 @@
+"""Test Reader.state() and Reader.from_state()."""
+
+import json
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_resume_from_state(write_csv):
+    """Test that a reader rebuilt from a saved state continues with the next row."""
+    test_file = write_csv('a;b\n1;"x\ny"\n2;z\n3;w\n')
+    try:
+        reader = Reader(test_file, delimiter=";")
+        assert await reader.read_rows(2) == [["a", "b"], ["1", "x\ny"]]
+        state = json.loads(json.dumps(reader.state()))
+        assert state["row_index"] == 2
+        assert state["line_num"] == 3
+        assert state["byte_offset"] == reader.byte_offset
+        assert state["dialect"]["delimiter"] == ";"
+
+        resumed = Reader.from_state(state)
+        assert await resumed.read_row() == ["2", "z"]
+        assert resumed.line_num == 4
+        assert await resumed.read_all() == [["3", "w"]]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_resume_multi_file_and_rows(write_csv):
+    """Test resuming across files and with Row objects."""
+    first = write_csv("k,v\na,1\n")
+    second = write_csv("k,v\nb,2\nc,3\n")
+    try:
+        reader = Reader([first, second], row_type="row")
+        assert (await reader.read_row()).k == "a"
+        state = reader.state()
+        assert state["header"] == ["k", "v"]
+
+        resumed = Reader.from_state(state, row_type="row")
+        rows = await resumed.read_all()
+        assert [row.k for row in rows] == ["b", "c"]
+    finally:
+        os.unlink(first)
+        os.unlink(second)
+
+
+def test_invalid_states(write_csv):
+    """Test that unusable states and sources are rejected."""
+    test_file = write_csv("a\n1\n")
+    try:
+        state = Reader(test_file).state()
+        with pytest.raises(ValueError, match="version"):
+            Reader.from_state({**state, "version": 99})
+        with pytest.raises(ValueError, match="missing 'paths'"):
+            Reader.from_state({k: v for k, v in state.items() if k != "paths"})
+        with pytest.raises(ValueError, match="past the end"):
+            Reader.from_state({**state, "byte_offset": 1000})
+        with pytest.raises(ValueError, match="concurrency"):
+            Reader([test_file, test_file], concurrency=2).state()
+    finally:
+        os.unlink(test_file)
@@ -0,0 +1,85 @@
+"""Test rapcsv.repair()."""
+
+import os
+
+import pytest
+
+from rapcsv import lint, repair
+
+
+def _read(path):
+    with open(path, "rb") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_repair_all_fixes(write_csv):
+    """Test each fix and the changes reported for it."""
+    src = write_csv(
+        b"id,name,city\r\n"
+        b"1,ann\r\n"  # Short
+        b"2,bob,rome,extra\r\n"  # Long
+        b"3,caf\xe9,paris\r\n"  # Latin-1 byte
+        b'4,"dan,oslo\r\n'  # Quote never closed
+        b"5,eve,kyiv\r\n"
+    )
+    dst = src + ".out"
+    report = await repair(src, dst)
+    assert _read(dst) == (
+        b"id,name,city\r\n"
+        b"1,ann,\r\n"
+        b"2,bob,rome\r\n"
+        b"3,caf\xef\xbf\xbd,paris\r\n"
+        b'4,"dan,oslo",\r\n'
+        b"5,eve,kyiv\r\n"
+    )
+    assert report["rows_written"] == 5
+    assert report["quotes_closed"] == 1
+    assert report["rows_padded"] == 2
+    assert report["rows_truncated"] == 1
+    assert report["fields_reencoded"] == 1
+    assert report["truncated"] is False
+    changes = [(c["fix"], c["row"], c["line"]) for c in report["changes"]]
+    assert changes == [
+        ("quotes", None, 5),
+        ("ragged", 1, 2),
+        ("ragged", 2, 3),
+        ("encoding", 3, 4),
+        ("ragged", 4, 5),
+    ]
+    assert report["changes"][2]["message"] == "truncated from 4 to 3 fields, dropping 'extra'"
+    assert (await lint(dst))["clean"]
+
+
+@pytest.mark.asyncio
+async def test_repair_selected_fixes(write_csv):
+    """Test that fixes not asked for leave rows as parsed."""
+    src = write_csv(b"a,b\n1\n2,\xff\n")
+    dst = src + ".out"
+    report = await repair(src, dst, fixes="encoding")
+    assert _read(dst) == b"a,b\r\n1\r\n2,\xef\xbf\xbd\r\n"
+    assert report["rows_padded"] == 0
+
+    report = await repair(src, dst, fixes=["ragged"], max_changes=0)
+    assert _read(dst) == b"a,b\r\n1,\r\n2,\xff\r\n"
+    assert report["rows_padded"] == 1
+    assert report["changes"] == []
+    assert report["truncated"] is True
+
+
+@pytest.mark.asyncio
+async def test_repair_dialect_and_errors(write_csv):
+    """Test the delimiter option, unknown fixes and a missing file."""
+    src = write_csv(b"a;b\n1;2;3\n")
+    dst = src + ".out"
+    report = await repair(src, dst, delimiter=";")
+    assert _read(dst) == b"a;b\r\n1;2\r\n"
+    assert report["rows_truncated"] == 1
+    with pytest.raises(ValueError, match="Unknown fix 'typos'"):
+        await repair(src, dst, fixes=["quotes", "typos"])
+    with pytest.raises(ValueError, match="is the input file"):
+        await repair(src, src)
+    assert _read(src) == b"a;b\n1;2;3\n"
+    os.unlink(src)
+    with pytest.raises(IOError):
+        await repair(src, dst)
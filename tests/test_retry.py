@@ -0,0 +1,82 @@
+"""Test retries of transient IO errors on Reader and Writer."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+
+class _FlakyHandle:
+    """Async file-like object whose first reads fail with `error`."""
+
+    def __init__(self, content, failures, error):
+        self.content = content
+        self.failures = failures
+        self.error = error
+
+    async def read(self, size):
+        if self.failures:
+            self.failures -= 1
+            raise self.error
+        chunk, self.content = self.content[:size], self.content[size:]
+        return chunk
+
+
+@pytest.mark.asyncio
+async def test_reader_retries_transient_errors():
+    """Test that transient read errors are retried with a growing delay."""
+    calls = []
+    handle = _FlakyHandle("a,b\n1,2\n", failures=2, error=ConnectionResetError("reset"))
+    reader = Reader(
+        handle,
+        retries=3,
+        retry_backoff=0.01,
+        on_retry=lambda attempt, error, delay: calls.append((attempt, type(error), delay)),
+    )
+    assert await reader.read_all() == [["a", "b"], ["1", "2"]]
+    assert calls == [(1, ConnectionResetError, 0.01), (2, ConnectionResetError, 0.02)]
+
+
+@pytest.mark.asyncio
+async def test_reader_gives_up_after_retries():
+    """Test that the error is raised once the retries are used up."""
+    handle = _FlakyHandle("a,b\n", failures=5, error=TimeoutError("stalled"))
+    reader = Reader(handle, retries=2, retry_backoff=0)
+    with pytest.raises(TimeoutError):
+        await reader.read_row()
+
+
+@pytest.mark.asyncio
+async def test_reader_does_not_retry_other_errors():
+    """Test that errors that aren't transient are raised straight away."""
+    calls = []
+    handle = _FlakyHandle("a,b\n", failures=1, error=ValueError("bad"))
+    reader = Reader(handle, retries=3, on_retry=lambda *args: calls.append(args))
+    with pytest.raises(ValueError):
+        await reader.read_row()
+    assert calls == []
+
+
+@pytest.mark.asyncio
+async def test_reader_without_retries_raises():
+    """Test that transient errors are raised when retries are not enabled."""
+    handle = _FlakyHandle("a,b\n", failures=1, error=BlockingIOError("again"))
+    reader = Reader(handle)
+    with pytest.raises(BlockingIOError):
+        await reader.read_row()
+
+
+@pytest.mark.parametrize("cls", [Reader, Writer])
+def test_invalid_retry_arguments(cls):
+    """Test that retry arguments are validated."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        test_file = f.name
+    try:
+        with pytest.raises(ValueError, match="retry_backoff"):
+            cls(test_file, retries=1, retry_backoff=-1)
+        with pytest.raises(TypeError, match="on_retry"):
+            cls(test_file, retries=1, on_retry="not callable")
+    finally:
+        os.unlink(test_file)
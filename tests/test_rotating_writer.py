@@ -0,0 +1,105 @@
+"""Test RotatingWriter rolling over to new files."""
+
+import asyncio
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, RotatingWriter
+
+
+def _read(path):
+    with open(path, "rb") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_rotate_by_rows_and_bytes():
+    """Test that parts fill to the row and size limits, each with the header."""
+    with tempfile.TemporaryDirectory() as tmp:
+        base = os.path.join(tmp, "out.csv")
+        async with RotatingWriter(base, headers=["id", "name"], max_rows=2) as writer:
+            assert writer.path is None
+            await writer.writerows([[1, "a"], [2, "b"], [3, "c"]])
+            assert writer.path == os.path.join(tmp, "out-0002.csv")
+            await writer.write_row([4, "d"])
+            await writer.write_row([5, "e"])
+        assert writer.paths == [os.path.join(tmp, f"out-000{n}.csv") for n in (1, 2, 3)]
+        assert _read(writer.paths[0]) == b"id,name\r\n1,a\r\n2,b\r\n"
+        assert _read(writer.paths[2]) == b"id,name\r\n5,e\r\n"
+        rows = []
+        for path in writer.paths:
+            rows.extend((await Reader(path).read_all())[1:])
+        assert rows == [[str(i), name] for i, name in enumerate("abcde", 1)]
+
+        # The header counts towards max_bytes; a row larger than it gets a part alone
+        base = os.path.join(tmp, "sized")
+        writer = RotatingWriter(base, headers=["h"], max_bytes=10, lineterminator="\n")
+        await writer.writerows([["aaa"], ["bbb"], ["c" * 20], ["d"]])
+        await writer.close()
+        assert [_read(path) for path in writer.paths] == [
+            b"h\naaa\nbbb\n",
+            b"h\n" + b"c" * 20 + b"\n",
+            b"h\nd\n",
+        ]
+        assert writer.paths[0] == os.path.join(tmp, "sized-0001")
+
+
+@pytest.mark.asyncio
+async def test_rotate_by_time_and_on_demand():
+    """Test max_seconds, rotate() and the constructor checks."""
+    with tempfile.TemporaryDirectory() as tmp:
+        base = os.path.join(tmp, "log.tsv")
+        writer = RotatingWriter(base, max_seconds=0.05, delimiter="\t")
+        await writer.write_row(["a", 1])
+        await writer.write_row(["b", 2])
+        await asyncio.sleep(0.1)
+        await writer.write_row(["c", 3])
+        await writer.rotate()
+        await writer.write_row(["d", 4])
+        await writer.close()
+        assert [_read(path) for path in writer.paths] == [
+            b"a\t1\r\nb\t2\r\n",
+            b"c\t3\r\n",
+            b"d\t4\r\n",
+        ]
+
+        with pytest.raises(ValueError, match="max_bytes, max_rows or max_seconds"):
+            RotatingWriter(base)
+        with pytest.raises(ValueError, match="at least 1"):
+            RotatingWriter(base, max_rows=0)
+        with pytest.raises(ValueError, match="positive"):
+            RotatingWriter(base, max_seconds=-1)
+
+
+@pytest.mark.asyncio
+async def test_restart_continues_numbering():
+    """Test that a second writer adds parts after the existing ones."""
+    with tempfile.TemporaryDirectory() as tmp:
+        base = os.path.join(tmp, "out.csv")
+        first = RotatingWriter(base, max_rows=1)
+        await first.writerows([["a"], ["b"]])
+        await first.close()
+        # Not parts of out.csv: a different stem, extension or number format
+        for name in ("out-0009.tsv", "out-12.csv", "outer-0010.csv"):
+            with open(os.path.join(tmp, name), "w") as f:
+                f.write("x\n")
+
+        second = RotatingWriter(base, max_rows=1)
+        await second.writerows([["c"], ["d"]])
+        await second.close()
+        assert second.paths == [os.path.join(tmp, f"out-000{n}.csv") for n in (3, 4)]
+        assert [_read(path) for path in first.paths] == [b"a\r\n", b"b\r\n"]
+        assert second.path is None
+
+        # The paths can be read while a write holds the writer
+        writer = RotatingWriter(base, max_rows=1)
+        write = asyncio.ensure_future(writer.writerows([[i] for i in range(100)]))
+        await asyncio.sleep(0)
+        seen = writer.paths
+        await write
+        assert writer.paths[: len(seen)] == seen
+        await writer.close()
+        assert len(writer.paths) == 100
+        assert writer.paths[0] == os.path.join(tmp, "out-0005.csv")
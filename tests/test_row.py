@@ -0,0 +1,60 @@
+"""Test Row objects returned by Reader(row_type="row")."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader, Row
+
+
+@pytest.mark.asyncio
+async def test_row_access(write_csv):
+    """Test access by position, name and attribute."""
+    test_file = write_csv("id,amount,note\n1,9.50,first\n2,3\n")
+    try:
+        reader = Reader(test_file, row_type="row")
+        row = await reader.read_row()
+        assert isinstance(row, Row)
+        assert row[1] == row["amount"] == row.amount == "9.50"
+        assert row[-1] == "first"
+        assert row[:2] == ("1", "9.50")
+        assert len(row) == 3
+        assert list(row) == ["1", "9.50", "first"]
+        assert row.keys() == ["id", "amount", "note"]
+        assert row.asdict() == {"id": "1", "amount": "9.50", "note": "first"}
+        assert repr(row) == "Row(id='1', amount='9.50', note='first')"
+        with pytest.raises(KeyError):
+            row["missing"]
+        with pytest.raises(AttributeError):
+            row.missing
+
+        short = await reader.read_row()
+        assert short.get("note", "-") == "-"
+        assert short == ("2", "3")
+        assert await reader.read_row() == []
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_row_batches_share_header(write_csv):
+    """Test read_rows, read_all and iteration consume the header once."""
+    test_file = write_csv("k,v\na,1\nb,2\na,1\n")
+    try:
+        reader = Reader(test_file, row_type="row")
+        first = await reader.read_rows(1)
+        rest = await reader.read_all()
+        assert [r.k for r in first + rest] == ["a", "b", "a"]
+        assert len(set(first + rest)) == 2  # Hashable, equal by fields
+
+        rows = [row async for row in Reader(test_file, row_type="row", raw=True)]
+        assert rows[1]["v"] == b"2"
+        assert (await Reader(test_file, row_type="row").read_all())[0].v == "1"
+    finally:
+        os.unlink(test_file)
+
+
+def test_row_requires_header():
+    """Test that row_type="row" needs a header row."""
+    with pytest.raises(ValueError, match="header"):
+        Reader("data.csv", row_type="row", has_header=False)
@@ -0,0 +1,38 @@
+"""Test row hashes appended by Reader(row_hash=True) and row_hash()."""
+
+import pytest
+
+from rapcsv import Reader, row_hash
+
+
+def test_row_hash_function():
+    """Test that hashes are stable and tell field boundaries apart."""
+    assert row_hash(["a", "b"]) == row_hash(["a", "b"])
+    assert len(row_hash(["a", "b"])) == 16
+    assert row_hash(["ab", "c"]) != row_hash(["a", "bc"])
+    assert row_hash([""]) != row_hash([])
+    # Pinned so hashes stored by earlier releases keep matching
+    assert row_hash([]) == "2d06800538d394c2"
+    with pytest.raises(TypeError):
+        row_hash("abc")
+
+
+@pytest.mark.asyncio
+async def test_reader_row_hash(write_csv):
+    """Test the _hash column on rows, headers and alongside include_filename."""
+    path = write_csv("id,name\r\n1,Alice\r\n2,Bob\r\n1,Alice\r\n")
+    rows = await Reader(path, row_hash=True).read_all()
+    assert rows[0] == ["id", "name", "_hash"]
+    assert rows[1] == ["1", "Alice", row_hash(["1", "Alice"])]
+    assert rows[1][2] == rows[3][2] != rows[2][2]
+
+    reader = Reader(path, row_hash=True, include_filename=True, read_size=4)
+    rows = [row async for row in reader]
+    assert rows[0] == ["id", "name", "_file", "_hash"]
+    assert rows[2] == ["2", "Bob", path, row_hash(["2", "Bob"])]
+
+    rows = await Reader(path, row_hash=True, has_header=False).read_rows(1)
+    assert rows == [["id", "name", row_hash(["id", "name"])]]
+
+    row = await Reader(path, row_hash=True, row_type="row").read_row()
+    assert row["_hash"] == row_hash(["1", "Alice"])
@@ -0,0 +1,32 @@
+"""Test Reader(row_type=...), which selects the Python type of returned rows."""
+
+import os
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_tuple_rows(write_csv):
+    """Test read_row, read_rows, read_all and iteration with row_type="tuple"."""
+    test_file = write_csv("id,name\n1,a\n2,b\n1,a\n")
+    try:
+        reader = Reader(test_file, row_type="tuple")
+        assert await reader.read_row() == ("id", "name")
+        assert await reader.read_rows(1) == [("1", "a")]
+        rows = await reader.read_all()
+        assert rows == [("2", "b"), ("1", "a")]
+        assert len({("1", "a"), *rows}) == 2  # Hashable
+
+        assert [row async for row in Reader(test_file, row_type="tuple")][-1] == ("1", "a")
+        raw = Reader(test_file, row_type="tuple", raw=True)
+        assert await raw.read_row() == (b"id", b"name")
+    finally:
+        os.unlink(test_file)
+
+
+def test_invalid_row_type():
+    """Test that an unknown row_type is rejected."""
+    with pytest.raises(ValueError, match="row_type must be"):
+        Reader("data.csv", row_type="set")
@@ -0,0 +1,65 @@
+"""Test neutralizing spreadsheet formulas with sanitize_formulas=True."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import AsyncDictWriter, Writer
+
+
+def _read(path):
+    with open(path, newline="") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_formula_fields_prefixed():
+    """Test that text starting like a formula gets a leading quote, and numbers don't."""
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    os.close(fd)
+    try:
+        async with Writer(path, sanitize_formulas=True, headers=["=name", "note"]) as writer:
+            await writer.write_row(["=HYPERLINK(\"http://x\")", "+1"])
+            await writer.write_row(["-2+3", "@SUM(A1)"])
+            await writer.write_row(["\tcmd", b"=bytes"])
+            await writer.write_row([-5, -2.5])
+            await writer.write_row(["a=b", "plain"])
+        assert _read(path) == (
+            "=name,note\r\n"
+            "\"'=HYPERLINK(\"\"http://x\"\")\",'+1\r\n"
+            "'-2+3,'@SUM(A1)\r\n"
+            "'\tcmd,'=bytes\r\n"
+            "-5,-2.5\r\n"
+            "a=b,plain\r\n"
+        )
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_off_by_default():
+    """Test that fields are written as given without the option."""
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    os.close(fd)
+    try:
+        async with Writer(path) as writer:
+            await writer.write_row(["=1+1", "@a"])
+        assert _read(path) == "=1+1,@a\r\n"
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_dict_writer():
+    """Test that AsyncDictWriter sanitizes values but not fieldnames."""
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    os.close(fd)
+    try:
+        writer = AsyncDictWriter(path, fieldnames=["-id", "name"], sanitize_formulas=True)
+        await writer.writeheader()
+        await writer.writerow({"-id": 1, "name": "=cmd|' /C calc'!A0"})
+        await writer.close()
+        assert _read(path) == "-id,name\r\n1,'=cmd|' /C calc'!A0\r\n"
+    finally:
+        os.unlink(path)
@@ -0,0 +1,61 @@
+"""Test leaving out trailing records with Reader(skip_footer=...)."""
+
+import pytest
+
+from rapcsv import Reader
+
+
+EXPORT = "date,amount\n2024-01-01,10\n2024-01-02,20\n2024-01-03,5\n\nTotal,35\nExported by Bank\n"
+
+
+@pytest.mark.asyncio
+async def test_skip_footer_every_read_method(write_csv):
+    """Test that the footer is left out by streaming and whole-file reads."""
+    path = write_csv(EXPORT)
+    expected = [["date", "amount"], ["2024-01-01", "10"], ["2024-01-02", "20"], ["2024-01-03", "5"]]
+    assert await Reader(path, skip_footer=2).read_all() == expected
+    assert await Reader(path, skip_footer=2, mmap=True).read_all() == expected
+    assert [row async for row in Reader(path, skip_footer=2, read_size=8)] == expected
+
+    reader = Reader(path, skip_footer=2)
+    assert await reader.read_rows(3) == expected[:3]
+    assert await reader.read_row() == expected[3]
+    assert await reader.read_row() == []  # EOF
+    assert await reader.read_rows(5) == []
+
+    # A footer longer than the file leaves nothing
+    assert await Reader(path, skip_footer=10).read_all() == []
+    assert await Reader(path, skip_footer=0).read_all() == expected + [["Total", "35"], ["Exported by Bank"]]
+
+
+@pytest.mark.asyncio
+async def test_skip_footer_per_file(write_csv):
+    """Test that each file of a multi-file reader loses its own footer."""
+    first = write_csv(EXPORT)
+    second = write_csv("date,amount\n2024-02-01,7\nTotal,7\nExported by Bank\n")
+    expected = [
+        ["date", "amount"],
+        ["2024-01-01", "10"],
+        ["2024-01-02", "20"],
+        ["2024-01-03", "5"],
+        ["2024-02-01", "7"],
+    ]
+    for concurrency in (1, 2):
+        reader = Reader([first, second], skip_footer=2, concurrency=concurrency)
+        assert await reader.read_all() == expected
+    reader = Reader([first, second], skip_footer=2, prefetch=2)
+    assert [row async for row in reader] == expected
+
+
+@pytest.mark.asyncio
+async def test_skip_footer_validation(write_csv):
+    """Test the options skip_footer cannot be combined with."""
+    path = write_csv(EXPORT)
+    with pytest.raises(ValueError, match="follow or checkpoint_path"):
+        Reader(path, skip_footer=1, follow=True)
+    with pytest.raises(ValueError, match="follow or checkpoint_path"):
+        Reader(path, skip_footer=1, checkpoint_path=path + ".ckpt")
+    reader = Reader(path, skip_footer=1)
+    await reader.read_row()
+    with pytest.raises(ValueError, match="skip_footer"):
+        reader.state()
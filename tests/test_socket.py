@@ -0,0 +1,141 @@
+"""Test Reader.from_socket() and Writer.to_socket()."""
+
+import asyncio
+import socket
+import threading
+import time
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+
+def _serve(handler):
+    """Accept one connection on a local port and run ``handler`` on it in a thread."""
+    server = socket.create_server(("127.0.0.1", 0))
+    port = server.getsockname()[1]
+
+    def run():
+        conn, _ = server.accept()
+        with conn:
+            handler(conn)
+        server.close()
+
+    thread = threading.Thread(target=run)
+    thread.start()
+    return port, thread
+
+
+@pytest.mark.asyncio
+async def test_reader_from_socket():
+    """Test parsing records sent in pieces by a server until it closes the connection."""
+
+    def send(conn):
+        for piece in (b"id,name\r\n1,Al", b"ice\r\n2,\"B\r\nob\"\r\n", b"3,Carol\r\n"):
+            conn.sendall(piece)
+
+    port, thread = _serve(send)
+    reader = Reader.from_socket("127.0.0.1", port, connect_timeout=5, row_type="tuple")
+    rows = [row async for row in reader]
+    thread.join()
+    assert rows == [("id", "name"), ("1", "Alice"), ("2", "B\r\nob"), ("3", "Carol")]
+
+
+@pytest.mark.asyncio
+async def test_writer_to_socket():
+    """Test that rows written reach the server and close() ends the connection."""
+    received = []
+
+    def collect(conn):
+        while chunk := conn.recv(4096):
+            received.append(chunk)
+
+    port, thread = _serve(collect)
+    async with Writer.to_socket("localhost", port, lineterminator="\n") as writer:
+        await writer.writerows([["id", "name"], ["1", "Alice"]])
+    thread.join(5)
+    assert b"".join(received) == b"id,name\n1,Alice\n"
+
+
+@pytest.mark.asyncio
+async def test_socket_errors():
+    """Test refused connections, raised by the first read or write, and options that
+    need a file path."""
+    server = socket.create_server(("127.0.0.1", 0))
+    port = server.getsockname()[1]
+    server.close()
+    reader = Reader.from_socket("127.0.0.1", port)
+    for _ in range(2):
+        with pytest.raises(ConnectionError, match=f"Failed to connect to 127.0.0.1:{port}"):
+            await reader.read_row()
+    writer = Writer.to_socket("127.0.0.1", port)
+    with pytest.raises(ConnectionError, match=f"Failed to connect to 127.0.0.1:{port}"):
+        await writer.write_row(["a"])
+    with pytest.raises(ValueError, match="cannot be used with to_socket()"):
+        Writer.to_socket("127.0.0.1", port, checksum="sha256")
+
+
+@pytest.mark.asyncio
+async def test_retry_reconnects():
+    """Test that a retried read connects again, reaching a server that starts listening
+    after the first attempt was refused."""
+    server = socket.create_server(("127.0.0.1", 0))
+    port = server.getsockname()[1]
+    server.close()
+    threads = []
+
+    def listen(attempt, error, delay):
+        assert isinstance(error, ConnectionError)
+        if not threads:
+            server = socket.create_server(("127.0.0.1", port))
+
+            def run():
+                conn, _ = server.accept()
+                with conn:
+                    conn.sendall(b"a,b\n1,2\n")
+                server.close()
+
+            threads.append(threading.Thread(target=run))
+            threads[0].start()
+
+    reader = Reader.from_socket(
+        "127.0.0.1", port, retries=2, retry_backoff=0.01, on_retry=listen
+    )
+    rows = [row async for row in reader]
+    threads[0].join()
+    assert rows == [["a", "b"], ["1", "2"]]
+
+
+@pytest.mark.asyncio
+async def test_connect_does_not_block():
+    """Test that constructing leaves the connection to the first read, which waits
+    on it without blocking the event loop."""
+    # Nothing answers at this documentation-only address
+    started = time.monotonic()
+    reader = Reader.from_socket("192.0.2.1", 9, connect_timeout=0.3)
+    assert time.monotonic() - started < 0.1
+    ticks = 0
+
+    async def tick():
+        nonlocal ticks
+        while True:
+            ticks += 1
+            await asyncio.sleep(0.01)
+
+    ticker = asyncio.ensure_future(tick())
+    with pytest.raises(ConnectionError, match="Failed to connect to 192.0.2.1:9"):
+        await reader.read_row()
+    ticker.cancel()
+    assert ticks > 0
+    assert time.monotonic() - started < 5
+
+
+def test_sync_reader_from_socket():
+    """Test the blocking reader over a TCP connection."""
+    from rapcsv import sync
+
+    port, thread = _serve(lambda conn: conn.sendall(b"a,b\n1,2\n"))
+    with sync.Reader.from_socket("127.0.0.1", port) as reader:
+        rows = list(reader)
+    thread.join()
+    assert rows == [["a", "b"], ["1", "2"]]
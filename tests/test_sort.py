@@ -0,0 +1,128 @@
+"""Test external-memory sorting with sort()."""
+
+import os
+import random
+import tempfile
+
+import pytest
+
+from rapcsv import sort
+
+
+def _read_rows(path):
+    with open(path, newline="") as f:
+        return [line.split(",") for line in f.read().split("\r\n") if line]
+
+
+@pytest.mark.asyncio
+async def test_sort_multiple_columns(write_csv):
+    """Test ascending/descending keys with typed comparison."""
+    src = write_csv("date,amount,id\n2024-02,5,a\n2024-01,10,b\n2024-02,30,c\n2024-01,9,d\n")
+    dst = src + ".sorted.csv"
+
+    try:
+        count = await sort(src, dst, by=["date", "-amount"])
+        assert count == 4
+        assert _read_rows(dst) == [
+            ["date", "amount", "id"],
+            ["2024-01", "10", "b"],
+            ["2024-01", "9", "d"],
+            ["2024-02", "30", "c"],
+            ["2024-02", "5", "a"],
+        ]
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_sort_untyped_is_lexicographic(write_csv):
+    """Test that typed=False compares fields as strings."""
+    src = write_csv("n\n10\n9\n100\n")
+    dst = src + ".sorted.csv"
+
+    try:
+        await sort(src, dst, by="n", typed=False)
+        assert _read_rows(dst) == [["n"], ["10"], ["100"], ["9"]]
+        await sort(src, dst, by="n")
+        assert _read_rows(dst) == [["n"], ["9"], ["10"], ["100"]]
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_sort_spills_to_disk(write_csv):
+    """Test that small chunks force a multi-run merge with a stable result."""
+    rng = random.Random(42)
+    rows = [(rng.randint(0, 50), i) for i in range(1000)]
+    src = write_csv("key,seq\n" + "".join(f"{k},{i}\n" for k, i in rows))
+    dst = src + ".sorted.csv"
+
+    try:
+        count = await sort(src, dst, by="key", chunk_rows=37)
+        assert count == 1000
+        expected = [[str(k), str(i)] for k, i in sorted(rows, key=lambda r: r[0])]
+        assert _read_rows(dst)[1:] == expected
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_sort_merges_many_runs_in_passes(write_csv):
+    """Test that more runs than are merged at once are merged in stable passes."""
+    rng = random.Random(7)
+    rows = [(rng.randint(0, 20), i) for i in range(1000)]
+    src = write_csv("key,seq\n" + "".join(f"{k},{i}\n" for k, i in rows))
+    dst = src + ".sorted.csv"
+
+    try:
+        with tempfile.TemporaryDirectory() as spill:
+            # 334 runs: a pass merges them into 6, then the final merge
+            assert await sort(src, dst, by="key", chunk_rows=3, temp_dir=spill) == 1000
+            assert os.listdir(spill) == []
+        expected = [[str(k), str(i)] for k, i in sorted(rows, key=lambda r: r[0])]
+        assert _read_rows(dst)[1:] == expected
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_sort_ragged_rows(write_csv):
+    """Test that rows with too few or many fields are sorted in memory and through spills."""
+    rows = [[str((i * 37) % 200)] + ["x"] * (i % 3) for i in range(200)]
+    src = write_csv("key,a\n" + "".join(",".join(row) + "\n" for row in rows))
+    dst = src + ".sorted.csv"
+    expected = [["key", "a"]] + sorted(rows, key=lambda row: int(row[0]))
+
+    try:
+        assert await sort(src, dst, by="key") == 200
+        assert _read_rows(dst) == expected
+        # Runs of a row or two, more than are merged at once
+        assert await sort(src, dst, by="key", max_memory=64) == 200
+        assert _read_rows(dst) == expected
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_sort_unknown_column(write_csv):
+    """Test that an unknown sort column raises ValueError."""
+    src = write_csv("a,b\n1,2\n")
+    dst = src + ".sorted.csv"
+
+    try:
+        with pytest.raises(ValueError, match="missing"):
+            await sort(src, dst, by=["-missing"])
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
@@ -0,0 +1,148 @@
+"""Test loading CSV files into SQLite with to_sqlite()."""
+
+import os
+import sqlite3
+import tempfile
+
+import pytest
+
+from rapcsv import CSVError, to_sqlite
+
+
+@pytest.mark.asyncio
+async def test_to_sqlite_basic():
+    """Test creating a table from the header and inserting all rows."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        f.write("name,age,city\n")
+        f.write("Alice,30,NYC\n")
+        f.write('"Smith, Bob",25,LA\n')
+    db_file = test_file + ".db"
+
+    try:
+        count = await to_sqlite(test_file, db_file, "people")
+        assert count == 2
+
+        conn = sqlite3.connect(db_file)
+        try:
+            rows = conn.execute("SELECT name, age, city FROM people").fetchall()
+        finally:
+            conn.close()
+        assert rows == [("Alice", "30", "NYC"), ("Smith, Bob", "25", "LA")]
+    finally:
+        os.unlink(test_file)
+        if os.path.exists(db_file):
+            os.unlink(db_file)
+
+
+@pytest.mark.asyncio
+async def test_to_sqlite_schema_and_batches():
+    """Test typed columns, NULL for empty typed fields, and small batches."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        f.write("id;amount;note\n")
+        for i in range(25):
+            amount = "" if i == 3 else f"{i}.5"
+            f.write(f"{i};{amount};row {i}\n")
+    db_file = test_file + ".db"
+
+    try:
+        count = await to_sqlite(
+            test_file,
+            db_file,
+            "sales",
+            schema={"id": "INTEGER", "amount": "REAL"},
+            batch_size=10,
+            delimiter=";",
+        )
+        assert count == 25
+
+        conn = sqlite3.connect(db_file)
+        try:
+            total = conn.execute("SELECT count(*), sum(id) FROM sales").fetchone()
+            missing = conn.execute("SELECT id FROM sales WHERE amount IS NULL").fetchall()
+            first = conn.execute("SELECT id, amount FROM sales WHERE id = 1").fetchone()
+        finally:
+            conn.close()
+        assert total == (25, sum(range(25)))
+        assert missing == [(3,)]
+        assert first == (1, 1.5)
+    finally:
+        os.unlink(test_file)
+        if os.path.exists(db_file):
+            os.unlink(db_file)
+
+
+@pytest.mark.asyncio
+async def test_to_sqlite_unknown_schema_column():
+    """Test that schema columns missing from the header raise ValueError."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        f.write("a,b\n1,2\n")
+    db_file = test_file + ".db"
+
+    try:
+        with pytest.raises(ValueError, match="missing"):
+            await to_sqlite(test_file, db_file, "t", schema={"missing": "INTEGER"})
+    finally:
+        os.unlink(test_file)
+        if os.path.exists(db_file):
+            os.unlink(db_file)
+
+
+@pytest.mark.asyncio
+async def test_to_sqlite_empty_file():
+    """Test that a file without a header raises CSVError."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+    db_file = test_file + ".db"
+
+    try:
+        with pytest.raises(CSVError):
+            await to_sqlite(test_file, db_file, "t")
+    finally:
+        os.unlink(test_file)
+        if os.path.exists(db_file):
+            os.unlink(db_file)
+
+
+@pytest.mark.asyncio
+async def test_to_sqlite_schema_types_and_existing_table():
+    """Test schema type checks and loading into a table with other columns."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        test_file = f.name
+        f.write("id,name\n1,\n")
+    db_file = test_file + ".db"
+
+    try:
+        bad_types = ("TEXT); DROP TABLE t; --", "INTEGER DEFAULT 1", "INT NOT NULL", "", "CHAR(x)")
+        for bad in bad_types:
+            with pytest.raises(ValueError, match="Invalid SQL type"):
+                await to_sqlite(test_file, db_file, "t", schema={"id": bad})
+
+        # VARCHAR has TEXT affinity, so the empty name stays a string
+        schema = {"id": "UNSIGNED BIG INT", "name": "VARCHAR(20)"}
+        await to_sqlite(test_file, db_file, "t", schema=schema)
+
+        conn = sqlite3.connect(db_file)
+        try:
+            conn.execute("CREATE TABLE other (name TEXT, extra TEXT, id INTEGER)")
+            conn.execute("CREATE TABLE narrow (id INTEGER)")
+            conn.commit()
+        finally:
+            conn.close()
+        await to_sqlite(test_file, db_file, "other")
+        with pytest.raises(ValueError, match="has no column name"):
+            await to_sqlite(test_file, db_file, "narrow")
+
+        conn = sqlite3.connect(db_file)
+        try:
+            assert conn.execute("SELECT id, name FROM t").fetchall() == [(1, "")]
+            assert conn.execute("SELECT * FROM other").fetchall() == [("", None, 1)]
+            assert conn.execute("SELECT count(*) FROM narrow").fetchone() == (0,)
+        finally:
+            conn.close()
+    finally:
+        os.unlink(test_file)
+        if os.path.exists(db_file):
+            os.unlink(db_file)
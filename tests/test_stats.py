@@ -0,0 +1,64 @@
+"""Test Reader.stats() and Writer.stats()."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import CSVError, Reader, Writer
+
+
+@pytest.mark.asyncio
+async def test_reader_stats(write_csv):
+    """Test that reader stats count rows, bytes and time."""
+    content = "a,b\n1,2\n3,4\n"
+    test_file = write_csv(content)
+    try:
+        reader = Reader(test_file)
+        stats = reader.stats()
+        assert stats["rows"] == 0 and stats["bytes"] == 0 and stats["active"] == 0
+
+        assert await reader.read_row() == ["a", "b"]
+        assert len(await reader.read_rows(10)) == 2
+        stats = reader.stats()
+        assert stats["rows"] == 3
+        assert stats["bytes"] == len(content)
+        assert stats["errors"] == 0 and stats["parse_errors"] == 0
+        assert 0 < stats["active"] <= stats["elapsed"]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_reader_stats_count_parse_errors(write_csv):
+    """Test that failed reads are counted, with parse errors counted separately."""
+    test_file = write_csv("a,b\n1,2,3\n")
+    try:
+        reader = Reader(test_file, strict=True)
+        with pytest.raises(CSVError):
+            await reader.read_all()
+        stats = reader.stats()
+        assert stats["errors"] == 1
+        assert stats["parse_errors"] == 1
+        assert stats["rows"] == 0
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_writer_stats():
+    """Test that writer stats count rows and encoded bytes."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        test_file = f.name
+    try:
+        writer = Writer(test_file)
+        await writer.write_row(["a", "b"])
+        await writer.writerows([["1", "2"], ["3", "4"]])
+        await writer.close()
+        stats = writer.stats()
+        assert stats["rows"] == 3
+        assert stats["bytes"] == os.path.getsize(test_file) == 15
+        assert stats["errors"] == 0
+        assert stats["active"] > 0
+    finally:
+        os.unlink(test_file)
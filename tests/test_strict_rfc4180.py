@@ -0,0 +1,62 @@
+"""Test rejecting deviations from RFC 4180 with strict_rfc4180=True."""
+
+import os
+
+import pytest
+
+from rapcsv import CSVError, Reader
+
+
+@pytest.mark.asyncio
+async def test_conforming_file(write_csv):
+    """Test that quoted commas, quotes and line breaks and a last record without CRLF pass."""
+    path = write_csv('id,note\r\n1,"a, ""b""\r\nc"\r\n2,\r\n3,plain')
+    try:
+        rows = await Reader(path, strict_rfc4180=True).read_all()
+        assert rows == [["id", "note"], ["1", 'a, "b"\r\nc'], ["2", ""], ["3", "plain"]]
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_deviations(write_csv):
+    """Test that each deviation raises CSVError naming it, with its row and field."""
+    cases = [
+        ("a,b\r\n1,2\n3,4\r\n", "bare LF", 1, 1),
+        ("a,b\r\n1,x\ry\r\n", "bare CR", 1, 1),
+        ('a,b\r\n1,x"y"\r\n', "quote inside an unquoted field", 1, 1),
+        ('a,b\r\n"1"x,2\r\n', "text after the closing quote", 1, 0),
+        ("a,b\r\n\r\n1,2\r\n", "blank line", 1, 0),
+        ("a,b\r\n1,2,3\r\n", "3 fields, the first record has 2", 1, 2),
+        ('a,b\r\n1,"open\r\n', "never closed", 1, 1),
+    ]
+    for content, message, row, column in cases:
+        path = write_csv(content)
+        try:
+            reader = Reader(path, strict_rfc4180=True)
+            assert await reader.read_row() == ["a", "b"]
+            with pytest.raises(CSVError, match=message) as info:
+                await reader.read_row()
+            assert (info.value.row, info.value.column) == (row, column), content
+            # The same files read without strict_rfc4180
+            assert await Reader(path).read_all()
+        finally:
+            os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_read_all_checks_every_record(write_csv):
+    """Test that read_all() doesn't skip the checks."""
+    path = write_csv("a,b\n1,2\n")
+    try:
+        with pytest.raises(CSVError, match="RFC 4180"):
+            await Reader(path, strict_rfc4180=True).read_all()
+    finally:
+        os.unlink(path)
+
+
+def test_other_dialects_rejected():
+    """Test that dialects other than RFC 4180's are rejected."""
+    for options in ({"delimiter": ";"}, {"lineterminator": "\n"}, {"escapechar": "\\"}):
+        with pytest.raises(ValueError, match="strict_rfc4180"):
+            Reader("data.csv", strict_rfc4180=True, **options)
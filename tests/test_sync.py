@@ -0,0 +1,107 @@
+"""Test the blocking rapcsv.sync.Reader and rapcsv.sync.Writer."""
+
+import os
+import tempfile
+
+import pytest
+
+import rapcsv
+from rapcsv.sync import Reader, Writer
+
+
+def _temp_path():
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        return f.name
+
+
+def test_write_then_read():
+    """Test a blocking write and read round trip without an event loop."""
+    test_file = _temp_path()
+    try:
+        with Writer(test_file, lineterminator="\n") as writer:
+            writer.write_row(["name", "age"])
+            writer.writerows([["Alice", "30"], ["Bob", "25"]])
+            assert writer.stats()["rows"] == 3
+        with open(test_file) as f:
+            assert f.read() == "name,age\nAlice,30\nBob,25\n"
+
+        with Reader(test_file) as reader:
+            assert reader.read_row() == ["name", "age"]
+            assert reader.line_num == 1
+            assert reader.read_rows(1) == [["Alice", "30"]]
+            assert reader.read_all() == [["Bob", "25"]]
+            assert reader.read_row() == []
+    finally:
+        os.unlink(test_file)
+
+
+def test_iteration_and_options():
+    """Test that iteration stops at EOF and options are passed through."""
+    test_file = _temp_path()
+    try:
+        with open(test_file, "w", newline="") as f:
+            f.write("a;b\n1;2\n3;4\n")
+        reader = Reader(test_file, delimiter=";")
+        reader.skip_rows(1)
+        assert list(reader) == [["1", "2"], ["3", "4"]]
+        reader.close()
+    finally:
+        os.unlink(test_file)
+
+
+def test_methods_match_the_async_api():
+    """Test the blocking counterparts of the column, raw, seek, split and open methods."""
+    test_file = _temp_path()
+    copy = _temp_path()
+    try:
+        with Writer(test_file, lineterminator="\n") as writer:
+            writer.write_row(["id", "name"])
+            writer.write_columns([["1", "2", "3"], ["a", "b", "c"]])
+        with Reader(test_file) as reader:
+            reader.open()
+            assert reader.dialect()["lineterminator"] == "\n"
+            assert reader.read_columns(2) == {"id": ["1", "2"], "name": ["a", "b"]}
+            reader.seek(0)
+            lines = reader.read_raw_lines(10)
+        assert lines == [b"id,name\n", b"1,a\n", b"2,b\n", b"3,c\n"]
+
+        with Writer(copy) as writer:
+            writer.write_raw(b"".join(lines))
+        with open(copy, "rb") as f:
+            assert f.read() == b"".join(lines)
+
+        parts = Reader(test_file).split(2)
+        assert all(isinstance(part, Reader) for part in parts)
+        assert [row for part in parts for row in part] == Reader(test_file).read_all()
+    finally:
+        os.unlink(test_file)
+        os.unlink(copy)
+
+
+def test_errors_are_raised():
+    """Test that errors from the async core are raised unchanged."""
+    test_file = _temp_path()
+    try:
+        with open(test_file, "w", newline="") as f:
+            f.write("a,b\n1,2,3\n")
+        with pytest.raises(rapcsv.CSVError):
+            Reader(test_file, strict=True).read_all()
+    finally:
+        os.unlink(test_file)
+
+    with pytest.raises(IOError):
+        Reader("/nonexistent/file.csv").read_row()
+    with pytest.raises(TypeError):
+        Writer(object())
+
+
+@pytest.mark.asyncio
+async def test_usable_inside_running_loop():
+    """Test that blocking calls work from code that already runs an event loop."""
+    test_file = _temp_path()
+    try:
+        with Writer(test_file) as writer:
+            writer.write_row(["x"])
+        assert rapcsv.sync.Reader(test_file).read_all() == [["x"]]
+    finally:
+        os.unlink(test_file)
@@ -0,0 +1,73 @@
+"""Test reading CSV members of tar archives and listing archive contents."""
+
+import io
+import os
+import tarfile
+import tempfile
+import zipfile
+
+import pytest
+
+from rapcsv import Reader, Writer, list_archive
+
+CSV = b"id,city\r\n1,Z\xc3\xbcrich\r\n2,Oslo\r\n"
+
+
+def _write_tar(members, suffix, mode):
+    with tempfile.NamedTemporaryFile(delete=False, suffix=suffix) as f:
+        path = f.name
+    with tarfile.open(path, mode) as archive:
+        for name, content in members.items():
+            info = tarfile.TarInfo(name)
+            info.size = len(content)
+            archive.addfile(info, io.BytesIO(content))
+        directory = tarfile.TarInfo("./empty")
+        directory.type = tarfile.DIRTYPE
+        archive.addfile(directory)
+    return path
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("suffix,mode", [(".tar", "w"), (".tar.gz", "w:gz"), (".tgz", "w:gz")])
+async def test_read_tar_member(suffix, mode):
+    """Test naming the member in the path or with zip_member, with and without ./."""
+    expected = [["id", "city"], ["1", "Zürich"], ["2", "Oslo"]]
+    path = _write_tar({"./data/people.csv": CSV, "big.csv": CSV * 500}, suffix, mode)
+    try:
+        reader = Reader(f"{path}::data/people.csv", read_size=5)
+        assert [row async for row in reader] == expected
+
+        assert await Reader(path, zip_member="./data/people.csv").read_all() == expected
+        rows = await Reader(path, zip_member="big.csv").read_all()
+        assert len(rows) == 1500 and rows[-1] == ["2", "Oslo"]
+
+        assert await list_archive(path) == ["data/people.csv", "big.csv"]
+
+        with pytest.raises(IOError, match="No member 'missing.csv'"):
+            await Reader(path, zip_member="missing.csv").read_row()
+        with pytest.raises(ValueError, match="name the member"):
+            Reader(path)
+        with pytest.raises(ValueError, match="only ZIP archives"):
+            Writer(f"{path}::out.csv")
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_list_archive():
+    """Test listing a ZIP archive and rejecting other files."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".zip") as f:
+        path = f.name
+    try:
+        with zipfile.ZipFile(path, "w") as archive:
+            archive.writestr("b.csv", "x\r\n")
+            archive.writestr("dir/", "")
+            archive.writestr("dir/a.csv", "y\r\n")
+        assert await list_archive(path) == ["b.csv", "dir/a.csv"]
+
+        with pytest.raises(ValueError, match="is not a .zip, .tar, .tar.gz or .tgz archive"):
+            await list_archive("data.csv")
+        with pytest.raises(IOError):
+            await list_archive("/nonexistent/archive.tar")
+    finally:
+        os.unlink(path)
@@ -0,0 +1,96 @@
+"""Test temp_dir= for the spilling operations and clean-up of their spill files."""
+
+import asyncio
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import dedupe, diff, sort
+
+
+def _read_rows(path):
+    with open(path, newline="") as f:
+        return [line.split(",") for line in f.read().split("\r\n") if line]
+
+
+@pytest.mark.asyncio
+async def test_sort_spills_under_temp_dir(write_csv):
+    """Test that runs go to temp_dir and are gone once the sort returns."""
+    src = write_csv("n\n" + "".join(f"{n}\n" for n in (5, 3, 9, 1, 7, 2, 8)))
+    dst = src + ".sorted.csv"
+
+    try:
+        with tempfile.TemporaryDirectory() as spill:
+            count = await sort(src, dst, by="n", chunk_rows=2, temp_dir=spill)
+            assert count == 7
+            assert _read_rows(dst) == [["n"]] + [[str(n)] for n in (1, 2, 3, 5, 7, 8, 9)]
+            assert os.listdir(spill) == []
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_dedupe_spills_under_temp_dir(write_csv):
+    """Test that partitions go to temp_dir and are gone once dedupe returns."""
+    src = write_csv("id,v\n1,a\n2,b\n1,c\n3,d\n2,e\n")
+    dst = src + ".unique.csv"
+
+    try:
+        with tempfile.TemporaryDirectory() as spill:
+            removed = await dedupe(src, dst, key="id", max_keys=1, temp_dir=spill)
+            assert removed == 2
+            assert _read_rows(dst) == [["id", "v"], ["1", "a"], ["2", "b"], ["3", "d"]]
+            assert os.listdir(spill) == []
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_temp_dir_must_be_a_directory(write_csv):
+    """Test that a temp_dir that is not a directory is rejected up front."""
+    src = write_csv("id\n1\n")
+    missing = src + ".missing"
+
+    try:
+        with pytest.raises(ValueError, match="not a directory"):
+            await sort(src, src + ".out", by="id", temp_dir=src)
+        with pytest.raises(ValueError, match="not a directory"):
+            await dedupe(src, src + ".out", temp_dir=missing)
+        with pytest.raises(ValueError, match="not a directory"):
+            await diff(src, src, key="id", temp_dir=missing)
+        assert not os.path.exists(src + ".out")
+    finally:
+        os.unlink(src)
+
+
+@pytest.mark.asyncio
+async def test_cancelled_sort_removes_spill_files(write_csv):
+    """Test that cancelling a sort stops it and removes its runs."""
+    src = write_csv("n\n" + "".join(f"{(n * 7919) % 200_000}\n" for n in range(200_000)))
+    dst = src + ".sorted.csv"
+
+    try:
+        with tempfile.TemporaryDirectory() as spill:
+            task = asyncio.ensure_future(sort(src, dst, by="n", chunk_rows=100, temp_dir=spill))
+            for _ in range(200):
+                await asyncio.sleep(0.005)
+                if os.listdir(spill):
+                    break
+            task.cancel()
+            with pytest.raises(asyncio.CancelledError):
+                await task
+            # The worker thread notices the cancellation at its next row
+            for _ in range(200):
+                if not os.listdir(spill):
+                    break
+                await asyncio.sleep(0.01)
+            assert os.listdir(spill) == []
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
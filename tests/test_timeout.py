@@ -0,0 +1,75 @@
+"""Test the timeout= option on Reader and Writer."""
+
+import asyncio
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+
+class _SlowHandle:
+    """File-like object whose writes stall."""
+
+    def __init__(self, delay):
+        self.delay = delay
+        self.data = []
+
+    async def write(self, data):
+        await asyncio.sleep(self.delay)
+        self.data.append(data)
+
+
+@pytest.mark.asyncio
+async def test_reader_timeout_raises_and_resumes(write_csv):
+    """Test that a read waiting past the timeout raises asyncio.TimeoutError."""
+    test_file = write_csv("a,b\n1,2\n")
+    try:
+        reader = Reader(test_file, follow=True, poll_interval=0.01, timeout=0.2)
+        assert await reader.read_rows(2) == [["a", "b"], ["1", "2"]]
+        with pytest.raises(asyncio.TimeoutError):
+            await reader.read_row()
+
+        # The reader is still usable once data arrives
+        with open(test_file, "a", newline="") as f:
+            f.write("3,4\n")
+        assert await reader.read_row() == ["3", "4"]
+        assert reader.stats()["errors"] == 1
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_reader_timeout_not_hit(write_csv):
+    """Test that reads finishing within the timeout are unaffected."""
+    test_file = write_csv("a,b\n1,2\n")
+    try:
+        reader = Reader(test_file, timeout=5)
+        assert await reader.read_all() == [["a", "b"], ["1", "2"]]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_writer_timeout_raises():
+    """Test that a write stalled on its destination raises asyncio.TimeoutError."""
+    handle = _SlowHandle(delay=0.5)
+    writer = Writer(handle, write_size=0, timeout=0.05)
+    with pytest.raises(asyncio.TimeoutError):
+        await writer.write_row(["a", "b"])
+    # Let the stalled write finish before the event loop closes
+    await asyncio.sleep(0.6)
+
+
+@pytest.mark.parametrize("cls", [Reader, Writer])
+def test_invalid_timeout(cls):
+    """Test that a non-positive timeout is rejected."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        test_file = f.name
+    try:
+        for timeout in (0, -1, float("nan")):
+            with pytest.raises(ValueError, match="timeout"):
+                cls(test_file, timeout=timeout)
+    finally:
+        os.unlink(test_file)
@@ -0,0 +1,102 @@
+"""Test streaming transforms with transform()."""
+
+import os
+
+import pytest
+
+from rapcsv import transform
+
+
+def _read(path):
+    with open(path) as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_transform_per_row(write_csv):
+    """Test mapping and dropping rows one at a time."""
+    src = write_csv("id,name\n1,alice\n2,bob\n3,carol\n")
+    dst = src + ".out"
+    try:
+
+        def upper_odd(row):
+            if int(row[0]) % 2 == 0:
+                return None
+            return [row[0], row[1].upper()]
+
+        written = await transform(src, dst, upper_odd)
+        assert written == 2
+        assert _read(dst) == "id,name\n1,ALICE\n3,CAROL\n"
+    finally:
+        os.unlink(src)
+        if os.path.exists(dst):
+            os.unlink(dst)
+
+
+@pytest.mark.asyncio
+async def test_transform_batched(write_csv):
+    """Test that batch mode passes lists of rows to the callback."""
+    src = write_csv("n\n" + "".join(f"{i}\n" for i in range(10)))
+    dst = src + ".out"
+    batch_lengths = []
+    try:
+
+        def double(rows):
+            batch_lengths.append(len(rows))
+            return [[int(r[0]) * 2] for r in rows]
+
+        written = await transform(src, dst, double, batch_size=4)
+        assert written == 10
+        assert batch_lengths == [4, 4, 2]
+        assert _read(dst).splitlines()[1:4] == ["0", "2", "4"]
+    finally:
+        os.unlink(src)
+        if os.path.exists(dst):
+            os.unlink(dst)
+
+
+@pytest.mark.asyncio
+async def test_transform_header_and_values(write_csv):
+    """Test header=False and conversion of non-string values."""
+    src = write_csv("a,b\nc,d\n")
+    dst = src + ".out"
+    try:
+        written = await transform(src, dst, lambda row: [len(row), None, "x,y"], header=False)
+        assert written == 2
+        assert _read(dst) == '2,,"x,y"\n2,,"x,y"\n'
+    finally:
+        os.unlink(src)
+        if os.path.exists(dst):
+            os.unlink(dst)
+
+
+@pytest.mark.asyncio
+async def test_transform_callback_error(write_csv):
+    """Test that exceptions raised by the callback propagate."""
+    src = write_csv("a\n1\n")
+    dst = src + ".out"
+    try:
+
+        def fail(row):
+            raise RuntimeError("boom")
+
+        with pytest.raises(RuntimeError, match="boom"):
+            await transform(src, dst, fail)
+        with pytest.raises(TypeError):
+            await transform(src, dst, "not callable")
+    finally:
+        os.unlink(src)
+        if os.path.exists(dst):
+            os.unlink(dst)
+
+
+@pytest.mark.asyncio
+async def test_transform_in_place_is_refused(write_csv):
+    """Test that transform(p, p, fn) raises instead of truncating the input."""
+    src = write_csv("id\n1\n")
+    try:
+        with pytest.raises(ValueError, match="is the input file"):
+            await transform(src, src, lambda row: row)
+        assert _read(src) == "id\n1\n"
+    finally:
+        os.unlink(src)
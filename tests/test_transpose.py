@@ -0,0 +1,51 @@
+"""Test transposing CSV files with transpose()."""
+
+import os
+
+import pytest
+
+from rapcsv import transpose
+
+
+def _read(path):
+    with open(path, newline="") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_transpose_matrix(write_csv):
+    """Test swapping rows and columns, padding short rows."""
+    src = write_csv('well,A1,A2,A3\nod600,0.1,"0,2"\ntemp,30,31,32\n')
+    dst = src + ".out.csv"
+    try:
+        assert await transpose(src, dst) == 4
+        assert _read(dst) == 'well,od600,temp\r\nA1,0.1,30\r\nA2,"0,2",31\r\nA3,,32\r\n'
+
+        # Transposing twice gives the padded input back
+        back = dst + ".back.csv"
+        assert await transpose(dst, back) == 3
+        assert _read(back) == 'well,A1,A2,A3\r\nod600,0.1,"0,2",\r\ntemp,30,31,32\r\n'
+        os.unlink(back)
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_transpose_max_cells(write_csv):
+    """Test the cell-count guard and the delimiter option."""
+    src = write_csv("a;b\n1;2\n")
+    dst = src + ".out.csv"
+    try:
+        with pytest.raises(MemoryError, match="max_cells=3"):
+            await transpose(src, dst, max_cells=3, delimiter=";")
+        assert not os.path.exists(dst)
+        assert await transpose(src, dst, max_cells=4, delimiter=";") == 2
+        assert _read(dst) == "a;1\r\nb;2\r\n"
+        with pytest.raises(ValueError, match="at least 1"):
+            await transpose(src, dst, max_cells=0)
+    finally:
+        for path in (src, dst):
+            if os.path.exists(path):
+                os.unlink(path)
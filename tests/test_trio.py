@@ -0,0 +1,105 @@
+"""Test rapcsv under trio and anyio's trio backend."""
+
+import os
+import tempfile
+
+import pytest
+
+try:
+    import trio
+except ImportError:
+    trio = None
+
+try:
+    import anyio
+except ImportError:
+    anyio = None
+
+from rapcsv import CSVError, Reader, Writer
+
+requires_trio = pytest.mark.skipif(trio is None, reason="trio not installed")
+
+
+def _temp_path():
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        return f.name
+
+
+async def _round_trip(test_file):
+    async with Writer(test_file) as writer:
+        await writer.write_row(["name", "age"])
+        await writer.writerows([["Alice", "30"], ["Bob", "25"]])
+    async with Reader(test_file) as reader:
+        header = await reader.read_row()
+        rows = await reader.read_all()
+    return header, rows
+
+
+@requires_trio
+def test_round_trip_under_trio():
+    """Test writing and reading a file inside trio.run()."""
+    test_file = _temp_path()
+    try:
+        header, rows = trio.run(_round_trip, test_file)
+        assert header == ["name", "age"]
+        assert rows == [["Alice", "30"], ["Bob", "25"]]
+    finally:
+        os.unlink(test_file)
+
+
+@requires_trio
+def test_errors_under_trio():
+    """Test that errors are raised in the awaiting trio task."""
+    test_file = _temp_path()
+    try:
+        with open(test_file, "w", newline="") as f:
+            f.write("a,b\n1,2,3\n")
+
+        async def main():
+            with pytest.raises(CSVError):
+                await Reader(test_file, strict=True).read_all()
+
+        trio.run(main)
+    finally:
+        os.unlink(test_file)
+
+
+@requires_trio
+def test_cancellation_under_trio():
+    """Test that a trio cancel scope cancels a pending read."""
+    test_file = _temp_path()
+    try:
+        with open(test_file, "w", newline="") as f:
+            f.write("a,b\n")
+
+        async def main():
+            reader = Reader(test_file, follow=True, poll_interval=0.01)
+            assert await reader.read_row() == ["a", "b"]
+            with trio.move_on_after(0.1) as scope:
+                await reader.read_row()  # Waits for rows that never come
+            assert scope.cancelled_caught
+
+        trio.run(main)
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.skipif(anyio is None or trio is None, reason="anyio or trio not installed")
+def test_round_trip_under_anyio_trio_backend():
+    """Test writing and reading a file under anyio's trio backend."""
+    test_file = _temp_path()
+    try:
+        header, rows = anyio.run(_round_trip, test_file, backend="trio")
+        assert rows == [["Alice", "30"], ["Bob", "25"]]
+    finally:
+        os.unlink(test_file)
+
+
+def test_no_event_loop_raises():
+    """Test that calling outside any event loop explains how to use the blocking API."""
+    test_file = _temp_path()
+    try:
+        with pytest.raises(RuntimeError, match="rapcsv.sync"):
+            Reader(test_file).read_row()
+    finally:
+        os.unlink(test_file)
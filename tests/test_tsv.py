@@ -0,0 +1,52 @@
+"""Test TsvReader and TsvWriter, and reading with quoting=0 (QUOTE_NONE)."""
+
+import pickle
+
+import pytest
+
+from rapcsv import Reader, TsvReader, TsvWriter, Writer
+
+
+def _read(path):
+    with open(path, newline="") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_reader_takes_quotes_literally(write_csv):
+    """Test that tabs split fields and quotes are kept, even unbalanced ones."""
+    path = write_csv('id\tname\tnote\n1\t"Alice"\t5" tall\n2\tBob,Jr\t\n', suffix=".tsv")
+    async with TsvReader(path) as reader:
+        assert isinstance(reader, Reader)
+        assert await reader.read_all() == [
+            ["id", "name", "note"],
+            ["1", '"Alice"', '5" tall'],
+            ["2", "Bob,Jr", ""],
+        ]
+    async with Reader(path, delimiter="\t", quoting=0, row_type="row") as reader:
+        assert [row["name"] for row in await reader.read_all()] == ['"Alice"', "Bob,Jr"]
+
+
+@pytest.mark.asyncio
+async def test_writer_round_trip(write_csv):
+    """Test that TsvWriter writes tabs, \\n and quotes as they are."""
+    path = write_csv("", suffix=".tsv")
+    async with TsvWriter(path, headers=["id", "label"]) as writer:
+        assert isinstance(writer, Writer)
+        await writer.writerows([[1, 'say "hi"'], [2, "a,b"]])
+        with pytest.raises(ValueError, match="no escapechar"):
+            await writer.write_row([3, "tab\there"])
+    assert _read(path) == 'id\tlabel\n1\tsay "hi"\n2\ta,b\n'
+    async with TsvReader(path) as reader:
+        assert (await reader.read_all())[1] == ["1", 'say "hi"']
+
+
+@pytest.mark.asyncio
+async def test_options_override_defaults(write_csv):
+    """Test that explicit options win, and that the classes pickle as themselves."""
+    path = write_csv('a|"b|c"\n', suffix=".tsv")
+    async with TsvReader(path, delimiter="|", quoting=1) as reader:
+        assert await reader.read_all() == [["a", "b|c"]]
+    copy = pickle.loads(pickle.dumps(TsvReader(path, delimiter="|")))
+    assert type(copy) is TsvReader
+    assert await copy.read_all() == [["a", '"b', 'c"']]
@@ -0,0 +1,78 @@
+"""Test Reader(schema=...), which converts fields to typed values in Rust."""
+
+import os
+from datetime import date, datetime, timedelta, timezone
+
+import pytest
+
+from rapcsv import CSVError, Reader
+
+
+@pytest.mark.asyncio
+async def test_schema_by_name(write_csv):
+    """Test conversion by column name, which consumes the header."""
+    test_file = write_csv(
+        "id,price,ok,day,note\n"
+        "1,2.5,yes,2024-02-29,a\n"
+        "2,,0,,b\n"
+    )
+    try:
+        schema = {"id": "int", "price": "float", "ok": "bool", "day": "date"}
+        reader = Reader(test_file, schema=schema)
+        assert await reader.read_row() == [1, 2.5, True, date(2024, 2, 29), "a"]
+        assert await reader.read_all() == [[2, None, False, None, "b"]]
+
+        rows = [row async for row in Reader(test_file, schema=schema, row_type="row")]
+        assert rows[0].day == date(2024, 2, 29)
+        assert rows[1]["id"] == 2
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_datetimes_and_offsets(write_csv):
+    """Test ISO 8601 datetimes, with and without offsets, and custom formats."""
+    test_file = write_csv(
+        "2024-01-02T03:04:05Z,01/02/2024\n"
+        "2024-01-02 03:04:05.250+05:30,31/12/2023\n"
+        "2024-01-02T03:04:05,01/01/2024\n"
+    )
+    try:
+        reader = Reader(
+            test_file,
+            has_header=False,
+            schema={0: "datetime", 1: "date"},
+            date_formats=["%d/%m/%Y"],
+            datetime_formats=["%Y-%m-%dT%H:%M:%SZ", "%Y-%m-%d %H:%M:%S%.f%:z", "%Y-%m-%dT%H:%M:%S"],
+        )
+        rows = await reader.read_all()
+        assert rows[0] == [datetime(2024, 1, 2, 3, 4, 5), date(2024, 2, 1)]
+        aware = rows[1][0]
+        assert aware.utcoffset() == timedelta(hours=5, minutes=30)
+        assert aware == datetime(2024, 1, 1, 21, 34, 5, 250000, tzinfo=timezone.utc)
+        assert rows[2][0].tzinfo is None
+
+        default = await Reader(test_file, has_header=False, schema={0: "datetime"}).read_all()
+        assert default[0][0] == datetime(2024, 1, 2, 3, 4, 5, tzinfo=timezone.utc)
+        assert [row[0] for row in default[1:]] == [aware, rows[2][0]]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_conversion_errors(write_csv):
+    """Test that unconvertible fields and bad schemas raise."""
+    test_file = write_csv("id\nabc\n")
+    try:
+        with pytest.raises(CSVError, match="Cannot convert 'abc' in column 'id' to int"):
+            await Reader(test_file, schema={"id": "int"}).read_row()
+        with pytest.raises(ValueError, match="not in the header"):
+            await Reader(test_file, schema={"missing": "int"}).read_row()
+    finally:
+        os.unlink(test_file)
+    with pytest.raises(ValueError, match="Unknown type 'decimal'"):
+        Reader("data.csv", schema={"a": "decimal"})
+    with pytest.raises(ValueError, match="raw=True"):
+        Reader("data.csv", schema={"a": "int"}, raw=True)
+    with pytest.raises(ValueError, match="has_header=True"):
+        Reader("data.csv", schema={"a": "int"}, has_header=False)
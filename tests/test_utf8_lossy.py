@@ -0,0 +1,64 @@
+"""Test Reader(utf8=...) and decoding of multi-byte characters split between reads."""
+
+import pytest
+
+from rapcsv import Reader
+
+
+@pytest.mark.asyncio
+async def test_lossy_replaces_invalid_sequences(write_csv):
+    """Test that invalid bytes become U+FFFD and are counted, in every read mode."""
+    test_file = write_csv(b"name,city\nJos\xe9,M\xfcnchen\nAnn,Oslo\n")
+    reader = Reader(test_file, utf8="lossy", read_size=4)
+    assert reader.utf8_replacements == 0
+    rows = [row async for row in reader]
+    assert rows[1] == ["Jos�", "M�nchen"]
+    assert reader.utf8_replacements == 2
+    assert reader.byte_offset is None
+
+    for kwargs in ({}, {"mmap": True}):
+        reader = Reader(test_file, utf8="lossy", **kwargs)
+        assert (await reader.read_all())[1] == ["Jos�", "M�nchen"]
+        assert reader.utf8_replacements == 2
+
+    reader = Reader([test_file, test_file], utf8="lossy", concurrency=2)
+    assert len(await reader.read_all()) == 5
+    assert reader.utf8_replacements == 4
+
+
+@pytest.mark.asyncio
+async def test_strict_is_default(write_csv):
+    """Test that invalid UTF-8 still raises by default and bad modes are rejected."""
+    test_file = write_csv(b"a,b\n\xff,1\n")
+    with pytest.raises(IOError, match="Invalid UTF-8"):
+        await Reader(test_file).read_all()
+    with pytest.raises(IOError, match="Invalid UTF-8"):
+        await Reader(test_file, read_size=3).read_rows(10)
+    with pytest.raises(ValueError, match="utf8 must be 'strict' or 'lossy'"):
+        Reader(test_file, utf8="ignore")
+    with pytest.raises(ValueError, match="utf8='lossy'"):
+        Reader(test_file, utf8="lossy").state()
+
+
+@pytest.mark.asyncio
+async def test_character_split_across_chunks(write_csv):
+    """Test that a multi-byte character straddling a read_size boundary is kept whole."""
+    content = "id,word\n1,café\n2,日本語\n3,\U0001f600\n".encode()
+    test_file = write_csv(content)
+    expected = [["id", "word"], ["1", "café"], ["2", "日本語"], ["3", "\U0001f600"]]
+    for read_size in (1, 2, 3, 5):
+        reader = Reader(test_file, read_size=read_size)
+        assert await reader.read_rows(10) == expected
+        assert reader.byte_offset == len(content)
+        assert reader.utf8_replacements == 0
+
+
+@pytest.mark.asyncio
+async def test_truncated_character_at_eof(write_csv):
+    """Test that a character cut off by the end of the file is invalid."""
+    test_file = write_csv(b"a\n\xe6\x97")
+    with pytest.raises(IOError, match="Invalid UTF-8"):
+        await Reader(test_file, read_size=2).read_rows(10)
+    reader = Reader(test_file, read_size=2, utf8="lossy")
+    assert await reader.read_rows(10) == [["a"], ["�"]]
+    assert reader.utf8_replacements == 1
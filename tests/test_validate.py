@@ -0,0 +1,121 @@
+"""Test schema validation with validate()."""
+
+import os
+
+import pytest
+
+from rapcsv import validate
+
+
+@pytest.mark.asyncio
+async def test_validate_valid_file(write_csv):
+    """Test that a conforming file produces an empty report."""
+    test_file = write_csv("id,name,score\n1,alice,9.5\n2,bob,7\n")
+    try:
+        report = await validate(
+            test_file,
+            {"id": "integer", "name": {"nullable": False}, "score": "float"},
+        )
+        assert report["valid"] is True
+        assert report["rows_checked"] == 2
+        assert report["truncated"] is False
+        assert report["errors"] == []
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_validate_type_and_null_violations(write_csv):
+    """Test that type and nullability violations report row and column."""
+    test_file = write_csv("id,name,active\n1,alice,true\nx,,maybe\n")
+    try:
+        report = await validate(
+            test_file,
+            {
+                "id": {"type": "integer"},
+                "name": {"nullable": False},
+                "active": {"type": "boolean"},
+            },
+        )
+        assert report["valid"] is False
+        assert [(e["row"], e["column"]) for e in report["errors"]] == [
+            (2, "id"),
+            (2, "name"),
+            (2, "active"),
+        ]
+        assert report["errors"][0]["value"] == "x"
+        assert report["errors"][0]["message"] == "expected integer"
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_validate_pattern_and_ranges(write_csv):
+    """Test regex patterns and numeric and length ranges."""
+    test_file = write_csv(
+        "email,age,code\na@example.com,30,AB\nnot-an-email,200,ABCD\n"
+    )
+    try:
+        report = await validate(
+            test_file,
+            {
+                "email": {"pattern": r"[^@]+@[^@]+"},
+                "age": {"type": "integer", "min": 0, "max": 150},
+                "code": {"max": 3},
+            },
+        )
+        columns = [e["column"] for e in report["errors"]]
+        assert columns == ["email", "age", "code"]
+        assert all(e["row"] == 2 for e in report["errors"])
+        assert "maximum" in report["errors"][1]["message"]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_validate_missing_columns(write_csv):
+    """Test that missing required columns are reported without a row."""
+    test_file = write_csv("id\n1\n")
+    try:
+        report = await validate(
+            test_file,
+            {"id": "integer", "name": {}, "nickname": {"required": False}},
+        )
+        assert report["errors"] == [
+            {
+                "row": None,
+                "column": "name",
+                "value": None,
+                "message": "required column is missing",
+            }
+        ]
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_validate_max_errors(write_csv):
+    """Test that scanning stops once max_errors violations are found."""
+    test_file = write_csv("n\n" + "bad\n" * 50)
+    try:
+        report = await validate(test_file, {"n": "integer"}, max_errors=5)
+        assert len(report["errors"]) == 5
+        assert report["truncated"] is True
+        assert report["rows_checked"] == 6
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_validate_invalid_schema(write_csv):
+    """Test that unknown types, keys and bad patterns raise ValueError."""
+    test_file = write_csv("a\n1\n")
+    try:
+        with pytest.raises(ValueError, match="Unknown type"):
+            await validate(test_file, {"a": "decimal"})
+        with pytest.raises(ValueError, match="Unknown schema key"):
+            await validate(test_file, {"a": {"minimum": 1}})
+        with pytest.raises(ValueError, match="Invalid pattern"):
+            await validate(test_file, {"a": {"pattern": "("}})
+    finally:
+        os.unlink(test_file)
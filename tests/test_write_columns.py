@@ -0,0 +1,55 @@
+"""Test writing column-oriented data with Writer.write_columns()."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+
+def _read(path):
+    with open(path, newline="") as f:
+        return f.read()
+
+
+@pytest.mark.asyncio
+async def test_dict_of_columns():
+    """Test a dict of columns written in its order, after the headers row."""
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    os.close(fd)
+    try:
+        async with Writer(path, headers=["id", "price", "ok"]) as writer:
+            await writer.write_columns({"id": [1, 2], "price": [2.5, 3.0], "ok": [True, None]})
+            await writer.write_columns({"id": (3,), "price": [4.25], "ok": [False]})
+        assert _read(path) == "id,price,ok\r\n1,2.5,True\r\n2,3.0,\r\n3,4.25,False\r\n"
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_round_trip_with_read_columns():
+    """Test that a list of columns read by read_columns() writes back the same file."""
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    os.close(fd)
+    copy = path + ".copy.csv"
+    try:
+        async with Writer(path) as writer:
+            await writer.writerows([["a", "b"], ["1", 'x,"y"'], ["2", "z"]])
+        columns = await Reader(path).read_columns()
+        async with Writer(copy, headers=list(columns)) as writer:
+            await writer.write_columns(list(columns.values()))
+        assert _read(copy) == _read(path)
+    finally:
+        for p in (path, copy):
+            if os.path.exists(p):
+                os.unlink(p)
+
+
+def test_rejected_columns():
+    """Test columns of different lengths and a str given as a column."""
+    writer = Writer("unused.csv")
+    with pytest.raises(ValueError, match="same length: column 1 has 1, column 0 has 2"):
+        writer.write_columns({"a": [1, 2], "b": [3]})
+    with pytest.raises(TypeError, match="not str"):
+        writer.write_columns(["ab", "cd"])
@@ -0,0 +1,58 @@
+"""Test Writer.write_from()."""
+
+import asyncio
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Writer
+
+
+async def _rows(n):
+    for i in range(n):
+        if i % 100 == 0:
+            await asyncio.sleep(0)
+        yield [i, f"name {i}", i % 2 == 0]
+
+
+@pytest.mark.asyncio
+async def test_write_from_async_generator():
+    """Test that every row of an async generator is written, in batches."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        path = f.name
+    try:
+        async with Writer(path, lineterminator="\n", headers=["id", "name", "even"]) as writer:
+            assert await writer.write_from(_rows(2500), batch_size=1000) == 2500
+            await writer.write_row(["end", "", ""])
+            assert writer.stats()["rows"] == 2501
+        with open(path) as f:
+            lines = f.read().splitlines()
+        assert lines[:3] == ["id,name,even", "0,name 0,True", "1,name 1,False"]
+        assert len(lines) == 2502
+        assert lines[-1] == "end,,"
+    finally:
+        os.unlink(path)
+
+
+@pytest.mark.asyncio
+async def test_write_from_empty_and_errors():
+    """Test empty iterables, a failing generator and invalid arguments."""
+    with tempfile.NamedTemporaryFile(mode="w", delete=False, suffix=".csv") as f:
+        path = f.name
+
+    async def failing():
+        yield ["a"]
+        raise KeyError("upstream")
+
+    try:
+        async with Writer(path) as writer:
+            assert await writer.write_from(_rows(0)) == 0
+            with pytest.raises(KeyError, match="upstream"):
+                await writer.write_from(failing(), batch_size=10)
+            with pytest.raises(TypeError, match="async iterable"):
+                writer.write_from([["a"]])
+            with pytest.raises(ValueError, match="batch_size"):
+                writer.write_from(_rows(1), batch_size=0)
+    finally:
+        os.unlink(path)
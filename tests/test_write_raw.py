@@ -0,0 +1,60 @@
+"""Test writing pre-encoded CSV bytes with Writer.write_raw()."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import QuotaExceeded, Reader, Writer
+
+
+def _path():
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        return f.name
+
+
+@pytest.mark.asyncio
+async def test_bytes_written_as_they_are():
+    """Test that raw bytes follow the pending header and mix with encoded rows."""
+    test_file = _path()
+    try:
+        async with Writer(test_file, headers=["id", "note"]) as writer:
+            await writer.write_raw(b'1,"two\r\nlines"\r\n')
+            await writer.write_row([2, "plain"])
+            await writer.write_raw(b"3,x\r\n4,y\r\n")
+            assert writer.stats()["rows"] == 4
+        with open(test_file, "rb") as f:
+            assert f.read() == b'id,note\r\n1,"two\r\nlines"\r\n2,plain\r\n3,x\r\n4,y\r\n'
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_copy_from_raw_lines():
+    """Test copying records between files without parsing them."""
+    source, target = _path(), _path()
+    try:
+        with open(source, "w", newline="") as f:
+            f.write('a,b\n1,"x\ny"\n2,z\n')
+        reader = Reader(source)
+        async with Writer(target, lineterminator="\n") as writer:
+            while records := await reader.read_raw_lines(2):
+                await writer.write_raw(b"".join(records))
+        assert await Reader(target).read_all() == [["a", "b"], ["1", "x\ny"], ["2", "z"]]
+    finally:
+        os.unlink(source)
+        os.unlink(target)
+
+
+@pytest.mark.asyncio
+async def test_quota_counts_records():
+    """Test that max_rows counts the records held by raw bytes."""
+    test_file = _path()
+    try:
+        async with Writer(test_file, max_rows=2) as writer:
+            await writer.write_raw(b'1,"a\r\nb"\r\n')
+            with pytest.raises(QuotaExceeded):
+                await writer.write_raw(b"2\r\n3")
+        assert await Reader(test_file).read_all() == [["1", "a\r\nb"]]
+    finally:
+        os.unlink(test_file)
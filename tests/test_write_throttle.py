@@ -0,0 +1,41 @@
+"""Test Writer(max_bytes_per_sec=...) write throttling."""
+
+import os
+import tempfile
+import time
+
+import pytest
+
+from rapcsv import Writer
+
+
+@pytest.mark.asyncio
+async def test_writes_are_paced():
+    """Test that writing 6 KB at 10 KB/s takes about half a second and loses nothing."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        test_file = f.name
+    try:
+        rows = [["x" * 98] for _ in range(60)]  # 100 bytes each with CRLF
+        started = time.monotonic()
+        async with Writer(test_file, max_bytes_per_sec=10_000, write_size=0) as writer:
+            for row in rows:
+                await writer.write_row(row)
+        elapsed = time.monotonic() - started
+        assert 0.45 <= elapsed < 2
+        with open(test_file, "rb") as f:
+            assert f.read() == b"".join(b"x" * 98 + b"\r\n" for _ in rows)
+
+        # A large buffered chunk is cut into slices rather than sent in one burst
+        started = time.monotonic()
+        async with Writer(test_file, max_bytes_per_sec=20_000) as writer:
+            await writer.writerows(rows)
+        assert 0.2 <= time.monotonic() - started < 2
+    finally:
+        os.unlink(test_file)
+
+
+def test_invalid_rate():
+    """Test that the rate must be a positive number."""
+    for rate in (0, -1, float("inf")):
+        with pytest.raises(ValueError, match="max_bytes_per_sec"):
+            Writer("unused.csv", max_bytes_per_sec=rate)
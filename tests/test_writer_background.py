@@ -0,0 +1,99 @@
+"""Test Writer(queue_size=...), which writes through a background task."""
+
+import asyncio
+import gc
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+
+def _temp_path():
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        return f.name
+
+
+@pytest.mark.asyncio
+async def test_background_writer_writes_all_rows():
+    """Test that queued rows are all written, in order, by close()."""
+    test_file = _temp_path()
+    try:
+        writer = Writer(test_file, queue_size=4, write_size=64)
+        for i in range(1000):
+            await writer.write_row([str(i), f"value {i}"])
+        await writer.writerows([["x", "y"]])
+        await writer.close()
+
+        rows = await Reader(test_file).read_all()
+        assert rows[:2] == [["0", "value 0"], ["1", "value 1"]]
+        assert rows[-1] == ["x", "y"]
+        assert len(rows) == 1001
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_background_writer_flush_and_context_manager():
+    """Test that flush() waits for the queue to drain."""
+    test_file = _temp_path()
+    try:
+        async with Writer(test_file, queue_size=2) as writer:
+            await writer.write_row(["a", "b"])
+            await writer.flush()
+            with open(test_file, newline="") as f:
+                assert f.read() == "a,b\r\n"
+            await writer.write_row(["c", "d"])
+        with open(test_file, newline="") as f:
+            assert f.read() == "a,b\r\nc,d\r\n"
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_background_writer_reports_errors():
+    """Test that a failed background write surfaces on flush() or close()."""
+    missing = os.path.join(tempfile.gettempdir(), "rapcsv-missing-dir", "out.csv")
+    writer = Writer(missing, queue_size=2, write_size=0)
+    await writer.write_row(["a"])
+    with pytest.raises(IOError):
+        await writer.flush()
+    with pytest.raises(IOError):
+        await writer.close()
+    with pytest.raises(ValueError, match="queue_size"):
+        Writer(missing, queue_size=0)
+
+
+@pytest.mark.asyncio
+async def test_background_writer_concurrent_producers():
+    """Test that many concurrent producers can share one queued writer."""
+    test_file = _temp_path()
+    try:
+        writer = Writer(test_file, queue_size=1000)
+        await asyncio.gather(*(writer.write_row([str(i)]) for i in range(100)))
+        await writer.close()
+        assert len(await Reader(test_file).read_all()) == 100
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_dropped_background_writer_flushes():
+    """Test that queued and buffered rows of a writer never closed still reach the file."""
+    test_file = _temp_path()
+    try:
+        writer = Writer(test_file, queue_size=8, write_size=1 << 20)
+        for i in range(5):
+            await writer.write_row([str(i)])
+        with pytest.warns(ResourceWarning, match="not closed"):
+            del writer
+            gc.collect()
+        for _ in range(100):
+            if os.path.getsize(test_file):
+                break
+            await asyncio.sleep(0.02)
+        with open(test_file, newline="") as f:
+            assert f.read() == "0\r\n1\r\n2\r\n3\r\n4\r\n"
+    finally:
+        os.unlink(test_file)
@@ -0,0 +1,39 @@
+"""Test Writer(headers=...), which writes a header only to new or empty files."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Writer
+
+
+@pytest.mark.asyncio
+async def test_header_written_once_across_appends():
+    """Test that a second writer appending to the file doesn't repeat the header."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "out.csv")
+        for row in (["1", "a"], ["2", "b"]):
+            async with Writer(path, headers=["id", "name"], lineterminator="\n") as writer:
+                await writer.write_row(row)
+        with open(path) as f:
+            assert f.read() == "id,name\n1,a\n2,b\n"
+
+
+@pytest.mark.asyncio
+async def test_header_for_empty_file_without_rows():
+    """Test that an existing empty file gets the header even if no rows are written."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        path = f.name
+    try:
+        writer = Writer(path, headers=["id"], lineterminator="\n", queue_size=4)
+        await writer.close()
+        with open(path) as f:
+            assert f.read() == "id\n"
+
+        async with Writer(path, headers=["id"], lineterminator="\n") as writer:
+            await writer.writerows([["1"], ["2"]])
+        with open(path) as f:
+            assert f.read() == "id\n1\n2\n"
+    finally:
+        os.unlink(path)
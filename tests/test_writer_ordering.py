@@ -0,0 +1,38 @@
+"""Test that concurrent Writer calls are applied in submission order."""
+
+import asyncio
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import Reader, Writer
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("queue_size", [None, 8])
+async def test_concurrent_writes_keep_submission_order(queue_size):
+    """Test that rows from concurrent write_row()/writerows() calls are not reordered."""
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        test_file = f.name
+    try:
+        writer = Writer(test_file, queue_size=queue_size, write_size=0)
+        calls = []
+        expected = []
+        for i in range(500):
+            if i % 10 == 0:
+                rows = [[str(i), "batch", str(n)] for n in range(3)]
+                calls.append(writer.writerows(rows))
+                expected.extend(rows)
+            else:
+                row = [str(i), "x" * (i % 50)]
+                calls.append(writer.write_row(row))
+                expected.append(row)
+        # Start every call before awaiting any of them
+        await asyncio.gather(*calls)
+        await writer.close()
+
+        assert await Reader(test_file).read_all() == expected
+    finally:
+        os.unlink(test_file)
+
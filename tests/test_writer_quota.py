@@ -0,0 +1,48 @@
+"""Test Writer(max_rows=..., max_bytes=...) and QuotaExceeded."""
+
+import os
+import tempfile
+
+import pytest
+
+from rapcsv import QuotaExceeded, RapcsvError, Reader, Writer
+
+
+def _path():
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".csv") as f:
+        return f.name
+
+
+@pytest.mark.asyncio
+async def test_max_rows():
+    """Test that a call crossing max_rows writes none of its rows."""
+    test_file = _path()
+    try:
+        async with Writer(test_file, headers=["n"], max_rows=3) as writer:
+            await writer.writerows([[1], [2]])
+            with pytest.raises(QuotaExceeded, match="max_rows=3"):
+                await writer.writerows([[3], [4]])
+            await writer.write_row([3])
+            with pytest.raises(QuotaExceeded):
+                await writer.write_row([4])
+        assert await Reader(test_file).read_all() == [["n"], ["1"], ["2"], ["3"]]
+        assert issubclass(QuotaExceeded, RapcsvError)
+    finally:
+        os.unlink(test_file)
+
+
+@pytest.mark.asyncio
+async def test_max_bytes_counts_header():
+    """Test that max_bytes includes the header, which waits for rows that fit."""
+    test_file = _path()
+    try:
+        async with Writer(test_file, headers=["id"], max_bytes=12, lineterminator="\n") as writer:
+            with pytest.raises(QuotaExceeded, match="max_bytes=12"):
+                await writer.write_row(["x" * 20])
+            await writer.writerows([["aaaa"], ["bb"]])  # 3 + 5 + 3 bytes
+            with pytest.raises(QuotaExceeded):
+                await writer.write_row(["c"])
+        with open(test_file, "rb") as f:
+            assert f.read() == b"id\naaaa\nbb\n"
+    finally:
+        os.unlink(test_file)
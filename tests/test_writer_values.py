@@ -0,0 +1,94 @@
+"""Test writing non-string values with Writer, serialized in Rust."""
+
+import os
+import tempfile
+from decimal import Decimal
+
+import pytest
+
+from rapcsv import Writer
+
+
+async def _write(rows, **kwargs):
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "out.csv")
+        async with Writer(path, lineterminator="\n", **kwargs) as writer:
+            await writer.writerows(rows)
+        with open(path) as f:
+            return f.read()
+
+
+@pytest.mark.asyncio
+async def test_default_serialization():
+    """Test that scalars are written as str() would, except None which is empty."""
+    rows = [
+        ["a", 1, -20, 10**30, 1.5],
+        [0.1, 1e16, 1e-5, float("nan"), float("-inf")],
+        (x for x in (True, False, None, Decimal("1.10"), b"raw")),
+    ]
+    assert await _write(rows) == (
+        "a,1,-20,1000000000000000000000000000000,1.5\n"
+        "0.1,1e+16,1e-05,nan,-inf\n"
+        "True,False,,1.10,raw\n"
+    )
+
+
+@pytest.mark.asyncio
+async def test_ragged_rows():
+    """Test that one writerows() call takes rows of different lengths, as csv.writer does."""
+    assert await _write([["a", "b"], [1], [2, 3, 4]]) == "a,b\n1\n2,3,4\n"
+
+
+@pytest.mark.asyncio
+async def test_configured_serialization():
+    """Test float_precision, true_value, false_value and none_value."""
+    rows = [[3.14159, 2, True, False, None]]
+    data = await _write(
+        rows, float_precision=2, true_value="yes", false_value="no", none_value="NULL"
+    )
+    assert data == "3.14,2,yes,no,NULL\n"
+
+
+@pytest.mark.asyncio
+async def test_string_row_rejected():
+    """Test that a bare string is not taken as a row of characters."""
+    with tempfile.TemporaryDirectory() as tmp:
+        writer = Writer(os.path.join(tmp, "out.csv"))
+        with pytest.raises(TypeError, match="not str"):
+            await writer.write_row("abc")
+        await writer.close()
+
+
+@pytest.mark.asyncio
+async def test_float_format_matches_printf():
+    """Test that float_format renders like Python's % operator."""
+    values = [3.14159, -0.000012345, 123456789.0, 0.5, 100.0, float("nan"), float("-inf")]
+    for fmt in ("%.4f", "%e", "%.2E", "%.3g", "%g", "%+08.2f", "%.1f%%"):
+        data = await _write([values], float_format=fmt)
+        assert data == ",".join(fmt % v for v in values) + "\n", fmt
+
+
+@pytest.mark.asyncio
+async def test_date_formats_match_strftime():
+    """Test that date_format and datetime_format render like strftime()."""
+    from datetime import date, datetime, timedelta, timezone
+
+    when = datetime(2024, 2, 29, 15, 4, 5, 123, tzinfo=timezone(timedelta(hours=-5, minutes=-30)))
+    day = date(2023, 12, 31)
+    dt_fmt = "%Y-%m-%dT%H:%M:%S.%f%z %a %b %I%p %j %y"
+    d_fmt = "%d/%m/%Y %A %B %j %F"
+    data = await _write([[when, day]], datetime_format=dt_fmt, date_format=d_fmt)
+    assert data == f"{when.strftime(dt_fmt)},{day.strftime(d_fmt)}\n"
+
+    # Without a pattern, dates are written as str()
+    assert await _write([[day]]) == "2023-12-31\n"
+
+
+def test_invalid_formats():
+    """Test that bad patterns are rejected when the writer is created."""
+    with pytest.raises(ValueError, match="float_format"):
+        Writer("out.csv", float_format="%d")
+    with pytest.raises(ValueError, match="cannot both be set"):
+        Writer("out.csv", float_format="%.2f", float_precision=2)
+    with pytest.raises(ValueError, match="unsupported directive '%Q'"):
+        Writer("out.csv", date_format="%Q")
@@ -0,0 +1,68 @@
+"""Test reading CSV members of ZIP archives."""
+
+import os
+import tempfile
+import zipfile
+
+import pytest
+
+from rapcsv import Reader
+
+CSV = "id,city\r\n1,Zürich\r\n2,Oslo\r\n"
+
+
+def _write_zip(members, compression=zipfile.ZIP_DEFLATED):
+    with tempfile.NamedTemporaryFile(delete=False, suffix=".zip") as f:
+        path = f.name
+    with zipfile.ZipFile(path, "w", compression=compression) as archive:
+        for name, content in members.items():
+            archive.writestr(name, content)
+    return path
+
+
+@pytest.mark.asyncio
+async def test_read_zip_member():
+    """Test naming the member in the path, with zip_member, or not at all."""
+    expected = [["id", "city"], ["1", "Zürich"], ["2", "Oslo"]]
+    bundle = _write_zip({"data/people.csv": CSV, "README.txt": "hello"})
+    single = _write_zip({"only.csv": CSV * 200})
+    try:
+        reader = Reader(f"{bundle}::data/people.csv", read_size=5)
+        assert [row async for row in reader] == expected
+        assert reader.line_num == 3
+        assert reader.byte_offset is None
+
+        reader = Reader(bundle, zip_member="data/people.csv")
+        assert await reader.read_all() == expected
+
+        rows = await Reader(single).read_all()
+        assert len(rows) == 600 and rows[-1] == ["2", "Oslo"]
+
+        with pytest.raises(ValueError, match="holds 2 files"):
+            Reader(bundle)
+        with pytest.raises(IOError, match="No member 'missing.csv'"):
+            Reader(bundle, zip_member="missing.csv")
+        with pytest.raises(ValueError, match="not both"):
+            Reader(f"{bundle}::data/people.csv", zip_member="README.txt")
+        with pytest.raises(ValueError, match="archive member"):
+            Reader(single, mmap=True)
+        with pytest.raises(ValueError, match="archive member"):
+            Reader(single).state()
+    finally:
+        os.unlink(bundle)
+        os.unlink(single)
+
+
+@pytest.mark.asyncio
+async def test_corrupt_member_fails_the_read():
+    """Test that a checksum mismatch raises instead of ending the stream quietly."""
+    path = _write_zip({"data.csv": CSV}, compression=zipfile.ZIP_STORED)
+    try:
+        with open(path, "r+b") as f:
+            content = f.read()
+            f.seek(content.index(b"Oslo"))
+            f.write(b"Olso")
+        with pytest.raises(IOError, match="data.csv"):
+            await Reader(path).read_all()
+    finally:
+        os.unlink(path)
@@ -0,0 +1,47 @@
+"""Test writing CSV members into ZIP archives."""
+
+import os
+import tempfile
+import zipfile
+
+import pytest
+
+from rapcsv import AsyncDictWriter, Reader, Writer
+
+
+@pytest.mark.asyncio
+async def test_write_zip_members():
+    """Test writing deflated members, appending to an archive and reading them back."""
+    with tempfile.TemporaryDirectory() as tmp:
+        bundle = os.path.join(tmp, "bundle.zip")
+        rows = [["id", "city"]] + [[str(i), "Zürich"] for i in range(5000)]
+
+        async with Writer(f"{bundle}::people.csv", write_size=100) as writer:
+            await writer.writerows(rows)
+        writer = Writer(bundle, zip_member="totals.csv", queue_size=4)
+        await writer.write_row(["total", 5000])
+        await writer.close()
+
+        with zipfile.ZipFile(bundle) as archive:
+            assert archive.testzip() is None
+            assert archive.namelist() == ["people.csv", "totals.csv"]
+            info = archive.getinfo("people.csv")
+            assert info.compress_type == zipfile.ZIP_DEFLATED
+            assert info.compress_size < info.file_size
+            assert archive.read("totals.csv") == b"total,5000\r\n"
+        assert await Reader(f"{bundle}::people.csv").read_all() == rows
+
+        with pytest.raises(ValueError, match="people.csv"):
+            Writer(bundle, zip_member="people.csv")
+
+        # A bare .zip path gets a member named after it
+        export = os.path.join(tmp, "export.zip")
+        async with Writer(export, headers=["a", "b"]) as writer:
+            await writer.write_row([1, 2])
+        with zipfile.ZipFile(export) as archive:
+            assert archive.read("export.csv") == b"a,b\r\n1,2\r\n"
+
+        with pytest.raises(ValueError, match="archive member"):
+            Writer(os.path.join(tmp, "locked.zip"), lock=True)
+        with pytest.raises(ValueError, match="ZIP archive"):
+            AsyncDictWriter(os.path.join(tmp, "dicts.zip"), fieldnames=["a"])